@@ -241,10 +241,19 @@ pub fn inspect(mode: &str) -> Result<()> {
     println!("{}", "🔍 Launching widget inspector...".cyan().bold());
     println!();
     println!("  {} {}", "Mode:".green(), mode);
+    if mode == "remote" {
+        println!(
+            "  {} run 'revue inspect --attach' in another terminal",
+            "Attach:".green()
+        );
+    }
     println!();
 
     // Set environment variable to enable inspector
     std::env::set_var("REVUE_INSPECTOR", mode);
+    if mode == "remote" {
+        std::env::set_var("REVUE_DEVTOOLS", "auto");
+    }
 
     let status = Command::new("cargo")
         .args(["run"])
@@ -257,6 +266,11 @@ pub fn inspect(mode: &str) -> Result<()> {
     Ok(())
 }
 
+/// Attach the inspector to a running app's devtools server
+pub fn inspect_attach(addr: Option<&str>, token: Option<&str>) -> Result<()> {
+    crate::inspect::attach(addr, token)
+}
+
 /// List available themes
 pub fn list_themes(verbose: bool) -> Result<()> {
     println!("{}", "🎨 Available Themes".cyan().bold());
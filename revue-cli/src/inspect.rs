@@ -0,0 +1,491 @@
+//! Client for the remote devtools server of a running Revue app
+//!
+//! Apps serve devtools with `REVUE_DEVTOOLS=1` (or
+//! `App::builder().devtools_server(..)`) on a Unix socket or loopback TCP
+//! port, speaking one JSON object per line. TCP connections must start
+//! with the token the app printed at startup.
+
+use colored::Colorize;
+use serde_json::{json, Value};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+
+use crate::commands::Result;
+
+/// File name prefix of the sockets apps create in the temp directory
+pub const SOCKET_PREFIX: &str = "revue-devtools-";
+
+/// Environment variable holding the token for TCP servers
+pub const TOKEN_ENV_VAR: &str = "REVUE_DEVTOOLS_TOKEN";
+
+/// A connection to a devtools server
+pub struct Connection {
+    reader: BufReader<Box<dyn Read>>,
+    writer: Box<dyn Write>,
+}
+
+impl Connection {
+    /// Connect to `unix:<path>`, `tcp:<host:port>`, `host:port` or a socket path
+    pub fn connect(addr: &str) -> Result<Self> {
+        let tcp = addr.strip_prefix("tcp:").unwrap_or(addr);
+        if !addr.starts_with("unix:") && tcp.parse::<std::net::SocketAddr>().is_ok() {
+            let stream = TcpStream::connect(tcp)?;
+            let reader: Box<dyn Read> = Box::new(stream.try_clone()?);
+            return Ok(Self {
+                reader: BufReader::new(reader),
+                writer: Box::new(stream),
+            });
+        }
+
+        #[cfg(unix)]
+        {
+            let path = addr.strip_prefix("unix:").unwrap_or(addr);
+            let stream = UnixStream::connect(path)?;
+            let reader: Box<dyn Read> = Box::new(stream.try_clone()?);
+            Ok(Self {
+                reader: BufReader::new(reader),
+                writer: Box::new(stream),
+            })
+        }
+        #[cfg(not(unix))]
+        Err(format!("Invalid devtools address: {}", addr).into())
+    }
+
+    /// Present the token of a TCP server
+    pub fn authenticate(&mut self, token: &str) -> Result<()> {
+        self.request(json!({"type": "auth", "token": token}))?;
+        Ok(())
+    }
+
+    /// Send a request without waiting for the answer
+    pub fn send(&mut self, request: &Value) -> Result<()> {
+        let mut line = serde_json::to_vec(request)?;
+        line.push(b'\n');
+        self.writer.write_all(&line)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Read the next message
+    pub fn recv(&mut self) -> Result<Value> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err("Connection closed by app".into());
+        }
+        Ok(serde_json::from_str(&line)?)
+    }
+
+    /// Send a request and wait for its answer, skipping pushed messages
+    pub fn request(&mut self, request: Value) -> Result<Value> {
+        self.send(&request)?;
+        loop {
+            let message = self.recv()?;
+            if !is_push(&message) {
+                if message["type"] == "error" {
+                    let text = message["message"].as_str().unwrap_or("unknown error");
+                    return Err(text.to_string().into());
+                }
+                return Ok(message);
+            }
+        }
+    }
+}
+
+/// Whether a message was pushed for a subscription rather than answering a request
+fn is_push(message: &Value) -> bool {
+    matches!(message["type"].as_str(), Some("event") | Some("frame"))
+}
+
+/// Find the most recently started app that still accepts connections
+pub fn discover_socket(dir: &Path) -> Option<PathBuf> {
+    let mut sockets: Vec<(std::time::SystemTime, PathBuf)> = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with(SOCKET_PREFIX) && name.ends_with(".sock")
+        })
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, entry.path()))
+        })
+        .collect();
+    sockets.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+
+    sockets
+        .into_iter()
+        .map(|(_, path)| path)
+        .find(|path| Connection::connect(&path.to_string_lossy()).is_ok())
+}
+
+/// A parsed REPL command
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplCommand {
+    /// Print the DOM tree
+    Tree,
+    /// Print the rules and computed declarations of a node
+    Styles(u64),
+    /// Print watched signals
+    Signals,
+    /// Print recent events
+    Events,
+    /// Print recent frame timings
+    Frames,
+    /// Stream events, frames and signal changes
+    Watch,
+    /// Set a declaration: `set <selector> { <property>: <value> }`
+    Set {
+        /// Rule selector
+        selector: String,
+        /// CSS property
+        property: String,
+        /// New value
+        value: String,
+    },
+    /// Remove a declaration: `unset <selector> { <property> }`
+    Unset {
+        /// Rule selector
+        selector: String,
+        /// CSS property
+        property: String,
+    },
+    /// Print the command list
+    Help,
+    /// Leave the REPL
+    Quit,
+}
+
+impl ReplCommand {
+    /// Parse one input line
+    pub fn parse(line: &str) -> std::result::Result<Self, String> {
+        let line = line.trim();
+        let (word, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+        match word {
+            "tree" | "t" => Ok(ReplCommand::Tree),
+            "styles" | "s" => rest
+                .parse()
+                .map(ReplCommand::Styles)
+                .map_err(|_| "usage: styles <node id>".to_string()),
+            "signals" => Ok(ReplCommand::Signals),
+            "events" => Ok(ReplCommand::Events),
+            "frames" => Ok(ReplCommand::Frames),
+            "watch" | "w" => Ok(ReplCommand::Watch),
+            "set" => {
+                let (selector, body) = split_block(rest)
+                    .ok_or("usage: set <selector> { <property>: <value> }")?;
+                let (property, value) = body
+                    .split_once(':')
+                    .ok_or("usage: set <selector> { <property>: <value> }")?;
+                Ok(ReplCommand::Set {
+                    selector,
+                    property: property.trim().to_string(),
+                    value: value.trim().trim_end_matches(';').trim().to_string(),
+                })
+            }
+            "unset" => {
+                let (selector, property) =
+                    split_block(rest).ok_or("usage: unset <selector> { <property> }")?;
+                Ok(ReplCommand::Unset {
+                    selector,
+                    property: property.trim_end_matches(';').trim().to_string(),
+                })
+            }
+            "help" | "?" => Ok(ReplCommand::Help),
+            "quit" | "exit" | "q" => Ok(ReplCommand::Quit),
+            other => Err(format!("unknown command '{}' (try 'help')", other)),
+        }
+    }
+}
+
+/// Split `selector { body }` into a non-empty selector and a non-empty body
+fn split_block(input: &str) -> Option<(String, String)> {
+    let (selector, body) = input.split_once('{')?;
+    let body = body.trim().strip_suffix('}')?.trim();
+    let selector = selector.trim();
+    if selector.is_empty() || body.is_empty() {
+        return None;
+    }
+    Some((selector.to_string(), body.to_string()))
+}
+
+/// Render a `tree` message as an indented outline
+pub fn format_tree(message: &Value) -> String {
+    let mut out = String::new();
+    match message.get("root").filter(|root| !root.is_null()) {
+        Some(root) => format_node(root, 0, &mut out),
+        None => out.push_str("(no DOM yet)\n"),
+    }
+    out
+}
+
+fn format_node(node: &Value, depth: usize, out: &mut String) {
+    let mut label = node["widget"].as_str().unwrap_or("?").to_string();
+    if let Some(id) = node["element_id"].as_str() {
+        label.push('#');
+        label.push_str(id);
+    }
+    for class in node["classes"].as_array().into_iter().flatten() {
+        label.push('.');
+        label.push_str(class.as_str().unwrap_or_default());
+    }
+    for state in node["states"].as_array().into_iter().flatten() {
+        label.push(':');
+        label.push_str(state.as_str().unwrap_or_default());
+    }
+
+    let rect = match node["rect"].as_array().map(|r| r.as_slice()) {
+        Some([x, y, w, h]) => format!(" {},{} {}x{}", x, y, w, h),
+        _ => String::new(),
+    };
+    out.push_str(&format!(
+        "{}[{}] {}{}\n",
+        "  ".repeat(depth),
+        node["id"],
+        label,
+        rect
+    ));
+    for child in node["children"].as_array().into_iter().flatten() {
+        format_node(child, depth + 1, out);
+    }
+}
+
+/// Render a `node` message: matched rules, then the computed result
+pub fn format_styles(message: &Value) -> String {
+    let mut out = String::new();
+    let declarations = |list: &Value, out: &mut String| {
+        for decl in list.as_array().into_iter().flatten() {
+            out.push_str(&format!(
+                "  {}: {};\n",
+                decl["property"].as_str().unwrap_or_default(),
+                decl["value"].as_str().unwrap_or_default()
+            ));
+        }
+    };
+
+    let rules = message["rules"].as_array().cloned().unwrap_or_default();
+    if rules.is_empty() {
+        out.push_str("/* no matching rules */\n");
+    }
+    for rule in &rules {
+        let specificity: Vec<String> = rule["specificity"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|n| n.to_string())
+            .collect();
+        out.push_str(&format!(
+            "{} {{ /* specificity {} */\n",
+            rule["selector"].as_str().unwrap_or_default(),
+            specificity.join(",")
+        ));
+        declarations(&rule["declarations"], &mut out);
+        out.push_str("}\n");
+    }
+    out.push_str("/* computed */\n");
+    declarations(&message["computed"], &mut out);
+    out
+}
+
+/// Render a `frames` message as a timing summary
+pub fn format_frames(message: &Value) -> String {
+    let frames = message["frames"].as_array().cloned().unwrap_or_default();
+    if frames.is_empty() {
+        return "no frames yet\n".to_string();
+    }
+    let durations: Vec<u64> = frames
+        .iter()
+        .filter_map(|f| f["duration_us"].as_u64())
+        .collect();
+    let total: u64 = durations.iter().sum();
+    let max = durations.iter().copied().max().unwrap_or(0);
+    let mut out = format!(
+        "{} frames, avg {:.2}ms, max {:.2}ms\n",
+        frames.len(),
+        total as f64 / durations.len().max(1) as f64 / 1000.0,
+        max as f64 / 1000.0
+    );
+    for frame in frames.iter().rev().take(10).rev() {
+        out.push_str(&format_frame(frame));
+        out.push('\n');
+    }
+    out
+}
+
+fn format_frame(frame: &Value) -> String {
+    format!(
+        "#{} at {}ms: {:.2}ms, {} dirty",
+        frame["index"],
+        frame["time_ms"],
+        frame["duration_us"].as_u64().unwrap_or(0) as f64 / 1000.0,
+        frame["dirty_regions"]
+    )
+}
+
+fn format_event(event: &Value) -> String {
+    format!(
+        "{}ms {} {}",
+        event["time_ms"],
+        event["kind"].as_str().unwrap_or_default(),
+        event["detail"].as_str().unwrap_or_default()
+    )
+}
+
+fn print_signals(message: &Value) {
+    let signals = message["signals"].as_array().cloned().unwrap_or_default();
+    if signals.is_empty() {
+        println!("{}", "no watched signals".dimmed());
+    }
+    for signal in signals {
+        println!(
+            "  {} = {}",
+            signal["name"].as_str().unwrap_or_default().green(),
+            signal["value"].as_str().unwrap_or_default()
+        );
+    }
+}
+
+fn print_help() {
+    println!("  {}                         DOM tree with node ids", "tree".cyan());
+    println!("  {}                  matched rules and computed style", "styles <id>".cyan());
+    println!("  {}                      watched signal values", "signals".cyan());
+    println!("  {}                       recent input events", "events".cyan());
+    println!("  {}                       recent frame timings", "frames".cyan());
+    println!("  {}                        stream events, frames and signals", "watch".cyan());
+    println!("  {}  edit the live stylesheet", "set <selector> { prop: value }".cyan());
+    println!("  {}        remove a declaration", "unset <selector> { prop }".cyan());
+    println!("  {}                         leave", "quit".cyan());
+}
+
+/// Attach to a running app and start the inspector REPL
+///
+/// `token` falls back to `REVUE_DEVTOOLS_TOKEN`; Unix sockets need none.
+pub fn attach(addr: Option<&str>, token: Option<&str>) -> Result<()> {
+    let addr = match addr {
+        Some(addr) => addr.to_string(),
+        None => discover_socket(&std::env::temp_dir())
+            .map(|path| path.to_string_lossy().into_owned())
+            .ok_or("No running Revue app found. Start one with REVUE_DEVTOOLS=1 or pass --addr.")?,
+    };
+
+    let mut conn = Connection::connect(&addr)?;
+    let token = token
+        .map(str::to_string)
+        .or_else(|| std::env::var(TOKEN_ENV_VAR).ok());
+    if let Some(token) = token.filter(|token| !token.is_empty()) {
+        conn.authenticate(&token)?;
+    }
+    let hello = conn.request(json!({"type": "hello"})).map_err(|e| {
+        if e.to_string() == "authentication required" {
+            format!(
+                "{}: pass --token with the token the app printed, or set {}",
+                e, TOKEN_ENV_VAR
+            )
+            .into()
+        } else {
+            e
+        }
+    })?;
+    println!(
+        "{} {} (pid {}) at {}",
+        "🔍 Attached to".cyan().bold(),
+        hello["app"].as_str().unwrap_or("app").bold(),
+        hello["pid"],
+        addr.dimmed()
+    );
+    println!("{}", "Type 'help' for commands.".dimmed());
+    println!();
+
+    let stdin = io::stdin();
+    loop {
+        print!("{} ", "inspect>".cyan());
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            break;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let command = match ReplCommand::parse(&line) {
+            Ok(command) => command,
+            Err(e) => {
+                println!("{}", e.yellow());
+                continue;
+            }
+        };
+        if command == ReplCommand::Quit {
+            break;
+        }
+        if let Err(e) = run_command(&mut conn, command) {
+            println!("{} {}", "error:".red(), e);
+            if e.to_string() == "Connection closed by app" {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_command(conn: &mut Connection, command: ReplCommand) -> Result<()> {
+    match command {
+        ReplCommand::Tree => print!("{}", format_tree(&conn.request(json!({"type": "get_tree"}))?)),
+        ReplCommand::Styles(id) => print!(
+            "{}",
+            format_styles(&conn.request(json!({"type": "get_node", "id": id}))?)
+        ),
+        ReplCommand::Signals => print_signals(&conn.request(json!({"type": "get_signals"}))?),
+        ReplCommand::Events => {
+            let message = conn.request(json!({"type": "get_events"}))?;
+            for event in message["events"].as_array().into_iter().flatten() {
+                println!("  {}", format_event(event));
+            }
+        }
+        ReplCommand::Frames => print!("{}", format_frames(&conn.request(json!({"type": "get_frames"}))?)),
+        ReplCommand::Watch => watch(conn)?,
+        ReplCommand::Set {
+            selector,
+            property,
+            value,
+        } => {
+            conn.request(json!({
+                "type": "set_style",
+                "selector": selector,
+                "property": property,
+                "value": value,
+            }))?;
+            println!("{} {} {{ {}: {} }}", "✓".green(), selector, property, value);
+        }
+        ReplCommand::Unset { selector, property } => {
+            conn.request(json!({
+                "type": "set_style",
+                "selector": selector,
+                "property": property,
+            }))?;
+            println!("{} removed {} from {}", "✓".green(), property, selector);
+        }
+        ReplCommand::Help => print_help(),
+        ReplCommand::Quit => {}
+    }
+    Ok(())
+}
+
+/// Stream pushed messages until the app exits (Ctrl+C to stop)
+fn watch(conn: &mut Connection) -> Result<()> {
+    conn.request(json!({"type": "subscribe", "topics": ["events", "frames", "signals"]}))?;
+    println!("{}", "Watching... press Ctrl+C to stop".dimmed());
+    loop {
+        let message = conn.recv()?;
+        match message["type"].as_str() {
+            Some("event") => println!("{} {}", "event".blue(), format_event(&message["event"])),
+            Some("frame") => println!("{} {}", "frame".magenta(), format_frame(&message["frame"])),
+            Some("signals") => print_signals(&message),
+            _ => {}
+        }
+    }
+}
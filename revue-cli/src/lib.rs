@@ -1,4 +1,5 @@
 //! Revue CLI - Development tools for Revue TUI framework
 
 pub mod commands;
+pub mod inspect;
 pub mod templates;
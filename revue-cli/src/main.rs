@@ -66,9 +66,21 @@ enum Commands {
 
     /// Launch widget inspector
     Inspect {
-        /// Inspector mode
+        /// Inspector mode (use "remote" to start the app with a devtools server)
         #[arg(short, long, default_value = "overlay")]
         mode: String,
+
+        /// Attach to a running app instead of launching one
+        #[arg(long)]
+        attach: bool,
+
+        /// Devtools address to attach to (unix:<path> or host:port)
+        #[arg(long)]
+        addr: Option<String>,
+
+        /// Token printed by the app for TCP addresses (default: $REVUE_DEVTOOLS_TOKEN)
+        #[arg(long)]
+        token: Option<String>,
     },
 
     /// List available themes
@@ -190,8 +202,17 @@ fn main() {
         Commands::Snapshot { update, filter } => {
             commands::run_snapshots(update, filter.as_deref())
         }
-        Commands::Inspect { mode } => {
-            commands::inspect(&mode)
+        Commands::Inspect {
+            mode,
+            attach,
+            addr,
+            token,
+        } => {
+            if attach || addr.is_some() {
+                commands::inspect_attach(addr.as_deref(), token.as_deref())
+            } else {
+                commands::inspect(&mode)
+            }
         }
         Commands::Themes { verbose } => {
             commands::list_themes(verbose)
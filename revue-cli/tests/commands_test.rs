@@ -106,3 +106,134 @@ fn add_component_unknown_type_returns_error() {
     let result = revue_cli::commands::add_component("nonexistent", None);
     assert!(result.is_err());
 }
+
+// =============================================================================
+// inspect --attach client (fake devtools server on a temp socket)
+// =============================================================================
+
+#[test]
+fn inspect_repl_parses_commands() {
+    use revue_cli::inspect::ReplCommand;
+
+    assert_eq!(ReplCommand::parse("tree").unwrap(), ReplCommand::Tree);
+    assert_eq!(ReplCommand::parse("styles 12").unwrap(), ReplCommand::Styles(12));
+    assert!(ReplCommand::parse("styles x").is_err());
+    assert_eq!(
+        ReplCommand::parse("set .list .item { color: #ff0000; }").unwrap(),
+        ReplCommand::Set {
+            selector: ".list .item".into(),
+            property: "color".into(),
+            value: "#ff0000".into(),
+        }
+    );
+    assert_eq!(
+        ReplCommand::parse("unset .btn { padding }").unwrap(),
+        ReplCommand::Unset {
+            selector: ".btn".into(),
+            property: "padding".into(),
+        }
+    );
+    assert!(ReplCommand::parse("set .btn color red").is_err());
+    assert!(ReplCommand::parse("bogus").is_err());
+}
+
+#[test]
+fn inspect_formats_tree_and_styles() {
+    use serde_json::json;
+
+    let tree = json!({
+        "type": "tree",
+        "root": {
+            "id": 1, "widget": "Stack", "rect": [0, 0, 80, 24],
+            "children": [{
+                "id": 2, "widget": "Button", "element_id": "ok",
+                "classes": ["btn", "primary"], "states": ["focus"], "rect": [2, 1, 10, 1]
+            }]
+        }
+    });
+    assert_eq!(
+        revue_cli::inspect::format_tree(&tree),
+        "[1] Stack 0,0 80x24\n  [2] Button#ok.btn.primary:focus 2,1 10x1\n"
+    );
+    assert_eq!(
+        revue_cli::inspect::format_tree(&json!({"type": "tree", "root": null})),
+        "(no DOM yet)\n"
+    );
+
+    let styles = json!({
+        "type": "node", "id": 2,
+        "rules": [{
+            "selector": ".btn", "specificity": [0, 1, 0],
+            "declarations": [{"property": "color", "value": "red"}]
+        }],
+        "computed": [{"property": "color", "value": "red"}]
+    });
+    assert_eq!(
+        revue_cli::inspect::format_styles(&styles),
+        ".btn { /* specificity 0,1,0 */\n  color: red;\n}\n/* computed */\n  color: red;\n"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn inspect_connection_round_trip_and_discovery() {
+    use serde_json::json;
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixListener;
+
+    let tmp = TempDir::new().unwrap();
+    let path = tmp.path().join("revue-devtools-4242.sock");
+    let listener = UnixListener::bind(&path).unwrap();
+    // A stale socket nobody listens on must be skipped
+    drop(UnixListener::bind(tmp.path().join("revue-devtools-1.sock")).unwrap());
+
+    let server = std::thread::spawn(move || {
+        // First connection is the discovery probe
+        drop(listener.accept().unwrap());
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut writer = stream;
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert!(line.contains("\"get_node\""));
+        writer
+            .write_all(b"{\"type\":\"event\",\"event\":{\"time_ms\":1,\"kind\":\"tick\",\"detail\":\"\"}}\n")
+            .unwrap();
+        writer.write_all(b"{\"type\":\"error\",\"message\":\"no node with id 9\"}\n").unwrap();
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        writer.write_all(b"{\"type\":\"ok\"}\n").unwrap();
+    });
+
+    let found = revue_cli::inspect::discover_socket(tmp.path()).unwrap();
+    assert_eq!(found, path);
+
+    let mut conn = revue_cli::inspect::Connection::connect(&format!("unix:{}", found.display())).unwrap();
+    let err = conn.request(json!({"type": "get_node", "id": 9})).unwrap_err();
+    assert_eq!(err.to_string(), "no node with id 9");
+    let ok = conn.request(json!({"type": "hello"})).unwrap();
+    assert_eq!(ok["type"], "ok");
+    server.join().unwrap();
+}
+
+#[test]
+fn inspect_connection_authenticates_over_tcp() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut writer = stream;
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line.trim(), r#"{"token":"secret","type":"auth"}"#);
+        writer.write_all(b"{\"type\":\"ok\"}\n").unwrap();
+    });
+
+    let mut conn = revue_cli::inspect::Connection::connect(&format!("tcp:{}", addr)).unwrap();
+    conn.authenticate("secret").unwrap();
+    server.join().unwrap();
+}
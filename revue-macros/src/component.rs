@@ -0,0 +1,505 @@
+//! `#[derive(Component)]` implementation
+//!
+//! Generates the `View`, `StyledView` and (optionally) `Interactive` impls for
+//! a widget struct, together with builder methods for annotated props.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Data, DeriveInput, Field, Fields, GenericArgument, Ident, LitStr, PathArguments,
+    Type,
+};
+
+/// Container-level `#[component(...)]` options
+struct ComponentAttrs {
+    /// CSS type name returned by `widget_type()`
+    name: Option<LitStr>,
+    /// Default CSS classes, stored by `default_props()` (or added to
+    /// `meta()` when there is no `WidgetProps` field)
+    classes: Vec<LitStr>,
+    /// Inherent method that renders the widget
+    render: Option<Ident>,
+}
+
+/// Field-level `#[prop(...)]` options
+struct PropAttrs {
+    /// Builder method name (defaults to the field name)
+    name: Option<Ident>,
+    /// Take the value as-is instead of `impl Into<T>`
+    no_into: bool,
+}
+
+/// A field with a generated builder method
+struct PropField<'a> {
+    field: &'a Field,
+    ident: &'a Ident,
+    attrs: PropAttrs,
+}
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(named) => &named.named,
+            _ => {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "#[derive(Component)] requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "#[derive(Component)] can only be used on structs",
+            ))
+        }
+    };
+
+    let attrs = parse_component_attrs(&input.attrs)?;
+
+    let mut props_field: Option<&Ident> = None;
+    let mut state_field: Option<&Ident> = None;
+    let mut interactive_field: Option<&Ident> = None;
+    let mut children_field: Option<&Ident> = None;
+    let mut prop_fields = Vec::new();
+
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+
+        if type_is(&field.ty, "WidgetProps") {
+            if props_field.is_some() {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "only one `WidgetProps` field is allowed",
+                ));
+            }
+            props_field = Some(ident);
+        } else if type_is(&field.ty, "WidgetState") {
+            if state_field.is_some() {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "only one `WidgetState` field is allowed",
+                ));
+            }
+            state_field = Some(ident);
+        }
+
+        for attr in &field.attrs {
+            if attr.path().is_ident("prop") {
+                prop_fields.push(PropField {
+                    field,
+                    ident,
+                    attrs: parse_prop_attrs(attr)?,
+                });
+            } else if attr.path().is_ident("interactive") {
+                attr.meta.require_path_only()?;
+                if interactive_field.is_some() {
+                    return Err(syn::Error::new(
+                        attr.span(),
+                        "only one field can be marked #[interactive]",
+                    ));
+                }
+                interactive_field = Some(ident);
+            } else if attr.path().is_ident("children") {
+                attr.meta.require_path_only()?;
+                if children_field.is_some() {
+                    return Err(syn::Error::new(
+                        attr.span(),
+                        "only one field can be marked #[children]",
+                    ));
+                }
+                children_field = Some(ident);
+            }
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let type_name = attrs
+        .name
+        .map(|lit| lit.value())
+        .unwrap_or_else(|| name.to_string());
+    let render = attrs
+        .render
+        .unwrap_or_else(|| format_ident!("render_content"));
+    let default_classes: Vec<String> = attrs.classes.iter().map(|lit| lit.value()).collect();
+
+    // View: id/classes/meta
+    let id_classes = props_field.map(|props| {
+        quote! {
+            fn id(&self) -> ::std::option::Option<&str> {
+                self.#props.id.as_deref()
+            }
+
+            fn classes(&self) -> &[::std::string::String] {
+                &self.#props.classes
+            }
+        }
+    });
+    let meta_props = props_field.map(|props| {
+        quote! {
+            if let ::std::option::Option::Some(ref id) = self.#props.id {
+                meta.id = ::std::option::Option::Some(id.clone());
+            }
+            for class in &self.#props.classes {
+                meta.classes.insert(class.clone());
+            }
+        }
+    });
+    // With a `WidgetProps` field the default classes live in its storage
+    // (see `default_props()`), so `classes()`, `has_class()` and CSS agree
+    let meta_classes = match meta_props {
+        Some(meta_props) => meta_props,
+        None => quote! {
+            #(meta.classes.insert(::std::string::String::from(#default_classes));)*
+        },
+    };
+    let children = children_field.map(|children| {
+        quote! {
+            fn children(&self) -> &[::std::boxed::Box<dyn ::revue::widget::View>] {
+                &self.#children
+            }
+        }
+    });
+
+    let view_impl = quote_spanned! {render.span()=>
+        impl #impl_generics ::revue::widget::View for #name #ty_generics #where_clause {
+            fn render(&self, ctx: &mut ::revue::widget::RenderContext) {
+                Self::#render(self, ctx)
+            }
+
+            fn widget_type(&self) -> &'static str {
+                #type_name
+            }
+
+            #id_classes
+            #children
+
+            fn meta(&self) -> ::revue::dom::WidgetMeta {
+                let mut meta = ::revue::dom::WidgetMeta::new(#type_name);
+                #meta_classes
+                meta
+            }
+        }
+    };
+
+    // StyledView and props builders
+    let styled_impl = props_field.map(|props| {
+        quote! {
+            impl #impl_generics ::revue::widget::StyledView for #name #ty_generics #where_clause {
+                fn set_id(&mut self, id: impl Into<::std::string::String>) {
+                    self.#props.id = ::std::option::Option::Some(id.into());
+                }
+
+                fn add_class(&mut self, class: impl Into<::std::string::String>) {
+                    let class_str = class.into();
+                    if !self.#props.classes.contains(&class_str) {
+                        self.#props.classes.push(class_str);
+                    }
+                }
+
+                fn remove_class(&mut self, class: &str) {
+                    self.#props.classes.retain(|c| c != class);
+                }
+
+                fn toggle_class(&mut self, class: &str) {
+                    if self.has_class(class) {
+                        self.remove_class(class);
+                    } else {
+                        self.add_class(class);
+                    }
+                }
+
+                fn has_class(&self, class: &str) -> bool {
+                    self.#props.classes.iter().any(|c| c == class)
+                }
+            }
+        }
+    });
+    let props_builders = props_field.map(|props| {
+        quote! {
+            /// Widget properties holding the `#[component(class = ...)]` classes
+            ///
+            /// Initialize the `WidgetProps` field with this in the constructor.
+            pub fn default_props() -> ::revue::widget::WidgetProps {
+                ::revue::widget::WidgetProps::new()
+                    #(.class(#default_classes))*
+            }
+
+            /// Set element ID for CSS selector (#id)
+            pub fn element_id(mut self, id: impl Into<::std::string::String>) -> Self {
+                self.#props.id = ::std::option::Option::Some(id.into());
+                self
+            }
+
+            /// Add a CSS class
+            pub fn class(mut self, class: impl Into<::std::string::String>) -> Self {
+                let class_str = class.into();
+                if !self.#props.classes.contains(&class_str) {
+                    self.#props.classes.push(class_str);
+                }
+                self
+            }
+
+            /// Add multiple CSS classes
+            pub fn classes<I, S>(mut self, classes: I) -> Self
+            where
+                I: IntoIterator<Item = S>,
+                S: Into<::std::string::String>,
+            {
+                for class in classes {
+                    let class_str = class.into();
+                    if !self.#props.classes.contains(&class_str) {
+                        self.#props.classes.push(class_str);
+                    }
+                }
+                self
+            }
+        }
+    });
+
+    let state_builders = state_field.map(|state| {
+        quote! {
+            /// Set focused state
+            pub fn focused(mut self, focused: bool) -> Self {
+                self.#state.focused = focused;
+                self
+            }
+
+            /// Set disabled state
+            pub fn disabled(mut self, disabled: bool) -> Self {
+                self.#state.disabled = disabled;
+                self
+            }
+
+            /// Set foreground color
+            pub fn fg(mut self, color: ::revue::style::Color) -> Self {
+                self.#state.fg = ::std::option::Option::Some(color);
+                self
+            }
+
+            /// Set background color
+            pub fn bg(mut self, color: ::revue::style::Color) -> Self {
+                self.#state.bg = ::std::option::Option::Some(color);
+                self
+            }
+
+            /// Check if widget is focused
+            pub fn is_focused(&self) -> bool {
+                self.#state.focused
+            }
+
+            /// Check if widget is disabled
+            pub fn is_disabled(&self) -> bool {
+                self.#state.disabled
+            }
+
+            /// Set focused state (mutable)
+            pub fn set_focused(&mut self, focused: bool) {
+                self.#state.focused = focused;
+            }
+        }
+    });
+
+    let prop_builders = prop_fields.iter().map(prop_builder);
+
+    let inherent_impl = quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#prop_builders)*
+            #props_builders
+            #state_builders
+        }
+    };
+
+    // Interactive forwarding
+    let interactive_impl = interactive_field.map(|inner| {
+        let (focusable, on_focus, on_blur) = match state_field {
+            Some(state) => (
+                quote! { !self.#state.disabled && ::revue::widget::Interactive::focusable(&self.#inner) },
+                quote! { self.#state.focused = true; },
+                quote! { self.#state.focused = false; },
+            ),
+            None => (
+                quote! { ::revue::widget::Interactive::focusable(&self.#inner) },
+                quote! {},
+                quote! {},
+            ),
+        };
+        let disabled_guard = state_field.map(|state| {
+            quote! {
+                if self.#state.disabled {
+                    return ::revue::widget::EventResult::Ignored;
+                }
+            }
+        });
+
+        quote! {
+            impl #impl_generics ::revue::widget::Interactive for #name #ty_generics #where_clause {
+                fn handle_key(
+                    &mut self,
+                    event: &::revue::event::KeyEvent,
+                ) -> ::revue::widget::EventResult {
+                    #disabled_guard
+                    ::revue::widget::Interactive::handle_key(&mut self.#inner, event)
+                }
+
+                fn handle_mouse(
+                    &mut self,
+                    event: &::revue::event::MouseEvent,
+                    area: ::revue::layout::Rect,
+                ) -> ::revue::widget::EventResult {
+                    #disabled_guard
+                    ::revue::widget::Interactive::handle_mouse(&mut self.#inner, event, area)
+                }
+
+                fn focusable(&self) -> bool {
+                    #focusable
+                }
+
+                fn on_focus(&mut self) {
+                    #on_focus
+                    ::revue::widget::Interactive::on_focus(&mut self.#inner);
+                }
+
+                fn on_blur(&mut self) {
+                    #on_blur
+                    ::revue::widget::Interactive::on_blur(&mut self.#inner);
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #view_impl
+        #styled_impl
+        #inherent_impl
+        #interactive_impl
+    })
+}
+
+/// Generate the builder method for a `#[prop]` field
+fn prop_builder(prop: &PropField<'_>) -> TokenStream {
+    let field = prop.ident;
+    let method = prop.attrs.name.as_ref().unwrap_or(field);
+    let docs: Vec<&Attribute> = prop
+        .field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .collect();
+    let doc = if docs.is_empty() {
+        let text = format!("Set `{}`", field);
+        quote! { #[doc = #text] }
+    } else {
+        quote! { #(#docs)* }
+    };
+
+    let ty = &prop.field.ty;
+    let (param_ty, value) = match (option_inner(ty), prop.attrs.no_into) {
+        (Some(inner), false) => (
+            quote! { impl Into<#inner> },
+            quote! { ::std::option::Option::Some(value.into()) },
+        ),
+        (Some(inner), true) => (
+            quote! { #inner },
+            quote! { ::std::option::Option::Some(value) },
+        ),
+        (None, false) => (quote! { impl Into<#ty> }, quote! { value.into() }),
+        (None, true) => (quote! { #ty }, quote! { value }),
+    };
+
+    quote_spanned! {prop.field.span()=>
+        #doc
+        pub fn #method(mut self, value: #param_ty) -> Self {
+            self.#field = #value;
+            self
+        }
+    }
+}
+
+fn parse_component_attrs(attrs: &[Attribute]) -> syn::Result<ComponentAttrs> {
+    let mut parsed = ComponentAttrs {
+        name: None,
+        classes: Vec::new(),
+        render: None,
+    };
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("component")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                parsed.name = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("class") {
+                parsed.classes.push(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("render") {
+                let lit: LitStr = meta.value()?.parse()?;
+                parsed.render = Some(lit.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unknown component attribute, expected `name`, `class` or `render`"))
+            }
+        })?;
+    }
+
+    Ok(parsed)
+}
+
+fn parse_prop_attrs(attr: &Attribute) -> syn::Result<PropAttrs> {
+    let mut parsed = PropAttrs {
+        name: None,
+        no_into: false,
+    };
+
+    // A bare `#[prop]` has no arguments
+    if matches!(attr.meta, syn::Meta::Path(_)) {
+        return Ok(parsed);
+    }
+
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("name") {
+            let lit: LitStr = meta.value()?.parse()?;
+            parsed.name = Some(lit.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("no_into") {
+            parsed.no_into = true;
+            Ok(())
+        } else {
+            Err(meta.error("unknown prop attribute, expected `name` or `no_into`"))
+        }
+    })?;
+
+    Ok(parsed)
+}
+
+/// Check whether the last path segment of `ty` is `name`
+fn type_is(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == name),
+        _ => false,
+    }
+}
+
+/// Extract `T` from `Option<T>`
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
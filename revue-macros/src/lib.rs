@@ -2,6 +2,9 @@
 //!
 //! This crate provides derive macros for Revue traits.
 
+mod component;
+mod view;
+
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, DeriveInput};
@@ -49,9 +52,48 @@ use syn::{parse_macro_input, DeriveInput};
 /// - `id()` method that returns a unique ID based on the type name
 /// - `name()` method that returns the struct name
 /// - `get_state()` and `get_getters()` methods (basic implementations)
-#[proc_macro_derive(Store)]
+///
+/// # Undo History
+///
+/// Add `#[store(history)]` to also implement `TrackHistory`, which registers
+/// every `Signal<T>` field with a `History` (labeled with the field name):
+///
+/// ```rust,ignore
+/// #[derive(Store)]
+/// #[store(history)]
+/// struct EditorStore {
+///     text: Signal<String>,
+/// }
+///
+/// let store = EditorStore { text: signal(String::new()) };
+/// let history = History::new();
+/// history.track_store(&store);
+/// ```
+///
+/// # Time-Travel Debugging
+///
+/// Add `#[store(time_travel)]` to implement `TrackTimeTravel`, which records
+/// every `Signal<T>` field with a `TimeTravelRecorder` (labeled
+/// `Store.field`). Field values must implement `TimeTravelValue`. Both
+/// options can be combined as `#[store(history, time_travel)]`.
+///
+/// ```rust,ignore
+/// #[derive(Store)]
+/// #[store(time_travel)]
+/// struct CounterStore {
+///     count: Signal<i32>,
+/// }
+///
+/// let recorder = TimeTravelRecorder::new();
+/// recorder.track_store(&store);
+/// ```
+#[proc_macro_derive(Store, attributes(store))]
 pub fn derive_store(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
+    let options = match store_options(&input) {
+        Ok(options) => options,
+        Err(err) => return err.to_compile_error().into(),
+    };
     let struct_name = &input.ident;
     let struct_name_string = struct_name.to_string();
 
@@ -85,5 +127,227 @@ pub fn derive_store(input: TokenStream) -> TokenStream {
         }
     };
 
-    TokenStream::from(expanded)
+    if !options.history && !options.time_travel {
+        return TokenStream::from(expanded);
+    }
+
+    let signal_fields: Vec<_> = match &input.data {
+        syn::Data::Struct(data) => data
+            .fields
+            .iter()
+            .filter(|field| is_signal_type(&field.ty))
+            .filter_map(|field| field.ident.as_ref())
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let history_impl = options.history.then(|| {
+        let labels = signal_fields.iter().map(|ident| ident.to_string());
+        quote! {
+            impl ::revue::reactive::TrackHistory for #struct_name {
+                fn track_history(&self, history: &::revue::reactive::History) {
+                    #( history.track(&self.#signal_fields, #labels); )*
+                }
+            }
+        }
+    });
+
+    let time_travel_impl = options.time_travel.then(|| {
+        let labels = signal_fields
+            .iter()
+            .map(|ident| format!("{}.{}", struct_name_string, ident));
+        quote! {
+            impl ::revue::devtools::TrackTimeTravel for #struct_name {
+                fn track_time_travel(&self, recorder: &::revue::devtools::TimeTravelRecorder) {
+                    #( recorder.track(&self.#signal_fields, #labels); )*
+                }
+            }
+        }
+    });
+
+    TokenStream::from(quote! {
+        #expanded
+        #history_impl
+        #time_travel_impl
+    })
+}
+
+/// Options set with `#[store(...)]` on a `Store` derive input
+#[derive(Default)]
+struct StoreOptions {
+    history: bool,
+    time_travel: bool,
+}
+
+/// Parse `#[store(history, time_travel)]` on a `Store` derive input
+fn store_options(input: &DeriveInput) -> syn::Result<StoreOptions> {
+    let mut options = StoreOptions::default();
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("store")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("history") {
+                options.history = true;
+                Ok(())
+            } else if meta.path.is_ident("time_travel") {
+                options.time_travel = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown store attribute, expected `history` or `time_travel`"))
+            }
+        })?;
+    }
+    Ok(options)
+}
+
+/// Check if a field type is `Signal<..>` (by last path segment)
+fn is_signal_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Signal"),
+        _ => false,
+    }
+}
+
+/// Derive macro for widget boilerplate
+///
+/// Generates the `View` and `StyledView` impls, builder methods and
+/// `Interactive` forwarding for a widget struct, replacing hand-written
+/// `impl_styled_view!`/`impl_props_builders!` invocations.
+///
+/// # Container Attributes
+///
+/// - `#[component(name = "Counter")]` - CSS type name returned by `widget_type()`
+///   (defaults to the struct name)
+/// - `#[component(class = "counter")]` - default CSS class; may be repeated.
+///   With a `WidgetProps` field, initialize it with the generated
+///   `Self::default_props()` so the classes show up in `classes()`,
+///   `has_class()` and CSS matching alike
+/// - `#[component(render = "draw")]` - inherent method with signature
+///   `fn(&self, &mut RenderContext)` used as `View::render` (defaults to `render_content`)
+///
+/// # Field Attributes
+///
+/// - `#[prop]` - generate a builder method taking `impl Into<T>`; for `Option<T>`
+///   fields the builder takes `impl Into<T>` and wraps it in `Some`
+/// - `#[prop(name = "with_label")]` - rename the builder method
+/// - `#[prop(no_into)]` - take the value as-is instead of `impl Into<T>`
+/// - `#[interactive]` - forward `Interactive` to this field
+/// - `#[children]` - expose this `Vec<Box<dyn View>>` field via `View::children()`
+///
+/// Fields of type `WidgetProps` and `WidgetState` are detected automatically:
+///
+/// - `WidgetProps` provides `id()`, `classes()`, `StyledView`, and the
+///   `element_id`/`class`/`classes` builders
+/// - `WidgetState` provides the `focused`/`disabled`/`fg`/`bg` builders and
+///   gates the forwarded `Interactive` impl on the disabled state
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use revue::prelude::*;
+/// use revue::widget::{Input, WidgetProps, WidgetState};
+///
+/// #[derive(Component)]
+/// #[component(name = "SearchField", class = "search-field", render = "draw")]
+/// struct SearchField {
+///     /// Set the label shown before the input
+///     #[prop]
+///     label: String,
+///     #[prop]
+///     hint: Option<String>,
+///     #[interactive]
+///     input: Input,
+///     state: WidgetState,
+///     props: WidgetProps,
+/// }
+///
+/// impl SearchField {
+///     fn new() -> Self {
+///         Self {
+///             label: String::new(),
+///             hint: None,
+///             input: Input::new(),
+///             state: WidgetState::new(),
+///             props: Self::default_props(),
+///         }
+///     }
+///
+///     fn draw(&self, ctx: &mut RenderContext) {
+///         ctx.draw_text(0, 0, &self.label, Color::WHITE);
+///     }
+/// }
+///
+/// let field = SearchField::new().label("Find").hint("name").class("wide");
+/// ```
+#[proc_macro_derive(Component, attributes(component, prop, interactive, children))]
+pub fn derive_component(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    component::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Declarative view template with JSX/Vue-like syntax
+///
+/// Expands to ordinary widget construction code: elements become constructor
+/// calls, attributes become builder method calls, and children are added with
+/// `.child(...)`.
+///
+/// # Syntax
+///
+/// | Template | Generated code |
+/// |----------|----------------|
+/// | `<vstack>` | `vstack()` |
+/// | `<Button("OK")>` | `Button::new("OK")` |
+/// | `gap=1` / `gap={n + 1}` | `.gap(1)` / `.gap(n + 1)` |
+/// | `bold` | `.bold()` |
+/// | `class="a b"` | `.class("a").class("b")` |
+/// | `id="save"` | `.element_id("save")` |
+/// | `on:click={f}` / `@click={f}` | `.on_click(f)` |
+/// | `"Hello"` (child) | `.child(Text::new("Hello"))` |
+/// | `{expr}` (child) | `.child(expr)` |
+/// | `if cond { ... } else { ... }` | conditional children |
+/// | `for item in iter { ... }` | one child per item |
+///
+/// String literals in text nodes, constructor arguments and attribute values
+/// are passed through `format!` when they contain `{name}` placeholders, so
+/// signals and other `Display` values can be interpolated (`{{` escapes a
+/// brace). Literals whose braces don't form placeholders, such as `"{"`, are
+/// used verbatim. Lowercase element names are called as functions, capitalized ones
+/// as `Type::new(...)`; both resolve in the caller's scope.
+///
+/// Unknown attributes are reported by the compiler as a missing builder
+/// method, with the error pointing at the attribute name.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use revue::prelude::*;
+/// use revue::widget::{hstack, vstack, Button, Text};
+///
+/// let count = signal(3);
+/// let items = ["a", "b"];
+///
+/// let ui = view! {
+///     <vstack gap=1 class="main panel">
+///         <Text("Count: {count}") bold />
+///         if count.get() > 0 {
+///             "Positive"
+///         } else {
+///             "Zero"
+///         }
+///         for item in &items {
+///             <Text(*item) />
+///         }
+///         <hstack>
+///             <Button("Save") id="save" />
+///         </hstack>
+///     </vstack>
+/// };
+/// ```
+#[proc_macro]
+pub fn view(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as view::View).expand().into()
 }
@@ -0,0 +1,516 @@
+//! `view!` template macro implementation
+//!
+//! Parses a JSX/Vue-like element tree and lowers it to ordinary builder
+//! calls. Every attribute becomes a method call spanned at the attribute
+//! name, so unknown attributes surface as `no method named ...` errors on
+//! the offending token.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{braced, parenthesized, token, Expr, ExprLit, Ident, Lit, LitStr, Pat, Token};
+
+/// Root of a `view!` invocation
+pub struct View {
+    root: Node,
+}
+
+/// A node in the template tree
+enum Node {
+    Element(Element),
+    Text(LitStr),
+    Expr(Expr),
+    If(IfNode),
+    For(ForNode),
+}
+
+/// `<Name(args) attr=value ...>children</Name>`
+struct Element {
+    name: Vec<Ident>,
+    args: Option<Punctuated<Expr, Token![,]>>,
+    attrs: Vec<Attr>,
+    children: Vec<Node>,
+}
+
+/// A single attribute on an element
+struct Attr {
+    kind: AttrKind,
+    name: Ident,
+    value: Option<AttrValue>,
+}
+
+enum AttrKind {
+    /// `name=value` or bare `name`
+    Method,
+    /// `on:event={handler}` / `@event={handler}`
+    Event,
+}
+
+enum AttrValue {
+    Lit(Lit),
+    Expr(Expr),
+}
+
+/// `if cond { ... } else if cond { ... } else { ... }`
+struct IfNode {
+    cond: Expr,
+    then: Vec<Node>,
+    otherwise: Option<Box<ElseBranch>>,
+}
+
+enum ElseBranch {
+    If(IfNode),
+    Nodes(Vec<Node>),
+}
+
+/// `for pat in iter { ... }`
+struct ForNode {
+    pat: Pat,
+    iter: Expr,
+    body: Vec<Node>,
+}
+
+impl Parse for View {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Err(input.error("view! expects a root element"));
+        }
+        let root = input.parse::<Node>()?;
+        if !input.is_empty() {
+            return Err(input.error(
+                "view! expects a single root element; wrap multiple elements in a container",
+            ));
+        }
+        if matches!(root, Node::If(_) | Node::For(_)) {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "view! root must be an element, text or expression",
+            ));
+        }
+        Ok(Self { root })
+    }
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![<]) {
+            input.parse().map(Node::Element)
+        } else if input.peek(LitStr) {
+            input.parse().map(Node::Text)
+        } else if input.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            content.parse().map(Node::Expr)
+        } else if input.peek(Token![if]) {
+            input.parse().map(Node::If)
+        } else if input.peek(Token![for]) {
+            input.parse().map(Node::For)
+        } else {
+            Err(input
+                .error("expected an element `<...>`, a string literal, `{expr}`, `if` or `for`"))
+        }
+    }
+}
+
+/// Parse nodes until the end of the stream or a closing tag
+fn parse_nodes(input: ParseStream) -> syn::Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+    while !input.is_empty() {
+        if input.peek(Token![<]) && input.peek2(Token![/]) {
+            break;
+        }
+        nodes.push(input.parse()?);
+    }
+    Ok(nodes)
+}
+
+/// Parse a `{ nodes }` block used by `if`/`for`
+fn parse_block(input: ParseStream) -> syn::Result<Vec<Node>> {
+    let content;
+    braced!(content in input);
+    let nodes = parse_nodes(&content)?;
+    if !content.is_empty() {
+        return Err(content.error("unexpected closing tag"));
+    }
+    Ok(nodes)
+}
+
+/// Parse an element path like `Text` or `widget::Text`
+fn parse_name(input: ParseStream) -> syn::Result<Vec<Ident>> {
+    let mut name = vec![input.call(Ident::parse_any)?];
+    while input.peek(Token![::]) {
+        input.parse::<Token![::]>()?;
+        name.push(input.call(Ident::parse_any)?);
+    }
+    Ok(name)
+}
+
+fn name_string(name: &[Ident]) -> String {
+    name.iter()
+        .map(|ident| ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![<]>()?;
+        let name = parse_name(input)?;
+
+        let args = if input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            Some(content.parse_terminated(Expr::parse, Token![,])?)
+        } else {
+            None
+        };
+
+        let mut attrs = Vec::new();
+        loop {
+            if input.peek(Token![/]) {
+                input.parse::<Token![/]>()?;
+                input.parse::<Token![>]>()?;
+                return Ok(Self {
+                    name,
+                    args,
+                    attrs,
+                    children: Vec::new(),
+                });
+            }
+            if input.peek(Token![>]) {
+                input.parse::<Token![>]>()?;
+                break;
+            }
+            if input.is_empty() {
+                return Err(syn::Error::new(
+                    name[0].span(),
+                    format!("unclosed element `<{}>`", name_string(&name)),
+                ));
+            }
+            attrs.push(input.parse()?);
+        }
+
+        let children = parse_nodes(input)?;
+
+        if input.is_empty() {
+            return Err(syn::Error::new(
+                name[0].span(),
+                format!("missing closing tag `</{}>`", name_string(&name)),
+            ));
+        }
+        input.parse::<Token![<]>()?;
+        input.parse::<Token![/]>()?;
+        let close = parse_name(input)?;
+        if close != name {
+            return Err(syn::Error::new(
+                close[0].span(),
+                format!(
+                    "mismatched closing tag: expected `</{}>`, found `</{}>`",
+                    name_string(&name),
+                    name_string(&close)
+                ),
+            ));
+        }
+        input.parse::<Token![>]>()?;
+
+        Ok(Self {
+            name,
+            args,
+            attrs,
+            children,
+        })
+    }
+}
+
+impl Parse for Attr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let kind = if input.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+            AttrKind::Event
+        } else if input.peek(Ident::peek_any) && input.peek2(Token![:]) && !input.peek2(Token![::])
+        {
+            let prefix = input.call(Ident::parse_any)?;
+            if prefix != "on" {
+                return Err(syn::Error::new(
+                    prefix.span(),
+                    format!("unknown attribute namespace `{prefix}:`, expected `on:`"),
+                ));
+            }
+            input.parse::<Token![:]>()?;
+            AttrKind::Event
+        } else {
+            AttrKind::Method
+        };
+
+        let name = input.call(Ident::parse_any)?;
+
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            if input.peek(token::Brace) {
+                let content;
+                braced!(content in input);
+                Some(AttrValue::Expr(content.parse()?))
+            } else if input.peek(Lit) {
+                Some(AttrValue::Lit(input.parse()?))
+            } else {
+                return Err(input.error("expected a literal or `{expr}` attribute value"));
+            }
+        } else {
+            None
+        };
+
+        if matches!(kind, AttrKind::Event) && value.is_none() {
+            return Err(syn::Error::new(
+                name.span(),
+                format!("event binding `{name}` requires a handler: `on:{name}={{handler}}`"),
+            ));
+        }
+
+        Ok(Self { kind, name, value })
+    }
+}
+
+impl Parse for IfNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![if]>()?;
+        let cond = input.call(Expr::parse_without_eager_brace)?;
+        let then = parse_block(input)?;
+        let otherwise = if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+            if input.peek(Token![if]) {
+                Some(Box::new(ElseBranch::If(input.parse()?)))
+            } else {
+                Some(Box::new(ElseBranch::Nodes(parse_block(input)?)))
+            }
+        } else {
+            None
+        };
+        Ok(Self {
+            cond,
+            then,
+            otherwise,
+        })
+    }
+}
+
+impl Parse for ForNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![for]>()?;
+        let pat = input.call(Pat::parse_multi_with_leading_vert)?;
+        input.parse::<Token![in]>()?;
+        let iter = input.call(Expr::parse_without_eager_brace)?;
+        let body = parse_block(input)?;
+        Ok(Self { pat, iter, body })
+    }
+}
+
+// =============================================================================
+// Code generation
+// =============================================================================
+
+impl View {
+    pub fn expand(&self) -> TokenStream {
+        expand_node(&self.root)
+    }
+}
+
+/// Lower a string literal, interpolating `{name}` placeholders with `format!`
+fn expand_str(lit: &LitStr) -> TokenStream {
+    if is_format_template(&lit.value()) {
+        quote_spanned! {lit.span()=> ::std::format!(#lit) }
+    } else {
+        lit.to_token_stream()
+    }
+}
+
+/// Check if a literal is a valid `format!` string using `{name}` /
+/// `{name:spec}` placeholders or `{{`/`}}` escapes
+///
+/// Anything else (e.g. `"{"` or `"a { b"`) is used verbatim.
+fn is_format_template(value: &str) -> bool {
+    let mut chars = value.chars().peekable();
+    let mut templated = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' if chars.peek() == Some(&c) => {
+                chars.next();
+                templated = true;
+            }
+            '{' => {
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some('{') | None => return false,
+                        Some(ch) => inner.push(ch),
+                    }
+                }
+                let name = inner.split(':').next().unwrap_or_default();
+                if syn::parse_str::<Ident>(name).is_err() {
+                    return false;
+                }
+                templated = true;
+            }
+            '}' => return false,
+            _ => {}
+        }
+    }
+    templated
+}
+
+/// Lower an expression, interpolating bare string literals
+fn expand_expr(expr: &Expr) -> TokenStream {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => expand_str(lit),
+        _ => expr.to_token_stream(),
+    }
+}
+
+/// Lower a single node to an expression producing a widget
+fn expand_node(node: &Node) -> TokenStream {
+    match node {
+        Node::Element(element) => expand_element(element),
+        Node::Text(lit) => {
+            let content = expand_str(lit);
+            quote_spanned! {lit.span()=> ::revue::widget::Text::new(#content) }
+        }
+        Node::Expr(expr) => expr.to_token_stream(),
+        Node::If(_) | Node::For(_) => unreachable!("control flow is lowered by expand_children"),
+    }
+}
+
+fn expand_element(element: &Element) -> TokenStream {
+    let name = &element.name;
+    let last = name.last().expect("element name");
+    let is_type = last
+        .to_string()
+        .trim_start_matches("r#")
+        .starts_with(|c: char| c.is_ascii_uppercase());
+
+    // `<Text(..)>` -> `Text::new(..)`, `<vstack>` -> `vstack()`
+    let args = element
+        .args
+        .iter()
+        .flat_map(|args| args.iter())
+        .map(expand_expr);
+    let ctor = if is_type {
+        let new = Ident::new("new", last.span());
+        quote! { #(#name)::*::#new(#(#args),*) }
+    } else {
+        quote! { #(#name)::*(#(#args),*) }
+    };
+
+    let attrs = element.attrs.iter().map(expand_attr);
+
+    // Plain children are chained directly; once control flow appears the
+    // remaining children are applied through a mutable binding.
+    let split = element
+        .children
+        .iter()
+        .position(|child| matches!(child, Node::If(_) | Node::For(_)))
+        .unwrap_or(element.children.len());
+    let (chained, rest) = element.children.split_at(split);
+    let chained = chained.iter().map(|child| {
+        let child = expand_node(child);
+        quote! { .child(#child) }
+    });
+
+    let base = quote! { #ctor #(#attrs)* #(#chained)* };
+    if rest.is_empty() {
+        return base;
+    }
+
+    let var = Ident::new("__revue_view", Span::mixed_site());
+    let stmts = expand_children(&var, rest);
+    quote! {
+        {
+            #[allow(unused_mut)]
+            let mut #var = #base;
+            #stmts
+            #var
+        }
+    }
+}
+
+/// Lower children to statements that append to `var`
+fn expand_children(var: &Ident, nodes: &[Node]) -> TokenStream {
+    let stmts = nodes.iter().map(|node| match node {
+        Node::If(if_node) => expand_if(var, if_node),
+        Node::For(for_node) => {
+            let ForNode { pat, iter, body } = for_node;
+            let body = expand_children(var, body);
+            quote! {
+                for #pat in #iter {
+                    #body
+                }
+            }
+        }
+        _ => {
+            let child = expand_node(node);
+            quote! { #var = #var.child(#child); }
+        }
+    });
+    quote! { #(#stmts)* }
+}
+
+fn expand_if(var: &Ident, if_node: &IfNode) -> TokenStream {
+    let cond = &if_node.cond;
+    let then = expand_children(var, &if_node.then);
+    let otherwise = if_node.otherwise.as_deref().map(|branch| match branch {
+        ElseBranch::If(nested) => {
+            let nested = expand_if(var, nested);
+            quote! { else #nested }
+        }
+        ElseBranch::Nodes(nodes) => {
+            let nodes = expand_children(var, nodes);
+            quote! { else { #nodes } }
+        }
+    });
+    quote! {
+        if #cond {
+            #then
+        }
+        #otherwise
+    }
+}
+
+fn expand_attr(attr: &Attr) -> TokenStream {
+    let name = &attr.name;
+    let span = name.span();
+
+    let method = match attr.kind {
+        AttrKind::Event => format_ident!("on_{}", name.unraw(), span = span),
+        AttrKind::Method if name == "id" => Ident::new("element_id", span),
+        AttrKind::Method => name.clone(),
+    };
+
+    match &attr.value {
+        None => quote_spanned! {span=> .#method() },
+        // `class="a b"` adds each class separately
+        Some(AttrValue::Lit(Lit::Str(lit)))
+            if name == "class" && !is_format_template(&lit.value()) =>
+        {
+            let classes = lit
+                .value()
+                .split_whitespace()
+                .map(|class| LitStr::new(class, lit.span()))
+                .collect::<Vec<_>>();
+            quote_spanned! {span=> #(.#method(#classes))* }
+        }
+        Some(AttrValue::Lit(Lit::Str(lit))) => {
+            let value = expand_str(lit);
+            quote_spanned! {span=> .#method(#value) }
+        }
+        Some(AttrValue::Lit(lit)) => quote_spanned! {span=> .#method(#lit) },
+        Some(AttrValue::Expr(expr)) => {
+            let expr_span = expr.span();
+            let value = quote_spanned! {expr_span=> #expr };
+            quote_spanned! {span=> .#method(#value) }
+        }
+    }
+}
@@ -1,9 +1,11 @@
 //! Application builder
 
+use super::recording::SessionRecorder;
 use super::App;
 use crate::constants::MAX_CSS_FILE_SIZE;
 use crate::plugin::{Plugin, PluginRegistry};
-use crate::style::{parse_css, StyleSheet};
+use crate::reactive::History;
+use crate::style::{parse_css, parse_css_with_source_map, SourceMap, StyleSheet};
 use std::fs;
 use std::path::PathBuf;
 
@@ -13,12 +15,17 @@ use super::HotReload;
 /// Builder for configuring and creating an App
 pub struct AppBuilder {
     stylesheet: StyleSheet,
+    source_map: SourceMap,
     // To keep track of file paths for hot reload
     style_paths: Vec<PathBuf>,
     hot_reload: bool,
     devtools: bool,
     mouse_capture: bool,
     plugins: PluginRegistry,
+    history: Option<History>,
+    record_path: Option<PathBuf>,
+    #[cfg(feature = "devtools")]
+    devtools_addr: Option<String>,
 }
 
 impl AppBuilder {
@@ -26,11 +33,16 @@ impl AppBuilder {
     pub fn new() -> Self {
         Self {
             stylesheet: StyleSheet::new(),
+            source_map: SourceMap::new(),
             style_paths: Vec::new(),
             hot_reload: false,
             devtools: cfg!(feature = "devtools"),
             mouse_capture: true,
             plugins: PluginRegistry::new(),
+            history: None,
+            record_path: None,
+            #[cfg(feature = "devtools")]
+            devtools_addr: None,
         }
     }
 
@@ -86,8 +98,14 @@ impl AppBuilder {
             }
         };
 
-        match parse_css(&content) {
-            Ok(sheet) => self.stylesheet.merge(sheet),
+        match parse_css_with_source_map(&content) {
+            Ok((sheet, mut source_map)) => {
+                // Remember where rules came from so style edits can be written back
+                source_map.set_file(fs::canonicalize(&path).unwrap_or(path));
+                self.source_map
+                    .merge(self.stylesheet.rules.len(), source_map);
+                self.stylesheet.merge(sheet);
+            }
             Err(e) => log_warn!("Failed to parse CSS from {:?}: {}", path, e),
         }
 
@@ -122,6 +140,68 @@ impl AppBuilder {
         self
     }
 
+    /// Install an app-wide undo history
+    ///
+    /// Ctrl+Z undoes and Ctrl+Shift+Z (or Ctrl+Y) redoes after the event
+    /// handler runs. A handler that forwards those keys to a focused editor
+    /// calls [`App::stop_propagation()`] to keep the editor's own undo.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use revue::reactive::History;
+    ///
+    /// let history = History::new();
+    /// history.track(&document, "Edit");
+    ///
+    /// let app = App::builder().history(history.clone()).build();
+    /// ```
+    pub fn history(mut self, history: History) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Record the session to a file for replay in tests
+    ///
+    /// Input events and screen hashes are written to `path` when
+    /// [`App::run()`] returns. Load the file with
+    /// [`Recording::load()`](super::Recording::load) and replay it with
+    /// [`Pilot::replay()`](crate::testing::Pilot::replay).
+    ///
+    /// Recording can also be enabled without code changes by setting the
+    /// `REVUE_RECORD` environment variable to an output path.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let app = App::builder().record("session.rec").build();
+    /// ```
+    pub fn record(mut self, path: impl Into<PathBuf>) -> Self {
+        self.record_path = Some(path.into());
+        self
+    }
+
+    /// Serve the remote devtools on a local socket
+    ///
+    /// `addr` is `unix:<path>`, a loopback `host:port`, or `auto` for
+    /// `$TMPDIR/revue-devtools-<pid>.sock`. Attach with
+    /// `revue inspect --attach`. TCP servers print a token to stderr that
+    /// clients must present (`revue inspect --addr .. --token ..`).
+    ///
+    /// The server can also be started without code changes by setting the
+    /// `REVUE_DEVTOOLS` environment variable to `1` or an address.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let app = App::builder().devtools_server("127.0.0.1:7878").build();
+    /// ```
+    #[cfg(feature = "devtools")]
+    pub fn devtools_server(mut self, addr: impl Into<String>) -> Self {
+        self.devtools_addr = Some(addr.into());
+        self
+    }
+
     /// Build the application
     pub fn build(mut self) -> App {
         let initial_size = {
@@ -165,7 +245,7 @@ impl AppBuilder {
         };
 
         #[cfg(feature = "hot-reload")]
-        return App::new_with_hot_reload(
+        let mut app = App::new_with_hot_reload(
             initial_size,
             self.stylesheet,
             self.mouse_capture,
@@ -176,13 +256,28 @@ impl AppBuilder {
         );
 
         #[cfg(not(feature = "hot-reload"))]
-        App::new_with_plugins(
+        let mut app = App::new_with_plugins(
             initial_size,
             self.stylesheet,
             self.mouse_capture,
             self.plugins,
             self.devtools,
-        )
+        );
+
+        app.dom.set_source_map(self.source_map);
+        app.history = self.history;
+        app.recorder = match self.record_path {
+            Some(path) => Some(SessionRecorder::new(path)),
+            None => SessionRecorder::from_env(),
+        };
+        #[cfg(feature = "devtools")]
+        {
+            app.devtools_server = match self.devtools_addr {
+                Some(addr) => crate::devtools::remote::DevToolsServer::start(&addr),
+                None => crate::devtools::remote::DevToolsServer::from_env(),
+            };
+        }
+        app
     }
 }
 
@@ -402,4 +497,11 @@ mod tests {
             "hot_reload should be None when no style paths"
         );
     }
+
+    #[test]
+    fn test_builder_record() {
+        let app = AppBuilder::new().record("session.rec").build();
+        let recorder = app.recorder().expect("recorder should be set");
+        assert_eq!(recorder.path(), std::path::Path::new("session.rec"));
+    }
 }
@@ -95,9 +95,13 @@ pub mod declarative_router;
 mod hot_reload;
 mod inspector;
 pub mod profiler;
+pub mod recording;
+#[cfg(feature = "devtools")]
+mod remote;
 pub mod router;
 pub mod screen;
 pub mod snapshot;
+mod style_edit;
 
 pub use builder::AppBuilder;
 pub use declarative_router::{
@@ -110,6 +114,9 @@ pub use inspector::{inspector, Inspector, WidgetInfo};
 pub use profiler::{
     fps_counter, profiler as new_profiler, FpsCounter, Metric, MetricType, Profiler, Sample, Stats,
 };
+pub use recording::{
+    screen_hash, RecordedEntry, RecordedKind, Recording, RecordingError, SessionRecorder,
+};
 pub use router::{
     router, routes, HistoryEntry, NavigationEvent, QueryParams, Route, RouteBuilder, RouteParams,
     Router,
@@ -121,17 +128,19 @@ pub use screen::{
 pub use snapshot::{snapshot, Snapshot, SnapshotConfig, SnapshotResult};
 
 use crate::constants::FRAME_DURATION_60FPS;
+use crate::devtools::{FramePhase, RenderEvent, RenderReason};
 use crate::dom::DomRenderer;
 use crate::event::{Event, KeyEvent};
 use crate::layout::LayoutEngine;
 use crate::render::{Buffer, Terminal};
 use crate::style::{StyleSheet, TransitionManager};
+use crate::utils::clock;
 use crate::widget::View;
 use std::io::stdout;
 use std::time::{Duration, Instant};
 
 #[cfg(feature = "hot-reload")]
-use crate::style::parse_css;
+use crate::style::parse_css_with_source_map;
 #[cfg(feature = "hot-reload")]
 use std::fs;
 #[cfg(feature = "hot-reload")]
@@ -215,6 +224,17 @@ pub struct App {
     plugins: crate::plugin::PluginRegistry,
     /// Whether devtools are enabled for this app instance
     devtools_enabled: bool,
+    /// App-wide undo history bound to Ctrl+Z / Ctrl+Shift+Z
+    history: Option<crate::reactive::History>,
+    /// Set by the event handler to keep app-wide shortcuts off the event
+    propagation_stopped: bool,
+    /// Session recorder for replayable regression tests
+    recorder: Option<SessionRecorder>,
+    /// Per-frame phase and widget timings, recorded on request
+    frame_profiler: crate::devtools::Profiler,
+    /// Remote devtools server for `revue inspect --attach`
+    #[cfg(feature = "devtools")]
+    devtools_server: Option<crate::devtools::remote::DevToolsServer>,
     /// Hot reload watcher
     #[cfg(feature = "hot-reload")]
     hot_reload: Option<HotReload>,
@@ -241,13 +261,19 @@ impl App {
             current_buffer: 0,
             running: false,
             transitions: TransitionManager::new(),
-            last_tick: Instant::now(),
+            last_tick: clock::now(),
             mouse_capture,
             needs_force_redraw: true, // Initial render should be a full draw
             needs_layout_rebuild: true, // Initial render needs full layout build
             needs_dom_rebuild: true,  // Initial render needs DOM root creation
             plugins,
             devtools_enabled,
+            history: None,
+            propagation_stopped: false,
+            recorder: None,
+            frame_profiler: crate::devtools::Profiler::new(),
+            #[cfg(feature = "devtools")]
+            devtools_server: None,
             #[cfg(feature = "hot-reload")]
             hot_reload: None,
             #[cfg(feature = "hot-reload")]
@@ -274,13 +300,19 @@ impl App {
             current_buffer: 0,
             running: false,
             transitions: TransitionManager::new(),
-            last_tick: Instant::now(),
+            last_tick: clock::now(),
             mouse_capture,
             needs_force_redraw: true,
             needs_layout_rebuild: true,
             needs_dom_rebuild: true,
             plugins,
             devtools_enabled,
+            history: None,
+            propagation_stopped: false,
+            recorder: None,
+            frame_profiler: crate::devtools::Profiler::new(),
+            #[cfg(feature = "devtools")]
+            devtools_server: None,
             hot_reload,
             style_paths,
         }
@@ -352,7 +384,11 @@ impl App {
         }
 
         self.running = true;
-        self.last_tick = Instant::now();
+        self.last_tick = clock::now();
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.start(width, height);
+        }
 
         self.dom.build(&view);
         self.draw(&view, &mut terminal, true)?;
@@ -372,7 +408,17 @@ impl App {
             }
 
             let event = reader.read()?;
+            let is_tick = matches!(event, Event::Tick);
+            if !is_tick {
+                self.record_event(&event);
+            }
             let should_draw = self.handle_event(event, &mut view, &mut handler);
+            // Only ticks that changed something are worth replaying
+            if is_tick && should_draw {
+                self.record_event(&Event::Tick);
+            }
+            #[cfg(feature = "devtools")]
+            let should_draw = self.serve_devtools() || should_draw;
 
             if should_draw {
                 self.draw(&view, &mut terminal, false)?;
@@ -384,6 +430,12 @@ impl App {
             crate::log_warn!("Plugin unmount failed: {}", e);
         }
 
+        if let Some(recorder) = &mut self.recorder {
+            if let Err(e) = recorder.finish() {
+                crate::log_warn!("Failed to save recording {:?}: {}", recorder.path(), e);
+            }
+        }
+
         terminal.restore()?;
         Ok(())
     }
@@ -434,8 +486,17 @@ impl App {
         V: View,
         H: FnMut(&Event, &mut V, &mut Self) -> bool,
     {
+        self.propagation_stopped = false;
         let mut should_draw = handler(&event, view, self);
 
+        // App-wide undo/redo shortcuts run after the handler unless it
+        // stopped propagation, so a focused editor keeps its own undo
+        if !std::mem::take(&mut self.propagation_stopped) {
+            if let (Event::Key(key), Some(history)) = (&event, &self.history) {
+                should_draw |= history.handle_key(key);
+            }
+        }
+
         match event {
             Event::Key(key) if is_quit_key(&key) => {
                 self.quit();
@@ -450,7 +511,7 @@ impl App {
                 should_draw = true;
             }
             Event::Tick => {
-                let now = Instant::now();
+                let now = clock::now();
                 let delta = now.duration_since(self.last_tick);
                 self.last_tick = now;
                 // Update both legacy and node-aware transitions
@@ -470,6 +531,17 @@ impl App {
         should_draw || self.needs_force_redraw
     }
 
+    /// Record an input event if a session recording or devtools client is active
+    fn record_event(&mut self, event: &Event) {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record_event(event);
+        }
+        #[cfg(feature = "devtools")]
+        if let Some(server) = &mut self.devtools_server {
+            server.record_event(event);
+        }
+    }
+
     /// Check for hot reload events and reload stylesheets if needed
     #[cfg(feature = "hot-reload")]
     fn check_hot_reload(&mut self) -> Option<bool> {
@@ -530,8 +602,14 @@ impl App {
     /// Parse CSS content and merge into stylesheet
     #[cfg(feature = "hot-reload")]
     fn parse_and_merge_stylesheet(&mut self, path: &PathBuf, content: &str) {
-        match parse_css(content) {
-            Ok(sheet) => {
+        match parse_css_with_source_map(content) {
+            Ok((sheet, mut source_map)) => {
+                let source = fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+                // Rules merged from earlier versions of the file now sit elsewhere
+                self.dom.source_map_mut().refresh(&source, &source_map);
+                source_map.set_file(source);
+                let offset = self.dom.stylesheet().rules.len();
+                self.dom.source_map_mut().merge(offset, source_map);
                 self.dom.stylesheet_mut().merge(sheet);
                 self.needs_force_redraw = true;
                 crate::log_debug!("Hot reload: reloaded {:?}", path);
@@ -549,17 +627,60 @@ impl App {
         terminal: &mut Terminal<W>,
         force_redraw: bool,
     ) -> crate::Result<()> {
+        #[cfg(feature = "devtools")]
+        let started = Instant::now();
+        self.frame_profiler.start_frame();
         let root_dom_id = self.update_dom_and_get_root(view)?;
         let (width, height) = self.get_buffer_size();
+        let phase = Instant::now();
         self.update_layout_tree(root_dom_id, width, height);
-        let dirty_rects = self.collect_dirty_regions(width, height, force_redraw);
+        self.frame_profiler.record_phase(FramePhase::Layout, phase);
+        let mut dirty_rects = self.collect_dirty_regions(width, height, force_redraw);
+
+        // A redraw was requested (e.g. the handler changed view state) but no
+        // DOM node is marked dirty: re-render everything and let the diff
+        // decide what reaches the terminal
+        if dirty_rects.is_empty() {
+            dirty_rects.push(crate::layout::Rect::new(0, 0, width, height));
+        }
 
         let new_buffer_idx = self.swap_buffers();
-        self.render_to_buffer(view, new_buffer_idx, &dirty_rects);
+        let phase = Instant::now();
+        if self.frame_profiler.is_recording() {
+            let ((), timings) = crate::widget::traits::profile_render(|| {
+                self.render_to_buffer(view, new_buffer_idx, &dirty_rects)
+            });
+            self.frame_profiler.record_phase(FramePhase::Render, phase);
+            for timing in timings {
+                let mut event = RenderEvent::new(timing.name, timing.duration).depth(timing.depth);
+                event.parent = timing.parent;
+                event.timestamp = timing.start;
+                event.reason = if force_redraw {
+                    RenderReason::ForceUpdate
+                } else {
+                    RenderReason::StateChange
+                };
+                self.frame_profiler.record_render(event);
+            }
+        } else {
+            self.render_to_buffer(view, new_buffer_idx, &dirty_rects);
+        }
+        let written = terminal.bytes_written();
         self.draw_to_terminal(terminal, new_buffer_idx, force_redraw, &dirty_rects)?;
+        self.frame_profiler
+            .record_bytes_written(terminal.bytes_written() - written);
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record_screen(&self.buffers[self.current_buffer]);
+        }
+        #[cfg(feature = "devtools")]
+        if let Some(server) = &mut self.devtools_server {
+            server.record_frame(started.elapsed(), dirty_rects.len());
+        }
 
         // Clear dirty flags after rendering
         self.dom.tree_mut().clear_dirty_flags();
+        self.frame_profiler.end_frame();
 
         Ok(())
     }
@@ -568,14 +689,18 @@ impl App {
     fn update_dom_and_get_root<V: View>(&mut self, view: &V) -> crate::Result<crate::dom::DomId> {
         // Only rebuild DOM root if needed (first frame or explicit request)
         if self.needs_dom_rebuild {
+            let phase = Instant::now();
             self.dom.build(view);
+            self.frame_profiler.record_phase(FramePhase::Build, phase);
             self.needs_dom_rebuild = false;
             // DOM rebuild requires layout rebuild
             self.needs_layout_rebuild = true;
         }
 
         // Always compute styles (has internal dirty checking optimization)
+        let phase = Instant::now();
         self.dom.compute_styles_with_inheritance();
+        self.frame_profiler.record_phase(FramePhase::Cascade, phase);
 
         self.dom.tree().root_id().ok_or_else(|| {
             crate::Error::Other(anyhow::anyhow!(
@@ -743,11 +868,17 @@ impl App {
         let new_buffer = &self.buffers[buffer_idx];
 
         if force_redraw || self.needs_force_redraw {
+            let phase = Instant::now();
             terminal.force_redraw(new_buffer)?;
+            self.frame_profiler.record_phase(FramePhase::Write, phase);
             self.needs_force_redraw = false;
         } else {
+            let phase = Instant::now();
             let changes = crate::render::diff(old_buffer, new_buffer, dirty_rects);
+            self.frame_profiler.record_phase(FramePhase::Diff, phase);
+            let phase = Instant::now();
             terminal.draw_changes(changes, new_buffer)?;
+            self.frame_profiler.record_phase(FramePhase::Write, phase);
         }
 
         // Swap to the new buffer
@@ -837,6 +968,15 @@ impl App {
         self.running = false;
     }
 
+    /// Stop the event being handled from reaching app-wide shortcuts
+    ///
+    /// Call from the event handler when a widget consumed the event, e.g. a
+    /// focused editor handling Ctrl+Z with its own undo stack. The handler's
+    /// return value only controls redrawing.
+    pub fn stop_propagation(&mut self) {
+        self.propagation_stopped = true;
+    }
+
     /// Request a full screen redraw on the next frame
     pub fn request_redraw(&mut self) {
         self.needs_force_redraw = true;
@@ -880,6 +1020,43 @@ impl App {
         self.devtools_enabled
     }
 
+    /// Get the frame profiler
+    pub fn frame_profiler(&self) -> &crate::devtools::Profiler {
+        &self.frame_profiler
+    }
+
+    /// Get the frame profiler mutably, e.g. to start recording
+    ///
+    /// While recording, every drawn frame is timed per phase (DOM build,
+    /// cascade, layout, render, diff, terminal write) along with each
+    /// widget's render time and the bytes written to the terminal.
+    pub fn frame_profiler_mut(&mut self) -> &mut crate::devtools::Profiler {
+        &mut self.frame_profiler
+    }
+
+    /// Get the app-wide undo history, if one was installed
+    pub fn history(&self) -> Option<&crate::reactive::History> {
+        self.history.as_ref()
+    }
+
+    /// Install or remove the app-wide undo history
+    pub fn set_history(&mut self, history: Option<crate::reactive::History>) {
+        self.history = history;
+    }
+
+    /// Get the session recorder, if recording is enabled
+    pub fn recorder(&self) -> Option<&SessionRecorder> {
+        self.recorder.as_ref()
+    }
+
+    /// Set or clear the session recorder
+    ///
+    /// The recording starts when [`run()`][Self::run] begins and is written
+    /// when it returns.
+    pub fn set_recorder(&mut self, recorder: Option<SessionRecorder>) {
+        self.recorder = recorder;
+    }
+
     /// Get mutable access to the DOM renderer
     pub fn dom_renderer(&mut self) -> &mut DomRenderer {
         &mut self.dom
@@ -1090,6 +1267,76 @@ mod tests {
         assert_eq!(app.current_buffer, 0);
     }
 
+    #[test]
+    fn test_frame_profiler_records_phases() {
+        use crate::widget::{vstack, Text};
+
+        let mut app = create_test_app();
+        let view = vstack().child(Text::new("a")).child(Text::new("b"));
+        let mut terminal = Terminal::with_size(Vec::new(), 80, 24);
+
+        app.draw(&view, &mut terminal, true).unwrap();
+        assert_eq!(app.frame_profiler().frame_count(), 0);
+
+        app.frame_profiler_mut().start_recording();
+        app.request_dom_rebuild();
+        let view = vstack().child(Text::new("c")).child(Text::new("d"));
+        app.draw(&view, &mut terminal, false).unwrap();
+        app.frame_profiler_mut().stop_recording();
+
+        let frames = app.frame_profiler().frames();
+        assert_eq!(frames.len(), 1);
+        let phases: Vec<_> = frames[0].phases.iter().map(|p| p.phase).collect();
+        assert_eq!(
+            phases,
+            vec![
+                FramePhase::Build,
+                FramePhase::Cascade,
+                FramePhase::Layout,
+                FramePhase::Render,
+                FramePhase::Diff,
+                FramePhase::Write,
+            ]
+        );
+        let widgets: Vec<_> = frames[0]
+            .events
+            .iter()
+            .map(|e| (e.component.as_str(), e.depth))
+            .collect();
+        assert_eq!(widgets, vec![("Text", 1), ("Text", 1), ("Stack", 0)]);
+        assert!(frames[0].bytes_written > 0);
+    }
+
+    #[test]
+    fn test_draw_without_dirty_nodes_rerenders_view_state() {
+        struct Counter {
+            count: i32,
+        }
+
+        impl View for Counter {
+            fn render(&self, ctx: &mut crate::widget::RenderContext) {
+                let text = format!("Count: {}", self.count);
+                for (i, ch) in text.chars().enumerate() {
+                    ctx.draw_char(i as u16, 0, ch, crate::style::Color::WHITE);
+                }
+            }
+        }
+
+        let mut app = create_test_app();
+        let mut view = Counter { count: 0 };
+        let mut terminal = Terminal::with_size(Vec::new(), 80, 24);
+        app.draw(&view, &mut terminal, true).unwrap();
+
+        // A handler changed view state and asked for a redraw, but no DOM
+        // node was marked dirty
+        view.count = 1;
+        app.draw(&view, &mut terminal, false).unwrap();
+
+        let buffer = &app.buffers[app.current_buffer];
+        let line: String = (0..8).map(|x| buffer.get(x, 0).unwrap().symbol).collect();
+        assert_eq!(line, "Count: 1");
+    }
+
     #[test]
     fn test_devtools_methods() {
         let mut app = create_test_app();
@@ -1164,6 +1411,91 @@ mod tests {
         // Just verify it doesn't panic
     }
 
+    #[test]
+    fn test_handle_event_history_shortcuts() {
+        let value = crate::reactive::signal(0);
+        let history = crate::reactive::History::new();
+        history.track(&value, "Set");
+        value.set(1);
+
+        let mut app = create_test_app();
+        app.set_history(Some(history));
+        let mut view = TestView;
+        let handled = std::cell::Cell::new(false);
+        let mut handler = |_: &Event, _: &mut TestView, _: &mut App| {
+            handled.set(true);
+            false
+        };
+
+        let event = Event::Key(KeyEvent::ctrl(Key::Char('z')));
+        assert!(app.handle_event(event, &mut view, &mut handler));
+        assert_eq!(value.get(), 0);
+        // The handler saw the key first and left it alone
+        assert!(handled.get());
+
+        let event = Event::Key(KeyEvent::ctrl(Key::Char('y')));
+        assert!(app.handle_event(event, &mut view, &mut handler));
+        assert_eq!(value.get(), 1);
+    }
+
+    #[test]
+    fn test_handle_event_focused_editor_keeps_its_undo() {
+        use crate::widget::Input;
+
+        struct Editor {
+            input: Input,
+            editing: bool,
+        }
+
+        impl View for Editor {
+            fn render(&self, _ctx: &mut crate::widget::RenderContext) {}
+        }
+
+        let value = crate::reactive::signal(0);
+        let history = crate::reactive::History::new();
+        history.track(&value, "Set");
+        value.set(1);
+
+        let mut app = create_test_app();
+        app.set_history(Some(history));
+        let mut view = Editor {
+            input: Input::new().focused(true),
+            editing: true,
+        };
+        let mut handler = |event: &Event, view: &mut Editor, app: &mut App| match event {
+            Event::Key(key) if view.editing => {
+                app.stop_propagation();
+                view.input.handle_key_event(key)
+            }
+            _ => false,
+        };
+
+        for ch in ['a', 'b'] {
+            let event = Event::Key(KeyEvent::new(Key::Char(ch)));
+            app.handle_event(event, &mut view, &mut handler);
+        }
+        assert_eq!(view.input.text(), "ab");
+
+        // The focused editor consumes Ctrl+Z for its own undo
+        let undo = Event::Key(KeyEvent::ctrl(Key::Char('z')));
+        assert!(app.handle_event(undo.clone(), &mut view, &mut handler));
+        assert_ne!(view.input.text(), "ab");
+        assert_eq!(value.get(), 1);
+
+        // It still eats Ctrl+Z once its own undo stack is empty
+        while view.input.can_undo() {
+            app.handle_event(undo.clone(), &mut view, &mut handler);
+        }
+        assert_eq!(view.input.text(), "");
+        app.handle_event(undo.clone(), &mut view, &mut handler);
+        assert_eq!(value.get(), 1);
+
+        // Without a focused editor the app-wide history undoes
+        view.editing = false;
+        assert!(app.handle_event(undo, &mut view, &mut handler));
+        assert_eq!(value.get(), 0);
+    }
+
     #[test]
     fn test_handle_event_handler_returns_true() {
         let mut app = create_test_app();
@@ -0,0 +1,681 @@
+//! Session recording for regression tests
+//!
+//! A [`Recording`] captures the input events of a real session together with
+//! hashes of the screen after each frame that changed. It is stored in a
+//! line-based text format so it can be attached to bug reports, reviewed and
+//! edited by hand, then replayed against a `TestApp` with
+//! [`Pilot::replay()`](crate::testing::Pilot::replay).
+//!
+//! Enable recording with [`AppBuilder::record()`](super::AppBuilder::record)
+//! or by setting the `REVUE_RECORD` environment variable to an output path.
+//!
+//! # Format
+//!
+//! ```text
+//! # revue recording v1
+//! size 80 24
+//! 0 screen 8c3f9a1e2b7d4c60
+//! 412 key j
+//! 412 screen 1d2e3f4a5b6c7d8e
+//! 980 key ctrl-s
+//! 1204 mouse down-left 10 4
+//! 1600 resize 100 30
+//! 2010 paste hello\nworld
+//! 2500 tick
+//! ```
+//!
+//! Each entry starts with the milliseconds elapsed since recording began.
+//! Ticks are only recorded when they caused a redraw. Screen hashes cover
+//! the rendered characters only, not colors or styles, so they match between
+//! a styled terminal session and an unstyled `TestApp`.
+
+use crate::event::{Event, Key, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use crate::render::Buffer;
+use crate::utils::clock;
+use crate::utils::keymap::parse_key_binding;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Environment variable that enables recording to the given path
+pub const RECORD_ENV_VAR: &str = "REVUE_RECORD";
+
+const HEADER: &str = "# revue recording v1";
+
+/// Errors from loading or saving a recording
+#[derive(Debug, thiserror::Error)]
+pub enum RecordingError {
+    /// Failed to read or write the recording file
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A line could not be parsed
+    #[error("Invalid recording at line {line}: {message}")]
+    Parse {
+        /// 1-based line number
+        line: usize,
+        /// What was wrong with the line
+        message: String,
+    },
+}
+
+/// What happened at a point in a recording
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordedKind {
+    /// An input event was received
+    Event(Event),
+    /// A frame was drawn with this screen hash
+    Screen(u64),
+}
+
+/// A timestamped recording entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedEntry {
+    /// Time since recording began
+    pub at: Duration,
+    /// Event or screen checkpoint
+    pub kind: RecordedKind,
+}
+
+/// Recorded input events and screen hashes of a session
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Recording {
+    /// Terminal size when recording began
+    pub size: (u16, u16),
+    /// Entries in the order they occurred
+    pub entries: Vec<RecordedEntry>,
+}
+
+impl Recording {
+    /// Create an empty recording for a terminal size
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            size: (width, height),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Load a recording from a file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RecordingError> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    /// Save the recording to a file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RecordingError> {
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    /// Append an input event
+    pub fn push_event(&mut self, at: Duration, event: Event) {
+        self.entries.push(RecordedEntry {
+            at,
+            kind: RecordedKind::Event(event),
+        });
+    }
+
+    /// Append a screen checkpoint
+    pub fn push_screen(&mut self, at: Duration, hash: u64) {
+        self.entries.push(RecordedEntry {
+            at,
+            kind: RecordedKind::Screen(hash),
+        });
+    }
+
+    /// Iterate over the recorded events, skipping screen checkpoints
+    pub fn events(&self) -> impl Iterator<Item = &Event> {
+        self.entries.iter().filter_map(|e| match &e.kind {
+            RecordedKind::Event(event) => Some(event),
+            RecordedKind::Screen(_) => None,
+        })
+    }
+
+    /// Number of screen checkpoints
+    pub fn screen_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.kind, RecordedKind::Screen(_)))
+            .count()
+    }
+}
+
+impl fmt::Display for Recording {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+        writeln!(f, "size {} {}", self.size.0, self.size.1)?;
+        for entry in &self.entries {
+            let ms = entry.at.as_millis();
+            match &entry.kind {
+                RecordedKind::Screen(hash) => writeln!(f, "{} screen {:016x}", ms, hash)?,
+                RecordedKind::Event(event) => writeln!(f, "{} {}", ms, format_event(event))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Recording {
+    type Err = RecordingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut recording = Recording::default();
+        let mut has_size = false;
+
+        for (idx, raw) in s.lines().enumerate() {
+            let line = raw.trim_end_matches('\r');
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: String| RecordingError::Parse {
+                line: idx + 1,
+                message,
+            };
+
+            let (first, rest) = split_token(line);
+            if first == "size" {
+                let (w, h) = parse_pair(rest).ok_or_else(|| error("expected 'size W H'".into()))?;
+                recording.size = (w, h);
+                has_size = true;
+                continue;
+            }
+
+            let ms: u64 = first
+                .parse()
+                .map_err(|_| error(format!("invalid timestamp '{}'", first)))?;
+            let at = Duration::from_millis(ms);
+            let (kind, args) = split_token(rest);
+
+            if kind == "screen" {
+                let hash = u64::from_str_radix(args.trim(), 16)
+                    .map_err(|_| error(format!("invalid screen hash '{}'", args.trim())))?;
+                recording.push_screen(at, hash);
+            } else {
+                let event = parse_event(kind, args).map_err(error)?;
+                recording.push_event(at, event);
+            }
+        }
+
+        if !has_size {
+            return Err(RecordingError::Parse {
+                line: 0,
+                message: "missing 'size' line".into(),
+            });
+        }
+        Ok(recording)
+    }
+}
+
+/// Hash the characters on screen
+///
+/// Only symbols are hashed, so the result does not depend on colors or
+/// styles. Uses FNV-1a, which is stable across platforms and releases.
+pub fn screen_hash(buffer: &Buffer) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = OFFSET;
+    let mut feed = |c: char| {
+        let mut bytes = [0; 4];
+        for &b in c.encode_utf8(&mut bytes).as_bytes() {
+            hash ^= u64::from(b);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    };
+
+    for y in 0..buffer.height() {
+        for x in 0..buffer.width() {
+            feed(buffer.get(x, y).map_or(' ', |cell| cell.symbol));
+        }
+        feed('\n');
+    }
+    hash
+}
+
+/// Records events and screen hashes while an [`App`](super::App) runs
+///
+/// Anything recorded since the last [`finish()`](Self::finish) is saved when
+/// the recorder is dropped, so a session that ends in an error or a panic
+/// still leaves a recording behind.
+pub struct SessionRecorder {
+    path: PathBuf,
+    recording: Recording,
+    started: Instant,
+    last_screen: Option<u64>,
+    unsaved: bool,
+}
+
+impl SessionRecorder {
+    /// Create a recorder that writes to `path` when finished
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            recording: Recording::default(),
+            started: clock::now(),
+            last_screen: None,
+            unsaved: false,
+        }
+    }
+
+    /// Create a recorder from the `REVUE_RECORD` environment variable
+    pub fn from_env() -> Option<Self> {
+        std::env::var_os(RECORD_ENV_VAR)
+            .filter(|path| !path.is_empty())
+            .map(Self::new)
+    }
+
+    /// Output path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Recorded data so far
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Start a new recording at the given terminal size
+    pub fn start(&mut self, width: u16, height: u16) {
+        self.recording = Recording::new(width, height);
+        self.started = clock::now();
+        self.last_screen = None;
+    }
+
+    /// Record an input event
+    pub fn record_event(&mut self, event: &Event) {
+        let at = clock::elapsed(self.started);
+        self.recording.push_event(at, event.clone());
+        self.unsaved = true;
+    }
+
+    /// Record the screen if it changed since the last checkpoint
+    pub fn record_screen(&mut self, buffer: &Buffer) {
+        let hash = screen_hash(buffer);
+        if self.last_screen != Some(hash) {
+            self.last_screen = Some(hash);
+            let at = clock::elapsed(self.started);
+            self.recording.push_screen(at, hash);
+            self.unsaved = true;
+        }
+    }
+
+    /// Write the recording to the output path
+    pub fn finish(&mut self) -> Result<(), RecordingError> {
+        self.recording.save(&self.path)?;
+        self.unsaved = false;
+        Ok(())
+    }
+}
+
+impl Drop for SessionRecorder {
+    fn drop(&mut self) {
+        if self.unsaved {
+            if let Err(e) = self.finish() {
+                crate::log_warn!("Failed to save recording {:?}: {}", self.path(), e);
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Text encoding
+// =============================================================================
+
+/// Split off the first whitespace-separated token
+fn split_token(s: &str) -> (&str, &str) {
+    let s = s.trim_start();
+    match s.find(char::is_whitespace) {
+        Some(i) => (&s[..i], &s[i + 1..]),
+        None => (s, ""),
+    }
+}
+
+fn parse_pair(s: &str) -> Option<(u16, u16)> {
+    let mut parts = s.split_whitespace();
+    let a = parts.next()?.parse().ok()?;
+    let b = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((a, b))
+}
+
+fn format_event(event: &Event) -> String {
+    match event {
+        Event::Key(key) => format!("key {}", format_key(key)),
+        Event::Mouse(mouse) => {
+            let mut text = format!(
+                "mouse {} {} {}",
+                mouse_kind_name(mouse.kind),
+                mouse.x,
+                mouse.y
+            );
+            for (held, name) in [
+                (mouse.ctrl, "ctrl"),
+                (mouse.alt, "alt"),
+                (mouse.shift, "shift"),
+            ] {
+                if held {
+                    text.push(' ');
+                    text.push_str(name);
+                }
+            }
+            text
+        }
+        Event::Resize(w, h) => format!("resize {} {}", w, h),
+        Event::Tick => "tick".to_string(),
+        Event::FocusGained => "focus-gained".to_string(),
+        Event::FocusLost => "focus-lost".to_string(),
+        Event::Paste(text) => format!("paste {}", escape(text)),
+    }
+}
+
+fn parse_event(kind: &str, args: &str) -> Result<Event, String> {
+    match kind {
+        "key" => parse_key(args)
+            .map(Event::Key)
+            .ok_or_else(|| format!("invalid key '{}'", args)),
+        "mouse" => parse_mouse(args).ok_or_else(|| format!("invalid mouse event '{}'", args)),
+        "resize" => parse_pair(args)
+            .map(|(w, h)| Event::Resize(w, h))
+            .ok_or_else(|| "expected 'resize W H'".to_string()),
+        "tick" => Ok(Event::Tick),
+        "focus-gained" => Ok(Event::FocusGained),
+        "focus-lost" => Ok(Event::FocusLost),
+        "paste" => Ok(Event::Paste(unescape(args))),
+        other => Err(format!("unknown entry '{}'", other)),
+    }
+}
+
+/// Format a key in the syntax accepted by `parse_key_binding`
+///
+/// Keys that syntax can't name are written as `f13` and up, `null` and
+/// `unknown`; [`parse_key()`] reads them back.
+fn format_key(event: &KeyEvent) -> String {
+    let name = match event.key {
+        Key::Char(' ') => "space".to_string(),
+        Key::Char(c) => c.to_string(),
+        Key::Enter => "enter".to_string(),
+        Key::Escape => "esc".to_string(),
+        Key::Tab => "tab".to_string(),
+        Key::BackTab => "backtab".to_string(),
+        Key::Backspace => "backspace".to_string(),
+        Key::Delete => "delete".to_string(),
+        Key::Up => "up".to_string(),
+        Key::Down => "down".to_string(),
+        Key::Left => "left".to_string(),
+        Key::Right => "right".to_string(),
+        Key::Home => "home".to_string(),
+        Key::End => "end".to_string(),
+        Key::PageUp => "pageup".to_string(),
+        Key::PageDown => "pagedown".to_string(),
+        Key::Insert => "insert".to_string(),
+        Key::F(n) => format!("f{}", n),
+        Key::Null => "null".to_string(),
+        Key::Unknown => "unknown".to_string(),
+    };
+
+    let mut text = String::new();
+    if event.ctrl {
+        text.push_str("ctrl-");
+    }
+    if event.alt {
+        text.push_str("alt-");
+    }
+    if event.shift {
+        text.push_str("shift-");
+    }
+    text.push_str(&name);
+    text
+}
+
+/// Parse a key written by [`format_key()`]
+fn parse_key(text: &str) -> Option<KeyEvent> {
+    let binding = parse_key_binding(text).or_else(|| {
+        // Names outside the key binding syntax never contain '-', so
+        // everything before the last one is modifiers
+        let name = text.rsplit('-').next()?;
+        let key = match name.to_lowercase().as_str() {
+            "null" => Key::Null,
+            "unknown" => Key::Unknown,
+            other => Key::F(other.strip_prefix('f')?.parse().ok()?),
+        };
+        let modifiers = &text[..text.len() - name.len()];
+        let mut binding = parse_key_binding(&format!("{}x", modifiers))?;
+        binding.key = key;
+        Some(binding)
+    })?;
+    Some(KeyEvent {
+        key: binding.key,
+        ctrl: binding.ctrl,
+        alt: binding.alt,
+        shift: binding.shift,
+    })
+}
+
+fn mouse_kind_name(kind: MouseEventKind) -> &'static str {
+    match kind {
+        MouseEventKind::Down(MouseButton::Left) => "down-left",
+        MouseEventKind::Down(MouseButton::Right) => "down-right",
+        MouseEventKind::Down(MouseButton::Middle) => "down-middle",
+        MouseEventKind::Up(MouseButton::Left) => "up-left",
+        MouseEventKind::Up(MouseButton::Right) => "up-right",
+        MouseEventKind::Up(MouseButton::Middle) => "up-middle",
+        MouseEventKind::Drag(MouseButton::Left) => "drag-left",
+        MouseEventKind::Drag(MouseButton::Right) => "drag-right",
+        MouseEventKind::Drag(MouseButton::Middle) => "drag-middle",
+        MouseEventKind::Move => "move",
+        MouseEventKind::ScrollDown => "scroll-down",
+        MouseEventKind::ScrollUp => "scroll-up",
+        MouseEventKind::ScrollLeft => "scroll-left",
+        MouseEventKind::ScrollRight => "scroll-right",
+    }
+}
+
+fn parse_mouse(args: &str) -> Option<Event> {
+    let mut parts = args.split_whitespace();
+    let kind = match parts.next()? {
+        "move" => MouseEventKind::Move,
+        "scroll-down" => MouseEventKind::ScrollDown,
+        "scroll-up" => MouseEventKind::ScrollUp,
+        "scroll-left" => MouseEventKind::ScrollLeft,
+        "scroll-right" => MouseEventKind::ScrollRight,
+        name => {
+            let (action, button) = name.split_once('-')?;
+            let button = match button {
+                "left" => MouseButton::Left,
+                "right" => MouseButton::Right,
+                "middle" => MouseButton::Middle,
+                _ => return None,
+            };
+            match action {
+                "down" => MouseEventKind::Down(button),
+                "up" => MouseEventKind::Up(button),
+                "drag" => MouseEventKind::Drag(button),
+                _ => return None,
+            }
+        }
+    };
+    let x = parts.next()?.parse().ok()?;
+    let y = parts.next()?.parse().ok()?;
+
+    let mut event = MouseEvent::new(x, y, kind);
+    for modifier in parts {
+        match modifier {
+            "ctrl" => event.ctrl = true,
+            "alt" => event.alt = true,
+            "shift" => event.shift = true,
+            _ => return None,
+        }
+    }
+    Some(Event::Mouse(event))
+}
+
+/// Escape backslashes and line breaks so text fits on one line
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Recording {
+        let mut rec = Recording::new(40, 10);
+        rec.push_screen(Duration::ZERO, 0xdead_beef);
+        rec.push_event(
+            Duration::from_millis(5),
+            Event::Key(KeyEvent::ctrl(Key::Char('s'))),
+        );
+        rec.push_event(
+            Duration::from_millis(6),
+            Event::Key(KeyEvent::new(Key::Char(' '))),
+        );
+        rec.push_event(
+            Duration::from_millis(7),
+            Event::Key(KeyEvent::new(Key::Char('-'))),
+        );
+        let mut mouse = MouseEvent::new(3, 4, MouseEventKind::Drag(MouseButton::Right));
+        mouse.shift = true;
+        rec.push_event(Duration::from_millis(8), Event::Mouse(mouse));
+        rec.push_event(Duration::from_millis(9), Event::Resize(100, 30));
+        rec.push_event(
+            Duration::from_millis(10),
+            Event::Paste("a\\b\nc\td".to_string()),
+        );
+        rec.push_event(Duration::from_millis(11), Event::Tick);
+        rec.push_event(Duration::from_millis(12), Event::FocusLost);
+        rec.push_screen(Duration::from_millis(12), 1);
+        rec
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let rec = sample();
+        let text = rec.to_string();
+        assert!(text.starts_with(HEADER));
+        assert!(text.contains("5 key ctrl-s\n"));
+        assert!(text.contains("8 mouse drag-right 3 4 shift\n"));
+        assert!(text.contains("10 paste a\\\\b\\nc\\td\n"));
+
+        let parsed: Recording = text.parse().unwrap();
+        assert_eq!(parsed, rec);
+        assert_eq!(parsed.screen_count(), 2);
+        assert_eq!(parsed.events().count(), 8);
+    }
+
+    #[test]
+    fn test_parse_errors_report_line() {
+        let err = "size 10 5\n0 key\n".parse::<Recording>().unwrap_err();
+        assert!(matches!(err, RecordingError::Parse { line: 2, .. }));
+
+        let err = "size 10 5\nsoon key a\n".parse::<Recording>().unwrap_err();
+        assert!(err.to_string().contains("invalid timestamp"));
+
+        let err = "0 key a\n".parse::<Recording>().unwrap_err();
+        assert!(err.to_string().contains("missing 'size'"));
+    }
+
+    #[test]
+    fn test_screen_hash_ignores_style() {
+        let mut a = Buffer::new(4, 2);
+        let mut b = Buffer::new(4, 2);
+        a.put_str(0, 0, "hi");
+        b.put_str(0, 0, "hi");
+        if let Some(cell) = b.get_mut(0, 0) {
+            cell.fg = Some(crate::style::Color::RED);
+        }
+        assert_eq!(screen_hash(&a), screen_hash(&b));
+
+        b.put_str(0, 1, "x");
+        assert_ne!(screen_hash(&a), screen_hash(&b));
+    }
+
+    #[test]
+    fn test_session_recorder_skips_unchanged_screens() {
+        let time = clock::VirtualClock::new();
+        let _guard = time.install();
+        let path = std::env::temp_dir().join("revue_recorder_skips_screens.rec");
+        let mut recorder = SessionRecorder::new(&path);
+        recorder.start(4, 1);
+
+        let buffer = Buffer::new(4, 1);
+        recorder.record_screen(&buffer);
+        time.advance_ms(20);
+        recorder.record_event(&Event::Key(KeyEvent::new(Key::Enter)));
+        recorder.record_screen(&buffer);
+
+        let entries = &recorder.recording().entries;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].at, Duration::from_millis(20));
+
+        drop(recorder);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_session_recorder_saves_on_drop() {
+        let path = std::env::temp_dir().join("revue_recorder_saves_on_drop.rec");
+        let _ = fs::remove_file(&path);
+
+        let mut recorder = SessionRecorder::new(&path);
+        recorder.start(4, 1);
+        recorder.record_event(&Event::Key(KeyEvent::new(Key::Enter)));
+        // Dropped without finish(), as when the app errors out or panics
+        drop(recorder);
+
+        let saved = Recording::load(&path).unwrap();
+        assert_eq!(saved.events().count(), 1);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_unnamed_keys_round_trip() {
+        let mut rec = Recording::new(10, 2);
+        for key in [
+            KeyEvent::new(Key::F(13)),
+            KeyEvent::ctrl(Key::F(24)),
+            KeyEvent::new(Key::Null),
+            KeyEvent::new(Key::Unknown),
+        ] {
+            rec.push_event(Duration::ZERO, Event::Key(key));
+        }
+
+        let text = rec.to_string();
+        assert!(text.contains("0 key f13\n"));
+        assert!(text.contains("0 key ctrl-f24\n"));
+        assert!(text.contains("0 key null\n"));
+        assert!(text.contains("0 key unknown\n"));
+        assert_eq!(text.parse::<Recording>().unwrap(), rec);
+    }
+}
@@ -0,0 +1,261 @@
+//! Remote devtools integration
+//!
+//! Answers the [`DevToolsServer`] requests that need the DOM, layout or
+//! stylesheet. The server itself handles signals, events and frames.
+
+use super::App;
+use crate::devtools::remote::{
+    DeclarationInfo, DevToolsServer, Message, NodeInfo, Request, RuleInfo,
+};
+use crate::devtools::StyleEdit;
+use crate::dom::DomId;
+
+impl App {
+    /// Get the remote devtools server, if one is running
+    pub fn devtools_server(&self) -> Option<&DevToolsServer> {
+        self.devtools_server.as_ref()
+    }
+
+    /// Get mutable access to the remote devtools server, e.g. to watch signals
+    pub fn devtools_server_mut(&mut self) -> Option<&mut DevToolsServer> {
+        self.devtools_server.as_mut()
+    }
+
+    /// Start, replace or stop the remote devtools server
+    pub fn set_devtools_server(&mut self, server: Option<DevToolsServer>) {
+        self.devtools_server = server;
+    }
+
+    /// Answer pending devtools requests
+    ///
+    /// Returns `true` when a style edit requires a redraw.
+    pub(crate) fn serve_devtools(&mut self) -> bool {
+        let Some(server) = self.devtools_server.as_mut() else {
+            return false;
+        };
+        let requests = server.poll();
+
+        let mut changed = false;
+        for (client, request) in requests {
+            let message = match request {
+                Request::GetTree => Message::Tree {
+                    root: self.dom.tree().root_id().and_then(|id| self.node_info(id)),
+                },
+                Request::GetNode { id } => self.node_styles(DomId::new(id)),
+                Request::SetStyle {
+                    selector,
+                    property,
+                    value,
+                } => {
+                    let edit = match value {
+                        Some(value) => StyleEdit::set(selector, property, value),
+                        None => StyleEdit::remove(selector, property),
+                    };
+                    match self.apply_style_edit(&edit) {
+                        Ok(()) => {
+                            changed = true;
+                            Message::Ok
+                        }
+                        Err(e) => Message::Error {
+                            message: e.to_string(),
+                        },
+                    }
+                }
+                other => Message::Error {
+                    message: format!("unexpected request: {:?}", other),
+                },
+            };
+            if let Some(server) = self.devtools_server.as_mut() {
+                server.reply(client, &message);
+            }
+        }
+        changed
+    }
+
+    /// Describe a node and its subtree
+    fn node_info(&self, id: DomId) -> Option<NodeInfo> {
+        let node = self.dom.tree().get(id)?;
+
+        let mut classes: Vec<String> = node.meta.classes.iter().cloned().collect();
+        classes.sort();
+
+        let state = &node.state;
+        let states = [
+            ("focus", state.focused),
+            ("hover", state.hovered),
+            ("active", state.active),
+            ("disabled", state.disabled),
+            ("selected", state.selected),
+            ("checked", state.checked),
+        ]
+        .into_iter()
+        .filter(|(_, on)| *on)
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+        Some(NodeInfo {
+            id: id.inner(),
+            widget: node.meta.widget_type.clone(),
+            element_id: node.meta.id.clone(),
+            classes,
+            states,
+            rect: self
+                .layout
+                .layout(id)
+                .ok()
+                .map(|r| [r.x, r.y, r.width, r.height]),
+            children: node
+                .children
+                .iter()
+                .filter_map(|child| self.node_info(*child))
+                .collect(),
+        })
+    }
+
+    /// Matched rules of a node and the declarations that win the cascade
+    fn node_styles(&mut self, id: DomId) -> Message {
+        if self.dom.tree().get(id).is_none() {
+            return Message::Error {
+                message: format!("no node with id {}", id.inner()),
+            };
+        }
+
+        let mut rules = Vec::new();
+        let mut computed: Vec<DeclarationInfo> = Vec::new();
+        for (rule, specificity) in self.dom.matching_rules(id) {
+            let declarations: Vec<DeclarationInfo> = rule
+                .declarations
+                .iter()
+                .map(|d| DeclarationInfo {
+                    property: d.property.clone(),
+                    value: d.value.clone(),
+                })
+                .collect();
+            for declaration in &declarations {
+                match computed
+                    .iter_mut()
+                    .find(|c| c.property == declaration.property)
+                {
+                    Some(existing) => existing.value = declaration.value.clone(),
+                    None => computed.push(declaration.clone()),
+                }
+            }
+            rules.push(RuleInfo {
+                selector: rule.selector,
+                specificity: [specificity.ids, specificity.classes, specificity.types],
+                declarations,
+            });
+        }
+
+        Message::Node {
+            id: id.inner(),
+            rules,
+            computed,
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::devtools::remote::DevToolsAddr;
+    use crate::dom::WidgetMeta;
+    use crate::style::parse_css;
+    use crate::widget::{RenderContext, View};
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+
+    struct Button;
+    impl View for Button {
+        fn render(&self, _ctx: &mut RenderContext) {}
+        fn meta(&self) -> WidgetMeta {
+            WidgetMeta::new("Button").id("ok").class("btn")
+        }
+    }
+
+    fn request(app: &mut App, stream: &mut BufReader<UnixStream>, line: &str) -> Message {
+        stream.get_mut().write_all(line.as_bytes()).unwrap();
+        stream.get_mut().write_all(b"\n").unwrap();
+        app.serve_devtools();
+        let mut reply = String::new();
+        stream.read_line(&mut reply).unwrap();
+        serde_json::from_str(&reply).unwrap()
+    }
+
+    #[test]
+    fn test_serve_devtools() {
+        let sheet = parse_css(".btn { color: red; padding: 1; } #ok { color: blue; }").unwrap();
+        let mut app = App::new_with_plugins(
+            (20, 4),
+            sheet,
+            false,
+            crate::plugin::PluginRegistry::new(),
+            false,
+        );
+        let path = std::env::temp_dir().join(format!("revue-app-test-{}.sock", std::process::id()));
+        let server = DevToolsServer::bind(&DevToolsAddr::Unix(path.clone())).unwrap();
+        app.set_devtools_server(Some(server));
+
+        let root = app.update_dom_and_get_root(&Button).unwrap();
+        app.update_layout_tree(root, 20, 4);
+
+        let stream = UnixStream::connect(&path).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let mut stream = BufReader::new(stream);
+        assert!(!app.serve_devtools());
+        assert_eq!(app.devtools_server().unwrap().client_count(), 1);
+
+        let root_info = match request(&mut app, &mut stream, r#"{"type":"get_tree"}"#) {
+            Message::Tree { root: Some(root) } => root,
+            other => panic!("expected tree, got {:?}", other),
+        };
+        assert_eq!(root_info.widget, "Button");
+        assert_eq!(root_info.element_id.as_deref(), Some("ok"));
+        assert_eq!(root_info.classes, vec!["btn".to_string()]);
+        assert_eq!(root_info.rect, Some([0, 0, 20, 4]));
+
+        let get_node = format!(r#"{{"type":"get_node","id":{}}}"#, root_info.id);
+        match request(&mut app, &mut stream, &get_node) {
+            Message::Node {
+                rules, computed, ..
+            } => {
+                let selectors: Vec<&str> = rules.iter().map(|r| r.selector.as_str()).collect();
+                assert_eq!(selectors, vec![".btn", "#ok"]);
+                assert_eq!(rules[1].specificity, [1, 0, 0]);
+                let color = computed.iter().find(|d| d.property == "color").unwrap();
+                assert_eq!(color.value, "blue");
+            }
+            other => panic!("expected node, got {:?}", other),
+        }
+
+        app.needs_force_redraw = false;
+        let set = r##"{"type":"set_style","selector":"#ok","property":"color","value":"green"}"##;
+        assert_eq!(request(&mut app, &mut stream, set), Message::Ok);
+        assert!(app.needs_force_redraw);
+        let rule = app.dom.stylesheet().rules.last().unwrap();
+        assert_eq!(rule.declarations[0].value, "green");
+
+        let remove = r#"{"type":"set_style","selector":".btn","property":"padding"}"#;
+        assert_eq!(request(&mut app, &mut stream, remove), Message::Ok);
+        assert_eq!(app.dom.stylesheet().rules[0].declarations.len(), 1);
+        assert!(matches!(
+            request(&mut app, &mut stream, remove),
+            Message::Error { .. }
+        ));
+
+        let smuggled = r#"{"type":"set_style","selector":".btn","property":"color","value":"red; } * { color: red"}"#;
+        assert!(matches!(
+            request(&mut app, &mut stream, smuggled),
+            Message::Error { .. }
+        ));
+
+        let missing = r#"{"type":"get_node","id":999999}"#;
+        assert!(matches!(
+            request(&mut app, &mut stream, missing),
+            Message::Error { .. }
+        ));
+    }
+}
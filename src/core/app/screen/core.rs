@@ -3,6 +3,7 @@
 use super::types::{
     Screen, ScreenConfig, ScreenData, ScreenEvent, ScreenId, ScreenResult, Transition,
 };
+use crate::utils::clock;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
@@ -34,7 +35,7 @@ struct TransitionState {
 impl TransitionState {
     /// Get transition progress (0.0 to 1.0)
     fn progress(&self) -> f32 {
-        let elapsed = self.start.elapsed().as_secs_f32();
+        let elapsed = clock::elapsed(self.start).as_secs_f32();
         let total = self.duration.as_secs_f32();
         (elapsed / total).min(1.0)
     }
@@ -84,7 +85,7 @@ impl ScreenManager {
     pub fn register_screen(&mut self, screen: Box<dyn Screen>) {
         let entry = StackEntry {
             screen,
-            _entered_at: Instant::now(),
+            _entered_at: clock::now(),
             visible: false,
         };
         // For direct registration, we push it but keep it hidden
@@ -141,7 +142,7 @@ impl ScreenManager {
         if config.enter_transition != Transition::None {
             self.transition = Some(TransitionState {
                 _transition: config.enter_transition,
-                start: Instant::now(),
+                start: clock::now(),
                 duration: config.transition_duration,
                 _entering: true,
             });
@@ -149,7 +150,7 @@ impl ScreenManager {
 
         self.stack.push(StackEntry {
             screen,
-            _entered_at: Instant::now(),
+            _entered_at: clock::now(),
             visible: true,
         });
 
@@ -0,0 +1,92 @@
+//! Live style edits from the devtools
+
+use super::App;
+use crate::devtools::{StyleEdit, StyleEditError};
+use crate::style::parse_css_with_source_map;
+
+impl App {
+    /// Apply a declaration edit to the live stylesheet
+    ///
+    /// The change shows on the next frame. With [`StyleEdit::write_back`] set,
+    /// the rule's stylesheet file is rewritten too; the hot reload watcher
+    /// is told to skip that write since it is already applied. The live
+    /// edit stays in place when writing back fails.
+    ///
+    /// ```rust,ignore
+    /// for edit in inspector.take_edits() {
+    ///     app.apply_style_edit(&edit)?;
+    /// }
+    /// inspector.inspect(app.dom_renderer(), node);
+    /// ```
+    pub fn apply_style_edit(&mut self, edit: &StyleEdit) -> Result<(), StyleEditError> {
+        edit.apply(&mut self.dom.stylesheet, &self.dom.source_map)?;
+        self.dom.invalidate_styles();
+        self.needs_force_redraw = true;
+        if !edit.write_back {
+            return Ok(());
+        }
+
+        let written = edit.write_source()?;
+        // Rules later in the file moved when the edited block changed size
+        if let Ok((_, source_map)) = parse_css_with_source_map(&written.contents) {
+            self.dom
+                .source_map_mut()
+                .refresh(&written.path, &source_map);
+        }
+        #[cfg(feature = "hot-reload")]
+        if let Some(hot_reload) = self.hot_reload.as_mut() {
+            hot_reload.ignore_write(&written.path, &written.contents);
+        }
+        crate::log_debug!("Wrote style edit to {:?}", written.path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_style_edit_writes_back() {
+        let path = std::env::temp_dir().join(format!("revue-app-style-{}.css", std::process::id()));
+        let css = ".a { color: red; }\n.b {\n  width: 1;\n}\n";
+        std::fs::write(&path, css).unwrap();
+        let (sheet, mut source_map) = parse_css_with_source_map(css).unwrap();
+        source_map.set_file(&path);
+        let mut app = App::new_with_plugins(
+            (20, 4),
+            sheet,
+            false,
+            crate::plugin::PluginRegistry::new(),
+            false,
+        );
+        app.dom.set_source_map(source_map);
+
+        let span = app.dom.source_map().span(0).cloned();
+        let edit = StyleEdit::set(".a", "color", "light-blue")
+            .at(span)
+            .write_back(true);
+        app.apply_style_edit(&edit).unwrap();
+        assert!(app.needs_force_redraw);
+        assert_eq!(
+            app.dom.stylesheet().rules[0].declarations[0].value,
+            "light-blue"
+        );
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(written, ".a { color: light-blue; }\n.b {\n  width: 1;\n}\n");
+        // .b moved down by the longer value and its span follows
+        let span = app.dom.source_map().span(1).cloned().unwrap();
+        assert!(written[span.range.clone()].starts_with(".b {"));
+
+        let edit = StyleEdit::remove(".b", "width")
+            .at(Some(span))
+            .write_back(true);
+        app.apply_style_edit(&edit).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            ".a { color: light-blue; }\n.b {\n}\n"
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+}
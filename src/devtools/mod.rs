@@ -8,6 +8,7 @@
 //! | [`StateDebugger`] | Reactive state viewer |
 //! | [`StyleInspector`] | CSS style inspector |
 //! | [`EventLogger`] | Event stream logger |
+//! | [`remote::DevToolsServer`] | Local socket server for `revue inspect --attach` (`devtools` feature) |
 //!
 //! # Quick Start
 //!
@@ -34,18 +35,28 @@ mod events;
 mod helpers;
 mod inspector;
 mod profiler;
+#[cfg(feature = "devtools")]
+pub mod remote;
 mod state;
 mod style;
 mod time_travel;
 
 pub use events::{EventFilter, EventLogger, EventType, LoggedEvent};
 pub use inspector::{ComponentPicker, Inspector, InspectorConfig, PickerMode, WidgetNode};
-pub use profiler::{ComponentStats, Frame, Profiler, ProfilerView, RenderEvent, RenderReason};
+#[cfg(feature = "tracing")]
+pub use profiler::ProfilerLayer;
+pub use profiler::{
+    ComponentStats, Frame, FramePhase, PhaseTiming, Profiler, ProfilerView, RenderEvent,
+    RenderReason, TraceSpan,
+};
 pub use state::{StateDebugger, StateEntry, StateValue};
-pub use style::{ComputedProperty, PropertySource, StyleCategory, StyleInspector};
+pub use style::{
+    ComputedProperty, PropertySource, StyleCategory, StyleEdit, StyleEditError, StyleInspector,
+    WrittenSource,
+};
 pub use time_travel::{
     Action, SnapshotValue, StateDiff, StateSnapshot, TimeTravelConfig, TimeTravelDebugger,
-    TimeTravelView,
+    TimeTravelRecorder, TimeTravelValue, TimeTravelView, TrackTimeTravel,
 };
 
 use crate::layout::Rect;
@@ -2,7 +2,11 @@
 
 use super::super::helpers::draw_text_overlay;
 use super::super::DevToolsConfig;
-use super::types::{ComponentStats, Frame, ProfilerView, RenderEvent};
+use super::export::frame_intervals;
+use super::spans;
+use super::types::{
+    ComponentStats, Frame, FramePhase, PhaseTiming, ProfilerView, RenderEvent, TraceSpan,
+};
 use crate::layout::Rect;
 use crate::render::Buffer;
 use crate::style::Color;
@@ -10,11 +14,15 @@ use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
 /// Performance profiler for tracking render performance
+///
+/// The app records into its own profiler every frame while it is recording
+/// (see [`App::frame_profiler_mut`](crate::core::app::App::frame_profiler_mut)),
+/// including per-phase timings, widget renders and bytes written.
 pub struct Profiler {
     /// Is recording
     recording: bool,
     /// Recorded frames
-    frames: Vec<Frame>,
+    pub(super) frames: Vec<Frame>,
     /// Current frame (while recording)
     current_frame: Option<Frame>,
     /// Component statistics
@@ -49,6 +57,9 @@ impl Profiler {
 
     /// Start recording
     pub fn start_recording(&mut self) {
+        if !self.recording {
+            spans::start_collecting();
+        }
         self.recording = true;
         self.recording_start = Some(Instant::now());
         self.frames.clear();
@@ -60,6 +71,9 @@ impl Profiler {
     /// Stop recording
     pub fn stop_recording(&mut self) {
         self.end_frame();
+        if self.recording {
+            spans::stop_collecting();
+        }
         self.recording = false;
         self.recording_start = None;
     }
@@ -78,22 +92,65 @@ impl Profiler {
         }
     }
 
-    /// Start a new frame
+    /// Start a new frame, ending the current one
+    ///
+    /// A current frame with nothing recorded in it is restarted instead.
     pub fn start_frame(&mut self) {
-        if self.recording {
-            self.frame_counter += 1;
-            self.current_frame = Some(Frame::new(self.frame_counter));
+        if !self.recording {
+            return;
+        }
+        if let Some(frame) = &self.current_frame {
+            if frame.events.is_empty() && frame.phases.is_empty() && frame.spans.is_empty() {
+                self.current_frame = Some(Frame::new(frame.number));
+                return;
+            }
         }
+        self.end_frame();
+        self.frame_counter += 1;
+        self.current_frame = Some(Frame::new(self.frame_counter));
     }
 
     /// End the current frame
+    ///
+    /// `tracing` spans closed since the previous frame ended are added to it.
     pub fn end_frame(&mut self) {
         if let Some(mut frame) = self.current_frame.take() {
             frame.end();
+            frame.spans.extend(spans::take_spans());
             self.frames.push(frame);
         }
     }
 
+    /// Record time spent in a pipeline phase that started at `start`
+    pub fn record_phase(&mut self, phase: FramePhase, start: Instant) {
+        if let Some(frame) = &mut self.current_frame {
+            frame.phases.push(PhaseTiming {
+                phase,
+                start,
+                duration: start.elapsed(),
+            });
+        }
+    }
+
+    /// Record bytes written to the terminal
+    pub fn record_bytes_written(&mut self, bytes: u64) {
+        if let Some(frame) = &mut self.current_frame {
+            frame.bytes_written += bytes;
+        }
+    }
+
+    /// Record a span in the current frame
+    pub fn record_span(&mut self, span: TraceSpan) {
+        if let Some(frame) = &mut self.current_frame {
+            frame.spans.push(span);
+        }
+    }
+
+    /// Get recorded frames
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
     /// Record a render event
     pub fn record_render(&mut self, event: RenderEvent) {
         // Update stats
@@ -216,50 +273,51 @@ impl Profiler {
             .and_then(|i| self.frames.get(i))
             .or_else(|| self.frames.last());
 
-        if let Some(frame) = frame {
-            let content_y = area.y + 2;
-            let content_height = area.height.saturating_sub(3);
+        let Some(frame) = frame else {
+            return;
+        };
+        let summary = format!("Frame {}: {}", frame.number, phase_summary(frame));
+        self.render_text(buffer, area.x, area.y + 1, &summary, config.accent_color);
 
-            // Group events by depth for flamegraph rows
-            let max_depth = frame.events.iter().map(|e| e.depth).max().unwrap_or(0);
-            let row_height = if max_depth > 0 {
-                (content_height as usize / (max_depth + 1)).max(1)
-            } else {
-                content_height as usize
-            };
+        // One row per nesting depth, bars placed by offset into the frame
+        let intervals = frame_intervals(frame);
+        let span = intervals
+            .iter()
+            .map(|i| i.start + i.duration)
+            .max()
+            .unwrap_or_default()
+            .max(frame.duration)
+            .as_nanos()
+            .max(1) as f64;
+        let content_y = area.y + 2;
+        let rows = area.height.saturating_sub(2) as usize;
+        let scale = |d: Duration| (d.as_nanos() as f64 / span * area.width as f64) as u16;
 
-            for event in &frame.events {
-                let y = content_y + (event.depth * row_height) as u16;
-                if y >= area.y + area.height {
-                    continue;
-                }
+        for interval in &intervals {
+            let Some(row) = interval.depth.checked_sub(self.scroll_offset) else {
+                continue;
+            };
+            if row >= rows {
+                continue;
+            }
+            let y = content_y + row as u16;
+            let x = area.x + scale(interval.start).min(area.width.saturating_sub(1));
+            let width = scale(interval.duration).max(1).min(area.x + area.width - x);
 
-                // Calculate bar width based on duration
-                let total_time = frame.total_render_time().as_nanos() as f64;
-                let event_time = event.duration.as_nanos() as f64;
-                let width = if total_time > 0.0 {
-                    ((event_time / total_time) * area.width as f64) as u16
-                } else {
-                    1
-                };
-                let width = width.max(1).min(area.width);
-
-                // Draw bar
-                let color = event.reason.color();
-                for x in area.x..area.x + width {
-                    if let Some(cell) = buffer.get_mut(x, y) {
-                        cell.bg = Some(color);
-                    }
+            for bx in x..x + width {
+                if let Some(cell) = buffer.get_mut(bx, y) {
+                    cell.symbol = ' ';
+                    cell.bg = Some(interval.color);
                 }
-
-                // Draw label
-                let label = format!(
-                    "{} ({:.2}ms)",
-                    event.component,
-                    event.duration.as_secs_f64() * 1000.0
-                );
-                self.render_text(buffer, area.x, y, &label, config.bg_color);
             }
+
+            let label = format!(
+                "{} ({:.2}ms)",
+                interval.name,
+                interval.duration.as_secs_f64() * 1000.0
+            );
+            let label: String = label.chars().take(width as usize).collect();
+            self.render_text(buffer, x, y, &label, config.bg_color);
         }
     }
 
@@ -336,10 +394,11 @@ impl Profiler {
         if let Some(idx) = self.selected_frame {
             if let Some(frame) = self.frames.get(idx) {
                 let info = format!(
-                    "Frame {}: {:.2}ms, {} renders",
+                    "Frame {}: {:.2}ms, {} renders  {}",
                     frame.number,
                     frame.duration.as_secs_f64() * 1000.0,
-                    frame.event_count()
+                    frame.event_count(),
+                    phase_summary(frame)
                 );
                 self.render_text(
                     buffer,
@@ -464,6 +523,33 @@ impl Profiler {
     }
 }
 
+/// Per-phase times and bytes written, e.g. `Layout 0.20ms  Write 0.10ms  512B`
+fn phase_summary(frame: &Frame) -> String {
+    let mut parts: Vec<String> = FramePhase::all()
+        .iter()
+        .filter(|phase| frame.phases.iter().any(|p| p.phase == **phase))
+        .map(|phase| {
+            format!(
+                "{} {:.2}ms",
+                phase.label(),
+                frame.phase_time(*phase).as_secs_f64() * 1000.0
+            )
+        })
+        .collect();
+    if frame.bytes_written > 0 {
+        parts.push(format!("{}B", frame.bytes_written));
+    }
+    parts.join("  ")
+}
+
+impl Drop for Profiler {
+    fn drop(&mut self) {
+        if self.recording {
+            spans::stop_collecting();
+        }
+    }
+}
+
 impl Default for Profiler {
     fn default() -> Self {
         Self::new()
@@ -703,6 +789,34 @@ mod tests {
         assert_eq!(profiler.recording_duration(), Duration::ZERO);
     }
 
+    #[test]
+    fn test_flamegraph_nests_widgets_in_phases() {
+        let mut profiler = Profiler::new();
+        profiler.start_recording();
+        let start = Instant::now();
+        let mut event = RenderEvent::new("Stack", Duration::from_millis(5));
+        event.timestamp = start;
+        profiler.record_phase(FramePhase::Render, start);
+        profiler.record_render(event);
+        if let Some(frame) = &mut profiler.current_frame {
+            frame.phases[0].duration = Duration::from_millis(10);
+            frame.bytes_written = 64;
+        }
+        profiler.stop_recording();
+
+        let mut buffer = Buffer::new(60, 6);
+        let area = Rect::new(0, 0, 60, 6);
+        profiler.render_content(&mut buffer, area, &DevToolsConfig::default());
+        let row = |y: u16| -> String {
+            (0..60)
+                .map(|x| buffer.get(x, y).map(|c| c.symbol).unwrap_or(' '))
+                .collect()
+        };
+        assert!(row(1).starts_with("Frame 1: Render 10.00ms  64B"));
+        assert!(row(2).starts_with("Render (10.00ms)"));
+        assert!(row(3).starts_with("Stack (5.00ms)"));
+    }
+
     #[test]
     fn test_avg_frame_time_empty() {
         let profiler = Profiler::new();
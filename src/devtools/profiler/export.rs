@@ -0,0 +1,302 @@
+//! Offline export of recorded frames
+//!
+//! - [`Profiler::to_chrome_trace`]: Chrome trace-event JSON, for
+//!   `chrome://tracing`, Perfetto or speedscope
+//! - [`Profiler::to_folded`]: folded stacks, for `flamegraph.pl` or inferno
+
+use super::core::Profiler;
+use super::types::Frame;
+use crate::style::Color;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Color of `tracing` spans in the flame view
+const SPAN_COLOR: Color = Color::rgb(150, 150, 180);
+
+/// A timed piece of a frame: a phase, widget render or `tracing` span
+#[derive(Debug, Clone)]
+pub(super) struct Interval {
+    /// Display name
+    pub(super) name: String,
+    /// Trace-event category
+    pub(super) category: String,
+    /// Offset from the frame start
+    pub(super) start: Duration,
+    /// Time spent
+    pub(super) duration: Duration,
+    /// Nesting depth (0 for outermost)
+    pub(super) depth: usize,
+    /// Index of the enclosing interval
+    pub(super) parent: Option<usize>,
+    /// Color in the flame view
+    pub(super) color: Color,
+}
+
+impl Interval {
+    fn end(&self) -> Duration {
+        self.start + self.duration
+    }
+}
+
+/// All intervals of a frame in start order, nested by time
+///
+/// An interval that starts before the previous one ends is treated as its
+/// child, so widget renders nest inside the render phase and inside their
+/// parents without needing explicit links.
+pub(super) fn frame_intervals(frame: &Frame) -> Vec<Interval> {
+    let offset = |at: Instant| at.saturating_duration_since(frame.start_time);
+    let mut intervals: Vec<Interval> = Vec::new();
+    for phase in &frame.phases {
+        intervals.push(Interval {
+            name: phase.phase.label().to_string(),
+            category: "phase".to_string(),
+            start: offset(phase.start),
+            duration: phase.duration,
+            depth: 0,
+            parent: None,
+            color: phase.phase.color(),
+        });
+    }
+    for event in &frame.events {
+        intervals.push(Interval {
+            name: event.component.clone(),
+            category: "widget".to_string(),
+            start: offset(event.timestamp),
+            duration: event.duration,
+            depth: 0,
+            parent: None,
+            color: event.reason.color(),
+        });
+    }
+    for span in &frame.spans {
+        intervals.push(Interval {
+            name: span.name.clone(),
+            category: span.target.clone(),
+            start: offset(span.start),
+            duration: span.duration,
+            depth: 0,
+            parent: None,
+            color: SPAN_COLOR,
+        });
+    }
+
+    // Longer first on ties so parents come before children starting with them
+    intervals.sort_by(|a, b| a.start.cmp(&b.start).then(b.duration.cmp(&a.duration)));
+    let mut open: Vec<usize> = Vec::new();
+    for i in 0..intervals.len() {
+        while let Some(&last) = open.last() {
+            if intervals[last].end() > intervals[i].start {
+                break;
+            }
+            open.pop();
+        }
+        intervals[i].depth = open.len();
+        intervals[i].parent = open.last().copied();
+        open.push(i);
+    }
+    intervals
+}
+
+/// Microseconds with sub-microsecond precision, as trace events expect
+fn micros(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1_000_000.0)
+}
+
+/// Quote and escape a string for JSON
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl Profiler {
+    /// Export recorded frames as Chrome trace-event JSON
+    ///
+    /// Every frame, phase, widget render and `tracing` span becomes a
+    /// complete (`"ph": "X"`) event. Timestamps are relative to the first
+    /// recorded frame.
+    pub fn to_chrome_trace(&self) -> String {
+        let mut events = Vec::new();
+        if let Some(origin) = self.frames().first().map(|f| f.start_time) {
+            for frame in self.frames() {
+                let frame_start = frame.start_time.saturating_duration_since(origin);
+                events.push(format!(
+                    "{{\"name\":{},\"cat\":\"frame\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":1,\"tid\":1,\"args\":{{\"bytes_written\":{}}}}}",
+                    json_string(&format!("Frame {}", frame.number)),
+                    micros(frame_start),
+                    micros(frame.duration),
+                    frame.bytes_written
+                ));
+                for interval in frame_intervals(frame) {
+                    events.push(format!(
+                        "{{\"name\":{},\"cat\":{},\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":1,\"tid\":1}}",
+                        json_string(&interval.name),
+                        json_string(&interval.category),
+                        micros(frame_start + interval.start),
+                        micros(interval.duration)
+                    ));
+                }
+            }
+        }
+
+        let mut json = String::from("{\"displayTimeUnit\":\"ms\",\"traceEvents\":[\n");
+        json.push_str(&events.join(",\n"));
+        json.push_str("\n]}\n");
+        json
+    }
+
+    /// Export recorded frames as folded stacks
+    ///
+    /// One `frame;Render;Stack;Text 42` line per distinct stack, weighted by
+    /// self time in microseconds and summed over all frames.
+    pub fn to_folded(&self) -> String {
+        let mut stacks: BTreeMap<String, u128> = BTreeMap::new();
+        for frame in self.frames() {
+            let intervals = frame_intervals(frame);
+            let mut paths: Vec<String> = Vec::with_capacity(intervals.len());
+            let mut self_times: Vec<Duration> = intervals.iter().map(|i| i.duration).collect();
+            let mut frame_self = frame.duration;
+
+            for interval in &intervals {
+                let name = interval.name.replace(';', ":");
+                let path = match interval.parent {
+                    Some(parent) => {
+                        self_times[parent] = self_times[parent].saturating_sub(interval.duration);
+                        format!("{};{}", paths[parent], name)
+                    }
+                    None => {
+                        frame_self = frame_self.saturating_sub(interval.duration);
+                        format!("frame;{}", name)
+                    }
+                };
+                paths.push(path);
+            }
+
+            *stacks.entry("frame".to_string()).or_default() += frame_self.as_micros();
+            for (path, time) in paths.into_iter().zip(self_times) {
+                *stacks.entry(path).or_default() += time.as_micros();
+            }
+        }
+
+        stacks
+            .into_iter()
+            .filter(|(_, micros)| *micros > 0)
+            .map(|(path, micros)| format!("{} {}\n", path, micros))
+            .collect()
+    }
+
+    /// Write [`to_chrome_trace`](Self::to_chrome_trace) to a file
+    pub fn save_chrome_trace(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_chrome_trace())
+    }
+
+    /// Write [`to_folded`](Self::to_folded) to a file
+    pub fn save_folded(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_folded())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::devtools::profiler::types::{FramePhase, PhaseTiming, RenderEvent, TraceSpan};
+
+    fn frame() -> Frame {
+        let mut frame = Frame::new(1);
+        let at = |us| frame.start_time + Duration::from_micros(us);
+        let phase = |phase, start, us| PhaseTiming {
+            phase,
+            start,
+            duration: Duration::from_micros(us),
+        };
+        let widget = |name, start, us, depth| {
+            let mut event = RenderEvent::new(name, Duration::from_micros(us)).depth(depth);
+            event.timestamp = start;
+            event
+        };
+
+        frame.phases = vec![
+            phase(FramePhase::Layout, at(0), 100),
+            phase(FramePhase::Render, at(100), 300),
+        ];
+        // Recorded in the order widgets finish
+        frame.events = vec![
+            widget("Text", at(120), 50, 1),
+            widget("Stack", at(110), 250, 0),
+        ];
+        frame.spans = vec![TraceSpan {
+            name: "load \"data\"".to_string(),
+            target: "app".to_string(),
+            start: at(500),
+            duration: Duration::from_micros(200),
+        }];
+        frame.duration = Duration::from_micros(1000);
+        frame
+    }
+
+    #[test]
+    fn test_frame_intervals_nest_by_time() {
+        let intervals = frame_intervals(&frame());
+        let shape: Vec<_> = intervals
+            .iter()
+            .map(|i| (i.name.as_str(), i.depth, i.parent))
+            .collect();
+        assert_eq!(
+            shape,
+            vec![
+                ("Layout", 0, None),
+                ("Render", 0, None),
+                ("Stack", 1, Some(1)),
+                ("Text", 2, Some(2)),
+                ("load \"data\"", 0, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_folded_uses_self_time() {
+        let mut profiler = Profiler::new();
+        profiler.frames.push(frame());
+        assert_eq!(
+            profiler.to_folded(),
+            "frame 400\n\
+             frame;Layout 100\n\
+             frame;Render 50\n\
+             frame;Render;Stack 200\n\
+             frame;Render;Stack;Text 50\n\
+             frame;load \"data\" 200\n"
+        );
+    }
+
+    #[test]
+    fn test_chrome_trace_events() {
+        let mut profiler = Profiler::new();
+        profiler.frames.push(frame());
+        let json = profiler.to_chrome_trace();
+        assert!(json.starts_with("{\"displayTimeUnit\":\"ms\",\"traceEvents\":["));
+        assert!(json.contains(
+            "{\"name\":\"Frame 1\",\"cat\":\"frame\",\"ph\":\"X\",\"ts\":0.000,\"dur\":1000.000"
+        ));
+        assert!(json.contains(
+            "{\"name\":\"Text\",\"cat\":\"widget\",\"ph\":\"X\",\"ts\":120.000,\"dur\":50.000"
+        ));
+        assert!(json.contains("\"name\":\"load \\\"data\\\"\",\"cat\":\"app\""));
+        assert_eq!(json.matches("\"ph\":\"X\"").count(), 6);
+    }
+}
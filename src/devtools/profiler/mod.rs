@@ -10,6 +10,9 @@
 //! | Timeline | Frame-by-frame rendering timeline |
 //! | Ranked View | Components sorted by render time |
 //! | Render Counts | Track component render frequency |
+//! | Phases | Per-frame build, cascade, layout, render, diff and write times |
+//! | Export | Chrome trace-event JSON and folded stacks |
+//! | `tracing` spans | [`ProfilerLayer`] adds your spans to the timeline (`tracing` feature) |
 //!
 //! # Example
 //!
@@ -24,9 +27,24 @@
 //! profiler.stop_recording();
 //! let report = profiler.generate_report();
 //! ```
+//!
+//! # Profiling an app
+//!
+//! ```rust,ignore
+//! app.frame_profiler_mut().start_recording();
+//! // ... run some frames ...
+//! let profiler = app.frame_profiler_mut();
+//! profiler.stop_recording();
+//! profiler.save_chrome_trace("frames.json")?;
+//! profiler.save_folded("frames.folded")?;
+//! ```
 
 mod core;
+mod export;
+mod spans;
 mod types;
 
 pub use core::Profiler;
+#[cfg(feature = "tracing")]
+pub use spans::ProfilerLayer;
 pub use types::*;
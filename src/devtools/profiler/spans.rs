@@ -0,0 +1,117 @@
+//! `tracing` span collection for the frame profiler
+//!
+//! [`ProfilerLayer`] times spans on any thread while a [`Profiler`] is
+//! recording; the profiler moves them into the frame that is open when it
+//! next ends a frame.
+//!
+//! [`Profiler`]: super::Profiler
+
+use super::types::TraceSpan;
+use crate::utils::lock::lock_or_recover;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Number of profilers currently recording
+static RECORDING: AtomicUsize = AtomicUsize::new(0);
+static SPANS: Mutex<Vec<TraceSpan>> = Mutex::new(Vec::new());
+
+/// Note that a profiler started recording
+pub(super) fn start_collecting() {
+    RECORDING.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Note that a profiler stopped recording
+///
+/// Spans not yet taken are dropped once no profiler is recording.
+pub(super) fn stop_collecting() {
+    if RECORDING.fetch_sub(1, Ordering::Relaxed) == 1 {
+        lock_or_recover(&SPANS).clear();
+    }
+}
+
+#[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+fn collecting() -> bool {
+    RECORDING.load(Ordering::Relaxed) > 0
+}
+
+/// Take the spans closed since the last call
+pub(super) fn take_spans() -> Vec<TraceSpan> {
+    std::mem::take(&mut *lock_or_recover(&SPANS))
+}
+
+/// Add a closed span if collecting
+#[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+pub(super) fn push_span(span: TraceSpan) {
+    if collecting() {
+        lock_or_recover(&SPANS).push(span);
+    }
+}
+
+#[cfg(feature = "tracing")]
+pub use layer::ProfilerLayer;
+
+#[cfg(feature = "tracing")]
+mod layer {
+    use super::{collecting, push_span};
+    use crate::devtools::profiler::types::TraceSpan;
+    use std::time::Instant;
+    use tracing::span::Id;
+    use tracing::Subscriber;
+    use tracing_subscriber::layer::Context;
+    use tracing_subscriber::registry::LookupSpan;
+    use tracing_subscriber::Layer;
+
+    /// `tracing` layer that adds spans to the frame profiler timeline
+    ///
+    /// ```rust,ignore
+    /// use tracing_subscriber::prelude::*;
+    ///
+    /// tracing_subscriber::registry()
+    ///     .with(ProfilerLayer::new())
+    ///     .init();
+    /// ```
+    ///
+    /// Each time a span is entered and exited counts as one span in the
+    /// timeline. Nothing is recorded unless a profiler is recording.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct ProfilerLayer;
+
+    impl ProfilerLayer {
+        /// Create a new layer
+        pub fn new() -> Self {
+            Self
+        }
+    }
+
+    /// When the span was entered, stored in the span's extensions
+    struct Entered(Instant);
+
+    impl<S> Layer<S> for ProfilerLayer
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+            if !collecting() {
+                return;
+            }
+            if let Some(span) = ctx.span(id) {
+                span.extensions_mut().replace(Entered(Instant::now()));
+            }
+        }
+
+        fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+            let Some(span) = ctx.span(id) else {
+                return;
+            };
+            let Some(Entered(start)) = span.extensions_mut().remove::<Entered>() else {
+                return;
+            };
+            push_span(TraceSpan {
+                name: span.name().to_string(),
+                target: span.metadata().target().to_string(),
+                start,
+                duration: start.elapsed(),
+            });
+        }
+    }
+}
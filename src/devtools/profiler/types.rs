@@ -143,6 +143,87 @@ impl RenderReason {
     }
 }
 
+/// Stage of the app's render pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FramePhase {
+    /// Building (reconciling) the DOM from the view tree
+    Build,
+    /// Computing styles from the stylesheet cascade
+    Cascade,
+    /// Computing layout
+    Layout,
+    /// Rendering widgets into the buffer
+    Render,
+    /// Diffing against the previous buffer
+    Diff,
+    /// Writing changes to the terminal
+    Write,
+}
+
+impl FramePhase {
+    /// Get display label
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Build => "Build",
+            Self::Cascade => "Cascade",
+            Self::Layout => "Layout",
+            Self::Render => "Render",
+            Self::Diff => "Diff",
+            Self::Write => "Write",
+        }
+    }
+
+    /// Get all phases in pipeline order
+    pub fn all() -> &'static [FramePhase] {
+        &[
+            FramePhase::Build,
+            FramePhase::Cascade,
+            FramePhase::Layout,
+            FramePhase::Render,
+            FramePhase::Diff,
+            FramePhase::Write,
+        ]
+    }
+
+    /// Get color for visualization
+    pub fn color(&self) -> Color {
+        match self {
+            Self::Build => Color::rgb(100, 150, 220),
+            Self::Cascade => Color::rgb(180, 100, 220),
+            Self::Layout => Color::rgb(220, 180, 100),
+            Self::Render => Color::rgb(100, 180, 100),
+            Self::Diff => Color::rgb(100, 200, 200),
+            Self::Write => Color::rgb(220, 100, 100),
+        }
+    }
+}
+
+/// Time spent in one phase of a frame
+#[derive(Debug, Clone)]
+pub struct PhaseTiming {
+    /// Pipeline phase
+    pub phase: FramePhase,
+    /// When the phase started
+    pub start: Instant,
+    /// Time spent
+    pub duration: Duration,
+}
+
+/// A `tracing` span closed during a frame
+///
+/// Collected by [`ProfilerLayer`](super::ProfilerLayer) (`tracing` feature).
+#[derive(Debug, Clone)]
+pub struct TraceSpan {
+    /// Span name
+    pub name: String,
+    /// Span target (usually the module path)
+    pub target: String,
+    /// When the span was entered
+    pub start: Instant,
+    /// Time between entering and exiting the span
+    pub duration: Duration,
+}
+
 /// A frame in the timeline
 #[derive(Debug, Clone)]
 pub struct Frame {
@@ -154,6 +235,12 @@ pub struct Frame {
     pub events: Vec<RenderEvent>,
     /// Frame start time
     pub start_time: Instant,
+    /// Time spent in each pipeline phase
+    pub phases: Vec<PhaseTiming>,
+    /// `tracing` spans closed during the frame
+    pub spans: Vec<TraceSpan>,
+    /// Bytes written to the terminal
+    pub bytes_written: u64,
 }
 
 impl Frame {
@@ -164,6 +251,9 @@ impl Frame {
             duration: Duration::ZERO,
             events: Vec::new(),
             start_time: Instant::now(),
+            phases: Vec::new(),
+            spans: Vec::new(),
+            bytes_written: 0,
         }
     }
 
@@ -172,6 +262,15 @@ impl Frame {
         self.events.push(event);
     }
 
+    /// Get total time spent in a phase
+    pub fn phase_time(&self, phase: FramePhase) -> Duration {
+        self.phases
+            .iter()
+            .filter(|p| p.phase == phase)
+            .map(|p| p.duration)
+            .sum()
+    }
+
     /// End the frame and calculate duration
     pub fn end(&mut self) {
         self.duration = self.start_time.elapsed();
@@ -377,6 +476,28 @@ mod tests {
         assert_eq!(frame.event_count(), 2);
     }
 
+    #[test]
+    fn test_frame_phase_time() {
+        let mut frame = Frame::new(1);
+        for (phase, ms) in [
+            (FramePhase::Layout, 2),
+            (FramePhase::Render, 3),
+            (FramePhase::Layout, 1),
+        ] {
+            frame.phases.push(PhaseTiming {
+                phase,
+                start: Instant::now(),
+                duration: Duration::from_millis(ms),
+            });
+        }
+        assert_eq!(
+            frame.phase_time(FramePhase::Layout),
+            Duration::from_millis(3)
+        );
+        assert_eq!(frame.phase_time(FramePhase::Write), Duration::ZERO);
+        assert_eq!(FramePhase::all().len(), 6);
+    }
+
     #[test]
     fn test_component_stats_new() {
         let stats = ComponentStats::new("TestComponent");
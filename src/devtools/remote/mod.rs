@@ -0,0 +1,754 @@
+//! Remote devtools over a local socket
+//!
+//! A [`DevToolsServer`] exposes a running app to external tools such as
+//! `revue inspect --attach`: the DOM tree, the rules matched by each node,
+//! watched signals, input events and frame timings, plus live style edits.
+//! It listens on a Unix domain socket or a loopback TCP port and speaks
+//! newline-delimited JSON (see [`protocol`]).
+//!
+//! The server never blocks and owns no threads. [`App::run()`] polls it once
+//! per loop iteration, answering DOM and style requests between frames, so
+//! the app's state is never touched from another thread.
+//!
+//! # Enabling
+//!
+//! ```rust,ignore
+//! // Explicit address
+//! let app = App::builder().devtools_server("unix:/tmp/my-app.sock").build();
+//!
+//! // Or without code changes: REVUE_DEVTOOLS=1 cargo run
+//! // (listens on $TMPDIR/revue-devtools-<pid>.sock)
+//! ```
+//!
+//! Watch signals to make them visible to clients:
+//!
+//! ```rust,ignore
+//! let count = signal(0);
+//! if let Some(server) = app.devtools_server_mut() {
+//!     server.watch_signal("count", &count);
+//! }
+//! ```
+//!
+//! # Access control
+//!
+//! Unix sockets are created owner-only (`0600`), so only the user running
+//! the app can connect. TCP sockets are reachable by every local user, so
+//! the server generates a token (or takes it from `REVUE_DEVTOOLS_TOKEN`),
+//! prints it to stderr at startup, and drops TCP clients whose first
+//! request is not [`Request::Auth`] with that token.
+//!
+//! [`App::run()`]: crate::core::app::App::run
+
+pub mod protocol;
+mod transport;
+
+pub use protocol::{
+    DeclarationInfo, EventInfo, FrameInfo, Message, NodeInfo, Request, RuleInfo, SignalInfo, Topic,
+    PROTOCOL_VERSION,
+};
+pub use transport::{DevToolsAddr, SOCKET_PREFIX};
+
+use crate::event::Event;
+use crate::reactive::Signal;
+use crate::utils::clock;
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Debug;
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant};
+use transport::{Listener, Stream};
+
+/// Environment variable that starts the server: `1`/`auto` for the default
+/// address, or any address accepted by [`DevToolsAddr`]
+pub const DEVTOOLS_ENV_VAR: &str = "REVUE_DEVTOOLS";
+
+/// Environment variable that fixes the TCP token instead of generating one
+pub const DEVTOOLS_TOKEN_ENV_VAR: &str = "REVUE_DEVTOOLS_TOKEN";
+
+/// Events and frames kept for `get_events` / `get_frames`
+const HISTORY_LEN: usize = 256;
+
+/// Requests longer than this are rejected and the client is dropped
+const MAX_LINE_LEN: usize = 1 << 20;
+
+/// Clients that let this much output pile up are dropped
+const MAX_OUTBOX_LEN: usize = 16 << 20;
+
+/// Type alias for signal watch callbacks
+type WatchFn = Box<dyn Fn() -> String>;
+
+/// Identifies a connected client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClientId(u64);
+
+struct Client {
+    id: ClientId,
+    stream: Stream,
+    inbox: Vec<u8>,
+    outbox: Vec<u8>,
+    topics: HashSet<Topic>,
+    authenticated: bool,
+    closed: bool,
+}
+
+impl Client {
+    fn send(&mut self, message: &Message) {
+        match serde_json::to_vec(message) {
+            Ok(mut line) => {
+                line.push(b'\n');
+                self.outbox.extend_from_slice(&line);
+            }
+            Err(e) => crate::log_warn!("devtools: failed to encode message: {}", e),
+        }
+    }
+
+    /// Read everything available and split it into complete lines
+    fn read_lines(&mut self) -> Vec<String> {
+        let mut buf = [0u8; 4096];
+        loop {
+            match self.stream.read(&mut buf) {
+                Ok(0) => {
+                    self.closed = true;
+                    break;
+                }
+                Ok(n) => self.inbox.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(_) => {
+                    self.closed = true;
+                    break;
+                }
+            }
+        }
+
+        let mut lines = Vec::new();
+        while let Some(end) = self.inbox.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.inbox.drain(..=end).collect();
+            lines.push(String::from_utf8_lossy(&line).trim().to_string());
+        }
+        if self.inbox.len() > MAX_LINE_LEN {
+            self.closed = true;
+        }
+        lines
+    }
+
+    fn flush(&mut self) {
+        while !self.outbox.is_empty() {
+            match self.stream.write(&self.outbox) {
+                Ok(0) => {
+                    self.closed = true;
+                    return;
+                }
+                Ok(n) => {
+                    self.outbox.drain(..n);
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(_) => {
+                    self.closed = true;
+                    return;
+                }
+            }
+        }
+        if self.outbox.len() > MAX_OUTBOX_LEN {
+            self.closed = true;
+        }
+    }
+}
+
+/// Devtools server for external inspectors
+///
+/// Handles connection bookkeeping, signals, events, frames and
+/// subscriptions itself; [`poll()`](Self::poll) hands the requests that
+/// need the DOM or stylesheet back to the caller, which answers them with
+/// [`reply()`](Self::reply).
+pub struct DevToolsServer {
+    listener: Listener,
+    addr: DevToolsAddr,
+    token: Option<String>,
+    clients: Vec<Client>,
+    next_client: u64,
+    started: Instant,
+    events: VecDeque<EventInfo>,
+    frames: VecDeque<FrameInfo>,
+    frame_count: u64,
+    watches: Vec<(String, WatchFn)>,
+    last_signals: Vec<String>,
+}
+
+impl DevToolsServer {
+    /// Listen on `addr`
+    ///
+    /// TCP addresses must be loopback. Port 0 picks a free port; see
+    /// [`addr()`](Self::addr) for the one chosen. TCP servers require the
+    /// [`token()`](Self::token) from every client.
+    pub fn bind(addr: &DevToolsAddr) -> io::Result<Self> {
+        let (listener, addr) = Listener::bind(addr)?;
+        let token = match addr {
+            DevToolsAddr::Tcp(_) => Some(
+                std::env::var(DEVTOOLS_TOKEN_ENV_VAR)
+                    .ok()
+                    .filter(|token| !token.is_empty())
+                    .unwrap_or_else(generate_token),
+            ),
+            #[cfg(unix)]
+            DevToolsAddr::Unix(_) => None,
+        };
+        Ok(Self {
+            listener,
+            addr,
+            token,
+            clients: Vec::new(),
+            next_client: 0,
+            started: clock::now(),
+            events: VecDeque::new(),
+            frames: VecDeque::new(),
+            frame_count: 0,
+            watches: Vec::new(),
+            last_signals: Vec::new(),
+        })
+    }
+
+    /// Start a server from the `REVUE_DEVTOOLS` environment variable
+    ///
+    /// Returns `None` when the variable is unset, empty or `0`; see
+    /// [`start()`](Self::start) for the accepted values.
+    pub fn from_env() -> Option<Self> {
+        Self::start(&std::env::var(DEVTOOLS_ENV_VAR).ok()?)
+    }
+
+    /// Start a server from an address string, logging failures
+    ///
+    /// `1`, `true` and `auto` select
+    /// [`DevToolsAddr::default_for_process()`]; an empty string, `0` and
+    /// `false` start nothing. The token of a TCP server is printed to
+    /// stderr.
+    pub fn start(spec: &str) -> Option<Self> {
+        let addr = match spec.trim() {
+            "" | "0" | "false" => return None,
+            "1" | "true" | "auto" => DevToolsAddr::default_for_process(),
+            other => match other.parse() {
+                Ok(addr) => addr,
+                Err(e) => {
+                    crate::log_warn!("Invalid devtools address: {}", e);
+                    return None;
+                }
+            },
+        };
+        match Self::bind(&addr) {
+            Ok(server) => {
+                match server.token() {
+                    Some(token) => eprintln!(
+                        "Revue devtools listening on {} (token: {})",
+                        server.addr(),
+                        token
+                    ),
+                    None => crate::log_debug!("Devtools server listening on {}", server.addr()),
+                }
+                Some(server)
+            }
+            Err(e) => {
+                crate::log_warn!("Failed to start devtools server on {}: {}", addr, e);
+                None
+            }
+        }
+    }
+
+    /// The address clients connect to
+    pub fn addr(&self) -> &DevToolsAddr {
+        &self.addr
+    }
+
+    /// Token clients must send with [`Request::Auth`], for TCP servers
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// Number of connected clients
+    pub fn client_count(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Expose a value to clients under `name`
+    ///
+    /// The callback is evaluated for `get_signals` and after every frame
+    /// while a client subscribes to [`Topic::Signals`].
+    pub fn watch(&mut self, name: impl Into<String>, value: impl Fn() -> String + 'static) {
+        self.watches.push((name.into(), Box::new(value)));
+    }
+
+    /// Expose a signal's `Debug` value to clients under `name`
+    pub fn watch_signal<T: Debug + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        signal: &Signal<T>,
+    ) {
+        let signal = signal.clone();
+        self.watch(name, move || signal.with(|value| format!("{:?}", value)));
+    }
+
+    /// Accept connections, read requests and flush pending output
+    ///
+    /// Returns the requests that need app state: [`Request::GetTree`],
+    /// [`Request::GetNode`] and [`Request::SetStyle`]. Everything else is
+    /// answered here.
+    pub fn poll(&mut self) -> Vec<(ClientId, Request)> {
+        loop {
+            match self.listener.accept() {
+                Ok(Some(stream)) => {
+                    let id = ClientId(self.next_client);
+                    self.next_client += 1;
+                    self.clients.push(Client {
+                        id,
+                        stream,
+                        inbox: Vec::new(),
+                        outbox: Vec::new(),
+                        topics: HashSet::new(),
+                        authenticated: self.token.is_none(),
+                        closed: false,
+                    });
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    crate::log_warn!("devtools: accept failed: {}", e);
+                    break;
+                }
+            }
+        }
+
+        let mut pending = Vec::new();
+        for index in 0..self.clients.len() {
+            for line in self.clients[index].read_lines() {
+                if line.is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<Request>(&line) {
+                    Ok(request) if !self.clients[index].authenticated => {
+                        self.authenticate(index, request);
+                        if self.clients[index].closed {
+                            break;
+                        }
+                    }
+                    Ok(request) => {
+                        if let Some(request) = self.handle(index, request) {
+                            pending.push((self.clients[index].id, request));
+                        }
+                    }
+                    Err(e) => self.clients[index].send(&Message::Error {
+                        message: format!("invalid request: {}", e),
+                    }),
+                }
+            }
+        }
+
+        self.flush();
+        pending
+    }
+
+    /// Answer a request returned by [`poll()`](Self::poll)
+    pub fn reply(&mut self, client: ClientId, message: &Message) {
+        if let Some(client) = self.clients.iter_mut().find(|c| c.id == client) {
+            client.send(message);
+            client.flush();
+        }
+    }
+
+    /// Record an input event for `get_events` and event subscribers
+    pub fn record_event(&mut self, event: &Event) {
+        let (kind, detail) = match event {
+            Event::Key(key) => ("key", format!("{:?}", key)),
+            Event::Mouse(mouse) => ("mouse", format!("{:?}", mouse)),
+            Event::Resize(w, h) => ("resize", format!("{}x{}", w, h)),
+            Event::Paste(text) => ("paste", format!("{:?}", text)),
+            Event::FocusGained => ("focus", "gained".to_string()),
+            Event::FocusLost => ("focus", "lost".to_string()),
+            Event::Tick => ("tick", String::new()),
+        };
+        let info = EventInfo {
+            time_ms: self.elapsed_ms(),
+            kind: kind.to_string(),
+            detail,
+        };
+        push_bounded(&mut self.events, info.clone());
+        self.broadcast(Topic::Events, &Message::Event { event: info });
+        self.flush();
+    }
+
+    /// Record a drawn frame for `get_frames` and frame subscribers
+    ///
+    /// Also pushes watched signals to [`Topic::Signals`] subscribers when
+    /// any value changed since the last frame.
+    pub fn record_frame(&mut self, duration: Duration, dirty_regions: usize) {
+        let info = FrameInfo {
+            index: self.frame_count,
+            time_ms: self.elapsed_ms(),
+            duration_us: duration.as_micros() as u64,
+            dirty_regions,
+        };
+        self.frame_count += 1;
+        push_bounded(&mut self.frames, info.clone());
+        self.broadcast(Topic::Frames, &Message::Frame { frame: info });
+
+        if self.subscribed(Topic::Signals) {
+            let signals = self.signals();
+            let values: Vec<String> = signals.iter().map(|s| s.value.clone()).collect();
+            if values != self.last_signals {
+                self.last_signals = values;
+                self.broadcast(Topic::Signals, &Message::Signals { signals });
+            }
+        }
+        self.flush();
+    }
+
+    /// Check the first request of a TCP client, dropping it unless it is
+    /// [`Request::Auth`] with the right token
+    fn authenticate(&mut self, index: usize, request: Request) {
+        let client = &mut self.clients[index];
+        let message = match (request, &self.token) {
+            (Request::Auth { token }, Some(expected)) if tokens_match(&token, expected) => {
+                client.authenticated = true;
+                Message::Ok
+            }
+            (Request::Auth { .. }, _) => Message::Error {
+                message: "invalid devtools token".into(),
+            },
+            _ => Message::Error {
+                message: "authentication required".into(),
+            },
+        };
+        client.closed = !client.authenticated;
+        client.send(&message);
+    }
+
+    /// Handle a request locally, or return it for the app to answer
+    fn handle(&mut self, index: usize, request: Request) -> Option<Request> {
+        let message = match request {
+            Request::Auth { .. } => Message::Ok,
+            Request::Hello => Message::Hello {
+                protocol: PROTOCOL_VERSION,
+                pid: std::process::id(),
+                app: std::env::current_exe()
+                    .ok()
+                    .and_then(|exe| exe.file_stem().map(|s| s.to_string_lossy().into_owned()))
+                    .unwrap_or_default(),
+            },
+            Request::GetSignals => Message::Signals {
+                signals: self.signals(),
+            },
+            Request::GetEvents => Message::Events {
+                events: self.events.iter().cloned().collect(),
+            },
+            Request::GetFrames => Message::Frames {
+                frames: self.frames.iter().cloned().collect(),
+            },
+            Request::Subscribe { topics } => {
+                self.clients[index].topics.extend(topics);
+                Message::Ok
+            }
+            Request::Unsubscribe { topics } => {
+                for topic in topics {
+                    self.clients[index].topics.remove(&topic);
+                }
+                Message::Ok
+            }
+            other => return Some(other),
+        };
+        self.clients[index].send(&message);
+        None
+    }
+
+    fn signals(&self) -> Vec<SignalInfo> {
+        self.watches
+            .iter()
+            .map(|(name, value)| SignalInfo {
+                name: name.clone(),
+                value: value(),
+            })
+            .collect()
+    }
+
+    fn subscribed(&self, topic: Topic) -> bool {
+        self.clients.iter().any(|c| c.topics.contains(&topic))
+    }
+
+    fn broadcast(&mut self, topic: Topic, message: &Message) {
+        for client in self
+            .clients
+            .iter_mut()
+            .filter(|c| c.topics.contains(&topic))
+        {
+            client.send(message);
+        }
+    }
+
+    fn flush(&mut self) {
+        for client in &mut self.clients {
+            client.flush();
+        }
+        self.clients.retain(|c| !c.closed);
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        clock::elapsed(self.started).as_millis() as u64
+    }
+}
+
+/// 128 random bits as hex
+///
+/// `RandomState` keys come from the OS random number generator, so the
+/// hashes are unpredictable to other processes.
+fn generate_token() -> String {
+    use std::hash::{BuildHasher, Hasher};
+    (0..2u8)
+        .map(|part| {
+            let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+            hasher.write_u8(part);
+            format!("{:016x}", hasher.finish())
+        })
+        .collect()
+}
+
+/// Compare tokens without exiting at the first differing byte
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn push_bounded<T>(queue: &mut VecDeque<T>, item: T) {
+    if queue.len() == HISTORY_LEN {
+        queue.pop_front();
+    }
+    queue.push_back(item);
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::event::{Key, KeyEvent};
+    use std::io::{BufRead, BufReader};
+    use std::os::unix::net::UnixStream;
+    use std::path::PathBuf;
+
+    fn socket_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("revue-test-{}-{}.sock", name, std::process::id()))
+    }
+
+    struct TestClient {
+        writer: UnixStream,
+        reader: BufReader<UnixStream>,
+    }
+
+    impl TestClient {
+        fn connect(server: &mut DevToolsServer, path: &PathBuf) -> Self {
+            let writer = UnixStream::connect(path).unwrap();
+            writer
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+            let reader = BufReader::new(writer.try_clone().unwrap());
+            server.poll();
+            Self { writer, reader }
+        }
+
+        fn send(&mut self, line: &str) {
+            self.writer.write_all(line.as_bytes()).unwrap();
+            self.writer.write_all(b"\n").unwrap();
+        }
+
+        fn recv(&mut self) -> Message {
+            let mut line = String::new();
+            self.reader.read_line(&mut line).unwrap();
+            serde_json::from_str(&line).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_server_round_trip() {
+        let path = socket_path("round-trip");
+        let mut server = DevToolsServer::bind(&DevToolsAddr::Unix(path.clone())).unwrap();
+        let count = Signal::new(1);
+        server.watch_signal("count", &count);
+        let mut client = TestClient::connect(&mut server, &path);
+        assert_eq!(server.client_count(), 1);
+
+        client.send(r#"{"type":"hello"}"#);
+        client.send(r#"{"type":"get_signals"}"#);
+        client.send("not json");
+        client.send(r#"{"type":"get_tree"}"#);
+        let pending = server.poll();
+
+        assert!(matches!(
+            client.recv(),
+            Message::Hello {
+                protocol: PROTOCOL_VERSION,
+                ..
+            }
+        ));
+        assert_eq!(
+            client.recv(),
+            Message::Signals {
+                signals: vec![SignalInfo {
+                    name: "count".into(),
+                    value: "1".into(),
+                }],
+            }
+        );
+        assert!(matches!(client.recv(), Message::Error { .. }));
+
+        assert_eq!(pending.len(), 1);
+        let (id, request) = &pending[0];
+        assert_eq!(*request, Request::GetTree);
+        server.reply(*id, &Message::Tree { root: None });
+        assert_eq!(client.recv(), Message::Tree { root: None });
+
+        drop(server);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_server_subscriptions() {
+        let path = socket_path("subscribe");
+        let mut server = DevToolsServer::bind(&DevToolsAddr::Unix(path.clone())).unwrap();
+        let count = Signal::new(1);
+        server.watch_signal("count", &count);
+        let mut client = TestClient::connect(&mut server, &path);
+
+        client.send(r#"{"type":"subscribe","topics":["events","frames","signals"]}"#);
+        server.poll();
+        assert_eq!(client.recv(), Message::Ok);
+
+        server.record_event(&Event::Key(KeyEvent::new(Key::Char('a'))));
+        match client.recv() {
+            Message::Event { event } => assert_eq!(event.kind, "key"),
+            other => panic!("expected event, got {:?}", other),
+        }
+
+        server.record_frame(Duration::from_micros(1500), 2);
+        match client.recv() {
+            Message::Frame { frame } => {
+                assert_eq!(frame.index, 0);
+                assert_eq!(frame.duration_us, 1500);
+                assert_eq!(frame.dirty_regions, 2);
+            }
+            other => panic!("expected frame, got {:?}", other),
+        }
+        assert!(matches!(client.recv(), Message::Signals { .. }));
+
+        // Unchanged signals are not pushed again
+        count.set(2);
+        server.record_frame(Duration::ZERO, 0);
+        server.record_frame(Duration::ZERO, 0);
+        assert!(matches!(client.recv(), Message::Frame { .. }));
+        match client.recv() {
+            Message::Signals { signals } => assert_eq!(signals[0].value, "2"),
+            other => panic!("expected signals, got {:?}", other),
+        }
+        assert!(matches!(client.recv(), Message::Frame { .. }));
+
+        client.send(r#"{"type":"get_frames"}"#);
+        client.send(r#"{"type":"get_events"}"#);
+        server.poll();
+        match client.recv() {
+            Message::Frames { frames } => assert_eq!(frames.len(), 3),
+            other => panic!("expected frames, got {:?}", other),
+        }
+        match client.recv() {
+            Message::Events { events } => assert_eq!(events.len(), 1),
+            other => panic!("expected events, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_server_drops_closed_clients() {
+        let path = socket_path("closed");
+        let mut server = DevToolsServer::bind(&DevToolsAddr::Unix(path.clone())).unwrap();
+        let client = TestClient::connect(&mut server, &path);
+        assert_eq!(server.client_count(), 1);
+        drop(client);
+        server.poll();
+        assert_eq!(server.client_count(), 0);
+    }
+
+    #[test]
+    fn test_bind_rejects_live_socket() {
+        let path = socket_path("in-use");
+        let addr = DevToolsAddr::Unix(path);
+        let _server = DevToolsServer::bind(&addr).unwrap();
+        let err = DevToolsServer::bind(&addr).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::AddrInUse);
+    }
+
+    #[test]
+    fn test_tcp_requires_token() {
+        use std::net::TcpStream;
+
+        let addr: DevToolsAddr = "127.0.0.1:0".parse().unwrap();
+        let mut server = DevToolsServer::bind(&addr).unwrap();
+        let token = server.token().unwrap().to_string();
+        assert_eq!(token.len(), 32);
+        let DevToolsAddr::Tcp(socket) = server.addr().clone() else {
+            panic!("expected tcp");
+        };
+
+        // Poll until the server has answered the last request
+        let exchange = |server: &mut DevToolsServer, stream: &mut TcpStream, line: &str| {
+            stream.write_all(line.as_bytes()).unwrap();
+            stream.write_all(b"\n").unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut reply = String::new();
+            for _ in 0..500 {
+                server.poll();
+                if reader.read_line(&mut reply).is_ok() && !reply.is_empty() {
+                    return serde_json::from_str::<Message>(&reply).unwrap();
+                }
+            }
+            panic!("no reply to {}", line);
+        };
+        let connect = || {
+            let stream = TcpStream::connect(socket).unwrap();
+            stream
+                .set_read_timeout(Some(Duration::from_millis(10)))
+                .unwrap();
+            stream
+        };
+
+        let mut stream = connect();
+        assert_eq!(
+            exchange(&mut server, &mut stream, r#"{"type":"hello"}"#),
+            Message::Error {
+                message: "authentication required".into()
+            }
+        );
+        assert_eq!(stream.read(&mut [0; 1]).unwrap(), 0);
+
+        let mut stream = connect();
+        let reply = exchange(
+            &mut server,
+            &mut stream,
+            r#"{"type":"auth","token":"guess"}"#,
+        );
+        assert!(matches!(reply, Message::Error { .. }));
+        assert_eq!(server.client_count(), 0);
+
+        let mut stream = connect();
+        let auth = format!(r#"{{"type":"auth","token":"{}"}}"#, token);
+        assert_eq!(exchange(&mut server, &mut stream, &auth), Message::Ok);
+        assert!(matches!(
+            exchange(&mut server, &mut stream, r#"{"type":"hello"}"#),
+            Message::Hello { .. }
+        ));
+        assert_eq!(server.client_count(), 1);
+    }
+
+    #[test]
+    fn test_tcp_port_zero_reports_bound_port() {
+        let addr: DevToolsAddr = "127.0.0.1:0".parse().unwrap();
+        let server = DevToolsServer::bind(&addr).unwrap();
+        match server.addr() {
+            DevToolsAddr::Tcp(socket) => assert_ne!(socket.port(), 0),
+            other => panic!("expected tcp, got {}", other),
+        }
+    }
+}
@@ -0,0 +1,287 @@
+//! Wire protocol: one JSON object per line in each direction
+//!
+//! Every message carries a `"type"` tag. Clients send [`Request`]s; the
+//! server answers each with one [`Message`] and, for subscribed
+//! [`Topic`]s, pushes `event`, `frame` and `signals` messages as they happen.
+//!
+//! On a TCP socket the first request must be `auth` with the token the
+//! server printed at startup; any other first request closes the
+//! connection.
+//!
+//! ```text
+//! → {"type":"auth","token":"3f9c..."}
+//! ← {"type":"ok"}
+//! → {"type":"get_node","id":4}
+//! ← {"type":"node","id":4,"rules":[{"selector":".btn","specificity":[0,1,0],...}],...}
+//! → {"type":"set_style","selector":".btn","property":"color","value":"red"}
+//! ← {"type":"ok"}
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Protocol version reported in the `hello` message
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// Client → server request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request {
+    /// Authenticate the connection (answered with [`Message::Ok`])
+    ///
+    /// Required as the first request on TCP sockets; accepted and ignored
+    /// on Unix sockets, which are only reachable by the socket's owner.
+    Auth {
+        /// Token printed by the server at startup
+        token: String,
+    },
+    /// Identify the app (answered with [`Message::Hello`])
+    Hello,
+    /// Fetch the DOM tree (answered with [`Message::Tree`])
+    GetTree,
+    /// Fetch matched rules and computed declarations of a node
+    GetNode {
+        /// DOM node id from the tree
+        id: u64,
+    },
+    /// Fetch watched signal values
+    GetSignals,
+    /// Fetch recent events
+    GetEvents,
+    /// Fetch recent frame timings
+    GetFrames,
+    /// Start pushing messages for the given topics
+    Subscribe {
+        /// Topics to add
+        topics: Vec<Topic>,
+    },
+    /// Stop pushing messages for the given topics
+    Unsubscribe {
+        /// Topics to remove
+        topics: Vec<Topic>,
+    },
+    /// Set (or, without a value, remove) a declaration in the live stylesheet
+    ///
+    /// Targets the last rule with exactly this selector; a new rule is
+    /// appended when none exists.
+    SetStyle {
+        /// Rule selector, e.g. `.btn:focus`
+        selector: String,
+        /// CSS property
+        property: String,
+        /// New value, or `None` to remove the declaration
+        #[serde(default)]
+        value: Option<String>,
+    },
+}
+
+/// Server → client message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    /// Answer to [`Request::Hello`]
+    Hello {
+        /// Protocol version
+        protocol: u32,
+        /// Process id of the app
+        pid: u32,
+        /// Executable name of the app
+        app: String,
+    },
+    /// DOM tree (`root` is `None` before the first frame)
+    Tree {
+        /// Root node
+        root: Option<NodeInfo>,
+    },
+    /// Styles of one node
+    Node {
+        /// DOM node id
+        id: u64,
+        /// Matched stylesheet rules, lowest priority first
+        rules: Vec<RuleInfo>,
+        /// Effective declarations after the cascade
+        computed: Vec<DeclarationInfo>,
+    },
+    /// Watched signal values
+    Signals {
+        /// Values in registration order
+        signals: Vec<SignalInfo>,
+    },
+    /// Recent events, oldest first
+    Events {
+        /// Buffered events
+        events: Vec<EventInfo>,
+    },
+    /// Recent frames, oldest first
+    Frames {
+        /// Buffered frames
+        frames: Vec<FrameInfo>,
+    },
+    /// Pushed for the [`Topic::Events`] subscription
+    Event {
+        /// The event
+        event: EventInfo,
+    },
+    /// Pushed for the [`Topic::Frames`] subscription
+    Frame {
+        /// The frame
+        frame: FrameInfo,
+    },
+    /// Request succeeded with nothing to report
+    Ok,
+    /// Request failed
+    Error {
+        /// What went wrong
+        message: String,
+    },
+}
+
+/// Push subscription topic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Topic {
+    /// Every input event
+    Events,
+    /// Every rendered frame
+    Frames,
+    /// Signal values, pushed after a frame when any changed
+    Signals,
+}
+
+/// A DOM node and its subtree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeInfo {
+    /// DOM node id
+    pub id: u64,
+    /// Widget type name
+    pub widget: String,
+    /// Element id (`#id`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub element_id: Option<String>,
+    /// CSS classes, sorted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub classes: Vec<String>,
+    /// Active pseudo-class states (`focus`, `hover`, ...)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub states: Vec<String>,
+    /// Layout rect as `[x, y, width, height]`, if laid out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rect: Option<[u16; 4]>,
+    /// Child nodes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<NodeInfo>,
+}
+
+/// A matched stylesheet rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleInfo {
+    /// Rule selector
+    pub selector: String,
+    /// Specificity as `[ids, classes, types]`
+    pub specificity: [usize; 3],
+    /// Declarations in source order
+    pub declarations: Vec<DeclarationInfo>,
+}
+
+/// A `property: value` pair
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeclarationInfo {
+    /// CSS property
+    pub property: String,
+    /// CSS value
+    pub value: String,
+}
+
+/// A watched signal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignalInfo {
+    /// Name given when watching
+    pub name: String,
+    /// Debug-formatted value
+    pub value: String,
+}
+
+/// An input event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventInfo {
+    /// Milliseconds since the server started
+    pub time_ms: u64,
+    /// `key`, `mouse`, `resize`, `paste`, `focus` or `tick`
+    pub kind: String,
+    /// Debug-formatted event
+    pub detail: String,
+}
+
+/// A rendered frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameInfo {
+    /// Frame number, starting at 0
+    pub index: u64,
+    /// Milliseconds since the server started
+    pub time_ms: u64,
+    /// Time spent drawing, in microseconds
+    pub duration_us: u64,
+    /// Number of dirty regions redrawn
+    pub dirty_regions: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_wire_format() {
+        let request: Request = serde_json::from_str(r#"{"type":"get_node","id":4}"#).unwrap();
+        assert_eq!(request, Request::GetNode { id: 4 });
+
+        let request: Request =
+            serde_json::from_str(r#"{"type":"set_style","selector":".a","property":"color"}"#)
+                .unwrap();
+        assert_eq!(
+            request,
+            Request::SetStyle {
+                selector: ".a".into(),
+                property: "color".into(),
+                value: None,
+            }
+        );
+
+        let request: Request = serde_json::from_str(r#"{"type":"auth","token":"abc"}"#).unwrap();
+        assert_eq!(
+            request,
+            Request::Auth {
+                token: "abc".into()
+            }
+        );
+
+        let request: Request =
+            serde_json::from_str(r#"{"type":"subscribe","topics":["events","frames"]}"#).unwrap();
+        assert_eq!(
+            request,
+            Request::Subscribe {
+                topics: vec![Topic::Events, Topic::Frames],
+            }
+        );
+    }
+
+    #[test]
+    fn test_message_wire_format() {
+        let node = NodeInfo {
+            id: 1,
+            widget: "Button".into(),
+            element_id: None,
+            classes: vec!["primary".into()],
+            states: Vec::new(),
+            rect: Some([0, 0, 10, 1]),
+            children: Vec::new(),
+        };
+        let json = serde_json::to_string(&Message::Tree { root: Some(node) }).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"tree","root":{"id":1,"widget":"Button","classes":["primary"],"rect":[0,0,10,1]}}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Message::Ok).unwrap(),
+            r#"{"type":"ok"}"#
+        );
+    }
+}
@@ -0,0 +1,254 @@
+//! Local socket transport: Unix domain sockets or loopback TCP
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+
+/// File name prefix of default sockets, used by `revue inspect --attach`
+/// to discover running apps
+pub const SOCKET_PREFIX: &str = "revue-devtools-";
+
+/// Where the devtools server listens
+///
+/// Parsed from `unix:<path>`, `tcp:<host:port>`, a bare `host:port`, or a
+/// bare path containing `/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DevToolsAddr {
+    /// Unix domain socket at a path
+    #[cfg(unix)]
+    Unix(PathBuf),
+    /// TCP socket; only loopback addresses are accepted
+    Tcp(SocketAddr),
+}
+
+impl DevToolsAddr {
+    /// The default address for this process
+    ///
+    /// `$TMPDIR/revue-devtools-<pid>.sock` on Unix, `127.0.0.1:7878`
+    /// elsewhere.
+    pub fn default_for_process() -> Self {
+        #[cfg(unix)]
+        {
+            DevToolsAddr::Unix(std::env::temp_dir().join(format!(
+                "{}{}.sock",
+                SOCKET_PREFIX,
+                std::process::id()
+            )))
+        }
+        #[cfg(not(unix))]
+        {
+            DevToolsAddr::Tcp(SocketAddr::from(([127, 0, 0, 1], 7878)))
+        }
+    }
+}
+
+impl FromStr for DevToolsAddr {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> io::Result<Self> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, msg);
+        if let Some(path) = s.strip_prefix("unix:") {
+            #[cfg(unix)]
+            return Ok(DevToolsAddr::Unix(PathBuf::from(path)));
+            #[cfg(not(unix))]
+            return Err(invalid(format!(
+                "unix sockets are not supported here: {}",
+                path
+            )));
+        }
+        let tcp = s.strip_prefix("tcp:").unwrap_or(s);
+        if let Ok(addr) = tcp.parse::<SocketAddr>() {
+            return Ok(DevToolsAddr::Tcp(addr));
+        }
+        #[cfg(unix)]
+        if s.contains('/') {
+            return Ok(DevToolsAddr::Unix(PathBuf::from(s)));
+        }
+        Err(invalid(format!("invalid devtools address: {}", s)))
+    }
+}
+
+impl fmt::Display for DevToolsAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(unix)]
+            DevToolsAddr::Unix(path) => write!(f, "unix:{}", path.display()),
+            DevToolsAddr::Tcp(addr) => write!(f, "tcp:{}", addr),
+        }
+    }
+}
+
+/// Non-blocking listener
+pub(super) enum Listener {
+    #[cfg(unix)]
+    Unix(UnixListener, PathBuf),
+    Tcp(TcpListener),
+}
+
+impl Listener {
+    /// Bind and switch to non-blocking mode
+    ///
+    /// A stale socket file left behind by a crashed app is replaced; one
+    /// that still accepts connections is an `AddrInUse` error. Socket files
+    /// are made owner-only (`0600`) so other local users cannot connect.
+    pub(super) fn bind(addr: &DevToolsAddr) -> io::Result<(Self, DevToolsAddr)> {
+        match addr {
+            #[cfg(unix)]
+            DevToolsAddr::Unix(path) => {
+                if path.exists() {
+                    if UnixStream::connect(path).is_ok() {
+                        return Err(io::Error::new(
+                            io::ErrorKind::AddrInUse,
+                            format!("{} is in use", path.display()),
+                        ));
+                    }
+                    std::fs::remove_file(path)?;
+                }
+                let listener = UnixListener::bind(path)?;
+                std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+                listener.set_nonblocking(true)?;
+                Ok((Listener::Unix(listener, path.clone()), addr.clone()))
+            }
+            DevToolsAddr::Tcp(socket) => {
+                if !socket.ip().is_loopback() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::PermissionDenied,
+                        format!("devtools only listen on loopback, not {}", socket),
+                    ));
+                }
+                let listener = TcpListener::bind(socket)?;
+                listener.set_nonblocking(true)?;
+                let local = listener.local_addr()?;
+                Ok((Listener::Tcp(listener), DevToolsAddr::Tcp(local)))
+            }
+        }
+    }
+
+    /// Accept one pending connection, if any
+    pub(super) fn accept(&self) -> io::Result<Option<Stream>> {
+        let result = match self {
+            #[cfg(unix)]
+            Listener::Unix(listener, _) => listener.accept().map(|(s, _)| Stream::Unix(s)),
+            Listener::Tcp(listener) => listener.accept().map(|(s, _)| Stream::Tcp(s)),
+        };
+        match result {
+            Ok(stream) => {
+                stream.set_nonblocking(true)?;
+                Ok(Some(stream))
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Listener::Unix(_, path) = self {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// A connected client stream
+pub(super) enum Stream {
+    #[cfg(unix)]
+    Unix(UnixStream),
+    Tcp(TcpStream),
+}
+
+impl Stream {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            Stream::Unix(s) => s.set_nonblocking(nonblocking),
+            Stream::Tcp(s) => s.set_nonblocking(nonblocking),
+        }
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            #[cfg(unix)]
+            Stream::Unix(s) => s.read(buf),
+            Stream::Tcp(s) => s.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            #[cfg(unix)]
+            Stream::Unix(s) => s.write(buf),
+            Stream::Tcp(s) => s.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            Stream::Unix(s) => s.flush(),
+            Stream::Tcp(s) => s.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_addr() {
+        assert_eq!(
+            "127.0.0.1:7000".parse::<DevToolsAddr>().unwrap(),
+            DevToolsAddr::Tcp(SocketAddr::from(([127, 0, 0, 1], 7000)))
+        );
+        assert_eq!(
+            "tcp:[::1]:7000"
+                .parse::<DevToolsAddr>()
+                .unwrap()
+                .to_string(),
+            "tcp:[::1]:7000"
+        );
+        assert!("nonsense".parse::<DevToolsAddr>().is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_parse_unix_addr() {
+        let addr: DevToolsAddr = "unix:/tmp/a.sock".parse().unwrap();
+        assert_eq!(addr, DevToolsAddr::Unix(PathBuf::from("/tmp/a.sock")));
+        assert_eq!(addr.to_string(), "unix:/tmp/a.sock");
+        assert_eq!(
+            "/tmp/b.sock".parse::<DevToolsAddr>().unwrap(),
+            DevToolsAddr::Unix(PathBuf::from("/tmp/b.sock"))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unix_socket_is_owner_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("owner.sock");
+        let _listener = Listener::bind(&DevToolsAddr::Unix(path.clone())).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_tcp_must_be_loopback() {
+        let addr = DevToolsAddr::Tcp(SocketAddr::from(([0, 0, 0, 0], 0)));
+        let err = Listener::bind(&addr).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+    }
+}
@@ -2,6 +2,7 @@
 
 use super::types::ComputedProperty;
 use super::types::PropertySource;
+use crate::style::SourceSpan;
 
 impl ComputedProperty {
     /// Create a new computed property
@@ -11,6 +12,9 @@ impl ComputedProperty {
             value: value.into(),
             source: PropertySource::Computed,
             overridden: false,
+            selector: None,
+            span: None,
+            disabled: false,
         }
     }
 
@@ -25,4 +29,16 @@ impl ComputedProperty {
         self.overridden = true;
         self
     }
+
+    /// Set the stylesheet rule declaring this property, making it editable
+    pub fn rule(mut self, selector: impl Into<String>, span: Option<SourceSpan>) -> Self {
+        self.selector = Some(selector.into());
+        self.span = span;
+        self
+    }
+
+    /// Can be edited from the inspector
+    pub fn is_editable(&self) -> bool {
+        self.selector.is_some()
+    }
 }
@@ -1,6 +1,8 @@
 //! Style inspector for viewing computed styles
 
+use super::edit::StyleEdit;
 use super::types::{ComputedProperty, StyleCategory};
+use crate::dom::DomId;
 use std::collections::HashMap;
 
 /// Style inspector for viewing computed styles
@@ -26,4 +28,12 @@ pub struct StyleInspector {
     pub category_filter: Option<StyleCategory>,
     /// Expanded categories
     pub expanded_categories: HashMap<StyleCategory, bool>,
+    /// DOM node last passed to `inspect`
+    pub node: Option<DomId>,
+    /// Value being typed for the selected property
+    pub editing: Option<String>,
+    /// Write committed edits back to the stylesheet files
+    pub write_back: bool,
+    /// Edits waiting to be applied by the app
+    pub pending_edits: Vec<StyleEdit>,
 }
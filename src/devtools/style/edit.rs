@@ -0,0 +1,479 @@
+//! Declaration edits made from the style inspector
+//!
+//! A [`StyleEdit`] sets or removes one declaration. It is applied to the live
+//! stylesheet and, when the rule came from a file, can be written back to the
+//! rule's source span.
+
+use crate::dom::parse_selector;
+use crate::style::{parse_css, Declaration, Rule, SourceMap, SourceSpan, StyleSheet};
+use std::fs;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Error produced when applying or writing back a [`StyleEdit`]
+#[derive(Debug, Error)]
+pub enum StyleEditError {
+    /// The selector does not parse
+    #[error("invalid selector {selector:?}: {reason}")]
+    InvalidSelector {
+        /// Selector as given
+        selector: String,
+        /// Parser message
+        reason: String,
+    },
+
+    /// The value is not a single valid declaration
+    #[error("invalid declaration {property}: {value}")]
+    InvalidDeclaration {
+        /// CSS property
+        property: String,
+        /// Rejected value
+        value: String,
+    },
+
+    /// Removing a declaration the rule does not have
+    #[error("{selector} has no {property} declaration")]
+    MissingDeclaration {
+        /// Rule selector
+        selector: String,
+        /// CSS property
+        property: String,
+    },
+
+    /// The edited rule was not parsed from a file
+    #[error("{0} has no source file to write back to")]
+    NoSource(String),
+
+    /// The file changed since it was parsed and the rule is no longer at its span
+    #[error("{} changed on disk; {selector} is no longer at line {line}", path.display())]
+    StaleSource {
+        /// Stylesheet file
+        path: PathBuf,
+        /// Rule selector
+        selector: String,
+        /// Line the rule was parsed from
+        line: usize,
+    },
+
+    /// Reading or writing the stylesheet failed
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Set or remove one declaration of a rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleEdit {
+    /// Selector of the rule to edit
+    pub selector: String,
+    /// CSS property
+    pub property: String,
+    /// New value, or `None` to remove the declaration
+    pub value: Option<String>,
+    /// Source span of the rule, to pick it among rules with the same selector
+    pub span: Option<SourceSpan>,
+    /// Also write the change to the rule's stylesheet file
+    pub write_back: bool,
+}
+
+/// A stylesheet file rewritten by [`StyleEdit::write_source`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrittenSource {
+    /// Stylesheet file
+    pub path: PathBuf,
+    /// New file contents
+    pub contents: String,
+}
+
+impl StyleEdit {
+    /// Set `property` to `value` in the rule with `selector`
+    pub fn set(
+        selector: impl Into<String>,
+        property: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        Self {
+            selector: selector.into(),
+            property: property.into(),
+            value: Some(value.into()),
+            span: None,
+            write_back: false,
+        }
+    }
+
+    /// Remove `property` from the rule with `selector`
+    pub fn remove(selector: impl Into<String>, property: impl Into<String>) -> Self {
+        Self {
+            selector: selector.into(),
+            property: property.into(),
+            value: None,
+            span: None,
+            write_back: false,
+        }
+    }
+
+    /// Target the rule parsed from `span`
+    pub fn at(mut self, span: Option<SourceSpan>) -> Self {
+        self.span = span;
+        self
+    }
+
+    /// Write the change back to the stylesheet file
+    pub fn write_back(mut self, enabled: bool) -> Self {
+        self.write_back = enabled;
+        self
+    }
+
+    /// Apply to a stylesheet
+    ///
+    /// Edits the rule at [`span`](Self::span) in `sources` if it is still
+    /// there, else the last rule with exactly [`selector`](Self::selector).
+    /// Setting a value appends a new rule when none exists.
+    pub fn apply(&self, sheet: &mut StyleSheet, sources: &SourceMap) -> Result<(), StyleEditError> {
+        parse_selector(&self.selector).map_err(|e| StyleEditError::InvalidSelector {
+            selector: self.selector.clone(),
+            reason: e.to_string(),
+        })?;
+        let index = self.find_rule(sheet, sources);
+
+        match &self.value {
+            Some(value) => {
+                let declaration = parse_declaration(&self.property, value)?;
+                match index {
+                    Some(index) => {
+                        let declarations = &mut sheet.rules[index].declarations;
+                        match declarations
+                            .iter_mut()
+                            .rfind(|d| d.property == declaration.property)
+                        {
+                            Some(existing) => existing.value = declaration.value,
+                            None => declarations.push(declaration),
+                        }
+                    }
+                    None => sheet
+                        .rules
+                        .push(Rule::new(self.selector.clone(), vec![declaration])),
+                }
+            }
+            None => {
+                let index = index
+                    .filter(|&index| {
+                        sheet.rules[index]
+                            .declarations
+                            .iter()
+                            .any(|d| d.property == self.property)
+                    })
+                    .ok_or_else(|| self.missing())?;
+                sheet.rules[index]
+                    .declarations
+                    .retain(|d| d.property != self.property);
+            }
+        }
+        Ok(())
+    }
+
+    /// Rewrite the declaration in the rule's stylesheet file
+    ///
+    /// Only the rule's block is touched; the rest of the file, including
+    /// comments and formatting, is kept. Fails with
+    /// [`StyleEditError::StaleSource`] when the file no longer has the rule
+    /// at its span.
+    pub fn write_source(&self) -> Result<WrittenSource, StyleEditError> {
+        let Some(SourceSpan {
+            file: Some(path),
+            line,
+            range,
+        }) = &self.span
+        else {
+            return Err(StyleEditError::NoSource(self.selector.clone()));
+        };
+        if let Some(value) = &self.value {
+            parse_declaration(&self.property, value)?;
+        }
+
+        let stale = || StyleEditError::StaleSource {
+            path: path.clone(),
+            selector: self.selector.clone(),
+            line: *line,
+        };
+        let source = fs::read_to_string(path)?;
+        let rule = source.get(range.clone()).ok_or_else(stale)?;
+        let rule = edit_rule(rule, &self.selector, &self.property, self.value.as_deref())
+            .ok_or_else(stale)?
+            .map_err(|()| self.missing())?;
+
+        let contents = format!("{}{}{}", &source[..range.start], rule, &source[range.end..]);
+        fs::write(path, &contents)?;
+        Ok(WrittenSource {
+            path: path.clone(),
+            contents,
+        })
+    }
+
+    fn find_rule(&self, sheet: &StyleSheet, sources: &SourceMap) -> Option<usize> {
+        let by_span =
+            self.span.as_ref().and_then(|span| {
+                sheet.rules.iter().enumerate().rposition(|(i, r)| {
+                    r.selector == self.selector && sources.span(i) == Some(span)
+                })
+            });
+        by_span.or_else(|| {
+            sheet
+                .rules
+                .iter()
+                .rposition(|r| r.selector == self.selector)
+        })
+    }
+
+    fn missing(&self) -> StyleEditError {
+        StyleEditError::MissingDeclaration {
+            selector: self.selector.clone(),
+            property: self.property.clone(),
+        }
+    }
+}
+
+/// Parse a single `property: value` pair, rejecting anything that would
+/// smuggle in further declarations or rules
+fn parse_declaration(property: &str, value: &str) -> Result<Declaration, StyleEditError> {
+    let invalid = || StyleEditError::InvalidDeclaration {
+        property: property.to_string(),
+        value: value.to_string(),
+    };
+    let sheet = parse_css(&format!("* {{ {}: {}; }}", property, value)).map_err(|_| invalid())?;
+    match sheet.rules.as_slice() {
+        [rule] if rule.declarations.len() == 1 => Ok(rule.declarations[0].clone()),
+        _ => Err(invalid()),
+    }
+}
+
+/// Edit the source text of one rule (`selector { ... }`)
+///
+/// Returns `None` when the text is not a rule with `selector`, and
+/// `Some(Err(()))` when removing a declaration the rule does not have.
+fn edit_rule(
+    rule: &str,
+    selector: &str,
+    property: &str,
+    value: Option<&str>,
+) -> Option<Result<String, ()>> {
+    let open = rule.find('{')?;
+    if rule[..open].trim() != selector || !rule.ends_with('}') {
+        return None;
+    }
+    let body = &rule[open + 1..rule.len() - 1];
+
+    let mut body = body.to_string();
+    match (find_declaration(&body, property), value) {
+        (Some(found), Some(value)) => {
+            body.replace_range(found.value, value);
+        }
+        (Some(found), None) => {
+            body.replace_range(found.whole, "");
+        }
+        (None, Some(value)) => insert_declaration(&mut body, property, value),
+        (None, None) => return Some(Err(())),
+    }
+    Some(Ok(format!("{}{{{}}}", &rule[..open], body)))
+}
+
+/// Byte ranges of a declaration within a rule body
+struct FoundDeclaration {
+    /// The value, without surrounding whitespace
+    value: std::ops::Range<usize>,
+    /// Leading whitespace through the terminating `;`
+    whole: std::ops::Range<usize>,
+}
+
+/// Find the last declaration of `property` in a rule body
+///
+/// Splits on `;` like the parser does, skipping comments before the name.
+fn find_declaration(body: &str, property: &str) -> Option<FoundDeclaration> {
+    let mut found = None;
+    let mut start = 0;
+    while start < body.len() {
+        let end = body[start..].find(';').map_or(body.len(), |i| start + i);
+        let segment = &body[start..end];
+        let name_start = skip_comments(segment);
+        if let Some(colon) = segment[name_start..].find(':') {
+            let colon = name_start + colon;
+            if segment[name_start..colon].trim() == property {
+                let raw = &segment[colon + 1..];
+                let value_start = start + colon + 1 + (raw.len() - raw.trim_start().len());
+                let value_end = (start + segment.trim_end().len()).max(value_start);
+                found = Some(FoundDeclaration {
+                    value: value_start..value_end,
+                    whole: start..(end + 1).min(body.len()),
+                });
+            }
+        }
+        start = end + 1;
+    }
+    found
+}
+
+/// Offset of the first character that is neither whitespace nor in a comment
+fn skip_comments(text: &str) -> usize {
+    let mut pos = 0;
+    loop {
+        pos += text[pos..].len() - text[pos..].trim_start().len();
+        if !text[pos..].starts_with("/*") {
+            return pos;
+        }
+        match text[pos + 2..].find("*/") {
+            Some(end) => pos += 2 + end + 2,
+            None => return text.len(),
+        }
+    }
+}
+
+/// Append a declaration to a rule body, following its layout
+fn insert_declaration(body: &mut String, property: &str, value: &str) {
+    let content_end = body.trim_end().len();
+    if content_end == 0 {
+        *body = format!(" {}: {}; ", property, value);
+        return;
+    }
+
+    let mut insert = String::new();
+    if !body[..content_end].ends_with(';') && !body[..content_end].ends_with("*/") {
+        insert.push(';');
+    }
+    if body.contains('\n') {
+        // Indent like the first declaration line
+        let indent: String = body
+            .lines()
+            .skip(1)
+            .find(|line| !line.trim().is_empty())
+            .map(|line| line.chars().take_while(|c| c.is_whitespace()).collect())
+            .unwrap_or_else(|| "    ".to_string());
+        insert.push_str(&format!("\n{}{}: {};", indent, property, value));
+    } else {
+        insert.push_str(&format!(" {}: {};", property, value));
+    }
+    body.insert_str(content_end, &insert);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::parse_css_with_source_map;
+
+    fn edited(rule: &str, property: &str, value: Option<&str>) -> String {
+        edit_rule(rule, ".a", property, value).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_edit_rule_text() {
+        let rule = ".a { color: red; width: 3; }";
+        assert_eq!(
+            edited(rule, "color", Some("blue")),
+            ".a { color: blue; width: 3; }"
+        );
+        assert_eq!(edited(rule, "color", None), ".a { width: 3; }");
+        assert_eq!(edited(rule, "width", None), ".a { color: red; }");
+        assert_eq!(
+            edited(rule, "height", Some("2")),
+            ".a { color: red; width: 3; height: 2; }"
+        );
+        assert_eq!(edited(".a {}", "color", Some("red")), ".a { color: red; }");
+        assert_eq!(
+            edited(".a { color: red }", "width", Some("1")),
+            ".a { color: red; width: 1; }"
+        );
+
+        let multiline = ".a {\n  /* main */ color: red;\n  width: 3;\n}";
+        assert_eq!(
+            edited(multiline, "width", Some("4")),
+            ".a {\n  /* main */ color: red;\n  width: 4;\n}"
+        );
+        assert_eq!(
+            edited(multiline, "height", Some("2")),
+            ".a {\n  /* main */ color: red;\n  width: 3;\n  height: 2;\n}"
+        );
+        assert_eq!(
+            edited(multiline, "width", None),
+            ".a {\n  /* main */ color: red;\n}"
+        );
+
+        assert!(edit_rule(".b { color: red; }", ".a", "color", None).is_none());
+        assert_eq!(edit_rule(rule, ".a", "height", None), Some(Err(())));
+    }
+
+    #[test]
+    fn test_apply_edit() {
+        let (mut sheet, sources) =
+            parse_css_with_source_map(".a { color: red; } .b { width: 1; } .a { width: 2; }")
+                .unwrap();
+        let first = sources.span(0).cloned();
+
+        StyleEdit::set(".a", "color", "blue")
+            .at(first)
+            .apply(&mut sheet, &sources)
+            .unwrap();
+        assert_eq!(sheet.rules[0].declarations[0].value, "blue");
+
+        // Without a span the last rule with the selector wins
+        StyleEdit::set(".a", "color", "green")
+            .apply(&mut sheet, &sources)
+            .unwrap();
+        assert_eq!(sheet.rules[2].declarations[1].value, "green");
+
+        StyleEdit::remove(".b", "width")
+            .apply(&mut sheet, &sources)
+            .unwrap();
+        assert!(sheet.rules[1].declarations.is_empty());
+        assert!(matches!(
+            StyleEdit::remove(".b", "width").apply(&mut sheet, &sources),
+            Err(StyleEditError::MissingDeclaration { .. })
+        ));
+
+        StyleEdit::set(".c", "height", "3")
+            .apply(&mut sheet, &sources)
+            .unwrap();
+        assert_eq!(sheet.rules.len(), 4);
+
+        assert!(matches!(
+            StyleEdit::set(".a", "color", "red; } * { color: red").apply(&mut sheet, &sources),
+            Err(StyleEditError::InvalidDeclaration { .. })
+        ));
+        assert!(matches!(
+            StyleEdit::set("[bad", "color", "red").apply(&mut sheet, &sources),
+            Err(StyleEditError::InvalidSelector { .. })
+        ));
+    }
+
+    #[test]
+    fn test_write_source() {
+        let path =
+            std::env::temp_dir().join(format!("revue-style-edit-{}.css", std::process::id()));
+        let css = "/* theme */\n.a {\n    color: red;\n}\n\n.b { width: 1; }\n";
+        fs::write(&path, css).unwrap();
+        let (_, mut sources) = parse_css_with_source_map(css).unwrap();
+        sources.set_file(&path);
+        let span = sources.span(1).cloned();
+        assert_eq!(span.as_ref().unwrap().line, 6);
+
+        let written = StyleEdit::set(".b", "height", "2")
+            .at(span.clone())
+            .write_source()
+            .unwrap();
+        assert_eq!(
+            written.contents,
+            "/* theme */\n.a {\n    color: red;\n}\n\n.b { width: 1; height: 2; }\n"
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), written.contents);
+
+        // The span of .b is stale once the file moves the rule
+        fs::write(&path, format!("\n\n{}", written.contents)).unwrap();
+        assert!(matches!(
+            StyleEdit::remove(".b", "width").at(span).write_source(),
+            Err(StyleEditError::StaleSource { .. })
+        ));
+
+        assert!(matches!(
+            StyleEdit::remove(".b", "width").write_source(),
+            Err(StyleEditError::NoSource(_))
+        ));
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,401 @@
+//! Live editing for StyleInspector
+//!
+//! The inspector only queues [`StyleEdit`]s; the app applies them with
+//! [`App::apply_style_edit`](crate::core::app::App::apply_style_edit) and
+//! then calls [`StyleInspector::inspect`] again to show the new cascade.
+
+use super::core::StyleInspector;
+use super::edit::StyleEdit;
+use super::types::{ComputedProperty, PropertySource, StyleCategory};
+use crate::dom::{DomId, DomRenderer};
+use crate::event::{Key, KeyEvent};
+
+/// Properties a node inherits from its parent (see `Style::inherit`)
+const INHERITED_PROPERTIES: &[&str] = &[
+    "color",
+    "opacity",
+    "visible",
+    "visibility",
+    "text-align",
+    "font-weight",
+];
+
+impl StyleInspector {
+    /// Show the rules matching a DOM node
+    ///
+    /// Declarations of later rules mark earlier ones as overridden, and
+    /// inheritable properties not set on the node are taken from its nearest
+    /// ancestor that sets them. Properties disabled on the same node stay
+    /// listed so they can be toggled back on.
+    pub fn inspect(&mut self, dom: &mut DomRenderer, id: DomId) {
+        let disabled: Vec<ComputedProperty> = if self.node == Some(id) {
+            self.properties
+                .iter()
+                .filter(|p| p.disabled)
+                .cloned()
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let selected = if self.node == Some(id) {
+            self.selected
+        } else {
+            None
+        };
+        self.clear();
+        self.node = Some(id);
+
+        let Some(node) = dom.tree().get(id) else {
+            return;
+        };
+        self.set_widget(node.meta.widget_type.clone(), node.meta.id.clone());
+        let mut classes: Vec<String> = node.meta.classes.iter().cloned().collect();
+        classes.sort();
+        self.classes = classes;
+        let mut parent = node.parent;
+
+        let mut properties: Vec<ComputedProperty> = Vec::new();
+        for (index, specificity) in dom.matching_rule_indices(id) {
+            let rule = dom.stylesheet().rules[index].clone();
+            let span = dom.source_map().span(index).cloned();
+            let source = if specificity.ids > 0 {
+                PropertySource::Id
+            } else {
+                PropertySource::Class
+            };
+            for declaration in rule.declarations {
+                for earlier in properties
+                    .iter_mut()
+                    .filter(|p| p.name == declaration.property)
+                {
+                    earlier.overridden = true;
+                }
+                properties.push(
+                    ComputedProperty::new(declaration.property, declaration.value)
+                        .source(source)
+                        .rule(rule.selector.clone(), span.clone()),
+                );
+            }
+        }
+
+        while let Some(ancestor) = parent {
+            // Later rules win, so walk them backwards and keep the first hit
+            for (rule, _) in dom.matching_rules(ancestor).into_iter().rev() {
+                for declaration in rule.declarations.into_iter().rev() {
+                    if INHERITED_PROPERTIES.contains(&declaration.property.as_str())
+                        && !properties.iter().any(|p| p.name == declaration.property)
+                    {
+                        properties.push(
+                            ComputedProperty::new(declaration.property, declaration.value)
+                                .source(PropertySource::Inherited),
+                        );
+                    }
+                }
+            }
+            parent = dom.tree().get(ancestor).and_then(|n| n.parent);
+        }
+
+        for property in disabled {
+            if !properties
+                .iter()
+                .any(|p| p.name == property.name && p.selector == property.selector)
+            {
+                properties.push(property);
+            }
+        }
+        self.properties = properties;
+        self.selected = selected.filter(|&i| i < self.filtered().len());
+    }
+
+    /// Indices into `properties` in the order they are rendered
+    ///
+    /// Rendering groups shown properties by category, and `selected`
+    /// counts rows in that order.
+    pub(super) fn display_order(&self) -> Vec<usize> {
+        StyleCategory::all()
+            .iter()
+            .flat_map(|category| {
+                self.properties
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, p)| {
+                        self.is_shown(p) && StyleCategory::from_property(&p.name) == *category
+                    })
+                    .map(|(i, _)| i)
+            })
+            .collect()
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        self.selected
+            .and_then(|row| self.display_order().get(row).copied())
+    }
+
+    /// The property at the selected row
+    pub fn selected_property(&self) -> Option<&ComputedProperty> {
+        self.selected_index().map(|i| &self.properties[i])
+    }
+
+    /// Disable or re-enable the selected declaration
+    ///
+    /// Toggling only changes the live stylesheet and is never written back.
+    /// Returns `false` if the selected property is not from a stylesheet rule.
+    pub fn toggle_selected(&mut self) -> bool {
+        let Some(index) = self.selected_index() else {
+            return false;
+        };
+        let property = &mut self.properties[index];
+        let Some(selector) = property.selector.clone() else {
+            return false;
+        };
+
+        property.disabled = !property.disabled;
+        let edit = if property.disabled {
+            StyleEdit::remove(selector, &property.name)
+        } else {
+            StyleEdit::set(selector, &property.name, &property.value)
+        };
+        self.pending_edits.push(edit.at(property.span.clone()));
+        true
+    }
+
+    /// Start editing the selected property's value
+    ///
+    /// Returns `false` if it is not from a stylesheet rule or is disabled.
+    pub fn begin_edit(&mut self) -> bool {
+        match self.selected_property() {
+            Some(property) if property.is_editable() && !property.disabled => {
+                self.editing = Some(property.value.clone());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Queue the edited value, writing it back if `write_back` is on
+    ///
+    /// An empty value cancels the edit.
+    pub fn commit_edit(&mut self) -> bool {
+        let Some(value) = self.editing.take() else {
+            return false;
+        };
+        let value = value.trim().to_string();
+        let Some(index) = self.selected_index() else {
+            return false;
+        };
+        let property = &mut self.properties[index];
+        let Some(selector) = property.selector.clone() else {
+            return false;
+        };
+        if value.is_empty() || value == property.value {
+            return false;
+        }
+
+        property.value = value.clone();
+        self.pending_edits.push(
+            StyleEdit::set(selector, &property.name, value)
+                .at(property.span.clone())
+                .write_back(self.write_back),
+        );
+        true
+    }
+
+    /// Abandon the current edit
+    pub fn cancel_edit(&mut self) {
+        self.editing = None;
+    }
+
+    /// Is a value being edited
+    pub fn is_editing(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    /// Toggle writing committed edits back to the stylesheet files
+    pub fn toggle_write_back(&mut self) {
+        self.write_back = !self.write_back;
+    }
+
+    /// Take the edits queued since the last call
+    pub fn take_edits(&mut self) -> Vec<StyleEdit> {
+        std::mem::take(&mut self.pending_edits)
+    }
+
+    /// Handle a key press
+    ///
+    /// `↑`/`↓` (or `k`/`j`) select, `Space` toggles, `Enter` edits, `w`
+    /// toggles write-back. While editing, `Enter` commits and `Esc` cancels.
+    /// Returns `true` if the key was used.
+    pub fn handle_key(&mut self, event: &KeyEvent) -> bool {
+        if let Some(buffer) = self.editing.as_mut() {
+            match event.key {
+                Key::Enter => {
+                    self.commit_edit();
+                }
+                Key::Escape => self.cancel_edit(),
+                Key::Backspace => {
+                    buffer.pop();
+                }
+                Key::Char(c) if !event.ctrl && !event.alt => buffer.push(c),
+                _ => return false,
+            }
+            return true;
+        }
+
+        match event.key {
+            Key::Up | Key::Char('k') => self.select_prev(),
+            Key::Down | Key::Char('j') => self.select_next(),
+            Key::Char(' ') => return self.toggle_selected(),
+            Key::Enter => return self.begin_edit(),
+            Key::Char('w') => self.toggle_write_back(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom::WidgetMeta;
+    use crate::style::parse_css_with_source_map;
+    use crate::widget::{RenderContext, View};
+
+    struct Button;
+    impl View for Button {
+        fn render(&self, _ctx: &mut RenderContext) {}
+        fn meta(&self) -> WidgetMeta {
+            WidgetMeta::new("Button").id("ok").class("btn")
+        }
+    }
+
+    fn inspector() -> StyleInspector {
+        let mut inspector = StyleInspector::new();
+        inspector.node = Some(DomId::new(1));
+        inspector.set_properties(vec![
+            ComputedProperty::new("width", "3")
+                .source(PropertySource::Class)
+                .rule(".btn", None),
+            ComputedProperty::new("color", "red")
+                .source(PropertySource::Class)
+                .rule(".btn", None),
+            ComputedProperty::new("opacity", "0.5").source(PropertySource::Inherited),
+        ]);
+        inspector
+    }
+
+    fn key(inspector: &mut StyleInspector, key: Key) -> bool {
+        inspector.handle_key(&KeyEvent::new(key))
+    }
+
+    #[test]
+    fn test_selection_follows_display_order() {
+        let mut inspector = inspector();
+        // Layout is rendered before Colors and Effects
+        inspector.selected = Some(0);
+        assert_eq!(inspector.selected_property().unwrap().name, "width");
+        inspector.selected = Some(1);
+        assert_eq!(inspector.selected_property().unwrap().name, "color");
+        inspector.selected = Some(2);
+        assert_eq!(inspector.selected_property().unwrap().name, "opacity");
+    }
+
+    #[test]
+    fn test_toggle_selected() {
+        let mut inspector = inspector();
+        inspector.selected = Some(1);
+
+        assert!(key(&mut inspector, Key::Char(' ')));
+        assert!(inspector.selected_property().unwrap().disabled);
+        assert!(key(&mut inspector, Key::Char(' ')));
+        assert_eq!(
+            inspector.take_edits(),
+            vec![
+                StyleEdit::remove(".btn", "color"),
+                StyleEdit::set(".btn", "color", "red"),
+            ]
+        );
+
+        // Inherited values have no rule to edit
+        inspector.selected = Some(2);
+        assert!(!inspector.toggle_selected());
+        assert!(inspector.take_edits().is_empty());
+    }
+
+    #[test]
+    fn test_edit_keys() {
+        let mut inspector = inspector();
+        key(&mut inspector, Key::Down);
+        key(&mut inspector, Key::Down);
+        assert!(key(&mut inspector, Key::Char('w')));
+        assert!(inspector.write_back);
+
+        assert!(key(&mut inspector, Key::Enter));
+        assert_eq!(inspector.editing.as_deref(), Some("red"));
+        for _ in 0..3 {
+            key(&mut inspector, Key::Backspace);
+        }
+        for c in "blue".chars() {
+            key(&mut inspector, Key::Char(c));
+        }
+        key(&mut inspector, Key::Enter);
+        assert!(!inspector.is_editing());
+        assert_eq!(inspector.selected_property().unwrap().value, "blue");
+        assert_eq!(
+            inspector.take_edits(),
+            vec![StyleEdit::set(".btn", "color", "blue").write_back(true)]
+        );
+
+        key(&mut inspector, Key::Enter);
+        key(&mut inspector, Key::Char('x'));
+        key(&mut inspector, Key::Escape);
+        assert!(!inspector.is_editing());
+        assert!(inspector.take_edits().is_empty());
+    }
+
+    #[test]
+    fn test_inspect_dom_node() {
+        let (sheet, sources) =
+            parse_css_with_source_map(".btn { color: red; width: 3; } #ok { color: blue; }")
+                .unwrap();
+        let mut dom = DomRenderer::with_stylesheet(sheet);
+        dom.set_source_map(sources.clone());
+        dom.build(&Button);
+        let id = dom.tree().root_id().unwrap();
+
+        let mut inspector = StyleInspector::new();
+        inspector.inspect(&mut dom, id);
+        assert_eq!(inspector.widget_type, "Button");
+        assert_eq!(inspector.widget_id.as_deref(), Some("ok"));
+        assert_eq!(inspector.classes, vec!["btn".to_string()]);
+
+        let colors: Vec<_> = inspector
+            .properties
+            .iter()
+            .filter(|p| p.name == "color")
+            .map(|p| (p.value.as_str(), p.source, p.overridden))
+            .collect();
+        assert_eq!(
+            colors,
+            vec![
+                ("red", PropertySource::Class, true),
+                ("blue", PropertySource::Id, false),
+            ]
+        );
+        assert_eq!(inspector.properties[0].selector.as_deref(), Some(".btn"));
+        assert_eq!(inspector.properties[0].span.as_ref().unwrap().line, 1);
+
+        // Disabled declarations survive re-inspecting the same node
+        inspector.selected = Some(0);
+        assert!(inspector.toggle_selected());
+        for edit in inspector.take_edits() {
+            edit.apply(dom.stylesheet_mut(), &sources).unwrap();
+        }
+        inspector.inspect(&mut dom, id);
+        let width = inspector
+            .properties
+            .iter()
+            .find(|p| p.name == "width")
+            .unwrap();
+        assert!(width.disabled);
+    }
+}
@@ -2,6 +2,8 @@
 
 mod computed_property;
 mod core;
+mod edit;
+mod editing;
 mod helper;
 mod impls;
 mod style_inspector;
@@ -540,4 +542,5 @@ mod types;
 mod view;
 
 pub use core::StyleInspector;
+pub use edit::{StyleEdit, StyleEditError, WrittenSource};
 pub use types::{ComputedProperty, PropertySource, StyleCategory};
@@ -27,6 +27,7 @@ impl StyleInspector {
         self.widget_id = None;
         self.widget_type.clear();
         self.selected = None;
+        self.editing = None;
     }
 
     /// Set widget info
@@ -75,23 +76,26 @@ impl StyleInspector {
     pub fn filtered(&self) -> Vec<&ComputedProperty> {
         self.properties
             .iter()
-            .filter(|p| {
-                if !self.show_inherited && p.source == PropertySource::Inherited {
-                    return false;
-                }
-                if !self.show_overridden && p.overridden {
-                    return false;
-                }
-                if let Some(cat) = self.category_filter {
-                    if StyleCategory::from_property(&p.name) != cat {
-                        return false;
-                    }
-                }
-                true
-            })
+            .filter(|p| self.is_shown(p))
             .collect()
     }
 
+    /// Whether a property passes the current filters
+    pub(super) fn is_shown(&self, p: &ComputedProperty) -> bool {
+        if !self.show_inherited && p.source == PropertySource::Inherited {
+            return false;
+        }
+        if !self.show_overridden && p.overridden {
+            return false;
+        }
+        if let Some(cat) = self.category_filter {
+            if StyleCategory::from_property(&p.name) != cat {
+                return false;
+            }
+        }
+        true
+    }
+
     /// Select next property
     pub fn select_next(&mut self) {
         let count = self.filtered().len();
@@ -1,5 +1,7 @@
 //! Type definitions for style inspector
 
+use crate::style::SourceSpan;
+
 /// Computed CSS property
 #[derive(Debug, Clone)]
 pub struct ComputedProperty {
@@ -11,6 +13,12 @@ pub struct ComputedProperty {
     pub source: PropertySource,
     /// Is overridden by higher specificity
     pub overridden: bool,
+    /// Selector of the rule declaring it, if it came from a stylesheet
+    pub selector: Option<String>,
+    /// Source span of that rule
+    pub span: Option<SourceSpan>,
+    /// Removed from the stylesheet by the inspector, can be toggled back on
+    pub disabled: bool,
 }
 
 /// Source of a CSS property
@@ -39,6 +39,11 @@ impl StyleInspector {
             y += 1;
         }
 
+        if self.write_back {
+            ctx.draw_text(y, "✎ edits write back to source", config.accent_color);
+            y += 1;
+        }
+
         // Properties by category
         let filtered = self.filtered();
         if filtered.is_empty() {
@@ -78,7 +83,8 @@ impl StyleInspector {
                         }
 
                         let is_selected = self.selected == Some(prop_idx);
-                        Self::render_property(&mut ctx, 2, y, prop, is_selected);
+                        let editing = self.editing.as_deref().filter(|_| is_selected);
+                        Self::render_property(&mut ctx, 2, y, prop, is_selected, editing);
                         y += 1;
                         prop_idx += 1;
                     }
@@ -97,14 +103,22 @@ impl StyleInspector {
         y: u16,
         prop: &super::types::ComputedProperty,
         selected: bool,
+        editing: Option<&str>,
     ) {
-        let source_icon = prop.source.icon();
+        let source_icon = if prop.disabled {
+            "✗"
+        } else {
+            prop.source.icon()
+        };
         let strike = if prop.overridden { "̶" } else { "" };
-        let line = format!("{} {}{}: {}", source_icon, prop.name, strike, prop.value);
+        let line = match editing {
+            Some(buffer) => format!("{} {}: {}▏", source_icon, prop.name, buffer),
+            None => format!("{} {}{}: {}", source_icon, prop.name, strike, prop.value),
+        };
 
         let fg = if selected {
             ctx.config.bg_color
-        } else if prop.overridden {
+        } else if prop.overridden || prop.disabled {
             PLACEHOLDER_FG
         } else {
             ctx.config.fg_color
@@ -188,12 +202,18 @@ mod tests {
                 value: "red".to_string(),
                 source: PropertySource::Class,
                 overridden: false,
+                selector: None,
+                span: None,
+                disabled: false,
             },
             ComputedProperty {
                 name: "background".to_string(),
                 value: "blue".to_string(),
                 source: PropertySource::Inline,
                 overridden: false,
+                selector: None,
+                span: None,
+                disabled: false,
             },
         ];
 
@@ -229,6 +249,9 @@ mod tests {
             value: "red".to_string(),
             source: PropertySource::Class,
             overridden: false,
+            selector: None,
+            span: None,
+            disabled: false,
         }];
         inspector.selected = Some(0);
 
@@ -240,6 +263,30 @@ mod tests {
         inspector.render_content(&mut buffer, area, &config);
     }
 
+    #[test]
+    fn test_render_content_while_editing() {
+        use crate::devtools::style::types::ComputedProperty;
+
+        let mut inspector = StyleInspector::new();
+        inspector.widget_type = "Button".to_string();
+        inspector.properties = vec![ComputedProperty::new("color", "red").rule(".btn", None)];
+        inspector.selected = Some(0);
+        inspector.write_back = true;
+        inspector.begin_edit();
+        inspector.editing = Some("blu".to_string());
+
+        let mut buffer = Buffer::new(40, 10);
+        let config = DevToolsConfig::default();
+        inspector.render_content(&mut buffer, Rect::new(0, 0, 40, 10), &config);
+
+        let text: String = (0..10)
+            .flat_map(|y| (0..40).map(move |x| (x, y)))
+            .filter_map(|(x, y)| buffer.get(x, y).map(|c| c.symbol))
+            .collect();
+        assert!(text.contains("write back"));
+        assert!(text.contains("color: blu▏"));
+    }
+
     #[test]
     fn test_render_content_with_overridden_property() {
         use crate::devtools::style::types::{ComputedProperty, PropertySource};
@@ -251,6 +298,9 @@ mod tests {
             value: "red".to_string(),
             source: PropertySource::Class,
             overridden: true,
+            selector: None,
+            span: None,
+            disabled: false,
         }];
 
         let mut buffer = Buffer::new(80, 24);
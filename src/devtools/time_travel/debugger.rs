@@ -2,7 +2,10 @@
 
 use super::super::helpers::draw_text_overlay;
 use super::super::DevToolsConfig;
-use super::{Action, SnapshotValue, StateDiff, StateSnapshot, TimeTravelConfig, TimeTravelView};
+use super::{
+    Action, SnapshotValue, StateDiff, StateSnapshot, TimeTravelConfig, TimeTravelRecorder,
+    TimeTravelView,
+};
 use crate::layout::Rect;
 use crate::render::Buffer;
 use crate::style::Color;
@@ -31,6 +34,8 @@ pub struct TimeTravelDebugger {
     last_snapshot: Option<Instant>,
     /// Is "traveling" (viewing past state)
     is_traveling: bool,
+    /// Records live signal changes and restores state when traveling
+    recorder: Option<TimeTravelRecorder>,
 }
 
 impl TimeTravelDebugger {
@@ -47,6 +52,7 @@ impl TimeTravelDebugger {
             selected: None,
             last_snapshot: None,
             is_traveling: false,
+            recorder: None,
         }
     }
 
@@ -79,6 +85,11 @@ impl TimeTravelDebugger {
             }
         }
 
+        self.push(snapshot);
+    }
+
+    /// Append a snapshot, bypassing the rate limit
+    fn push(&mut self, snapshot: StateSnapshot) {
         // If we're traveling in history, truncate future snapshots
         if self.is_traveling && self.position < self.snapshots.len() {
             self.snapshots.truncate(self.position + 1);
@@ -115,6 +126,54 @@ impl TimeTravelDebugger {
         self.record(snapshot);
     }
 
+    /// Attach a recorder of live signal changes
+    ///
+    /// Its current state is recorded as an `initial` snapshot. From then on
+    /// [`sync`](Self::sync) adds every recorded mutation (bypassing
+    /// `record_interval`), and moving through history writes the values of
+    /// the current snapshot back into the tracked signals.
+    pub fn attach(&mut self, recorder: TimeTravelRecorder) {
+        let _ = recorder.take_pending();
+        if !self.paused {
+            self.push(recorder.snapshot().with_label("initial"));
+        }
+        self.recorder = Some(recorder);
+    }
+
+    /// Detach the recorder; history is kept but no longer restored
+    pub fn detach(&mut self) -> Option<TimeTravelRecorder> {
+        self.recorder.take()
+    }
+
+    /// Get the attached recorder
+    pub fn recorder(&self) -> Option<&TimeTravelRecorder> {
+        self.recorder.as_ref()
+    }
+
+    /// Add the mutations recorded since the last call
+    ///
+    /// Call once per frame while a recorder is attached. Mutations made while
+    /// paused are dropped.
+    pub fn sync(&mut self) {
+        let Some(recorder) = self.recorder.as_ref() else {
+            return;
+        };
+        let pending = recorder.take_pending();
+        if self.paused {
+            return;
+        }
+        for snapshot in pending {
+            self.push(snapshot);
+        }
+    }
+
+    /// Write the current snapshot into the attached recorder's signals
+    fn restore_current(&self) {
+        if let (Some(recorder), Some(snapshot)) = (&self.recorder, self.current()) {
+            recorder.restore(snapshot);
+        }
+    }
+
     /// Pause recording
     pub fn pause(&mut self) {
         self.paused = true;
@@ -174,16 +233,20 @@ impl TimeTravelDebugger {
 
     /// Step backward one snapshot
     pub fn step_back(&mut self) {
+        self.sync();
         if self.position > 0 {
             self.position -= 1;
             self.is_traveling = true;
+            self.restore_current();
         }
     }
 
     /// Step forward one snapshot
     pub fn step_forward(&mut self) {
+        self.sync();
         if self.position < self.snapshots.len().saturating_sub(1) {
             self.position += 1;
+            self.restore_current();
         }
         if self.position == self.snapshots.len().saturating_sub(1) {
             self.is_traveling = false;
@@ -192,25 +255,31 @@ impl TimeTravelDebugger {
 
     /// Jump to specific snapshot
     pub fn jump_to(&mut self, index: usize) {
+        self.sync();
         if index < self.snapshots.len() {
             self.position = index;
             self.is_traveling = index < self.snapshots.len().saturating_sub(1);
+            self.restore_current();
         }
     }
 
     /// Jump to latest snapshot
     pub fn jump_to_latest(&mut self) {
+        self.sync();
         if !self.snapshots.is_empty() {
             self.position = self.snapshots.len() - 1;
             self.is_traveling = false;
+            self.restore_current();
         }
     }
 
     /// Jump to first snapshot
     pub fn jump_to_first(&mut self) {
+        self.sync();
         if !self.snapshots.is_empty() {
             self.position = 0;
             self.is_traveling = true;
+            self.restore_current();
         }
     }
 
@@ -289,6 +358,43 @@ impl TimeTravelDebugger {
         }
     }
 
+    /// Save the full session to a JSON file for sharing
+    ///
+    /// Unlike [`export`](Self::export), every snapshot's state and action is
+    /// kept so [`load_session`](Self::load_session) can replay it.
+    #[cfg(feature = "devtools")]
+    pub fn save_session(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let session = Session {
+            position: self.position,
+            snapshots: self.snapshots.clone(),
+        };
+        let json = serde_json::to_string_pretty(&session)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(path, json)
+    }
+
+    /// Load a session saved with [`save_session`](Self::save_session)
+    ///
+    /// Replaces the current history and, if a recorder is attached, restores
+    /// the saved position into the live app.
+    #[cfg(feature = "devtools")]
+    pub fn load_session(&mut self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let json = std::fs::read_to_string(path)?;
+        let session: Session = serde_json::from_str(&json)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        if let Some(recorder) = &self.recorder {
+            let _ = recorder.take_pending();
+        }
+        self.import(session.snapshots);
+        if !self.snapshots.is_empty() {
+            self.position = session.position.min(self.snapshots.len() - 1);
+            self.is_traveling = self.position < self.snapshots.len() - 1;
+            self.restore_current();
+        }
+        Ok(())
+    }
+
     // -------------------------------------------------------------------------
     // View
     // -------------------------------------------------------------------------
@@ -608,6 +714,14 @@ impl TimeTravelDebugger {
     }
 }
 
+/// On-disk format of [`TimeTravelDebugger::save_session`]
+#[cfg(feature = "devtools")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Session {
+    position: usize,
+    snapshots: Vec<StateSnapshot>,
+}
+
 impl Default for TimeTravelDebugger {
     fn default() -> Self {
         Self::new()
@@ -12,11 +12,33 @@
 //! - Export/import session history
 //! - Configurable snapshot limits
 //! - Pause/resume recording
+//! - Automatic recording of named signals and `#[store(time_travel)]` stores
+//!   via [`TimeTravelRecorder`], restoring live state when stepping
+//!
+//! # Recording live state
+//!
+//! ```rust,ignore
+//! #[derive(Store)]
+//! #[store(time_travel)]
+//! struct Counter {
+//!     count: Signal<i32>,
+//! }
+//!
+//! let recorder = TimeTravelRecorder::new();
+//! recorder.track_store(&counter);
+//! debugger.attach(recorder.clone());
+//!
+//! recorder.action("increment", || counter.count.update(|c| *c += 1));
+//! debugger.sync(); // once per frame
+//! debugger.step_back(); // counter.count is 0 again
+//! ```
 
 mod debugger;
+mod recorder;
 mod types;
 
 pub use debugger::TimeTravelDebugger;
+pub use recorder::{TimeTravelRecorder, TimeTravelValue, TrackTimeTravel};
 pub use types::{
     Action, SnapshotValue, StateDiff, StateSnapshot, TimeTravelConfig, TimeTravelView,
 };
@@ -660,4 +682,72 @@ mod tests {
         tt.step_back();
         assert_eq!(tt.position(), 0);
     }
+
+    #[test]
+    fn test_time_travel_attach_ignores_rate_limit() {
+        let count = crate::reactive::signal(0);
+        let recorder = TimeTravelRecorder::new();
+        recorder.track(&count, "count");
+        let mut tt = TimeTravelDebugger::new();
+        tt.attach(recorder.clone());
+
+        count.set(1);
+        count.set(2);
+        tt.sync();
+        assert_eq!(tt.snapshots().len(), 3);
+        assert_eq!(tt.get(0).unwrap().label.as_deref(), Some("initial"));
+
+        tt.pause();
+        count.set(3);
+        tt.sync();
+        assert_eq!(tt.snapshots().len(), 3);
+
+        tt.step_back();
+        assert_eq!(count.get(), 1);
+        tt.detach();
+        tt.step_back();
+        assert_eq!(count.get(), 1);
+    }
+
+    #[cfg(feature = "devtools")]
+    #[test]
+    fn test_time_travel_session_round_trip() {
+        let count = crate::reactive::signal(0i64);
+        let name = crate::reactive::signal(String::new());
+        let recorder = TimeTravelRecorder::new();
+        recorder.track(&count, "count");
+        recorder.track(&name, "name");
+        let mut tt = TimeTravelDebugger::new();
+        tt.attach(recorder.clone());
+        recorder.action("rename", || name.set("a".into()));
+        count.set(7);
+        tt.sync();
+        tt.step_back();
+
+        let path =
+            std::env::temp_dir().join(format!("revue_time_travel_{}.json", std::process::id()));
+        tt.save_session(&path).unwrap();
+
+        count.set(0);
+        name.set(String::new());
+        let mut loaded = TimeTravelDebugger::new();
+        loaded.attach(recorder);
+        loaded.load_session(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.snapshots().len(), 3);
+        assert_eq!(loaded.position(), 1);
+        assert!(loaded.is_traveling());
+        let action = loaded.current().unwrap().action.as_ref().unwrap();
+        assert_eq!(action.name, "rename");
+        assert_eq!(action.payload, Some(SnapshotValue::from("a")));
+        assert_eq!((count.get(), name.get()), (0, "a".to_string()));
+        loaded.step_forward();
+        assert_eq!(count.get(), 7);
+
+        std::fs::write(&path, "not json").unwrap();
+        let err = loaded.load_session(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
 }
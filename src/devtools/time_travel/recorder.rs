@@ -0,0 +1,453 @@
+//! Automatic recording of signal and store mutations
+//!
+//! A [`TimeTravelRecorder`] subscribes to named signals and turns every
+//! change into a [`StateSnapshot`] holding the value of all tracked signals.
+//! Attached to a [`TimeTravelDebugger`](super::TimeTravelDebugger), the
+//! snapshots land in its history and stepping through it writes the recorded
+//! values back into the signals.
+//!
+//! ```rust,ignore
+//! use revue::devtools::{TimeTravelDebugger, TimeTravelRecorder};
+//! use revue::reactive::signal;
+//!
+//! let count = signal(0);
+//! let recorder = TimeTravelRecorder::new();
+//! recorder.track(&count, "count");
+//!
+//! let mut debugger = TimeTravelDebugger::new();
+//! debugger.attach(recorder.clone());
+//!
+//! recorder.action("increment", || count.update(|c| *c += 1));
+//! debugger.sync();
+//! debugger.step_back(); // count is 0 again
+//! ```
+//!
+//! Stores deriving `Store` with `#[store(time_travel)]` implement
+//! [`TrackTimeTravel`], tracking every `Signal` field as `Store.field`.
+
+use super::{Action, SnapshotValue, StateSnapshot};
+use crate::reactive::{batch, batch_id, Signal, Subscription};
+use crate::utils::lock::lock_or_recover;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+/// Writes a recorded value back into its signal
+type RestoreFn = Arc<dyn Fn(&SnapshotValue) + Send + Sync>;
+
+/// Values that can be recorded and restored by a [`TimeTravelRecorder`]
+pub trait TimeTravelValue: Clone + PartialEq + Send + Sync + 'static {
+    /// Convert to a snapshot value
+    fn to_snapshot(&self) -> SnapshotValue;
+
+    /// Convert back, or `None` if the value has the wrong shape
+    fn from_snapshot(value: &SnapshotValue) -> Option<Self>;
+}
+
+/// Types whose state can be recorded by a [`TimeTravelRecorder`]
+///
+/// Implemented by `#[derive(Store)]` when the store is marked with
+/// `#[store(time_travel)]`.
+pub trait TrackTimeTravel {
+    /// Register all recordable state with the recorder
+    fn track_time_travel(&self, recorder: &TimeTravelRecorder);
+}
+
+struct State {
+    /// Current value of every tracked signal
+    values: HashMap<String, SnapshotValue>,
+    restorers: HashMap<String, RestoreFn>,
+    /// Snapshots not yet taken by the debugger
+    pending: Vec<StateSnapshot>,
+    /// Batch of the last pending snapshot, to merge changes made together
+    last_batch: Option<usize>,
+    /// Open `action()` label and nesting depth
+    action: Option<(String, usize)>,
+}
+
+impl State {
+    fn record(&mut self, name: &str, value: SnapshotValue) {
+        self.values.insert(name.to_string(), value.clone());
+
+        let batch = batch_id();
+        if batch.is_some() && batch == self.last_batch {
+            if let Some(snapshot) = self.pending.last_mut() {
+                snapshot.state = self.values.clone();
+                if let Some(action) = snapshot.action.as_mut() {
+                    action.payload = None;
+                    let source = action.source.get_or_insert_with(String::new);
+                    if !source.split(", ").any(|s| s == name) {
+                        source.push_str(", ");
+                        source.push_str(name);
+                    }
+                }
+                return;
+            }
+        }
+
+        let label = match &self.action {
+            Some((label, _)) => label.clone(),
+            None => name.to_string(),
+        };
+        let action = Action::new(label).with_source(name).with_payload(value);
+        self.pending.push(StateSnapshot {
+            state: self.values.clone(),
+            ..StateSnapshot::new(0).with_action(action)
+        });
+        self.last_batch = batch;
+    }
+}
+
+struct Inner {
+    state: Mutex<State>,
+    /// Set while recorded values are written back, so those writes aren't recorded
+    restoring: AtomicBool,
+    subscriptions: Mutex<Vec<Subscription>>,
+}
+
+/// Records every change of tracked signals as a time-travel snapshot
+///
+/// `TimeTravelRecorder` is cheap to clone; clones share the same state.
+/// Recording stops when the last clone is dropped.
+#[derive(Clone)]
+pub struct TimeTravelRecorder {
+    inner: Arc<Inner>,
+}
+
+impl TimeTravelRecorder {
+    /// Create a recorder tracking nothing
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                state: Mutex::new(State {
+                    values: HashMap::new(),
+                    restorers: HashMap::new(),
+                    pending: Vec::new(),
+                    last_batch: None,
+                    action: None,
+                }),
+                restoring: AtomicBool::new(false),
+                subscriptions: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Record changes to a signal under `name`
+    ///
+    /// Setting a signal to an equal value is not recorded. Tracking another
+    /// signal under the same name replaces the restore target.
+    pub fn track<T: TimeTravelValue>(&self, signal: &Signal<T>, name: impl Into<String>) {
+        let name = name.into();
+        let initial = signal.with_untracked(T::clone);
+        let last = Mutex::new(initial.clone());
+
+        let writer = signal.clone();
+        let restore: RestoreFn = Arc::new(move |value: &SnapshotValue| {
+            if let Some(value) = T::from_snapshot(value) {
+                writer.set(value);
+            }
+        });
+        {
+            let mut state = lock_or_recover(&self.inner.state);
+            state.values.insert(name.clone(), initial.to_snapshot());
+            state.restorers.insert(name.clone(), restore);
+        }
+
+        let weak: Weak<Inner> = Arc::downgrade(&self.inner);
+        let watched = signal.clone();
+        let subscription = signal.subscribe(move || {
+            let Some(inner) = weak.upgrade() else {
+                return;
+            };
+            let new = watched.with_untracked(T::clone);
+            let old = std::mem::replace(&mut *lock_or_recover(&last), new.clone());
+            if old == new {
+                return;
+            }
+            let mut state = lock_or_recover(&inner.state);
+            if inner.restoring.load(Ordering::SeqCst) {
+                state.values.insert(name.clone(), new.to_snapshot());
+            } else {
+                state.record(&name, new.to_snapshot());
+            }
+        });
+
+        lock_or_recover(&self.inner.subscriptions).push(subscription);
+    }
+
+    /// Track all recordable state of a store
+    pub fn track_store(&self, store: &impl TrackTimeTravel) {
+        store.track_time_travel(self);
+    }
+
+    /// Run `f` as one named action
+    ///
+    /// All tracked changes made inside `f` (including nested `action()`
+    /// calls) become one snapshot labeled `name`. Updates are batched.
+    pub fn action<R>(&self, name: impl Into<String>, f: impl FnOnce() -> R) -> R {
+        {
+            let mut state = lock_or_recover(&self.inner.state);
+            match state.action.as_mut() {
+                Some((_, depth)) => *depth += 1,
+                None => state.action = Some((name.into(), 1)),
+            }
+        }
+
+        let result = batch(f);
+
+        let mut state = lock_or_recover(&self.inner.state);
+        if let Some((_, depth)) = state.action.as_mut() {
+            *depth -= 1;
+            if *depth == 0 {
+                state.action = None;
+            }
+        }
+        result
+    }
+
+    /// Names of the tracked signals, sorted
+    pub fn tracked(&self) -> Vec<String> {
+        let state = lock_or_recover(&self.inner.state);
+        let mut names: Vec<String> = state.values.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Snapshot of the current value of every tracked signal
+    pub fn snapshot(&self) -> StateSnapshot {
+        let state = lock_or_recover(&self.inner.state);
+        StateSnapshot {
+            state: state.values.clone(),
+            ..StateSnapshot::new(0)
+        }
+    }
+
+    /// Take the snapshots recorded since the last call
+    pub fn take_pending(&self) -> Vec<StateSnapshot> {
+        let mut state = lock_or_recover(&self.inner.state);
+        state.last_batch = None;
+        std::mem::take(&mut state.pending)
+    }
+
+    /// Write the values of a snapshot back into the tracked signals
+    ///
+    /// Keys that are not tracked, or whose value no longer fits the signal's
+    /// type, are skipped. The writes are not recorded.
+    pub fn restore(&self, snapshot: &StateSnapshot) {
+        let targets: Vec<(RestoreFn, SnapshotValue)> = {
+            let state = lock_or_recover(&self.inner.state);
+            snapshot
+                .state
+                .iter()
+                .filter(|(name, value)| state.values.get(*name) != Some(value))
+                .filter_map(|(name, value)| {
+                    let restore = state.restorers.get(name)?;
+                    Some((Arc::clone(restore), value.clone()))
+                })
+                .collect()
+        };
+        if targets.is_empty() {
+            return;
+        }
+
+        self.inner.restoring.store(true, Ordering::SeqCst);
+        batch(|| {
+            for (restore, value) in &targets {
+                restore(value);
+            }
+        });
+        self.inner.restoring.store(false, Ordering::SeqCst);
+    }
+}
+
+impl Default for TimeTravelRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// =============================================================================
+// TimeTravelValue implementations
+// =============================================================================
+
+impl TimeTravelValue for bool {
+    fn to_snapshot(&self) -> SnapshotValue {
+        SnapshotValue::Bool(*self)
+    }
+
+    fn from_snapshot(value: &SnapshotValue) -> Option<Self> {
+        match value {
+            SnapshotValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_time_travel_int {
+    ($($ty:ty),*) => {
+        $(
+            impl TimeTravelValue for $ty {
+                fn to_snapshot(&self) -> SnapshotValue {
+                    SnapshotValue::Int(*self as i64)
+                }
+
+                fn from_snapshot(value: &SnapshotValue) -> Option<Self> {
+                    match value {
+                        SnapshotValue::Int(i) => <$ty>::try_from(*i).ok(),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_time_travel_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl TimeTravelValue for f32 {
+    fn to_snapshot(&self) -> SnapshotValue {
+        SnapshotValue::Float(*self as f64)
+    }
+
+    fn from_snapshot(value: &SnapshotValue) -> Option<Self> {
+        f64::from_snapshot(value).map(|f| f as f32)
+    }
+}
+
+impl TimeTravelValue for f64 {
+    fn to_snapshot(&self) -> SnapshotValue {
+        SnapshotValue::Float(*self)
+    }
+
+    fn from_snapshot(value: &SnapshotValue) -> Option<Self> {
+        match value {
+            SnapshotValue::Float(f) => Some(*f),
+            SnapshotValue::Int(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+}
+
+impl TimeTravelValue for String {
+    fn to_snapshot(&self) -> SnapshotValue {
+        SnapshotValue::String(self.clone())
+    }
+
+    fn from_snapshot(value: &SnapshotValue) -> Option<Self> {
+        match value {
+            SnapshotValue::String(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl<T: TimeTravelValue> TimeTravelValue for Option<T> {
+    fn to_snapshot(&self) -> SnapshotValue {
+        match self {
+            Some(value) => value.to_snapshot(),
+            None => SnapshotValue::Null,
+        }
+    }
+
+    fn from_snapshot(value: &SnapshotValue) -> Option<Self> {
+        match value {
+            SnapshotValue::Null => Some(None),
+            value => T::from_snapshot(value).map(Some),
+        }
+    }
+}
+
+impl<T: TimeTravelValue> TimeTravelValue for Vec<T> {
+    fn to_snapshot(&self) -> SnapshotValue {
+        SnapshotValue::Array(self.iter().map(T::to_snapshot).collect())
+    }
+
+    fn from_snapshot(value: &SnapshotValue) -> Option<Self> {
+        match value {
+            SnapshotValue::Array(items) => items.iter().map(T::from_snapshot).collect(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactive::signal;
+
+    #[test]
+    fn test_records_each_change() {
+        let count = signal(0);
+        let name = signal(String::from("a"));
+        let recorder = TimeTravelRecorder::new();
+        recorder.track(&count, "count");
+        recorder.track(&name, "name");
+        assert_eq!(recorder.tracked(), vec!["count", "name"]);
+
+        count.set(1);
+        count.set(1); // unchanged, not recorded
+        name.set("b".into());
+
+        let pending = recorder.take_pending();
+        assert_eq!(pending.len(), 2);
+        let action = pending[0].action.as_ref().unwrap();
+        assert_eq!(action.name, "count");
+        assert_eq!(action.payload, Some(SnapshotValue::Int(1)));
+        assert_eq!(pending[1].state["count"], SnapshotValue::Int(1));
+        assert_eq!(pending[1].state["name"], SnapshotValue::from("b"));
+        assert!(recorder.take_pending().is_empty());
+    }
+
+    #[test]
+    fn test_action_groups_changes() {
+        let a = signal(0);
+        let b = signal(0);
+        let recorder = TimeTravelRecorder::new();
+        recorder.track(&a, "a");
+        recorder.track(&b, "b");
+
+        recorder.action("reset", || {
+            a.set(5);
+            recorder.action("nested", || b.set(6));
+        });
+
+        let pending = recorder.take_pending();
+        assert_eq!(pending.len(), 1);
+        let action = pending[0].action.as_ref().unwrap();
+        assert_eq!(action.name, "reset");
+        assert_eq!(action.source.as_deref(), Some("a, b"));
+        assert_eq!(action.payload, None);
+        assert_eq!(pending[0].state["b"], SnapshotValue::Int(6));
+    }
+
+    #[test]
+    fn test_restore_is_not_recorded() {
+        let items = signal(vec![1u8, 2]);
+        let recorder = TimeTravelRecorder::new();
+        recorder.track(&items, "items");
+        let initial = recorder.snapshot();
+
+        items.update(|v| v.push(3));
+        assert_eq!(recorder.take_pending().len(), 1);
+
+        recorder.restore(&initial);
+        assert_eq!(items.get(), vec![1, 2]);
+        assert!(recorder.take_pending().is_empty());
+        assert_eq!(recorder.snapshot().state, initial.state);
+
+        // Values of the wrong shape are skipped
+        recorder.restore(&StateSnapshot::new(0).with_state("items", SnapshotValue::Int(1)));
+        assert_eq!(items.get(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_value_conversions() {
+        assert_eq!(u8::from_snapshot(&SnapshotValue::Int(300)), None);
+        assert_eq!(f32::from_snapshot(&SnapshotValue::Int(2)), Some(2.0));
+        assert_eq!(
+            Option::<i32>::from_snapshot(&SnapshotValue::Null),
+            Some(None)
+        );
+        assert_eq!(Some(3i32).to_snapshot(), SnapshotValue::Int(3));
+    }
+}
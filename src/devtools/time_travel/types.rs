@@ -5,6 +5,7 @@ use std::time::{Duration, SystemTime};
 
 /// A snapshot of application state at a point in time
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "devtools", derive(serde::Serialize, serde::Deserialize))]
 pub struct StateSnapshot {
     /// Unique snapshot ID
     pub id: u64,
@@ -84,6 +85,11 @@ impl StateSnapshot {
 
 /// Value stored in a snapshot
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "devtools",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(untagged)
+)]
 pub enum SnapshotValue {
     /// Null value
     Null,
@@ -190,6 +196,7 @@ impl StateDiff {
 
 /// An action that caused a state change
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "devtools", derive(serde::Serialize, serde::Deserialize))]
 pub struct Action {
     /// Action type/name
     pub name: String,
@@ -256,7 +256,7 @@ pub mod utils;
 pub mod widget;
 
 // Re-export derive macros
-pub use revue_macros::Store;
+pub use revue_macros::{view, Component, Store};
 
 /// Error type for Revue operations.
 ///
@@ -445,7 +445,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// - [`worker::WorkerPool`], [`worker::WorkerHandle`] - Background tasks
 pub mod prelude {
     // Macros
-    pub use crate::Store;
+    pub use crate::{view, Component, Store};
 
     // App
     pub use crate::core::app::App;
@@ -810,7 +810,8 @@ pub mod prelude {
         disable_devtools, enable_devtools, is_devtools_enabled, toggle_devtools, ComputedProperty,
         DevTools, DevToolsConfig, DevToolsPosition, DevToolsTab, EventFilter, EventLogger,
         EventType, Inspector, InspectorConfig, LoggedEvent, PropertySource, StateDebugger,
-        StateEntry, StateValue, StyleCategory, StyleInspector, WidgetNode,
+        StateEntry, StateValue, StyleCategory, StyleEdit, StyleInspector, TimeTravelRecorder,
+        WidgetNode,
     };
 
     // Profiler
@@ -13,6 +13,11 @@ pub struct WidgetMeta {
     pub id: Option<String>,
     /// CSS classes (e.g., ["primary", "large"])
     pub classes: HashSet<String>,
+    /// Reconciliation key, unique among siblings (e.g., a list item's ID)
+    ///
+    /// Keyed nodes keep their identity when siblings are inserted,
+    /// removed or reordered, instead of being matched by position.
+    pub key: Option<String>,
 }
 
 impl WidgetMeta {
@@ -22,6 +27,7 @@ impl WidgetMeta {
             widget_type: widget_type.into(),
             id: None,
             classes: HashSet::new(),
+            key: None,
         }
     }
 
@@ -53,6 +59,12 @@ impl WidgetMeta {
     pub fn has_class(&self, class: &str) -> bool {
         self.classes.contains(class)
     }
+
+    /// Set reconciliation key
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
 }
 
 /// Node state for pseudo-class matching
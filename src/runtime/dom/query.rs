@@ -197,6 +197,26 @@ impl DomTree {
         id
     }
 
+    /// Replace a node's child list, updating sibling positions
+    ///
+    /// Children whose position changed are marked dirty so they get
+    /// repainted at their new location.
+    pub fn set_children(&mut self, parent_id: DomId, children: Vec<DomId>) {
+        let count = children.len();
+        for (idx, &child_id) in children.iter().enumerate() {
+            if let Some(child) = self.nodes.get_mut(&child_id) {
+                if child.state.child_index != idx || child.state.sibling_count != count {
+                    child.state.dirty = true;
+                }
+                child.state.update_position(idx, count);
+                child.parent = Some(parent_id);
+            }
+        }
+        if let Some(parent) = self.nodes.get_mut(&parent_id) {
+            parent.children = children;
+        }
+    }
+
     /// Remove a node and its children
     pub fn remove(&mut self, id: DomId) {
         // Collect info we need before modifying (convert to Arc<str> for lookup)
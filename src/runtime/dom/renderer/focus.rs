@@ -2,12 +2,17 @@
 
 use crate::dom::query::Query;
 use crate::dom::renderer::types::DomRenderer;
+use crate::dom::DomId;
 
 impl DomRenderer {
     /// Set focused node by element ID
     pub fn set_focus(&mut self, element_id: Option<&str>) {
         let new_focus_id = element_id.and_then(|id| self.tree.get_by_id(id).map(|node| node.id));
+        self.set_focus_node(new_focus_id);
+    }
 
+    /// Set focused node by DOM ID
+    pub fn set_focus_node(&mut self, new_focus_id: Option<DomId>) {
         // Mark old focused node as dirty
         if let Some(old_id) = self.focused {
             if Some(old_id) != new_focus_id {
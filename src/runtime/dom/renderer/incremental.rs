@@ -90,8 +90,8 @@ fn update_node_meta_internal(
         return false;
     }
 
-    // Check if element ID matches (if present)
-    if node.meta.id != new_meta.id {
+    // Check if element ID and key match (if present)
+    if node.meta.id != new_meta.id || node.meta.key != new_meta.key {
         return false;
     }
 
@@ -110,6 +110,10 @@ fn update_node_meta_internal(
 }
 
 /// Standalone function to recursively update children
+///
+/// Children are matched to existing nodes by key, then by element ID, then by
+/// position and type. Keyed and ID-matched nodes keep their `DomId` (and with
+/// it focus, hover and cached style) when siblings are inserted or reordered.
 fn update_children_internal(
     renderer: &mut DomRenderer,
     parent_id: DomId,
@@ -122,21 +126,30 @@ fn update_children_internal(
         .map(|n| n.children.clone())
         .unwrap_or_default();
 
-    // Build ID lookup map for efficient matching
+    // Build key and ID lookup maps for efficient matching
+    let mut old_by_key: std::collections::HashMap<String, DomId> = std::collections::HashMap::new();
     let mut old_by_id: std::collections::HashMap<String, DomId> = std::collections::HashMap::new();
 
     for &child_id in &old_children {
         if let Some(node) = renderer.tree.get(child_id) {
+            if let Some(ref key) = node.meta.key {
+                old_by_key.insert(key.clone(), child_id);
+            }
             if let Some(ref id) = node.meta.id {
                 old_by_id.insert(id.clone(), child_id);
             }
         }
     }
 
-    // Collect widget types for positional matching (need owned strings)
-    let old_types: Vec<String> = old_children
+    // Collect widget types and keys for positional matching (need owned strings)
+    let old_types: Vec<(String, bool)> = old_children
         .iter()
-        .filter_map(|&id| renderer.tree.get(id).map(|n| n.meta.widget_type.clone()))
+        .filter_map(|&id| {
+            renderer
+                .tree
+                .get(id)
+                .map(|n| (n.meta.widget_type.clone(), n.meta.key.is_some()))
+        })
         .collect();
 
     let mut matched_old: std::collections::HashSet<DomId> = std::collections::HashSet::new();
@@ -146,15 +159,18 @@ fn update_children_internal(
         let child_meta = child_view.meta();
 
         // Try to find matching existing node
-        let matched_id = if let Some(ref id) = child_meta.id {
-            // Match by element ID (highest priority)
+        let matched_id = if let Some(ref key) = child_meta.key {
+            // Match by key (highest priority); keyed children never match by position
+            old_by_key.get(key).copied()
+        } else if let Some(ref id) = child_meta.id {
+            // Match by element ID
             old_by_id.get(id).copied()
         } else {
-            // Match by position and type
+            // Match by position and type (unkeyed nodes only)
             old_children.get(pos).and_then(|&old_id| {
                 if !matched_old.contains(&old_id) {
-                    let old_type = old_types.get(pos)?;
-                    if old_type == &child_meta.widget_type {
+                    let (old_type, old_keyed) = old_types.get(pos)?;
+                    if !old_keyed && old_type == &child_meta.widget_type {
                         Some(old_id)
                     } else {
                         None
@@ -205,10 +221,8 @@ fn update_children_internal(
         }
     }
 
-    // Update parent's children list
-    if let Some(parent) = renderer.tree.get_mut(parent_id) {
-        parent.children = new_child_ids;
-    }
+    // Update parent's children list and sibling positions
+    renderer.tree.set_children(parent_id, new_child_ids);
 }
 
 /// Standalone function to collect all descendant node IDs
@@ -386,6 +386,32 @@ mod tests {
             assert!(renderer.get_by_id("second").is_none());
         }
 
+        #[test]
+        fn test_incremental_build_matches_keyed_children_across_reorder() {
+            use crate::reactive::signal_vec;
+            use crate::widget::each;
+
+            let items = signal_vec(vec![1, 2, 3]);
+            let mut list = each(
+                items.clone(),
+                |n| n.to_string(),
+                |n| Text::new(n.to_string()),
+            );
+
+            let mut renderer = DomRenderer::new_internal();
+            renderer.build(&list);
+            let root_id = renderer.tree.root_id().unwrap();
+            let before = renderer.tree.get(root_id).unwrap().children.clone();
+
+            items.move_item(2, 0);
+            assert!(list.sync());
+            renderer.build(&list);
+
+            let after = renderer.tree.get(root_id).unwrap().children.clone();
+            assert_eq!(after, vec![before[2], before[0], before[1]]);
+            assert_eq!(renderer.tree.get(before[2]).unwrap().state.child_index, 0);
+        }
+
         #[test]
         fn test_invalidate_forces_fresh_build() {
             let mut renderer = DomRenderer::new_internal();
@@ -34,7 +34,7 @@ impl DomRenderer {
         };
         ctx = ctx.with_overlay_queue(&mut overlay_queue);
 
-        root.render(&mut ctx);
+        ctx.render_view(root);
 
         // Phase 2: Render overlays on top (sorted by z-index)
         // ctx must go out of scope so buffer borrow is released
@@ -1,8 +1,8 @@
 //! Style computation for DomRenderer
 
 use crate::dom::renderer::types::DomRenderer;
-use crate::dom::{DomId, DomNode, StyleResolver};
-use crate::style::Style;
+use crate::dom::{DomId, DomNode, Specificity, StyleResolver};
+use crate::style::{Rule, Style};
 
 impl DomRenderer {
     /// Ensure selector cache is populated (parse once, reuse everywhere)
@@ -38,6 +38,40 @@ impl DomRenderer {
         Some(style)
     }
 
+    /// Rules matching a node with their specificity, lowest priority first
+    ///
+    /// Inline styles are not included. Used by the devtools to show where a
+    /// node's declarations come from.
+    pub fn matching_rules(&mut self, node_id: DomId) -> Vec<(Rule, Specificity)> {
+        self.matching_rule_indices(node_id)
+            .into_iter()
+            .map(|(index, specificity)| (self.stylesheet.rules[index].clone(), specificity))
+            .collect()
+    }
+
+    /// Like [`matching_rules()`](Self::matching_rules), but yields indices
+    /// into the stylesheet's rules (and [`source_map()`](Self::source_map))
+    pub fn matching_rule_indices(&mut self, node_id: DomId) -> Vec<(usize, Specificity)> {
+        self.ensure_selectors_cached();
+        let cached = self.cached_selectors.as_ref().unwrap();
+
+        let mut resolver = StyleResolver::with_cached_selectors(&self.stylesheet, cached);
+        let Some(node) = self.tree.get(node_id) else {
+            return Vec::new();
+        };
+        let get_node = |id: DomId| -> Option<&DomNode> { self.tree.get(id) };
+
+        let rules = &self.stylesheet.rules;
+        resolver
+            .match_node(node, get_node)
+            .into_iter()
+            .filter_map(|matched| {
+                let index = rules.iter().position(|r| std::ptr::eq(r, matched.rule))?;
+                Some((index, matched.specificity))
+            })
+            .collect()
+    }
+
     /// Get computed style for a node with inheritance from parent
     pub fn style_for_with_inheritance(&mut self, node_id: DomId) -> Option<Style> {
         // Check cache
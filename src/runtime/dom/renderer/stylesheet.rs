@@ -1,7 +1,7 @@
 //! Stylesheet management for DomRenderer
 
 use crate::dom::renderer::types::DomRenderer;
-use crate::style::StyleSheet;
+use crate::style::{SourceMap, StyleSheet};
 
 impl DomRenderer {
     /// Create with a stylesheet
@@ -9,6 +9,7 @@ impl DomRenderer {
         Self {
             tree: crate::dom::DomTree::new(),
             stylesheet,
+            source_map: SourceMap::new(),
             styles: std::collections::HashMap::new(),
             cached_selectors: None,
             focused: None,
@@ -17,14 +18,22 @@ impl DomRenderer {
     }
 
     /// Set the stylesheet
+    ///
+    /// Clears the source map, which described the previous stylesheet.
     pub fn set_stylesheet(&mut self, stylesheet: StyleSheet) {
         self.stylesheet = stylesheet;
+        self.source_map = SourceMap::new();
         // Invalidate cached selectors when stylesheet changes
         self.cached_selectors = None;
         // Invalidate style cache
         self.styles.clear();
     }
 
+    /// Get the stylesheet
+    pub fn stylesheet(&self) -> &StyleSheet {
+        &self.stylesheet
+    }
+
     /// Get mutable access to the stylesheet (for hot reload)
     pub fn stylesheet_mut(&mut self) -> &mut StyleSheet {
         // Invalidate cached selectors when stylesheet might be modified
@@ -34,6 +43,21 @@ impl DomRenderer {
         &mut self.stylesheet
     }
 
+    /// Get the source locations of the stylesheet's rules
+    pub fn source_map(&self) -> &SourceMap {
+        &self.source_map
+    }
+
+    /// Get mutable access to the source map
+    pub fn source_map_mut(&mut self) -> &mut SourceMap {
+        &mut self.source_map
+    }
+
+    /// Set the source map, indexed like the stylesheet's rules
+    pub fn set_source_map(&mut self, source_map: SourceMap) {
+        self.source_map = source_map;
+    }
+
     /// Invalidate stylesheet and style caches
     pub fn invalidate_styles(&mut self) {
         self.cached_selectors = None;
@@ -1,7 +1,7 @@
 //! Core types for DOM renderer
 
 use crate::dom::{DomId, DomTree};
-use crate::style::{Style, StyleSheet};
+use crate::style::{SourceMap, Style, StyleSheet};
 
 /// DOM-aware renderer
 ///
@@ -12,6 +12,8 @@ pub struct DomRenderer {
     pub(crate) tree: DomTree,
     /// The stylesheet, owned by the renderer
     pub(crate) stylesheet: StyleSheet,
+    /// Where the stylesheet's rules were parsed from
+    pub(crate) source_map: SourceMap,
     /// Computed styles cache
     pub(crate) styles: std::collections::HashMap<DomId, Style>,
     /// Cached parsed selectors (selector, rule_index)
@@ -29,6 +31,7 @@ impl DomRenderer {
         Self {
             tree: DomTree::new(),
             stylesheet: StyleSheet::new(),
+            source_map: SourceMap::new(),
             styles: std::collections::HashMap::new(),
             cached_selectors: None,
             focused: None,
@@ -21,6 +21,7 @@
 //! }
 //! ```
 
+use crate::utils::clock;
 use std::time::{Duration, Instant};
 
 /// Default double-click threshold (500ms - platform standard)
@@ -144,7 +145,7 @@ impl ClickDetector {
     /// - `Some(ClickType::Triple)` - Triple click detected (3rd click)
     /// - `None` - Waiting for more clicks or click sequence reset
     pub fn handle_click(&mut self, x: u16, y: u16) -> Option<ClickType> {
-        let now = Instant::now();
+        let now = clock::now();
 
         if let Some((last_time, last_x, last_y)) = self.last_click {
             // Check if within time threshold
@@ -201,7 +202,7 @@ impl ClickDetector {
     pub fn check_timeout(&mut self) -> Option<ClickType> {
         if self.click_count == 1 {
             if let Some((last_time, _, _)) = self.last_click {
-                if last_time.elapsed() > self.double_click_threshold {
+                if clock::elapsed(last_time) > self.double_click_threshold {
                     // Timeout expired, emit single click
                     let result = Some(ClickType::Single);
                     self.reset();
@@ -218,7 +219,7 @@ impl ClickDetector {
     pub fn time_until_timeout(&self) -> Option<Duration> {
         if self.click_count == 1 {
             if let Some((last_time, _, _)) = self.last_click {
-                let elapsed = last_time.elapsed();
+                let elapsed = clock::elapsed(last_time);
                 if elapsed < self.double_click_threshold {
                     return Some(self.double_click_threshold - elapsed);
                 }
@@ -0,0 +1,486 @@
+//! Embedded bitmap font for the PNG rasterizer
+//!
+//! Printable ASCII comes from a 5×7 dot-matrix table. Box drawing, block
+//! elements and braille, which TUIs use for borders, bars and charts, are
+//! drawn procedurally so they join seamlessly across cells. Anything else
+//! renders as an outlined box.
+
+/// Cell width in font pixels
+pub(super) const CELL_WIDTH: u32 = 6;
+/// Cell height in font pixels
+pub(super) const CELL_HEIGHT: u32 = 10;
+
+/// 5×7 glyphs for `' '..='~'`, one byte per row, bit 4 = leftmost column
+#[rustfmt::skip]
+const ASCII: [[u8; 7]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x04, 0x04, 0x04, 0x04, 0x00, 0x00, 0x04], // !
+    [0x0A, 0x0A, 0x0A, 0x00, 0x00, 0x00, 0x00], // "
+    [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A], // #
+    [0x04, 0x0F, 0x14, 0x0E, 0x05, 0x1E, 0x04], // $
+    [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03], // %
+    [0x0C, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0D], // &
+    [0x0C, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00], // '
+    [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02], // (
+    [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08], // )
+    [0x00, 0x04, 0x15, 0x0E, 0x15, 0x04, 0x00], // *
+    [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00], // +
+    [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08], // ,
+    [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00], // -
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C], // .
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00], // /
+    [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E], // 0
+    [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E], // 1
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F], // 2
+    [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E], // 3
+    [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02], // 4
+    [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E], // 5
+    [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E], // 6
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08], // 7
+    [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E], // 8
+    [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C], // 9
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00], // :
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x04, 0x08], // ;
+    [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02], // <
+    [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00], // =
+    [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08], // >
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04], // ?
+    [0x0E, 0x11, 0x01, 0x0D, 0x15, 0x15, 0x0E], // @
+    [0x0E, 0x11, 0x11, 0x11, 0x1F, 0x11, 0x11], // A
+    [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E], // B
+    [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E], // C
+    [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C], // D
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F], // E
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10], // F
+    [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F], // G
+    [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11], // H
+    [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E], // I
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C], // J
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11], // K
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F], // L
+    [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11], // M
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11], // N
+    [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E], // O
+    [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10], // P
+    [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D], // Q
+    [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11], // R
+    [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E], // S
+    [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // T
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E], // U
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04], // V
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A], // W
+    [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11], // X
+    [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04], // Y
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F], // Z
+    [0x0E, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0E], // [
+    [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00], // \
+    [0x0E, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0E], // ]
+    [0x04, 0x0A, 0x11, 0x00, 0x00, 0x00, 0x00], // ^
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F], // _
+    [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00], // `
+    [0x00, 0x00, 0x0E, 0x01, 0x0F, 0x11, 0x0F], // a
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1E], // b
+    [0x00, 0x00, 0x0E, 0x10, 0x10, 0x11, 0x0E], // c
+    [0x01, 0x01, 0x0D, 0x13, 0x11, 0x11, 0x0F], // d
+    [0x00, 0x00, 0x0E, 0x11, 0x1F, 0x10, 0x0E], // e
+    [0x06, 0x09, 0x08, 0x1C, 0x08, 0x08, 0x08], // f
+    [0x00, 0x0F, 0x11, 0x11, 0x0F, 0x01, 0x0E], // g
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11], // h
+    [0x04, 0x00, 0x0C, 0x04, 0x04, 0x04, 0x0E], // i
+    [0x02, 0x00, 0x06, 0x02, 0x02, 0x12, 0x0C], // j
+    [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12], // k
+    [0x0C, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E], // l
+    [0x00, 0x00, 0x1A, 0x15, 0x15, 0x11, 0x11], // m
+    [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11], // n
+    [0x00, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E], // o
+    [0x00, 0x00, 0x1E, 0x11, 0x1E, 0x10, 0x10], // p
+    [0x00, 0x00, 0x0D, 0x13, 0x0F, 0x01, 0x01], // q
+    [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10], // r
+    [0x00, 0x00, 0x0E, 0x10, 0x0E, 0x01, 0x1E], // s
+    [0x08, 0x08, 0x1C, 0x08, 0x08, 0x09, 0x06], // t
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0D], // u
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0A, 0x04], // v
+    [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0A], // w
+    [0x00, 0x00, 0x11, 0x0A, 0x04, 0x0A, 0x11], // x
+    [0x00, 0x00, 0x11, 0x11, 0x0F, 0x01, 0x0E], // y
+    [0x00, 0x00, 0x1F, 0x02, 0x04, 0x08, 0x1F], // z
+    [0x02, 0x04, 0x04, 0x08, 0x04, 0x04, 0x02], // {
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // |
+    [0x08, 0x04, 0x04, 0x02, 0x04, 0x04, 0x08], // }
+    [0x00, 0x00, 0x08, 0x15, 0x02, 0x00, 0x00], // ~
+];
+
+/// Monochrome glyph covering one or two cells
+pub(super) struct Bitmap {
+    width: u32,
+    rows: Vec<u16>,
+}
+
+impl Bitmap {
+    fn new(cells: u32) -> Self {
+        Self {
+            width: CELL_WIDTH * cells,
+            rows: vec![0; CELL_HEIGHT as usize],
+        }
+    }
+
+    /// Whether the pixel at (x, y) is set
+    pub fn get(&self, x: u32, y: u32) -> bool {
+        x < self.width
+            && self
+                .rows
+                .get(y as usize)
+                .is_some_and(|row| row & (1 << x) != 0)
+    }
+
+    fn set(&mut self, x: u32, y: u32) {
+        if x < self.width {
+            if let Some(row) = self.rows.get_mut(y as usize) {
+                *row |= 1 << x;
+            }
+        }
+    }
+
+    fn fill(&mut self, x0: u32, y0: u32, x1: u32, y1: u32) {
+        for y in y0..y1 {
+            for x in x0..x1 {
+                self.set(x, y);
+            }
+        }
+    }
+
+    /// Embolden by smearing every pixel one column to the right
+    pub fn embolden(&mut self) {
+        let mask = (1u32 << self.width) - 1;
+        for row in &mut self.rows {
+            *row = ((*row as u32 | ((*row as u32) << 1)) & mask) as u16;
+        }
+    }
+
+    /// Slant the upper rows one pixel to the right
+    pub fn italicize(&mut self) {
+        let mask = (1u32 << self.width) - 1;
+        for row in self.rows.iter_mut().take(4) {
+            *row = (((*row as u32) << 1) & mask) as u16;
+        }
+    }
+
+    /// Draw a horizontal line across the full width
+    pub fn hline(&mut self, y: u32) {
+        self.fill(0, y, self.width, y + 1);
+    }
+}
+
+/// Bitmap for a character spanning `cells` columns
+pub(super) fn glyph(ch: char, cells: u32) -> Bitmap {
+    let mut bitmap = Bitmap::new(cells);
+    let ch = substitute(ch);
+    if let Some(rows) = ascii(ch) {
+        for (y, bits) in rows.iter().enumerate() {
+            for x in 0..5 {
+                if bits & (0x10 >> x) != 0 {
+                    bitmap.set(x, y as u32 + 1);
+                }
+            }
+        }
+    } else if !(box_drawing(&mut bitmap, ch) || block(&mut bitmap, ch) || braille(&mut bitmap, ch))
+    {
+        // Unknown character: outlined box
+        let right = bitmap.width - 2;
+        bitmap.fill(1, 1, right + 1, 2);
+        bitmap.fill(1, 8, right + 1, 9);
+        bitmap.fill(1, 1, 2, 9);
+        bitmap.fill(right, 1, right + 1, 9);
+    }
+    bitmap
+}
+
+fn ascii(ch: char) -> Option<&'static [u8; 7]> {
+    let index = (ch as u32).checked_sub(0x20)?;
+    ASCII.get(index as usize)
+}
+
+/// Map typographic characters to their ASCII look-alikes
+fn substitute(ch: char) -> char {
+    match ch {
+        '\u{a0}' => ' ',
+        '‘' | '’' | '′' => '\'',
+        '“' | '”' | '″' => '"',
+        '‐' | '‑' | '‒' | '–' | '—' | '−' => '-',
+        '·' | '•' | '∙' => '*',
+        '…' => '.',
+        '×' => 'x',
+        '«' => '<',
+        '»' => '>',
+        '▶' | '►' | '▸' | '›' => '>',
+        '◀' | '◄' | '◂' | '‹' => '<',
+        '▲' | '▴' => '^',
+        '▼' | '▾' => 'v',
+        '✓' | '✔' => 'v',
+        '✗' | '✘' => 'x',
+        _ => ch,
+    }
+}
+
+/// Line weights for box-drawing characters: (up, down, left, right)
+/// where 1 = light, 2 = heavy, 3 = double
+fn box_weights(ch: char) -> Option<[u8; 4]> {
+    let weights = match ch {
+        '─' | '┄' | '┈' | '╌' => [0, 0, 1, 1],
+        '━' | '┅' | '┉' | '╍' => [0, 0, 2, 2],
+        '│' | '┆' | '┊' | '╎' => [1, 1, 0, 0],
+        '┃' | '┇' | '┋' | '╏' => [2, 2, 0, 0],
+        '┌' | '╭' => [0, 1, 0, 1],
+        '┐' | '╮' => [0, 1, 1, 0],
+        '└' | '╰' => [1, 0, 0, 1],
+        '┘' | '╯' => [1, 0, 1, 0],
+        '├' => [1, 1, 0, 1],
+        '┤' => [1, 1, 1, 0],
+        '┬' => [0, 1, 1, 1],
+        '┴' => [1, 0, 1, 1],
+        '┼' => [1, 1, 1, 1],
+        '┏' => [0, 2, 0, 2],
+        '┓' => [0, 2, 2, 0],
+        '┗' => [2, 0, 0, 2],
+        '┛' => [2, 0, 2, 0],
+        '┣' => [2, 2, 0, 2],
+        '┫' => [2, 2, 2, 0],
+        '┳' => [0, 2, 2, 2],
+        '┻' => [2, 0, 2, 2],
+        '╋' => [2, 2, 2, 2],
+        '═' => [0, 0, 3, 3],
+        '║' => [3, 3, 0, 0],
+        '╔' => [0, 3, 0, 3],
+        '╗' => [0, 3, 3, 0],
+        '╚' => [3, 0, 0, 3],
+        '╝' => [3, 0, 3, 0],
+        '╠' => [3, 3, 0, 3],
+        '╣' => [3, 3, 3, 0],
+        '╦' => [0, 3, 3, 3],
+        '╩' => [3, 0, 3, 3],
+        '╬' => [3, 3, 3, 3],
+        '╴' => [0, 0, 1, 0],
+        '╵' => [1, 0, 0, 0],
+        '╶' => [0, 0, 0, 1],
+        '╷' => [0, 1, 0, 0],
+        _ => return None,
+    };
+    Some(weights)
+}
+
+fn box_drawing(bitmap: &mut Bitmap, ch: char) -> bool {
+    let Some([up, down, left, right]) = box_weights(ch) else {
+        return false;
+    };
+    let (w, h) = (bitmap.width, CELL_HEIGHT);
+    let (cx, cy) = (w / 2 - 1, h / 2 - 1);
+
+    // Offsets of the parallel strokes for each weight; heavy strokes are two
+    // pixels wide and double strokes two lines a pixel apart
+    let strokes = |weight: u8| -> &'static [u32] {
+        match weight {
+            1 => &[1],
+            2 => &[1, 2],
+            _ => &[0, 2],
+        }
+    };
+    // Segments meeting at the center extend across the crossing strokes
+    let span = |weight: u8| match weight {
+        2 => (0, 1),
+        3 => (-1, 1),
+        _ => (0, 0),
+    };
+    let (v_lo, v_hi) = span(up.max(down));
+    let (h_lo, h_hi) = span(left.max(right));
+    let horizontal = [
+        (left, 0, cx as i32 + v_hi + 1),
+        (right, cx as i32 + v_lo, w as i32),
+    ];
+    for (weight, x0, x1) in horizontal {
+        for &d in strokes(weight).iter().take(weight.min(2) as usize) {
+            let y = cy + d - 1;
+            bitmap.fill(x0 as u32, y, x1 as u32, y + 1);
+        }
+    }
+    let vertical = [
+        (up, 0, cy as i32 + h_hi + 1),
+        (down, cy as i32 + h_lo, h as i32),
+    ];
+    for (weight, y0, y1) in vertical {
+        for &d in strokes(weight).iter().take(weight.min(2) as usize) {
+            let x = cx + d - 1;
+            bitmap.fill(x, y0 as u32, x + 1, y1 as u32);
+        }
+    }
+    true
+}
+
+fn block(bitmap: &mut Bitmap, ch: char) -> bool {
+    let (w, h) = (bitmap.width, CELL_HEIGHT);
+    let code = ch as u32;
+    match ch {
+        '█' => bitmap.fill(0, 0, w, h),
+        '▀' => bitmap.fill(0, 0, w, h / 2),
+        '▔' => bitmap.fill(0, 0, w, 1),
+        '▐' => bitmap.fill(w / 2, 0, w, h),
+        '▕' => bitmap.fill(w - 1, 0, w, h),
+        // ▁▂▃▄▅▆▇: lower eighths
+        '\u{2581}'..='\u{2587}' => {
+            let eighths = code - 0x2580;
+            bitmap.fill(0, h - (h * eighths + 4) / 8, w, h);
+        }
+        // ▉▊▋▌▍▎▏: left eighths
+        '\u{2589}'..='\u{258f}' => {
+            let eighths = 0x2590 - code;
+            bitmap.fill(0, 0, (w * eighths + 4) / 8, h);
+        }
+        '░' | '▒' | '▓' => {
+            for y in 0..h {
+                for x in 0..w {
+                    let on = match ch {
+                        '░' => x % 2 == 0 && y % 2 == 0,
+                        '▒' => (x + y) % 2 == 0,
+                        _ => !(x % 2 == 0 && y % 2 == 0),
+                    };
+                    if on {
+                        bitmap.set(x, y);
+                    }
+                }
+            }
+        }
+        // Quadrants ▖▗▘▙▚▛▜▝▞▟
+        '\u{2596}'..='\u{259f}' => {
+            // Bits: upper-left, upper-right, lower-left, lower-right
+            const QUADRANTS: [u8; 10] = [
+                0b0010, 0b0001, 0b1000, 0b1011, 0b1001, 0b1110, 0b1101, 0b0100, 0b0110, 0b0111,
+            ];
+            let bits = QUADRANTS[(code - 0x2596) as usize];
+            let (mx, my) = (w / 2, h / 2);
+            if bits & 0b1000 != 0 {
+                bitmap.fill(0, 0, mx, my);
+            }
+            if bits & 0b0100 != 0 {
+                bitmap.fill(mx, 0, w, my);
+            }
+            if bits & 0b0010 != 0 {
+                bitmap.fill(0, my, mx, h);
+            }
+            if bits & 0b0001 != 0 {
+                bitmap.fill(mx, my, w, h);
+            }
+        }
+        '●' | '◉' => {
+            bitmap.fill(1, 3, 4, 6);
+            bitmap.fill(2, 2, 3, 7);
+            bitmap.fill(0, 4, 5, 5);
+        }
+        '○' | '◯' => {
+            bitmap.fill(1, 2, 4, 3);
+            bitmap.fill(1, 6, 4, 7);
+            bitmap.fill(0, 3, 1, 6);
+            bitmap.fill(4, 3, 5, 6);
+        }
+        '■' | '◆' => bitmap.fill(1, 2, 5, 7),
+        '□' | '◇' => {
+            bitmap.fill(1, 2, 5, 3);
+            bitmap.fill(1, 6, 5, 7);
+            bitmap.fill(1, 2, 2, 7);
+            bitmap.fill(4, 2, 5, 7);
+        }
+        _ => return false,
+    }
+    true
+}
+
+fn braille(bitmap: &mut Bitmap, ch: char) -> bool {
+    let code = ch as u32;
+    if !(0x2800..=0x28ff).contains(&code) {
+        return false;
+    }
+    // Dot bit -> (column, row) in the 2×4 braille grid
+    const DOTS: [(u32, u32); 8] = [
+        (0, 0),
+        (0, 1),
+        (0, 2),
+        (1, 0),
+        (1, 1),
+        (1, 2),
+        (0, 3),
+        (1, 3),
+    ];
+    let bits = code - 0x2800;
+    for (bit, (col, row)) in DOTS.iter().enumerate() {
+        if bits & (1 << bit) != 0 {
+            let x = 1 + col * 3;
+            let y = 1 + row * 2;
+            bitmap.fill(x, y, x + 2, y + 1);
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(bitmap: &Bitmap) -> Vec<String> {
+        (0..CELL_HEIGHT)
+            .map(|y| {
+                (0..bitmap.width)
+                    .map(|x| if bitmap.get(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ascii_glyph() {
+        let rows = render(&glyph('T', 1));
+        assert_eq!(rows[0], "......");
+        assert_eq!(rows[1], "#####.");
+        assert_eq!(rows[7], "..#...");
+        assert_eq!(rows[9], "......");
+    }
+
+    #[test]
+    fn test_box_lines_join_at_edges() {
+        let horizontal = render(&glyph('─', 1));
+        assert_eq!(horizontal[4], "######");
+        let vertical = render(&glyph('│', 1));
+        assert!(vertical.iter().all(|row| row == "..#..."));
+        let corner = render(&glyph('┌', 1));
+        assert_eq!(corner[4], "..####");
+        assert_eq!(corner[0], "......");
+        assert_eq!(corner[9], "..#...");
+    }
+
+    #[test]
+    fn test_blocks_and_braille() {
+        let full = glyph('█', 1);
+        assert!((0..CELL_HEIGHT).all(|y| (0..CELL_WIDTH).all(|x| full.get(x, y))));
+        let lower = render(&glyph('▄', 1));
+        assert_eq!(lower[4], "......");
+        assert_eq!(lower[5], "######");
+        let dots = render(&glyph('⠁', 1));
+        assert_eq!(dots[1], ".##...");
+        assert_eq!(dots[3], "......");
+    }
+
+    #[test]
+    fn test_unknown_and_wide_glyphs_are_boxes() {
+        let rows = render(&glyph('中', 2));
+        assert_eq!(rows[1], ".##########.");
+        assert_eq!(rows[4], ".#........#.");
+    }
+
+    #[test]
+    fn test_bold_and_italic() {
+        let mut bold = glyph('|', 1);
+        bold.embolden();
+        assert_eq!(render(&bold)[3], "..##..");
+        let mut italic = glyph('|', 1);
+        italic.italicize();
+        assert_eq!(render(&italic)[1], "...#..");
+        assert_eq!(render(&italic)[5], "..#...");
+    }
+}
@@ -0,0 +1,156 @@
+//! HTML exporter
+
+use super::svg::decoration;
+use super::{escape, hex, row_glyphs, ExportOptions, ResolvedStyle};
+use crate::render::{Buffer, Modifier};
+use std::fmt::Write;
+
+/// Render a buffer as a standalone HTML page with default options
+pub fn to_html(buffer: &Buffer) -> String {
+    to_html_with(buffer, &ExportOptions::default())
+}
+
+/// Render a buffer as a standalone HTML page
+pub fn to_html_with(buffer: &Buffer, options: &ExportOptions) -> String {
+    let title = options.title.as_deref().unwrap_or("revue");
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(out, "<title>{}</title>", escape(title));
+    let _ = writeln!(
+        out,
+        "<style>body{{margin:0;background:{}}}</style>",
+        hex(options.background)
+    );
+    out.push_str("</head>\n<body>\n");
+    out.push_str(&to_html_fragment(buffer, options));
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Render a buffer as a self-contained `<pre>` element for embedding
+///
+/// All styling is inline, so the fragment renders the same inside any page.
+pub fn to_html_fragment(buffer: &Buffer, options: &ExportOptions) -> String {
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<pre style=\"margin:0;padding:0;font-family:{};font-size:{}px;line-height:1.25;color:{};background:{}\">",
+        escape(&options.font_family),
+        options.font_size,
+        hex(options.foreground),
+        hex(options.background)
+    );
+
+    let default_style = ResolvedStyle {
+        fg: options.foreground,
+        bg: options.background,
+        modifier: Modifier::empty(),
+    };
+
+    for y in 0..buffer.height() {
+        if y > 0 {
+            out.push('\n');
+        }
+        let glyphs = row_glyphs(buffer, y, options);
+        let mut start = 0;
+        while start < glyphs.len() {
+            let style = glyphs[start].style;
+            let link = glyphs[start].hyperlink_id;
+            let mut end = start + 1;
+            while end < glyphs.len()
+                && glyphs[end].style == style
+                && glyphs[end].hyperlink_id == link
+            {
+                end += 1;
+            }
+
+            let mut text = String::new();
+            for glyph in &glyphs[start..end] {
+                if glyph.width == 2 {
+                    // Pin wide characters to two columns whatever the font
+                    let _ = write!(
+                        text,
+                        "<span style=\"display:inline-block;width:2ch\">{}</span>",
+                        escape(&glyph.symbol.to_string())
+                    );
+                } else {
+                    text.push_str(&escape(&glyph.symbol.to_string()));
+                }
+            }
+
+            let url = link.and_then(|id| buffer.get_hyperlink(id));
+            if let Some(url) = url {
+                let _ = write!(out, "<a href=\"{}\" style=\"color:inherit\">", escape(url));
+            }
+            if style == default_style {
+                out.push_str(&text);
+            } else {
+                let _ = write!(out, "<span style=\"{}\">{}</span>", css(&style), text);
+            }
+            if url.is_some() {
+                out.push_str("</a>");
+            }
+            start = end;
+        }
+    }
+
+    out.push_str("</pre>\n");
+    out
+}
+
+/// Inline CSS for a resolved style
+fn css(style: &ResolvedStyle) -> String {
+    let mut css = format!("color:{};background:{}", hex(style.fg), hex(style.bg));
+    if style.modifier.contains(Modifier::BOLD) {
+        css.push_str(";font-weight:bold");
+    }
+    if style.modifier.contains(Modifier::ITALIC) {
+        css.push_str(";font-style:italic");
+    }
+    let decoration = decoration(style.modifier);
+    if !decoration.is_empty() {
+        let _ = write!(css, ";text-decoration:{}", decoration);
+    }
+    css
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::Cell;
+    use crate::style::Color;
+
+    #[test]
+    fn test_html_document() {
+        let mut buffer = Buffer::new(5, 2);
+        buffer.put_str(0, 0, "a&b");
+        let html = to_html_with(&buffer, &ExportOptions::default().title("Shot"));
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Shot</title>"));
+        assert!(html.contains(">a&amp;b  \n     </pre>"));
+    }
+
+    #[test]
+    fn test_html_styled_runs() {
+        let mut buffer = Buffer::new(4, 1);
+        buffer.set(1, 0, Cell::new('x').fg(Color::RED).underline());
+        buffer.set(2, 0, Cell::new('y').fg(Color::RED).underline());
+        let html = to_html_fragment(&buffer, &ExportOptions::default());
+        assert!(html.contains(
+            "> <span style=\"color:#ff0000;background:#181818;text-decoration:underline\">xy</span> </pre>"
+        ));
+    }
+
+    #[test]
+    fn test_html_wide_chars_and_links() {
+        let mut buffer = Buffer::new(4, 1);
+        buffer.put_str(0, 0, "中");
+        let id = buffer.register_hyperlink("https://example.com/?a=1&b=2");
+        buffer.set(2, 0, Cell::new('L').hyperlink(id));
+        let html = to_html_fragment(&buffer, &ExportOptions::default());
+        assert!(html.contains("<span style=\"display:inline-block;width:2ch\">中</span>"));
+        assert!(html.contains(
+            "<a href=\"https://example.com/?a=1&amp;b=2\" style=\"color:inherit\">L</a>"
+        ));
+    }
+}
@@ -0,0 +1,307 @@
+//! Export buffers as standalone SVG, HTML and PNG images
+//!
+//! Turns a rendered [`Buffer`] into a file that can be embedded in docs,
+//! attached to bug reports or shown in visual-diff reports. Colors,
+//! modifiers (bold, italic, underline, strikethrough, dim, reverse) and wide
+//! characters are preserved; unset colors fall back to the configured
+//! terminal foreground and background.
+//!
+//! | Format | Function | Notes |
+//! |--------|----------|-------|
+//! | SVG | [`to_svg`] | Text rendered with the viewer's monospace font |
+//! | HTML | [`to_html`] | Standalone page; [`to_html_fragment`] for a `<pre>` only |
+//! | PNG | `to_png` | Embedded bitmap font; requires the `image` feature |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use revue::render::export::{self, ExportOptions};
+//!
+//! let svg = export::to_svg(&buffer);
+//! let options = ExportOptions::default().font_size(16.0).title("Login form");
+//! export::save(&buffer, "docs/login.html", &options)?;
+//! ```
+
+#[cfg(feature = "image")]
+mod font;
+mod html;
+#[cfg(feature = "image")]
+mod png;
+mod svg;
+
+pub use html::{to_html, to_html_fragment, to_html_with};
+#[cfg(feature = "image")]
+pub use png::{rasterize, to_png, to_png_with, Raster};
+pub use svg::{to_svg, to_svg_with};
+
+use super::{Buffer, Cell, Modifier};
+use crate::style::Color;
+use crate::utils::unicode::char_width;
+use std::path::Path;
+use thiserror::Error;
+
+/// Error produced when exporting a buffer
+#[derive(Debug, Error)]
+pub enum ExportError {
+    /// Writing the output file failed
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The output format is not known or not enabled
+    #[error("Unsupported export format: {0}")]
+    UnsupportedFormat(String),
+
+    /// PNG encoding failed
+    #[error("PNG encoding failed: {0}")]
+    EncodeFailed(String),
+}
+
+/// Options shared by all exporters
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    /// Color for cells without a foreground
+    pub foreground: Color,
+    /// Color for cells without a background
+    pub background: Color,
+    /// CSS font stack for SVG and HTML output
+    pub font_family: String,
+    /// Font size in pixels for SVG and HTML output
+    pub font_size: f32,
+    /// Pixel scale of the PNG bitmap font (1 = 6x10 pixels per cell)
+    pub scale: u32,
+    /// Document title for SVG and HTML output
+    pub title: Option<String>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            foreground: Color::rgb(204, 204, 204),
+            background: Color::rgb(24, 24, 24),
+            font_family: "ui-monospace, 'DejaVu Sans Mono', Menlo, Consolas, monospace".into(),
+            font_size: 14.0,
+            scale: 2,
+            title: None,
+        }
+    }
+}
+
+impl ExportOptions {
+    /// Set the default foreground color
+    pub fn foreground(mut self, color: Color) -> Self {
+        self.foreground = color;
+        self
+    }
+
+    /// Set the default background color
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    /// Set the font stack used by SVG and HTML output
+    pub fn font_family(mut self, family: impl Into<String>) -> Self {
+        self.font_family = family.into();
+        self
+    }
+
+    /// Set the font size used by SVG and HTML output
+    pub fn font_size(mut self, size: f32) -> Self {
+        self.font_size = size.max(1.0);
+        self
+    }
+
+    /// Set the PNG pixel scale
+    pub fn scale(mut self, scale: u32) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Set the document title
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+/// Export a buffer to a file, choosing the format from the extension
+///
+/// Supports `.svg`, `.html`/`.htm` and, with the `image` feature, `.png`.
+pub fn save(
+    buffer: &Buffer,
+    path: impl AsRef<Path>,
+    options: &ExportOptions,
+) -> Result<(), ExportError> {
+    let path = path.as_ref();
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+
+    let bytes = match extension.as_str() {
+        "svg" => to_svg_with(buffer, options).into_bytes(),
+        "html" | "htm" => to_html_with(buffer, options).into_bytes(),
+        #[cfg(feature = "image")]
+        "png" => to_png_with(buffer, options)?,
+        _ => return Err(ExportError::UnsupportedFormat(extension)),
+    };
+
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    std::fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// Final colors and modifiers of a cell after applying defaults
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ResolvedStyle {
+    pub fg: Color,
+    pub bg: Color,
+    pub modifier: Modifier,
+}
+
+impl ResolvedStyle {
+    /// Resolve a cell's style: unset or transparent colors take the
+    /// defaults, `REVERSE` swaps them and `DIM` fades the foreground.
+    pub fn of(cell: &Cell, options: &ExportOptions) -> Self {
+        let mut fg = cell.fg.filter(|c| c.a > 0).unwrap_or(options.foreground);
+        let mut bg = cell.bg.filter(|c| c.a > 0).unwrap_or(options.background);
+        if cell.modifier.contains(Modifier::REVERSE) {
+            std::mem::swap(&mut fg, &mut bg);
+        }
+        if cell.modifier.contains(Modifier::DIM) {
+            fg = fg.blend(bg, 0.5);
+        }
+        Self {
+            fg: Color::rgb(fg.r, fg.g, fg.b),
+            bg: Color::rgb(bg.r, bg.g, bg.b),
+            modifier: cell.modifier - Modifier::REVERSE - Modifier::DIM,
+        }
+    }
+}
+
+/// A printable cell: its column, symbol, display width and style
+pub(crate) struct Glyph {
+    pub x: u16,
+    pub symbol: char,
+    pub width: u16,
+    pub style: ResolvedStyle,
+    pub hyperlink_id: Option<u16>,
+}
+
+/// Printable cells of a row, skipping wide-character continuations
+pub(crate) fn row_glyphs(buffer: &Buffer, y: u16, options: &ExportOptions) -> Vec<Glyph> {
+    let mut glyphs = Vec::with_capacity(buffer.width() as usize);
+    for x in 0..buffer.width() {
+        let Some(cell) = buffer.get(x, y) else {
+            continue;
+        };
+        if cell.is_continuation() {
+            continue;
+        }
+        let symbol = if cell.symbol.is_control() {
+            ' '
+        } else {
+            cell.symbol
+        };
+        let width = (char_width(symbol) as u16)
+            .clamp(1, 2)
+            .min(buffer.width() - x);
+        glyphs.push(Glyph {
+            x,
+            symbol,
+            width,
+            style: ResolvedStyle::of(cell, options),
+            hyperlink_id: cell.hyperlink_id,
+        });
+    }
+    glyphs
+}
+
+/// Format a color as `#rrggbb`
+pub(crate) fn hex(color: Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+/// Escape text for XML and HTML
+pub(crate) fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolved_style_defaults_reverse_and_dim() {
+        let options = ExportOptions::default();
+        let plain = ResolvedStyle::of(&Cell::new('a'), &options);
+        assert_eq!(plain.fg, options.foreground);
+        assert_eq!(plain.bg, options.background);
+
+        let reversed = ResolvedStyle::of(&Cell::new('a').fg(Color::RED).reverse(), &options);
+        assert_eq!(reversed.fg, options.background);
+        assert_eq!(reversed.bg, Color::RED);
+        assert!(reversed.modifier.is_empty());
+
+        let dim = ResolvedStyle::of(
+            &Cell::new('a').fg(Color::WHITE).bg(Color::BLACK).dim(),
+            &options,
+        );
+        assert_eq!(dim.fg, Color::rgb(128, 128, 128));
+    }
+
+    #[test]
+    fn test_row_glyphs_skips_continuations() {
+        let mut buffer = Buffer::new(5, 1);
+        buffer.put_str(0, 0, "a中b");
+        let glyphs = row_glyphs(&buffer, 0, &ExportOptions::default());
+        let cols: Vec<_> = glyphs.iter().map(|g| (g.x, g.symbol, g.width)).collect();
+        assert_eq!(
+            cols,
+            vec![(0, 'a', 1), (1, '中', 2), (3, 'b', 1), (4, ' ', 1)]
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("<a & 'b'>"), "&lt;a &amp; &#39;b&#39;&gt;");
+    }
+
+    #[test]
+    fn test_save_by_extension() {
+        let dir = std::env::temp_dir().join(format!("revue_export_{}", std::process::id()));
+        let mut buffer = Buffer::new(4, 1);
+        buffer.put_str(0, 0, "hi");
+        let options = ExportOptions::default();
+
+        save(&buffer, dir.join("out.svg"), &options).unwrap();
+        save(&buffer, dir.join("out.html"), &options).unwrap();
+        assert!(std::fs::read_to_string(dir.join("out.svg"))
+            .unwrap()
+            .starts_with("<svg"));
+        assert!(std::fs::read_to_string(dir.join("out.html"))
+            .unwrap()
+            .starts_with("<!DOCTYPE html>"));
+        assert!(matches!(
+            save(&buffer, dir.join("out.txt"), &options),
+            Err(ExportError::UnsupportedFormat(_))
+        ));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,210 @@
+//! PNG rasterizer using the embedded bitmap font
+
+use super::font::{glyph, CELL_HEIGHT, CELL_WIDTH};
+use super::{row_glyphs, ExportError, ExportOptions};
+use crate::render::{Buffer, Modifier};
+use crate::style::Color;
+
+/// An RGB pixel image of a rendered buffer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Raster {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Raster {
+    /// Create a raster filled with one color
+    pub fn new(width: u32, height: u32, color: Color) -> Self {
+        let pixels = [color.r, color.g, color.b].repeat((width * height) as usize);
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Width in pixels
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Height in pixels
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// RGB pixel data, row by row
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Color of the pixel at (x, y)
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = ((y * self.width + x) * 3) as usize;
+        Some(Color::rgb(
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+        ))
+    }
+
+    /// Fill a rectangle, clipped to the raster
+    pub fn fill(&mut self, x: u32, y: u32, width: u32, height: u32, color: Color) {
+        let x1 = (x + width).min(self.width);
+        let y1 = (y + height).min(self.height);
+        for py in y.min(y1)..y1 {
+            for px in x.min(x1)..x1 {
+                let i = ((py * self.width + px) * 3) as usize;
+                self.pixels[i..i + 3].copy_from_slice(&[color.r, color.g, color.b]);
+            }
+        }
+    }
+
+    /// Copy another raster onto this one at (x, y), clipped
+    pub fn blit(&mut self, other: &Raster, x: u32, y: u32) {
+        for oy in 0..other.height.min(self.height.saturating_sub(y)) {
+            for ox in 0..other.width.min(self.width.saturating_sub(x)) {
+                let src = ((oy * other.width + ox) * 3) as usize;
+                let dst = (((y + oy) * self.width + x + ox) * 3) as usize;
+                self.pixels[dst..dst + 3].copy_from_slice(&other.pixels[src..src + 3]);
+            }
+        }
+    }
+
+    /// Encode as PNG
+    pub fn to_png(&self) -> Result<Vec<u8>, ExportError> {
+        use image::ImageEncoder;
+
+        let mut png = Vec::new();
+        image::codecs::png::PngEncoder::new(&mut png)
+            .write_image(
+                &self.pixels,
+                self.width,
+                self.height,
+                image::ExtendedColorType::Rgb8,
+            )
+            .map_err(|e| ExportError::EncodeFailed(e.to_string()))?;
+        Ok(png)
+    }
+}
+
+/// Rasterize a buffer with the embedded bitmap font
+///
+/// Each cell becomes `6 × 10` font pixels, each drawn as a
+/// `scale × scale` square.
+pub fn rasterize(buffer: &Buffer, options: &ExportOptions) -> Raster {
+    let scale = options.scale.max(1);
+    let (cell_w, cell_h) = (CELL_WIDTH * scale, CELL_HEIGHT * scale);
+    let mut raster = Raster::new(
+        buffer.width() as u32 * cell_w,
+        buffer.height() as u32 * cell_h,
+        options.background,
+    );
+
+    for y in 0..buffer.height() {
+        let top = y as u32 * cell_h;
+        for g in row_glyphs(buffer, y, options) {
+            let left = g.x as u32 * cell_w;
+            let cells = g.width as u32;
+            raster.fill(left, top, cells * cell_w, cell_h, g.style.bg);
+
+            let mut bitmap = glyph(g.symbol, cells);
+            let modifier = g.style.modifier;
+            if modifier.contains(Modifier::BOLD) {
+                bitmap.embolden();
+            }
+            if modifier.contains(Modifier::ITALIC) {
+                bitmap.italicize();
+            }
+            if modifier.contains(Modifier::UNDERLINE) {
+                bitmap.hline(CELL_HEIGHT - 1);
+            }
+            if modifier.contains(Modifier::CROSSED_OUT) {
+                bitmap.hline(CELL_HEIGHT / 2);
+            }
+
+            for py in 0..CELL_HEIGHT {
+                for px in 0..cells * CELL_WIDTH {
+                    if bitmap.get(px, py) {
+                        raster.fill(
+                            left + px * scale,
+                            top + py * scale,
+                            scale,
+                            scale,
+                            g.style.fg,
+                        );
+                    }
+                }
+            }
+        }
+    }
+    raster
+}
+
+/// Render a buffer as PNG with default options
+pub fn to_png(buffer: &Buffer) -> Result<Vec<u8>, ExportError> {
+    to_png_with(buffer, &ExportOptions::default())
+}
+
+/// Render a buffer as PNG
+pub fn to_png_with(buffer: &Buffer, options: &ExportOptions) -> Result<Vec<u8>, ExportError> {
+    rasterize(buffer, options).to_png()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::Cell;
+
+    #[test]
+    fn test_rasterize_size_and_colors() {
+        let mut buffer = Buffer::new(3, 2);
+        buffer.set(1, 0, Cell::new('█').fg(Color::RED).bg(Color::BLUE));
+        buffer.set(2, 1, Cell::new(' ').bg(Color::GREEN));
+        let options = ExportOptions::default().scale(1);
+        let raster = rasterize(&buffer, &options);
+
+        assert_eq!((raster.width(), raster.height()), (18, 20));
+        assert_eq!(raster.pixel(0, 0), Some(options.background));
+        assert_eq!(raster.pixel(6, 0), Some(Color::RED));
+        assert_eq!(raster.pixel(11, 9), Some(Color::RED));
+        assert_eq!(raster.pixel(12, 10), Some(Color::GREEN));
+        assert_eq!(raster.pixel(18, 0), None);
+    }
+
+    #[test]
+    fn test_rasterize_scale_and_underline() {
+        let mut buffer = Buffer::new(1, 1);
+        buffer.set(0, 0, Cell::new(' ').fg(Color::WHITE).underline());
+        let raster = rasterize(&buffer, &ExportOptions::default().scale(3));
+        assert_eq!((raster.width(), raster.height()), (18, 30));
+        assert_eq!(raster.pixel(17, 27), Some(Color::WHITE));
+        assert_eq!(
+            raster.pixel(17, 26),
+            Some(ExportOptions::default().background)
+        );
+    }
+
+    #[test]
+    fn test_blit_clips() {
+        let mut target = Raster::new(4, 4, Color::BLACK);
+        let source = Raster::new(3, 3, Color::WHITE);
+        target.blit(&source, 2, 2);
+        assert_eq!(target.pixel(1, 1), Some(Color::BLACK));
+        assert_eq!(target.pixel(3, 3), Some(Color::WHITE));
+    }
+
+    #[test]
+    fn test_png_roundtrip() {
+        let mut buffer = Buffer::new(4, 2);
+        buffer.put_str(0, 0, "Hi!");
+        let png = to_png(&buffer).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        let decoded = image::load_from_memory(&png).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (48, 40));
+    }
+}
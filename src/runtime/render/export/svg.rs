@@ -0,0 +1,207 @@
+//! SVG exporter
+
+use super::{escape, hex, row_glyphs, ExportOptions, Glyph, ResolvedStyle};
+use crate::render::{Buffer, Modifier};
+use std::fmt::Write;
+
+/// Render a buffer as a standalone SVG document with default options
+pub fn to_svg(buffer: &Buffer) -> String {
+    to_svg_with(buffer, &ExportOptions::default())
+}
+
+/// Render a buffer as a standalone SVG document
+///
+/// Each cell is `0.6 × font_size` wide and `1.25 × font_size` tall. Text
+/// runs are stretched with `textLength` so the grid stays aligned whatever
+/// monospace font the viewer substitutes.
+pub fn to_svg_with(buffer: &Buffer, options: &ExportOptions) -> String {
+    let cell_w = options.font_size * 0.6;
+    let cell_h = options.font_size * 1.25;
+    let width = num(buffer.width() as f32 * cell_w);
+    let height = num(buffer.height() as f32 * cell_h);
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = width,
+        h = height
+    );
+    if let Some(title) = &options.title {
+        let _ = writeln!(out, "<title>{}</title>", escape(title));
+    }
+    let _ = writeln!(
+        out,
+        "<style>text{{font-family:{};font-size:{}px;white-space:pre}}</style>",
+        escape(&options.font_family),
+        num(options.font_size)
+    );
+    let _ = writeln!(
+        out,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        hex(options.background)
+    );
+
+    for y in 0..buffer.height() {
+        let glyphs = row_glyphs(buffer, y, options);
+        let top = y as f32 * cell_h;
+
+        // Backgrounds, merged into runs of equal color
+        let mut start = 0;
+        while start < glyphs.len() {
+            let bg = glyphs[start].style.bg;
+            let mut end = start + 1;
+            while end < glyphs.len() && glyphs[end].style.bg == bg {
+                end += 1;
+            }
+            if bg != options.background {
+                let cells = span_width(&glyphs[start..end]);
+                let _ = writeln!(
+                    out,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    num(glyphs[start].x as f32 * cell_w),
+                    num(top),
+                    num(cells as f32 * cell_w),
+                    num(cell_h),
+                    hex(bg)
+                );
+            }
+            start = end;
+        }
+
+        // Text, in runs of equal style; wide characters stand alone so
+        // stretching never shifts the narrow characters around them
+        let baseline = top + cell_h * 0.8;
+        let mut start = 0;
+        while start < glyphs.len() {
+            let first = &glyphs[start];
+            let mut end = start + 1;
+            if first.width == 1 {
+                while end < glyphs.len()
+                    && glyphs[end].width == 1
+                    && same_text_style(&glyphs[end].style, &first.style)
+                {
+                    end += 1;
+                }
+            }
+            write_text(&mut out, &glyphs[start..end], baseline, cell_w);
+            start = end;
+        }
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Emit one `<text>` element for a run, trimming surrounding spaces
+fn write_text(out: &mut String, run: &[Glyph], baseline: f32, cell_w: f32) {
+    let Some(first) = run.iter().position(|g| g.symbol != ' ') else {
+        return;
+    };
+    let last = run.iter().rposition(|g| g.symbol != ' ').unwrap_or(first);
+    let run = &run[first..=last];
+    let text: String = run.iter().map(|g| g.symbol).collect();
+    let style = &run[0].style;
+
+    let mut attrs = format!(r#" fill="{}""#, hex(style.fg));
+    if style.modifier.contains(Modifier::BOLD) {
+        attrs.push_str(r#" font-weight="bold""#);
+    }
+    if style.modifier.contains(Modifier::ITALIC) {
+        attrs.push_str(r#" font-style="italic""#);
+    }
+    let decoration = decoration(style.modifier);
+    if !decoration.is_empty() {
+        let _ = write!(attrs, r#" text-decoration="{}""#, decoration);
+    }
+
+    let _ = writeln!(
+        out,
+        r#"<text x="{}" y="{}"{} textLength="{}" lengthAdjust="spacingAndGlyphs">{}</text>"#,
+        num(run[0].x as f32 * cell_w),
+        num(baseline),
+        attrs,
+        num(span_width(run) as f32 * cell_w),
+        escape(&text)
+    );
+}
+
+/// Space-separated text decorations for the modifiers
+pub(super) fn decoration(modifier: Modifier) -> String {
+    let mut parts = Vec::new();
+    if modifier.contains(Modifier::UNDERLINE) {
+        parts.push("underline");
+    }
+    if modifier.contains(Modifier::CROSSED_OUT) {
+        parts.push("line-through");
+    }
+    parts.join(" ")
+}
+
+fn same_text_style(a: &ResolvedStyle, b: &ResolvedStyle) -> bool {
+    a.fg == b.fg && a.modifier == b.modifier
+}
+
+fn span_width(glyphs: &[Glyph]) -> u16 {
+    glyphs.iter().map(|g| g.width).sum()
+}
+
+/// Format a coordinate with at most two decimals
+fn num(value: f32) -> String {
+    let s = format!("{:.2}", value);
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::Cell;
+    use crate::style::Color;
+
+    #[test]
+    fn test_svg_size_and_background() {
+        let buffer = Buffer::new(10, 2);
+        let svg = to_svg(&buffer);
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="84" height="35""#)
+        );
+        assert!(svg.contains(r##"fill="#181818""##));
+        assert!(!svg.contains("<text"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_svg_text_runs_and_styles() {
+        let mut buffer = Buffer::new(12, 1);
+        buffer.put_str(0, 0, "  a<b");
+        buffer.set(6, 0, Cell::new('X').fg(Color::RED).bg(Color::BLUE).bold());
+        let svg = to_svg_with(&buffer, &ExportOptions::default().title("demo"));
+
+        assert!(svg.contains("<title>demo</title>"));
+        // Leading spaces are trimmed and markup is escaped
+        assert!(svg.contains(r##"<text x="16.8" y="14" fill="#cccccc" textLength="25.2""##));
+        assert!(svg.contains(">a&lt;b</text>"));
+        assert!(
+            svg.contains(r##"<rect x="50.4" y="0" width="8.4" height="17.5" fill="#0000ff"/>"##)
+        );
+        assert!(svg.contains(r##"fill="#ff0000" font-weight="bold""##));
+    }
+
+    #[test]
+    fn test_svg_wide_chars_stand_alone() {
+        let mut buffer = Buffer::new(6, 1);
+        buffer.put_str(0, 0, "a中b");
+        let svg = to_svg(&buffer);
+        assert!(svg.contains(r#"textLength="16.8" lengthAdjust="spacingAndGlyphs">中</text>"#));
+        assert!(svg.contains(r#"<text x="25.2""#));
+    }
+
+    #[test]
+    fn test_decoration() {
+        assert_eq!(decoration(Modifier::empty()), "");
+        assert_eq!(
+            decoration(Modifier::UNDERLINE | Modifier::CROSSED_OUT),
+            "underline line-through"
+        );
+    }
+}
@@ -14,6 +14,7 @@
 //! | **Terminal** | High-level diff-based renderer | (see [`Terminal`]) |
 //! | **Diff** | Efficient buffer diffing algorithm | (see [`diff`](diff())) |
 //! | **Images** | Kitty, iTerm2, and Sixel graphics | Available with `image` feature |
+//! | **Export** | Buffer snapshots as SVG, HTML, and PNG | [`export`] |
 //!
 //! # Quick Start
 //!
@@ -79,6 +80,7 @@ mod batch;
 mod buffer;
 mod cell;
 mod diff;
+pub mod export;
 #[cfg(feature = "image")]
 pub mod image_protocol;
 mod terminal;
@@ -23,13 +23,24 @@ impl<W: Write> Terminal<W> {
     pub fn new(writer: W) -> Result<Self> {
         let (width, height) = terminal::size()?;
         Ok(Self {
-            writer,
+            writer: super::types::CountingWriter::new(writer),
             current: Buffer::new(width, height),
             raw_mode: false,
             mouse_capture: false,
         })
     }
 
+    /// Create a terminal of a fixed size without querying the real one
+    #[cfg(test)]
+    pub(crate) fn with_size(writer: W, width: u16, height: u16) -> Self {
+        Self {
+            writer: super::types::CountingWriter::new(writer),
+            current: Buffer::new(width, height),
+            raw_mode: false,
+            mouse_capture: false,
+        }
+    }
+
     /// Initialize the terminal for TUI mode with mouse capture
     pub fn init(&mut self) -> Result<()> {
         self.init_with_mouse(true)
@@ -87,6 +98,11 @@ impl<W: Write> Terminal<W> {
         (self.current.width(), self.current.height())
     }
 
+    /// Get the total bytes written to the terminal
+    pub fn bytes_written(&self) -> u64 {
+        self.writer.count()
+    }
+
     /// Resize the terminal buffer
     pub fn resize(&mut self, width: u16, height: u16) {
         self.current.resize(width, height);
@@ -15,10 +15,39 @@ pub(crate) struct RenderState {
     pub(crate) cursor: Option<(u16, u16)>,
 }
 
+/// Writer that counts the bytes written through it
+pub(crate) struct CountingWriter<W: Write> {
+    inner: W,
+    count: u64,
+}
+
+impl<W: Write> CountingWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self { inner, count: 0 }
+    }
+
+    /// Total bytes written so far
+    pub(crate) fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.count += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
 /// Terminal backend for rendering
 pub struct Terminal<W: Write> {
     /// Output writer
-    pub(crate) writer: W,
+    pub(crate) writer: CountingWriter<W>,
     /// Current buffer (what's on screen)
     pub(crate) current: crate::render::Buffer,
     /// Whether we're in raw mode
@@ -1,5 +1,6 @@
 //! Animation group support for coordinating multiple animations
 
+use crate::utils::clock;
 use std::time::{Duration, Instant};
 
 use super::{AnimationState, KeyframeAnimation};
@@ -65,7 +66,7 @@ impl AnimationGroup {
 
     /// Start all animations
     pub fn start(&mut self) {
-        self.start_time = Some(Instant::now());
+        self.start_time = Some(clock::now());
         self.state = AnimationState::Running;
 
         match self.mode {
@@ -1,5 +1,6 @@
 //! CSS @keyframes style animation support
 
+use crate::utils::clock;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
@@ -195,7 +196,7 @@ impl KeyframeAnimation {
             return;
         }
 
-        self.start_time = Some(Instant::now());
+        self.start_time = Some(clock::now());
         self.state = AnimationState::Running;
         self.current_iteration = 0;
     }
@@ -246,7 +247,7 @@ impl KeyframeAnimation {
             return if self.is_completed() { 1.0 } else { 0.0 };
         }
 
-        let elapsed = start.elapsed();
+        let elapsed = clock::elapsed(start);
         if elapsed < self.delay {
             return 0.0;
         }
@@ -344,7 +345,7 @@ impl KeyframeAnimation {
             return;
         };
 
-        let elapsed = start.elapsed();
+        let elapsed = clock::elapsed(start);
         if elapsed < self.delay {
             return;
         }
@@ -359,7 +360,7 @@ impl KeyframeAnimation {
                 self.state = AnimationState::Completed;
             } else {
                 // Start next iteration
-                self.start_time = Some(Instant::now());
+                self.start_time = Some(clock::now());
             }
         }
     }
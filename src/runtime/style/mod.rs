@@ -162,7 +162,8 @@ pub use error::{
     KNOWN_PROPERTIES,
 };
 pub use parser::{
-    apply_declaration, Declaration, KeyframeBlock, KeyframesDefinition, Rule, StyleSheet,
+    apply_declaration, Declaration, KeyframeBlock, KeyframesDefinition, Rule, SourceMap,
+    SourceSpan, StyleSheet,
 };
 pub use properties::*;
 pub use theme::{
@@ -192,6 +193,11 @@ pub fn parse_css(css: &str) -> Result<StyleSheet, ParseError> {
     parser::parse(css)
 }
 
+/// Parse a CSS file, also returning the source location of each rule
+pub fn parse_css_with_source_map(css: &str) -> Result<(StyleSheet, SourceMap), ParseError> {
+    parser::parse_with_source_map(css)
+}
+
 /// CSS parsing error with rich context
 ///
 /// Provides detailed error messages with source location, suggestions,
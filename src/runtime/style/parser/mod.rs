@@ -6,8 +6,10 @@ mod types;
 mod value_parsers;
 
 pub use apply::apply_declaration;
-pub use parse::parse;
-pub use types::{Declaration, KeyframeBlock, KeyframesDefinition, Rule, StyleSheet};
+pub use parse::{parse, parse_with_source_map};
+pub use types::{
+    Declaration, KeyframeBlock, KeyframesDefinition, Rule, SourceMap, SourceSpan, StyleSheet,
+};
 #[allow(unused_imports)]
 pub use value_parsers::{
     parse_calc, parse_color, parse_grid_placement, parse_grid_template, parse_signed_length,
@@ -21,6 +23,7 @@ mod tests {
         AlignSelf, Color, Display, FlexDirection, FlexWrap, FontWeight, Overflow, Position, Size,
         Spacing, Style, TextAlign, VisualStyle,
     };
+    use std::path::Path;
 
     #[test]
     fn test_parse_empty() {
@@ -82,6 +85,72 @@ mod tests {
         assert_eq!(sheet.rules[0].declarations.len(), 1);
     }
 
+    #[test]
+    fn test_parse_rule_spans() {
+        let css = "/* a */\n.a { color: red; }\n\n.b {\n    width: 1;\n}\n";
+        let (sheet, mut map) = parse_with_source_map(css).unwrap();
+        assert_eq!(sheet.rules[0].selector, ".a");
+
+        let spans: Vec<_> = (0..2).map(|i| map.span(i).unwrap().clone()).collect();
+        assert_eq!(spans[0].line, 2);
+        assert_eq!(&css[spans[0].range.clone()], ".a { color: red; }");
+        assert_eq!(spans[1].line, 4);
+        assert_eq!(&css[spans[1].range.clone()], ".b {\n    width: 1;\n}");
+        assert!(map.span(2).is_none());
+
+        map.set_file("theme.css");
+        assert!(
+            (0..2).all(|i| map.span(i).unwrap().file.as_deref() == Some(Path::new("theme.css")))
+        );
+
+        // Spans follow the rules when the file changes around them
+        let (_, moved) = parse_with_source_map(&format!("\n\n{}", css)).unwrap();
+        map.refresh(Path::new("theme.css"), &moved);
+        assert_eq!(map.span(1).unwrap().line, 6);
+    }
+
+    #[test]
+    fn test_source_map_merge_and_refresh_copies() {
+        let path = Path::new("theme.css");
+        let two = ".a { color: red; }\n.b { width: 1; }\n";
+        let mut sheet = StyleSheet::new();
+        let mut map = SourceMap::new();
+
+        // Inline rules without spans, then the file merged twice
+        sheet.merge(parse(".x { width: 2; }").unwrap());
+        for _ in 0..2 {
+            let (other, mut other_map) = parse_with_source_map(two).unwrap();
+            other_map.set_file(path);
+            map.merge(sheet.rules.len(), other_map);
+            sheet.merge(other);
+        }
+        assert!(map.span(0).is_none());
+        assert_eq!(map.span(3).unwrap().line, 1);
+        assert_eq!(map.span(4).unwrap().line, 2);
+
+        let (_, moved) = parse_with_source_map(&format!("\n{}", two)).unwrap();
+        map.refresh(path, &moved);
+        assert_eq!(
+            (1..5)
+                .map(|i| map.span(i).unwrap().line)
+                .collect::<Vec<_>>(),
+            vec![2, 3, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_source_map_refresh_ignores_shrunk_file() {
+        let path = Path::new("theme.css");
+        let (_, mut map) = parse_with_source_map(".a { color: red; }\n.b { width: 1; }\n").unwrap();
+        map.set_file(path);
+
+        // One rule left: 2 is a multiple of 1, but the rules can't be paired
+        let (_, shrunk) = parse_with_source_map("\n\n.b { width: 1; }\n").unwrap();
+        map.refresh(path, &shrunk);
+        assert_eq!(map.span(0).unwrap().line, 1);
+        assert_eq!(map.span(1).unwrap().line, 2);
+    }
+
     #[test]
     fn test_apply_stylesheet() {
         let css = r#"
@@ -1,7 +1,7 @@
 //! CSS parser for TUI styling
 
 use crate::constants::{MAX_COMMENT_LENGTH, MAX_CSS_FILE_SIZE};
-use crate::style::{Declaration, ErrorCode, ParseError, Rule, StyleSheet};
+use crate::style::{Declaration, ErrorCode, ParseError, Rule, SourceMap, SourceSpan, StyleSheet};
 
 /// Create a ParseError at the given position
 fn make_error(css: &str, pos: usize, message: &str, code: ErrorCode) -> ParseError {
@@ -30,6 +30,11 @@ const MAX_KEYFRAMES: usize = 100;
 const MAX_KEYFRAME_BLOCKS: usize = 50;
 
 pub fn parse(css: &str) -> Result<StyleSheet, ParseError> {
+    parse_with_source_map(css).map(|(sheet, _)| sheet)
+}
+
+/// Parse CSS, also returning where each rule is in `css`
+pub fn parse_with_source_map(css: &str) -> Result<(StyleSheet, SourceMap), ParseError> {
     // Check CSS size limit before parsing
     if css.len() > MAX_CSS_SIZE {
         return Err(make_error(
@@ -45,9 +50,13 @@ pub fn parse(css: &str) -> Result<StyleSheet, ParseError> {
     }
 
     let mut sheet = StyleSheet::new();
+    let mut source_map = SourceMap::new();
     let bytes = css.as_bytes();
     let mut pos = 0;
     let mut total_declarations = 0;
+    // Line of `line_pos`, advanced lazily as rules are found
+    let mut line = 1;
+    let mut line_pos = 0;
 
     while pos < bytes.len() {
         // Check rule limit
@@ -64,7 +73,7 @@ pub fn parse(css: &str) -> Result<StyleSheet, ParseError> {
             ));
         }
         // Skip whitespace and comments
-        pos = skip_whitespace_bytes(bytes, pos);
+        pos = skip_whitespace_and_comments_bytes(bytes, pos);
         if pos >= bytes.len() {
             break;
         }
@@ -94,6 +103,7 @@ pub fn parse(css: &str) -> Result<StyleSheet, ParseError> {
         }
 
         // Parse selector
+        let rule_start = pos;
         let (selector, new_pos) = parse_selector_str(css, pos)?;
         pos = new_pos;
 
@@ -143,13 +153,24 @@ pub fn parse(css: &str) -> Result<StyleSheet, ParseError> {
         }
         pos += 1;
 
+        line += bytes[line_pos..rule_start]
+            .iter()
+            .filter(|&&b| b == b'\n')
+            .count();
+        line_pos = rule_start;
+
         sheet.rules.push(Rule {
             selector,
             declarations,
         });
+        source_map.push(SourceSpan {
+            file: None,
+            line,
+            range: rule_start..pos,
+        });
     }
 
-    Ok(sheet)
+    Ok((sheet, source_map))
 }
 
 /// Skip ASCII whitespace using byte slice (no allocation)
@@ -1,6 +1,8 @@
 //! CSS parser types
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
 /// A parsed @keyframes definition
 #[derive(Debug, Clone)]
@@ -40,6 +42,114 @@ pub struct Rule {
     pub declarations: Vec<Declaration>,
 }
 
+impl Rule {
+    /// Create a rule
+    pub fn new(selector: impl Into<String>, declarations: Vec<Declaration>) -> Self {
+        Self {
+            selector: selector.into(),
+            declarations,
+        }
+    }
+}
+
+/// Location of a rule in its stylesheet source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceSpan {
+    /// Stylesheet file, if the sheet was loaded from one
+    pub file: Option<PathBuf>,
+    /// Line of the selector (1-indexed)
+    pub line: usize,
+    /// Byte range from the selector through the closing brace
+    pub range: Range<usize>,
+}
+
+/// Source locations of a stylesheet's rules
+///
+/// Kept beside a [`StyleSheet`] and indexed like its `rules`. Rules built
+/// in code, or added after the map was last merged, have no span.
+#[derive(Debug, Default, Clone)]
+pub struct SourceMap {
+    entries: Vec<Option<SourceEntry>>,
+}
+
+#[derive(Debug, Clone)]
+struct SourceEntry {
+    span: SourceSpan,
+    /// Index of the first rule of the sheet this entry was merged with
+    origin: usize,
+}
+
+impl SourceMap {
+    /// Create an empty source map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the span of the next rule
+    pub(crate) fn push(&mut self, span: SourceSpan) {
+        self.entries.push(Some(SourceEntry { span, origin: 0 }));
+    }
+
+    /// Span of the rule at `index`
+    pub fn span(&self, index: usize) -> Option<&SourceSpan> {
+        self.entries.get(index)?.as_ref().map(|entry| &entry.span)
+    }
+
+    /// Record `path` as the source file of every span without one
+    pub fn set_file(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        for entry in self.entries.iter_mut().flatten() {
+            if entry.span.file.is_none() {
+                entry.span.file = Some(path.clone());
+            }
+        }
+    }
+
+    /// Append the map of a sheet merged after the first `offset` rules
+    ///
+    /// Call with the rule count from before [`StyleSheet::merge`].
+    pub fn merge(&mut self, offset: usize, other: SourceMap) {
+        self.entries.resize(offset, None);
+        self.entries.extend(other.entries.into_iter().map(|entry| {
+            entry.map(|entry| SourceEntry {
+                origin: offset + entry.origin,
+                ..entry
+            })
+        }));
+    }
+
+    /// Update the spans of rules from `path` after the file changed
+    ///
+    /// `fresh` is the map of the new contents. Each merged copy of the file
+    /// is paired up with it in source order, so a copy only updates when it
+    /// has exactly as many rules as the file now does.
+    pub(crate) fn refresh(&mut self, path: &Path, fresh: &SourceMap) {
+        let fresh: Vec<&SourceSpan> = fresh.entries.iter().flatten().map(|e| &e.span).collect();
+        // The same file may have been merged more than once by hot reload
+        let mut copies: BTreeMap<usize, Vec<&mut SourceSpan>> = BTreeMap::new();
+        for entry in self
+            .entries
+            .iter_mut()
+            .flatten()
+            .filter(|entry| entry.span.file.as_deref() == Some(path))
+        {
+            copies
+                .entry(entry.origin)
+                .or_default()
+                .push(&mut entry.span);
+        }
+        for stale in copies
+            .into_values()
+            .filter(|spans| spans.len() == fresh.len())
+        {
+            for (span, new) in stale.into_iter().zip(&fresh) {
+                span.line = new.line;
+                span.range = new.range.clone();
+            }
+        }
+    }
+}
+
 /// A CSS declaration (property: value)
 #[derive(Debug, Clone)]
 pub struct Declaration {
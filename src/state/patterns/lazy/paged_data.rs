@@ -1,6 +1,6 @@
 //! Paged data source for large datasets
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 /// Paged data source for large datasets
 pub struct PagedData<T, F>
@@ -8,7 +8,7 @@ where
     F: Fn(usize, usize) -> Vec<T>,
 {
     /// Total item count
-    total: usize,
+    total: Cell<usize>,
     /// Page size
     page_size: usize,
     /// Loaded pages (page_index -> items)
@@ -29,7 +29,7 @@ where
         let pages: Vec<Option<Vec<T>>> = (0..num_pages).map(|_| None).collect();
         let loading: Vec<bool> = vec![false; num_pages];
         Self {
-            total,
+            total: Cell::new(total),
             page_size,
             pages: RefCell::new(pages),
             loader,
@@ -39,7 +39,18 @@ where
 
     /// Get total item count
     pub fn total(&self) -> usize {
-        self.total
+        self.total.get()
+    }
+
+    /// Change the total item count, keeping the pages already loaded
+    ///
+    /// Useful when the total is only discovered while paging. Pages past the
+    /// new end are dropped.
+    pub fn set_total(&self, total: usize) {
+        self.total.set(total);
+        let num_pages = total.div_ceil(self.page_size);
+        self.pages.borrow_mut().resize_with(num_pages, || None);
+        self.loading.borrow_mut().resize(num_pages, false);
     }
 
     /// Get page size
@@ -49,7 +60,7 @@ where
 
     /// Get number of pages
     pub fn page_count(&self) -> usize {
-        self.total.div_ceil(self.page_size)
+        self.total().div_ceil(self.page_size)
     }
 
     /// Check if a page is loaded
@@ -63,7 +74,7 @@ where
 
     /// Get item at index
     pub fn get(&self, index: usize) -> Option<std::cell::Ref<'_, T>> {
-        if index >= self.total {
+        if index >= self.total() {
             return None;
         }
 
@@ -86,7 +97,7 @@ where
     /// Get a range of items (loads pages as needed)
     pub fn get_range(&self, start: usize, end: usize) -> Vec<std::cell::Ref<'_, T>> {
         let mut result = Vec::new();
-        for i in start..end.min(self.total) {
+        for i in start..end.min(self.total()) {
             if let Some(item) = self.get(i) {
                 result.push(item);
             }
@@ -260,6 +271,20 @@ mod tests {
         assert!(!paged.is_page_loaded(1));
     }
 
+    #[test]
+    fn test_paged_data_set_total() {
+        let paged = create_test_paged();
+        paged.get(0);
+        paged.set_total(150);
+        assert_eq!(paged.page_count(), 15);
+        assert!(paged.is_page_loaded(0));
+        assert_eq!(*paged.get(120).unwrap(), 120);
+
+        paged.set_total(5);
+        assert_eq!(paged.page_count(), 1);
+        assert!(paged.get(5).is_none());
+    }
+
     #[test]
     fn test_paged_data_with_strings() {
         let paged = PagedData::new(20, 5, |page, size| {
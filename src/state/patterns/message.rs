@@ -34,6 +34,7 @@
 //! ```
 
 use crate::constants::MESSAGE_DEFAULT_DURATION;
+use crate::utils::clock;
 use std::time::{Duration, Instant};
 
 /// Default message display duration (3 seconds)
@@ -97,7 +98,7 @@ impl MessageState {
     /// ```
     pub fn set(&mut self, message: String) {
         self.message = Some(message);
-        self.message_time = Some(Instant::now());
+        self.message_time = Some(clock::now());
     }
 
     /// Set message with custom duration (one-time override)
@@ -113,7 +114,7 @@ impl MessageState {
     /// ```
     pub fn set_with_duration(&mut self, message: String, duration: Duration) {
         self.message = Some(message);
-        self.message_time = Some(Instant::now());
+        self.message_time = Some(clock::now());
         self.duration = duration;
     }
 
@@ -150,7 +151,7 @@ impl MessageState {
     /// ```
     pub fn check_timeout(&mut self) -> bool {
         if let Some(time) = self.message_time {
-            if time.elapsed() >= self.duration {
+            if clock::elapsed(time) >= self.duration {
                 self.clear();
                 return true; // Needs redraw
             }
@@ -163,7 +164,7 @@ impl MessageState {
     /// Returns `None` if no message is active.
     pub fn remaining(&self) -> Option<Duration> {
         self.message_time.map(|time| {
-            let elapsed = time.elapsed();
+            let elapsed = clock::elapsed(time);
             self.duration.saturating_sub(elapsed)
         })
     }
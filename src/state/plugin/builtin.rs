@@ -1,6 +1,7 @@
 //! Built-in plugins
 
 use super::{Plugin, PluginContext};
+use crate::utils::clock;
 use std::time::{Duration, Instant};
 
 // =============================================================================
@@ -124,7 +125,7 @@ impl PerformancePlugin {
         Self {
             frame_times: Vec::with_capacity(120),
             max_samples: 120,
-            last_report: Instant::now(),
+            last_report: clock::now(),
             report_interval: Duration::from_secs(5),
         }
     }
@@ -206,7 +207,7 @@ impl Plugin for PerformancePlugin {
     }
 
     fn on_mount(&mut self, _ctx: &mut PluginContext) -> crate::Result<()> {
-        self.last_report = Instant::now();
+        self.last_report = clock::now();
         Ok(())
     }
 
@@ -223,7 +224,7 @@ impl Plugin for PerformancePlugin {
         ctx.set_data("frame_time_ms", delta.as_secs_f64() * 1000.0);
 
         // Periodic report
-        if self.last_report.elapsed() >= self.report_interval {
+        if clock::elapsed(self.last_report) >= self.report_interval {
             let (min, max, avg) = self.frame_time_stats();
             ctx.log(&format!(
                 "FPS: {:.1} | Frame time: {:.2}ms (min: {:.2}ms, max: {:.2}ms)",
@@ -232,7 +233,7 @@ impl Plugin for PerformancePlugin {
                 min.as_secs_f64() * 1000.0,
                 max.as_secs_f64() * 1000.0
             ));
-            self.last_report = Instant::now();
+            self.last_report = clock::now();
         }
 
         Ok(())
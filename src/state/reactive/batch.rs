@@ -32,6 +32,9 @@ thread_local! {
     /// Batch depth counter for nested batches (per-thread)
     static BATCH_DEPTH: RefCell<usize> = const { RefCell::new(0) };
 
+    /// Identifier of the outermost active batch (per-thread)
+    static BATCH_ID: RefCell<Option<usize>> = const { RefCell::new(None) };
+
     /// Pending updates to flush (per-thread)
     static PENDING_UPDATES: RefCell<Vec<Box<dyn FnOnce()>>> = const { RefCell::new(Vec::new()) };
 }
@@ -90,10 +93,15 @@ where
 /// end_batch();
 /// ```
 pub fn start_batch() {
-    BATCH_DEPTH.with(|depth| {
-        *depth.borrow_mut() += 1;
+    let outermost = BATCH_DEPTH.with(|depth| {
+        let mut d = depth.borrow_mut();
+        *d += 1;
+        *d == 1
     });
-    BATCH_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let id = BATCH_COUNTER.fetch_add(1, Ordering::Relaxed);
+    if outermost {
+        BATCH_ID.with(|batch_id| *batch_id.borrow_mut() = Some(id));
+    }
 }
 
 /// End a batch manually
@@ -105,6 +113,7 @@ pub fn end_batch() {
         *d = d.saturating_sub(1);
 
         if *d == 0 {
+            BATCH_ID.with(|batch_id| *batch_id.borrow_mut() = None);
             flush_updates();
         }
     });
@@ -123,6 +132,15 @@ pub fn batch_depth() -> usize {
     BATCH_DEPTH.with(|depth| *depth.borrow())
 }
 
+/// Get an identifier for the outermost batch currently active on this thread
+///
+/// Returns `None` outside a batch. Nested batches share the identifier of the
+/// outermost one, so all updates made inside a single `batch()` call can be
+/// grouped together (e.g. into one undo step).
+pub fn batch_id() -> Option<usize> {
+    BATCH_ID.with(|batch_id| *batch_id.borrow())
+}
+
 /// Get total batch count (for debugging)
 pub fn batch_count() -> usize {
     BATCH_COUNTER.load(Ordering::Relaxed)
@@ -381,6 +399,22 @@ mod tests {
         assert_eq!(batch_depth(), 0);
     }
 
+    #[test]
+    fn test_batch_id_shared_by_nested_batches() {
+        assert_eq!(batch_id(), None);
+        let (outer, inner) = batch(|| {
+            let outer = batch_id();
+            let inner = batch(batch_id);
+            (outer, inner)
+        });
+        assert!(outer.is_some());
+        assert_eq!(outer, inner);
+        assert_eq!(batch_id(), None);
+
+        let next = batch(batch_id);
+        assert_ne!(next, outer);
+    }
+
     // batch_depth tests
     #[test]
     fn test_batch_depth_initial() {
@@ -150,6 +150,13 @@ impl<T: Clone + Send + Sync + 'static> Clone for Computed<T> {
     }
 }
 
+/// Formats the current value, recomputing if dirty
+impl<T: Clone + Send + Sync + std::fmt::Display + 'static> std::fmt::Display for Computed<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
 impl<T: Clone + Send + Sync + 'static> Drop for Computed<T> {
     /// Dispose subscriber when the last clone is dropped.
     ///
@@ -0,0 +1,644 @@
+//! Transactional undo/redo history for signals and stores
+//!
+//! A [`History`] observes tracked signals and records every change as a
+//! reversible entry. Changes made inside one [`batch()`](super::batch) or
+//! [`History::transact()`] call are recorded as a single entry, so undo
+//! restores all of them at once.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use revue::reactive::{batch, signal, History};
+//! use std::time::Duration;
+//!
+//! let title = signal(String::new());
+//! let width = signal(80);
+//! let height = signal(24);
+//!
+//! let history = History::new().merge_window(Duration::from_millis(500));
+//! history.track(&title, "Edit title");
+//! history.track(&width, "Resize");
+//! history.track(&height, "Resize");
+//!
+//! // Keystrokes within the merge window become one undo step
+//! history.transact("Typing", || title.update(|t| t.push('H')));
+//! history.transact("Typing", || title.update(|t| t.push('i')));
+//!
+//! // Changes inside one batch are one undo step
+//! batch(|| {
+//!     width.set(120);
+//!     height.set(40);
+//! });
+//!
+//! history.undo(); // width = 80, height = 24
+//! history.undo(); // title = ""
+//! history.redo(); // title = "Hi"
+//! ```
+//!
+//! # Branching Redo
+//!
+//! Recording a change after an undo does not discard the undone entries.
+//! They remain as a sibling branch that can be selected with
+//! [`History::select_branch()`] before calling [`History::redo()`].
+//!
+//! # Stores
+//!
+//! Stores deriving `Store` with `#[store(history)]` implement
+//! [`TrackHistory`], which tracks every `Signal` field:
+//!
+//! ```rust,ignore
+//! #[derive(Store)]
+//! #[store(history)]
+//! struct EditorStore {
+//!     text: Signal<String>,
+//!     cursor: Signal<usize>,
+//! }
+//!
+//! let store = EditorStore { text: signal(String::new()), cursor: signal(0) };
+//! history.track_store(&store);
+//! ```
+
+use super::batch::{batch, batch_id};
+use super::signal::{Signal, Subscription};
+use super::SignalId;
+use crate::event::{Key, KeyEvent};
+use crate::utils::clock;
+use crate::utils::lock::lock_or_recover;
+use std::any::Any;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+/// Type-erased signal value
+type Value = Arc<dyn Any + Send + Sync>;
+
+/// Writes a type-erased value back into its signal
+type ApplyFn = Arc<dyn Fn(&Value) + Send + Sync>;
+
+/// Types whose state can be tracked by a [`History`]
+///
+/// Implemented by `#[derive(Store)]` when the store is marked with
+/// `#[store(history)]`.
+pub trait TrackHistory {
+    /// Register all reversible state with the history
+    fn track_history(&self, history: &History);
+}
+
+/// A single signal change within an entry
+#[derive(Clone)]
+struct Change {
+    signal: SignalId,
+    old: Value,
+    new: Value,
+    apply: ApplyFn,
+}
+
+/// How a change joins an existing entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Group {
+    Batch(usize),
+    Transaction(u64),
+}
+
+/// A reversible history entry
+struct Entry {
+    label: String,
+    changes: Vec<Change>,
+    group: Option<Group>,
+    updated_at: Instant,
+}
+
+impl Entry {
+    /// Add a change, keeping the oldest value for signals already in the entry
+    fn push(&mut self, change: Change) {
+        match self.changes.iter_mut().find(|c| c.signal == change.signal) {
+            Some(existing) => existing.new = change.new,
+            None => self.changes.push(change),
+        }
+    }
+}
+
+/// Node in the undo tree; the root (index 0) has no entry
+struct Node {
+    parent: Option<usize>,
+    children: Vec<usize>,
+    /// Child that `redo()` moves to
+    redo_child: Option<usize>,
+    entry: Option<Entry>,
+}
+
+/// Open transaction started by `transact()`
+struct OpenTransaction {
+    id: u64,
+    label: String,
+    depth: usize,
+}
+
+struct State {
+    nodes: Vec<Node>,
+    current: usize,
+    merge_window: Duration,
+    /// Prevents the next change from merging into the current entry
+    merge_barrier: bool,
+    transaction: Option<OpenTransaction>,
+    next_transaction: u64,
+}
+
+impl State {
+    fn new() -> Self {
+        Self {
+            nodes: vec![Node {
+                parent: None,
+                children: Vec::new(),
+                redo_child: None,
+                entry: None,
+            }],
+            current: 0,
+            merge_window: Duration::ZERO,
+            merge_barrier: false,
+            transaction: None,
+            next_transaction: 0,
+        }
+    }
+
+    /// Record a change at the current position
+    fn record(&mut self, change: Change, label: &str) {
+        let (group, label) = match &self.transaction {
+            Some(tx) => (Some(Group::Transaction(tx.id)), tx.label.as_str()),
+            None => (batch_id().map(Group::Batch), label),
+        };
+        let now = clock::now();
+        let merge_window = self.merge_window;
+        let merge_barrier = self.merge_barrier;
+
+        // Only the tip of a branch can absorb changes; entries with redo
+        // children are the base their children were recorded against.
+        let current = &mut self.nodes[self.current];
+        if current.children.is_empty() {
+            if let Some(entry) = current.entry.as_mut() {
+                let same_group = group.is_some() && entry.group == group;
+                let within_window = !merge_barrier
+                    && entry.label == label
+                    && !merge_window.is_zero()
+                    && now.duration_since(entry.updated_at) <= merge_window;
+                if same_group || within_window {
+                    entry.push(change);
+                    entry.group = group;
+                    entry.updated_at = now;
+                    return;
+                }
+            }
+        }
+
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            parent: Some(self.current),
+            children: Vec::new(),
+            redo_child: None,
+            entry: Some(Entry {
+                label: label.to_string(),
+                changes: vec![change],
+                group,
+                updated_at: now,
+            }),
+        });
+        let parent = &mut self.nodes[self.current];
+        parent.children.push(index);
+        parent.redo_child = Some(index);
+        self.current = index;
+        self.merge_barrier = false;
+    }
+}
+
+struct Inner {
+    state: Mutex<State>,
+    /// Set while undo/redo writes values back, so those writes aren't recorded
+    applying: AtomicBool,
+    subscriptions: Mutex<Vec<Subscription>>,
+}
+
+/// Undo/redo history recording signal changes as reversible transactions
+///
+/// `History` is cheap to clone; clones share the same history. Tracking
+/// stops when the last clone is dropped.
+#[derive(Clone)]
+pub struct History {
+    inner: Arc<Inner>,
+}
+
+impl History {
+    /// Create an empty history
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                state: Mutex::new(State::new()),
+                applying: AtomicBool::new(false),
+                subscriptions: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Set the merge window (default: no merging)
+    ///
+    /// Consecutive entries with the same label recorded within this window
+    /// are merged into one, so a burst of typing undoes as a single step.
+    pub fn merge_window(self, window: Duration) -> Self {
+        lock_or_recover(&self.inner.state).merge_window = window;
+        self
+    }
+
+    /// Record changes to a signal under the given label
+    ///
+    /// Changes made outside [`transact()`][Self::transact] use `label` as
+    /// their entry label. Setting a signal to an equal value is not recorded.
+    pub fn track<T>(&self, signal: &Signal<T>, label: impl Into<String>)
+    where
+        T: Clone + PartialEq + Send + Sync + 'static,
+    {
+        let label = label.into();
+        let snapshot = Mutex::new(signal.with_untracked(T::clone));
+        let weak: Weak<Inner> = Arc::downgrade(&self.inner);
+        let watched = signal.clone();
+
+        let writer = signal.clone();
+        let apply: ApplyFn = Arc::new(move |value: &Value| {
+            if let Some(value) = value.downcast_ref::<T>() {
+                writer.set(value.clone());
+            }
+        });
+
+        let subscription = signal.subscribe(move || {
+            let Some(inner) = weak.upgrade() else {
+                return;
+            };
+            let new = watched.with_untracked(T::clone);
+            let old = std::mem::replace(&mut *lock_or_recover(&snapshot), new.clone());
+            if inner.applying.load(Ordering::SeqCst) || old == new {
+                return;
+            }
+            let change = Change {
+                signal: watched.id(),
+                old: Arc::new(old),
+                new: Arc::new(new),
+                apply: Arc::clone(&apply),
+            };
+            lock_or_recover(&inner.state).record(change, &label);
+        });
+
+        lock_or_recover(&self.inner.subscriptions).push(subscription);
+    }
+
+    /// Track all reversible state of a store
+    pub fn track_store(&self, store: &impl TrackHistory) {
+        store.track_history(self);
+    }
+
+    /// Run `f` as a single labeled transaction
+    ///
+    /// All tracked changes made inside `f` (including nested `transact()`
+    /// calls) become one entry. Updates are batched.
+    pub fn transact<R>(&self, label: impl Into<String>, f: impl FnOnce() -> R) -> R {
+        {
+            let mut state = lock_or_recover(&self.inner.state);
+            match state.transaction.as_mut() {
+                Some(tx) => tx.depth += 1,
+                None => {
+                    let id = state.next_transaction;
+                    state.next_transaction += 1;
+                    state.transaction = Some(OpenTransaction {
+                        id,
+                        label: label.into(),
+                        depth: 1,
+                    });
+                }
+            }
+        }
+
+        let result = batch(f);
+
+        let mut state = lock_or_recover(&self.inner.state);
+        if let Some(tx) = state.transaction.as_mut() {
+            tx.depth -= 1;
+            if tx.depth == 0 {
+                state.transaction = None;
+            }
+        }
+        result
+    }
+
+    /// Undo the current entry, returning its label
+    pub fn undo(&self) -> Option<String> {
+        let (label, changes) = {
+            let mut state = lock_or_recover(&self.inner.state);
+            let current = state.current;
+            let node = &state.nodes[current];
+            let parent = node.parent?;
+            let entry = node.entry.as_ref()?;
+            let result = (entry.label.clone(), entry.changes.clone());
+            state.nodes[parent].redo_child = Some(current);
+            state.current = parent;
+            state.merge_barrier = true;
+            result
+        };
+
+        self.apply(changes.iter().rev().map(|c| (&c.apply, &c.old)));
+        Some(label)
+    }
+
+    /// Redo the next entry on the selected branch, returning its label
+    pub fn redo(&self) -> Option<String> {
+        let (label, changes) = {
+            let mut state = lock_or_recover(&self.inner.state);
+            let next = state.nodes[state.current].redo_child?;
+            let entry = state.nodes[next].entry.as_ref()?;
+            let result = (entry.label.clone(), entry.changes.clone());
+            state.current = next;
+            state.merge_barrier = true;
+            result
+        };
+
+        self.apply(changes.iter().map(|c| (&c.apply, &c.new)));
+        Some(label)
+    }
+
+    /// Write values back to their signals without recording them
+    fn apply<'a>(&self, values: impl Iterator<Item = (&'a ApplyFn, &'a Value)>) {
+        self.inner.applying.store(true, Ordering::SeqCst);
+        batch(|| {
+            for (apply, value) in values {
+                apply(value);
+            }
+        });
+        self.inner.applying.store(false, Ordering::SeqCst);
+    }
+
+    /// Check if there is an entry to undo
+    pub fn can_undo(&self) -> bool {
+        lock_or_recover(&self.inner.state).current != 0
+    }
+
+    /// Check if there is an entry to redo
+    pub fn can_redo(&self) -> bool {
+        let state = lock_or_recover(&self.inner.state);
+        state.nodes[state.current].redo_child.is_some()
+    }
+
+    /// Get the label of the entry `undo()` would revert
+    pub fn undo_label(&self) -> Option<String> {
+        let state = lock_or_recover(&self.inner.state);
+        state.nodes[state.current]
+            .entry
+            .as_ref()
+            .map(|e| e.label.clone())
+    }
+
+    /// Get the label of the entry `redo()` would reapply
+    pub fn redo_label(&self) -> Option<String> {
+        let state = lock_or_recover(&self.inner.state);
+        let next = state.nodes[state.current].redo_child?;
+        state.nodes[next].entry.as_ref().map(|e| e.label.clone())
+    }
+
+    /// Get the number of entries that can be undone
+    pub fn undo_depth(&self) -> usize {
+        let state = lock_or_recover(&self.inner.state);
+        let mut depth = 0;
+        let mut node = state.current;
+        while let Some(parent) = state.nodes[node].parent {
+            depth += 1;
+            node = parent;
+        }
+        depth
+    }
+
+    /// Get the number of redo branches at the current position
+    pub fn redo_branches(&self) -> usize {
+        let state = lock_or_recover(&self.inner.state);
+        state.nodes[state.current].children.len()
+    }
+
+    /// Select which branch `redo()` follows (0 = oldest)
+    ///
+    /// Returns `false` if `index` is out of range.
+    pub fn select_branch(&self, index: usize) -> bool {
+        let mut state = lock_or_recover(&self.inner.state);
+        let current = state.current;
+        match state.nodes[current].children.get(index).copied() {
+            Some(child) => {
+                state.nodes[current].redo_child = Some(child);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Start a new entry for the next change even within the merge window
+    pub fn break_merge(&self) {
+        lock_or_recover(&self.inner.state).merge_barrier = true;
+    }
+
+    /// Discard all entries (tracked signals stay tracked)
+    pub fn clear(&self) {
+        let mut state = lock_or_recover(&self.inner.state);
+        let merge_window = state.merge_window;
+        *state = State::new();
+        state.merge_window = merge_window;
+    }
+
+    /// Handle the standard undo/redo shortcuts
+    ///
+    /// Ctrl+Z undoes; Ctrl+Shift+Z and Ctrl+Y redo. Returns `true` if the
+    /// key was an undo/redo shortcut, whether or not there was anything
+    /// to undo or redo.
+    pub fn handle_key(&self, key: &KeyEvent) -> bool {
+        if !key.ctrl || key.alt {
+            return false;
+        }
+        match key.key {
+            Key::Char('z') if !key.shift => {
+                self.undo();
+                true
+            }
+            Key::Char('z') | Key::Char('Z') | Key::Char('y') => {
+                self.redo();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for History {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("History")
+            .field("undo_depth", &self.undo_depth())
+            .field("can_redo", &self.can_redo())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactive::signal;
+
+    #[test]
+    fn test_history_records_and_undoes_single_changes() {
+        let count = signal(0);
+        let history = History::new();
+        history.track(&count, "Count");
+
+        count.set(1);
+        count.set(2);
+        assert_eq!(history.undo_depth(), 2);
+
+        assert_eq!(history.undo(), Some("Count".to_string()));
+        assert_eq!(count.get(), 1);
+        history.undo();
+        assert_eq!(count.get(), 0);
+        assert!(!history.can_undo());
+        assert_eq!(history.undo(), None);
+
+        history.redo();
+        history.redo();
+        assert_eq!(count.get(), 2);
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn test_history_ignores_equal_values() {
+        let count = signal(1);
+        let history = History::new();
+        history.track(&count, "Count");
+
+        count.set(1);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn test_history_groups_batch() {
+        let x = signal(0);
+        let y = signal(0);
+        let history = History::new();
+        history.track(&x, "Move");
+        history.track(&y, "Move");
+
+        batch(|| {
+            x.set(5);
+            y.set(7);
+            x.set(6);
+        });
+        assert_eq!(history.undo_depth(), 1);
+
+        history.undo();
+        assert_eq!((x.get(), y.get()), (0, 0));
+        history.redo();
+        assert_eq!((x.get(), y.get()), (6, 7));
+    }
+
+    #[test]
+    fn test_history_transaction_label() {
+        let x = signal(0);
+        let y = signal(0);
+        let history = History::new();
+        history.track(&x, "x");
+        history.track(&y, "y");
+
+        history.transact("Reset", || {
+            x.set(1);
+            history.transact("Inner", || y.set(1));
+        });
+
+        assert_eq!(history.undo_depth(), 1);
+        assert_eq!(history.undo_label().as_deref(), Some("Reset"));
+    }
+
+    #[test]
+    fn test_history_merge_window() {
+        let text = signal(String::new());
+        let history = History::new().merge_window(Duration::from_secs(60));
+        history.track(&text, "Edit");
+
+        for c in "abc".chars() {
+            history.transact("Typing", || text.update(|t| t.push(c)));
+        }
+        assert_eq!(history.undo_depth(), 1);
+
+        history.break_merge();
+        history.transact("Typing", || text.update(|t| t.push('d')));
+        assert_eq!(history.undo_depth(), 2);
+
+        history.undo();
+        assert_eq!(text.get(), "abc");
+        history.undo();
+        assert_eq!(text.get(), "");
+    }
+
+    #[test]
+    fn test_history_no_merge_without_window() {
+        let text = signal(String::new());
+        let history = History::new();
+        history.track(&text, "Edit");
+
+        text.set("a".into());
+        text.set("ab".into());
+        assert_eq!(history.undo_depth(), 2);
+    }
+
+    #[test]
+    fn test_history_branching_redo() {
+        let value = signal(0);
+        let history = History::new();
+        history.track(&value, "Set");
+
+        value.set(1);
+        history.undo();
+        value.set(2);
+        history.undo();
+
+        assert_eq!(history.redo_branches(), 2);
+        history.redo();
+        assert_eq!(value.get(), 2);
+
+        history.undo();
+        assert!(history.select_branch(0));
+        history.redo();
+        assert_eq!(value.get(), 1);
+        assert!(!history.select_branch(5));
+    }
+
+    #[test]
+    fn test_history_handle_key() {
+        let value = signal(0);
+        let history = History::new();
+        history.track(&value, "Set");
+        value.set(1);
+
+        assert!(history.handle_key(&KeyEvent::ctrl(Key::Char('z'))));
+        assert_eq!(value.get(), 0);
+
+        let mut redo = KeyEvent::ctrl(Key::Char('z'));
+        redo.shift = true;
+        assert!(history.handle_key(&redo));
+        assert_eq!(value.get(), 1);
+
+        assert!(!history.handle_key(&KeyEvent::new(Key::Char('z'))));
+    }
+
+    #[test]
+    fn test_history_stops_tracking_when_dropped() {
+        let value = signal(0);
+        let history = History::new();
+        history.track(&value, "Set");
+        drop(history);
+
+        value.set(1);
+        assert_eq!(value.get(), 1);
+    }
+}
@@ -96,19 +96,22 @@ mod batch;
 mod computed;
 mod context;
 mod effect;
+mod history;
 mod incremental;
+mod order_tree;
 mod runtime;
 mod signal;
 mod signal_vec;
 pub mod store;
 mod tracker;
+mod vec_ops;
 
 pub use async_state::{
     use_async, use_async_immediate, use_async_poll, AsyncResource, AsyncResult, AsyncState,
 };
 pub use batch::{
-    batch, batch_count, batch_depth, end_batch, flush, is_batching, pending_count, queue_update,
-    start_batch, BatchGuard, Transaction,
+    batch, batch_count, batch_depth, batch_id, end_batch, flush, is_batching, pending_count,
+    queue_update, start_batch, BatchGuard, Transaction,
 };
 pub use computed::Computed;
 pub use context::{
@@ -117,10 +120,11 @@ pub use context::{
     ContextId, ContextScope, Provider,
 };
 pub use effect::Effect;
+pub use history::{History, TrackHistory};
 pub use incremental::{IncrementalComputed, IncrementalHandlers};
 pub use runtime::ReactiveRuntime;
 pub use signal::{Signal, Subscription, SubscriptionId};
-pub use signal_vec::{SignalVec, VecDiff, VecSubscription};
+pub use signal_vec::{DiffQueue, SignalVec, VecDiff, VecSubscription};
 pub use store::{
     create_store, store_registry, use_store, Store, StoreExt, StoreId, StoreRegistry,
     StoreSubscription,
@@ -129,6 +133,7 @@ pub use tracker::{
     dispose_subscriber, is_tracking, notify_dependents, start_tracking, stop_tracking, track_read,
     with_tracker, DependencyTracker, Subscriber, SubscriberCallback, SubscriberId,
 };
+pub use vec_ops::VecGroup;
 
 use std::sync::atomic::{AtomicU64, Ordering};
 
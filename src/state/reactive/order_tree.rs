@@ -0,0 +1,345 @@
+//! Order-statistics sequence backing the `SignalVec` operators
+//!
+//! [`OrderTree`] is an implicit treap: a sequence that supports insert and
+//! remove at any position, position-of-entry lookup through stable
+//! [`NodeId`] handles, and prefix sums of a per-entry weight, all in
+//! expected O(log n). The operators in `vec_ops` use it so that a single
+//! source diff never scans or shifts the whole vector.
+
+use std::cmp::Ordering;
+
+/// Stable handle to an entry, valid until the entry is removed
+pub(super) type NodeId = usize;
+
+struct Node<P> {
+    value: P,
+    weight: usize,
+    priority: u64,
+    left: Option<NodeId>,
+    right: Option<NodeId>,
+    parent: Option<NodeId>,
+    /// Number of entries in this subtree
+    size: usize,
+    /// Sum of weights in this subtree
+    total: usize,
+}
+
+pub(super) struct OrderTree<P> {
+    nodes: Vec<Option<Node<P>>>,
+    free: Vec<NodeId>,
+    root: Option<NodeId>,
+    seed: u64,
+}
+
+impl<P> OrderTree<P> {
+    pub(super) fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            seed: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    /// Build a tree holding `(value, weight)` entries in order
+    pub(super) fn from_entries(entries: impl IntoIterator<Item = (P, usize)>) -> Self {
+        let mut tree = Self::new();
+        for (value, weight) in entries {
+            tree.insert_at(tree.len(), value, weight);
+        }
+        tree
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.size(self.root)
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub(super) fn get(&self, id: NodeId) -> &P {
+        &self.node(id).value
+    }
+
+    pub(super) fn get_mut(&mut self, id: NodeId) -> &mut P {
+        &mut self.node_mut(id).value
+    }
+
+    pub(super) fn weight(&self, id: NodeId) -> usize {
+        self.node(id).weight
+    }
+
+    /// Change an entry's weight
+    pub(super) fn set_weight(&mut self, id: NodeId, weight: usize) {
+        self.node_mut(id).weight = weight;
+        let mut current = Some(id);
+        while let Some(n) = current {
+            self.update(n);
+            current = self.node(n).parent;
+        }
+    }
+
+    /// Handle of the entry at `pos`
+    pub(super) fn at(&self, mut pos: usize) -> NodeId {
+        let mut current = self.root.expect("position out of bounds");
+        loop {
+            let node = self.node(current);
+            let left = self.size(node.left);
+            match pos.cmp(&left) {
+                Ordering::Less => current = node.left.expect("position out of bounds"),
+                Ordering::Equal => return current,
+                Ordering::Greater => {
+                    pos -= left + 1;
+                    current = node.right.expect("position out of bounds");
+                }
+            }
+        }
+    }
+
+    /// Position of an entry
+    pub(super) fn rank(&self, id: NodeId) -> usize {
+        let mut rank = self.size(self.node(id).left);
+        let mut child = id;
+        while let Some(parent) = self.node(child).parent {
+            let node = self.node(parent);
+            if node.right == Some(child) {
+                rank += self.size(node.left) + 1;
+            }
+            child = parent;
+        }
+        rank
+    }
+
+    /// Sum of the weights of the first `pos` entries
+    pub(super) fn prefix_weight(&self, mut pos: usize) -> usize {
+        let mut sum = 0;
+        let mut current = self.root;
+        while let Some(n) = current {
+            let node = self.node(n);
+            let left = self.size(node.left);
+            if pos <= left {
+                current = node.left;
+            } else {
+                sum += self.total(node.left) + node.weight;
+                pos -= left + 1;
+                current = node.right;
+            }
+        }
+        sum
+    }
+
+    /// Number of leading entries for which `before` holds
+    ///
+    /// `before` must be true for a prefix of the sequence and false after.
+    pub(super) fn partition_point(&self, mut before: impl FnMut(&P) -> bool) -> usize {
+        let mut pos = 0;
+        let mut current = self.root;
+        while let Some(n) = current {
+            let node = self.node(n);
+            if before(&node.value) {
+                pos += self.size(node.left) + 1;
+                current = node.right;
+            } else {
+                current = node.left;
+            }
+        }
+        pos
+    }
+
+    /// Insert an entry so that it ends up at `pos`
+    pub(super) fn insert_at(&mut self, pos: usize, value: P, weight: usize) -> NodeId {
+        let id = self.alloc(value, weight);
+        let (left, right) = self.split(self.root, pos);
+        let merged = self.merge(left, Some(id));
+        let root = self.merge(merged, right);
+        self.set_root(root);
+        id
+    }
+
+    /// Remove an entry, returning its value
+    pub(super) fn remove(&mut self, id: NodeId) -> P {
+        let pos = self.rank(id);
+        let (left, rest) = self.split(self.root, pos);
+        let (_, right) = self.split(rest, 1);
+        let root = self.merge(left, right);
+        self.set_root(root);
+        self.free.push(id);
+        self.nodes[id].take().expect("live node").value
+    }
+
+    /// Values in order
+    pub(super) fn iter(&self) -> impl Iterator<Item = &P> {
+        let mut stack = Vec::new();
+        let mut current = self.root;
+        std::iter::from_fn(move || {
+            while let Some(n) = current {
+                stack.push(n);
+                current = self.node(n).left;
+            }
+            let n = stack.pop()?;
+            current = self.node(n).right;
+            Some(&self.node(n).value)
+        })
+    }
+
+    fn node(&self, id: NodeId) -> &Node<P> {
+        self.nodes[id].as_ref().expect("live node")
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> &mut Node<P> {
+        self.nodes[id].as_mut().expect("live node")
+    }
+
+    fn size(&self, id: Option<NodeId>) -> usize {
+        id.map_or(0, |n| self.node(n).size)
+    }
+
+    fn total(&self, id: Option<NodeId>) -> usize {
+        id.map_or(0, |n| self.node(n).total)
+    }
+
+    fn alloc(&mut self, value: P, weight: usize) -> NodeId {
+        // xorshift64: priorities only need to be well spread
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        let node = Node {
+            value,
+            weight,
+            priority: self.seed,
+            left: None,
+            right: None,
+            parent: None,
+            size: 1,
+            total: weight,
+        };
+        match self.free.pop() {
+            Some(id) => {
+                self.nodes[id] = Some(node);
+                id
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn set_root(&mut self, root: Option<NodeId>) {
+        if let Some(n) = root {
+            self.node_mut(n).parent = None;
+        }
+        self.root = root;
+    }
+
+    /// Recompute a node's aggregates and re-parent its children
+    fn update(&mut self, id: NodeId) {
+        let (left, right) = {
+            let node = self.node(id);
+            (node.left, node.right)
+        };
+        let size = self.size(left) + self.size(right) + 1;
+        let total = self.total(left) + self.total(right) + self.node(id).weight;
+        for child in [left, right].into_iter().flatten() {
+            self.node_mut(child).parent = Some(id);
+        }
+        let node = self.node_mut(id);
+        node.size = size;
+        node.total = total;
+    }
+
+    /// Split into the first `pos` entries and the rest
+    fn split(&mut self, id: Option<NodeId>, pos: usize) -> (Option<NodeId>, Option<NodeId>) {
+        let Some(n) = id else {
+            return (None, None);
+        };
+        let left = self.node(n).left;
+        let left_size = self.size(left);
+        if pos <= left_size {
+            let (a, b) = self.split(left, pos);
+            self.node_mut(n).left = b;
+            self.update(n);
+            (a, Some(n))
+        } else {
+            let right = self.node(n).right;
+            let (a, b) = self.split(right, pos - left_size - 1);
+            self.node_mut(n).right = a;
+            self.update(n);
+            (Some(n), b)
+        }
+    }
+
+    /// Concatenate two trees
+    fn merge(&mut self, a: Option<NodeId>, b: Option<NodeId>) -> Option<NodeId> {
+        match (a, b) {
+            (None, other) | (other, None) => other,
+            (Some(x), Some(y)) => {
+                if self.node(x).priority > self.node(y).priority {
+                    let right = self.node(x).right;
+                    let merged = self.merge(right, Some(y));
+                    self.node_mut(x).right = merged;
+                    self.update(x);
+                    Some(x)
+                } else {
+                    let left = self.node(y).left;
+                    let merged = self.merge(Some(x), left);
+                    self.node_mut(y).left = merged;
+                    self.update(y);
+                    Some(y)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_remove_and_rank_match_vec() {
+        let mut tree = OrderTree::new();
+        let mut model: Vec<(NodeId, usize)> = Vec::new();
+        let mut seed = 11u64;
+        let mut next = |bound: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            (seed >> 33) as usize % bound.max(1)
+        };
+
+        for round in 0..2_000 {
+            if round % 3 == 2 && !model.is_empty() {
+                let (id, value) = model.remove(next(model.len()));
+                assert_eq!(tree.remove(id), value);
+            } else {
+                let pos = next(model.len() + 1);
+                model.insert(pos, (tree.insert_at(pos, round, round % 2), round));
+            }
+        }
+
+        assert_eq!(tree.len(), model.len());
+        let values: Vec<usize> = tree.iter().copied().collect();
+        assert_eq!(values, model.iter().map(|(_, v)| *v).collect::<Vec<_>>());
+        for (pos, (id, value)) in model.iter().enumerate().step_by(7) {
+            assert_eq!(tree.rank(*id), pos);
+            assert_eq!(tree.at(pos), *id);
+            let odd = model[..pos].iter().filter(|(_, v)| v % 2 == 1).count();
+            assert_eq!(tree.prefix_weight(pos), odd);
+            assert_eq!(tree.weight(*id), value % 2);
+        }
+    }
+
+    #[test]
+    fn test_partition_point_and_set_weight() {
+        let mut tree = OrderTree::from_entries([1, 3, 5, 7].map(|v| (v, 1)));
+        assert_eq!(tree.partition_point(|v| *v < 4), 2);
+        assert_eq!(tree.partition_point(|v| *v < 0), 0);
+
+        let id = tree.at(1);
+        tree.set_weight(id, 0);
+        assert_eq!(tree.prefix_weight(4), 3);
+        *tree.get_mut(id) = 4;
+        assert_eq!(*tree.get(tree.at(1)), 4);
+        assert!(!tree.is_empty());
+    }
+}
@@ -168,6 +168,16 @@ impl<T: 'static> Signal<T> {
         f(&*guard)
     }
 
+    /// Access the value without registering a dependency
+    ///
+    /// Used by framework internals (e.g. history recording) that observe
+    /// signals from subscription callbacks.
+    #[inline]
+    pub(crate) fn with_untracked<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        let guard = read_or_recover(&self.value);
+        f(&*guard)
+    }
+
     /// Modify the value with a closure (zero-copy), WITHOUT notifying subscribers
     ///
     /// Like `with` but for mutations. Does NOT notify subscribers.
@@ -335,6 +345,16 @@ impl<T: std::fmt::Debug + 'static> std::fmt::Debug for Signal<T> {
     }
 }
 
+/// Formats the current value, registering a dependency like [`Signal::with`]
+///
+/// This lets signals be interpolated directly in `format!` strings and
+/// `view!` templates.
+impl<T: std::fmt::Display + 'static> std::fmt::Display for Signal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.with(|value| value.fmt(f))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -467,4 +487,12 @@ mod tests {
         signal.with_mut(|v| v.push(4));
         assert_eq!(*signal.read(), vec![1, 2, 3, 4]);
     }
+
+    #[test]
+    fn test_signal_display() {
+        let signal = Signal::new(42);
+        assert_eq!(format!("{signal}"), "42");
+        signal.set(7);
+        assert_eq!(signal.to_string(), "7");
+    }
 }
@@ -7,6 +7,8 @@
 use super::signal::{Signal, Subscription};
 use super::tracker::notify_dependents;
 use super::SignalId;
+use crate::utils::lock::lock_or_recover;
+use std::any::Any;
 use std::sync::Arc;
 use std::sync::Mutex as StdMutex;
 
@@ -76,6 +78,43 @@ impl<T> VecDiff<T> {
             Self::Replace { .. } => None,
         }
     }
+
+    /// Apply this change to a plain vector
+    ///
+    /// Out-of-range indices are ignored, so a mirror that has drifted from
+    /// its source doesn't panic.
+    pub fn apply_to(self, target: &mut Vec<T>) {
+        match self {
+            Self::Insert { index, value } => {
+                if index <= target.len() {
+                    target.insert(index, value);
+                }
+            }
+            Self::Update {
+                index, new_value, ..
+            } => {
+                if let Some(slot) = target.get_mut(index) {
+                    *slot = new_value;
+                }
+            }
+            Self::Remove { index, .. } => {
+                if index < target.len() {
+                    target.remove(index);
+                }
+            }
+            Self::Move {
+                old_index,
+                new_index,
+                ..
+            } => {
+                if old_index < target.len() && new_index < target.len() {
+                    let value = target.remove(old_index);
+                    target.insert(new_index, value);
+                }
+            }
+            Self::Replace { new_values, .. } => *target = new_values,
+        }
+    }
 }
 
 /// A reactive vector that emits granular changes
@@ -110,6 +149,8 @@ pub struct SignalVec<T> {
     id: SignalId,
     /// Diff subscribers (callbacks for granular changes)
     diff_subscribers: Arc<StdMutex<Vec<Arc<dyn Fn(VecDiff<T>) + Send + Sync>>>>,
+    /// Upstream state kept alive by derived vectors (see `filter`, `map`, ...)
+    upstream: Option<Arc<dyn Any + Send + Sync>>,
 }
 
 impl<T: Send + Sync + Clone + 'static> SignalVec<T> {
@@ -121,6 +162,26 @@ impl<T: Send + Sync + Clone + 'static> SignalVec<T> {
             inner,
             id,
             diff_subscribers: Arc::new(StdMutex::new(Vec::new())),
+            upstream: None,
+        }
+    }
+
+    /// Keep `upstream` alive for as long as any clone of this vector exists
+    pub(super) fn with_upstream(mut self, upstream: Arc<dyn Any + Send + Sync>) -> Self {
+        self.upstream = Some(upstream);
+        self
+    }
+
+    /// Clone without the upstream handle
+    ///
+    /// Used inside upstream subscription callbacks, where holding the
+    /// upstream handle would create a reference cycle.
+    pub(super) fn detached(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            id: self.id,
+            diff_subscribers: Arc::clone(&self.diff_subscribers),
+            upstream: None,
         }
     }
 
@@ -213,6 +274,30 @@ impl<T: Send + Sync + Clone + 'static> SignalVec<T> {
         }
     }
 
+    /// Move element from `old_index` to `new_index`
+    ///
+    /// Emits a single [`VecDiff::Move`] so keyed views can reorder the
+    /// existing item instead of removing and re-inserting it.
+    pub fn move_item(&self, old_index: usize, new_index: usize) {
+        let len = self.len();
+        if old_index >= len || new_index >= len || old_index == new_index {
+            return;
+        }
+        let mut moved = None;
+        self.inner.update(|v| {
+            let value = v.remove(old_index);
+            v.insert(new_index, value.clone());
+            moved = Some(value);
+        });
+        if let Some(value) = moved {
+            self.notify_diff(VecDiff::Move {
+                old_index,
+                new_index,
+                value,
+            });
+        }
+    }
+
     /// Pop element from the end
     pub fn pop(&self) -> Option<T> {
         let index = self.len().checked_sub(1)?;
@@ -263,6 +348,23 @@ impl<T: Send + Sync + Clone + 'static> SignalVec<T> {
         }
     }
 
+    /// Collect changes into a queue for later application
+    ///
+    /// Useful for feeding widgets that own their data (e.g. `VirtualList`,
+    /// `Table`, `DataGrid`): drain the queue in the event handler and apply
+    /// each diff with the widget's `apply_diff()`.
+    pub fn diff_queue(&self) -> DiffQueue<T> {
+        let pending = Arc::new(StdMutex::new(Vec::new()));
+        let sink = Arc::clone(&pending);
+        let subscription = self.subscribe_diff(move |diff| {
+            lock_or_recover(&sink).push(diff);
+        });
+        DiffQueue {
+            pending,
+            _subscription: subscription,
+        }
+    }
+
     /// Notify diff subscribers
     fn notify_diff(&self, diff: VecDiff<T>) {
         // Emit to all diff subscribers
@@ -292,6 +394,7 @@ impl<T: Send + Sync + Clone + 'static> Clone for SignalVec<T> {
             inner: self.inner.clone(),
             id: self.id,
             diff_subscribers: Arc::clone(&self.diff_subscribers),
+            upstream: self.upstream.clone(),
         }
     }
 }
@@ -312,6 +415,32 @@ impl<T> Drop for VecSubscription<T> {
     }
 }
 
+/// Queue of changes received from a [`SignalVec`]
+///
+/// Created by [`SignalVec::diff_queue()`]. Changes stop being collected
+/// when the queue is dropped.
+pub struct DiffQueue<T> {
+    pending: Arc<StdMutex<Vec<VecDiff<T>>>>,
+    _subscription: VecSubscription<T>,
+}
+
+impl<T> DiffQueue<T> {
+    /// Take all changes received since the last call, in order
+    pub fn drain(&self) -> Vec<VecDiff<T>> {
+        std::mem::take(&mut *lock_or_recover(&self.pending))
+    }
+
+    /// Check if no changes are waiting
+    pub fn is_empty(&self) -> bool {
+        lock_or_recover(&self.pending).is_empty()
+    }
+
+    /// Get the number of waiting changes
+    pub fn len(&self) -> usize {
+        lock_or_recover(&self.pending).len()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -439,4 +568,69 @@ mod tests {
         vec1.push(4);
         assert_eq!(vec2.get(), vec![1, 2, 3, 4]);
     }
+
+    #[test]
+    fn test_signal_vec_move_item() {
+        use std::sync::Mutex;
+
+        let vec = SignalVec::new(vec![1, 2, 3, 4]);
+        let diffs = Arc::new(Mutex::new(Vec::new()));
+        let diffs_clone = Arc::clone(&diffs);
+        let _sub = vec.subscribe_diff(move |diff| diffs_clone.lock().unwrap().push(diff));
+
+        vec.move_item(0, 2);
+        assert_eq!(vec.get(), vec![2, 3, 1, 4]);
+
+        // Out of range and no-op moves are ignored
+        vec.move_item(1, 1);
+        vec.move_item(0, 10);
+
+        let diffs = diffs.lock().unwrap();
+        assert_eq!(
+            *diffs,
+            vec![VecDiff::Move {
+                old_index: 0,
+                new_index: 2,
+                value: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn test_vec_diff_apply_to() {
+        let mut mirror = vec![1, 2, 3];
+        VecDiff::Insert { index: 0, value: 0 }.apply_to(&mut mirror);
+        VecDiff::Update {
+            index: 1,
+            old_value: 1,
+            new_value: 10,
+        }
+        .apply_to(&mut mirror);
+        VecDiff::Move {
+            old_index: 0,
+            new_index: 3,
+            value: 0,
+        }
+        .apply_to(&mut mirror);
+        VecDiff::Remove { index: 9, value: 0 }.apply_to(&mut mirror);
+        assert_eq!(mirror, vec![10, 2, 3, 0]);
+    }
+
+    #[test]
+    fn test_signal_vec_diff_queue() {
+        let vec = SignalVec::new(vec![1, 2]);
+        let queue = vec.diff_queue();
+        assert!(queue.is_empty());
+
+        vec.push(3);
+        vec.remove(0);
+        assert_eq!(queue.len(), 2);
+
+        let mut mirror = vec![1, 2];
+        for diff in queue.drain() {
+            diff.apply_to(&mut mirror);
+        }
+        assert_eq!(mirror, vec.get());
+        assert!(queue.is_empty());
+    }
 }
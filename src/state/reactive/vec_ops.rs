@@ -0,0 +1,795 @@
+//! Derived-data operators for `SignalVec`
+//!
+//! Each operator returns a new, read-only [`SignalVec`] that is kept up to
+//! date from the source's [`VecDiff`]s: a single insert, update, remove or
+//! move in the source costs at most one predicate/key evaluation plus
+//! O(log n) bookkeeping, and emits the minimal diff downstream. Only
+//! [`VecDiff::Replace`] recomputes the whole output.
+//!
+//! Operators chain, and a derived vector keeps its whole upstream chain
+//! alive for as long as any clone of it exists.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use revue::reactive::{signal, signal_vec};
+//!
+//! #[derive(Clone)]
+//! struct Trade { symbol: String, price: f64, venue: String }
+//!
+//! let trades = signal_vec(Vec::<Trade>::new());
+//! let query = signal(String::new());
+//!
+//! // Re-filters when either a trade or the search query changes
+//! let visible = trades
+//!     .filter_with(&query, |t, q| t.symbol.contains(q.as_str()))
+//!     .sort_by_key(|t| t.symbol.clone());
+//!
+//! let by_venue = trades.group_by(|t| t.venue.clone());
+//!
+//! // Feed a widget incrementally
+//! let queue = visible.diff_queue();
+//! // ... in the event handler:
+//! for diff in queue.drain() {
+//!     list.apply_diff(diff);
+//! }
+//! ```
+
+use super::order_tree::{NodeId, OrderTree};
+use super::signal::{Signal, Subscription};
+use super::signal_vec::{SignalVec, VecDiff, VecSubscription};
+use crate::utils::lock::lock_or_recover;
+use std::cmp::Ordering;
+use std::sync::{Arc, Mutex};
+
+/// A group of items sharing a key, produced by [`SignalVec::group_by()`]
+pub struct VecGroup<K, T> {
+    /// Group key
+    pub key: K,
+    /// Items in the group, in source order
+    ///
+    /// Kept up to date from the source; subscribe to it for per-item diffs.
+    pub items: SignalVec<T>,
+}
+
+impl<K: Clone, T: Send + Sync + Clone + 'static> Clone for VecGroup<K, T> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            items: self.items.clone(),
+        }
+    }
+}
+
+impl<K: std::fmt::Debug, T: std::fmt::Debug + Send + Sync + Clone + 'static> std::fmt::Debug
+    for VecGroup<K, T>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VecGroup")
+            .field("key", &self.key)
+            .field("items", &*self.items.read())
+            .finish()
+    }
+}
+
+impl<K: PartialEq, T: PartialEq + Send + Sync + Clone + 'static> PartialEq for VecGroup<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key && *self.items.read() == *other.items.read()
+    }
+}
+
+/// Build a derived vector maintained by `apply` from the source's diffs
+fn derive<T, U, S>(
+    source: &SignalVec<T>,
+    initial: Vec<U>,
+    state: Arc<Mutex<S>>,
+    apply: impl Fn(&mut S, &SignalVec<U>, VecDiff<T>) + Send + Sync + 'static,
+) -> (SignalVec<U>, VecSubscription<T>)
+where
+    T: Send + Sync + Clone + 'static,
+    U: Send + Sync + Clone + 'static,
+    S: Send + 'static,
+{
+    let output = SignalVec::new(initial);
+    let sink = output.detached();
+    let subscription = source.subscribe_diff(move |diff| {
+        apply(&mut lock_or_recover(&state), &sink, diff);
+    });
+    (output, subscription)
+}
+
+impl<T: Send + Sync + Clone + 'static> SignalVec<T> {
+    /// Derive a vector by transforming each item
+    pub fn map<U, F>(&self, f: F) -> SignalVec<U>
+    where
+        U: Send + Sync + Clone + 'static,
+        F: Fn(&T) -> U + Send + Sync + 'static,
+    {
+        let initial = self.read().iter().map(&f).collect();
+        let (output, subscription) = derive(
+            self,
+            initial,
+            Arc::new(Mutex::new(())),
+            move |_, out, diff| match diff {
+                VecDiff::Insert { index, value } => out.insert(index, f(&value)),
+                VecDiff::Update {
+                    index, new_value, ..
+                } => out.update(index, f(&new_value)),
+                VecDiff::Remove { index, .. } => {
+                    out.remove(index);
+                }
+                VecDiff::Move {
+                    old_index,
+                    new_index,
+                    ..
+                } => out.move_item(old_index, new_index),
+                VecDiff::Replace { new_values, .. } => {
+                    out.replace(new_values.iter().map(&f).collect())
+                }
+            },
+        );
+        output.with_upstream(Arc::new((self.clone(), subscription)))
+    }
+
+    /// Derive a vector containing only items matching `predicate`
+    pub fn filter<F>(&self, predicate: F) -> SignalVec<T>
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        let (output, _, subscription) = filter_core(self, Arc::new(predicate));
+        output.with_upstream(Arc::new((self.clone(), subscription)))
+    }
+
+    /// Derive a vector filtered by a predicate that also reads `query`
+    ///
+    /// Source changes are applied incrementally; changing `query`
+    /// re-evaluates every item and replaces the output.
+    pub fn filter_with<Q, F>(&self, query: &Signal<Q>, predicate: F) -> SignalVec<T>
+    where
+        Q: Send + Sync + 'static,
+        F: Fn(&T, &Q) -> bool + Send + Sync + 'static,
+    {
+        let predicate = Arc::new(predicate);
+        let query_reader = query.clone();
+        let item_predicate = {
+            let predicate = Arc::clone(&predicate);
+            Arc::new(move |item: &T| query_reader.with_untracked(|q| predicate(item, q)))
+        };
+        let (output, included, subscription) = filter_core(self, item_predicate);
+
+        let source = self.detached();
+        let sink = output.detached();
+        let query_reader = query.clone();
+        let query_subscription: Subscription = query.subscribe(move || {
+            let mut included = lock_or_recover(&included);
+            let values = query_reader.with_untracked(|q| {
+                let items = source.read();
+                let keep: Vec<bool> = items.iter().map(|item| predicate(item, q)).collect();
+                *included = inclusion_tree(&keep);
+                items
+                    .iter()
+                    .zip(keep)
+                    .filter(|(_, keep)| *keep)
+                    .map(|(item, _)| item.clone())
+                    .collect()
+            });
+            sink.replace(values);
+        });
+
+        output.with_upstream(Arc::new((self.clone(), subscription, query_subscription)))
+    }
+
+    /// Derive a vector sorted by `key` (stable: equal keys keep source order)
+    pub fn sort_by_key<K, F>(&self, key: F) -> SignalVec<T>
+    where
+        K: Ord + Send + 'static,
+        F: Fn(&T) -> K + Send + Sync + 'static,
+    {
+        let (state, initial) = {
+            let items = self.read();
+            let mut state = SortState::new();
+            let order = state.rebuild(items.iter().map(&key).collect());
+            (state, order.iter().map(|&i| items[i].clone()).collect())
+        };
+
+        let (output, subscription) = derive(
+            self,
+            initial,
+            Arc::new(Mutex::new(state)),
+            move |state, out, diff| state.apply(&key, out, diff),
+        );
+        output.with_upstream(Arc::new((self.clone(), subscription)))
+    }
+
+    /// Derive a vector of groups of items sharing a key, ordered by key
+    ///
+    /// Each group's items are a nested [`SignalVec`]: a change to one item
+    /// emits a single diff on its group's items, without copying the group.
+    /// The outer vector only changes when a group appears or empties.
+    pub fn group_by<K, F>(&self, key: F) -> SignalVec<VecGroup<K, T>>
+    where
+        K: Ord + Clone + Send + Sync + 'static,
+        F: Fn(&T) -> K + Send + Sync + 'static,
+    {
+        let state = GroupState::new(&key, &self.read());
+        let initial = state.snapshot();
+
+        let (output, subscription) = derive(
+            self,
+            initial,
+            Arc::new(Mutex::new(state)),
+            move |state, out, diff| state.apply(&key, out, diff),
+        );
+        output.with_upstream(Arc::new((self.clone(), subscription)))
+    }
+}
+
+// =============================================================================
+// Filter
+// =============================================================================
+
+type Predicate<T> = Arc<dyn Fn(&T) -> bool + Send + Sync>;
+
+/// Source entries weighted 1 when included in the output, so an output
+/// position is a prefix sum of weights
+fn inclusion_tree(keep: &[bool]) -> OrderTree<()> {
+    OrderTree::from_entries(keep.iter().map(|&keep| ((), usize::from(keep))))
+}
+
+/// Shared filter implementation; returns the output, the per-source-index
+/// inclusion tree and the source subscription
+fn filter_core<T: Send + Sync + Clone + 'static>(
+    source: &SignalVec<T>,
+    predicate: Predicate<T>,
+) -> (SignalVec<T>, Arc<Mutex<OrderTree<()>>>, VecSubscription<T>) {
+    let (included, initial) = {
+        let items = source.read();
+        let keep: Vec<bool> = items.iter().map(|item| predicate(item)).collect();
+        let initial = items
+            .iter()
+            .zip(&keep)
+            .filter(|(_, keep)| **keep)
+            .map(|(item, _)| item.clone())
+            .collect();
+        (inclusion_tree(&keep), initial)
+    };
+    let included = Arc::new(Mutex::new(included));
+
+    let (output, subscription) = derive(
+        source,
+        initial,
+        Arc::clone(&included),
+        move |included: &mut OrderTree<()>, out, diff| match diff {
+            VecDiff::Insert { index, value } => {
+                let keep = predicate(&value);
+                included.insert_at(index, (), usize::from(keep));
+                if keep {
+                    out.insert(included.prefix_weight(index), value);
+                }
+            }
+            VecDiff::Update {
+                index, new_value, ..
+            } => {
+                let id = included.at(index);
+                let was = included.weight(id) == 1;
+                let keep = predicate(&new_value);
+                included.set_weight(id, usize::from(keep));
+                let pos = included.prefix_weight(index);
+                match (was, keep) {
+                    (true, true) => out.update(pos, new_value),
+                    (true, false) => {
+                        out.remove(pos);
+                    }
+                    (false, true) => out.insert(pos, new_value),
+                    (false, false) => {}
+                }
+            }
+            VecDiff::Remove { index, .. } => {
+                let pos = included.prefix_weight(index);
+                let id = included.at(index);
+                let was = included.weight(id) == 1;
+                included.remove(id);
+                if was {
+                    out.remove(pos);
+                }
+            }
+            VecDiff::Move {
+                old_index,
+                new_index,
+                ..
+            } => {
+                let old_pos = included.prefix_weight(old_index);
+                let id = included.at(old_index);
+                let weight = included.weight(id);
+                included.remove(id);
+                included.insert_at(new_index, (), weight);
+                if weight == 1 {
+                    out.move_item(old_pos, included.prefix_weight(new_index));
+                }
+            }
+            VecDiff::Replace { new_values, .. } => {
+                let keep: Vec<bool> = new_values.iter().map(|item| predicate(item)).collect();
+                *included = inclusion_tree(&keep);
+                out.replace(
+                    new_values
+                        .into_iter()
+                        .zip(keep)
+                        .filter(|(_, keep)| *keep)
+                        .map(|(item, _)| item)
+                        .collect(),
+                );
+            }
+        },
+    );
+    (output, included, subscription)
+}
+
+// =============================================================================
+// Sort
+// =============================================================================
+
+struct SortState<K> {
+    /// Source order; each entry is the item's node in `sorted`
+    source: OrderTree<NodeId>,
+    /// Output order; each entry is the item's sort key and node in `source`
+    sorted: OrderTree<(K, NodeId)>,
+}
+
+impl<K: Ord> SortState<K> {
+    fn new() -> Self {
+        Self {
+            source: OrderTree::new(),
+            sorted: OrderTree::new(),
+        }
+    }
+
+    /// Source index per output position for `keys` (ties keep source order)
+    fn rebuild(&mut self, keys: Vec<K>) -> Vec<usize> {
+        let mut order: Vec<(K, usize)> = keys.into_iter().zip(0..).collect();
+        order.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+        let indices: Vec<usize> = order.iter().map(|(_, i)| *i).collect();
+
+        self.source = OrderTree::from_entries((0..indices.len()).map(|_| (0, 1)));
+        self.sorted = OrderTree::new();
+        for (key, index) in order {
+            let source_id = self.source.at(index);
+            let id = self
+                .sorted
+                .insert_at(self.sorted.len(), (key, source_id), 1);
+            *self.source.get_mut(source_id) = id;
+        }
+        indices
+    }
+
+    /// Place the item at source index `index` in the output order
+    fn place(&mut self, index: usize, key: K) -> usize {
+        let source_id = self.source.at(index);
+        let source = &self.source;
+        let pos = self.sorted.partition_point(|(k, s)| {
+            k.cmp(&key).then(source.rank(*s).cmp(&index)) == Ordering::Less
+        });
+        let id = self.sorted.insert_at(pos, (key, source_id), 1);
+        *self.source.get_mut(source_id) = id;
+        pos
+    }
+
+    /// Take the item at source index `index` out of the output order,
+    /// returning its output position and key
+    fn unplace(&mut self, index: usize) -> (usize, K) {
+        let id = *self.source.get(self.source.at(index));
+        let pos = self.sorted.rank(id);
+        (pos, self.sorted.remove(id).0)
+    }
+
+    fn apply<T: Send + Sync + Clone + 'static>(
+        &mut self,
+        key: &impl Fn(&T) -> K,
+        out: &SignalVec<T>,
+        diff: VecDiff<T>,
+    ) {
+        match diff {
+            VecDiff::Insert { index, value } => {
+                self.source.insert_at(index, 0, 1);
+                let pos = self.place(index, key(&value));
+                out.insert(pos, value);
+            }
+            VecDiff::Update {
+                index, new_value, ..
+            } => {
+                let (old_pos, _) = self.unplace(index);
+                let new_pos = self.place(index, key(&new_value));
+                out.update(old_pos, new_value);
+                out.move_item(old_pos, new_pos);
+            }
+            VecDiff::Remove { index, .. } => {
+                let (pos, _) = self.unplace(index);
+                self.source.remove(self.source.at(index));
+                out.remove(pos);
+            }
+            VecDiff::Move {
+                old_index,
+                new_index,
+                ..
+            } => {
+                // Only the moved item's tie-break order can change
+                let (old_pos, moved_key) = self.unplace(old_index);
+                self.source.remove(self.source.at(old_index));
+                self.source.insert_at(new_index, 0, 1);
+                let new_pos = self.place(new_index, moved_key);
+                out.move_item(old_pos, new_pos);
+            }
+            VecDiff::Replace { new_values, .. } => {
+                let order = self.rebuild(new_values.iter().map(key).collect());
+                out.replace(order.iter().map(|&i| new_values[i].clone()).collect());
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Group
+// =============================================================================
+
+struct Group<K, T> {
+    key: K,
+    /// The group's entries in source order; each is the item's node in
+    /// `GroupState::source`
+    members: OrderTree<NodeId>,
+    /// Items parallel to `members`, shared with the output
+    items: SignalVec<T>,
+}
+
+struct GroupState<K, T> {
+    /// Source order; each entry is the item's group and node in its
+    /// group's `members`
+    source: OrderTree<(NodeId, NodeId)>,
+    /// Groups ordered by key
+    groups: OrderTree<Group<K, T>>,
+}
+
+impl<K: Ord + Clone + Send + Sync + 'static, T: Send + Sync + Clone + 'static> GroupState<K, T> {
+    fn new(key: &impl Fn(&T) -> K, items: &[T]) -> Self {
+        let mut state = Self {
+            source: OrderTree::new(),
+            groups: OrderTree::new(),
+        };
+        for (index, item) in items.iter().enumerate() {
+            state.source.insert_at(index, (0, 0), 1);
+            state.attach(index, key(item), item.clone(), None);
+        }
+        state
+    }
+
+    fn snapshot(&self) -> Vec<VecGroup<K, T>> {
+        self.groups
+            .iter()
+            .map(|group| VecGroup {
+                key: group.key.clone(),
+                items: group.items.clone(),
+            })
+            .collect()
+    }
+
+    /// Add the item at source index `index` to its group, creating the
+    /// group (and emitting it to `out`) if needed
+    fn attach(&mut self, index: usize, k: K, value: T, out: Option<&SignalVec<VecGroup<K, T>>>) {
+        let source_id = self.source.at(index);
+        let g = self.groups.partition_point(|group| group.key < k);
+        let existing = (g < self.groups.len())
+            .then(|| self.groups.at(g))
+            .filter(|&id| self.groups.get(id).key == k);
+
+        let group_id = match existing {
+            Some(group_id) => {
+                let source = &self.source;
+                let group = self.groups.get_mut(group_id);
+                let pos = group.members.partition_point(|&m| source.rank(m) < index);
+                let member = group.members.insert_at(pos, source_id, 1);
+                group.items.insert(pos, value);
+                *self.source.get_mut(source_id) = (group_id, member);
+                return;
+            }
+            None => {
+                let group = Group {
+                    key: k.clone(),
+                    members: OrderTree::new(),
+                    items: SignalVec::new(vec![value]),
+                };
+                self.groups.insert_at(g, group, 1)
+            }
+        };
+        let group = self.groups.get_mut(group_id);
+        let member = group.members.insert_at(0, source_id, 1);
+        *self.source.get_mut(source_id) = (group_id, member);
+        if let Some(out) = out {
+            out.insert(
+                g,
+                VecGroup {
+                    key: k,
+                    items: group.items.clone(),
+                },
+            );
+        }
+    }
+
+    /// Remove the item at source index `index` from its group, dropping
+    /// the group from `out` once it is empty
+    fn detach(&mut self, index: usize, out: &SignalVec<VecGroup<K, T>>) {
+        let (group_id, member) = *self.source.get(self.source.at(index));
+        let group = self.groups.get_mut(group_id);
+        let pos = group.members.rank(member);
+        group.members.remove(member);
+        group.items.remove(pos);
+        if group.members.is_empty() {
+            let g = self.groups.rank(group_id);
+            self.groups.remove(group_id);
+            out.remove(g);
+        }
+    }
+
+    fn apply(&mut self, key: &impl Fn(&T) -> K, out: &SignalVec<VecGroup<K, T>>, diff: VecDiff<T>) {
+        match diff {
+            VecDiff::Insert { index, value } => {
+                self.source.insert_at(index, (0, 0), 1);
+                self.attach(index, key(&value), value, Some(out));
+            }
+            VecDiff::Update {
+                index, new_value, ..
+            } => {
+                let new_key = key(&new_value);
+                let (group_id, member) = *self.source.get(self.source.at(index));
+                let group = self.groups.get(group_id);
+                if group.key == new_key {
+                    group.items.update(group.members.rank(member), new_value);
+                } else {
+                    self.detach(index, out);
+                    self.attach(index, new_key, new_value, Some(out));
+                }
+            }
+            VecDiff::Remove { index, .. } => {
+                self.detach(index, out);
+                self.source.remove(self.source.at(index));
+            }
+            VecDiff::Move {
+                old_index,
+                new_index,
+                ..
+            } => {
+                // Only the moved item's position within its group can change
+                let source_id = self.source.at(old_index);
+                let (group_id, member) = *self.source.get(source_id);
+                let group = self.groups.get_mut(group_id);
+                let old_pos = group.members.rank(member);
+                group.members.remove(member);
+                self.source.remove(source_id);
+                let source_id = self.source.insert_at(new_index, (group_id, 0), 1);
+
+                let source = &self.source;
+                let group = self.groups.get_mut(group_id);
+                let new_pos = group
+                    .members
+                    .partition_point(|&m| source.rank(m) < new_index);
+                let member = group.members.insert_at(new_pos, source_id, 1);
+                if old_pos != new_pos {
+                    group.items.move_item(old_pos, new_pos);
+                }
+                self.source.get_mut(source_id).1 = member;
+            }
+            VecDiff::Replace { new_values, .. } => {
+                *self = Self::new(key, &new_values);
+                out.replace(self.snapshot());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_tracks_source() {
+        let source = SignalVec::new(vec![1, 2, 3]);
+        let doubled = source.map(|x| x * 2);
+        assert_eq!(doubled.get(), vec![2, 4, 6]);
+
+        source.insert(0, 10);
+        source.update(2, 20);
+        source.remove(3);
+        source.move_item(0, 2);
+        assert_eq!(doubled.get(), vec![2, 40, 20]);
+    }
+
+    #[test]
+    fn test_filter_incremental() {
+        let source = SignalVec::new(vec![1, 2, 3, 4]);
+        let evens = source.filter(|x| x % 2 == 0);
+        let queue = evens.diff_queue();
+        assert_eq!(evens.get(), vec![2, 4]);
+
+        source.push(6);
+        source.push(7);
+        source.update(0, 8); // 1 -> 8 enters at front
+        source.update(1, 3); // 2 -> 3 leaves
+        assert_eq!(evens.get(), vec![8, 4, 6]);
+
+        let diffs = queue.drain();
+        assert_eq!(diffs.len(), 3, "odd push emits nothing");
+        assert_eq!(diffs[1], VecDiff::Insert { index: 0, value: 8 });
+    }
+
+    #[test]
+    fn test_filter_remove_and_move() {
+        let source = SignalVec::new(vec![2, 1, 4, 6]);
+        let evens = source.filter(|x| x % 2 == 0);
+
+        source.move_item(3, 0);
+        assert_eq!(evens.get(), vec![6, 2, 4]);
+        source.remove(2);
+        assert_eq!(evens.get(), vec![6, 2, 4]);
+        source.remove(0);
+        assert_eq!(evens.get(), vec![2, 4]);
+    }
+
+    #[test]
+    fn test_filter_with_query() {
+        let source = SignalVec::new(vec!["apple", "banana", "avocado"]);
+        let query = Signal::new("a".to_string());
+        let matches = source.filter_with(&query, |item, q| item.starts_with(q.as_str()));
+        assert_eq!(matches.get(), vec!["apple", "avocado"]);
+
+        query.set("b".to_string());
+        assert_eq!(matches.get(), vec!["banana"]);
+
+        source.push("blueberry");
+        assert_eq!(matches.get(), vec!["banana", "blueberry"]);
+    }
+
+    #[test]
+    fn test_sort_by_key_incremental() {
+        let source = SignalVec::new(vec![3, 1, 2]);
+        let sorted = source.sort_by_key(|x| *x);
+        assert_eq!(sorted.get(), vec![1, 2, 3]);
+
+        source.push(0);
+        source.update(0, 5); // 3 -> 5
+        source.remove(1); // remove 1
+        assert_eq!(sorted.get(), vec![0, 2, 5]);
+        assert_eq!(source.get(), vec![5, 2, 0]);
+    }
+
+    #[test]
+    fn test_sort_by_key_stable_ties_follow_moves() {
+        let source = SignalVec::new(vec![(1, 'a'), (0, 'x'), (1, 'b')]);
+        let sorted = source.sort_by_key(|(k, _)| *k);
+        assert_eq!(sorted.get(), vec![(0, 'x'), (1, 'a'), (1, 'b')]);
+
+        source.move_item(2, 0);
+        assert_eq!(sorted.get(), vec![(0, 'x'), (1, 'b'), (1, 'a')]);
+    }
+
+    #[test]
+    fn test_group_by_incremental() {
+        let source = SignalVec::new(vec![("b", 1), ("a", 2), ("b", 3)]);
+        let groups = source.group_by(|(k, _)| *k);
+        let queue = groups.diff_queue();
+
+        let keys: Vec<_> = groups.get().iter().map(|g| g.key).collect();
+        assert_eq!(keys, vec!["a", "b"]);
+        assert_eq!(groups.get()[1].items.get(), vec![("b", 1), ("b", 3)]);
+
+        source.push(("c", 4));
+        source.update(1, ("b", 5)); // "a" group empties
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            groups.get()[0].items.get(),
+            vec![("b", 1), ("b", 5), ("b", 3)]
+        );
+
+        source.move_item(0, 2);
+        assert_eq!(
+            groups.get()[0].items.get(),
+            vec![("b", 5), ("b", 3), ("b", 1)]
+        );
+
+        source.remove(0);
+        assert_eq!(groups.get()[0].items.get(), vec![("b", 3), ("b", 1)]);
+        assert!(!queue.is_empty());
+    }
+
+    #[test]
+    fn test_group_by_emits_per_group_diffs() {
+        let source = SignalVec::new(vec![("a", 1), ("b", 2), ("a", 3)]);
+        let groups = source.group_by(|(k, _)| *k);
+        let outer = groups.diff_queue();
+        let group_a = groups.get()[0].items.diff_queue();
+
+        source.update(2, ("a", 30));
+        source.insert(1, ("a", 10));
+        assert!(outer.is_empty(), "item changes stay inside their group");
+        assert_eq!(
+            group_a.drain(),
+            vec![
+                VecDiff::Update {
+                    index: 1,
+                    old_value: ("a", 3),
+                    new_value: ("a", 30),
+                },
+                VecDiff::Insert {
+                    index: 1,
+                    value: ("a", 10),
+                },
+            ]
+        );
+
+        source.update(2, ("c", 2)); // "b" empties, "c" appears
+        let diffs = outer.drain();
+        assert!(matches!(diffs[0], VecDiff::Remove { index: 1, .. }));
+        assert!(matches!(diffs[1], VecDiff::Insert { index: 1, .. }));
+    }
+
+    #[test]
+    fn test_operators_match_recomputation_over_many_edits() {
+        let source = SignalVec::new((0..200).map(|i| (i * 7) % 13).collect::<Vec<u32>>());
+        let evens = source.filter(|x| x % 2 == 0);
+        let sorted = source.sort_by_key(|x| *x);
+        let groups = source.group_by(|x| x % 3);
+        let mut seed = 5u64;
+        let mut next = |bound: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            (seed >> 33) as usize % bound.max(1)
+        };
+
+        for round in 0..600u32 {
+            let len = source.len();
+            match round % 4 {
+                0 => source.insert(next(len + 1), round % 17),
+                1 if len > 0 => source.update(next(len), round % 11),
+                2 if len > 0 => {
+                    source.remove(next(len));
+                }
+                _ if len > 0 => source.move_item(next(len), next(len)),
+                _ => {}
+            }
+        }
+
+        let items = source.get();
+        let expected: Vec<u32> = items.iter().copied().filter(|x| x % 2 == 0).collect();
+        assert_eq!(evens.get(), expected);
+        let mut expected = items.clone();
+        expected.sort();
+        assert_eq!(sorted.get(), expected);
+        for group in groups.get() {
+            let expected: Vec<u32> = items
+                .iter()
+                .copied()
+                .filter(|x| x % 3 == group.key)
+                .collect();
+            assert_eq!(group.items.get(), expected);
+        }
+    }
+
+    #[test]
+    fn test_chained_operators_keep_upstream_alive() {
+        let source = SignalVec::new(vec![5, 3, 8, 1]);
+        let view = source
+            .filter(|x| *x > 2)
+            .map(|x| x * 10)
+            .sort_by_key(|x| *x);
+        assert_eq!(view.get(), vec![30, 50, 80]);
+
+        source.push(4);
+        assert_eq!(view.get(), vec![30, 40, 50, 80]);
+    }
+
+    #[test]
+    fn test_derived_vec_detaches_when_dropped() {
+        let source = SignalVec::new(vec![1, 2]);
+        let mapped = source.map(|x| x + 1);
+        drop(mapped);
+
+        // No subscribers remain to receive the diff
+        source.push(3);
+        assert_eq!(source.get(), vec![1, 2, 3]);
+    }
+}
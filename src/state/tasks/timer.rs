@@ -2,6 +2,7 @@
 //!
 //! Integrates with App's tick loop for non-blocking timers.
 
+use crate::utils::clock;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
@@ -58,7 +59,7 @@ impl Timer {
         self.timers.insert(
             id,
             TimerEntry {
-                expires_at: Instant::now() + duration,
+                expires_at: clock::now() + duration,
                 repeat: None,
                 active: true,
             },
@@ -70,7 +71,7 @@ impl Timer {
         self.timers.insert(
             id,
             TimerEntry {
-                expires_at: Instant::now() + interval,
+                expires_at: clock::now() + interval,
                 repeat: Some(interval),
                 active: true,
             },
@@ -104,7 +105,7 @@ impl Timer {
     /// Get remaining time for a timer
     pub fn remaining(&self, id: TimerId) -> Option<Duration> {
         self.timers.get(id).and_then(|entry| {
-            let now = Instant::now();
+            let now = clock::now();
             if entry.expires_at > now {
                 Some(entry.expires_at - now)
             } else {
@@ -118,7 +119,7 @@ impl Timer {
     pub fn poll_expired(&mut self) -> Option<TimerId> {
         // First, collect expired timers
         if self.expired.is_empty() {
-            let now = Instant::now();
+            let now = clock::now();
             let mut to_reschedule = Vec::new();
 
             for (&id, entry) in self.timers.iter_mut() {
@@ -316,7 +317,7 @@ mod tests {
     #[test]
     fn test_timer_entry_debug() {
         let entry = TimerEntry {
-            expires_at: Instant::now() + Duration::from_secs(10),
+            expires_at: clock::now() + Duration::from_secs(10),
             repeat: None,
             active: true,
         };
@@ -327,7 +328,7 @@ mod tests {
     #[test]
     fn test_timer_entry_clone() {
         let entry = TimerEntry {
-            expires_at: Instant::now() + Duration::from_secs(10),
+            expires_at: clock::now() + Duration::from_secs(10),
             repeat: Some(Duration::from_secs(5)),
             active: true,
         };
@@ -368,4 +368,40 @@ mod tests {
         assert!(markdown.contains("Diff:"));
         assert!(markdown.contains("diff.png"));
     }
+
+    #[test]
+    fn test_report_markdown_embeds_images() {
+        let mut report = TestReport::new();
+        report.add_failed_with_diff("visual_test", "Mismatch", "path/to/visual_test.png");
+        report.add_failed_with_diff("text_test", "Mismatch", "golden/text_test.diff");
+
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("![visual_test](visual_test.png)"));
+        assert!(!markdown.contains("![text_test]"));
+    }
+
+    #[test]
+    fn test_save_artifacts_keeps_images_already_in_place() {
+        let dir = std::env::temp_dir().join(format!("revue_ci_artifacts_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let image = dir.join("failure.svg");
+        std::fs::write(&image, "<svg/>").unwrap();
+
+        let mut report = TestReport::new();
+        report.add_failed_with_diff("failure", "Mismatch", &image);
+        let ci = CiEnvironment {
+            provider: CiProvider::Local,
+            is_ci: false,
+            branch: None,
+            commit: None,
+            pr_number: None,
+            build_number: None,
+            artifacts_dir: dir.clone(),
+        };
+        report.save_artifacts(&ci).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&image).unwrap(), "<svg/>");
+        assert!(dir.join("visual-test-report.md").exists());
+        let _ = std::fs::remove_dir_all(dir);
+    }
 }
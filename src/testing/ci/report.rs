@@ -215,6 +215,16 @@ impl TestReport {
                 }
                 if let Some(ref diff) = result.diff_path {
                     output.push_str(&format!("**Diff:** `{}`\n\n", diff.display()));
+                    if is_image(diff) {
+                        // Images are copied next to the report by save_artifacts
+                        if let Some(name) = diff.file_name() {
+                            output.push_str(&format!(
+                                "![{}]({})\n\n",
+                                result.name,
+                                name.to_string_lossy()
+                            ));
+                        }
+                    }
                 }
             }
         }
@@ -257,7 +267,10 @@ impl TestReport {
             if let Some(ref diff_path) = result.diff_path {
                 if diff_path.exists() {
                     let dest = ci.artifacts_dir.join(diff_path.file_name().unwrap());
-                    fs::copy(diff_path, dest)?;
+                    // Failure images may already have been written there
+                    if !same_file(diff_path, &dest) {
+                        fs::copy(diff_path, dest)?;
+                    }
                 }
             }
         }
@@ -265,3 +278,21 @@ impl TestReport {
         Ok(())
     }
 }
+
+/// Whether a path points to an image that markdown viewers can display
+fn is_image(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()).is_some_and(|e| {
+        matches!(
+            e.to_ascii_lowercase().as_str(),
+            "png" | "svg" | "gif" | "jpg" | "jpeg"
+        )
+    })
+}
+
+/// Whether two paths refer to the same existing file
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
@@ -0,0 +1,733 @@
+//! Property-based fuzzing for interactive widgets
+//!
+//! [`WidgetFuzzer`] drives any `View + Interactive` with random sequences of
+//! key, mouse, paste, resize and focus events, rendering after every step,
+//! and checks a set of invariants:
+//!
+//! | Invariant | Checked |
+//! |-----------|---------|
+//! | **no panic** | Construction, event handling and rendering never panic |
+//! | **writes inside area** | Rendering leaves every cell outside the area untouched |
+//! | **focus valid** | A focused widget stays focusable |
+//! | **cursor inside bounds** | The [`cursor`](WidgetFuzzer::cursor) probe stays on screen |
+//! | custom | Each [`invariant`](WidgetFuzzer::invariant) holds |
+//!
+//! A failing case is shrunk to a minimal event sequence and reported as a
+//! ready-to-paste [`Pilot`](crate::testing::Pilot) regression test.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use revue::testing::fuzz::WidgetFuzzer;
+//! use revue::widget::TextArea;
+//!
+//! #[test]
+//! fn fuzz_textarea() {
+//!     WidgetFuzzer::new(TextArea::new)
+//!         .constructor("TextArea::new()")
+//!         .cases(200)
+//!         .invariant("content survives render", |view, _, _| view.line_count() > 0)
+//!         .run();
+//! }
+//! ```
+//!
+//! Runs use a random seed unless [`FuzzConfig::seed`] or the
+//! `REVUE_FUZZ_SEED` environment variable fixes one; the seed is part of
+//! every failure report.
+
+mod reproducer;
+mod strategy;
+
+pub use strategy::{FuzzCase, FuzzStep};
+
+use crate::event::{Key, KeyEvent, MouseEvent};
+use crate::layout::Rect;
+use crate::render::{Buffer, Cell};
+use crate::widget::{Interactive, RenderContext, View};
+use proptest::test_runner::{Config, RngAlgorithm, TestCaseError, TestError, TestRng, TestRunner};
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Environment variable that fixes the seed of every fuzz run
+pub const SEED_ENV: &str = "REVUE_FUZZ_SEED";
+
+/// Symbol pre-filled around the render area to detect stray writes
+const SENTINEL: char = '\u{e000}';
+
+/// Invariant names used in failure reports
+const NO_PANIC: &str = "no panic";
+const IN_BOUNDS: &str = "writes inside area";
+const FOCUS_VALID: &str = "focus valid";
+const CURSOR_IN_BOUNDS: &str = "cursor inside bounds";
+
+/// Type alias for the widget factory
+type Factory<V> = Box<dyn Fn() -> V>;
+
+/// Type alias for a named custom invariant
+type Invariant<V> = (String, Box<dyn Fn(&V, &Buffer, Rect) -> bool>);
+
+/// Type alias for the cursor probe
+type CursorProbe<V> = Box<dyn Fn(&V) -> Option<(u16, u16)>>;
+
+/// Type alias for the paste handler
+type PasteHandler<V> = Box<dyn Fn(&mut V, &str)>;
+
+/// Fuzz run configuration
+#[derive(Debug, Clone)]
+pub struct FuzzConfig {
+    /// Number of random cases to run
+    pub cases: u32,
+    /// Maximum number of steps per case
+    pub max_steps: usize,
+    /// Smallest generated screen size
+    pub min_size: (u16, u16),
+    /// Largest generated screen size
+    pub max_size: (u16, u16),
+    /// Fixed seed (random when `None` and `REVUE_FUZZ_SEED` is unset)
+    pub seed: Option<u64>,
+    /// Upper bound on shrinking iterations for a failing case
+    pub max_shrink_iters: u32,
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        Self {
+            cases: 256,
+            max_steps: 32,
+            min_size: (1, 1),
+            max_size: (80, 24),
+            seed: None,
+            max_shrink_iters: 4096,
+        }
+    }
+}
+
+impl FuzzConfig {
+    /// Set the number of cases
+    pub fn cases(mut self, cases: u32) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// Set the maximum number of steps per case
+    pub fn max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Set the range of generated screen sizes
+    pub fn sizes(mut self, min: (u16, u16), max: (u16, u16)) -> Self {
+        self.min_size = min;
+        self.max_size = max;
+        self
+    }
+
+    /// Fix the seed
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set the shrinking iteration limit
+    pub fn max_shrink_iters(mut self, iters: u32) -> Self {
+        self.max_shrink_iters = iters;
+        self
+    }
+
+    /// Seed for this run: the configured one, `REVUE_FUZZ_SEED`, or a fresh one
+    fn resolve_seed(&self) -> u64 {
+        self.seed
+            .or_else(|| std::env::var(SEED_ENV).ok()?.trim().parse().ok())
+            .unwrap_or_else(|| {
+                let nanos = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_nanos() as u64)
+                    .unwrap_or_default();
+                nanos ^ u64::from(std::process::id()).rotate_left(32)
+            })
+    }
+}
+
+/// A shrunk failing case
+#[derive(Debug, Clone)]
+pub struct FuzzFailure {
+    /// Seed of the run that found the failure
+    pub seed: u64,
+    /// Initial screen width
+    pub width: u16,
+    /// Initial screen height
+    pub height: u16,
+    /// Steps actually sent, with mouse coordinates resolved and skipped
+    /// focus changes removed
+    pub steps: Vec<FuzzStep>,
+    /// Index of the step after which the invariant broke (`None` when the
+    /// widget failed before any input)
+    pub step: Option<usize>,
+    /// Name of the violated invariant
+    pub invariant: String,
+    /// What went wrong
+    pub message: String,
+    /// Expression building the widget in the emitted test
+    pub constructor: String,
+    /// Statement handling pastes in the emitted test, if the fuzzer had a
+    /// [`paste handler`](WidgetFuzzer::on_paste)
+    pub paste: Option<String>,
+}
+
+impl fmt::Display for FuzzFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "fuzzing found a violation of \"{}\"", self.invariant)?;
+        writeln!(f, "  {}", self.message)?;
+        match self.step {
+            Some(step) => writeln!(f, "  after step {} of {}", step + 1, self.steps.len())?,
+            None => writeln!(f, "  before any input")?,
+        }
+        writeln!(
+            f,
+            "  seed: {} (rerun with {}={})",
+            self.seed, SEED_ENV, self.seed
+        )?;
+        writeln!(f)?;
+        writeln!(f, "Minimal reproducer:")?;
+        writeln!(f)?;
+        write!(f, "{}", self.to_pilot_test("fuzz_regression"))
+    }
+}
+
+/// A broken invariant found while running one case
+struct Violation {
+    step: Option<usize>,
+    invariant: String,
+    message: String,
+}
+
+/// Outcome of running one case
+struct Run {
+    sent: Vec<FuzzStep>,
+    violation: Option<Violation>,
+}
+
+/// Property-based fuzzer for a `View + Interactive` widget
+pub struct WidgetFuzzer<V: Interactive> {
+    factory: Factory<V>,
+    config: FuzzConfig,
+    invariants: Vec<Invariant<V>>,
+    cursor: Option<CursorProbe<V>>,
+    paste: Option<(String, PasteHandler<V>)>,
+    constructor: String,
+}
+
+impl<V: Interactive> WidgetFuzzer<V> {
+    /// Create a fuzzer that builds a fresh widget for every case
+    pub fn new<F>(factory: F) -> Self
+    where
+        F: Fn() -> V + 'static,
+    {
+        Self {
+            factory: Box::new(factory),
+            config: FuzzConfig::default(),
+            invariants: Vec::new(),
+            cursor: None,
+            paste: None,
+            constructor: "make_widget()".to_string(),
+        }
+    }
+
+    /// Replace the run configuration
+    pub fn config(mut self, config: FuzzConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Set the number of cases
+    pub fn cases(mut self, cases: u32) -> Self {
+        self.config.cases = cases;
+        self
+    }
+
+    /// Set the maximum number of steps per case
+    pub fn max_steps(mut self, max_steps: usize) -> Self {
+        self.config.max_steps = max_steps;
+        self
+    }
+
+    /// Set the range of generated screen sizes
+    pub fn sizes(mut self, min: (u16, u16), max: (u16, u16)) -> Self {
+        self.config = self.config.sizes(min, max);
+        self
+    }
+
+    /// Fix the seed
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
+    /// Set the expression used to build the widget in emitted tests
+    pub fn constructor(mut self, expr: impl Into<String>) -> Self {
+        self.constructor = expr.into();
+        self
+    }
+
+    /// Report the widget's cursor, relative to its area
+    ///
+    /// After every step the cursor must lie inside the area. Return `None`
+    /// when no cursor is shown.
+    pub fn cursor<F>(mut self, probe: F) -> Self
+    where
+        F: Fn(&V) -> Option<(u16, u16)> + 'static,
+    {
+        self.cursor = Some(Box::new(probe));
+        self
+    }
+
+    /// Deliver pastes to `handler` in one piece
+    ///
+    /// The runtime delivers a paste as a single `Event::Paste`; without a
+    /// handler, pastes are sent as key presses. `call` is the same handling
+    /// as a statement over `view` and `text`, used in emitted tests.
+    pub fn on_paste<F>(mut self, call: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(&mut V, &str) + 'static,
+    {
+        self.paste = Some((call.into(), Box::new(handler)));
+        self
+    }
+
+    /// Add a named invariant checked after every render
+    ///
+    /// The closure receives the widget, the rendered buffer (larger than the
+    /// area, which always starts at the origin) and the area.
+    pub fn invariant<F>(mut self, name: impl Into<String>, check: F) -> Self
+    where
+        F: Fn(&V, &Buffer, Rect) -> bool + 'static,
+    {
+        self.invariants.push((name.into(), Box::new(check)));
+        self
+    }
+
+    /// Run the fuzzer and panic with a minimal reproducer on failure
+    pub fn run(&self) {
+        if let Err(failure) = self.check() {
+            panic!("{}", failure);
+        }
+    }
+
+    /// Run the fuzzer and return the shrunk failure, if any
+    pub fn check(&self) -> Result<(), Box<FuzzFailure>> {
+        let seed = self.config.resolve_seed();
+        let config = Config {
+            cases: self.config.cases,
+            max_shrink_iters: self.config.max_shrink_iters,
+            failure_persistence: None,
+            ..Config::default()
+        };
+        let mut runner = TestRunner::new_with_rng(config, seed_rng(seed));
+        let strategy = strategy::case(&self.config);
+
+        // Shrinking only keeps candidates that break the invariant the
+        // first failure broke, so the reproducer shows the original bug
+        let target = RefCell::new(None::<String>);
+        let result = runner.run(&strategy, |case| {
+            let Some(violation) = self.execute(&case).violation else {
+                return Ok(());
+            };
+            let mut target = target.borrow_mut();
+            match target.as_deref() {
+                Some(invariant) if invariant != violation.invariant => Ok(()),
+                _ => {
+                    target.get_or_insert(violation.invariant);
+                    Err(TestCaseError::fail(violation.message))
+                }
+            }
+        });
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(TestError::Fail(_, case)) => {
+                let run = self.execute(&case);
+                let violation = run.violation.unwrap_or_else(|| Violation {
+                    step: None,
+                    invariant: NO_PANIC.to_string(),
+                    message: "case failed but did not fail again when replayed".to_string(),
+                });
+                Err(Box::new(FuzzFailure {
+                    seed,
+                    width: case.width,
+                    height: case.height,
+                    steps: run.sent,
+                    step: violation.step,
+                    invariant: violation.invariant,
+                    message: violation.message,
+                    constructor: self.constructor.clone(),
+                    paste: self.paste.as_ref().map(|(call, _)| call.clone()),
+                }))
+            }
+            Err(TestError::Abort(reason)) => panic!("fuzzing aborted: {}", reason),
+        }
+    }
+
+    /// Run one case, stopping at the first violation
+    fn execute(&self, case: &FuzzCase) -> Run {
+        let mut sent = Vec::new();
+        let fail = |sent, step, (invariant, message): (&str, String)| Run {
+            sent,
+            violation: Some(Violation {
+                step,
+                invariant: invariant.to_string(),
+                message,
+            }),
+        };
+
+        let mut view = match catch(|| (self.factory)()) {
+            Ok(view) => view,
+            Err(message) => return fail(sent, None, (NO_PANIC, message)),
+        };
+        let (mut width, mut height) = (case.width, case.height);
+        let mut focused = false;
+        if let Some(broken) = self.check_view(&view, width, height, focused) {
+            return fail(sent, None, broken);
+        }
+
+        for step in &case.steps {
+            let step = match catch(|| resolve(&view, step, width, height, focused)) {
+                Ok(Some(step)) => step,
+                Ok(None) => continue,
+                Err(message) => {
+                    sent.push(step.clone());
+                    let index = Some(sent.len() - 1);
+                    return fail(sent, index, (NO_PANIC, message));
+                }
+            };
+            sent.push(step.clone());
+            let index = Some(sent.len() - 1);
+
+            let area = Rect::new(0, 0, width, height);
+            let paste = self.paste.as_ref().map(|(_, handler)| handler);
+            if let Err(message) = catch(|| apply(&mut view, &step, area, paste)) {
+                return fail(sent, index, (NO_PANIC, message));
+            }
+            match step {
+                FuzzStep::Resize(w, h) => (width, height) = (w, h),
+                FuzzStep::Focus => focused = true,
+                FuzzStep::Blur => focused = false,
+                _ => {}
+            }
+            if let Some(broken) = self.check_view(&view, width, height, focused) {
+                return fail(sent, index, broken);
+            }
+        }
+
+        Run {
+            sent,
+            violation: None,
+        }
+    }
+
+    /// Render at the given size and check every invariant
+    fn check_view(
+        &self,
+        view: &V,
+        width: u16,
+        height: u16,
+        focused: bool,
+    ) -> Option<(&str, String)> {
+        let (buffer, stray) = match catch(|| render_probe(view, width, height)) {
+            Ok(probe) => probe,
+            Err(message) => return Some((NO_PANIC, format!("render panicked: {}", message))),
+        };
+        if let Some((x, y)) = stray {
+            return Some((IN_BOUNDS, stray_message(&buffer, x, y, width, height)));
+        }
+
+        let checks = catch(|| {
+            if focused && !view.focusable() {
+                let message = "widget stopped being focusable while focused".to_string();
+                return Some((FOCUS_VALID, message));
+            }
+            if let Some(probe) = &self.cursor {
+                if let Some((x, y)) = probe(view) {
+                    if width > 0 && height > 0 && (x >= width || y >= height) {
+                        let message = format!(
+                            "cursor at ({}, {}) outside the {}x{} area",
+                            x, y, width, height
+                        );
+                        return Some((CURSOR_IN_BOUNDS, message));
+                    }
+                }
+            }
+            let area = Rect::new(0, 0, width, height);
+            self.invariants
+                .iter()
+                .find(|(_, check)| !check(view, &buffer, area))
+                .map(|(name, _)| (name.as_str(), format!("invariant \"{}\" failed", name)))
+        });
+        checks.unwrap_or_else(|message| Some((NO_PANIC, message)))
+    }
+}
+
+/// Assert that a view renders only inside a `width` x `height` area
+///
+/// Emitted reproducers call this to replay "writes inside area" failures.
+pub fn assert_in_bounds<V: View + ?Sized>(view: &V, width: u16, height: u16) {
+    let (buffer, stray) = render_probe(view, width, height);
+    if let Some((x, y)) = stray {
+        panic!("{}", stray_message(&buffer, x, y, width, height));
+    }
+}
+
+/// Resolve a generated step against the current state
+///
+/// Wraps mouse coordinates into the screen and drops focus changes that a
+/// focus manager would never deliver.
+fn resolve<V: Interactive>(
+    view: &V,
+    step: &FuzzStep,
+    width: u16,
+    height: u16,
+    focused: bool,
+) -> Option<FuzzStep> {
+    match step {
+        FuzzStep::Mouse(event) => {
+            if width == 0 || height == 0 {
+                return None;
+            }
+            Some(FuzzStep::Mouse(MouseEvent {
+                x: event.x % width,
+                y: event.y % height,
+                ..event.clone()
+            }))
+        }
+        FuzzStep::Focus if focused || !view.focusable() => None,
+        FuzzStep::Blur if !focused => None,
+        step => Some(step.clone()),
+    }
+}
+
+/// Deliver a step to the widget
+fn apply<V: Interactive>(
+    view: &mut V,
+    step: &FuzzStep,
+    area: Rect,
+    paste: Option<&PasteHandler<V>>,
+) {
+    match step {
+        FuzzStep::Key(event) => {
+            view.handle_key(event);
+        }
+        FuzzStep::Mouse(event) => {
+            view.handle_mouse(event, area);
+        }
+        FuzzStep::Paste(text) => {
+            if let Some(handler) = paste {
+                return handler(view, text);
+            }
+            for c in text.chars() {
+                let key = if c == '\n' { Key::Enter } else { Key::Char(c) };
+                view.handle_key(&KeyEvent::new(key));
+            }
+        }
+        FuzzStep::Resize(..) => {}
+        FuzzStep::Focus => view.on_focus(),
+        FuzzStep::Blur => view.on_blur(),
+    }
+}
+
+/// Render into the top-left of an oversized buffer pre-filled with sentinel
+/// cells, returning the buffer and the first cell written outside the area
+fn render_probe<V: View + ?Sized>(
+    view: &V,
+    width: u16,
+    height: u16,
+) -> (Buffer, Option<(u16, u16)>) {
+    let area = Rect::new(0, 0, width, height);
+    let mut buffer = Buffer::new(
+        width.saturating_mul(2).saturating_add(4),
+        height.saturating_mul(2).saturating_add(4),
+    );
+    let sentinel = Cell::new(SENTINEL);
+    let outside = |x: u16, y: u16| x >= width || y >= height;
+    for y in 0..buffer.height() {
+        for x in 0..buffer.width() {
+            if outside(x, y) {
+                buffer.set(x, y, sentinel);
+            }
+        }
+    }
+
+    {
+        let mut ctx = RenderContext::new(&mut buffer, area);
+        view.render(&mut ctx);
+    }
+
+    let stray = (0..buffer.height())
+        .flat_map(|y| (0..buffer.width()).map(move |x| (x, y)))
+        .find(|&(x, y)| outside(x, y) && buffer.get(x, y) != Some(&sentinel));
+    (buffer, stray)
+}
+
+fn stray_message(buffer: &Buffer, x: u16, y: u16, width: u16, height: u16) -> String {
+    let symbol = buffer.get(x, y).map(|cell| cell.symbol).unwrap_or(' ');
+    format!(
+        "wrote {:?} at ({}, {}) outside the {}x{} area",
+        symbol, x, y, width, height
+    )
+}
+
+/// Run a closure, turning a panic into its message
+fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    catch_unwind(AssertUnwindSafe(f)).map_err(|payload| panic_message(&*payload))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        format!("panicked: {}", s)
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        format!("panicked: {}", s)
+    } else {
+        "panicked".to_string()
+    }
+}
+
+/// Expand a 64-bit seed into the runner's RNG
+fn seed_rng(seed: u64) -> TestRng {
+    let mut bytes = [0u8; 32];
+    for (i, chunk) in bytes.chunks_mut(8).enumerate() {
+        let word = seed ^ (i as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    TestRng::from_seed(RngAlgorithm::ChaCha, &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::MouseEventKind;
+    use crate::widget::{Checkbox, EventResult};
+
+    /// Panics once it has seen three character key presses
+    struct Fragile {
+        chars: usize,
+    }
+
+    impl View for Fragile {
+        fn render(&self, _ctx: &mut RenderContext) {}
+    }
+
+    impl Interactive for Fragile {
+        fn handle_key(&mut self, event: &KeyEvent) -> EventResult {
+            if let Key::Char(_) = event.key {
+                self.chars += 1;
+                assert!(self.chars < 3, "too many chars");
+            }
+            EventResult::Consumed
+        }
+    }
+
+    /// Draws its label without clipping to the area
+    struct Overflow;
+
+    impl View for Overflow {
+        fn render(&self, ctx: &mut RenderContext) {
+            ctx.buffer
+                .put_str(ctx.area.x, ctx.area.y, "overflowing label");
+        }
+    }
+
+    impl Interactive for Overflow {}
+
+    #[test]
+    fn test_fuzz_passes_well_behaved_widget() {
+        WidgetFuzzer::new(|| Checkbox::new("Accept"))
+            .cases(32)
+            .seed(7)
+            .run();
+    }
+
+    #[test]
+    fn test_fuzz_shrinks_panic_to_minimal_case() {
+        let failure = WidgetFuzzer::new(|| Fragile { chars: 0 })
+            .constructor("Fragile { chars: 0 }")
+            .seed(1)
+            .check()
+            .unwrap_err();
+
+        assert_eq!(failure.invariant, NO_PANIC);
+        assert!(failure.message.contains("too many chars"));
+        assert_eq!(failure.step, Some(failure.steps.len() - 1));
+        let chars: usize = failure
+            .steps
+            .iter()
+            .map(|step| match step {
+                FuzzStep::Key(KeyEvent {
+                    key: Key::Char(_), ..
+                }) => 1,
+                FuzzStep::Paste(text) => text.chars().filter(|c| *c != '\n').count(),
+                _ => 0,
+            })
+            .sum();
+        assert_eq!(chars, 3, "not shrunk: {:?}", failure.steps);
+        assert!(failure.steps.len() <= 3);
+        assert!(failure
+            .to_string()
+            .contains("TestApp::with_size(Fragile { chars: 0 }"));
+    }
+
+    #[test]
+    fn test_fuzz_detects_writes_outside_area() {
+        let failure = WidgetFuzzer::new(|| Overflow).seed(3).check().unwrap_err();
+        assert_eq!(failure.invariant, IN_BOUNDS);
+        assert_eq!(failure.step, None);
+        assert!(failure.steps.is_empty());
+        assert!(failure.message.contains("outside the"));
+    }
+
+    #[test]
+    fn test_fuzz_cursor_and_custom_invariants() {
+        let failure = WidgetFuzzer::new(|| Checkbox::new("Accept"))
+            .seed(5)
+            .cursor(|_| Some((500, 0)))
+            .check()
+            .unwrap_err();
+        assert_eq!(failure.invariant, CURSOR_IN_BOUNDS);
+
+        let failure = WidgetFuzzer::new(|| Checkbox::new("Accept"))
+            .seed(5)
+            .invariant("never checked", |view, _, _| !view.is_checked())
+            .check()
+            .unwrap_err();
+        assert_eq!(failure.invariant, "never checked");
+        assert_eq!(failure.steps.len(), 1);
+    }
+
+    #[test]
+    fn test_resolve_wraps_mouse_and_skips_focus() {
+        let view = Checkbox::new("a").disabled(true);
+        let click = FuzzStep::Mouse(MouseEvent::new(25, 7, MouseEventKind::ScrollDown));
+        assert_eq!(
+            resolve(&view, &click, 10, 3, false),
+            Some(FuzzStep::Mouse(MouseEvent::new(
+                5,
+                1,
+                MouseEventKind::ScrollDown
+            )))
+        );
+        assert_eq!(resolve(&view, &click, 0, 3, false), None);
+        assert_eq!(resolve(&view, &FuzzStep::Focus, 10, 3, false), None);
+        assert_eq!(resolve(&view, &FuzzStep::Blur, 10, 3, false), None);
+    }
+
+    #[test]
+    fn test_assert_in_bounds() {
+        assert_in_bounds(&Checkbox::new("a"), 10, 1);
+        let result = catch(|| assert_in_bounds(&Overflow, 4, 1));
+        assert!(result.unwrap_err().contains("outside the 4x1 area"));
+    }
+
+    #[test]
+    fn test_seed_from_config() {
+        assert_eq!(FuzzConfig::default().seed(42).resolve_seed(), 42);
+    }
+}
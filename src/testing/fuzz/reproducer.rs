@@ -0,0 +1,219 @@
+//! Emit shrunk fuzz failures as Pilot regression tests
+
+use super::{FuzzFailure, FuzzStep, FOCUS_VALID, IN_BOUNDS, NO_PANIC};
+use crate::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use std::fmt::Write;
+
+impl FuzzFailure {
+    /// Render the failure as a `#[test]` function driving a [`Pilot`]
+    ///
+    /// The test builds the widget with the fuzzer's constructor expression,
+    /// wires it up with [`TestApp::interactive`] (and the fuzzer's paste
+    /// handler, if any), replays the steps and ends with an assertion for the
+    /// violated invariant where one can be expressed without the fuzzer
+    /// (panics reproduce on their own).
+    ///
+    /// [`Pilot`]: crate::testing::Pilot
+    /// [`TestApp::interactive`]: crate::testing::TestApp::interactive
+    pub fn to_pilot_test(&self, name: &str) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "#[test]");
+        let _ = writeln!(out, "fn {}() {{", name);
+        let _ = writeln!(out, "    use revue::event::*;");
+        if self.invariant == IN_BOUNDS {
+            let _ = writeln!(out, "    use revue::testing::fuzz::assert_in_bounds;");
+        }
+        let _ = writeln!(out, "    use revue::testing::{{Pilot, TestApp}};");
+        let _ = writeln!(out);
+        let _ = writeln!(
+            out,
+            "    // Violates \"{}\": {}",
+            self.invariant, self.message
+        );
+        let _ = write!(
+            out,
+            "    let mut app = TestApp::with_size({}, {}, {}).interactive()",
+            self.constructor, self.width, self.height
+        );
+        match &self.paste {
+            Some(call) => {
+                let _ = writeln!(out, ".on_paste(|text, view| {{");
+                let _ = writeln!(out, "        {};", call);
+                let _ = writeln!(out, "        true");
+                let _ = writeln!(out, "    }});");
+            }
+            None => {
+                let _ = writeln!(out, ";");
+            }
+        }
+        let _ = writeln!(out, "    let mut pilot = Pilot::new(&mut app);");
+
+        let (mut width, mut height) = (self.width, self.height);
+        for step in &self.steps {
+            if let FuzzStep::Resize(w, h) = step {
+                (width, height) = (*w, *h);
+            }
+            let _ = writeln!(out, "    pilot.{};", call(step));
+        }
+
+        match self.invariant.as_str() {
+            NO_PANIC => {}
+            IN_BOUNDS => {
+                let _ = writeln!(
+                    out,
+                    "    assert_in_bounds(pilot.view(), {}, {});",
+                    width, height
+                );
+            }
+            FOCUS_VALID => {
+                let _ = writeln!(out, "    assert!(pilot.view().focusable());");
+            }
+            other => {
+                let _ = writeln!(out, "    // Check \"{}\" here", other);
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// The Pilot method call replaying one step
+fn call(step: &FuzzStep) -> String {
+    match step {
+        FuzzStep::Key(event) => key_call(event),
+        FuzzStep::Mouse(event) => mouse_call(event),
+        FuzzStep::Paste(text) => format!("paste({:?})", text),
+        FuzzStep::Resize(w, h) => format!("resize({}, {})", w, h),
+        FuzzStep::Focus => "update(|view| view.on_focus())".to_string(),
+        FuzzStep::Blur => "update(|view| view.on_blur())".to_string(),
+    }
+}
+
+fn key_call(event: &KeyEvent) -> String {
+    let key = format!("Key::{:?}", event.key);
+    match (event.ctrl, event.alt, event.shift) {
+        (false, false, false) => format!("press_key({})", key),
+        (true, false, false) => format!("press_ctrl({})", key),
+        (false, true, false) => format!("press_alt({})", key),
+        (ctrl, alt, shift) => format!(
+            "send_key(KeyEvent {{ key: {}, ctrl: {}, alt: {}, shift: {} }})",
+            key, ctrl, alt, shift
+        ),
+    }
+}
+
+fn mouse_call(event: &MouseEvent) -> String {
+    let plain = !(event.ctrl || event.alt || event.shift);
+    if plain && event.kind == MouseEventKind::Down(MouseButton::Left) {
+        return format!("click({}, {})", event.x, event.y);
+    }
+    let kind = match event.kind {
+        MouseEventKind::Down(button) => format!("MouseEventKind::Down(MouseButton::{:?})", button),
+        MouseEventKind::Up(button) => format!("MouseEventKind::Up(MouseButton::{:?})", button),
+        MouseEventKind::Drag(button) => format!("MouseEventKind::Drag(MouseButton::{:?})", button),
+        kind => format!("MouseEventKind::{:?}", kind),
+    };
+    if plain {
+        return format!(
+            "send_mouse(MouseEvent::new({}, {}, {}))",
+            event.x, event.y, kind
+        );
+    }
+    format!(
+        "send_mouse(MouseEvent {{ x: {}, y: {}, kind: {}, ctrl: {}, alt: {}, shift: {} }})",
+        event.x, event.y, kind, event.ctrl, event.alt, event.shift
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Key;
+
+    fn failure(invariant: &str, steps: Vec<FuzzStep>) -> FuzzFailure {
+        FuzzFailure {
+            seed: 9,
+            width: 10,
+            height: 2,
+            steps,
+            step: Some(0),
+            invariant: invariant.to_string(),
+            message: "boom".to_string(),
+            constructor: "Input::new()".to_string(),
+            paste: None,
+        }
+    }
+
+    #[test]
+    fn test_pilot_test_steps() {
+        let test = failure(
+            IN_BOUNDS,
+            vec![
+                FuzzStep::Focus,
+                FuzzStep::Key(KeyEvent::new(Key::Char('\''))),
+                FuzzStep::Key(KeyEvent::ctrl(Key::Left)),
+                FuzzStep::Key(KeyEvent {
+                    key: Key::F(2),
+                    ctrl: true,
+                    alt: false,
+                    shift: true,
+                }),
+                FuzzStep::Mouse(MouseEvent::new(
+                    1,
+                    0,
+                    MouseEventKind::Down(MouseButton::Left),
+                )),
+                FuzzStep::Mouse(MouseEvent::new(
+                    2,
+                    1,
+                    MouseEventKind::Drag(MouseButton::Right),
+                )),
+                FuzzStep::Paste("a\n\"b".to_string()),
+                FuzzStep::Resize(4, 1),
+            ],
+        )
+        .to_pilot_test("regression");
+
+        let expected = [
+            "fn regression() {",
+            "    // Violates \"writes inside area\": boom",
+            "    let mut app = TestApp::with_size(Input::new(), 10, 2).interactive();",
+            "    pilot.update(|view| view.on_focus());",
+            "    pilot.press_key(Key::Char('\\''));",
+            "    pilot.press_ctrl(Key::Left);",
+            "    pilot.send_key(KeyEvent { key: Key::F(2), ctrl: true, alt: false, shift: true });",
+            "    pilot.click(1, 0);",
+            "    pilot.send_mouse(MouseEvent::new(2, 1, MouseEventKind::Drag(MouseButton::Right)));",
+            "    pilot.paste(\"a\\n\\\"b\");",
+            "    pilot.resize(4, 1);",
+            "    assert_in_bounds(pilot.view(), 4, 1);",
+        ];
+        for line in expected {
+            assert!(test.contains(line), "missing {:?} in:\n{}", line, test);
+        }
+        assert!(test.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_pilot_test_invariant_assertions() {
+        let panic = failure(NO_PANIC, vec![]).to_pilot_test("t");
+        assert!(!panic.contains("assert"), "{}", panic);
+
+        let focus = failure(FOCUS_VALID, vec![]).to_pilot_test("t");
+        assert!(focus.contains("assert!(pilot.view().focusable());"));
+
+        let custom = failure("sorted", vec![]).to_pilot_test("t");
+        assert!(custom.contains("// Check \"sorted\" here"));
+    }
+
+    #[test]
+    fn test_pilot_test_wires_paste_handler() {
+        let mut failure = failure(NO_PANIC, vec![FuzzStep::Paste("ab".to_string())]);
+        failure.paste = Some("view.insert_str(text)".to_string());
+        let test = failure.to_pilot_test("t");
+
+        let expected = "    let mut app = TestApp::with_size(Input::new(), 10, 2).interactive().on_paste(|text, view| {\n        view.insert_str(text);\n        true\n    });\n";
+        assert!(test.contains(expected), "{}", test);
+        assert!(test.contains("    pilot.paste(\"ab\");"));
+    }
+}
@@ -0,0 +1,151 @@
+//! Random event sequences for fuzzing
+
+use super::FuzzConfig;
+use crate::event::{Key, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use proptest::prelude::*;
+
+/// One input step of a fuzz case
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FuzzStep {
+    /// Key press
+    Key(KeyEvent),
+    /// Mouse event
+    ///
+    /// In generated cases the coordinates are raw and get wrapped into the
+    /// current screen when the step runs; in a [`FuzzFailure`] they are the
+    /// coordinates actually sent.
+    ///
+    /// [`FuzzFailure`]: super::FuzzFailure
+    Mouse(MouseEvent),
+    /// Bracketed paste, delivered whole to the fuzzer's paste handler, or
+    /// as one key press per character without one
+    Paste(String),
+    /// Screen resize
+    Resize(u16, u16),
+    /// Focus gained (skipped while the widget is not focusable)
+    Focus,
+    /// Focus lost
+    Blur,
+}
+
+/// A generated fuzz case: the initial screen size and the steps to run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzCase {
+    /// Initial screen width
+    pub width: u16,
+    /// Initial screen height
+    pub height: u16,
+    /// Steps to run, rendering after each
+    pub steps: Vec<FuzzStep>,
+}
+
+/// Strategy for whole fuzz cases
+pub(super) fn case(config: &FuzzConfig) -> impl Strategy<Value = FuzzCase> {
+    (
+        size(config),
+        prop::collection::vec(step(config), 0..=config.max_steps),
+    )
+        .prop_map(|((width, height), steps)| FuzzCase {
+            width,
+            height,
+            steps,
+        })
+}
+
+fn size(config: &FuzzConfig) -> impl Strategy<Value = (u16, u16)> {
+    let (min_w, min_h) = config.min_size;
+    let (max_w, max_h) = config.max_size;
+    (min_w..=max_w.max(min_w), min_h..=max_h.max(min_h))
+}
+
+fn step(config: &FuzzConfig) -> impl Strategy<Value = FuzzStep> {
+    prop_oneof![
+        8 => key_event().prop_map(FuzzStep::Key),
+        3 => mouse_event().prop_map(FuzzStep::Mouse),
+        1 => prop::collection::vec(character(), 0..16)
+            .prop_map(|chars| FuzzStep::Paste(chars.into_iter().collect())),
+        1 => size(config).prop_map(|(w, h)| FuzzStep::Resize(w, h)),
+        1 => Just(FuzzStep::Focus),
+        1 => Just(FuzzStep::Blur),
+    ]
+}
+
+fn key_event() -> impl Strategy<Value = KeyEvent> {
+    let modifier = || prop::bool::weighted(0.15);
+    (key(), modifier(), modifier(), modifier()).prop_map(|(key, ctrl, alt, shift)| KeyEvent {
+        key,
+        ctrl,
+        alt,
+        shift,
+    })
+}
+
+fn key() -> impl Strategy<Value = Key> {
+    prop_oneof![
+        6 => character().prop_map(Key::Char),
+        4 => prop::sample::select(vec![
+            Key::Enter,
+            Key::Escape,
+            Key::Tab,
+            Key::BackTab,
+            Key::Backspace,
+            Key::Delete,
+            Key::Up,
+            Key::Down,
+            Key::Left,
+            Key::Right,
+            Key::Home,
+            Key::End,
+            Key::PageUp,
+            Key::PageDown,
+            Key::Insert,
+        ]),
+        1 => (1u8..=12).prop_map(Key::F),
+    ]
+}
+
+/// Mostly printable ASCII, with the characters that tend to break width math
+fn character() -> impl Strategy<Value = char> {
+    prop_oneof![
+        8 => (0x20u8..0x7f).prop_map(char::from),
+        1 => prop::sample::select(vec![
+            '\n', '\t', '\u{0}', 'é', '\u{301}', '中', '😀', '\u{200d}', '\u{feff}',
+        ]),
+    ]
+}
+
+fn mouse_event() -> impl Strategy<Value = MouseEvent> {
+    let button = || {
+        prop::sample::select(vec![
+            MouseButton::Left,
+            MouseButton::Right,
+            MouseButton::Middle,
+        ])
+    };
+    let kind = prop_oneof![
+        3 => button().prop_map(MouseEventKind::Down),
+        2 => button().prop_map(MouseEventKind::Up),
+        2 => button().prop_map(MouseEventKind::Drag),
+        1 => Just(MouseEventKind::Move),
+        1 => Just(MouseEventKind::ScrollDown),
+        1 => Just(MouseEventKind::ScrollUp),
+        1 => Just(MouseEventKind::ScrollLeft),
+        1 => Just(MouseEventKind::ScrollRight),
+    ];
+    (
+        kind,
+        any::<u16>(),
+        any::<u16>(),
+        prop::bool::weighted(0.1),
+        prop::bool::weighted(0.1),
+        prop::bool::weighted(0.1),
+    )
+        .prop_map(|(kind, x, y, ctrl, alt, shift)| MouseEvent {
+            x,
+            y,
+            kind,
+            ctrl,
+            alt,
+            shift,
+        })
+}
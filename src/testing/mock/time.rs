@@ -1,12 +1,18 @@
 //! Mock time controller for testing time-dependent code
 
+use crate::utils::clock::{self, Clock, ClockGuard};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Mock time controller for testing time-dependent code
+///
+/// Implements [`Clock`], so it can be installed with
+/// [`install()`](Self::install) to drive runtime code that reads
+/// [`clock::now()`].
 #[derive(Debug, Clone)]
 pub struct MockTime {
+    origin: Instant,
     elapsed_ms: Arc<AtomicU64>,
 }
 
@@ -14,6 +20,7 @@ impl MockTime {
     /// Create a new mock time starting at 0
     pub fn new() -> Self {
         Self {
+            origin: Instant::now(),
             elapsed_ms: Arc::new(AtomicU64::new(0)),
         }
     }
@@ -54,6 +61,19 @@ impl MockTime {
         self.elapsed_ms
             .store(duration.as_millis() as u64, Ordering::Relaxed);
     }
+
+    /// Install as the clock for the current thread
+    ///
+    /// The previous clock is restored when the guard is dropped.
+    pub fn install(&self) -> ClockGuard {
+        clock::set_clock(self.clone())
+    }
+}
+
+impl Clock for MockTime {
+    fn now(&self) -> Instant {
+        self.origin + self.elapsed()
+    }
 }
 
 impl Default for MockTime {
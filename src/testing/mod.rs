@@ -14,6 +14,8 @@
 //! | **CI Integration** | GitHub Actions, GitLab CI |
 //! | **Async Support** | Test async operations |
 //! | **Action Sequences** | Chain multiple actions |
+//! | **End-to-End** | Drive compiled binaries under a PTY |
+//! | **Fuzzing** | Property-based event fuzzing with shrinking |
 //!
 //! # Quick Start
 //!
@@ -73,11 +75,72 @@
 //! ## Waiting
 //!
 //! ```rust,ignore
-//! pilot
-//!     .wait_ms(100)                        // Wait 100ms
-//!     .wait_until(|screen| {               // Wait for condition
-//!         screen.contains("Loaded")
-//!     });
+//! A [`Pilot`] installs a virtual clock (see [`crate::utils::clock`]), so
+//! animations, timers and debouncers can be driven without sleeping:
+//!
+//! ```rust,ignore
+//! pilot.advance(Duration::from_millis(500));   // Jump ahead instantly
+//! pilot.wait_ms(100);                          // Same as advance, never sleeps
+//! pilot.advance_until(|buf| buf.get(0, 0).unwrap().symbol == '#'); // Virtual time only
+//! let frames = pilot.capture_frames(10, Duration::from_millis(16));
+//! ```
+//!
+//! Work on background threads or async tasks needs real time to pass.
+//! `wait_until` polls on a real-time budget and advances the virtual clock
+//! along with it:
+//!
+//! ```rust,ignore
+//! pilot.wait_until(|buf| buf.get(0, 0).unwrap().symbol == 'L');
+//! ```
+//!
+//! ## Selector Queries
+//!
+//! Target widgets through the DOM instead of screen coordinates:
+//!
+//! ```rust,ignore
+//! pilot.query("#save-button").unwrap().click();
+//! pilot.query("#search").unwrap().type_text("revue"); // needs TestApp::on_focus
+//!
+//! let rows = pilot.query_all(".row");
+//! assert_eq!(rows[0].text, "First");
+//!
+//! let toast = pilot.wait_for_selector(".toast", Duration::from_secs(1));
+//! assert!(toast.is_visible());
+//! ```
+//!
+//! ## Record and Replay
+//!
+//! Sessions recorded from a real run (with `App::builder().record(path)` or
+//! `REVUE_RECORD=path`) replay as regression tests:
+//!
+//! ```rust,ignore
+//! use revue::core::app::Recording;
+//!
+//! let recording = Recording::load("tests/recordings/issue_123.rec").unwrap();
+//! pilot.replay(&recording);   // Panics if any recorded screen differs
+//! ```
+//!
+//! # End-to-End Testing
+//!
+//! With the `pty` feature, `PtyPilot` runs a compiled binary under a real
+//! pseudo-terminal and offers the same assertions:
+//!
+//! ```rust,ignore
+//! let mut pilot = PtyPilot::spawn(Command::new(env!("CARGO_BIN_EXE_myapp")))?;
+//! pilot.assert_contains("Ready").press(Key::Char('q'));
+//! pilot.wait_for_exit();
+//! pilot.assert_restored();
+//! ```
+//!
+//! # Fuzzing
+//!
+//! With the `fuzz` feature, [`fuzz::WidgetFuzzer`] throws random key, mouse,
+//! paste, resize and focus events at any interactive widget and checks that
+//! it never panics, never draws outside its area and keeps its cursor and
+//! focus valid. Failures shrink to a minimal Pilot test:
+//!
+//! ```rust,ignore
+//! WidgetFuzzer::new(Input::new).constructor("Input::new()").run();
 //! ```
 //!
 //! # TestApp
@@ -180,15 +243,20 @@
 //! 1. **Name snapshots descriptively**: `"login_form_with_error"` not `"test1"`
 //! 2. **Test user flows**: Simulate realistic user interactions
 //! 3. **Keep tests focused**: One behavior per test
-//! 4. **Use wait_until for async**: Don't rely on fixed delays
+//! 4. **Use wait_until for async**: Don't rely on fixed delays; use
+//!    `advance`/`advance_until` for anything driven by virtual time
 //! 5. **Use visual tests for styling**: Catch color and layout regressions
 //! 6. **Run in CI**: Use `CiEnvironment` for portable test reports
 
 mod actions;
 mod assertions;
 pub mod ci;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
 pub mod mock;
 mod pilot;
+#[cfg(all(unix, feature = "pty"))]
+mod pty;
 mod snapshot;
 mod test_app;
 pub mod visual;
@@ -200,7 +268,9 @@ pub use mock::{
     mock_time, simulate_user, EventSimulator, MockState, MockTerminal, MockTime, RenderCapture,
     SimulatedEvent,
 };
-pub use pilot::{AsyncPilot, Pilot};
+pub use pilot::{AsyncPilot, ElementHandle, ElementInfo, Pilot};
+#[cfg(all(unix, feature = "pty"))]
+pub use pty::PtyPilot;
 pub use snapshot::SnapshotManager;
 pub use test_app::TestApp;
 
@@ -1,35 +1,53 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use crate::event::{Key, KeyEvent};
+use crate::event::{Key, KeyEvent, MouseEvent};
 use crate::render::Buffer;
 use crate::testing::{Action, KeyAction, MouseAction, TestApp, TestConfig};
+use crate::utils::clock::{ClockGuard, VirtualClock};
+
+/// Time stepped per poll by `wait_until`, `advance_until` and `wait_for_selector`
+pub(super) const WAIT_TICK: Duration = Duration::from_millis(10);
 
 /// Pilot controller for automated testing
 pub struct Pilot<'a, V: crate::widget::View> {
     /// The test app being controlled
-    app: &'a mut TestApp<V>,
+    pub(super) app: &'a mut TestApp<V>,
     /// Test configuration
     config: TestConfig,
     /// Action history
     history: Vec<Action>,
+    /// Virtual clock installed for the pilot's lifetime
+    clock: VirtualClock,
+    _clock_guard: ClockGuard,
 }
 
 impl<'a, V: crate::widget::View> Pilot<'a, V> {
     /// Create a new pilot for a test app
+    ///
+    /// Installs a [`VirtualClock`] on the current thread until the pilot is
+    /// dropped, so animations, timers and debouncers only see time pass
+    /// through [`advance()`][Self::advance] and the wait methods.
     pub fn new(app: &'a mut TestApp<V>) -> Self {
-        Self {
-            app,
-            config: TestConfig::default(),
-            history: Vec::new(),
-        }
+        Self::with_config(app, TestConfig::default())
     }
 
     /// Create with custom config
     pub fn with_config(app: &'a mut TestApp<V>, config: TestConfig) -> Self {
+        Self::with_clock(app, config, VirtualClock::new())
+    }
+
+    /// Create with custom config and an existing virtual clock
+    ///
+    /// Use this to share one clock between the pilot and state created
+    /// before it (e.g. animations started while building the view).
+    pub fn with_clock(app: &'a mut TestApp<V>, config: TestConfig, clock: VirtualClock) -> Self {
+        let clock_guard = clock.install();
         Self {
             app,
             config,
             history: Vec::new(),
+            clock,
+            _clock_guard: clock_guard,
         }
     }
 
@@ -116,6 +134,22 @@ impl<'a, V: crate::widget::View> Pilot<'a, V> {
         self.press_ctrl(Key::Char('c'))
     }
 
+    /// Send a key event with arbitrary modifiers
+    pub fn send_key(&mut self, event: KeyEvent) -> &mut Self {
+        self.app.send_key(event);
+        self
+    }
+
+    /// Paste text as one bracketed paste
+    ///
+    /// Delivered whole to the app's [`on_paste`](crate::testing::TestApp::on_paste)
+    /// handler, as the runtime delivers `Event::Paste`. Apps without one
+    /// receive the text as key presses (newlines as Enter).
+    pub fn paste(&mut self, text: &str) -> &mut Self {
+        self.app.send_paste(text);
+        self
+    }
+
     // =========================================================================
     // Mouse Actions
     // =========================================================================
@@ -152,41 +186,119 @@ impl<'a, V: crate::widget::View> Pilot<'a, V> {
         self
     }
 
+    /// Send a raw mouse event (drags, releases, other buttons)
+    pub fn send_mouse(&mut self, event: MouseEvent) -> &mut Self {
+        self.app.send_mouse(event);
+        self
+    }
+
     // =========================================================================
     // Timing
     // =========================================================================
 
-    /// Wait for a duration
-    pub fn wait(&mut self, duration: Duration) -> &mut Self {
-        std::thread::sleep(duration);
+    /// Get the virtual clock driving time for the app under test
+    pub fn clock(&self) -> &VirtualClock {
+        &self.clock
+    }
+
+    /// Advance virtual time instantly, then tick and re-render the app
+    ///
+    /// This is the default way to let time pass in a test: it never sleeps,
+    /// so timing-dependent tests stay fast and deterministic.
+    pub fn advance(&mut self, duration: Duration) -> &mut Self {
+        self.clock.advance(duration);
+        self.app.send_tick(duration);
         self.history.push(Action::Wait(duration));
         self
     }
 
+    /// Advance virtual time by milliseconds
+    pub fn advance_ms(&mut self, ms: u64) -> &mut Self {
+        self.advance(Duration::from_millis(ms))
+    }
+
+    /// Capture `count` frames, advancing virtual time by `interval` between them
+    ///
+    /// The first frame is the current screen. Useful for asserting on
+    /// animations frame-by-frame.
+    pub fn capture_frames(&mut self, count: usize, interval: Duration) -> Vec<Buffer> {
+        let mut frames = Vec::with_capacity(count);
+        for i in 0..count {
+            if i > 0 {
+                self.advance(interval);
+            }
+            frames.push(self.app.buffer().clone());
+        }
+        frames
+    }
+
+    /// Wait for a duration
+    ///
+    /// Same as [`advance()`][Self::advance]: virtual time jumps ahead
+    /// instantly and the app is ticked. Never sleeps.
+    pub fn wait(&mut self, duration: Duration) -> &mut Self {
+        self.advance(duration)
+    }
+
     /// Wait for milliseconds
     pub fn wait_ms(&mut self, ms: u64) -> &mut Self {
         self.wait(Duration::from_millis(ms))
     }
 
+    /// Advance virtual time until a condition is true
+    ///
+    /// Steps the virtual clock by [`WAIT_TICK`] until the condition holds,
+    /// panicking once the configured timeout of virtual time has passed.
+    /// Never sleeps, so only use it for conditions driven by virtual time
+    /// (animations, timers, debouncers).
+    pub fn advance_until<F>(&mut self, condition: F) -> &mut Self
+    where
+        F: Fn(&Buffer) -> bool,
+    {
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        let mut waited = Duration::ZERO;
+
+        while !condition(self.app.buffer()) {
+            if waited >= timeout {
+                panic!("advance_until timed out after {:?}", timeout);
+            }
+            self.step_virtual(WAIT_TICK);
+            waited += WAIT_TICK;
+        }
+
+        self
+    }
+
     /// Wait for a condition to be true
+    ///
+    /// Polls every [`WAIT_TICK`] of real time, advancing the virtual clock
+    /// by the same amount each poll, so both background threads and
+    /// virtual timers make progress. Panics once the configured timeout of
+    /// real time has passed.
     pub fn wait_until<F>(&mut self, condition: F) -> &mut Self
     where
         F: Fn(&Buffer) -> bool,
     {
-        let start = std::time::Instant::now();
         let timeout = Duration::from_millis(self.config.timeout_ms);
+        let start = Instant::now();
 
         while !condition(self.app.buffer()) {
             if start.elapsed() >= timeout {
                 panic!("wait_until timed out after {:?}", timeout);
             }
-            std::thread::sleep(Duration::from_millis(10));
-            self.app.render();
+            std::thread::sleep(WAIT_TICK);
+            self.step_virtual(WAIT_TICK);
         }
 
         self
     }
 
+    /// Advance the virtual clock and tick the app without recording history
+    pub(super) fn step_virtual(&mut self, duration: Duration) {
+        self.clock.advance(duration);
+        self.app.send_tick(duration);
+    }
+
     // =========================================================================
     // Assertions
     // =========================================================================
@@ -338,23 +450,30 @@ impl<'a, V: crate::widget::View> Pilot<'a, V> {
         self.app.resize(width, height);
     }
 
+    /// Get the view under test
+    pub fn view(&self) -> &V {
+        self.app.view()
+    }
+
+    /// Mutate the view directly (e.g. `on_focus()`), then re-render
+    pub fn update(&mut self, f: impl FnOnce(&mut V)) -> &mut Self {
+        f(self.app.view_mut());
+        self.app.render();
+        self
+    }
+
     // =========================================================================
     // Async Support
     // =========================================================================
 
     /// Wait for a duration (async version)
+    ///
+    /// Like [`wait()`][Self::wait], but yields to the async runtime first.
+    /// Never sleeps.
     pub async fn wait_async(&mut self, duration: Duration) -> &mut Self {
         #[cfg(feature = "async")]
-        {
-            tokio::time::sleep(duration).await;
-        }
-        #[cfg(not(feature = "async"))]
-        {
-            // Fallback to sync sleep if tokio not available
-            std::thread::sleep(duration);
-        }
-        self.history.push(Action::Wait(duration));
-        self
+        tokio::task::yield_now().await;
+        self.advance(duration)
     }
 
     /// Wait for milliseconds (async version)
@@ -363,26 +482,25 @@ impl<'a, V: crate::widget::View> Pilot<'a, V> {
     }
 
     /// Wait for a condition to be true (async version)
+    ///
+    /// Like [`wait_until()`][Self::wait_until], but sleeps on the async
+    /// runtime between polls so other tasks can make progress.
     pub async fn wait_until_async<F>(&mut self, condition: F) -> &mut Self
     where
         F: Fn(&Buffer) -> bool,
     {
-        let start = std::time::Instant::now();
         let timeout = Duration::from_millis(self.config.timeout_ms);
+        let start = Instant::now();
 
         while !condition(self.app.buffer()) {
             if start.elapsed() >= timeout {
                 panic!("wait_until_async timed out after {:?}", timeout);
             }
             #[cfg(feature = "async")]
-            {
-                tokio::time::sleep(Duration::from_millis(10)).await;
-            }
+            tokio::time::sleep(WAIT_TICK).await;
             #[cfg(not(feature = "async"))]
-            {
-                std::thread::sleep(Duration::from_millis(10));
-            }
-            self.app.render();
+            std::thread::sleep(WAIT_TICK);
+            self.step_virtual(WAIT_TICK);
         }
 
         self
@@ -2,6 +2,8 @@
 
 mod async_pilot;
 mod core;
+mod query;
+mod replay;
 
 #[cfg(test)]
 mod tests {
@@ -284,14 +286,44 @@ mod tests {
         let mut app = TestApp::new(view);
         let mut pilot = Pilot::new(&mut app);
 
-        let start = std::time::Instant::now();
-        pilot.wait(Duration::from_millis(10));
-        let elapsed = start.elapsed();
+        pilot.wait(Duration::from_secs(60));
 
-        assert!(elapsed >= Duration::from_millis(10));
+        assert_eq!(pilot.clock().elapsed(), Duration::from_secs(60));
         assert!(matches!(pilot.history()[0], Action::Wait(_)));
     }
 
+    struct FlagView {
+        loaded: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    impl View for FlagView {
+        fn render(&self, ctx: &mut RenderContext) {
+            let text = if self.loaded.load(std::sync::atomic::Ordering::SeqCst) {
+                "Ready"
+            } else {
+                "Waiting"
+            };
+            Text::new(text).render(ctx);
+        }
+    }
+
+    #[test]
+    fn test_pilot_wait_until_lets_background_threads_run() {
+        let loaded = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let mut app = TestApp::new(FlagView {
+            loaded: loaded.clone(),
+        });
+        let mut pilot = Pilot::new(&mut app);
+
+        let worker = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            loaded.store(true, std::sync::atomic::Ordering::SeqCst);
+        });
+        pilot.wait_until(|buf| buf.get(0, 0).is_some_and(|cell| cell.symbol == 'R'));
+        worker.join().unwrap();
+        assert!(pilot.clock().elapsed() >= Duration::from_millis(10));
+    }
+
     #[test]
     fn test_pilot_wait_ms() {
         let view = SimpleView {
@@ -479,8 +511,226 @@ mod tests {
 
         assert_eq!(pilot.history().len(), 4);
     }
+
+    fn form() -> crate::widget::Stack {
+        use crate::widget::{hstack, vstack};
+
+        vstack()
+            .child(Text::new("Title").element_id("title"))
+            .child(
+                hstack()
+                    .class("toolbar")
+                    .child_sized(Text::new("Save").element_id("save").class("btn"), 6)
+                    .child_sized(Text::new("Quit").class("btn"), 6),
+            )
+            .child(Text::new("Status: ok").element_id("status"))
+    }
+
+    #[test]
+    fn test_pilot_query_by_id_rect_and_text() {
+        let mut app = TestApp::with_size(form(), 20, 3);
+        let mut pilot = Pilot::new(&mut app);
+
+        let save = pilot.query("#save").unwrap();
+        assert_eq!(save.rect(), Some(crate::layout::Rect::new(0, 1, 6, 1)));
+        assert_eq!(save.text(), "Save");
+        assert!(save.is_visible());
+
+        assert_eq!(pilot.query("#status").unwrap().text(), "Status: ok");
+        assert!(pilot.query("#missing").is_none());
+    }
+
+    #[test]
+    fn test_pilot_query_all_in_document_order() {
+        let mut app = TestApp::with_size(form(), 20, 3);
+        let pilot = Pilot::new(&mut app);
+
+        let buttons = pilot.query_all(".toolbar .btn");
+        let texts: Vec<_> = buttons.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, vec!["Save", "Quit"]);
+        assert_eq!(buttons[0].element_id.as_deref(), Some("save"));
+        assert_eq!(buttons[1].rect.map(|r| r.x), Some(6));
+        assert!(pilot.exists("Text"));
+        assert!(!pilot.exists(".row"));
+    }
+
+    #[test]
+    fn test_pilot_element_click_hits_center() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let clicked = Rc::new(Cell::new(None));
+        let sink = Rc::clone(&clicked);
+        let mut app = TestApp::with_size(form(), 20, 3).on_mouse(move |event, _| {
+            sink.set(Some((event.x, event.y)));
+            false
+        });
+        let mut pilot = Pilot::new(&mut app);
+
+        pilot.query(".btn:last-child").unwrap().click();
+        assert_eq!(clicked.get(), Some((9, 1)));
+        assert!(matches!(
+            pilot.history()[0],
+            Action::Mouse(MouseAction::Click(9, 1))
+        ));
+    }
+
+    #[test]
+    fn test_pilot_element_focus_matches_pseudo_class() {
+        let mut app = TestApp::with_size(form(), 20, 3);
+        let mut pilot = Pilot::new(&mut app);
+
+        assert!(pilot.query(":focus").is_none());
+        pilot.query("#save").unwrap().focus();
+
+        let focused = pilot.query_all(":focus");
+        assert_eq!(focused.len(), 1);
+        assert_eq!(focused[0].element_id.as_deref(), Some("save"));
+    }
+
+    struct Fields {
+        inputs: Vec<(&'static str, crate::widget::Input)>,
+        focused: Option<usize>,
+        layout: crate::widget::Stack,
+    }
+
+    impl Fields {
+        fn new(ids: &[&'static str]) -> Self {
+            let mut fields = Self {
+                inputs: ids
+                    .iter()
+                    .map(|id| (*id, crate::widget::Input::new()))
+                    .collect(),
+                focused: None,
+                layout: crate::widget::vstack(),
+            };
+            fields.sync();
+            fields
+        }
+
+        /// Rebuild the displayed texts (and their DOM nodes) from the inputs
+        fn sync(&mut self) {
+            self.layout = self
+                .inputs
+                .iter()
+                .fold(crate::widget::vstack(), |stack, (id, input)| {
+                    stack.child(Text::new(input.text()).element_id(*id))
+                });
+        }
+    }
+
+    impl View for Fields {
+        fn render(&self, ctx: &mut RenderContext) {
+            self.layout.render(ctx);
+        }
+
+        fn children(&self) -> &[Box<dyn View>] {
+            self.layout.children()
+        }
+    }
+
+    #[test]
+    fn test_pilot_element_type_text_reaches_focused_widget() {
+        let mut app = TestApp::with_size(Fields::new(&["name", "email"]), 20, 2)
+            .on_focus(|node, view: &mut Fields| {
+                view.focused = view
+                    .inputs
+                    .iter()
+                    .position(|(id, _)| node.element_id() == Some(id));
+                true
+            })
+            .on_key(|key, view: &mut Fields| {
+                let Some(i) = view.focused else {
+                    return false;
+                };
+                let handled = view.inputs[i].1.handle_key_event(key);
+                view.sync();
+                handled
+            });
+        let mut pilot = Pilot::new(&mut app);
+
+        pilot.query("#email").unwrap().type_text("a@b");
+        pilot.query("#name").unwrap().type_text("Ann");
+
+        assert_eq!(pilot.query("#name").unwrap().text(), "Ann");
+        assert_eq!(pilot.query("#email").unwrap().text(), "a@b");
+        let focused = pilot.query_all(":focus");
+        assert_eq!(focused[0].element_id.as_deref(), Some("name"));
+    }
+
+    #[test]
+    fn test_pilot_element_computed_style() {
+        let sheet = crate::style::parse_css("#title { color: red; }").unwrap();
+        let mut app = TestApp::with_size(form(), 20, 3).stylesheet(sheet);
+        let mut pilot = Pilot::new(&mut app);
+
+        let style = pilot.query("#title").unwrap().computed_style();
+        assert_eq!(style.visual.color, crate::style::Color::RED);
+    }
+
+    #[test]
+    fn test_pilot_wait_for_selector() {
+        let mut app = TestApp::with_size(form(), 20, 3);
+        let mut pilot = Pilot::new(&mut app);
+
+        let status = pilot.wait_for_selector("#status", Duration::from_millis(50));
+        assert_eq!(status.text(), "Status: ok");
+    }
+
+    #[test]
+    #[should_panic(expected = "wait_for_selector('#nope') timed out")]
+    fn test_pilot_wait_for_selector_timeout() {
+        let mut app = TestApp::with_size(form(), 20, 3);
+        let mut pilot = Pilot::new(&mut app);
+
+        pilot.wait_for_selector("#nope", Duration::from_millis(20));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid selector")]
+    fn test_pilot_query_invalid_selector() {
+        let mut app = TestApp::with_size(form(), 20, 3);
+        let pilot = Pilot::new(&mut app);
+
+        pilot.query_all("##");
+    }
+
+    #[test]
+    fn test_pilot_paste_send_and_update() {
+        use crate::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+        use crate::widget::{Interactive, TextArea};
+
+        let mut app = TestApp::with_size(TextArea::new(), 20, 3).interactive();
+        let mut pilot = Pilot::new(&mut app);
+
+        pilot.update(|view| view.on_focus()).paste("ab\ncd");
+        assert_eq!(pilot.view().get_content(), "ab\ncd");
+
+        pilot
+            .send_key(KeyEvent::ctrl(Key::Char('a')))
+            .send_mouse(MouseEvent::new(0, 0, MouseEventKind::Up(MouseButton::Left)));
+        pilot.assert_contains("cd");
+    }
+
+    #[test]
+    fn test_pilot_paste_goes_to_paste_handler() {
+        use crate::widget::TextArea;
+
+        let mut app = TestApp::with_size(TextArea::new(), 20, 3)
+            .on_key(|_, _| panic!("paste sent as key presses"))
+            .on_paste(|text, view| {
+                view.insert_str(text);
+                true
+            });
+        let mut pilot = Pilot::new(&mut app);
+
+        pilot.paste("ab\ncd");
+        assert_eq!(pilot.view().get_content(), "ab\ncd");
+        pilot.assert_contains("cd");
+    }
 }
 
 // Re-exports
 pub use async_pilot::AsyncPilot;
 pub use core::Pilot;
+pub use query::{ElementHandle, ElementInfo};
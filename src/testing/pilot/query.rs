@@ -0,0 +1,257 @@
+//! CSS-selector queries and element actions for Pilot
+//!
+//! Queries run against the DOM built from the view on the last render, so
+//! tests can target widgets by `#id`, `.class`, type and pseudo-class instead
+//! of screen coordinates.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use revue::testing::*;
+//!
+//! let mut app = TestApp::new(my_view);
+//! let mut pilot = Pilot::new(&mut app);
+//!
+//! pilot.query("#save-button").unwrap().click();
+//! assert_eq!(pilot.query_all(".row").len(), 3);
+//!
+//! let status = pilot.wait_for_selector("#status", Duration::from_secs(1));
+//! assert_eq!(status.text(), "Saved");
+//! ```
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use super::core::{Pilot, WAIT_TICK};
+use crate::dom::{parse_selector, DomId, DomTree, Query};
+use crate::layout::Rect;
+use crate::style::Style;
+use crate::widget::View;
+
+/// Snapshot of a DOM node matched by a selector
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementInfo {
+    /// DOM node ID
+    pub id: DomId,
+    /// Widget type name (e.g., "Button")
+    pub widget_type: String,
+    /// Element ID, if set
+    pub element_id: Option<String>,
+    /// CSS classes, sorted
+    pub classes: Vec<String>,
+    /// Screen area from the last render (`None` if not rendered)
+    pub rect: Option<Rect>,
+    /// Text rendered inside the element's area
+    pub text: String,
+}
+
+impl ElementInfo {
+    /// Check if the element was rendered into a non-empty area
+    pub fn is_visible(&self) -> bool {
+        self.rect.is_some_and(|r| r.width > 0 && r.height > 0)
+    }
+}
+
+/// Handle to a DOM node for inspecting and interacting with it
+///
+/// Obtained from [`Pilot::query()`], [`Pilot::element()`] or
+/// [`Pilot::wait_for_selector()`]. Actions re-render the app, after which the
+/// handle keeps pointing at the same DOM node.
+pub struct ElementHandle<'p, 'a, V: View> {
+    pilot: &'p mut Pilot<'a, V>,
+    id: DomId,
+}
+
+impl<'p, 'a, V: View> ElementHandle<'p, 'a, V> {
+    /// Get the DOM node ID
+    pub fn dom_id(&self) -> DomId {
+        self.id
+    }
+
+    /// Get a snapshot of the element
+    pub fn info(&self) -> ElementInfo {
+        self.pilot
+            .element_info(self.id)
+            .unwrap_or_else(|| panic!("Element {:?} is no longer in the DOM", self.id))
+    }
+
+    /// Get the screen area the element was rendered into
+    pub fn rect(&self) -> Option<Rect> {
+        self.pilot.app.node_rect(self.id)
+    }
+
+    /// Get the text rendered inside the element's area
+    pub fn text(&self) -> String {
+        self.rect()
+            .map(|rect| self.pilot.app.region_text(rect))
+            .unwrap_or_default()
+    }
+
+    /// Check if the element was rendered into a non-empty area
+    pub fn is_visible(&self) -> bool {
+        self.rect().is_some_and(|r| r.width > 0 && r.height > 0)
+    }
+
+    /// Get the element's computed style (with inheritance)
+    ///
+    /// Uses the stylesheet set with [`TestApp::stylesheet()`](crate::testing::TestApp::stylesheet).
+    pub fn computed_style(&mut self) -> Style {
+        self.pilot
+            .app
+            .dom_mut()
+            .style_for_with_inheritance(self.id)
+            .unwrap_or_default()
+    }
+
+    /// Click the center of the element
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element is not visible.
+    pub fn click(&mut self) -> &mut Self {
+        let rect = self
+            .rect()
+            .filter(|r| r.width > 0 && r.height > 0)
+            .unwrap_or_else(|| panic!("Cannot click element {:?}: not visible", self.id));
+        self.pilot
+            .click(rect.x + rect.width / 2, rect.y + rect.height / 2);
+        self
+    }
+
+    /// Focus the element
+    ///
+    /// Moves DOM focus to the element (so `:focus` matches it) and passes it
+    /// to the app's [`on_focus`](crate::testing::TestApp::on_focus) handler,
+    /// which focuses the matching widget. Views are reached through
+    /// `View::children()`, which gives no mutable access, so without that
+    /// handler no widget is notified.
+    pub fn focus(&mut self) -> &mut Self {
+        self.pilot.app.send_focus(self.id);
+        self
+    }
+
+    /// Focus the element, then type text into it
+    ///
+    /// The keys go through the app's key handler, which delivers them to
+    /// the widget focused by [`focus()`](Self::focus).
+    pub fn type_text(&mut self, text: &str) -> &mut Self {
+        self.focus();
+        self.pilot.type_text(text);
+        self
+    }
+}
+
+impl<'a, V: View> Pilot<'a, V> {
+    // =========================================================================
+    // Selector Queries
+    // =========================================================================
+
+    /// Find the first element (in document order) matching a CSS selector
+    ///
+    /// # Panics
+    ///
+    /// Panics if the selector is invalid.
+    pub fn query(&mut self, selector: &str) -> Option<ElementHandle<'_, 'a, V>> {
+        let id = self.matching_ids(selector).into_iter().next()?;
+        Some(ElementHandle { pilot: self, id })
+    }
+
+    /// Find all elements matching a CSS selector, in document order
+    ///
+    /// # Panics
+    ///
+    /// Panics if the selector is invalid.
+    pub fn query_all(&self, selector: &str) -> Vec<ElementInfo> {
+        self.matching_ids(selector)
+            .into_iter()
+            .filter_map(|id| self.element_info(id))
+            .collect()
+    }
+
+    /// Get a handle to an element by DOM ID (e.g. from [`query_all()`][Self::query_all])
+    pub fn element(&mut self, id: DomId) -> Option<ElementHandle<'_, 'a, V>> {
+        self.app.dom().tree().get(id)?;
+        Some(ElementHandle { pilot: self, id })
+    }
+
+    /// Wait until an element matching a selector is visible
+    ///
+    /// Polls like [`wait_until()`][Self::wait_until]: each poll sleeps
+    /// for a short real-time tick and advances the virtual clock by the same
+    /// amount. `timeout` is measured in real time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no visible element matches within `timeout`.
+    pub fn wait_for_selector(
+        &mut self,
+        selector: &str,
+        timeout: Duration,
+    ) -> ElementHandle<'_, 'a, V> {
+        let start = Instant::now();
+        loop {
+            let found = self
+                .matching_ids(selector)
+                .into_iter()
+                .find(|&id| self.element_info(id).is_some_and(|e| e.is_visible()));
+            if let Some(id) = found {
+                return ElementHandle { pilot: self, id };
+            }
+            if start.elapsed() >= timeout {
+                panic!(
+                    "wait_for_selector('{}') timed out after {:?}",
+                    selector, timeout
+                );
+            }
+            std::thread::sleep(WAIT_TICK);
+            self.step_virtual(WAIT_TICK);
+        }
+    }
+
+    /// Check if any element matches a CSS selector
+    pub fn exists(&self, selector: &str) -> bool {
+        !self.matching_ids(selector).is_empty()
+    }
+
+    /// Get matching node IDs in document order
+    fn matching_ids(&self, selector: &str) -> Vec<DomId> {
+        if let Err(e) = parse_selector(selector) {
+            panic!("Invalid selector '{}': {}", selector, e);
+        }
+        let tree = self.app.dom().tree();
+        let matches: HashSet<DomId> = tree.query_all(selector).iter().map(|n| n.id).collect();
+        let mut ordered = Vec::with_capacity(matches.len());
+        if let Some(root) = tree.root_id() {
+            collect_in_order(tree, root, &matches, &mut ordered);
+        }
+        ordered
+    }
+
+    /// Build a snapshot of a node
+    fn element_info(&self, id: DomId) -> Option<ElementInfo> {
+        let node = self.app.dom().tree().get(id)?;
+        let rect = self.app.node_rect(id);
+        let mut classes: Vec<String> = node.classes().map(String::from).collect();
+        classes.sort();
+        Some(ElementInfo {
+            id,
+            widget_type: node.widget_type().to_string(),
+            element_id: node.element_id().map(String::from),
+            classes,
+            rect,
+            text: rect.map(|r| self.app.region_text(r)).unwrap_or_default(),
+        })
+    }
+}
+
+/// Depth-first walk collecting matched nodes in document order
+fn collect_in_order(tree: &DomTree, id: DomId, matches: &HashSet<DomId>, out: &mut Vec<DomId>) {
+    if matches.contains(&id) {
+        out.push(id);
+    }
+    if let Some(node) = tree.get(id) {
+        for &child in &node.children {
+            collect_in_order(tree, child, matches, out);
+        }
+    }
+}
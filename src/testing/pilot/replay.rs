@@ -0,0 +1,109 @@
+//! Replaying recorded sessions through Pilot
+//!
+//! A [`Recording`] captured from a real run (see
+//! [`AppBuilder::record()`](crate::core::app::AppBuilder::record)) is fed to the
+//! `TestApp` event by event, with the virtual clock advanced to each entry's
+//! timestamp. At every screen checkpoint the test app's screen must hash to
+//! the recorded value, turning a bug report into a regression test.
+//!
+//! The `TestApp` handlers should mirror the real app's event handler, since
+//! key, mouse and tick events are routed to them the same way.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use revue::core::app::Recording;
+//! use revue::testing::*;
+//!
+//! let recording = Recording::load("tests/recordings/issue_123.rec").unwrap();
+//! let mut app = TestApp::new(MyView::new()).on_key(handle_key);
+//! let mut pilot = Pilot::new(&mut app);
+//!
+//! pilot.replay(&recording);
+//! ```
+
+use std::path::Path;
+use std::time::Duration;
+
+use super::core::Pilot;
+use crate::core::app::{screen_hash, RecordedKind, Recording};
+use crate::event::Event;
+use crate::widget::View;
+
+impl<'a, V: View> Pilot<'a, V> {
+    // =========================================================================
+    // Replay
+    // =========================================================================
+
+    /// Replay a recorded session, asserting every screen checkpoint
+    ///
+    /// Resizes the app to the recorded terminal size first. Pasted text goes
+    /// through [`paste()`](Self::paste), and focus events only re-render.
+    ///
+    /// # Panics
+    ///
+    /// Panics at the first screen checkpoint whose hash does not match.
+    pub fn replay(&mut self, recording: &Recording) -> &mut Self {
+        let (width, height) = recording.size;
+        self.resize(width, height);
+
+        let mut now = Duration::ZERO;
+        let mut last_tick = Duration::ZERO;
+
+        for (index, entry) in recording.entries.iter().enumerate() {
+            if entry.at > now {
+                self.clock().advance(entry.at - now);
+                now = entry.at;
+            }
+
+            match &entry.kind {
+                RecordedKind::Event(event) => {
+                    if let Event::Tick = event {
+                        self.app.send_tick(entry.at.saturating_sub(last_tick));
+                        last_tick = entry.at;
+                    } else {
+                        self.replay_event(event);
+                    }
+                }
+                RecordedKind::Screen(expected) => {
+                    let actual = screen_hash(self.app.buffer());
+                    if actual != *expected {
+                        panic!(
+                            "Replay diverged at entry {} ({}ms): expected screen {:016x}, got {:016x}\n\nScreen:\n{}",
+                            index,
+                            entry.at.as_millis(),
+                            expected,
+                            actual,
+                            self.app.screen_text()
+                        );
+                    }
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Load a recording from a file and replay it
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file cannot be loaded or the replay diverges.
+    pub fn replay_file(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        let path = path.as_ref();
+        let recording = Recording::load(path)
+            .unwrap_or_else(|e| panic!("Failed to load recording {:?}: {}", path, e));
+        self.replay(&recording)
+    }
+
+    /// Dispatch a recorded input event to the test app
+    fn replay_event(&mut self, event: &Event) {
+        match event {
+            Event::Key(key) => self.app.send_key(key.clone()),
+            Event::Mouse(mouse) => self.app.send_mouse(mouse.clone()),
+            Event::Resize(w, h) => self.resize(*w, *h),
+            Event::Paste(text) => self.app.send_paste(text),
+            Event::Tick | Event::FocusGained | Event::FocusLost => self.app.render(),
+        }
+    }
+}
@@ -0,0 +1,488 @@
+//! End-to-end testing of compiled binaries under a pseudo-terminal
+//!
+//! [`PtyPilot`] spawns an executable attached to a real PTY of a given size,
+//! feeds it keystrokes, and runs its output through a VT emulator into a
+//! screen grid. Unlike [`Pilot`](super::Pilot), the program runs out of
+//! process, so terminal setup and restore, signal handling and panics are
+//! exercised exactly as in a user's terminal.
+//!
+//! The program runs concurrently with the test, so assertions that expect
+//! something on screen wait for it (up to `TestConfig::timeout_ms`), and
+//! negative assertions and snapshots first wait for output to settle.
+//!
+//! Requires the `pty` feature and a Unix platform.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use revue::testing::PtyPilot;
+//! use revue::event::Key;
+//! use std::process::Command;
+//!
+//! #[test]
+//! fn test_counter_binary() {
+//!     let mut pilot = PtyPilot::spawn(Command::new(env!("CARGO_BIN_EXE_counter"))).unwrap();
+//!
+//!     pilot.assert_contains("Count: 0");
+//!     pilot.press(Key::Up).assert_contains("Count: 1");
+//!     pilot.snapshot("counter_after_up");
+//!
+//!     pilot.press_ctrl_c();
+//!     assert!(pilot.wait_for_exit().success());
+//!     pilot.assert_restored();
+//! }
+//! ```
+
+use std::io;
+use std::process::{Command, ExitStatus};
+use std::time::{Duration, Instant};
+
+use super::TestConfig;
+use crate::event::{Key, KeyEvent};
+use crate::render::Buffer;
+use crate::utils::pty::PtyProcess;
+use crate::utils::vt::{encode_key, VtScreen};
+
+/// How long output must be idle before the screen counts as settled
+const SETTLE_QUIET: Duration = Duration::from_millis(50);
+
+/// Poll interval while waiting for output
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Pilot for a program running under a pseudo-terminal
+pub struct PtyPilot {
+    process: PtyProcess,
+    screen: VtScreen,
+    raw: Vec<u8>,
+    config: TestConfig,
+    status: Option<ExitStatus>,
+}
+
+impl PtyPilot {
+    /// Spawn a command under an 80x24 pseudo-terminal
+    pub fn spawn(command: Command) -> io::Result<Self> {
+        Self::spawn_with_config(command, TestConfig::default())
+    }
+
+    /// Spawn a command with a custom terminal size and timeout
+    pub fn spawn_with_config(command: Command, config: TestConfig) -> io::Result<Self> {
+        let process = PtyProcess::spawn(command, config.width, config.height)?;
+        Ok(Self {
+            process,
+            screen: VtScreen::new(config.width, config.height),
+            raw: Vec::new(),
+            config,
+            status: None,
+        })
+    }
+
+    // =========================================================================
+    // Input
+    // =========================================================================
+
+    /// Press a key
+    pub fn press(&mut self, key: Key) -> &mut Self {
+        self.press_key(KeyEvent::new(key))
+    }
+
+    /// Press a key with modifiers
+    pub fn press_key(&mut self, event: KeyEvent) -> &mut Self {
+        let bytes = encode_key(&event, self.screen.application_cursor());
+        self.send_bytes(&bytes)
+    }
+
+    /// Press a key with ctrl modifier
+    pub fn press_ctrl(&mut self, key: Key) -> &mut Self {
+        self.press_key(KeyEvent::ctrl(key))
+    }
+
+    /// Press a key with alt modifier
+    pub fn press_alt(&mut self, key: Key) -> &mut Self {
+        self.press_key(KeyEvent::alt(key))
+    }
+
+    /// Press Enter
+    pub fn press_enter(&mut self) -> &mut Self {
+        self.press(Key::Enter)
+    }
+
+    /// Press Escape
+    pub fn press_escape(&mut self) -> &mut Self {
+        self.press(Key::Escape)
+    }
+
+    /// Press Tab
+    pub fn press_tab(&mut self) -> &mut Self {
+        self.press(Key::Tab)
+    }
+
+    /// Press Ctrl+C
+    ///
+    /// In raw mode this arrives as a key; otherwise the terminal turns it
+    /// into `SIGINT`.
+    pub fn press_ctrl_c(&mut self) -> &mut Self {
+        self.send_bytes(&[0x03])
+    }
+
+    /// Type text as individual key presses
+    pub fn type_text(&mut self, text: &str) -> &mut Self {
+        for c in text.chars() {
+            self.press(Key::Char(c));
+        }
+        self
+    }
+
+    /// Paste text, bracketed if the program enabled bracketed paste
+    pub fn paste(&mut self, text: &str) -> &mut Self {
+        if self.screen.bracketed_paste() {
+            let mut bytes = b"\x1b[200~".to_vec();
+            bytes.extend_from_slice(text.as_bytes());
+            bytes.extend_from_slice(b"\x1b[201~");
+            self.send_bytes(&bytes)
+        } else {
+            self.send_bytes(text.as_bytes())
+        }
+    }
+
+    /// Write raw bytes to the terminal input
+    ///
+    /// # Panics
+    ///
+    /// Panics if the program has closed its terminal.
+    pub fn send_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.update();
+        if let Err(e) = self.process.write(bytes) {
+            panic!("Failed to write to terminal: {}", e);
+        }
+        self
+    }
+
+    /// Resize the terminal (the program receives `SIGWINCH`)
+    pub fn resize(&mut self, width: u16, height: u16) -> &mut Self {
+        self.update();
+        self.config.width = width;
+        self.config.height = height;
+        self.screen.resize(width, height);
+        if let Err(e) = self.process.resize(width, height) {
+            panic!("Failed to resize terminal: {}", e);
+        }
+        self
+    }
+
+    /// Send a signal (e.g. `libc::SIGTERM`) to the program
+    pub fn signal(&mut self, signal: i32) -> &mut Self {
+        if let Err(e) = self.process.signal(signal) {
+            panic!("Failed to send signal {}: {}", signal, e);
+        }
+        self
+    }
+
+    // =========================================================================
+    // Output
+    // =========================================================================
+
+    /// Process any output received so far
+    ///
+    /// Returns true if there was new output.
+    pub fn update(&mut self) -> bool {
+        let bytes = self.process.try_read();
+        self.consume(&bytes)
+    }
+
+    fn consume(&mut self, bytes: &[u8]) -> bool {
+        if bytes.is_empty() {
+            return false;
+        }
+        self.raw.extend_from_slice(bytes);
+        self.screen.feed(bytes);
+        let responses = self.screen.take_responses();
+        if !responses.is_empty() {
+            // Replies to terminal queries; the program may already be gone
+            let _ = self.process.write(&responses);
+        }
+        true
+    }
+
+    /// Wait for output to arrive, up to `timeout`
+    fn pump(&mut self, timeout: Duration) -> bool {
+        let bytes = self.process.read_timeout(timeout);
+        self.consume(&bytes)
+    }
+
+    /// Emulated terminal state
+    pub fn vt(&self) -> &VtScreen {
+        &self.screen
+    }
+
+    /// Screen grid
+    pub fn buffer(&self) -> &Buffer {
+        self.screen.buffer()
+    }
+
+    /// Everything the program wrote to the terminal
+    pub fn raw_output(&self) -> &[u8] {
+        &self.raw
+    }
+
+    /// Get screen size
+    pub fn size(&self) -> (u16, u16) {
+        (self.config.width, self.config.height)
+    }
+
+    // =========================================================================
+    // Waiting
+    // =========================================================================
+
+    /// Wait until no output has arrived for a short quiet period
+    ///
+    /// Gives up (without panicking) after the configured timeout, so
+    /// programs that redraw continuously don't hang the test.
+    pub fn settle(&mut self) -> &mut Self {
+        let start = Instant::now();
+        let timeout = self.timeout();
+        while start.elapsed() < timeout && !self.process.is_eof() {
+            if !self.pump(SETTLE_QUIET) {
+                break;
+            }
+        }
+        self
+    }
+
+    /// Wait for a duration while processing output
+    pub fn wait(&mut self, duration: Duration) -> &mut Self {
+        let deadline = Instant::now() + duration;
+        while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+            if self.process.is_eof() {
+                std::thread::sleep(left);
+                break;
+            }
+            self.pump(left.min(POLL_INTERVAL));
+        }
+        self
+    }
+
+    /// Wait for milliseconds
+    pub fn wait_ms(&mut self, ms: u64) -> &mut Self {
+        self.wait(Duration::from_millis(ms))
+    }
+
+    /// Wait for a condition on the screen to be true
+    ///
+    /// # Panics
+    ///
+    /// Panics if the condition is not met within the configured timeout.
+    pub fn wait_until<F>(&mut self, condition: F) -> &mut Self
+    where
+        F: Fn(&Buffer) -> bool,
+    {
+        if !self.poll_until(|vt| condition(vt.buffer())) {
+            panic!(
+                "wait_until timed out after {:?}\n\nScreen:\n{}",
+                self.timeout(),
+                self.screen()
+            );
+        }
+        self
+    }
+
+    /// Wait until the screen contains text
+    ///
+    /// # Panics
+    ///
+    /// Panics if the text does not appear within the configured timeout.
+    pub fn wait_for_text(&mut self, text: &str) -> &mut Self {
+        if !self.poll_until(|vt| vt.text().contains(text)) {
+            panic!(
+                "Timed out after {:?} waiting for '{}'\n\nScreen:\n{}",
+                self.timeout(),
+                text,
+                self.screen()
+            );
+        }
+        self
+    }
+
+    /// Poll output until a condition holds; false on timeout or EOF
+    fn poll_until(&mut self, condition: impl Fn(&VtScreen) -> bool) -> bool {
+        let start = Instant::now();
+        self.update();
+        loop {
+            if condition(&self.screen) {
+                return true;
+            }
+            if start.elapsed() >= self.timeout() || self.process.is_eof() {
+                return false;
+            }
+            self.pump(POLL_INTERVAL);
+        }
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_millis(self.config.timeout_ms)
+    }
+
+    // =========================================================================
+    // Process
+    // =========================================================================
+
+    /// Check if the program is still running
+    pub fn is_running(&mut self) -> bool {
+        self.exit_status().is_none()
+    }
+
+    /// Exit status, if the program has exited
+    pub fn exit_status(&mut self) -> Option<ExitStatus> {
+        if self.status.is_none() {
+            self.status = self.process.try_wait().ok().flatten();
+        }
+        self.status
+    }
+
+    /// Wait for the program to exit, then process its remaining output
+    ///
+    /// # Panics
+    ///
+    /// Panics if the program does not exit within the configured timeout.
+    pub fn wait_for_exit(&mut self) -> ExitStatus {
+        let start = Instant::now();
+        while self.exit_status().is_none() {
+            if start.elapsed() >= self.timeout() {
+                panic!(
+                    "Program did not exit within {:?}\n\nScreen:\n{}",
+                    self.timeout(),
+                    self.screen()
+                );
+            }
+            self.pump(POLL_INTERVAL);
+        }
+        self.settle();
+        self.status.expect("exit status was just observed")
+    }
+
+    /// Kill the program
+    pub fn kill(&mut self) -> &mut Self {
+        if let Ok(status) = self.process.kill() {
+            self.status = Some(status);
+        }
+        self
+    }
+
+    // =========================================================================
+    // Assertions
+    // =========================================================================
+
+    /// Get screen as text
+    pub fn screen(&self) -> String {
+        self.screen.text()
+    }
+
+    /// Check if screen contains text (without waiting)
+    pub fn screen_contains(&self, text: &str) -> bool {
+        self.screen.text().contains(text)
+    }
+
+    /// Get a specific line
+    pub fn line(&self, row: u16) -> String {
+        self.screen.line(row)
+    }
+
+    /// Find position of text on screen
+    pub fn find_text(&self, text: &str) -> Option<(u16, u16)> {
+        (0..self.screen.height()).find_map(|y| {
+            let line = self.screen.line(y);
+            let byte = line.find(text)?;
+            let x = crate::utils::unicode::display_width(&line[..byte]);
+            Some((x as u16, y))
+        })
+    }
+
+    /// Assert screen contains text, waiting for it to appear
+    pub fn assert_contains(&mut self, text: &str) -> &mut Self {
+        if !self.poll_until(|vt| vt.text().contains(text)) {
+            panic!(
+                "Expected screen to contain '{}'\n\nScreen:\n{}",
+                text,
+                self.screen()
+            );
+        }
+        self
+    }
+
+    /// Assert screen does not contain text once output settles
+    pub fn assert_not_contains(&mut self, text: &str) -> &mut Self {
+        self.settle();
+        if self.screen_contains(text) {
+            panic!(
+                "Expected screen NOT to contain '{}'\n\nScreen:\n{}",
+                text,
+                self.screen()
+            );
+        }
+        self
+    }
+
+    /// Assert a line contains text, waiting for it to appear
+    pub fn assert_line_contains(&mut self, row: u16, text: &str) -> &mut Self {
+        if !self.poll_until(|vt| vt.line(row).contains(text)) {
+            panic!(
+                "Expected line {} to contain '{}'\n\nLine: {}",
+                row,
+                text,
+                self.line(row)
+            );
+        }
+        self
+    }
+
+    /// Assert the settled screen matches exactly
+    pub fn assert_screen(&mut self, expected: &str) -> &mut Self {
+        self.settle();
+        let actual = self.screen();
+        if actual.trim() != expected.trim() {
+            panic!(
+                "Screen mismatch!\n\nExpected:\n{}\n\nActual:\n{}",
+                expected, actual
+            );
+        }
+        self
+    }
+
+    /// Assert the program left the terminal in a usable state
+    ///
+    /// Checks that the alternate screen was exited, the cursor is visible,
+    /// and mouse tracking and bracketed paste are off.
+    pub fn assert_restored(&mut self) -> &mut Self {
+        self.settle();
+        let vt = &self.screen;
+        let problems: Vec<&str> = [
+            (vt.is_alternate_screen(), "still on the alternate screen"),
+            (!vt.cursor_visible(), "cursor hidden"),
+            (vt.mouse_tracking(), "mouse tracking enabled"),
+            (vt.bracketed_paste(), "bracketed paste enabled"),
+        ]
+        .into_iter()
+        .filter_map(|(bad, what)| bad.then_some(what))
+        .collect();
+        if !problems.is_empty() {
+            panic!("Terminal not restored: {}", problems.join(", "));
+        }
+        self
+    }
+
+    /// Compare the settled screen against a snapshot
+    pub fn snapshot(&mut self, name: &str) -> &mut Self {
+        self.settle();
+        let manager = super::snapshot::SnapshotManager::new();
+        manager.assert_buffer_snapshot(name, self.screen.buffer());
+        self
+    }
+}
+
+impl std::fmt::Debug for PtyPilot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PtyPilot")
+            .field("process", &self.process)
+            .field("size", &(self.config.width, self.config.height))
+            .finish()
+    }
+}
@@ -1,20 +1,34 @@
 //! Test app wrapper for testing views without a terminal
 
 use super::TestConfig;
-use crate::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use crate::dom::{DomId, DomNode, DomRenderer, DomTree};
+use crate::event::{Key, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use crate::layout::Rect;
 use crate::render::Buffer;
-use crate::widget::{RenderContext, View};
+use crate::style::StyleSheet;
+use crate::widget::traits::{trace_render, view_key, RenderTrace};
+use crate::widget::{Interactive, RenderContext, View};
+use std::collections::HashMap;
+use std::time::Duration;
 
 /// Type alias for key event handler
 type KeyHandler<V> = Box<dyn FnMut(&KeyEvent, &mut V) -> bool>;
 
-/// Type alias for mouse event handler
-type MouseHandler<V> = Box<dyn FnMut(&MouseEvent, &mut V) -> bool>;
+/// Type alias for mouse event handler (also receives the screen area)
+type MouseHandler<V> = Box<dyn FnMut(&MouseEvent, Rect, &mut V) -> bool>;
 
 /// Type alias for scroll event handler
 type ScrollHandler<V> = Box<dyn FnMut(u16, u16, i16, &mut V) -> bool>;
 
+/// Type alias for tick handler
+type TickHandler<V> = Box<dyn FnMut(Duration, &mut V) -> bool>;
+
+/// Type alias for paste handler
+type PasteHandler<V> = Box<dyn FnMut(&str, &mut V) -> bool>;
+
+/// Type alias for focus handler
+type FocusHandler<V> = Box<dyn FnMut(&DomNode, &mut V) -> bool>;
+
 /// A test application that can run views without a real terminal
 pub struct TestApp<V: View> {
     /// The view being tested
@@ -30,8 +44,18 @@ pub struct TestApp<V: View> {
     mouse_handler: Option<MouseHandler<V>>,
     /// Scroll event handler
     scroll_handler: Option<ScrollHandler<V>>,
+    /// Tick handler
+    tick_handler: Option<TickHandler<V>>,
+    /// Paste handler
+    paste_handler: Option<PasteHandler<V>>,
+    /// Focus handler
+    focus_handler: Option<FocusHandler<V>>,
     /// Whether app is running
     running: bool,
+    /// DOM mirror of the view tree, rebuilt on every render
+    dom: DomRenderer,
+    /// Screen area of each DOM node from the last render
+    rects: HashMap<DomId, Rect>,
 }
 
 impl<V: View> TestApp<V> {
@@ -50,7 +74,12 @@ impl<V: View> TestApp<V> {
             key_handler: None,
             mouse_handler: None,
             scroll_handler: None,
+            tick_handler: None,
+            paste_handler: None,
+            focus_handler: None,
             running: true,
+            dom: DomRenderer::new(),
+            rects: HashMap::new(),
         };
         app.render();
         app
@@ -78,7 +107,27 @@ impl<V: View> TestApp<V> {
     where
         F: FnMut(&MouseEvent, &mut V) -> bool + 'static,
     {
-        self.mouse_handler = Some(Box::new(handler));
+        let mut handler = handler;
+        self.mouse_handler = Some(Box::new(move |event, _, view| handler(event, view)));
+        self
+    }
+
+    /// Route key and mouse events to the view's [`Interactive`] handlers
+    ///
+    /// Mouse events receive the whole screen as the widget area. The screen
+    /// re-renders after every event, handled or not.
+    pub fn interactive(mut self) -> Self
+    where
+        V: Interactive + 'static,
+    {
+        self.key_handler = Some(Box::new(|event, view: &mut V| {
+            view.handle_key(event);
+            true
+        }));
+        self.mouse_handler = Some(Box::new(|event, area, view: &mut V| {
+            view.handle_mouse(event, area);
+            true
+        }));
         self
     }
 
@@ -94,6 +143,53 @@ impl<V: View> TestApp<V> {
         self
     }
 
+    /// Set tick handler
+    ///
+    /// The handler receives the time since the previous tick and a mutable
+    /// reference to the view, like `Event::Tick` in a running app. Return
+    /// true to trigger a re-render.
+    pub fn on_tick<F>(mut self, handler: F) -> Self
+    where
+        F: FnMut(Duration, &mut V) -> bool + 'static,
+    {
+        self.tick_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Set paste handler
+    ///
+    /// The handler receives the whole pasted text, like `Event::Paste` in a
+    /// running app. Return true to trigger a re-render.
+    pub fn on_paste<F>(mut self, handler: F) -> Self
+    where
+        F: FnMut(&str, &mut V) -> bool + 'static,
+    {
+        self.paste_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Set focus handler
+    ///
+    /// Called by [`send_focus`](Self::send_focus) with the DOM node being
+    /// focused, so the view can move focus to the matching widget (and send
+    /// it later key presses). Return true to trigger a re-render.
+    pub fn on_focus<F>(mut self, handler: F) -> Self
+    where
+        F: FnMut(&DomNode, &mut V) -> bool + 'static,
+    {
+        self.focus_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Set the stylesheet used to compute node styles
+    ///
+    /// Only affects [`dom()`](Self::dom) styles (e.g. for
+    /// `Pilot` element queries); rendering stays unstyled.
+    pub fn stylesheet(mut self, stylesheet: StyleSheet) -> Self {
+        self.dom.set_stylesheet(stylesheet);
+        self
+    }
+
     /// Get reference to the view
     pub fn view(&self) -> &V {
         &self.view
@@ -109,12 +205,45 @@ impl<V: View> TestApp<V> {
         &self.buffer
     }
 
+    /// Get the DOM built from the view
+    pub fn dom(&self) -> &DomRenderer {
+        &self.dom
+    }
+
+    /// Get mutable access to the DOM (e.g. to set focus or compute styles)
+    pub fn dom_mut(&mut self) -> &mut DomRenderer {
+        &mut self.dom
+    }
+
+    /// Get the screen area a DOM node was rendered into
+    ///
+    /// Returns `None` if the node was not rendered in the last frame.
+    pub fn node_rect(&self, id: DomId) -> Option<Rect> {
+        self.rects.get(&id).copied()
+    }
+
     /// Render the view to buffer
     pub fn render(&mut self) {
         self.buffer.clear();
         let area = Rect::new(0, 0, self.width, self.height);
-        let mut ctx = RenderContext::new(&mut self.buffer, area);
-        self.view.render(&mut ctx);
+        self.dom.build(&self.view);
+
+        let ((), trace) = trace_render(|| {
+            let mut ctx = RenderContext::new(&mut self.buffer, area);
+            self.view.render(&mut ctx);
+        });
+
+        self.rects.clear();
+        if let Some(root_id) = self.dom.tree().root_id() {
+            self.rects.insert(root_id, area);
+            collect_rects(
+                self.dom.tree(),
+                root_id,
+                &self.view,
+                &trace,
+                &mut self.rects,
+            );
+        }
     }
 
     /// Send a key event
@@ -138,8 +267,9 @@ impl<V: View> TestApp<V> {
 
     /// Send a mouse event
     pub fn send_mouse(&mut self, event: MouseEvent) {
+        let area = Rect::new(0, 0, self.width, self.height);
         if let Some(ref mut handler) = self.mouse_handler {
-            let should_render = handler(&event, &mut self.view);
+            let should_render = handler(&event, area, &mut self.view);
             if should_render {
                 self.render();
             }
@@ -164,6 +294,56 @@ impl<V: View> TestApp<V> {
         }
     }
 
+    /// Send a tick with the time elapsed since the previous tick
+    pub fn send_tick(&mut self, delta: Duration) {
+        if let Some(ref mut handler) = self.tick_handler {
+            let should_render = handler(delta, &mut self.view);
+            if should_render {
+                self.render();
+            }
+        } else {
+            // Default: always re-render (views may read the clock while rendering)
+            self.render();
+        }
+    }
+
+    /// Send a bracketed paste
+    ///
+    /// Goes to the [`on_paste`](Self::on_paste) handler in one piece. Without
+    /// one, the text is sent as key presses instead (newlines as Enter).
+    pub fn send_paste(&mut self, text: &str) {
+        if let Some(ref mut handler) = self.paste_handler {
+            let should_render = handler(text, &mut self.view);
+            if should_render {
+                self.render();
+            }
+        } else {
+            for c in text.chars() {
+                let key = if c == '\n' { Key::Enter } else { Key::Char(c) };
+                self.send_key(KeyEvent::new(key));
+            }
+        }
+    }
+
+    /// Focus a DOM node
+    ///
+    /// Moves DOM focus (what `:focus` matches) to the node and passes it to
+    /// the [`on_focus`](Self::on_focus) handler. Without a handler only the
+    /// DOM focus changes.
+    pub fn send_focus(&mut self, id: DomId) {
+        self.dom.set_focus_node(Some(id));
+        let Some(handler) = self.focus_handler.as_mut() else {
+            return;
+        };
+        let should_render = match self.dom.tree().get(id) {
+            Some(node) => handler(node, &mut self.view),
+            None => false,
+        };
+        if should_render {
+            self.render();
+        }
+    }
+
     /// Send scroll up event
     pub fn scroll_up(&mut self, x: u16, y: u16) {
         let event = MouseEvent::new(x, y, MouseEventKind::ScrollUp);
@@ -231,6 +411,18 @@ impl<V: View> TestApp<V> {
         self.buffer.get(x, y).map(|c| c.symbol)
     }
 
+    /// Get the text inside an area, one line per row with trailing spaces trimmed
+    pub fn region_text(&self, area: Rect) -> String {
+        let mut lines = Vec::new();
+        for y in area.y..area.bottom().min(self.height) {
+            let line: String = (area.x..area.right().min(self.width))
+                .map(|x| self.buffer.get(x, y).map(|c| c.symbol).unwrap_or(' '))
+                .collect();
+            lines.push(line.trim_end().to_string());
+        }
+        lines.join("\n")
+    }
+
     /// Find text on screen, returns position of first occurrence
     pub fn find_text(&self, text: &str) -> Option<(u16, u16)> {
         for y in 0..self.height {
@@ -258,6 +450,28 @@ impl<V: View> TestApp<V> {
     }
 }
 
+/// Map rendered child areas from a trace onto the matching DOM nodes
+///
+/// DOM children are built from `View::children()` in order, so the two trees
+/// can be walked side by side.
+fn collect_rects(
+    tree: &DomTree,
+    node_id: DomId,
+    view: &dyn View,
+    trace: &RenderTrace,
+    rects: &mut HashMap<DomId, Rect>,
+) {
+    let Some(node) = tree.get(node_id) else {
+        return;
+    };
+    for (&child_id, child) in node.children.iter().zip(view.children()) {
+        if let Some(&area) = trace.get(&view_key(child.as_ref())) {
+            rects.insert(child_id, area);
+        }
+        collect_rects(tree, child_id, child.as_ref(), trace, rects);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -471,4 +685,18 @@ mod tests {
         // Handler was called
         assert!(app.is_running());
     }
+
+    #[test]
+    fn test_app_interactive() {
+        let checkbox = crate::widget::Checkbox::new("Accept");
+        let mut app = TestApp::new(checkbox).interactive();
+        app.send_key(KeyEvent::new(crate::event::Key::Char(' ')));
+        assert!(app.view().is_checked());
+        app.send_mouse(MouseEvent::new(
+            1,
+            0,
+            MouseEventKind::Down(MouseButton::Left),
+        ));
+        assert!(app.view().is_checked());
+    }
 }
@@ -0,0 +1,175 @@
+//! Failure images for visual regression tests
+//!
+//! When a visual test fails, the expected and actual captures and a diff
+//! panel highlighting the changed cells are laid out side by side and
+//! exported as an image into the CI artifacts directory.
+
+use crate::render::{export, Buffer, Cell, Modifier};
+use crate::style::Color;
+use crate::testing::visual::types::{VisualCapture, VisualDiff};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Background of changed cells in the diff panel
+const CHANGED_BG: Color = Color::rgb(170, 30, 30);
+/// Color of panel labels and separators
+const CHROME: Color = Color::rgb(120, 120, 120);
+
+/// Compose the expected, actual and diff panels into one buffer
+///
+/// The first row holds the panel labels. Unchanged cells in the diff panel
+/// are dimmed; changed cells show the actual symbol on a red background.
+/// With a size mismatch, cells covered by only one capture count as changed.
+pub fn side_by_side(expected: &VisualCapture, actual: &VisualCapture, diff: &VisualDiff) -> Buffer {
+    const LABELS: [&str; 3] = ["Expected", "Actual", "Diff"];
+    let diff_width = expected.width.max(actual.width);
+    let diff_height = expected.height.max(actual.height);
+    let widths = [expected.width, actual.width, diff_width]
+        .iter()
+        .zip(LABELS)
+        .map(|(w, label)| (*w).max(label.len() as u16))
+        .collect::<Vec<_>>();
+
+    let total_width = widths.iter().sum::<u16>() + 2;
+    let mut buffer = Buffer::new(total_width, diff_height + 1);
+
+    let mut left = 0;
+    for (i, (label, width)) in LABELS.iter().zip(&widths).enumerate() {
+        buffer.put_str_styled(left, 0, label, Some(CHROME), None);
+        if i > 0 {
+            for y in 0..=diff_height {
+                buffer.set(left - 1, y, Cell::new('│').fg(CHROME));
+            }
+        }
+        left += width + 1;
+    }
+
+    let actual_buffer = actual.to_buffer();
+    blit(&mut buffer, &expected.to_buffer(), expected, 0);
+    blit(&mut buffer, &actual_buffer, actual, widths[0] + 1);
+
+    // A size mismatch carries no per-cell differences, so compare the
+    // overlapping area directly
+    let listed: HashSet<(u16, u16)> = diff.differences.iter().map(|d| (d.x, d.y)).collect();
+    let changed = |x: u16, y: u16| match (expected.get(x, y), actual.get(x, y)) {
+        (Some(e), Some(a)) => {
+            listed.contains(&(x, y))
+                || (diff.size_mismatch.is_some()
+                    && !a.matches(e, 0, actual.include_styles, actual.include_colors))
+        }
+        _ => true,
+    };
+
+    let diff_left = widths[0] + widths[1] + 2;
+    for y in 0..diff_height {
+        for x in 0..diff_width {
+            let mut cell = match actual.get(x, y) {
+                Some(_) => actual_buffer.get(x, y).copied().unwrap_or_default(),
+                None => Cell::new(' '),
+            };
+            if changed(x, y) {
+                cell.fg = Some(Color::WHITE);
+                cell.bg = Some(CHANGED_BG);
+                cell.modifier = Modifier::BOLD;
+            } else {
+                cell.modifier |= Modifier::DIM;
+            }
+            buffer.set(diff_left + x, y + 1, cell);
+        }
+    }
+    buffer
+}
+
+/// Copy a panel below the label row, clipped to the capture's size
+fn blit(target: &mut Buffer, panel: &Buffer, capture: &VisualCapture, left: u16) {
+    for y in 0..capture.height {
+        for x in 0..capture.width {
+            if let Some(cell) = panel.get(x, y) {
+                target.set(left + x, y + 1, *cell);
+            }
+        }
+    }
+}
+
+/// Write the side-by-side failure image to `dir/<stem>.<ext>`
+///
+/// Produces a PNG with the `image` feature and an SVG otherwise. Returns
+/// the path of the written image.
+pub fn write_failure_image(
+    dir: &Path,
+    stem: &str,
+    expected: &VisualCapture,
+    actual: &VisualCapture,
+    diff: &VisualDiff,
+) -> Result<PathBuf, export::ExportError> {
+    let extension = if cfg!(feature = "image") {
+        "png"
+    } else {
+        "svg"
+    };
+    let path = dir.join(format!("{}.{}", stem, extension));
+    let composite = side_by_side(expected, actual, diff);
+    let options = export::ExportOptions::default().title(stem);
+    export::save(&composite, &path, &options)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capture(text: &str) -> VisualCapture {
+        let mut buffer = Buffer::new(text.len() as u16, 1);
+        buffer.put_str(0, 0, text);
+        VisualCapture::from_buffer(&buffer, true, true)
+    }
+
+    #[test]
+    fn test_side_by_side_layout() {
+        let expected = capture("hello");
+        let actual = capture("hallo");
+        let diff = actual.diff(&expected, 0);
+        let composite = side_by_side(&expected, &actual, &diff);
+
+        // Panels are at least as wide as their labels
+        assert_eq!(composite.width(), 8 + 1 + 6 + 1 + 5);
+        assert_eq!(composite.height(), 2);
+        assert_eq!(composite.get(0, 0).unwrap().symbol, 'E');
+        assert_eq!(composite.get(8, 1).unwrap().symbol, '│');
+        assert_eq!(composite.get(9, 1).unwrap().symbol, 'h');
+
+        let changed = composite.get(17, 1).unwrap();
+        assert_eq!(changed.symbol, 'a');
+        assert_eq!(changed.bg, Some(CHANGED_BG));
+        let unchanged = composite.get(16, 1).unwrap();
+        assert!(unchanged.modifier.contains(Modifier::DIM));
+    }
+
+    #[test]
+    fn test_side_by_side_size_mismatch() {
+        let expected = capture("ab");
+        let actual = capture("xbcd");
+        let diff = actual.diff(&expected, 0);
+        let composite = side_by_side(&expected, &actual, &diff);
+        let diff_left = 8 + 1 + 6 + 1;
+
+        let flagged = |x: u16| composite.get(diff_left + x, 1).unwrap().bg == Some(CHANGED_BG);
+        assert!(flagged(0));
+        assert!(!flagged(1));
+        assert!(flagged(3));
+        assert_eq!(composite.get(diff_left + 3, 1).unwrap().symbol, 'd');
+    }
+
+    #[test]
+    fn test_write_failure_image() {
+        let dir =
+            std::env::temp_dir().join(format!("revue_visual_artifacts_{}", std::process::id()));
+        let expected = capture("one");
+        let actual = capture("two");
+        let diff = actual.diff(&expected, 0);
+        let path = write_failure_image(&dir, "case", &expected, &actual, &diff).unwrap();
+        assert!(path.exists());
+        assert_eq!(path.parent(), Some(dir.as_path()));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -1,6 +1,6 @@
 //! Visual capture implementation for buffers
 
-use crate::render::{Buffer, Modifier};
+use crate::render::{Buffer, Cell, Modifier};
 use crate::style::Color;
 use crate::testing::visual::types::{CapturedCell, VisualCapture};
 use crate::testing::visual::{comparison::color_to_rgb, helpers::parse_hex_color};
@@ -53,6 +53,27 @@ impl VisualCapture {
         }
     }
 
+    /// Rebuild a buffer from the captured cells
+    pub fn to_buffer(&self) -> Buffer {
+        let mut buffer = Buffer::new(self.width.max(1), self.height.max(1));
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let Some(captured) = self.get(x, y) else {
+                    continue;
+                };
+                let mut cell = Cell::new(captured.symbol);
+                cell.fg = captured.fg;
+                cell.bg = captured.bg;
+                cell.modifier.set(Modifier::BOLD, captured.bold);
+                cell.modifier.set(Modifier::ITALIC, captured.italic);
+                cell.modifier.set(Modifier::UNDERLINE, captured.underline);
+                cell.modifier.set(Modifier::DIM, captured.dim);
+                buffer.set(x, y, cell);
+            }
+        }
+        buffer
+    }
+
     /// Compare with another capture
     pub fn diff(&self, other: &Self, tolerance: u8) -> crate::testing::visual::types::VisualDiff {
         let mut differences = Vec::new();
@@ -38,7 +38,15 @@
 //! # Update specific test
 //! REVUE_UPDATE_VISUALS=1 cargo test test_button_styles
 //! ```
+//!
+//! # Failure Images
+//!
+//! When a comparison fails, expected, actual and diff panels are rendered
+//! side by side into the CI artifacts directory (see
+//! [`VisualTest::image_path`]): PNG with the `image` feature, SVG otherwise.
+//! Buffers can also be exported directly with [`crate::render::export`].
 
+pub mod artifacts;
 mod capture;
 mod comparison;
 mod helpers;
@@ -284,6 +292,41 @@ CD",
         assert!(!diff.has_differences());
     }
 
+    #[test]
+    fn test_visual_test_image_path() {
+        let config = VisualTestConfig::default().artifacts_dir("artifacts");
+        let test = VisualTest::with_config("button", config).group("widgets");
+        let extension = if cfg!(feature = "image") {
+            "png"
+        } else {
+            "svg"
+        };
+        assert_eq!(
+            test.image_path(),
+            std::path::PathBuf::from(format!("artifacts/widgets-button.{}", extension))
+        );
+    }
+
+    #[test]
+    fn test_assert_matches_failure_writes_image() {
+        let root = std::env::temp_dir().join(format!("revue_visual_fail_{}", std::process::id()));
+        let mut config =
+            VisualTestConfig::with_dir(root.join("golden")).artifacts_dir(root.join("artifacts"));
+        config.update_mode = false;
+        let test = VisualTest::with_config("greeting", config);
+
+        assert_eq!(
+            test.assert_matches(&make_buffer("Hello")),
+            VisualTestResult::Created
+        );
+        let result = std::panic::catch_unwind(|| test.assert_matches(&make_buffer("Hallo")));
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+
+        assert!(test.image_path().exists());
+        assert!(message.contains(&test.image_path().display().to_string()));
+        let _ = std::fs::remove_dir_all(root);
+    }
+
     // VisualTestResult tests
 
     #[test]
@@ -659,6 +702,31 @@ impl VisualTest {
         path.join(format!("{}.diff", self.name))
     }
 
+    /// Path of the side-by-side image written when the test fails
+    ///
+    /// Lives in the configured artifacts directory; PNG with the `image`
+    /// feature, SVG otherwise. Pass it to
+    /// [`TestReport::add_failed_with_diff`](crate::testing::TestReport::add_failed_with_diff)
+    /// to embed the image in the CI report.
+    pub fn image_path(&self) -> PathBuf {
+        let extension = if cfg!(feature = "image") {
+            "png"
+        } else {
+            "svg"
+        };
+        self.config
+            .artifacts_dir
+            .join(format!("{}.{}", self.artifact_stem(), extension))
+    }
+
+    /// File stem for artifacts, prefixed with the group if any
+    fn artifact_stem(&self) -> String {
+        match &self.group {
+            Some(group) => format!("{}-{}", group, self.name),
+            None => self.name.clone(),
+        }
+    }
+
     /// Assert that buffer matches golden file
     pub fn assert_matches(&self, buffer: &Buffer) -> VisualTestResult {
         let actual = VisualCapture::from_buffer(
@@ -705,21 +773,35 @@ impl VisualTest {
         let diff = actual.diff(&expected, self.config.color_tolerance);
 
         if diff.has_differences() {
-            // Generate diff file if enabled
+            // Generate diff file and failure image if enabled
+            let mut image_note = String::new();
             if self.config.generate_diff {
                 let diff_content = diff.to_string();
                 let diff_path = self.diff_path();
                 fs::write(&diff_path, &diff_content)
                     .unwrap_or_else(|e| panic!("Failed to write diff file: {}", e));
+
+                image_note = match artifacts::write_failure_image(
+                    &self.config.artifacts_dir,
+                    &self.artifact_stem(),
+                    &expected,
+                    &actual,
+                    &diff,
+                ) {
+                    Ok(path) => format!("Failure image: {}\n\n", path.display()),
+                    Err(e) => format!("Failed to write failure image: {}\n\n", e),
+                };
             }
 
             panic!(
                 "\nVisual regression detected in '{}'!\n\n\
                  {}\n\n\
+                 {}\
                  To update golden files, run:\n\
                  REVUE_UPDATE_VISUALS=1 cargo test\n",
                 self.name,
-                diff.summary()
+                diff.summary(),
+                image_note
             );
         }
 
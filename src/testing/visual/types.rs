@@ -1,6 +1,7 @@
 //! Core types for visual regression testing
 
 use crate::style::Color;
+use crate::testing::ci::CiEnvironment;
 use std::path::PathBuf;
 
 /// Configuration for visual regression tests
@@ -20,6 +21,8 @@ pub struct VisualTestConfig {
     pub include_styles: bool,
     /// Include color information
     pub include_colors: bool,
+    /// Directory receiving side-by-side failure images
+    pub artifacts_dir: PathBuf,
 }
 
 impl Default for VisualTestConfig {
@@ -36,6 +39,7 @@ impl Default for VisualTestConfig {
             fail_on_missing: false,
             include_styles: true,
             include_colors: true,
+            artifacts_dir: CiEnvironment::detect().artifacts_dir,
         }
     }
 }
@@ -72,6 +76,13 @@ impl VisualTestConfig {
         self.include_colors = enable;
         self
     }
+
+    /// Set the directory for failure images (defaults to the CI artifacts
+    /// directory)
+    pub fn artifacts_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.artifacts_dir = dir.into();
+        self
+    }
 }
 
 /// Result of a visual test
@@ -1,5 +1,6 @@
 //! Timer for animation timing
 
+use crate::utils::clock;
 use crate::utils::easing::Easing;
 use std::time::{Duration, Instant};
 
@@ -38,7 +39,7 @@ impl Timer {
 
     /// Start the timer
     pub fn start(&mut self) {
-        self.start = Some(Instant::now());
+        self.start = Some(clock::now());
         self.elapsed_on_pause = Duration::ZERO;
         self.paused = false;
     }
@@ -54,7 +55,7 @@ impl Timer {
     /// Resume the timer
     pub fn resume(&mut self) {
         if self.paused {
-            self.start = Some(Instant::now() - self.elapsed_on_pause);
+            self.start = Some(clock::now() - self.elapsed_on_pause);
             self.paused = false;
         }
     }
@@ -85,7 +86,7 @@ impl Timer {
     /// Get elapsed time
     pub fn elapsed(&self) -> Duration {
         match self.start {
-            Some(start) if !self.paused => Instant::now() - start,
+            Some(start) if !self.paused => clock::now() - start,
             _ => self.elapsed_on_pause,
         }
     }
@@ -281,7 +281,7 @@ fn apply_sgr(state: &mut AnsiState, params: &str) {
 }
 
 /// Convert 256-color code to RGB
-fn color_256(code: u8) -> Color {
+pub(crate) fn color_256(code: u8) -> Color {
     match code {
         // Standard colors (0-7)
         0 => Color::BLACK,
@@ -0,0 +1,204 @@
+//! Pluggable clock for time-dependent runtime code
+//!
+//! Animations, transitions, timers, debouncers and the app tick loop read the
+//! current time through [`now()`] instead of `Instant::now()`. By default this
+//! is the system clock; tests can install a [`VirtualClock`] and advance it
+//! instantly, making time-based behavior deterministic.
+//!
+//! The installed clock is per thread, so tests running in parallel do not
+//! affect each other.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use revue::utils::clock::{self, VirtualClock};
+//! use std::time::Duration;
+//!
+//! let clock = VirtualClock::new();
+//! let _guard = clock.install();
+//!
+//! let start = clock::now();
+//! clock.advance(Duration::from_millis(500));
+//! assert_eq!(clock::elapsed(start), Duration::from_millis(500));
+//! ```
+
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Source of the current time
+pub trait Clock: Send + Sync {
+    /// Get the current instant
+    fn now(&self) -> Instant;
+}
+
+/// Clock backed by `Instant::now()`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Manually advanced clock for deterministic tests
+///
+/// Time only moves when [`advance()`](Self::advance) or
+/// [`set_elapsed()`](Self::set_elapsed) is called. Clones share the same time.
+#[derive(Debug, Clone)]
+pub struct VirtualClock {
+    origin: Instant,
+    elapsed_nanos: Arc<AtomicU64>,
+}
+
+impl VirtualClock {
+    /// Create a virtual clock starting at the current system time
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+            elapsed_nanos: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Get time elapsed since the clock was created
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.elapsed_nanos.load(Ordering::SeqCst))
+    }
+
+    /// Advance time by a duration
+    pub fn advance(&self, duration: Duration) {
+        self.elapsed_nanos
+            .fetch_add(duration.as_nanos() as u64, Ordering::SeqCst);
+    }
+
+    /// Advance time by milliseconds
+    pub fn advance_ms(&self, ms: u64) {
+        self.advance(Duration::from_millis(ms));
+    }
+
+    /// Set time elapsed since the clock was created
+    pub fn set_elapsed(&self, elapsed: Duration) {
+        self.elapsed_nanos
+            .store(elapsed.as_nanos() as u64, Ordering::SeqCst);
+    }
+
+    /// Install this clock for the current thread
+    ///
+    /// The previous clock is restored when the guard is dropped.
+    pub fn install(&self) -> ClockGuard {
+        set_clock(self.clone())
+    }
+}
+
+impl Default for VirtualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> Instant {
+        self.origin + self.elapsed()
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<dyn Clock>>> = const { RefCell::new(None) };
+}
+
+/// Restores the previously installed clock when dropped
+#[must_use = "the clock is uninstalled when the guard is dropped"]
+pub struct ClockGuard {
+    previous: Option<Arc<dyn Clock>>,
+    // Installation is per thread, so the guard must stay on it
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for ClockGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT.with(|c| *c.borrow_mut() = previous);
+    }
+}
+
+/// Install a clock for the current thread
+///
+/// The previous clock is restored when the returned guard is dropped.
+pub fn set_clock(clock: impl Clock + 'static) -> ClockGuard {
+    let previous = CURRENT.with(|c| c.borrow_mut().replace(Arc::new(clock)));
+    ClockGuard {
+        previous,
+        _not_send: PhantomData,
+    }
+}
+
+/// Get the current time from the installed clock
+pub fn now() -> Instant {
+    CURRENT.with(|c| match c.borrow().as_ref() {
+        Some(clock) => clock.now(),
+        None => Instant::now(),
+    })
+}
+
+/// Get time elapsed since an instant, according to the installed clock
+///
+/// Use instead of `Instant::elapsed()`, which always reads the system clock.
+pub fn elapsed(since: Instant) -> Duration {
+    now().saturating_duration_since(since)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_clock_by_default() {
+        let before = Instant::now();
+        let t = now();
+        assert!(t >= before);
+    }
+
+    #[test]
+    fn test_virtual_clock_advance() {
+        let clock = VirtualClock::new();
+        let _guard = clock.install();
+
+        let start = now();
+        assert_eq!(elapsed(start), Duration::ZERO);
+
+        clock.advance(Duration::from_millis(250));
+        clock.advance_ms(250);
+        assert_eq!(elapsed(start), Duration::from_millis(500));
+        assert_eq!(clock.elapsed(), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_guard_restores_previous_clock() {
+        let outer = VirtualClock::new();
+        let _outer_guard = outer.install();
+        outer.set_elapsed(Duration::from_secs(10));
+        let outer_now = now();
+
+        {
+            let inner = VirtualClock::new();
+            let _inner_guard = inner.install();
+            inner.advance_ms(1);
+            assert_ne!(now(), outer_now);
+        }
+
+        assert_eq!(now(), outer_now);
+    }
+
+    #[test]
+    fn test_clock_is_per_thread() {
+        let clock = VirtualClock::new();
+        let _guard = clock.install();
+        clock.advance(Duration::from_secs(3600));
+
+        let other = std::thread::spawn(now).join().unwrap();
+        assert!(other < now());
+    }
+}
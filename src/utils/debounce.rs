@@ -22,6 +22,7 @@
 //! }
 //! ```
 
+use crate::utils::clock;
 use std::time::{Duration, Instant};
 
 /// Edge mode for debounce/throttle behavior
@@ -97,7 +98,7 @@ impl Debouncer {
     ///
     /// Returns `true` if the handler should be called now.
     pub fn call(&mut self) -> bool {
-        let now = Instant::now();
+        let now = clock::now();
 
         match self.edge {
             Edge::Trailing => {
@@ -141,7 +142,7 @@ impl Debouncer {
         }
 
         if let Some(last) = self.last_call {
-            if last.elapsed() >= self.delay {
+            if clock::elapsed(last) >= self.delay {
                 self.pending = false;
                 self.leading_fired = false;
                 self.last_call = None;
@@ -177,7 +178,7 @@ impl Debouncer {
             return None;
         }
         self.last_call.map(|last| {
-            let elapsed = last.elapsed();
+            let elapsed = clock::elapsed(last);
             if elapsed >= self.delay {
                 Duration::ZERO
             } else {
@@ -259,11 +260,11 @@ impl Throttle {
     ///
     /// Returns `true` if the handler should be called now.
     pub fn call(&mut self) -> bool {
-        let now = Instant::now();
+        let now = clock::now();
 
         let can_execute = match self.last_exec {
             None => true,
-            Some(last) => last.elapsed() >= self.interval,
+            Some(last) => clock::elapsed(last) >= self.interval,
         };
 
         match self.edge {
@@ -301,11 +302,11 @@ impl Throttle {
 
         let can_execute = match self.last_exec {
             None => true,
-            Some(last) => last.elapsed() >= self.interval,
+            Some(last) => clock::elapsed(last) >= self.interval,
         };
 
         if can_execute && matches!(self.edge, Edge::Trailing | Edge::Both) {
-            self.last_exec = Some(Instant::now());
+            self.last_exec = Some(clock::now());
             self.pending = false;
             true
         } else {
@@ -334,7 +335,7 @@ impl Throttle {
         match self.last_exec {
             None => Duration::ZERO,
             Some(last) => {
-                let elapsed = last.elapsed();
+                let elapsed = clock::elapsed(last);
                 if elapsed >= self.interval {
                     Duration::ZERO
                 } else {
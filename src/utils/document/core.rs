@@ -0,0 +1,459 @@
+//! Document implementation
+
+use super::rope::Rope;
+use super::types::{DocumentChange, Edit};
+use crate::utils::undo::GroupedUndoHistory;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Default number of undo steps kept
+pub const DEFAULT_UNDO_LIMIT: usize = 100;
+
+/// Chars examined on each side when looking for a grapheme boundary
+const GRAPHEME_WINDOW: usize = 64;
+
+/// Rope-backed text document with undo and change notifications
+///
+/// All indices are char indices; lines are separated by `\n` and columns
+/// count chars from the start of the line.
+#[derive(Clone, Debug)]
+pub struct Document {
+    rope: Rope,
+    history: GroupedUndoHistory<Edit>,
+    /// Nesting depth of open transactions
+    transaction_depth: usize,
+    /// Changes not yet taken, when tracking is enabled
+    changes: Option<Vec<DocumentChange>>,
+    version: u64,
+}
+
+impl Default for Document {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Document {
+    /// Create an empty document
+    pub fn new() -> Self {
+        Self {
+            rope: Rope::default(),
+            history: GroupedUndoHistory::with_max_size(DEFAULT_UNDO_LIMIT),
+            transaction_depth: 0,
+            changes: None,
+            version: 0,
+        }
+    }
+
+    /// Create a document with initial content
+    pub fn with_content(text: &str) -> Self {
+        let mut doc = Self::new();
+        doc.rope = Rope::from_str(text);
+        doc
+    }
+
+    /// Keep at most `limit` undo steps
+    ///
+    /// Clears the existing history.
+    pub fn undo_limit(mut self, limit: usize) -> Self {
+        self.history = GroupedUndoHistory::with_max_size(limit);
+        self
+    }
+
+    /// Record a [`DocumentChange`] for every edit, for [`take_changes`]
+    ///
+    /// [`take_changes`]: Self::take_changes
+    pub fn track_changes(mut self, enabled: bool) -> Self {
+        self.set_track_changes(enabled);
+        self
+    }
+
+    /// Enable or disable change tracking
+    pub fn set_track_changes(&mut self, enabled: bool) {
+        if enabled {
+            self.changes.get_or_insert_with(Vec::new);
+        } else {
+            self.changes = None;
+        }
+    }
+
+    // =========================================================================
+    // Content
+    // =========================================================================
+
+    /// Replace the whole text, clearing undo history
+    ///
+    /// Reported as a single change covering the old text.
+    pub fn set_content(&mut self, text: &str) {
+        self.apply(&Edit {
+            at: 0,
+            deleted: self.rope.to_string(),
+            inserted: text.to_string(),
+        });
+        self.clear_history();
+    }
+
+    /// The whole text
+    pub fn text(&self) -> String {
+        self.rope.to_string()
+    }
+
+    /// Number of chars
+    pub fn len_chars(&self) -> usize {
+        self.rope.len_chars()
+    }
+
+    /// Number of bytes in UTF-8
+    pub fn len_bytes(&self) -> usize {
+        self.rope.len_bytes()
+    }
+
+    /// Number of lines
+    ///
+    /// Always at least 1; text ending in `\n` has an empty last line.
+    pub fn len_lines(&self) -> usize {
+        self.rope.len_lines()
+    }
+
+    /// Whether the document has no text
+    pub fn is_empty(&self) -> bool {
+        self.len_chars() == 0
+    }
+
+    /// Text of a line, without its newline
+    ///
+    /// Empty for lines past the end.
+    pub fn line(&self, line: usize) -> String {
+        if line >= self.len_lines() {
+            return String::new();
+        }
+        self.rope
+            .slice(self.line_to_char(line)..self.line_end(line))
+    }
+
+    /// Length of a line in chars, without its newline
+    pub fn line_len(&self, line: usize) -> usize {
+        if line >= self.len_lines() {
+            return 0;
+        }
+        self.line_end(line) - self.line_to_char(line)
+    }
+
+    /// Iterate over all lines, without newlines
+    pub fn lines(&self) -> impl Iterator<Item = String> + '_ {
+        let mut chunks = self.rope.chunks();
+        let mut pending = String::new();
+        let mut done = false;
+        std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            loop {
+                if let Some(nl) = pending.find('\n') {
+                    let line = pending[..nl].to_string();
+                    pending.drain(..=nl);
+                    return Some(line);
+                }
+                match chunks.next() {
+                    Some(chunk) => pending.push_str(chunk),
+                    None => {
+                        done = true;
+                        return Some(std::mem::take(&mut pending));
+                    }
+                }
+            }
+        })
+    }
+
+    /// Char at an index
+    pub fn char_at(&self, idx: usize) -> Option<char> {
+        self.rope.char_at(idx)
+    }
+
+    /// Text in a char range
+    pub fn slice(&self, range: Range<usize>) -> String {
+        self.rope.slice(range)
+    }
+
+    /// UTF-8 bytes from a byte offset to the end of its storage chunk
+    ///
+    /// Empty at the end of the text. Lets streaming readers such as
+    /// incremental parsers consume the text without copying it.
+    pub fn bytes_at(&self, byte: usize) -> &[u8] {
+        self.rope.bytes_at(byte)
+    }
+
+    /// Document version, incremented by every edit
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Take the changes recorded since the last call
+    ///
+    /// Empty unless [`track_changes`](Self::track_changes) is enabled.
+    pub fn take_changes(&mut self) -> Vec<DocumentChange> {
+        self.changes
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    // =========================================================================
+    // Indexing
+    // =========================================================================
+
+    /// Char index where a line starts
+    pub fn line_to_char(&self, line: usize) -> usize {
+        self.rope.line_to_char(line)
+    }
+
+    /// Line containing a char index
+    pub fn char_to_line(&self, idx: usize) -> usize {
+        self.rope.char_to_line(idx)
+    }
+
+    /// Byte offset of a char index
+    pub fn char_to_byte(&self, idx: usize) -> usize {
+        self.rope.char_to_byte(idx)
+    }
+
+    /// Char index of a byte offset
+    pub fn byte_to_char(&self, byte: usize) -> usize {
+        self.rope.byte_to_char(byte)
+    }
+
+    /// Char index of a `(line, column)` position, clamped to the document
+    pub fn pos_to_char(&self, line: usize, col: usize) -> usize {
+        if line >= self.len_lines() {
+            return self.len_chars();
+        }
+        self.line_to_char(line) + col.min(self.line_len(line))
+    }
+
+    /// `(line, column)` position of a char index
+    pub fn char_to_pos(&self, idx: usize) -> (usize, usize) {
+        let idx = idx.min(self.len_chars());
+        let line = self.char_to_line(idx);
+        (line, idx - self.line_to_char(line))
+    }
+
+    /// Char index of the next grapheme boundary after `idx`
+    pub fn next_grapheme(&self, idx: usize) -> usize {
+        let window = self.slice(idx..idx + GRAPHEME_WINDOW);
+        let len = window
+            .graphemes(true)
+            .next()
+            .map_or(0, |g| g.chars().count());
+        (idx + len).min(self.len_chars())
+    }
+
+    /// Char index of the previous grapheme boundary before `idx`
+    pub fn prev_grapheme(&self, idx: usize) -> usize {
+        let idx = idx.min(self.len_chars());
+        let window = self.slice(idx.saturating_sub(GRAPHEME_WINDOW)..idx);
+        let len = window
+            .graphemes(true)
+            .next_back()
+            .map_or(0, |g| g.chars().count());
+        idx - len
+    }
+
+    /// Char index just past the last char of a line, before its newline
+    fn line_end(&self, line: usize) -> usize {
+        if line + 1 < self.len_lines() {
+            self.line_to_char(line + 1) - 1
+        } else {
+            self.len_chars()
+        }
+    }
+
+    // =========================================================================
+    // Editing
+    // =========================================================================
+
+    /// Insert text at a char index
+    pub fn insert(&mut self, idx: usize, text: &str) {
+        self.replace(idx..idx, text);
+    }
+
+    /// Remove a char range, returning the removed text
+    pub fn remove(&mut self, range: Range<usize>) -> String {
+        self.replace(range, "")
+    }
+
+    /// Replace a char range with text, returning the replaced text
+    ///
+    /// Each call is one undo step unless made inside a transaction.
+    pub fn replace(&mut self, range: Range<usize>, text: &str) -> String {
+        let start = range.start.min(self.len_chars());
+        let end = range.end.clamp(start, self.len_chars());
+        let edit = Edit {
+            at: start,
+            deleted: self.rope.slice(start..end),
+            inserted: text.to_string(),
+        };
+        if edit.deleted.is_empty() && edit.inserted.is_empty() {
+            return String::new();
+        }
+        self.apply(&edit);
+        let deleted = edit.deleted.clone();
+        self.history.push(edit);
+        deleted
+    }
+
+    /// Start grouping edits into one undo step
+    ///
+    /// Transactions nest; the step ends with the outermost
+    /// [`commit_transaction`](Self::commit_transaction).
+    pub fn begin_transaction(&mut self) {
+        if self.transaction_depth == 0 {
+            self.history.begin_group();
+        }
+        self.transaction_depth += 1;
+    }
+
+    /// End a transaction started with [`begin_transaction`](Self::begin_transaction)
+    pub fn commit_transaction(&mut self) {
+        if self.transaction_depth == 0 {
+            return;
+        }
+        self.transaction_depth -= 1;
+        if self.transaction_depth == 0 {
+            self.history.end_group();
+        }
+    }
+
+    /// Run `f` inside a transaction
+    pub fn transaction<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.begin_transaction();
+        let result = f(self);
+        self.commit_transaction();
+        result
+    }
+
+    // =========================================================================
+    // Undo / Redo
+    // =========================================================================
+
+    /// Undo the last step
+    ///
+    /// Returns the char index where the cursor belongs: the end of the
+    /// restored text at the earliest edit of the step.
+    pub fn undo(&mut self) -> Option<usize> {
+        self.close_transactions();
+        let group = self.history.undo()?;
+        let mut cursor = None;
+        for edit in group.iter_rev() {
+            let inverse = edit.inverse();
+            self.apply(&inverse);
+            cursor = Some(inverse.at + inverse.inserted.chars().count());
+        }
+        cursor
+    }
+
+    /// Redo the last undone step
+    ///
+    /// Returns the char index where the cursor belongs: the end of the
+    /// text inserted by the last edit of the step.
+    pub fn redo(&mut self) -> Option<usize> {
+        self.close_transactions();
+        let group = self.history.redo()?;
+        let mut cursor = None;
+        for edit in group.iter() {
+            self.apply(edit);
+            cursor = Some(edit.at + edit.inserted.chars().count());
+        }
+        cursor
+    }
+
+    /// Whether there is a step to undo
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    /// Whether there is a step to redo
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    /// Forget all undo and redo steps
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+        self.transaction_depth = 0;
+    }
+
+    fn close_transactions(&mut self) {
+        if self.transaction_depth > 0 {
+            self.transaction_depth = 0;
+            self.history.end_group();
+        }
+    }
+
+    /// Apply an edit to the text and report it
+    fn apply(&mut self, edit: &Edit) {
+        let deleted_chars = edit.deleted.chars().count();
+        let change = self.changes.is_some().then(|| {
+            let start_position = self.char_to_pos(edit.at);
+            let start_byte = self.char_to_byte(edit.at);
+            let line_start = edit.at - start_position.1;
+            let start_utf16_column = self.slice(line_start..edit.at).encode_utf16().count();
+            let start_byte_column = start_byte - self.char_to_byte(line_start);
+            (
+                start_position,
+                start_byte,
+                start_byte_column,
+                start_utf16_column,
+            )
+        });
+
+        self.rope.remove(edit.at..edit.at + deleted_chars);
+        self.rope.insert(edit.at, &edit.inserted);
+        self.version += 1;
+
+        if let (
+            Some(changes),
+            Some((start_position, start_byte, start_byte_column, start_utf16_column)),
+        ) = (&mut self.changes, change)
+        {
+            changes.push(DocumentChange {
+                start_char: edit.at,
+                old_end_char: edit.at + deleted_chars,
+                new_end_char: edit.at + edit.inserted.chars().count(),
+                start_byte,
+                old_end_byte: start_byte + edit.deleted.len(),
+                new_end_byte: start_byte + edit.inserted.len(),
+                start_position,
+                old_end_position: end_position(start_position, &edit.deleted),
+                new_end_position: end_position(start_position, &edit.inserted),
+                start_byte_column,
+                start_utf16_column,
+                removed: edit.deleted.clone(),
+                text: edit.inserted.clone(),
+                version: self.version,
+            });
+        }
+    }
+}
+
+/// Position reached by writing `text` starting at `start`
+fn end_position(start: (usize, usize), text: &str) -> (usize, usize) {
+    match text.rfind('\n') {
+        Some(nl) => (
+            start.0 + text.matches('\n').count(),
+            text[nl + 1..].chars().count(),
+        ),
+        None => (start.0, start.1 + text.chars().count()),
+    }
+}
+
+impl From<&str> for Document {
+    fn from(text: &str) -> Self {
+        Self::with_content(text)
+    }
+}
+
+impl std::fmt::Display for Document {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.rope, f)
+    }
+}
@@ -0,0 +1,196 @@
+//! Rope-backed text document for multi-line editors
+//!
+//! [`Document`] is the text model shared by `TextArea` and `CodeEditor`:
+//! - Chunked rope storage, so edits in large files stay cheap
+//! - Char, byte, line and grapheme indexing
+//! - Edit transactions that undo as one step, built on
+//!   [`GroupedUndoHistory`](crate::utils::GroupedUndoHistory)
+//! - Optional [`DocumentChange`] notifications with char, byte and
+//!   `(line, column)` ranges for incremental consumers
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use revue::utils::Document;
+//!
+//! let mut doc = Document::with_content("hello\nworld").track_changes(true);
+//!
+//! doc.transaction(|doc| {
+//!     doc.insert(5, ",");
+//!     doc.replace(7..12, "there");
+//! });
+//! assert_eq!(doc.text(), "hello,\nthere");
+//! assert_eq!(doc.take_changes().len(), 2);
+//!
+//! doc.undo(); // Reverts both edits
+//! assert_eq!(doc.line(1), "world");
+//! ```
+
+mod core;
+mod rope;
+mod types;
+
+pub use core::{Document, DEFAULT_UNDO_LIMIT};
+pub use types::DocumentChange;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_and_positions() {
+        let doc = Document::with_content("ab\ncdé\n");
+        assert_eq!(doc.len_lines(), 3);
+        assert_eq!(doc.line(1), "cdé");
+        assert_eq!(doc.line(2), "");
+        assert_eq!(doc.line_len(1), 3);
+        assert_eq!(doc.lines().collect::<Vec<_>>(), vec!["ab", "cdé", ""]);
+        assert_eq!(doc.pos_to_char(1, 10), 6);
+        assert_eq!(doc.char_to_pos(6), (1, 3));
+        assert_eq!(doc.char_to_byte(7), 8);
+        assert_eq!(doc.byte_to_char(8), 7);
+    }
+
+    #[test]
+    fn test_large_text_spans_chunks() {
+        let line = "añb 🎉 line\n";
+        let text = line.repeat(500);
+        let mut doc = Document::with_content(&text);
+        assert_eq!(doc.text(), text);
+        assert_eq!(doc.len_lines(), 501);
+        assert_eq!(doc.line(321), "añb 🎉 line");
+
+        let at = doc.pos_to_char(250, 4);
+        doc.insert(at, &"x".repeat(3000));
+        assert_eq!(doc.line_len(250), 10 + 3000);
+        doc.remove(doc.line_to_char(10)..doc.line_to_char(400));
+        assert_eq!(doc.len_lines(), 111);
+        assert_eq!(doc.lines().count(), 111);
+        assert_eq!(doc.line(10), "añb 🎉 line");
+
+        doc.undo();
+        doc.undo();
+        assert_eq!(doc.text(), text);
+    }
+
+    #[test]
+    fn test_many_edits_match_string() {
+        // Enough text for a multi-level tree, edited at pseudo-random spots
+        let mut model = "añb 🎉 line\n".repeat(20_000);
+        let mut doc = Document::with_content(&model);
+        let mut seed = 7u64;
+        let mut next = |bound: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            (seed >> 33) as usize % bound.max(1)
+        };
+        let char_to_byte =
+            |text: &str, idx: usize| text.char_indices().nth(idx).map_or(text.len(), |(i, _)| i);
+
+        for round in 0..300 {
+            let len = model.chars().count();
+            let start = next(len + 1);
+            if round % 3 == 0 {
+                let end = (start + next(40_000)).min(len);
+                doc.remove(start..end);
+                model.replace_range(char_to_byte(&model, start)..char_to_byte(&model, end), "");
+            } else {
+                let text = "ü\nx".repeat(next(2_000));
+                doc.insert(start, &text);
+                model.insert_str(char_to_byte(&model, start), &text);
+            }
+        }
+
+        assert_eq!(doc.text(), model);
+        assert_eq!(doc.len_chars(), model.chars().count());
+        assert_eq!(doc.len_bytes(), model.len());
+        let lines: Vec<&str> = model.split('\n').collect();
+        assert_eq!(doc.len_lines(), lines.len());
+        for i in (0..lines.len()).step_by(97) {
+            assert_eq!(doc.line(i), lines[i]);
+            let start = doc.line_to_char(i);
+            assert_eq!(doc.char_to_line(start), i);
+            assert_eq!(
+                doc.slice(start..start + 3),
+                model.chars().skip(start).take(3).collect::<String>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_graphemes() {
+        let doc = Document::with_content("e\u{301}x👍🏽");
+        assert_eq!(doc.next_grapheme(0), 2);
+        assert_eq!(doc.next_grapheme(2), 3);
+        assert_eq!(doc.next_grapheme(3), 5);
+        assert_eq!(doc.prev_grapheme(5), 3);
+        assert_eq!(doc.prev_grapheme(2), 0);
+        assert_eq!(doc.prev_grapheme(0), 0);
+    }
+
+    #[test]
+    fn test_each_edit_undoes_separately() {
+        let mut doc = Document::new();
+        doc.insert(0, "a");
+        doc.insert(1, "b");
+        assert_eq!(doc.undo(), Some(1));
+        assert_eq!(doc.text(), "a");
+        assert_eq!(doc.redo(), Some(2));
+        assert_eq!(doc.text(), "ab");
+        assert!(!doc.can_redo());
+    }
+
+    #[test]
+    fn test_transactions_nest() {
+        let mut doc = Document::with_content("one two");
+        doc.begin_transaction();
+        doc.remove(0..4);
+        doc.transaction(|doc| doc.insert(3, "!"));
+        doc.commit_transaction();
+        assert_eq!(doc.text(), "two!");
+
+        assert_eq!(doc.undo(), Some(4));
+        assert_eq!(doc.text(), "one two");
+        assert!(!doc.can_undo());
+        assert_eq!(doc.redo(), Some(4));
+        assert_eq!(doc.text(), "two!");
+    }
+
+    #[test]
+    fn test_set_content_clears_history() {
+        let mut doc = Document::new();
+        doc.insert(0, "x");
+        doc.set_content("fresh");
+        assert!(!doc.can_undo());
+        assert_eq!(doc.text(), "fresh");
+    }
+
+    #[test]
+    fn test_change_notifications() {
+        let mut doc = Document::with_content("fn a() {\n}\n");
+        assert!(doc.take_changes().is_empty());
+        doc.set_track_changes(true);
+
+        doc.replace(3..4, "bé\n  ");
+        let changes = doc.take_changes();
+        assert_eq!(changes.len(), 1);
+        let change = &changes[0];
+        assert_eq!((change.start_char, change.old_end_char), (3, 4));
+        assert_eq!(change.new_end_char, 8);
+        assert_eq!((change.start_byte, change.old_end_byte), (3, 4));
+        assert_eq!(change.new_end_byte, 9);
+        assert_eq!(change.start_position, (0, 3));
+        assert_eq!(change.old_end_position, (0, 4));
+        assert_eq!(change.new_end_position, (1, 2));
+        assert_eq!(change.start_byte_column, 3);
+        assert_eq!(change.start_utf16_column, 3);
+        assert_eq!(change.removed, "a");
+        assert_eq!(change.text, "bé\n  ");
+        assert_eq!(change.version, doc.version());
+
+        doc.undo();
+        let changes = doc.take_changes();
+        assert_eq!(changes[0].text, "a");
+        assert_eq!(changes[0].old_end_position, (1, 2));
+        assert!(doc.take_changes().is_empty());
+    }
+}
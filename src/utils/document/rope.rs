@@ -0,0 +1,522 @@
+//! Chunked rope storage for [`Document`](super::Document)
+//!
+//! Text is kept in chunks of at most [`MAX_CHUNK`] bytes at the leaves of a
+//! B-tree. Every node caches the byte, char and newline counts of its
+//! subtree, so lookups by char, byte or line follow a single path from the
+//! root, and edits only touch the chunks they overlap and the counts on the
+//! way down to them.
+
+use std::ops::{AddAssign, Range, SubAssign};
+
+/// Largest chunk, in bytes
+const MAX_CHUNK: usize = 1024;
+
+/// Chunks smaller than this are merged with a neighbor after a removal
+const MIN_CHUNK: usize = MAX_CHUNK / 4;
+
+/// Most children of an internal node
+const MAX_CHILDREN: usize = 16;
+
+/// Internal nodes with fewer children are merged with a neighbor after a removal
+const MIN_CHILDREN: usize = MAX_CHILDREN / 2;
+
+/// Cached counts of a subtree
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Summary {
+    bytes: usize,
+    chars: usize,
+    newlines: usize,
+}
+
+impl Summary {
+    fn of(text: &str) -> Self {
+        Self {
+            bytes: text.len(),
+            chars: text.chars().count(),
+            newlines: text.bytes().filter(|&b| b == b'\n').count(),
+        }
+    }
+}
+
+impl AddAssign for Summary {
+    fn add_assign(&mut self, other: Self) {
+        self.bytes += other.bytes;
+        self.chars += other.chars;
+        self.newlines += other.newlines;
+    }
+}
+
+impl SubAssign for Summary {
+    fn sub_assign(&mut self, other: Self) {
+        self.bytes -= other.bytes;
+        self.chars -= other.chars;
+        self.newlines -= other.newlines;
+    }
+}
+
+/// Count a lookup descends by
+#[derive(Clone, Copy)]
+enum Metric {
+    Bytes,
+    Chars,
+    Newlines,
+}
+
+impl Metric {
+    fn of(self, summary: &Summary) -> usize {
+        match self {
+            Metric::Bytes => summary.bytes,
+            Metric::Chars => summary.chars,
+            Metric::Newlines => summary.newlines,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Kind {
+    Leaf(String),
+    Internal(Vec<Node>),
+}
+
+/// A chunk or a subtree, with its counts
+///
+/// All leaves sit at the same depth.
+#[derive(Clone, Debug)]
+struct Node {
+    summary: Summary,
+    kind: Kind,
+}
+
+/// Byte offset of a char offset within `text`
+fn byte_of(text: &str, char_offset: usize) -> usize {
+    text.char_indices()
+        .nth(char_offset)
+        .map_or(text.len(), |(i, _)| i)
+}
+
+/// Split text into chunk-sized leaves at char boundaries
+fn split_chunks(text: &str) -> impl Iterator<Item = Node> + '_ {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let mut end = rest.len().min(MAX_CHUNK);
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        let (head, tail) = rest.split_at(end);
+        rest = tail;
+        Some(Node::leaf(head.to_string()))
+    })
+}
+
+/// Group sibling nodes under as few parents as fit, sized evenly
+fn pack(nodes: Vec<Node>) -> Vec<Node> {
+    let groups = nodes.len().div_ceil(MAX_CHILDREN);
+    let (size, extra) = (nodes.len() / groups, nodes.len() % groups);
+    let mut nodes = nodes.into_iter();
+    (0..groups)
+        .map(|i| {
+            let take = size + usize::from(i < extra);
+            Node::internal(nodes.by_ref().take(take).collect())
+        })
+        .collect()
+}
+
+impl Node {
+    fn leaf(text: String) -> Self {
+        Self {
+            summary: Summary::of(&text),
+            kind: Kind::Leaf(text),
+        }
+    }
+
+    fn internal(children: Vec<Node>) -> Self {
+        let mut node = Self {
+            summary: Summary::default(),
+            kind: Kind::Internal(children),
+        };
+        node.resum();
+        node
+    }
+
+    /// Recompute an internal node's counts from its children
+    fn resum(&mut self) {
+        if let Kind::Internal(children) = &self.kind {
+            self.summary = Summary::default();
+            for child in children {
+                self.summary += child.summary;
+            }
+        }
+    }
+
+    fn is_underfull(&self) -> bool {
+        match &self.kind {
+            Kind::Leaf(text) => text.len() < MIN_CHUNK,
+            Kind::Internal(children) => children.len() < MIN_CHILDREN,
+        }
+    }
+
+    /// Insert `text` (counted as `added`) at char `idx` of this subtree
+    ///
+    /// Returns the siblings to place after this node when it overflowed.
+    fn insert(&mut self, idx: usize, text: &str, added: Summary) -> Vec<Node> {
+        match &mut self.kind {
+            Kind::Leaf(chunk) => {
+                let byte = byte_of(chunk, idx);
+                if chunk.len() + text.len() <= MAX_CHUNK {
+                    chunk.insert_str(byte, text);
+                    self.summary += added;
+                    return Vec::new();
+                }
+                let combined = format!("{}{}{}", &chunk[..byte], text, &chunk[byte..]);
+                let mut pieces = split_chunks(&combined);
+                if let Some(first) = pieces.next() {
+                    *self = first;
+                }
+                pieces.collect()
+            }
+            Kind::Internal(children) => {
+                if children.is_empty() {
+                    children.extend(split_chunks(text));
+                } else {
+                    // An index at a boundary goes to the end of the earlier child
+                    let mut i = 0;
+                    let mut offset = idx;
+                    while i + 1 < children.len() && offset > children[i].summary.chars {
+                        offset -= children[i].summary.chars;
+                        i += 1;
+                    }
+                    let overflow = children[i].insert(offset, text, added);
+                    children.splice(i + 1..i + 1, overflow);
+                }
+                self.summary += added;
+                if children.len() <= MAX_CHILDREN {
+                    return Vec::new();
+                }
+                let mut groups = pack(std::mem::take(children)).into_iter();
+                if let Some(first) = groups.next() {
+                    *self = first;
+                }
+                groups.collect()
+            }
+        }
+    }
+
+    /// Remove chars `start..end` of this subtree (`start < end`)
+    fn remove(&mut self, start: usize, end: usize) {
+        match &mut self.kind {
+            Kind::Leaf(chunk) => {
+                let (from, to) = (byte_of(chunk, start), byte_of(chunk, end));
+                self.summary -= Summary::of(&chunk[from..to]);
+                chunk.drain(from..to);
+            }
+            Kind::Internal(children) => {
+                let mut offset = 0;
+                let mut first = None;
+                for (i, child) in children.iter_mut().enumerate() {
+                    let (child_start, child_end) = (offset, offset + child.summary.chars);
+                    offset = child_end;
+                    if child_end <= start {
+                        continue;
+                    }
+                    if child_start >= end {
+                        break;
+                    }
+                    first.get_or_insert(i);
+                    if start <= child_start && child_end <= end {
+                        *child = Node::leaf(String::new());
+                    } else {
+                        child.remove(
+                            start.max(child_start) - child_start,
+                            end.min(child_end) - child_start,
+                        );
+                    }
+                }
+                children.retain(|child| child.summary.bytes > 0);
+                if let Some(first) = first {
+                    rebalance(children, first);
+                }
+                self.resum();
+            }
+        }
+    }
+}
+
+/// Merge underfull children around `at` with their neighbors
+fn rebalance(children: &mut Vec<Node>, at: usize) {
+    let mut i = at.saturating_sub(1);
+    while i + 1 < children.len() && i <= at + 1 {
+        if (children[i].is_underfull() || children[i + 1].is_underfull()) && merge_pair(children, i)
+        {
+            continue;
+        }
+        i += 1;
+    }
+}
+
+/// Merge child `i + 1` into child `i`; returns whether they became one
+///
+/// Internal nodes with too many children between them are split evenly
+/// instead.
+fn merge_pair(children: &mut Vec<Node>, i: usize) -> bool {
+    let fits = match (&children[i].kind, &children[i + 1].kind) {
+        (Kind::Leaf(a), Kind::Leaf(b)) => a.len() + b.len() <= MAX_CHUNK,
+        (Kind::Internal(_), Kind::Internal(_)) => true,
+        _ => false,
+    };
+    if !fits {
+        return false;
+    }
+
+    let next = children.remove(i + 1);
+    let node = &mut children[i];
+    match (&mut node.kind, next.kind) {
+        (Kind::Leaf(a), Kind::Leaf(b)) => {
+            a.push_str(&b);
+            node.summary += next.summary;
+            true
+        }
+        (Kind::Internal(a), Kind::Internal(b)) => {
+            a.extend(b);
+            if a.len() <= MAX_CHILDREN {
+                node.resum();
+                return true;
+            }
+            let second = Node::internal(a.split_off(a.len() / 2));
+            node.resum();
+            children.insert(i + 1, second);
+            false
+        }
+        _ => unreachable!("siblings are at the same depth"),
+    }
+}
+
+/// Text split into chunks
+#[derive(Clone, Debug)]
+pub(super) struct Rope {
+    /// Always internal; all-leaf children when the text fits in one level
+    root: Node,
+}
+
+impl Default for Rope {
+    fn default() -> Self {
+        Self {
+            root: Node::internal(Vec::new()),
+        }
+    }
+}
+
+impl Rope {
+    pub(super) fn from_str(text: &str) -> Self {
+        let mut level: Vec<Node> = split_chunks(text).collect();
+        while level.len() > MAX_CHILDREN {
+            level = pack(level);
+        }
+        Self {
+            root: Node::internal(level),
+        }
+    }
+
+    pub(super) fn len_chars(&self) -> usize {
+        self.root.summary.chars
+    }
+
+    pub(super) fn len_bytes(&self) -> usize {
+        self.root.summary.bytes
+    }
+
+    /// Number of lines (newlines + 1)
+    pub(super) fn len_lines(&self) -> usize {
+        self.root.summary.newlines + 1
+    }
+
+    /// Chunk where `metric` reaches `target`, with the counts before it
+    ///
+    /// With `strict` the chunk must contain position `target`; otherwise a
+    /// target at a chunk boundary resolves to the end of the earlier chunk.
+    /// `None` when the target is past the end.
+    fn leaf_at(&self, metric: Metric, mut target: usize, strict: bool) -> Option<(&str, Summary)> {
+        let mut node = &self.root;
+        let mut before = Summary::default();
+        loop {
+            match &node.kind {
+                Kind::Leaf(text) => return Some((text, before)),
+                Kind::Internal(children) => {
+                    let mut next = None;
+                    for child in children {
+                        let count = metric.of(&child.summary);
+                        if target < count || (!strict && target == count) {
+                            next = Some(child);
+                            break;
+                        }
+                        target -= count;
+                        before += child.summary;
+                    }
+                    node = next?;
+                }
+            }
+        }
+    }
+
+    pub(super) fn char_to_byte(&self, idx: usize) -> usize {
+        let idx = idx.min(self.len_chars());
+        self.leaf_at(Metric::Chars, idx, false)
+            .map_or(0, |(text, before)| {
+                before.bytes + byte_of(text, idx - before.chars)
+            })
+    }
+
+    pub(super) fn byte_to_char(&self, byte: usize) -> usize {
+        let byte = byte.min(self.len_bytes());
+        self.leaf_at(Metric::Bytes, byte, false)
+            .map_or(0, |(text, before)| {
+                let mut end = byte - before.bytes;
+                while !text.is_char_boundary(end) {
+                    end -= 1;
+                }
+                before.chars + text[..end].chars().count()
+            })
+    }
+
+    /// Bytes from `byte` to the end of the chunk containing it
+    pub(super) fn bytes_at(&self, byte: usize) -> &[u8] {
+        self.leaf_at(Metric::Bytes, byte, true)
+            .map_or(&[], |(text, before)| {
+                &text.as_bytes()[byte - before.bytes..]
+            })
+    }
+
+    /// Char index where `line` starts (clamped to the end of the text)
+    pub(super) fn line_to_char(&self, line: usize) -> usize {
+        if line == 0 {
+            return 0;
+        }
+        self.leaf_at(Metric::Newlines, line, false)
+            .and_then(|(text, before)| {
+                let (nl, _) = text.match_indices('\n').nth(line - before.newlines - 1)?;
+                Some(before.chars + text[..=nl].chars().count())
+            })
+            .unwrap_or(self.len_chars())
+    }
+
+    /// Line containing char `idx`
+    pub(super) fn char_to_line(&self, idx: usize) -> usize {
+        let idx = idx.min(self.len_chars());
+        self.leaf_at(Metric::Chars, idx, false)
+            .map_or(0, |(text, before)| {
+                let end = byte_of(text, idx - before.chars);
+                before.newlines + text[..end].bytes().filter(|&b| b == b'\n').count()
+            })
+    }
+
+    pub(super) fn char_at(&self, idx: usize) -> Option<char> {
+        let (text, before) = self.leaf_at(Metric::Chars, idx, true)?;
+        text.chars().nth(idx - before.chars)
+    }
+
+    /// Text in a char range
+    pub(super) fn slice(&self, range: Range<usize>) -> String {
+        let start = range.start.min(self.len_chars());
+        let end = range.end.clamp(start, self.len_chars());
+        let mut out = String::new();
+        if start < end {
+            slice_into(&self.root, start, end, &mut out);
+        }
+        out
+    }
+
+    /// Iterate over the chunks' text in order
+    pub(super) fn chunks(&self) -> impl Iterator<Item = &str> {
+        let mut stack = vec![&self.root];
+        std::iter::from_fn(move || {
+            while let Some(node) = stack.pop() {
+                match &node.kind {
+                    Kind::Leaf(text) => return Some(text.as_str()),
+                    Kind::Internal(children) => stack.extend(children.iter().rev()),
+                }
+            }
+            None
+        })
+    }
+
+    pub(super) fn insert(&mut self, idx: usize, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let idx = idx.min(self.len_chars());
+        let overflow = self.root.insert(idx, text, Summary::of(text));
+        if overflow.is_empty() {
+            return;
+        }
+        // The root split: grow the tree by a level
+        let mut level = vec![std::mem::take(&mut self.root)];
+        level.extend(overflow);
+        while level.len() > MAX_CHILDREN {
+            level = pack(level);
+        }
+        self.root = Node::internal(level);
+    }
+
+    pub(super) fn remove(&mut self, range: Range<usize>) {
+        let start = range.start.min(self.len_chars());
+        let end = range.end.clamp(start, self.len_chars());
+        if start == end {
+            return;
+        }
+        self.root.remove(start, end);
+        // Shrink the tree while the root has a single internal child
+        while let Kind::Internal(children) = &mut self.root.kind {
+            if children.len() != 1 || !matches!(children[0].kind, Kind::Internal(_)) {
+                break;
+            }
+            self.root = children.pop().unwrap_or_default();
+        }
+    }
+}
+
+impl Default for Node {
+    fn default() -> Self {
+        Node::internal(Vec::new())
+    }
+}
+
+/// Append chars `start..end` of a subtree to `out`
+fn slice_into(node: &Node, start: usize, end: usize, out: &mut String) {
+    match &node.kind {
+        Kind::Leaf(text) => {
+            let from = byte_of(text, start);
+            let to = from + byte_of(&text[from..], end - start);
+            out.push_str(&text[from..to]);
+        }
+        Kind::Internal(children) => {
+            let mut offset = 0;
+            for child in children {
+                let (child_start, child_end) = (offset, offset + child.summary.chars);
+                offset = child_end;
+                if child_end <= start {
+                    continue;
+                }
+                if child_start >= end {
+                    break;
+                }
+                slice_into(
+                    child,
+                    start.max(child_start) - child_start,
+                    end.min(child_end) - child_start,
+                    out,
+                );
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Rope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for chunk in self.chunks() {
+            f.write_str(chunk)?;
+        }
+        Ok(())
+    }
+}
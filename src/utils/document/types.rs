@@ -0,0 +1,60 @@
+//! Edit and change types for the document model
+
+/// A single text replacement, as stored in the undo history
+///
+/// `at` is a char index; `deleted` was removed there and `inserted` put in
+/// its place.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct Edit {
+    pub(super) at: usize,
+    pub(super) deleted: String,
+    pub(super) inserted: String,
+}
+
+impl Edit {
+    /// The edit that reverts this one
+    pub(super) fn inverse(&self) -> Self {
+        Self {
+            at: self.at,
+            deleted: self.inserted.clone(),
+            inserted: self.deleted.clone(),
+        }
+    }
+}
+
+/// Description of one change to a [`Document`](super::Document)
+///
+/// Carries the affected range in chars, bytes and `(line, column)` positions
+/// so consumers like incremental parsers and language servers can apply it
+/// without re-reading the whole text. Columns are in chars.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocumentChange {
+    /// Char index where the change starts
+    pub start_char: usize,
+    /// Char index where the replaced text ended
+    pub old_end_char: usize,
+    /// Char index where the new text ends
+    pub new_end_char: usize,
+    /// Byte offset where the change starts
+    pub start_byte: usize,
+    /// Byte offset where the replaced text ended
+    pub old_end_byte: usize,
+    /// Byte offset where the new text ends
+    pub new_end_byte: usize,
+    /// Position where the change starts
+    pub start_position: (usize, usize),
+    /// Position where the replaced text ended
+    pub old_end_position: (usize, usize),
+    /// Position where the new text ends
+    pub new_end_position: (usize, usize),
+    /// Byte offset of the start within its line
+    pub start_byte_column: usize,
+    /// Column of the start in UTF-16 code units, as used by LSP
+    pub start_utf16_column: usize,
+    /// Text that was replaced
+    pub removed: String,
+    /// Text that replaced the range
+    pub text: String,
+    /// Document version after the change
+    pub version: u64,
+}
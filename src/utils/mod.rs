@@ -10,6 +10,7 @@
 //! | Module | Description | Use Case |
 //!|--------|-------------|----------|
 //! | [`text`] | Text truncation, padding, wrapping | Text display |
+//! | [`document`] | Rope-backed document with undo | Multi-line editors |
 //! | [`textbuffer`] | UTF-8 aware text buffer | Text editing |
 //! | [`unicode`] | Unicode display width | Emoji, CJK support |
 //!
@@ -43,8 +44,10 @@
 //! | [`diff`] | Text comparison | Diffs, patches |
 //! | [`path`] | Path manipulation | File display |
 //! | [`ansi`] | ANSI escape parsing | Terminal output |
+//! | [`vt`] | VT100/xterm emulation | Screen grid from terminal output |
 //! | [`easing`] | Animation easing | Smooth transitions |
 //! | [`animation`] | Frame-based animation | Spring, keyframes |
+//! | [`clock`] | Pluggable time source | Deterministic tests |
 //! | [`debounce`] | Event debouncing | Rate limiting |
 //! | [`mod@once`] | One-shot execution | Call once pattern |
 //! | [`profiler`] | Performance timing | Benchmarks |
@@ -61,6 +64,7 @@
 //! | [`validation`] | Form validation | Input checking |
 //! | [`keymap`] | Key bindings | Keyboard shortcuts |
 //! | [`browser`] | System browser | Open URLs |
+//! | `pty` | Pseudo-terminal processes | End-to-end tests (`pty` feature) |
 //!
 //! # Quick Start
 //!
@@ -102,9 +106,11 @@ pub mod ansi;
 pub mod border;
 pub mod browser;
 pub mod clipboard;
+pub mod clock;
 pub mod color;
 pub mod debounce;
 pub mod diff;
+pub mod document;
 pub mod easing;
 pub mod figlet;
 pub mod filter;
@@ -120,6 +126,8 @@ pub mod once;
 pub mod overlay;
 pub mod path;
 pub mod profiler;
+#[cfg(all(unix, feature = "pty"))]
+pub mod pty;
 pub mod selection;
 pub mod shell;
 pub mod sort;
@@ -133,6 +141,7 @@ pub mod tree;
 pub mod undo;
 pub mod unicode;
 pub mod validation;
+pub mod vt;
 
 // Border utilities
 pub use border::{
@@ -311,6 +320,9 @@ pub use accessibility_signal::{
 // Text Buffer
 pub use textbuffer::TextBuffer;
 
+// Document
+pub use document::{Document, DocumentChange};
+
 // Undo/Redo
 pub use undo::{GroupedUndoHistory, Mergeable, UndoGroup, UndoHistory, DEFAULT_MAX_HISTORY};
 
@@ -359,6 +371,9 @@ pub use lock::{lock_or_recover, read_or_recover, write_or_recover};
 // Shell escaping
 pub use shell::{escape_applescript, escape_powershell, sanitize_string};
 
+// Clock
+pub use clock::{Clock, ClockGuard, SystemClock, VirtualClock};
+
 // Debounce and Throttle
 pub use debounce::{debounce_ms, debouncer, throttle, throttle_ms, Debouncer, Edge, Throttle};
 
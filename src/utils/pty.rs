@@ -0,0 +1,330 @@
+//! Processes running under a pseudo-terminal
+//!
+//! Spawns a program with a PTY as its controlling terminal, so it sees a
+//! real TTY of a given size: raw mode, alternate screen, `SIGWINCH` on
+//! resize and job-control signals all behave as in a terminal emulator.
+//! Output is read on a background thread and collected without blocking.
+//!
+//! Requires the `pty` feature and a Unix platform.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use revue::utils::pty::PtyProcess;
+//! use std::process::Command;
+//! use std::time::Duration;
+//!
+//! let mut proc = PtyProcess::spawn(Command::new("ls"), 80, 24)?;
+//! let output = proc.read_timeout(Duration::from_secs(1));
+//! ```
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, TryRecvError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// A child process attached to a pseudo-terminal
+pub struct PtyProcess {
+    master: File,
+    child: Child,
+    output: Receiver<Vec<u8>>,
+    reader: Option<JoinHandle<()>>,
+    eof: bool,
+}
+
+impl PtyProcess {
+    /// Spawn a command under a new pseudo-terminal of the given size
+    ///
+    /// The command's stdin, stdout and stderr are connected to the PTY, and
+    /// it runs in a new session with the PTY as controlling terminal. `TERM`
+    /// defaults to `xterm-256color` unless set on the command.
+    pub fn spawn(mut command: Command, width: u16, height: u16) -> io::Result<Self> {
+        let master = open_master()?;
+        set_size(&master, width, height)?;
+        let slave = open_slave(&master)?;
+
+        if !command.get_envs().any(|(key, _)| key == "TERM") {
+            command.env("TERM", "xterm-256color");
+        }
+        command
+            .stdin(Stdio::from(slave.try_clone()?))
+            .stdout(Stdio::from(slave.try_clone()?))
+            .stderr(Stdio::from(slave));
+
+        // SAFETY: Only async-signal-safe functions (setsid, ioctl) are
+        // called between fork and exec.
+        unsafe {
+            command.pre_exec(|| {
+                if libc::setsid() < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                if libc::ioctl(0, libc::TIOCSCTTY as _, 0) < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+
+        let child = command.spawn()?;
+        // Drop the parent's copies of the slave so reads hit EOF on exit
+        drop(command);
+
+        let mut reader_end = master.try_clone()?;
+        let (tx, rx) = mpsc::channel();
+        let reader = thread::Builder::new()
+            .name("revue-pty-reader".into())
+            .spawn(move || {
+                let mut chunk = [0u8; 4096];
+                loop {
+                    match reader_end.read(&mut chunk) {
+                        // EIO means every slave handle is closed
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => {
+                            if tx.send(chunk[..n].to_vec()).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            master,
+            child,
+            output: rx,
+            reader: Some(reader),
+            eof: false,
+        })
+    }
+
+    /// Process ID of the child
+    pub fn pid(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Write input bytes to the terminal
+    pub fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.master.write_all(bytes)?;
+        self.master.flush()
+    }
+
+    /// Change the terminal size (delivers `SIGWINCH` to the child)
+    pub fn resize(&self, width: u16, height: u16) -> io::Result<()> {
+        set_size(&self.master, width, height)
+    }
+
+    /// Take all output received so far without blocking
+    pub fn try_read(&mut self) -> Vec<u8> {
+        let mut out = Vec::new();
+        loop {
+            match self.output.try_recv() {
+                Ok(chunk) => out.extend_from_slice(&chunk),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.eof = true;
+                    break;
+                }
+            }
+        }
+        out
+    }
+
+    /// Wait up to `timeout` for output, then take everything available
+    ///
+    /// Returns an empty vector on timeout or end of output.
+    pub fn read_timeout(&mut self, timeout: Duration) -> Vec<u8> {
+        match self.output.recv_timeout(timeout) {
+            Ok(mut chunk) => {
+                chunk.extend(self.try_read());
+                chunk
+            }
+            Err(RecvTimeoutError::Timeout) => Vec::new(),
+            Err(RecvTimeoutError::Disconnected) => {
+                self.eof = true;
+                Vec::new()
+            }
+        }
+    }
+
+    /// Whether the terminal output has ended (all writers closed)
+    pub fn is_eof(&self) -> bool {
+        self.eof
+    }
+
+    /// Send a signal (e.g. `libc::SIGTERM`) to the child
+    pub fn signal(&self, signal: i32) -> io::Result<()> {
+        // SAFETY: kill() has no memory-safety preconditions.
+        if unsafe { libc::kill(self.child.id() as libc::pid_t, signal) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Check whether the child has exited, without blocking
+    pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        self.child.try_wait()
+    }
+
+    /// Wait up to `timeout` for the child to exit
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        let start = Instant::now();
+        loop {
+            if let Some(status) = self.child.try_wait()? {
+                return Ok(Some(status));
+            }
+            if start.elapsed() >= timeout {
+                return Ok(None);
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    /// Kill the child and wait for it to exit
+    pub fn kill(&mut self) -> io::Result<ExitStatus> {
+        if let Some(status) = self.child.try_wait()? {
+            return Ok(status);
+        }
+        self.child.kill()?;
+        self.child.wait()
+    }
+}
+
+impl Drop for PtyProcess {
+    fn drop(&mut self) {
+        let _ = self.kill();
+        if let Some(reader) = self.reader.take() {
+            // The reader exits once the child and its slave handles are gone;
+            // don't block on grandchildren that may still hold the terminal.
+            if reader.is_finished() {
+                let _ = reader.join();
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for PtyProcess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PtyProcess")
+            .field("pid", &self.child.id())
+            .field("eof", &self.eof)
+            .finish()
+    }
+}
+
+/// Open the master side of a new pseudo-terminal
+fn open_master() -> io::Result<File> {
+    // SAFETY: posix_openpt returns a new descriptor or -1; ownership of a
+    // valid descriptor is transferred to the OwnedFd.
+    let fd = unsafe { libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY | libc::O_CLOEXEC) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let master = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    // SAFETY: `master` is a valid PTY master descriptor.
+    unsafe {
+        if libc::grantpt(master.as_raw_fd()) != 0 || libc::unlockpt(master.as_raw_fd()) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(File::from(master))
+}
+
+/// Open the slave side of a pseudo-terminal
+fn open_slave(master: &File) -> io::Result<File> {
+    let path = slave_path(master)?;
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(libc::O_NOCTTY)
+        .open(path)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn slave_path(master: &File) -> io::Result<String> {
+    let mut buf = [0 as libc::c_char; 128];
+    // SAFETY: buf is valid for buf.len() bytes and ptsname_r NUL-terminates
+    // on success.
+    let rc = unsafe { libc::ptsname_r(master.as_raw_fd(), buf.as_mut_ptr(), buf.len()) };
+    if rc != 0 {
+        return Err(io::Error::from_raw_os_error(rc));
+    }
+    // SAFETY: ptsname_r succeeded, so buf holds a NUL-terminated string.
+    let name = unsafe { std::ffi::CStr::from_ptr(buf.as_ptr()) };
+    Ok(name.to_string_lossy().into_owned())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn slave_path(master: &File) -> io::Result<String> {
+    // SAFETY: ptsname returns NULL or a pointer to a static NUL-terminated
+    // string, which is copied before any other PTY call.
+    let ptr = unsafe { libc::ptsname(master.as_raw_fd()) };
+    if ptr.is_null() {
+        return Err(io::Error::last_os_error());
+    }
+    let name = unsafe { std::ffi::CStr::from_ptr(ptr) };
+    Ok(name.to_string_lossy().into_owned())
+}
+
+/// Set the terminal window size
+fn set_size(master: &File, width: u16, height: u16) -> io::Result<()> {
+    let size = libc::winsize {
+        ws_row: height,
+        ws_col: width,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    // SAFETY: TIOCSWINSZ reads a winsize struct from the given pointer.
+    if unsafe { libc::ioctl(master.as_raw_fd(), libc::TIOCSWINSZ as _, &size) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spawn_reads_output_and_exit_status() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "stty size; printf 'done'"]);
+        let mut proc = PtyProcess::spawn(command, 40, 12).unwrap();
+
+        let mut output = Vec::new();
+        let start = Instant::now();
+        while !proc.is_eof() && start.elapsed() < Duration::from_secs(5) {
+            output.extend(proc.read_timeout(Duration::from_millis(50)));
+        }
+        let text = String::from_utf8_lossy(&output);
+        assert!(text.contains("12 40"), "unexpected output: {:?}", text);
+        assert!(text.contains("done"));
+
+        let status = proc.wait_timeout(Duration::from_secs(5)).unwrap().unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn test_input_echo_and_signal() {
+        let mut proc = PtyProcess::spawn(Command::new("cat"), 20, 5).unwrap();
+        proc.write(b"ping\n").unwrap();
+
+        let mut output = Vec::new();
+        let start = Instant::now();
+        while !String::from_utf8_lossy(&output).contains("ping\r\nping")
+            && start.elapsed() < Duration::from_secs(5)
+        {
+            output.extend(proc.read_timeout(Duration::from_millis(50)));
+        }
+        assert!(String::from_utf8_lossy(&output).contains("ping\r\nping"));
+
+        proc.signal(libc::SIGTERM).unwrap();
+        let status = proc.wait_timeout(Duration::from_secs(5)).unwrap();
+        assert!(status.is_some_and(|s| !s.success()));
+    }
+}
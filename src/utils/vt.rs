@@ -0,0 +1,1307 @@
+//! VT100/xterm terminal emulator
+//!
+//! Interprets the byte stream a program writes to its terminal (text, C0
+//! controls, ESC/CSI/OSC sequences) and maintains the resulting screen grid.
+//! Used by the PTY test harness to read what a real binary drew, and usable
+//! anywhere terminal output needs to be rendered into a [`Buffer`].
+//!
+//! Supports cursor movement, erase/insert/delete, scroll regions, SGR
+//! colors and attributes, the alternate screen, DEC special graphics, and
+//! the common private modes (cursor visibility, autowrap, bracketed paste,
+//! mouse tracking). Queries such as cursor position reports are answered
+//! through [`VtScreen::take_responses()`]. [`encode_key()`] and
+//! [`encode_mouse()`] produce the input bytes a program expects.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use revue::utils::vt::VtScreen;
+//!
+//! let mut vt = VtScreen::new(20, 3);
+//! vt.feed(b"\x1b[2;3Hhello\x1b[31m!");
+//! assert_eq!(vt.line(1), "  hello!");
+//! assert_eq!(vt.cursor(), (8, 1));
+//! ```
+
+use crate::event::{Key, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use crate::render::{Buffer, Cell, Modifier};
+use crate::style::Color;
+use crate::utils::ansi::color_256;
+use unicode_width::UnicodeWidthChar;
+
+/// Maximum number of CSI parameters kept per sequence
+const MAX_PARAMS: usize = 32;
+
+/// Maximum length of an OSC/DCS string
+const MAX_STRING: usize = 4096;
+
+/// Parser state
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Ground,
+    Escape,
+    /// ESC followed by a charset designator (`(`, `)`, `*`, `+`)
+    Charset(u8),
+    /// ESC followed by another intermediate byte (e.g. `#`, space)
+    EscapeIntermediate,
+    Csi,
+    /// OSC string (`ESC ]`)
+    Osc,
+    /// DCS, APC, PM or SOS string, ignored
+    IgnoredString,
+    /// ESC seen inside a string, expecting `\`
+    StringEscape,
+}
+
+/// Cursor state saved by DECSC / `CSI s`
+#[derive(Clone, Copy, Debug)]
+struct SavedCursor {
+    x: u16,
+    y: u16,
+    pen: Cell,
+    dec_graphics: bool,
+}
+
+/// Mouse events a program asked to receive
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MouseMode {
+    /// No mouse reporting
+    #[default]
+    Off,
+    /// Button presses, releases and wheel (mode 1000)
+    Click,
+    /// Also motion while a button is held (mode 1002)
+    Drag,
+    /// Also motion without a button held (mode 1003)
+    Motion,
+}
+
+/// VT100/xterm screen emulator
+#[derive(Debug, Clone)]
+pub struct VtScreen {
+    /// Visible screen grid
+    grid: Buffer,
+    /// Primary screen contents while the alternate screen is active
+    primary: Option<Buffer>,
+    /// Lines scrolled off the top of the primary screen (oldest first)
+    scrollback: Vec<Vec<Cell>>,
+    /// Maximum scrollback lines
+    scrollback_limit: usize,
+
+    cursor_x: u16,
+    cursor_y: u16,
+    /// Cursor is past the last column; the next printable wraps first
+    pending_wrap: bool,
+    saved_cursor: Option<SavedCursor>,
+    /// Attributes applied to printed characters
+    pen: Cell,
+    /// Last printed character (for `CSI b`)
+    last_char: Option<char>,
+    /// G0 is the DEC special graphics (line drawing) set
+    dec_graphics: bool,
+
+    /// Scroll region, inclusive
+    scroll_top: u16,
+    scroll_bottom: u16,
+
+    cursor_visible: bool,
+    autowrap: bool,
+    origin_mode: bool,
+    application_cursor: bool,
+    bracketed_paste: bool,
+    mouse_mode: MouseMode,
+    /// Mouse reports use SGR encoding (mode 1006)
+    sgr_mouse: bool,
+    title: String,
+
+    state: State,
+    params: Vec<u16>,
+    current_param: Option<u32>,
+    private: Option<u8>,
+    intermediate: Option<u8>,
+    string: Vec<u8>,
+    /// The current string is an OSC (as opposed to an ignored DCS/APC/PM)
+    string_is_osc: bool,
+    utf8: Vec<u8>,
+    responses: Vec<u8>,
+}
+
+impl VtScreen {
+    /// Create an emulator with a blank screen
+    pub fn new(width: u16, height: u16) -> Self {
+        let width = width.max(1);
+        let height = height.max(1);
+        Self {
+            grid: Buffer::new(width, height),
+            primary: None,
+            scrollback: Vec::new(),
+            scrollback_limit: 0,
+            cursor_x: 0,
+            cursor_y: 0,
+            pending_wrap: false,
+            saved_cursor: None,
+            pen: Cell::default(),
+            last_char: None,
+            dec_graphics: false,
+            scroll_top: 0,
+            scroll_bottom: height - 1,
+            cursor_visible: true,
+            autowrap: true,
+            origin_mode: false,
+            application_cursor: false,
+            bracketed_paste: false,
+            mouse_mode: MouseMode::Off,
+            sgr_mouse: false,
+            title: String::new(),
+            state: State::Ground,
+            params: Vec::new(),
+            current_param: None,
+            private: None,
+            intermediate: None,
+            string: Vec::new(),
+            string_is_osc: false,
+            utf8: Vec::new(),
+            responses: Vec::new(),
+        }
+    }
+
+    /// Keep up to `limit` lines scrolled off the top of the primary screen
+    pub fn scrollback_limit(mut self, limit: usize) -> Self {
+        self.scrollback_limit = limit;
+        self
+    }
+
+    // =========================================================================
+    // Accessors
+    // =========================================================================
+
+    /// Screen width
+    pub fn width(&self) -> u16 {
+        self.grid.width()
+    }
+
+    /// Screen height
+    pub fn height(&self) -> u16 {
+        self.grid.height()
+    }
+
+    /// Visible screen grid
+    pub fn buffer(&self) -> &Buffer {
+        &self.grid
+    }
+
+    /// Cursor position as (column, row)
+    pub fn cursor(&self) -> (u16, u16) {
+        (self.cursor_x, self.cursor_y)
+    }
+
+    /// Whether the cursor is shown (DECTCEM)
+    pub fn cursor_visible(&self) -> bool {
+        self.cursor_visible
+    }
+
+    /// Whether the alternate screen is active
+    pub fn is_alternate_screen(&self) -> bool {
+        self.primary.is_some()
+    }
+
+    /// Whether cursor keys should send application sequences (DECCKM)
+    pub fn application_cursor(&self) -> bool {
+        self.application_cursor
+    }
+
+    /// Whether bracketed paste mode is enabled
+    pub fn bracketed_paste(&self) -> bool {
+        self.bracketed_paste
+    }
+
+    /// Whether any mouse tracking mode is enabled
+    pub fn mouse_tracking(&self) -> bool {
+        self.mouse_mode != MouseMode::Off
+    }
+
+    /// Mouse events the program asked to receive
+    pub fn mouse_mode(&self) -> MouseMode {
+        self.mouse_mode
+    }
+
+    /// Whether mouse reports should use SGR encoding (mode 1006)
+    pub fn sgr_mouse(&self) -> bool {
+        self.sgr_mouse
+    }
+
+    /// Window title set with OSC 0 or OSC 2
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Lines scrolled off the top of the primary screen, oldest first
+    pub fn scrollback(&self) -> &[Vec<Cell>] {
+        &self.scrollback
+    }
+
+    /// Text of a row, with trailing spaces trimmed
+    pub fn line(&self, row: u16) -> String {
+        self.grid.get_row(row).map(row_text).unwrap_or_default()
+    }
+
+    /// Screen as text, with trailing spaces and empty lines trimmed
+    pub fn text(&self) -> String {
+        let mut lines: Vec<String> = (0..self.height()).map(|y| self.line(y)).collect();
+        while lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
+        lines.join("\n")
+    }
+
+    /// Take bytes the terminal would send back to the program
+    ///
+    /// Answers to device status and attribute queries. Write them to the
+    /// program's input to keep programs that wait for a reply from hanging.
+    pub fn take_responses(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.responses)
+    }
+
+    // =========================================================================
+    // Input
+    // =========================================================================
+
+    /// Process output bytes
+    ///
+    /// UTF-8 sequences split across calls are handled.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.feed_byte(byte);
+        }
+    }
+
+    /// Process output text
+    pub fn feed_str(&mut self, text: &str) {
+        self.feed(text.as_bytes());
+    }
+
+    /// Resize the screen, keeping content where possible
+    pub fn resize(&mut self, width: u16, height: u16) {
+        let width = width.max(1);
+        let height = height.max(1);
+        self.grid.resize(width, height);
+        if let Some(primary) = &mut self.primary {
+            primary.resize(width, height);
+        }
+        self.scroll_top = 0;
+        self.scroll_bottom = height - 1;
+        self.cursor_x = self.cursor_x.min(width - 1);
+        self.cursor_y = self.cursor_y.min(height - 1);
+        self.pending_wrap = false;
+    }
+
+    /// Reset to the initial state (RIS), keeping the size
+    pub fn reset(&mut self) {
+        let limit = self.scrollback_limit;
+        *self = Self::new(self.width(), self.height());
+        self.scrollback_limit = limit;
+    }
+
+    fn feed_byte(&mut self, byte: u8) {
+        // Strings swallow everything up to their terminator
+        match self.state {
+            State::Osc | State::IgnoredString => {
+                match byte {
+                    0x07 => self.end_string(),
+                    0x1b => self.state = State::StringEscape,
+                    _ => {
+                        if self.string.len() < MAX_STRING {
+                            self.string.push(byte);
+                        }
+                    }
+                }
+                return;
+            }
+            State::StringEscape => {
+                // ESC \ (ST) ends the string; any other ESC aborts it
+                self.end_string();
+                if byte != b'\\' {
+                    self.state = State::Escape;
+                    self.feed_byte(byte);
+                }
+                return;
+            }
+            _ => {}
+        }
+
+        if byte < 0x20 || byte == 0x7f {
+            self.control(byte);
+            return;
+        }
+
+        match self.state {
+            State::Ground => self.ground(byte),
+            State::Escape => self.escape(byte),
+            State::Charset(slot) => {
+                if slot == b'(' {
+                    self.dec_graphics = byte == b'0';
+                }
+                self.state = State::Ground;
+            }
+            State::EscapeIntermediate => self.state = State::Ground,
+            State::Csi => self.csi_byte(byte),
+            State::Osc | State::IgnoredString | State::StringEscape => unreachable!(),
+        }
+    }
+
+    /// Accumulate UTF-8 and print complete characters
+    fn ground(&mut self, byte: u8) {
+        if byte < 0x80 {
+            self.utf8.clear();
+            self.print(byte as char);
+            return;
+        }
+
+        if byte & 0xc0 != 0x80 {
+            // Lead byte starts a new sequence
+            self.utf8.clear();
+        }
+        self.utf8.push(byte);
+
+        let expected = match self.utf8[0] {
+            b if b & 0xe0 == 0xc0 => 2,
+            b if b & 0xf0 == 0xe0 => 3,
+            b if b & 0xf8 == 0xf0 => 4,
+            _ => {
+                self.utf8.clear();
+                self.print(char::REPLACEMENT_CHARACTER);
+                return;
+            }
+        };
+        if self.utf8.len() >= expected {
+            let ch = std::str::from_utf8(&self.utf8)
+                .ok()
+                .and_then(|s| s.chars().next())
+                .unwrap_or(char::REPLACEMENT_CHARACTER);
+            self.utf8.clear();
+            self.print(ch);
+        }
+    }
+
+    fn control(&mut self, byte: u8) {
+        match byte {
+            0x1b => {
+                self.state = State::Escape;
+                self.intermediate = None;
+            }
+            // CAN and SUB abort a sequence
+            0x18 | 0x1a => self.state = State::Ground,
+            0x08 => {
+                self.cursor_x = self.cursor_x.saturating_sub(1);
+                self.pending_wrap = false;
+            }
+            0x09 => {
+                let next = (self.cursor_x / 8 + 1) * 8;
+                self.cursor_x = next.min(self.width() - 1);
+                self.pending_wrap = false;
+            }
+            0x0a..=0x0c => self.line_feed(),
+            0x0d => {
+                self.cursor_x = 0;
+                self.pending_wrap = false;
+            }
+            // BEL, SO/SI and other controls are ignored
+            _ => {}
+        }
+    }
+
+    fn escape(&mut self, byte: u8) {
+        self.state = State::Ground;
+        match byte {
+            b'[' => {
+                self.state = State::Csi;
+                self.params.clear();
+                self.current_param = None;
+                self.private = None;
+                self.intermediate = None;
+            }
+            b']' => {
+                self.state = State::Osc;
+                self.string.clear();
+                self.string_is_osc = true;
+            }
+            b'P' | b'X' | b'^' | b'_' => {
+                self.state = State::IgnoredString;
+                self.string.clear();
+                self.string_is_osc = false;
+            }
+            b'(' | b')' | b'*' | b'+' => self.state = State::Charset(byte),
+            b'#' | b' ' | b'%' => self.state = State::EscapeIntermediate,
+            b'7' => self.save_cursor(),
+            b'8' => self.restore_cursor(),
+            b'D' => self.line_feed(),
+            b'E' => {
+                self.cursor_x = 0;
+                self.line_feed();
+            }
+            b'M' => self.reverse_index(),
+            b'c' => self.reset(),
+            // Keypad modes and anything else are ignored
+            _ => {}
+        }
+    }
+
+    fn csi_byte(&mut self, byte: u8) {
+        match byte {
+            b'0'..=b'9' => {
+                let digit = u32::from(byte - b'0');
+                let value = self.current_param.unwrap_or(0) * 10 + digit;
+                self.current_param = Some(value.min(u32::from(u16::MAX)));
+            }
+            b';' | b':' => self.push_param(),
+            b'?' | b'>' | b'<' | b'=' if self.params.is_empty() && self.current_param.is_none() => {
+                self.private = Some(byte);
+            }
+            0x20..=0x2f => self.intermediate = Some(byte),
+            0x40..=0x7e => {
+                self.push_param();
+                self.state = State::Ground;
+                self.dispatch_csi(byte);
+            }
+            _ => self.state = State::Ground,
+        }
+    }
+
+    fn push_param(&mut self) {
+        if self.params.len() < MAX_PARAMS {
+            self.params.push(self.current_param.unwrap_or(0) as u16);
+        }
+        self.current_param = None;
+    }
+
+    /// Parameter `i`, with 0 or missing replaced by `default`
+    fn param(&self, i: usize, default: u16) -> u16 {
+        match self.params.get(i) {
+            Some(&0) | None => default,
+            Some(&v) => v,
+        }
+    }
+
+    fn end_string(&mut self) {
+        if self.string_is_osc {
+            self.osc();
+        }
+        self.string.clear();
+        self.state = State::Ground;
+    }
+
+    fn osc(&mut self) {
+        let text = String::from_utf8_lossy(&self.string);
+        if let Some((code, value)) = text.split_once(';') {
+            if code == "0" || code == "2" {
+                self.title = value.to_string();
+            }
+        }
+    }
+
+    // =========================================================================
+    // Printing
+    // =========================================================================
+
+    fn print(&mut self, ch: char) {
+        let ch = if self.dec_graphics {
+            dec_graphic(ch)
+        } else {
+            ch
+        };
+        let width = ch.width().unwrap_or(0) as u16;
+        if width == 0 {
+            return;
+        }
+
+        if self.pending_wrap {
+            self.cursor_x = 0;
+            self.line_feed();
+        }
+        if self.cursor_x + width > self.width() {
+            if self.autowrap && width <= self.width() {
+                self.cursor_x = 0;
+                self.line_feed();
+            } else {
+                self.cursor_x = self.width().saturating_sub(width);
+            }
+        }
+
+        let mut cell = self.pen;
+        cell.symbol = ch;
+        self.grid.set(self.cursor_x, self.cursor_y, cell);
+        if width == 2 {
+            let mut cont = Cell::continuation();
+            cont.bg = self.pen.bg;
+            self.grid.set(self.cursor_x + 1, self.cursor_y, cont);
+        }
+        self.last_char = Some(ch);
+
+        let next = self.cursor_x + width;
+        if next >= self.width() {
+            self.cursor_x = self.width() - 1;
+            self.pending_wrap = self.autowrap;
+        } else {
+            self.cursor_x = next;
+        }
+    }
+
+    /// Blank cell with the current background
+    fn blank(&self) -> Cell {
+        Cell {
+            bg: self.pen.bg,
+            ..Cell::default()
+        }
+    }
+
+    // =========================================================================
+    // Cursor & scrolling
+    // =========================================================================
+
+    fn line_feed(&mut self) {
+        self.pending_wrap = false;
+        if self.cursor_y == self.scroll_bottom {
+            self.scroll_up(1);
+        } else if self.cursor_y + 1 < self.height() {
+            self.cursor_y += 1;
+        }
+    }
+
+    fn reverse_index(&mut self) {
+        self.pending_wrap = false;
+        if self.cursor_y == self.scroll_top {
+            self.scroll_down(1);
+        } else {
+            self.cursor_y = self.cursor_y.saturating_sub(1);
+        }
+    }
+
+    /// Scroll the scroll region up by `n` lines
+    fn scroll_up(&mut self, n: u16) {
+        let (top, bottom) = (self.scroll_top, self.scroll_bottom);
+        let n = n.min(bottom - top + 1);
+        let keep_history = self.scrollback_limit > 0
+            && self.primary.is_none()
+            && top == 0
+            && bottom == self.height() - 1;
+
+        for _ in 0..n {
+            if keep_history {
+                if let Some(row) = self.grid.get_row(top) {
+                    self.scrollback.push(row.to_vec());
+                }
+                let overflow = self.scrollback.len().saturating_sub(self.scrollback_limit);
+                self.scrollback.drain(..overflow);
+            }
+            for y in top..bottom {
+                self.copy_row(y + 1, y);
+            }
+            self.clear_row(bottom);
+        }
+    }
+
+    /// Scroll the scroll region down by `n` lines
+    fn scroll_down(&mut self, n: u16) {
+        let (top, bottom) = (self.scroll_top, self.scroll_bottom);
+        let n = n.min(bottom - top + 1);
+        for _ in 0..n {
+            for y in (top..bottom).rev() {
+                self.copy_row(y, y + 1);
+            }
+            self.clear_row(top);
+        }
+    }
+
+    fn copy_row(&mut self, from: u16, to: u16) {
+        for x in 0..self.width() {
+            if let Some(&cell) = self.grid.get(x, from) {
+                self.grid.set(x, to, cell);
+            }
+        }
+    }
+
+    fn clear_row(&mut self, y: u16) {
+        self.clear_span(y, 0, self.width());
+    }
+
+    /// Blank columns `start..end` of row `y`
+    fn clear_span(&mut self, y: u16, start: u16, end: u16) {
+        let blank = self.blank();
+        for x in start..end.min(self.width()) {
+            self.grid.set(x, y, blank);
+        }
+    }
+
+    fn move_to(&mut self, x: u16, y: u16) {
+        let (min_y, max_y) = if self.origin_mode {
+            (self.scroll_top, self.scroll_bottom)
+        } else {
+            (0, self.height() - 1)
+        };
+        let y = if self.origin_mode {
+            y.saturating_add(self.scroll_top)
+        } else {
+            y
+        };
+        self.cursor_x = x.min(self.width() - 1);
+        self.cursor_y = y.clamp(min_y, max_y);
+        self.pending_wrap = false;
+    }
+
+    fn save_cursor(&mut self) {
+        self.saved_cursor = Some(SavedCursor {
+            x: self.cursor_x,
+            y: self.cursor_y,
+            pen: self.pen,
+            dec_graphics: self.dec_graphics,
+        });
+    }
+
+    fn restore_cursor(&mut self) {
+        if let Some(saved) = self.saved_cursor {
+            self.cursor_x = saved.x.min(self.width() - 1);
+            self.cursor_y = saved.y.min(self.height() - 1);
+            self.pen = saved.pen;
+            self.dec_graphics = saved.dec_graphics;
+        } else {
+            self.cursor_x = 0;
+            self.cursor_y = 0;
+        }
+        self.pending_wrap = false;
+    }
+
+    // =========================================================================
+    // CSI dispatch
+    // =========================================================================
+
+    fn dispatch_csi(&mut self, final_byte: u8) {
+        match self.private {
+            Some(b'?') => {
+                match final_byte {
+                    b'h' => self.set_private_modes(true),
+                    b'l' => self.set_private_modes(false),
+                    _ => {}
+                }
+                return;
+            }
+            Some(_) => return,
+            None => {}
+        }
+        if self.intermediate.is_some() {
+            // e.g. DECSCUSR (`CSI Ps SP q`); cursor shape is not tracked
+            return;
+        }
+
+        let n = self.param(0, 1);
+        match final_byte {
+            b'@' => self.insert_chars(n),
+            b'A' => {
+                self.cursor_y = self.cursor_y.saturating_sub(n).max(self.top_limit());
+                self.pending_wrap = false;
+            }
+            b'B' | b'e' => {
+                let y = self.cursor_y.saturating_add(n).min(self.bottom_limit());
+                self.cursor_y = y;
+                self.pending_wrap = false;
+            }
+            b'C' | b'a' => {
+                self.cursor_x = self.cursor_x.saturating_add(n).min(self.width() - 1);
+                self.pending_wrap = false;
+            }
+            b'D' => {
+                self.cursor_x = self.cursor_x.saturating_sub(n);
+                self.pending_wrap = false;
+            }
+            b'E' => {
+                self.cursor_y = self.cursor_y.saturating_add(n).min(self.bottom_limit());
+                self.cursor_x = 0;
+                self.pending_wrap = false;
+            }
+            b'F' => {
+                self.cursor_y = self.cursor_y.saturating_sub(n).max(self.top_limit());
+                self.cursor_x = 0;
+                self.pending_wrap = false;
+            }
+            b'G' | b'`' => {
+                self.cursor_x = (n - 1).min(self.width() - 1);
+                self.pending_wrap = false;
+            }
+            b'H' | b'f' => self.move_to(self.param(1, 1) - 1, n - 1),
+            b'd' => {
+                let x = self.cursor_x;
+                self.move_to(x, n - 1);
+            }
+            b'J' => self.erase_display(self.param(0, 0)),
+            b'K' => self.erase_line(self.param(0, 0)),
+            b'L' => self.insert_lines(n),
+            b'M' => self.delete_lines(n),
+            b'P' => self.delete_chars(n),
+            b'X' => {
+                let end = self.cursor_x.saturating_add(n);
+                self.clear_span(self.cursor_y, self.cursor_x, end);
+            }
+            b'S' => self.scroll_up(n),
+            b'T' => self.scroll_down(n),
+            b'b' => {
+                if let Some(ch) = self.last_char {
+                    for _ in 0..n {
+                        self.print(ch);
+                    }
+                }
+            }
+            b'm' => self.sgr(),
+            b'r' => {
+                let top = self.param(0, 1) - 1;
+                let bottom = self.param(1, self.height()).min(self.height()) - 1;
+                if top < bottom {
+                    self.scroll_top = top;
+                    self.scroll_bottom = bottom;
+                    self.move_to(0, 0);
+                }
+            }
+            b's' => self.save_cursor(),
+            b'u' => self.restore_cursor(),
+            b'n' => match self.param(0, 0) {
+                5 => self.responses.extend_from_slice(b"\x1b[0n"),
+                6 => {
+                    let report = format!("\x1b[{};{}R", self.cursor_y + 1, self.cursor_x + 1);
+                    self.responses.extend_from_slice(report.as_bytes());
+                }
+                _ => {}
+            },
+            b'c' => self.responses.extend_from_slice(b"\x1b[?1;2c"),
+            _ => {}
+        }
+    }
+
+    /// Topmost row reachable by relative cursor movement
+    fn top_limit(&self) -> u16 {
+        if self.cursor_y >= self.scroll_top {
+            self.scroll_top
+        } else {
+            0
+        }
+    }
+
+    /// Bottom row reachable by relative cursor movement
+    fn bottom_limit(&self) -> u16 {
+        if self.cursor_y <= self.scroll_bottom {
+            self.scroll_bottom
+        } else {
+            self.height() - 1
+        }
+    }
+
+    fn set_private_modes(&mut self, enabled: bool) {
+        for i in 0..self.params.len() {
+            match self.params[i] {
+                1 => self.application_cursor = enabled,
+                6 => {
+                    self.origin_mode = enabled;
+                    self.move_to(0, 0);
+                }
+                7 => self.autowrap = enabled,
+                25 => self.cursor_visible = enabled,
+                mode @ (1000 | 1002 | 1003) => {
+                    self.mouse_mode = match (enabled, mode) {
+                        (false, _) => MouseMode::Off,
+                        (true, 1000) => MouseMode::Click,
+                        (true, 1002) => MouseMode::Drag,
+                        _ => MouseMode::Motion,
+                    };
+                }
+                1006 => self.sgr_mouse = enabled,
+                2004 => self.bracketed_paste = enabled,
+                47 | 1047 => self.set_alternate_screen(enabled),
+                1049 => {
+                    if enabled {
+                        self.save_cursor();
+                        self.set_alternate_screen(true);
+                    } else {
+                        self.set_alternate_screen(false);
+                        self.restore_cursor();
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn set_alternate_screen(&mut self, enabled: bool) {
+        if enabled && self.primary.is_none() {
+            let alternate = Buffer::new(self.width(), self.height());
+            self.primary = Some(std::mem::replace(&mut self.grid, alternate));
+        } else if !enabled {
+            if let Some(primary) = self.primary.take() {
+                self.grid = primary;
+            }
+        }
+        self.pending_wrap = false;
+    }
+
+    fn erase_display(&mut self, mode: u16) {
+        let (x, y) = (self.cursor_x, self.cursor_y);
+        match mode {
+            0 => {
+                self.clear_span(y, x, self.width());
+                for row in y + 1..self.height() {
+                    self.clear_row(row);
+                }
+            }
+            1 => {
+                for row in 0..y {
+                    self.clear_row(row);
+                }
+                self.clear_span(y, 0, x + 1);
+            }
+            2 => {
+                for row in 0..self.height() {
+                    self.clear_row(row);
+                }
+            }
+            3 => self.scrollback.clear(),
+            _ => {}
+        }
+        self.pending_wrap = false;
+    }
+
+    fn erase_line(&mut self, mode: u16) {
+        let (x, y) = (self.cursor_x, self.cursor_y);
+        match mode {
+            0 => self.clear_span(y, x, self.width()),
+            1 => self.clear_span(y, 0, x + 1),
+            2 => self.clear_row(y),
+            _ => {}
+        }
+        self.pending_wrap = false;
+    }
+
+    fn insert_chars(&mut self, n: u16) {
+        let (x, y, width) = (self.cursor_x, self.cursor_y, self.width());
+        let n = n.min(width - x);
+        for col in (x + n..width).rev() {
+            if let Some(&cell) = self.grid.get(col - n, y) {
+                self.grid.set(col, y, cell);
+            }
+        }
+        self.clear_span(y, x, x + n);
+        self.pending_wrap = false;
+    }
+
+    fn delete_chars(&mut self, n: u16) {
+        let (x, y, width) = (self.cursor_x, self.cursor_y, self.width());
+        let n = n.min(width - x);
+        for col in x..width - n {
+            if let Some(&cell) = self.grid.get(col + n, y) {
+                self.grid.set(col, y, cell);
+            }
+        }
+        self.clear_span(y, width - n, width);
+        self.pending_wrap = false;
+    }
+
+    fn insert_lines(&mut self, n: u16) {
+        if self.cursor_y < self.scroll_top || self.cursor_y > self.scroll_bottom {
+            return;
+        }
+        let top = self.scroll_top;
+        self.scroll_top = self.cursor_y;
+        self.scroll_down(n);
+        self.scroll_top = top;
+        self.cursor_x = 0;
+        self.pending_wrap = false;
+    }
+
+    fn delete_lines(&mut self, n: u16) {
+        if self.cursor_y < self.scroll_top || self.cursor_y > self.scroll_bottom {
+            return;
+        }
+        let (top, limit) = (self.scroll_top, self.scrollback_limit);
+        self.scroll_top = self.cursor_y;
+        // Deleted lines never go to scrollback
+        self.scrollback_limit = 0;
+        self.scroll_up(n);
+        self.scrollback_limit = limit;
+        self.scroll_top = top;
+        self.cursor_x = 0;
+        self.pending_wrap = false;
+    }
+
+    // =========================================================================
+    // SGR
+    // =========================================================================
+
+    fn sgr(&mut self) {
+        if self.params.is_empty() {
+            self.params.push(0);
+        }
+        let mut i = 0;
+        while i < self.params.len() {
+            let code = self.params[i];
+            match code {
+                0 => {
+                    self.pen.fg = None;
+                    self.pen.bg = None;
+                    self.pen.modifier = Modifier::empty();
+                }
+                1 => self.pen.modifier |= Modifier::BOLD,
+                2 => self.pen.modifier |= Modifier::DIM,
+                3 => self.pen.modifier |= Modifier::ITALIC,
+                4 => self.pen.modifier |= Modifier::UNDERLINE,
+                7 => self.pen.modifier |= Modifier::REVERSE,
+                9 => self.pen.modifier |= Modifier::CROSSED_OUT,
+                22 => self.pen.modifier -= Modifier::BOLD | Modifier::DIM,
+                23 => self.pen.modifier -= Modifier::ITALIC,
+                24 => self.pen.modifier -= Modifier::UNDERLINE,
+                27 => self.pen.modifier -= Modifier::REVERSE,
+                29 => self.pen.modifier -= Modifier::CROSSED_OUT,
+                30..=37 => self.pen.fg = Some(color_256((code - 30) as u8)),
+                39 => self.pen.fg = None,
+                40..=47 => self.pen.bg = Some(color_256((code - 40) as u8)),
+                49 => self.pen.bg = None,
+                90..=97 => self.pen.fg = Some(color_256((code - 90 + 8) as u8)),
+                100..=107 => self.pen.bg = Some(color_256((code - 100 + 8) as u8)),
+                38 | 48 => {
+                    let (color, used) = self.extended_color(i + 1);
+                    if let Some(color) = color {
+                        if code == 38 {
+                            self.pen.fg = Some(color);
+                        } else {
+                            self.pen.bg = Some(color);
+                        }
+                    }
+                    i += used;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+
+    /// Parse `5;n` or `2;r;g;b` starting at `i`; returns the color and params used
+    fn extended_color(&self, i: usize) -> (Option<Color>, usize) {
+        match self.params.get(i) {
+            Some(5) => (
+                self.params.get(i + 1).map(|&n| color_256(n.min(255) as u8)),
+                2,
+            ),
+            Some(2) => {
+                let channel = |k: usize| self.params.get(i + k).map(|&v| v.min(255) as u8);
+                match (channel(1), channel(2), channel(3)) {
+                    (Some(r), Some(g), Some(b)) => (Some(Color::rgb(r, g, b)), 4),
+                    _ => (None, self.params.len() - i),
+                }
+            }
+            _ => (None, 0),
+        }
+    }
+}
+
+/// Encode a key press as the bytes an xterm-compatible terminal sends
+///
+/// `application_cursor` selects `ESC O` arrow keys, as requested by
+/// programs through DECCKM (see [`VtScreen::application_cursor()`]).
+pub fn encode_key(event: &KeyEvent, application_cursor: bool) -> Vec<u8> {
+    // xterm modifier parameter: 1 + shift + 2*alt + 4*ctrl
+    let modifier = 1 + u8::from(event.shift) + 2 * u8::from(event.alt) + 4 * u8::from(event.ctrl);
+    let cursor = |c: char| -> Vec<u8> {
+        if modifier > 1 {
+            format!("\x1b[1;{}{}", modifier, c).into_bytes()
+        } else if application_cursor {
+            format!("\x1bO{}", c).into_bytes()
+        } else {
+            format!("\x1b[{}", c).into_bytes()
+        }
+    };
+    let tilde = |n: u8| -> Vec<u8> {
+        if modifier > 1 {
+            format!("\x1b[{};{}~", n, modifier).into_bytes()
+        } else {
+            format!("\x1b[{}~", n).into_bytes()
+        }
+    };
+
+    let mut bytes = match event.key {
+        Key::Char(c) if event.ctrl && c.is_ascii_alphabetic() => {
+            vec![c.to_ascii_lowercase() as u8 & 0x1f]
+        }
+        Key::Char(' ') if event.ctrl => vec![0],
+        Key::Char(c) => c.to_string().into_bytes(),
+        Key::Enter => vec![b'\r'],
+        Key::Tab => vec![b'\t'],
+        Key::BackTab => b"\x1b[Z".to_vec(),
+        Key::Backspace => vec![0x7f],
+        Key::Escape => vec![0x1b],
+        Key::Up => return cursor('A'),
+        Key::Down => return cursor('B'),
+        Key::Right => return cursor('C'),
+        Key::Left => return cursor('D'),
+        Key::Home => return cursor('H'),
+        Key::End => return cursor('F'),
+        Key::Insert => return tilde(2),
+        Key::Delete => return tilde(3),
+        Key::PageUp => return tilde(5),
+        Key::PageDown => return tilde(6),
+        Key::F(n @ 1..=4) => {
+            let c = (b'P' + n - 1) as char;
+            return if modifier > 1 {
+                format!("\x1b[1;{}{}", modifier, c).into_bytes()
+            } else {
+                format!("\x1bO{}", c).into_bytes()
+            };
+        }
+        Key::F(n @ 5..=12) => return tilde([15, 17, 18, 19, 20, 21, 23, 24][(n - 5) as usize]),
+        Key::F(_) | Key::Null | Key::Unknown => return Vec::new(),
+    };
+
+    if event.alt {
+        bytes.insert(0, 0x1b);
+    }
+    bytes
+}
+
+/// Encode a mouse event as the report an xterm-compatible terminal sends
+///
+/// Coordinates are zero-based and relative to the screen. Returns `None` if
+/// `mode` does not report this kind of event, or if the position cannot be
+/// expressed in the legacy (non-SGR) encoding.
+pub fn encode_mouse(event: &MouseEvent, mode: MouseMode, sgr: bool) -> Option<Vec<u8>> {
+    let button_code = |button: MouseButton| match button {
+        MouseButton::Left => 0,
+        MouseButton::Middle => 1,
+        MouseButton::Right => 2,
+    };
+    let (code, release) = match event.kind {
+        _ if mode == MouseMode::Off => return None,
+        MouseEventKind::Down(button) => (button_code(button), false),
+        // Legacy reports don't say which button was released
+        MouseEventKind::Up(button) => (if sgr { button_code(button) } else { 3 }, true),
+        MouseEventKind::Drag(button) if mode != MouseMode::Click => {
+            (button_code(button) + 32, false)
+        }
+        MouseEventKind::Move if mode == MouseMode::Motion => (35, false),
+        MouseEventKind::ScrollUp => (64, false),
+        MouseEventKind::ScrollDown => (65, false),
+        MouseEventKind::ScrollLeft => (66, false),
+        MouseEventKind::ScrollRight => (67, false),
+        MouseEventKind::Drag(_) | MouseEventKind::Move => return None,
+    };
+    let code =
+        code + 4 * u16::from(event.shift) + 8 * u16::from(event.alt) + 16 * u16::from(event.ctrl);
+    let (x, y) = (event.x.saturating_add(1), event.y.saturating_add(1));
+
+    if sgr {
+        let end = if release { 'm' } else { 'M' };
+        return Some(format!("\x1b[<{};{};{}{}", code, x, y, end).into_bytes());
+    }
+    let byte = |v: u16| u8::try_from(v + 32).ok();
+    Some(vec![0x1b, b'[', b'M', byte(code)?, byte(x)?, byte(y)?])
+}
+
+/// Text of a grid row, skipping wide-character continuations
+fn row_text(cells: &[Cell]) -> String {
+    let text: String = cells
+        .iter()
+        .filter(|c| !c.is_continuation())
+        .map(|c| c.symbol)
+        .collect();
+    text.trim_end().to_string()
+}
+
+/// Map a character through the DEC special graphics set
+fn dec_graphic(ch: char) -> char {
+    match ch {
+        '`' => '◆',
+        'a' => '▒',
+        'f' => '°',
+        'g' => '±',
+        'j' => '┘',
+        'k' => '┐',
+        'l' => '┌',
+        'm' => '└',
+        'n' => '┼',
+        'q' => '─',
+        't' => '├',
+        'u' => '┤',
+        'v' => '┴',
+        'w' => '┬',
+        'x' => '│',
+        '~' => '·',
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen(width: u16, height: u16, input: &str) -> VtScreen {
+        let mut vt = VtScreen::new(width, height);
+        vt.feed_str(input);
+        vt
+    }
+
+    #[test]
+    fn test_print_and_wrap() {
+        let vt = screen(5, 3, "hello world");
+        assert_eq!(vt.text(), "hello\n worl\nd");
+        assert_eq!(vt.cursor(), (1, 2));
+    }
+
+    #[test]
+    fn test_pending_wrap_at_last_column() {
+        let vt = screen(5, 2, "abcde\r\nx");
+        // Filling the last column must not wrap until the next printable
+        assert_eq!(vt.text(), "abcde\nx");
+    }
+
+    #[test]
+    fn test_cursor_movement_and_erase() {
+        let vt = screen(10, 3, "0123456789\x1b[1;4H\x1b[K\x1b[3;2Hab\x1b[2D\x1b[1P");
+        assert_eq!(vt.line(0), "012");
+        assert_eq!(vt.line(2), " b");
+    }
+
+    #[test]
+    fn test_scrolling_and_scrollback() {
+        let mut vt = VtScreen::new(4, 2).scrollback_limit(10);
+        vt.feed_str("a\r\nb\r\nc\r\nd");
+        assert_eq!(vt.text(), "c\nd");
+        let history: Vec<String> = vt.scrollback().iter().map(|r| row_text(r)).collect();
+        assert_eq!(history, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_scroll_region() {
+        let vt = screen(3, 4, "1\r\n2\r\n3\r\n4\x1b[2;3r\x1b[3;1H\nx");
+        assert_eq!(vt.text(), "1\n3\nx\n4");
+    }
+
+    #[test]
+    fn test_sgr_colors_and_attributes() {
+        let vt = screen(10, 1, "\x1b[1;31ma\x1b[38;2;1;2;3;48;5;4mb\x1b[0mc");
+        let a = vt.buffer().get(0, 0).unwrap();
+        assert_eq!(a.fg, Some(Color::RED));
+        assert!(a.modifier.contains(Modifier::BOLD));
+        let b = vt.buffer().get(1, 0).unwrap();
+        assert_eq!(b.fg, Some(Color::rgb(1, 2, 3)));
+        assert_eq!(b.bg, Some(Color::BLUE));
+        let c = vt.buffer().get(2, 0).unwrap();
+        assert_eq!(c.fg, None);
+        assert!(c.modifier.is_empty());
+    }
+
+    #[test]
+    fn test_alternate_screen_and_modes() {
+        let mut vt = screen(10, 2, "shell$");
+        vt.feed_str("\x1b[?1049h\x1b[?25l\x1b[?2004h\x1b[2J\x1b[Happ");
+        assert!(vt.is_alternate_screen());
+        assert!(!vt.cursor_visible());
+        assert!(vt.bracketed_paste());
+        assert_eq!(vt.text(), "app");
+
+        vt.feed_str("\x1b[?2004l\x1b[?25h\x1b[?1049l");
+        assert!(!vt.is_alternate_screen());
+        assert!(vt.cursor_visible());
+        assert_eq!(vt.text(), "shell$");
+        assert_eq!(vt.cursor(), (6, 0));
+    }
+
+    #[test]
+    fn test_wide_chars_and_split_utf8() {
+        let mut vt = VtScreen::new(6, 1);
+        let bytes = "a한b".as_bytes();
+        vt.feed(&bytes[..2]);
+        vt.feed(&bytes[2..]);
+        assert_eq!(vt.line(0), "a한b");
+        assert_eq!(vt.cursor(), (4, 0));
+    }
+
+    #[test]
+    fn test_osc_title_and_dec_graphics() {
+        let vt = screen(5, 1, "\x1b]0;My App\x07\x1b(0lqk\x1b(Bx");
+        assert_eq!(vt.title(), "My App");
+        assert_eq!(vt.line(0), "┌─┐x");
+    }
+
+    #[test]
+    fn test_cursor_position_report() {
+        let mut vt = screen(10, 5, "\x1b[3;4H\x1b[6n\x1b[c");
+        assert_eq!(vt.take_responses(), b"\x1b[3;4R\x1b[?1;2c");
+        assert!(vt.take_responses().is_empty());
+    }
+
+    #[test]
+    fn test_insert_and_delete_lines() {
+        let mut vt = screen(3, 3, "a\r\nb\r\nc\x1b[2;1H\x1b[L");
+        assert_eq!(vt.text(), "a\n\nb");
+        vt.feed_str("\x1b[1;1H\x1b[2M");
+        assert_eq!(vt.text(), "b");
+    }
+
+    #[test]
+    fn test_encode_key() {
+        assert_eq!(
+            encode_key(&KeyEvent::new(Key::Char('é')), false),
+            "é".as_bytes()
+        );
+        assert_eq!(encode_key(&KeyEvent::ctrl(Key::Char('c')), false), vec![3]);
+        assert_eq!(encode_key(&KeyEvent::alt(Key::Char('x')), false), b"\x1bx");
+        assert_eq!(encode_key(&KeyEvent::new(Key::Up), false), b"\x1b[A");
+        assert_eq!(encode_key(&KeyEvent::new(Key::Up), true), b"\x1bOA");
+        assert_eq!(encode_key(&KeyEvent::ctrl(Key::Right), true), b"\x1b[1;5C");
+        assert_eq!(encode_key(&KeyEvent::new(Key::F(5)), false), b"\x1b[15~");
+        assert_eq!(encode_key(&KeyEvent::new(Key::Enter), false), b"\r");
+    }
+
+    #[test]
+    fn test_mouse_modes_and_encoding() {
+        let mut vt = screen(80, 24, "\x1b[?1002h\x1b[?1006h");
+        assert_eq!(vt.mouse_mode(), MouseMode::Drag);
+        assert!(vt.sgr_mouse());
+
+        let mut down = MouseEvent::new(4, 2, MouseEventKind::Down(MouseButton::Left));
+        assert_eq!(
+            encode_mouse(&down, vt.mouse_mode(), true).unwrap(),
+            b"\x1b[<0;5;3M"
+        );
+        let up = MouseEvent::new(4, 2, MouseEventKind::Up(MouseButton::Right));
+        assert_eq!(
+            encode_mouse(&up, MouseMode::Drag, true).unwrap(),
+            b"\x1b[<2;5;3m"
+        );
+        let drag = MouseEvent::new(5, 2, MouseEventKind::Drag(MouseButton::Left));
+        assert_eq!(
+            encode_mouse(&drag, MouseMode::Drag, true).unwrap(),
+            b"\x1b[<32;6;3M"
+        );
+        assert_eq!(encode_mouse(&drag, MouseMode::Click, true), None);
+        let moved = MouseEvent::new(5, 2, MouseEventKind::Move);
+        assert_eq!(encode_mouse(&moved, MouseMode::Drag, true), None);
+
+        down.ctrl = true;
+        assert_eq!(
+            encode_mouse(&down, MouseMode::Click, false).unwrap(),
+            b"\x1b[M0%#"
+        );
+        assert_eq!(
+            encode_mouse(&up, MouseMode::Click, false).unwrap(),
+            b"\x1b[M#%#"
+        );
+        let far = MouseEvent::new(300, 0, MouseEventKind::ScrollUp);
+        assert_eq!(encode_mouse(&far, MouseMode::Click, false), None);
+
+        vt.feed_str("\x1b[?1002l");
+        assert!(!vt.mouse_tracking());
+        assert_eq!(encode_mouse(&down, vt.mouse_mode(), true), None);
+    }
+
+    #[test]
+    fn test_resize_clamps_cursor() {
+        let mut vt = screen(10, 5, "\x1b[5;10H");
+        vt.resize(4, 2);
+        assert_eq!(vt.cursor(), (3, 1));
+        vt.feed_str("\x1b[2;1Hz");
+        assert_eq!(vt.line(1), "z");
+    }
+}
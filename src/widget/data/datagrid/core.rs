@@ -1,11 +1,19 @@
 //! DataGrid core structure and builders
 
 use super::editing::EditState;
+use super::filter_ui::FilterPopover;
+use super::pivot::PivotState;
+use super::source::SourceState;
 #[cfg(test)]
 use super::types::AggregationType;
-use super::types::{FooterRow, GridColors, GridColumn, GridOptions, GridRow, SortDirection};
+use super::types::{
+    ColumnAggregation, ColumnFilter, DisplayRow, FooterRow, GridColors, GridColumn, GridOptions,
+    GridRow, SortDirection,
+};
+use crate::query::{Query, QueryValue, Queryable};
 use crate::style::Color;
 use crate::{impl_props_builders, impl_styled_view};
+use std::collections::HashSet;
 
 /// Tree node info for flattened display
 #[derive(Clone, Debug)]
@@ -74,6 +82,8 @@ pub struct DataGrid {
     pub columns: Vec<GridColumn>,
     /// Rows
     pub rows: Vec<GridRow>,
+    /// External data source (replaces `rows` when set)
+    pub(super) source: Option<SourceState>,
 
     // ─────────────────────────────────────────────────────────────────────────
     // Sorting & Filtering
@@ -88,6 +98,14 @@ pub struct DataGrid {
     pub filter: String,
     /// Filter column (None = all columns)
     pub filter_column: Option<usize>,
+    /// Per-column filters: (column key, filter), in the order they were set
+    pub column_filters: Vec<(String, ColumnFilter)>,
+    /// Query DSL filter
+    pub query: Option<Query>,
+    /// Text the query was parsed from
+    pub query_text: String,
+    /// Open column filter popover
+    pub(super) filter_popover: Option<FilterPopover>,
     /// Cached filtered row indices (eagerly computed on mutation)
     pub filtered_cache: Vec<usize>,
 
@@ -165,6 +183,24 @@ pub struct DataGrid {
     /// Flattened tree cache for display
     pub tree_cache: Vec<TreeNodeInfo>,
 
+    // ─────────────────────────────────────────────────────────────────────────
+    // Grouping & Pivot State
+    // ─────────────────────────────────────────────────────────────────────────
+    /// Column keys rows are grouped by (outermost first)
+    pub group_by: Vec<String>,
+    /// Aggregates shown in group header rows
+    pub group_aggregations: Vec<ColumnAggregation>,
+    /// Collapsed groups (by group path)
+    pub collapsed_groups: HashSet<Vec<String>>,
+    /// Grouped display rows (eagerly computed on mutation, empty when not grouped)
+    pub group_cache: Vec<DisplayRow>,
+    /// Show the group panel and allow dragging columns onto it
+    pub groupable: bool,
+    /// Column being dragged is over the group panel
+    pub group_drop_active: bool,
+    /// Active pivot, with the data it replaced
+    pub(super) pivot: Option<PivotState>,
+
     // ─────────────────────────────────────────────────────────────────────────
     // Export & Aggregation State
     // ─────────────────────────────────────────────────────────────────────────
@@ -213,11 +249,16 @@ impl DataGrid {
         Self {
             columns: Vec::new(),
             rows: Vec::new(),
+            source: None,
             sort_column: None,
             sort_direction: SortDirection::Ascending,
             sort_columns: Vec::new(),
             filter: String::new(),
             filter_column: None,
+            column_filters: Vec::new(),
+            query: None,
+            query_text: String::new(),
+            filter_popover: None,
             filtered_cache: Vec::new(),
             selected_row: 0,
             selected_col: 0,
@@ -246,6 +287,14 @@ impl DataGrid {
             // Tree grid state
             tree_mode: false,
             tree_cache: Vec::new(),
+            // Grouping state
+            group_by: Vec::new(),
+            group_aggregations: Vec::new(),
+            collapsed_groups: HashSet::new(),
+            group_cache: Vec::new(),
+            groupable: false,
+            group_drop_active: false,
+            pivot: None,
             // Footer state
             footer_rows: Vec::new(),
             show_footer: false,
@@ -279,36 +328,59 @@ impl DataGrid {
     /// Recompute the filtered rows cache (called on mutation)
     pub fn recompute_cache(&mut self) {
         self.filtered_cache = self.compute_filtered_indices();
+        self.rebuild_group_cache();
     }
 
     /// Add a column
-    pub fn column(mut self, col: GridColumn) -> Self {
-        self.columns.push(col);
-        self
+    pub fn column(self, col: GridColumn) -> Self {
+        self.columns(vec![col])
     }
 
     /// Add columns
     pub fn columns(mut self, cols: Vec<GridColumn>) -> Self {
+        for row in &mut self.rows {
+            row.parse_cells(&cols);
+        }
         self.columns.extend(cols);
         self
     }
 
     /// Add a row
-    pub fn row(mut self, row: GridRow) -> Self {
-        self.rows.push(row);
-        self.recompute_cache();
-        self
+    pub fn row(self, row: GridRow) -> Self {
+        self.rows(vec![row])
     }
 
     /// Add rows
     pub fn rows(mut self, rows: Vec<GridRow>) -> Self {
+        let start = self.rows.len();
         self.rows.extend(rows);
+        self.parse_cells_from(start);
         self.recompute_cache();
         self
     }
 
+    /// Parse the typed cells of rows from `start` on
+    fn parse_cells_from(&mut self, start: usize) {
+        for row in &mut self.rows[start..] {
+            row.parse_cells(&self.columns);
+        }
+    }
+
+    /// Apply a row change from a `SignalVec`
+    ///
+    /// Use with [`SignalVec::diff_queue()`](crate::reactive::SignalVec::diff_queue)
+    /// to keep the grid in sync with a reactive (possibly derived) vector.
+    /// Active filters and sorting are re-applied to the updated rows.
+    pub fn apply_diff(&mut self, diff: crate::reactive::VecDiff<GridRow>) {
+        diff.apply_to(&mut self.rows);
+        // Rows kept from before are already parsed and skip quickly
+        self.parse_cells_from(0);
+        self.recompute_cache();
+    }
+
     /// Set data from 2D vector
     pub fn data(mut self, data: Vec<Vec<String>>) -> Self {
+        let start = self.rows.len();
         for row_data in data {
             let mut row = GridRow::new();
             for (i, value) in row_data.into_iter().enumerate() {
@@ -318,6 +390,7 @@ impl DataGrid {
             }
             self.rows.push(row);
         }
+        self.parse_cells_from(start);
         self.recompute_cache();
         self
     }
@@ -484,31 +557,65 @@ impl DataGrid {
     }
 
     /// Compute filtered row indices (internal)
+    ///
+    /// A row passes when it matches the text filter, every column filter and
+    /// the query.
     pub(super) fn compute_filtered_indices(&self) -> Vec<usize> {
-        if self.filter.is_empty() {
-            (0..self.rows.len()).collect()
-        } else {
-            self.rows
-                .iter()
-                .enumerate()
-                .filter(|(_, row)| match self.filter_column {
-                    Some(col_idx) => {
-                        if let Some(col) = self.columns.get(col_idx) {
-                            row.get(&col.key)
-                                .map(|v| v.to_lowercase().contains(&self.filter))
-                                .unwrap_or(false)
-                        } else {
-                            false
-                        }
+        let column_filters: Vec<_> = self
+            .column_filters
+            .iter()
+            .filter_map(|(key, filter)| {
+                self.columns
+                    .iter()
+                    .find(|c| &c.key == key)
+                    .map(|col| (col, filter))
+            })
+            .collect();
+        if self.filter.is_empty() && column_filters.is_empty() && self.query.is_none() {
+            return (0..self.rows.len()).collect();
+        }
+
+        let matches_text = |row: &GridRow| {
+            if self.filter.is_empty() {
+                return true;
+            }
+            match self.filter_column {
+                Some(col_idx) => {
+                    if let Some(col) = self.columns.get(col_idx) {
+                        row.get(&col.key)
+                            .map(|v| v.to_lowercase().contains(&self.filter))
+                            .unwrap_or(false)
+                    } else {
+                        false
                     }
-                    None => row
-                        .data
-                        .iter()
-                        .any(|(_, v)| v.to_lowercase().contains(&self.filter)),
+                }
+                None => row
+                    .data
+                    .iter()
+                    .any(|(_, v)| v.to_lowercase().contains(&self.filter)),
+            }
+        };
+        let matches_columns = |row: &GridRow| {
+            column_filters.iter().all(|(col, filter)| {
+                let text = row.get(&col.key).unwrap_or("");
+                filter.matches(text, &row.value(&col.key, col.col_type))
+            })
+        };
+        let matches_query = |row: &GridRow| {
+            self.query.as_ref().is_none_or(|query| {
+                query.matches(&QueryRow {
+                    row,
+                    columns: &self.columns,
                 })
-                .map(|(i, _)| i)
-                .collect()
-        }
+            })
+        };
+
+        self.rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| matches_text(row) && matches_columns(row) && matches_query(row))
+            .map(|(i, _)| i)
+            .collect()
     }
 
     /// Get cached filtered row indices (zero-cost, no allocation)
@@ -517,9 +624,12 @@ impl DataGrid {
         &self.filtered_cache
     }
 
-    /// Get filtered rows count (uses cache)
+    /// Get filtered rows count (uses cache, or the data source's count)
     pub fn filtered_count(&self) -> usize {
-        self.filtered_indices().len()
+        match &self.source {
+            Some(source) => source.len(),
+            None => self.filtered_indices().len(),
+        }
     }
 
     /// Get filtered rows (uses cached indices)
@@ -532,6 +642,37 @@ impl DataGrid {
     }
 }
 
+/// A row read through the grid's columns, for query matching
+///
+/// Fields are column keys or titles (case-insensitive) and values are typed
+/// by the column's [`ColumnType`](super::types::ColumnType).
+struct QueryRow<'a> {
+    row: &'a GridRow,
+    columns: &'a [GridColumn],
+}
+
+impl Queryable for QueryRow<'_> {
+    fn field_value(&self, field: &str) -> Option<QueryValue> {
+        let col = self
+            .columns
+            .iter()
+            .find(|c| c.key.eq_ignore_ascii_case(field))
+            .or_else(|| {
+                self.columns
+                    .iter()
+                    .find(|c| c.title.eq_ignore_ascii_case(field))
+            });
+        match col {
+            Some(col) => Some((&self.row.value(&col.key, col.col_type)).into()),
+            None => self.row.field_value(field),
+        }
+    }
+
+    fn full_text(&self) -> String {
+        self.row.full_text()
+    }
+}
+
 impl Default for DataGrid {
     fn default() -> Self {
         Self::new()
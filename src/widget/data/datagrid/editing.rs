@@ -38,9 +38,9 @@ impl DataGrid {
             return false;
         }
 
-        // Get actual row index from filtered cache (zero-copy access)
-        let row_idx = match self.filtered_cache.get(selected_row) {
-            Some(&idx) => idx,
+        // Get actual row index of the displayed row (None for group headers)
+        let row_idx = match self.display_row_index(selected_row) {
+            Some(idx) => idx,
             None => return false,
         };
 
@@ -82,11 +82,8 @@ impl DataGrid {
         let row = &mut self.rows[self.edit_state.row];
 
         // Update the cell value
-        if let Some(cell) = row.data.iter_mut().find(|(k, _)| k == &col_key) {
-            cell.1 = self.edit_state.buffer.clone();
-        } else {
-            row.data.push((col_key, self.edit_state.buffer.clone()));
-        }
+        row.set(&col_key, self.edit_state.buffer.clone());
+        row.parse_cells(&self.columns[self.edit_state.col..=self.edit_state.col]);
 
         self.edit_state.active = false;
         self.recompute_cache();
@@ -203,6 +200,11 @@ impl DataGrid {
 
     /// Handle key input
     pub fn handle_key(&mut self, key: &Key) -> bool {
+        // An open filter popover takes every key
+        if self.filter_popover.is_some() {
+            return self.handle_filter_key(key);
+        }
+
         // If editing, delegate to edit handler
         if self.edit_state.active {
             return self.handle_edit_key(key);
@@ -243,6 +245,10 @@ impl DataGrid {
                 self.select_last();
                 true
             }
+            Key::Enter if self.group_at(self.selected_row).is_some() => {
+                self.toggle_group();
+                true
+            }
             Key::Enter => {
                 // Try to start editing, fall back to sort
                 if !self.start_edit() {
@@ -250,6 +256,11 @@ impl DataGrid {
                 }
                 true
             }
+            Key::Char('f') => self.open_column_filter(self.selected_col),
+            Key::Char('F') if self.has_filters() => {
+                self.clear_filters();
+                true
+            }
             Key::Char(' ') if self.options.multi_select => {
                 self.toggle_selection();
                 true
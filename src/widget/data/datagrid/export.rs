@@ -82,7 +82,7 @@ impl DataGrid {
         let visible_cols: Vec<_> = self.columns.iter().filter(|c| c.visible).collect();
 
         if let Some(col) = visible_cols.get(self.selected_col) {
-            if let Some(&actual_idx) = self.filtered_indices().get(self.selected_row) {
+            if let Some(actual_idx) = self.display_row_index(self.selected_row) {
                 if let Some(row) = self.rows.get(actual_idx) {
                     return row.get(&col.key).unwrap_or("").to_string();
                 }
@@ -1,8 +1,8 @@
 //! DataGrid sorting and filtering
 
 use super::core::DataGrid;
-use super::types::SortDirection;
-use crate::utils::natural_cmp;
+use super::types::{CellValue, ColumnFilter, ColumnType, GridRow, SortDirection};
+use crate::query::{ParseError, Query};
 use std::cmp::Ordering;
 
 impl DataGrid {
@@ -27,38 +27,17 @@ impl DataGrid {
             self.sort_direction = SortDirection::Ascending;
         }
 
-        let key = &self.columns[column].key;
-        let col_type = self.columns[column].col_type;
-        let ascending = self.sort_direction == SortDirection::Ascending;
-        let use_natural = self.options.use_natural_sort;
-
-        self.rows.sort_by(|a, b| {
-            let va = a.get(key).unwrap_or("");
-            let vb = b.get(key).unwrap_or("");
-
-            let cmp = match col_type {
-                super::types::ColumnType::Number => {
-                    let na: f64 = va.parse().unwrap_or(0.0);
-                    let nb: f64 = vb.parse().unwrap_or(0.0);
-                    na.partial_cmp(&nb).unwrap_or(Ordering::Equal)
-                }
-                super::types::ColumnType::Text | super::types::ColumnType::Custom => {
-                    if use_natural {
-                        natural_cmp(va, vb)
-                    } else {
-                        va.cmp(vb)
-                    }
-                }
-                _ => va.cmp(vb),
-            };
+        if self.sort_source() {
+            return;
+        }
 
-            if ascending {
-                cmp
-            } else {
-                cmp.reverse()
-            }
-        });
-        self.recompute_cache();
+        let col = &self.columns[column];
+        let spec = vec![(
+            col.key.clone(),
+            col.col_type,
+            self.sort_direction == SortDirection::Ascending,
+        )];
+        self.sort_rows(&spec);
     }
 
     /// Add or toggle a column in the multi-column sort stack
@@ -98,7 +77,7 @@ impl DataGrid {
 
         if !self.sort_columns.is_empty() {
             self.apply_multi_sort();
-        } else {
+        } else if !self.sort_source() {
             self.recompute_cache();
         }
     }
@@ -107,7 +86,9 @@ impl DataGrid {
     pub fn clear_sort(&mut self) {
         self.sort_columns.clear();
         self.sort_column = None;
-        self.recompute_cache();
+        if !self.sort_source() {
+            self.recompute_cache();
+        }
     }
 
     /// Apply multi-column sort, sorting rows by all columns in priority order
@@ -116,7 +97,11 @@ impl DataGrid {
             return;
         }
 
-        // Build sort spec: (key, col_type, ascending, use_natural)
+        if self.sort_source() {
+            return;
+        }
+
+        // Build sort spec: (key, col_type, ascending)
         let sort_spec: Vec<_> = self
             .sort_columns
             .iter()
@@ -130,49 +115,150 @@ impl DataGrid {
                 })
             })
             .collect();
+        self.sort_rows(&sort_spec);
+    }
 
+    /// Sort rows by (key, col_type, ascending) in priority order
+    ///
+    /// Each row's typed values are computed once, so strings aren't
+    /// re-parsed on every comparison.
+    fn sort_rows(&mut self, spec: &[(String, ColumnType, bool)]) {
         let use_natural = self.options.use_natural_sort;
+        let mut keyed: Vec<(Vec<CellValue>, GridRow)> = std::mem::take(&mut self.rows)
+            .into_iter()
+            .map(|row| {
+                let keys = spec
+                    .iter()
+                    .map(|(key, col_type, _)| row.value(key, *col_type))
+                    .collect();
+                (keys, row)
+            })
+            .collect();
 
-        self.rows.sort_by(|a, b| {
-            for (key, col_type, ascending) in &sort_spec {
-                let va = a.get(key).unwrap_or("");
-                let vb = b.get(key).unwrap_or("");
-
-                let cmp = match col_type {
-                    super::types::ColumnType::Number => {
-                        let na: f64 = va.parse().unwrap_or(0.0);
-                        let nb: f64 = vb.parse().unwrap_or(0.0);
-                        na.partial_cmp(&nb).unwrap_or(Ordering::Equal)
-                    }
-                    super::types::ColumnType::Text | super::types::ColumnType::Custom => {
-                        if use_natural {
-                            natural_cmp(va, vb)
-                        } else {
-                            va.cmp(vb)
-                        }
-                    }
-                    _ => va.cmp(vb),
-                };
-
+        keyed.sort_by(|(a, _), (b, _)| {
+            for ((va, vb), (_, col_type, ascending)) in a.iter().zip(b).zip(spec) {
+                // Plain text compares as-is unless natural sort is on
+                let natural =
+                    use_natural && matches!(col_type, ColumnType::Text | ColumnType::Custom);
+                let cmp = va.compare(vb, natural);
                 let ordered = if *ascending { cmp } else { cmp.reverse() };
-
                 if ordered != Ordering::Equal {
                     return ordered;
                 }
             }
             Ordering::Equal
         });
+
+        self.rows = keyed.into_iter().map(|(_, row)| row).collect();
         self.recompute_cache();
     }
 
     /// Set filter (cancels any active edit)
     pub fn set_filter(&mut self, filter: impl Into<String>) {
+        self.filter = filter.into().to_lowercase();
+        self.refilter();
+    }
+
+    /// Filter a column
+    pub fn column_filter(mut self, key: impl Into<String>, filter: ColumnFilter) -> Self {
+        self.set_column_filter(key, filter);
+        self
+    }
+
+    /// Set a column's filter, replacing the one it had
+    pub fn set_column_filter(&mut self, key: impl Into<String>, filter: ColumnFilter) {
+        let key = key.into();
+        match self.column_filters.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = filter,
+            None => self.column_filters.push((key, filter)),
+        }
+        self.refilter();
+    }
+
+    /// Remove a column's filter
+    pub fn remove_column_filter(&mut self, key: &str) {
+        let before = self.column_filters.len();
+        self.column_filters.retain(|(k, _)| k != key);
+        if self.column_filters.len() != before {
+            self.refilter();
+        }
+    }
+
+    /// Remove all column filters
+    pub fn clear_column_filters(&mut self) {
+        if !self.column_filters.is_empty() {
+            self.column_filters.clear();
+            self.refilter();
+        }
+    }
+
+    /// Get a column's filter
+    pub fn get_column_filter(&self, key: &str) -> Option<&ColumnFilter> {
+        self.column_filters
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, filter)| filter)
+    }
+
+    /// Filter with the query DSL (an invalid query is ignored)
+    pub fn query(mut self, text: &str) -> Self {
+        let _ = self.set_query(text);
+        self
+    }
+
+    /// Filter with the query DSL, e.g. `status:!draft price:<100`
+    ///
+    /// Fields are column keys or titles, and values compare by the column's
+    /// type. Sorting and paging in the query are ignored. An empty query
+    /// clears it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(ParseError)` if the query is invalid; the current query
+    /// is kept.
+    pub fn set_query(&mut self, text: &str) -> Result<(), ParseError> {
+        let query = Query::parse(text)?;
+        self.query = (!query.filters.is_empty()).then_some(query);
+        self.query_text = if self.query.is_some() {
+            text.trim().to_string()
+        } else {
+            String::new()
+        };
+        self.refilter();
+        Ok(())
+    }
+
+    /// Remove the query
+    pub fn clear_query(&mut self) {
+        if self.query.take().is_some() {
+            self.query_text.clear();
+            self.refilter();
+        }
+    }
+
+    /// Check if any filter (text, column or query) is active
+    pub fn has_filters(&self) -> bool {
+        !self.filter.is_empty() || !self.column_filters.is_empty() || self.query.is_some()
+    }
+
+    /// Remove every filter
+    pub fn clear_filters(&mut self) {
+        self.filter.clear();
+        self.column_filters.clear();
+        self.query = None;
+        self.query_text.clear();
+        self.refilter();
+    }
+
+    /// Re-apply the filters (cancels any active edit)
+    fn refilter(&mut self) {
         // Cancel any active edit before filtering (row visibility will change)
         if self.edit_state.active {
             self.cancel_edit();
         }
-        self.filter = filter.into().to_lowercase();
-        self.recompute_cache();
+        if !self.filter_source() {
+            self.recompute_cache();
+        }
         // Reset selection to avoid pointing past filtered results
         self.selected_row = 0;
         self.scroll_row = 0;
@@ -0,0 +1,741 @@
+//! DataGrid filter popovers and filter chips
+//!
+//! A filter popover edits one column's [`ColumnFilter`] with a form suited
+//! to the column's type: a text box (contains, or a pattern) that Tab turns
+//! into a checklist of the column's values, min/max boxes for numbers and
+//! dates, or any/yes/no for booleans. Active column filters and the query
+//! are shown as chips in a bar above the header; clicking one removes it.
+
+use std::cell::Cell as StdCell;
+use std::collections::BTreeSet;
+
+use super::core::{ColumnSlot, DataGrid};
+use super::types::{CellValue, ColumnFilter, ColumnType};
+use crate::event::Key;
+use crate::layout::Rect;
+use crate::render::{Cell, Modifier};
+use crate::style::Color;
+use crate::utils::{char_width, display_width, truncate_to_width};
+use crate::widget::theme::DISABLED_FG;
+use crate::widget::traits::RenderContext;
+
+/// Checklist rows shown at once
+const VALUES_VISIBLE: usize = 8;
+
+/// Smallest popover width
+const POPOVER_MIN_WIDTH: u16 = 28;
+
+/// A one-line text box
+#[derive(Clone, Debug, Default)]
+pub(super) struct FilterInput {
+    /// Text
+    pub text: String,
+    /// Cursor position (in chars)
+    pub cursor: usize,
+}
+
+impl FilterInput {
+    fn new(text: String) -> Self {
+        Self {
+            cursor: text.chars().count(),
+            text,
+        }
+    }
+
+    /// Byte offset of the char at `cursor`
+    fn byte_pos(&self, cursor: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(cursor)
+            .map_or(self.text.len(), |(i, _)| i)
+    }
+
+    fn handle_key(&mut self, key: &Key) -> bool {
+        let len = self.text.chars().count();
+        match key {
+            Key::Char(ch) => {
+                let pos = self.byte_pos(self.cursor);
+                self.text.insert(pos, *ch);
+                self.cursor += 1;
+            }
+            Key::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                let pos = self.byte_pos(self.cursor);
+                self.text.remove(pos);
+            }
+            Key::Delete if self.cursor < len => {
+                let pos = self.byte_pos(self.cursor);
+                self.text.remove(pos);
+            }
+            Key::Left => self.cursor = self.cursor.saturating_sub(1),
+            Key::Right => self.cursor = (self.cursor + 1).min(len),
+            Key::Home => self.cursor = 0,
+            Key::End => self.cursor = len,
+            Key::Backspace | Key::Delete => {}
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Form of a filter popover
+#[derive(Clone, Debug)]
+pub(super) enum FilterForm {
+    /// Text contains, or matches a pattern
+    Text { input: FilterInput, regex: bool },
+    /// Number or date range
+    Range {
+        min: FilterInput,
+        max: FilterInput,
+        focus_max: bool,
+    },
+    /// Any (`None`), yes or no
+    Boolean { choice: Option<bool> },
+    /// Checklist of the column's distinct values
+    Values {
+        items: Vec<(String, bool)>,
+        cursor: usize,
+        scroll: usize,
+    },
+}
+
+/// Open column filter popover
+#[derive(Clone, Debug)]
+pub(super) struct FilterPopover {
+    /// Column being filtered (index into `columns`)
+    pub column: usize,
+    /// Form contents
+    pub form: FilterForm,
+    /// The range couldn't be parsed
+    pub invalid: bool,
+    /// Where the popover was last drawn (relative to the grid)
+    pub area: StdCell<Rect>,
+}
+
+/// How a popover line is drawn
+enum LineStyle {
+    Label,
+    /// Text box, with the cursor when focused
+    Input(Option<usize>),
+    Selected,
+    Hint,
+    Error,
+}
+
+/// What a filter chip removes
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum FilterChip {
+    /// A column filter (column key)
+    Column(String),
+    /// The query
+    Query,
+}
+
+impl DataGrid {
+    // ─────────────────────────────────────────────────────────────────────────
+    // Filter popover
+    // ─────────────────────────────────────────────────────────────────────────
+
+    /// Open the filter popover of a column
+    ///
+    /// Returns false if the column doesn't exist or isn't filterable.
+    pub fn open_column_filter(&mut self, col: usize) -> bool {
+        if !self.columns.get(col).is_some_and(|c| c.filterable) {
+            return false;
+        }
+        if self.edit_state.active {
+            self.cancel_edit();
+        }
+
+        let column = &self.columns[col];
+        let current = self.get_column_filter(&column.key);
+        let bound = |value: Option<CellValue>| {
+            FilterInput::new(value.map_or_else(String::new, |v| v.to_string()))
+        };
+        let form = match (column.col_type, current) {
+            (ColumnType::Number, Some(ColumnFilter::NumberRange(min, max))) => FilterForm::Range {
+                min: bound(min.map(CellValue::Number)),
+                max: bound(max.map(CellValue::Number)),
+                focus_max: false,
+            },
+            (ColumnType::Date, Some(ColumnFilter::DateRange(from, to))) => FilterForm::Range {
+                min: bound(from.map(CellValue::Date)),
+                max: bound(to.map(CellValue::Date)),
+                focus_max: false,
+            },
+            (ColumnType::Number | ColumnType::Date, _) => FilterForm::Range {
+                min: FilterInput::default(),
+                max: FilterInput::default(),
+                focus_max: false,
+            },
+            (ColumnType::Boolean, current) => FilterForm::Boolean {
+                choice: match current {
+                    Some(ColumnFilter::Boolean(b)) => Some(*b),
+                    _ => None,
+                },
+            },
+            (_, Some(ColumnFilter::Values(_))) => self.values_form(col),
+            (_, Some(ColumnFilter::Regex(pattern))) => FilterForm::Text {
+                input: FilterInput::new(pattern.as_str().to_string()),
+                regex: true,
+            },
+            (_, Some(ColumnFilter::Contains(text))) => FilterForm::Text {
+                input: FilterInput::new(text.clone()),
+                regex: false,
+            },
+            _ => FilterForm::Text {
+                input: FilterInput::default(),
+                regex: false,
+            },
+        };
+
+        self.filter_popover = Some(FilterPopover {
+            column: col,
+            form,
+            invalid: false,
+            area: StdCell::new(Rect::default()),
+        });
+        true
+    }
+
+    /// Close the filter popover without applying it
+    pub fn close_column_filter(&mut self) {
+        self.filter_popover = None;
+    }
+
+    /// Check if a filter popover is open
+    pub fn is_column_filter_open(&self) -> bool {
+        self.filter_popover.is_some()
+    }
+
+    /// Checklist of a column's distinct values, checked per its filter
+    fn values_form(&self, col: usize) -> FilterForm {
+        let column = &self.columns[col];
+        let mut values: Vec<(CellValue, &str)> = Vec::new();
+        let mut seen = BTreeSet::new();
+        for row in &self.rows {
+            let text = row.get(&column.key).unwrap_or("");
+            if seen.insert(text) {
+                values.push((row.value(&column.key, column.col_type), text));
+            }
+        }
+        values.sort_by(|(a, _), (b, _)| a.compare(b, true));
+
+        let checked = |text: &str| match self.get_column_filter(&column.key) {
+            Some(ColumnFilter::Values(set)) => set.contains(text),
+            _ => true,
+        };
+        FilterForm::Values {
+            items: values
+                .into_iter()
+                .map(|(_, text)| (text.to_string(), checked(text)))
+                .collect(),
+            cursor: 0,
+            scroll: 0,
+        }
+    }
+
+    /// Handle a key while the filter popover is open
+    pub(super) fn handle_filter_key(&mut self, key: &Key) -> bool {
+        match key {
+            Key::Escape => {
+                self.close_column_filter();
+                return true;
+            }
+            Key::Enter => {
+                self.apply_column_filter();
+                return true;
+            }
+            _ => {}
+        }
+
+        // Tab from a pattern switches to the checklist of the column's values
+        if *key == Key::Tab {
+            if let Some(FilterPopover {
+                column,
+                form: FilterForm::Text { regex: true, .. },
+                ..
+            }) = &self.filter_popover
+            {
+                let form = self.values_form(*column);
+                if let Some(popover) = &mut self.filter_popover {
+                    popover.form = form;
+                }
+                return true;
+            }
+        }
+
+        let Some(popover) = &mut self.filter_popover else {
+            return false;
+        };
+        if *key == Key::Tab {
+            match &mut popover.form {
+                FilterForm::Text { regex, .. } => *regex = true,
+                FilterForm::Values { .. } => {
+                    popover.form = FilterForm::Text {
+                        input: FilterInput::default(),
+                        regex: false,
+                    };
+                }
+                FilterForm::Range { focus_max, .. } => *focus_max = !*focus_max,
+                FilterForm::Boolean { choice } => *choice = next_choice(*choice),
+            }
+            return true;
+        }
+
+        popover.invalid = false;
+        match &mut popover.form {
+            FilterForm::Text { input, .. } => input.handle_key(key),
+            FilterForm::Range {
+                min,
+                max,
+                focus_max,
+            } => match key {
+                Key::Up | Key::Down | Key::BackTab => {
+                    *focus_max = !*focus_max;
+                    true
+                }
+                _ if *focus_max => max.handle_key(key),
+                _ => min.handle_key(key),
+            },
+            FilterForm::Boolean { choice } => match key {
+                Key::Down | Key::Right | Key::Char(' ') => {
+                    *choice = next_choice(*choice);
+                    true
+                }
+                Key::Up | Key::Left => {
+                    *choice = next_choice(next_choice(*choice));
+                    true
+                }
+                _ => false,
+            },
+            FilterForm::Values {
+                items,
+                cursor,
+                scroll,
+            } => {
+                match key {
+                    Key::Up => *cursor = cursor.saturating_sub(1),
+                    Key::Down => *cursor = (*cursor + 1).min(items.len().saturating_sub(1)),
+                    Key::Home => *cursor = 0,
+                    Key::End => *cursor = items.len().saturating_sub(1),
+                    Key::Char(' ') => {
+                        if let Some(item) = items.get_mut(*cursor) {
+                            item.1 = !item.1;
+                        }
+                    }
+                    Key::Char('a') => {
+                        let all = items.iter().all(|(_, checked)| *checked);
+                        items.iter_mut().for_each(|item| item.1 = !all);
+                    }
+                    _ => return false,
+                }
+                // Keep the cursor in view
+                if *cursor < *scroll {
+                    *scroll = *cursor;
+                } else if *cursor >= *scroll + VALUES_VISIBLE {
+                    *scroll = *cursor + 1 - VALUES_VISIBLE;
+                }
+                true
+            }
+        }
+    }
+
+    /// Apply the popover's form as the column's filter and close it
+    ///
+    /// An empty form (blank text, open range, "any", every value checked)
+    /// removes the filter. Returns false, leaving the popover open, if a
+    /// range bound doesn't parse.
+    pub fn apply_column_filter(&mut self) -> bool {
+        let Some(popover) = &mut self.filter_popover else {
+            return false;
+        };
+        let Some(column) = self.columns.get(popover.column) else {
+            self.filter_popover = None;
+            return false;
+        };
+
+        let filter = match &popover.form {
+            FilterForm::Text { input, .. } if input.text.is_empty() => None,
+            FilterForm::Text { input, regex: true } => Some(ColumnFilter::regex(&input.text)),
+            FilterForm::Text { input, .. } => Some(ColumnFilter::contains(&input.text)),
+            FilterForm::Range { min, max, .. } => {
+                let parse = |input: &FilterInput| {
+                    if input.text.trim().is_empty() {
+                        return Ok(CellValue::Empty);
+                    }
+                    match CellValue::parse(&input.text, column.col_type) {
+                        CellValue::Text(_) => Err(()),
+                        value => Ok(value),
+                    }
+                };
+                let (Ok(min), Ok(max)) = (parse(min), parse(max)) else {
+                    popover.invalid = true;
+                    return false;
+                };
+                match (column.col_type, min.is_empty() && max.is_empty()) {
+                    (_, true) => None,
+                    (ColumnType::Date, _) => {
+                        Some(ColumnFilter::date_range(min.as_date(), max.as_date()))
+                    }
+                    _ => Some(ColumnFilter::number_range(min.as_number(), max.as_number())),
+                }
+            }
+            FilterForm::Boolean { choice } => choice.map(ColumnFilter::Boolean),
+            FilterForm::Values { items, .. } if items.iter().all(|(_, checked)| *checked) => None,
+            FilterForm::Values { items, .. } => Some(ColumnFilter::values(
+                items
+                    .iter()
+                    .filter(|(_, checked)| *checked)
+                    .map(|(value, _)| value.clone()),
+            )),
+        };
+
+        let key = column.key.clone();
+        self.filter_popover = None;
+        match filter {
+            Some(filter) => self.set_column_filter(key, filter),
+            None => self.remove_column_filter(&key),
+        }
+        true
+    }
+
+    /// Handle a click while the popover is open (`x`/`y` relative to the grid)
+    ///
+    /// Clicking a checklist row toggles it; clicking outside closes the
+    /// popover.
+    pub(super) fn click_filter_popover(&mut self, x: u16, y: u16) -> bool {
+        let Some(popover) = &mut self.filter_popover else {
+            return false;
+        };
+        let area = popover.area.get();
+        if !area.contains(x, y) {
+            self.filter_popover = None;
+            return true;
+        }
+        if let FilterForm::Values {
+            items,
+            cursor,
+            scroll,
+        } = &mut popover.form
+        {
+            // Body lines start below the top border
+            let line = (y - area.y) as usize;
+            if (1..=VALUES_VISIBLE).contains(&line) {
+                let idx = *scroll + line - 1;
+                if let Some(item) = items.get_mut(idx) {
+                    item.1 = !item.1;
+                    *cursor = idx;
+                }
+            }
+        }
+        true
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Filter chips
+    // ─────────────────────────────────────────────────────────────────────────
+
+    /// Height of the filter chip bar (shown while column filters or a query
+    /// are active)
+    pub(super) fn filter_bar_height(&self) -> u16 {
+        if self.column_filters.is_empty() && self.query.is_none() {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// Height of the bars above the header
+    pub(super) fn top_bars_height(&self) -> u16 {
+        self.group_panel_height() + self.filter_bar_height()
+    }
+
+    /// Chips of the filter bar: (x offset, label, what it removes)
+    pub(super) fn filter_chips(&self) -> Vec<(u16, String, FilterChip)> {
+        let mut labels: Vec<(String, FilterChip)> = self
+            .column_filters
+            .iter()
+            .map(|(key, filter)| {
+                let title = self
+                    .columns
+                    .iter()
+                    .find(|c| &c.key == key)
+                    .map_or(key.as_str(), |c| c.title.as_str());
+                (
+                    format!(" {}: {} ✕ ", title, filter),
+                    FilterChip::Column(key.clone()),
+                )
+            })
+            .collect();
+        if self.query.is_some() {
+            labels.push((format!(" {} ✕ ", self.query_text), FilterChip::Query));
+        }
+
+        let mut x: u16 = 1;
+        labels
+            .into_iter()
+            .map(|(label, chip)| {
+                let start = x;
+                x = x.saturating_add(display_width(&label) as u16 + 1);
+                (start, label, chip)
+            })
+            .collect()
+    }
+
+    /// Remove the filter whose chip is at `x` (relative to the grid)
+    pub(super) fn click_filter_chip(&mut self, x: u16) -> bool {
+        let hit = self.filter_chips().into_iter().find(|(start, label, _)| {
+            x >= *start && x < start.saturating_add(display_width(label) as u16)
+        });
+        match hit {
+            Some((_, _, FilterChip::Column(key))) => {
+                self.remove_column_filter(&key);
+                true
+            }
+            Some((_, _, FilterChip::Query)) => {
+                self.clear_query();
+                true
+            }
+            None => false,
+        }
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Rendering
+    // ─────────────────────────────────────────────────────────────────────────
+
+    /// Render the filter chip bar
+    pub(super) fn render_filter_bar(&self, ctx: &mut RenderContext, width: u16, y: u16) {
+        for x in 0..width {
+            let mut cell = Cell::new(' ');
+            cell.bg = Some(self.colors.header_bg);
+            ctx.set(x, y, cell);
+        }
+        for (x, label, _) in self.filter_chips() {
+            if x >= width {
+                break;
+            }
+            self.draw_popover_text(
+                ctx,
+                &label,
+                (x, y, width - x),
+                self.colors.header_fg,
+                self.colors.selected_bg,
+                Modifier::BOLD,
+            );
+        }
+    }
+
+    /// Render the filter popover below its column's header
+    pub(super) fn render_filter_popover(
+        &self,
+        ctx: &mut RenderContext,
+        slots: &[ColumnSlot<'_>],
+        row_num_width: u16,
+        top: u16,
+    ) {
+        let Some(popover) = &self.filter_popover else {
+            return;
+        };
+        let Some(column) = self.columns.get(popover.column) else {
+            return;
+        };
+        let area = ctx.area;
+        let lines = self.popover_lines(popover, column.col_type);
+
+        let anchor = slots.iter().find(|s| s.orig_idx == popover.column);
+        let width = anchor
+            .map_or(0, |s| s.width + 1)
+            .max(POPOVER_MIN_WIDTH)
+            .min(area.width);
+        let x = anchor
+            .map_or(row_num_width, |s| s.x)
+            .min(area.width - width);
+        let height = (lines.len() as u16 + 2).min(area.height.saturating_sub(top));
+        if height < 3 {
+            return;
+        }
+        popover.area.set(Rect::new(x, top, width, height));
+
+        // Frame, with the column title in the top border
+        let bg = self.colors.row_bg;
+        let border = self.colors.border_color;
+        let inner = width - 2;
+        for dy in 0..height {
+            for dx in 0..width {
+                let ch = match (dx, dy) {
+                    (0, 0) => '┌',
+                    (dx, 0) if dx == width - 1 => '┐',
+                    (0, dy) if dy == height - 1 => '└',
+                    (dx, dy) if dx == width - 1 && dy == height - 1 => '┘',
+                    (_, 0) => '─',
+                    (_, dy) if dy == height - 1 => '─',
+                    (0, _) => '│',
+                    (dx, _) if dx == width - 1 => '│',
+                    _ => ' ',
+                };
+                let mut cell = Cell::new(ch);
+                cell.fg = Some(border);
+                cell.bg = Some(bg);
+                ctx.set(x + dx, top + dy, cell);
+            }
+        }
+        let title = format!(" Filter {} ", column.title);
+        self.draw_popover_text(
+            ctx,
+            &title,
+            (x + 1, top, inner),
+            self.colors.header_fg,
+            bg,
+            Modifier::BOLD,
+        );
+
+        for (i, (text, style)) in lines.iter().enumerate().take(height as usize - 2) {
+            let y = top + 1 + i as u16;
+            let (fg, line_bg, modifier) = match style {
+                LineStyle::Label => (self.colors.header_fg, bg, Modifier::empty()),
+                LineStyle::Input(_) => (
+                    self.colors.header_fg,
+                    self.colors.header_bg,
+                    Modifier::empty(),
+                ),
+                LineStyle::Selected => (
+                    self.colors.selected_fg,
+                    self.colors.selected_bg,
+                    Modifier::empty(),
+                ),
+                LineStyle::Hint => (DISABLED_FG, bg, Modifier::empty()),
+                LineStyle::Error => (Color::RED, bg, Modifier::empty()),
+            };
+            for dx in 0..inner {
+                let mut cell = Cell::new(' ');
+                cell.bg = Some(line_bg);
+                ctx.set(x + 1 + dx, y, cell);
+            }
+            self.draw_popover_text(ctx, text, (x + 1, y, inner), fg, line_bg, modifier);
+
+            // Cursor: the char under it, reversed (after the leading space)
+            if let LineStyle::Input(Some(cursor)) = style {
+                let before: String = text.chars().take(cursor + 1).collect();
+                let cx = display_width(&before) as u16;
+                if cx < inner {
+                    let ch = text.chars().nth(cursor + 1).unwrap_or(' ');
+                    let mut cell = Cell::new(ch);
+                    cell.fg = Some(fg);
+                    cell.bg = Some(line_bg);
+                    cell.modifier |= Modifier::REVERSE;
+                    ctx.set(x + 1 + cx, y, cell);
+                }
+            }
+        }
+    }
+
+    /// Lines of the popover's body
+    fn popover_lines(
+        &self,
+        popover: &FilterPopover,
+        col_type: ColumnType,
+    ) -> Vec<(String, LineStyle)> {
+        let mut lines = Vec::new();
+        match &popover.form {
+            FilterForm::Text { input, regex } => {
+                let label = if *regex {
+                    " Matches pattern"
+                } else {
+                    " Contains"
+                };
+                lines.push((label.to_string(), LineStyle::Label));
+                lines.push((
+                    format!(" {}", input.text),
+                    LineStyle::Input(Some(input.cursor)),
+                ));
+                lines.push((" Tab: mode  ⏎ apply  Esc".to_string(), LineStyle::Hint));
+            }
+            FilterForm::Range {
+                min,
+                max,
+                focus_max,
+            } => {
+                let (from, to, error) = if col_type == ColumnType::Date {
+                    (" From (YYYY-MM-DD)", " To", " Invalid date")
+                } else {
+                    (" Min", " Max", " Invalid number")
+                };
+                let cursor = |input: &FilterInput, focused: bool| {
+                    LineStyle::Input(focused.then_some(input.cursor))
+                };
+                lines.push((from.to_string(), LineStyle::Label));
+                lines.push((format!(" {}", min.text), cursor(min, !*focus_max)));
+                lines.push((to.to_string(), LineStyle::Label));
+                lines.push((format!(" {}", max.text), cursor(max, *focus_max)));
+                if popover.invalid {
+                    lines.push((error.to_string(), LineStyle::Error));
+                }
+                lines.push((" Tab: next  ⏎ apply  Esc".to_string(), LineStyle::Hint));
+            }
+            FilterForm::Boolean { choice } => {
+                for (label, value) in [("Any", None), ("Yes", Some(true)), ("No", Some(false))] {
+                    let (mark, style) = if *choice == value {
+                        ('●', LineStyle::Selected)
+                    } else {
+                        ('○', LineStyle::Label)
+                    };
+                    lines.push((format!(" {} {}", mark, label), style));
+                }
+                lines.push((" ↑↓ choose  ⏎ apply  Esc".to_string(), LineStyle::Hint));
+            }
+            FilterForm::Values {
+                items,
+                cursor,
+                scroll,
+            } => {
+                for (i, (value, checked)) in
+                    items.iter().enumerate().skip(*scroll).take(VALUES_VISIBLE)
+                {
+                    let mark = if *checked { "[x]" } else { "[ ]" };
+                    let value = if value.is_empty() { "(blank)" } else { value };
+                    let style = if i == *cursor {
+                        LineStyle::Selected
+                    } else {
+                        LineStyle::Label
+                    };
+                    lines.push((format!(" {} {}", mark, value), style));
+                }
+                lines.push((" Space: toggle  a: all  ⏎".to_string(), LineStyle::Hint));
+            }
+        }
+        lines
+    }
+
+    /// Draw text clipped to `(x, y, width)`
+    fn draw_popover_text(
+        &self,
+        ctx: &mut RenderContext,
+        text: &str,
+        (x, y, width): (u16, u16, u16),
+        fg: Color,
+        bg: Color,
+        modifier: Modifier,
+    ) {
+        let text = truncate_to_width(text, width as usize);
+        let mut dx: u16 = 0;
+        for ch in text.chars() {
+            let mut cell = Cell::new(ch);
+            cell.fg = Some(fg);
+            cell.bg = Some(bg);
+            cell.modifier |= modifier;
+            ctx.set(x + dx, y, cell);
+            dx += char_width(ch) as u16;
+        }
+    }
+}
+
+/// Cycle any → yes → no
+fn next_choice(choice: Option<bool>) -> Option<bool> {
+    match choice {
+        None => Some(true),
+        Some(true) => Some(false),
+        Some(false) => None,
+    }
+}
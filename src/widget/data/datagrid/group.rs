@@ -0,0 +1,299 @@
+//! DataGrid row grouping
+//!
+//! Grouping runs after sorting and filtering: the filtered rows are split by
+//! the values of the `group_by` columns, keeping their sorted order, so
+//! groups appear in the order of their first row. Each group gets a header
+//! row with its row count and the `group_aggregations`.
+
+use std::collections::HashMap;
+
+use super::core::DataGrid;
+use super::types::{ColumnAggregation, ColumnType, DisplayRow, GroupHeader};
+use crate::utils::display_width;
+
+impl DataGrid {
+    // ─────────────────────────────────────────────────────────────────────────
+    // Grouping API
+    // ─────────────────────────────────────────────────────────────────────────
+
+    /// Group rows by a column (call again for nested levels)
+    pub fn group_by(mut self, key: impl Into<String>) -> Self {
+        self.add_group(key);
+        self
+    }
+
+    /// Add an aggregate to group header rows
+    pub fn group_aggregate(mut self, agg: ColumnAggregation) -> Self {
+        self.group_aggregations.push(agg);
+        self.rebuild_group_cache();
+        self
+    }
+
+    /// Show the group panel, so columns can be dragged onto it to group by them
+    pub fn groupable(mut self, enable: bool) -> Self {
+        self.groupable = enable;
+        self
+    }
+
+    /// Add a grouping level (innermost)
+    pub fn add_group(&mut self, key: impl Into<String>) {
+        let key = key.into();
+        if self.group_by.contains(&key) {
+            return;
+        }
+        self.group_by.push(key);
+        self.regroup();
+    }
+
+    /// Remove a grouping level
+    pub fn remove_group(&mut self, key: &str) {
+        let before = self.group_by.len();
+        self.group_by.retain(|k| k != key);
+        if self.group_by.len() != before {
+            self.regroup();
+        }
+    }
+
+    /// Remove all grouping
+    pub fn clear_groups(&mut self) {
+        if !self.group_by.is_empty() {
+            self.group_by.clear();
+            self.regroup();
+        }
+    }
+
+    /// Check if rows are grouped
+    ///
+    /// Grouping needs every row, so it is off while a data source is set.
+    pub fn is_grouped(&self) -> bool {
+        !self.group_by.is_empty() && self.source.is_none()
+    }
+
+    /// Toggle the group whose header is selected
+    pub fn toggle_group(&mut self) {
+        if let Some(group) = self.group_at(self.selected_row) {
+            let path = group.path.clone();
+            let collapsed = !group.collapsed;
+            self.set_group_collapsed(path, collapsed);
+        }
+    }
+
+    /// Collapse or expand a group by its path (values from the outermost level)
+    pub fn set_group_collapsed(&mut self, path: Vec<String>, collapsed: bool) {
+        if collapsed {
+            self.collapsed_groups.insert(path);
+        } else {
+            self.collapsed_groups.remove(&path);
+        }
+        self.rebuild_group_cache();
+        self.clamp_selection();
+    }
+
+    /// Expand every group
+    pub fn expand_all_groups(&mut self) {
+        self.collapsed_groups.clear();
+        self.rebuild_group_cache();
+    }
+
+    /// Collapse every group, at every level
+    pub fn collapse_all_groups(&mut self) {
+        for &idx in &self.filtered_cache {
+            let Some(row) = self.rows.get(idx) else {
+                continue;
+            };
+            let mut path = Vec::with_capacity(self.group_by.len());
+            for key in &self.group_by {
+                path.push(row.get(key).unwrap_or("").to_string());
+                self.collapsed_groups.insert(path.clone());
+            }
+        }
+        self.rebuild_group_cache();
+        self.clamp_selection();
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Display rows
+    // ─────────────────────────────────────────────────────────────────────────
+
+    /// Number of displayed rows (group headers included)
+    pub fn display_count(&self) -> usize {
+        match &self.source {
+            Some(source) => source.len(),
+            None if self.is_grouped() => self.group_cache.len(),
+            None => self.filtered_cache.len(),
+        }
+    }
+
+    /// Index into `rows` of a displayed row (`None` for group headers)
+    pub fn display_row_index(&self, display_idx: usize) -> Option<usize> {
+        if self.is_grouped() {
+            match self.group_cache.get(display_idx)? {
+                DisplayRow::Row(idx) => Some(*idx),
+                DisplayRow::Group(_) => None,
+            }
+        } else {
+            self.filtered_cache.get(display_idx).copied()
+        }
+    }
+
+    /// Group header at a displayed row
+    pub fn group_at(&self, display_idx: usize) -> Option<&GroupHeader> {
+        match self.group_cache.get(display_idx)? {
+            DisplayRow::Group(group) if self.is_grouped() => Some(group),
+            _ => None,
+        }
+    }
+
+    /// Rebuild the grouped display rows (called on mutation)
+    pub fn rebuild_group_cache(&mut self) {
+        self.group_cache.clear();
+        if !self.is_grouped() {
+            return;
+        }
+        let mut cache = Vec::new();
+        self.build_groups(&self.filtered_cache, 0, &mut Vec::new(), &mut cache);
+        self.group_cache = cache;
+    }
+
+    /// Split rows into the groups of one level, recursing into expanded ones
+    fn build_groups(
+        &self,
+        indices: &[usize],
+        depth: usize,
+        path: &mut Vec<String>,
+        out: &mut Vec<DisplayRow>,
+    ) {
+        let Some(key) = self.group_by.get(depth) else {
+            out.extend(indices.iter().map(|&idx| DisplayRow::Row(idx)));
+            return;
+        };
+
+        // Groups in order of their first row
+        let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+        let mut positions: HashMap<&str, usize> = HashMap::new();
+        for &idx in indices {
+            let value = self.rows[idx].get(key).unwrap_or("");
+            let pos = *positions.entry(value).or_insert_with(|| {
+                groups.push((value.to_string(), Vec::new()));
+                groups.len() - 1
+            });
+            groups[pos].1.push(idx);
+        }
+
+        for (value, members) in groups {
+            path.push(value);
+            let collapsed = self.collapsed_groups.contains(path);
+            out.push(DisplayRow::Group(GroupHeader {
+                path: path.clone(),
+                column: key.clone(),
+                depth,
+                count: members.len(),
+                collapsed,
+                aggregates: self.group_aggregates(&members),
+            }));
+            if !collapsed {
+                self.build_groups(&members, depth + 1, path, out);
+            }
+            path.pop();
+        }
+    }
+
+    /// Format the group aggregates over some rows
+    fn group_aggregates(&self, members: &[usize]) -> Vec<(String, String)> {
+        self.group_aggregations
+            .iter()
+            .map(|agg| {
+                let col_type = self
+                    .columns
+                    .iter()
+                    .find(|c| c.key == agg.column_key)
+                    .map_or(ColumnType::Text, |c| c.col_type);
+                let values: Vec<_> = members
+                    .iter()
+                    .map(|&idx| self.rows[idx].value(&agg.column_key, col_type))
+                    .collect();
+                (agg.column_key.clone(), agg.format(&values))
+            })
+            .collect()
+    }
+
+    /// Rebuild groups and reset the selection after the grouping changed
+    fn regroup(&mut self) {
+        if self.edit_state.active {
+            self.cancel_edit();
+        }
+        self.rebuild_group_cache();
+        self.selected_row = 0;
+        self.scroll_row = 0;
+    }
+
+    /// Keep the selection within the displayed rows
+    fn clamp_selection(&mut self) {
+        let last = self.display_count().saturating_sub(1);
+        self.selected_row = self.selected_row.min(last);
+        self.scroll_row = self.scroll_row.min(self.selected_row);
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Group panel
+    // ─────────────────────────────────────────────────────────────────────────
+
+    /// Height of the group panel above the header
+    pub(super) fn group_panel_height(&self) -> u16 {
+        if self.groupable {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Text shown in the group panel when nothing is grouped
+    pub(super) const GROUP_PANEL_HINT: &'static str = " Drag a column here to group";
+
+    /// Chips of the group panel: (x offset, label, group key)
+    pub(super) fn group_chips(&self) -> Vec<(u16, String, String)> {
+        let mut x: u16 = 1;
+        let mut chips = Vec::with_capacity(self.group_by.len());
+        for key in &self.group_by {
+            let title = self
+                .columns
+                .iter()
+                .find(|c| &c.key == key)
+                .map_or(key.as_str(), |c| c.title.as_str());
+            let label = format!(" {} ✕ ", title);
+            let width = display_width(&label) as u16;
+            chips.push((x, label, key.clone()));
+            // Chip plus the " › " separator
+            x = x.saturating_add(width + 3);
+        }
+        chips
+    }
+
+    /// Drop the column being dragged onto the group panel
+    pub(super) fn drop_on_group_panel(&mut self) {
+        if let Some(key) = self
+            .dragging_col
+            .and_then(|col| self.columns.get(col))
+            .map(|col| col.key.clone())
+        {
+            self.add_group(key);
+        }
+        self.dragging_col = None;
+        self.drop_target_col = None;
+        self.group_drop_active = false;
+    }
+
+    /// Remove the grouping level whose chip is at `x` (relative to the grid)
+    pub(super) fn click_group_chip(&mut self, x: u16) -> bool {
+        let hit = self.group_chips().into_iter().find(|(start, label, _)| {
+            x >= *start && x < start.saturating_add(display_width(label) as u16)
+        });
+        match hit {
+            Some((_, _, key)) => {
+                self.remove_group(&key);
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -6,13 +6,17 @@ mod core;
 mod editing;
 mod export;
 mod filter;
+mod filter_ui;
 mod footer;
 mod freeze;
+mod group;
 mod mouse;
 mod navigation;
+mod pivot;
 mod render;
 mod reorder;
 mod resize;
+mod source;
 mod tree;
 mod types;
 mod width;
@@ -24,12 +28,14 @@ mod tests;
 // Re-export all types (explicit to avoid conflict with mod export)
 #[allow(unused_imports)]
 pub use types::{
-    AggregationType, Alignment, ColumnAggregation, ColumnType, ExportFormat, ExportOptions,
-    FooterRow, GridColors, GridColumn, GridOptions, GridRow, SortDirection,
+    AggregateFn, AggregationType, Alignment, CellValue, ColumnAggregation, ColumnFilter,
+    ColumnType, CustomAggregate, DisplayRow, ExportFormat, ExportOptions, FooterRow, GridColors,
+    GridColumn, GridOptions, GridRow, GroupHeader, PivotConfig, SortDirection, TextPattern,
 };
 
 // Re-export main widget
 pub use core::DataGrid;
+pub use source::GridDataSource;
 
 // Helper functions
 
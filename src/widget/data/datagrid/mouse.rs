@@ -9,15 +9,31 @@ impl DataGrid {
     ///
     /// Returns true if the event was handled.
     pub fn handle_mouse(&mut self, kind: MouseEventKind, x: u16, y: u16, area: Rect) -> bool {
+        // An open filter popover takes clicks; clicking outside closes it
+        if self.filter_popover.is_some() {
+            if let MouseEventKind::Down(_) = kind {
+                return self
+                    .click_filter_popover(x.saturating_sub(area.x), y.saturating_sub(area.y));
+            }
+        }
+
         match kind {
             MouseEventKind::Down(MouseButton::Left) => {
+                // Clicking a group chip removes that grouping level
+                if self.groupable && y == area.y {
+                    return self.click_group_chip(x.saturating_sub(area.x));
+                }
+                // Clicking a filter chip removes that filter
+                if self.filter_bar_height() > 0 && y == area.y + self.group_panel_height() {
+                    return self.click_filter_chip(x.saturating_sub(area.x));
+                }
                 // Check for resize handle first (higher priority)
                 if let Some(col) = self.hit_test_resize_handle(x, y, area) {
                     self.start_resize(col, x, area);
                     return true;
                 }
-                // Check for column header drag (reorder)
-                if self.reorderable {
+                // Check for column header drag (reorder or group)
+                if self.reorderable || self.groupable {
                     if let Some(col) = self.hit_test_header(x, y, area) {
                         self.start_column_drag(col);
                         return true;
@@ -25,13 +41,25 @@ impl DataGrid {
                 }
                 false
             }
+            MouseEventKind::Down(MouseButton::Right) => {
+                // Right-clicking a header opens its filter popover
+                match self.hit_test_header(x, y, area) {
+                    Some(col) => self.open_column_filter(col),
+                    None => false,
+                }
+            }
             MouseEventKind::Drag(MouseButton::Left) => {
                 if self.resizing_col.is_some() {
                     self.apply_resize_delta(x);
                     return true;
                 }
                 if self.dragging_col.is_some() {
-                    self.update_drop_target(x, area);
+                    self.group_drop_active = self.groupable && y == area.y;
+                    if self.group_drop_active {
+                        self.drop_target_col = None;
+                    } else {
+                        self.update_drop_target(x, area);
+                    }
                     return true;
                 }
                 false
@@ -42,7 +70,11 @@ impl DataGrid {
                     return true;
                 }
                 if self.dragging_col.is_some() {
-                    self.end_column_drag();
+                    if self.group_drop_active {
+                        self.drop_on_group_panel();
+                    } else {
+                        self.end_column_drag();
+                    }
                     return true;
                 }
                 false
@@ -60,7 +92,7 @@ impl DataGrid {
     /// Test if position is on a column resize handle
     pub(crate) fn hit_test_resize_handle(&self, x: u16, y: u16, area: Rect) -> Option<usize> {
         // Only detect in header row
-        if !self.options.show_header || y != area.y {
+        if !self.options.show_header || y != area.y + self.top_bars_height() {
             return None;
         }
 
@@ -87,7 +119,7 @@ impl DataGrid {
     /// Test if position is on a column header
     pub(crate) fn hit_test_header(&self, x: u16, y: u16, area: Rect) -> Option<usize> {
         // Only detect in header row
-        if !self.options.show_header || y != area.y {
+        if !self.options.show_header || y != area.y + self.top_bars_height() {
             return None;
         }
 
@@ -5,7 +5,7 @@ use super::core::DataGrid;
 impl DataGrid {
     /// Select next row (with auto-scroll)
     pub fn select_next(&mut self) {
-        let count = self.filtered_count();
+        let count = self.display_count();
         if self.selected_row < count.saturating_sub(1) {
             self.selected_row += 1;
             self.ensure_visible();
@@ -22,7 +22,7 @@ impl DataGrid {
 
     /// Page down
     pub fn page_down(&mut self, page_size: usize) {
-        let count = self.filtered_count();
+        let count = self.display_count();
         self.selected_row = (self.selected_row + page_size).min(count.saturating_sub(1));
         self.ensure_visible();
     }
@@ -41,7 +41,7 @@ impl DataGrid {
 
     /// Go to last row
     pub fn select_last(&mut self) {
-        let count = self.filtered_count();
+        let count = self.display_count();
         self.selected_row = count.saturating_sub(1);
         self.ensure_visible();
     }
@@ -71,18 +71,18 @@ impl DataGrid {
 
     /// Get scroll position info (current, total, viewport)
     pub fn scroll_info(&self) -> (usize, usize, usize) {
-        let total = self.filtered_count();
+        let total = self.display_count();
         (self.scroll_row, total, 20) // Default viewport, will be updated in render
     }
 
     /// Get total row count
     pub fn row_count(&self) -> usize {
-        self.filtered_count()
+        self.display_count()
     }
 
     /// Get visible row count
     pub fn visible_row_count(&self) -> usize {
-        self.filtered_count()
+        self.display_count()
     }
 
     /// Select next column
@@ -124,7 +124,7 @@ impl DataGrid {
     /// Toggle row selection
     pub fn toggle_selection(&mut self) {
         if self.options.multi_select {
-            if let Some(&actual_idx) = self.filtered_cache.get(self.selected_row) {
+            if let Some(actual_idx) = self.display_row_index(self.selected_row) {
                 if actual_idx < self.rows.len() {
                     self.rows[actual_idx].selected = !self.rows[actual_idx].selected;
                 }
@@ -0,0 +1,194 @@
+//! DataGrid pivot mode
+//!
+//! A pivot replaces the grid's columns and rows with a summary table built
+//! from the filtered rows, so sorting, filtering and grouping then work on
+//! the pivoted table. Clearing the pivot brings the original data back.
+
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use super::core::DataGrid;
+use super::types::{
+    AggregationType, CellValue, ColumnFilter, ColumnType, GridColumn, GridRow, PivotConfig,
+    SortDirection,
+};
+use crate::query::Query;
+
+/// The data and view state a pivot replaced
+pub(super) struct PivotState {
+    config: PivotConfig,
+    columns: Vec<GridColumn>,
+    rows: Vec<GridRow>,
+    column_order: Vec<usize>,
+    column_widths: Vec<u16>,
+    group_by: Vec<String>,
+    filter: String,
+    filter_column: Option<usize>,
+    column_filters: Vec<(String, ColumnFilter)>,
+    query: Option<Query>,
+    query_text: String,
+    sort_column: Option<usize>,
+    sort_direction: SortDirection,
+    sort_columns: Vec<(usize, SortDirection)>,
+}
+
+impl DataGrid {
+    /// Show a pivot of the rows
+    pub fn pivot(mut self, config: PivotConfig) -> Self {
+        self.set_pivot(config);
+        self
+    }
+
+    /// Show a pivot of the rows (mutable)
+    ///
+    /// Pivots the rows that pass the current filter. Not available with a
+    /// data source.
+    pub fn set_pivot(&mut self, config: PivotConfig) {
+        self.clear_pivot();
+        if self.source.is_some() {
+            return;
+        }
+        if self.edit_state.active {
+            self.cancel_edit();
+        }
+
+        let (columns, rows) = self.pivot_table(&config);
+        self.pivot = Some(PivotState {
+            config,
+            columns: mem::replace(&mut self.columns, columns),
+            rows: mem::replace(&mut self.rows, rows),
+            column_order: mem::take(&mut self.column_order),
+            column_widths: mem::take(&mut self.column_widths),
+            group_by: mem::take(&mut self.group_by),
+            filter: mem::take(&mut self.filter),
+            filter_column: self.filter_column.take(),
+            column_filters: mem::take(&mut self.column_filters),
+            query: self.query.take(),
+            query_text: mem::take(&mut self.query_text),
+            sort_column: self.sort_column.take(),
+            sort_direction: self.sort_direction,
+            sort_columns: mem::take(&mut self.sort_columns),
+        });
+        self.reset_view();
+    }
+
+    /// Leave pivot mode, restoring the original columns and rows
+    pub fn clear_pivot(&mut self) {
+        let Some(state) = self.pivot.take() else {
+            return;
+        };
+        if self.edit_state.active {
+            self.cancel_edit();
+        }
+        self.columns = state.columns;
+        self.rows = state.rows;
+        self.column_order = state.column_order;
+        self.column_widths = state.column_widths;
+        self.group_by = state.group_by;
+        self.filter = state.filter;
+        self.filter_column = state.filter_column;
+        self.column_filters = state.column_filters;
+        self.query = state.query;
+        self.query_text = state.query_text;
+        self.sort_column = state.sort_column;
+        self.sort_direction = state.sort_direction;
+        self.sort_columns = state.sort_columns;
+        self.reset_view();
+    }
+
+    /// Check if a pivot is shown
+    pub fn is_pivoted(&self) -> bool {
+        self.pivot.is_some()
+    }
+
+    /// Get the active pivot configuration
+    pub fn pivot_config(&self) -> Option<&PivotConfig> {
+        self.pivot.as_ref().map(|state| &state.config)
+    }
+
+    /// Build a pivot table from the filtered rows
+    ///
+    /// Returns the columns (the row column, then one per distinct value of
+    /// `column_key` in ascending order) and one row per distinct value of
+    /// `row_key`, in order of first appearance.
+    pub fn pivot_table(&self, config: &PivotConfig) -> (Vec<GridColumn>, Vec<GridRow>) {
+        let col_type = |key: &str| {
+            self.columns
+                .iter()
+                .find(|c| c.key == key)
+                .map_or(ColumnType::Text, |c| c.col_type)
+        };
+        let value_type = col_type(&config.value_key);
+        let pivot_type = col_type(&config.column_key);
+
+        // Distinct values of both keys, and the values in each cell
+        let mut row_values: Vec<String> = Vec::new();
+        let mut column_values: Vec<(String, CellValue)> = Vec::new();
+        let mut seen_rows = HashSet::new();
+        let mut seen_columns = HashSet::new();
+        let mut cells: HashMap<(String, String), Vec<CellValue>> = HashMap::new();
+        for &idx in self.filtered_indices() {
+            let Some(row) = self.rows.get(idx) else {
+                continue;
+            };
+            let row_value = row.get(&config.row_key).unwrap_or("").to_string();
+            let column_value = row.get(&config.column_key).unwrap_or("").to_string();
+            if seen_rows.insert(row_value.clone()) {
+                row_values.push(row_value.clone());
+            }
+            if seen_columns.insert(column_value.clone()) {
+                let typed = row.value(&config.column_key, pivot_type);
+                column_values.push((column_value.clone(), typed));
+            }
+            cells
+                .entry((row_value, column_value))
+                .or_default()
+                .push(row.value(&config.value_key, value_type));
+        }
+        column_values.sort_by(|(_, a), (_, b)| a.compare(b, true));
+
+        // Min/max keep the value column's type; the others are numbers
+        let cell_type = match config.aggregation {
+            AggregationType::Min | AggregationType::Max => value_type,
+            _ => ColumnType::Number,
+        };
+        let mut columns = vec![self
+            .columns
+            .iter()
+            .find(|c| c.key == config.row_key)
+            .cloned()
+            .unwrap_or_else(|| GridColumn::new(&config.row_key, &config.row_key))];
+        columns.extend(
+            column_values
+                .iter()
+                .map(|(value, _)| GridColumn::new(value, value).col_type(cell_type).right()),
+        );
+
+        let rows = row_values
+            .into_iter()
+            .map(|row_value| {
+                let mut row = GridRow::new().cell(&config.row_key, &row_value);
+                for (column_value, _) in &column_values {
+                    let key = (row_value.clone(), column_value.clone());
+                    let value = cells
+                        .get(&key)
+                        .map_or(CellValue::Empty, |values| config.aggregation.apply(values));
+                    row = row.typed(column_value, value);
+                }
+                row
+            })
+            .collect();
+
+        (columns, rows)
+    }
+
+    /// Reset selection and scroll after the columns and rows were swapped
+    fn reset_view(&mut self) {
+        self.filter_popover = None;
+        self.selected_row = 0;
+        self.selected_col = 0;
+        self.scroll_row = 0;
+        self.scroll_col = 0;
+        self.recompute_cache();
+    }
+}
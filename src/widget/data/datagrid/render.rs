@@ -1,7 +1,7 @@
 //! DataGrid rendering
 
 use super::core::{CellPos, CellState, ColumnSlot, DataGrid, RowRenderParams};
-use super::types::GridColumn;
+use super::types::{GridColumn, GroupHeader};
 use crate::layout::Rect;
 use crate::render::{Cell, Modifier};
 use crate::style::Color;
@@ -41,13 +41,14 @@ impl View for DataGrid {
 
         let row_num_width: u16 = if self.options.show_row_numbers {
             // Dynamic width: digits for total rows + 1 for separator
-            let total = self.filtered_count().max(1);
+            let total = self.display_count().max(1);
             let digits = format!("{}", total).len() as u16;
             digits + 2 // digits + space + separator
         } else {
             0
         };
-        let header_height: u16 = if self.options.show_header { 1 } else { 0 };
+        let header_height: u16 =
+            self.top_bars_height() + if self.options.show_header { 1 } else { 0 };
 
         // Position columns for this viewport, applying column freeze and
         // horizontal scroll. Both the header and the rows draw from this plan so
@@ -57,6 +58,18 @@ impl View for DataGrid {
 
         let mut y = 0u16;
 
+        // Draw group panel
+        if self.groupable {
+            self.render_group_panel(ctx, area.width, y);
+            y += 1;
+        }
+
+        // Draw filter chips
+        if self.filter_bar_height() > 0 {
+            self.render_filter_bar(ctx, area.width, y);
+            y += 1;
+        }
+
         // Draw header
         if self.options.show_header {
             self.render_header(ctx, &slots, content_end, row_num_width, y);
@@ -64,13 +77,14 @@ impl View for DataGrid {
         }
 
         // Calculate visible range with virtual scrolling
-        let total_rows = self.filtered_count();
-        let visible_height =
-            (area.height - header_height) as usize / self.options.row_height.max(1) as usize;
+        let total_rows = self.display_count();
+        let visible_height = area.height.saturating_sub(header_height) as usize
+            / self.options.row_height.max(1) as usize;
         self.last_viewport_height.set(visible_height);
 
-        // Virtual scroll: calculate render range with overscan
-        let (render_start, render_end) = if self.options.virtual_scroll {
+        // Virtual scroll: calculate render range with overscan (always on
+        // with a data source, which must not be fetched in full)
+        let (render_start, render_end) = if self.options.virtual_scroll || self.source.is_some() {
             let overscan = self.options.overscan;
             let start = self.scroll_row.saturating_sub(overscan);
             let end = (self.scroll_row + visible_height + overscan).min(total_rows);
@@ -93,6 +107,9 @@ impl View for DataGrid {
 
         // Draw scrollbar if needed
         self.render_scrollbar(ctx, total_rows, visible_height, area, y);
+
+        // Draw the filter popover over the rows
+        self.render_filter_popover(ctx, &slots, row_num_width, y);
     }
 }
 
@@ -192,7 +209,9 @@ impl DataGrid {
         render_end: usize,
         params: &RowRenderParams<'_, '_>,
     ) {
-        let filtered_indices = self.filtered_indices();
+        if let Some(source) = &self.source {
+            source.prefetch(render_start..render_end);
+        }
 
         for render_idx in render_start..render_end {
             // Skip rows above viewport (but within overscan)
@@ -200,13 +219,25 @@ impl DataGrid {
                 continue;
             }
 
-            // Get actual row index from filtered cache
-            let Some(&actual_row_idx) = filtered_indices.get(render_idx) else {
-                continue;
-            };
-
-            let Some(row) = self.rows.get(actual_row_idx) else {
-                continue;
+            // Get the row from the data source (None while loading), the
+            // group header, or the row by its actual index
+            let source_row;
+            let group = self.group_at(render_idx);
+            let row = match &self.source {
+                Some(source) => {
+                    source_row = source.row(render_idx);
+                    source_row.as_deref()
+                }
+                None if group.is_some() => None,
+                None => {
+                    let Some(actual_row_idx) = self.display_row_index(render_idx) else {
+                        continue;
+                    };
+                    let Some(row) = self.rows.get(actual_row_idx) else {
+                        continue;
+                    };
+                    Some(row)
+                }
             };
 
             // Calculate Y position relative to viewport
@@ -228,7 +259,7 @@ impl DataGrid {
             };
 
             // Draw row number
-            if self.options.show_row_numbers {
+            if self.options.show_row_numbers && group.is_none() {
                 self.render_row_number(ctx, params.area_x, row_y, render_idx + 1, row_bg);
             }
 
@@ -240,6 +271,16 @@ impl DataGrid {
                 ctx.set(gx, row_y, cell);
             }
 
+            if let Some(group) = group {
+                self.render_group_row(ctx, group, params, row_y, row_bg);
+                continue;
+            }
+
+            let Some(row) = row else {
+                self.render_placeholder(ctx, params, row_y, row_bg);
+                continue;
+            };
+
             // Draw cells from the positioned column slots.
             for slot in params.slots {
                 let is_editing = self.edit_state.active
@@ -265,6 +306,139 @@ impl DataGrid {
                 ctx.set(slot.x + slot.width, row_y, sep);
             }
         }
+
+        if let Some(source) = &self.source {
+            source.finish_frame(render_start..render_end);
+        }
+    }
+
+    /// Render a group header row: the group's value and row count, then its
+    /// aggregates under their columns
+    fn render_group_row(
+        &self,
+        ctx: &mut RenderContext,
+        group: &GroupHeader,
+        params: &RowRenderParams<'_, '_>,
+        y: u16,
+        bg: Color,
+    ) {
+        let title = self
+            .columns
+            .iter()
+            .find(|c| c.key == group.column)
+            .map_or(group.column.as_str(), |c| c.title.as_str());
+        let icon = if group.collapsed { '▶' } else { '▼' };
+        let label = format!(
+            "{}{} {}: {} ({})",
+            "  ".repeat(group.depth),
+            icon,
+            title,
+            group.value(),
+            group.count
+        );
+
+        let x = params.area_x + params.row_num_width;
+        let width = params.content_end.saturating_sub(x);
+        self.draw_group_text(ctx, &label, x, y, width, bg);
+
+        for slot in params.slots {
+            let Some((_, text)) = group.aggregates.iter().find(|(k, _)| *k == slot.col.key) else {
+                continue;
+            };
+            for dx in 0..slot.width {
+                let mut cell = Cell::new(' ');
+                cell.bg = Some(bg);
+                ctx.set(slot.x + dx, y, cell);
+            }
+            let text = truncate_to_width(text, slot.width.saturating_sub(1) as usize);
+            let dw = display_width(text) as u16;
+            let start = slot.x + slot.width.saturating_sub(dw + 1);
+            self.draw_group_text(ctx, text, start, y, dw, bg);
+        }
+    }
+
+    /// Draw bold header-colored text for group rows and the group panel
+    fn draw_group_text(
+        &self,
+        ctx: &mut RenderContext,
+        text: &str,
+        x: u16,
+        y: u16,
+        width: u16,
+        bg: Color,
+    ) {
+        let mut dx: u16 = 0;
+        for ch in text.chars() {
+            let cw = char_width(ch) as u16;
+            if dx + cw > width {
+                break;
+            }
+            let mut cell = Cell::new(ch);
+            cell.fg = Some(self.colors.header_fg);
+            cell.bg = Some(bg);
+            cell.modifier |= Modifier::BOLD;
+            ctx.set(x + dx, y, cell);
+            dx += cw;
+        }
+    }
+
+    /// Render the group panel: grouped columns as removable chips, or a hint
+    /// to drag one in
+    fn render_group_panel(&self, ctx: &mut RenderContext, width: u16, y: u16) {
+        let bg = if self.group_drop_active {
+            self.colors.selected_bg
+        } else {
+            self.colors.header_bg
+        };
+        for x in 0..width {
+            let mut cell = Cell::new(' ');
+            cell.bg = Some(bg);
+            ctx.set(x, y, cell);
+        }
+
+        if self.group_by.is_empty() {
+            let hint = truncate_to_width(Self::GROUP_PANEL_HINT, width as usize);
+            let mut dx: u16 = 0;
+            for ch in hint.chars() {
+                let mut cell = Cell::new(ch);
+                cell.fg = Some(DISABLED_FG);
+                cell.bg = Some(bg);
+                ctx.set(dx, y, cell);
+                dx += char_width(ch) as u16;
+            }
+            return;
+        }
+
+        for (i, (x, label, _)) in self.group_chips().iter().enumerate() {
+            if i > 0 {
+                self.draw_group_text(ctx, " › ", x.saturating_sub(3), y, 3, bg);
+            }
+            if *x < width {
+                self.draw_group_text(ctx, label, *x, y, width - x, self.colors.selected_bg);
+            }
+        }
+    }
+
+    /// Render a row of a data source that is still loading
+    fn render_placeholder(
+        &self,
+        ctx: &mut RenderContext,
+        params: &RowRenderParams<'_, '_>,
+        y: u16,
+        bg: Color,
+    ) {
+        let x = params.area_x + params.row_num_width;
+        let width = params.content_end.saturating_sub(x) as usize;
+        let text = truncate_to_width("Loading…", width);
+        let mut dx: u16 = 0;
+        for ch in text.chars() {
+            let mut cell = Cell::new(ch);
+            cell.fg = Some(DISABLED_FG);
+            cell.bg = Some(bg);
+            cell.modifier |= Modifier::ITALIC;
+            ctx.set(x + dx, y, cell);
+            dx += char_width(ch) as u16;
+        }
     }
 
     /// Render the header row
@@ -6,7 +6,7 @@ use crate::layout::Rect;
 impl DataGrid {
     /// Start dragging a column
     pub(super) fn start_column_drag(&mut self, col: usize) {
-        if !self.reorderable || col >= self.columns.len() {
+        if !(self.reorderable || self.groupable) || col >= self.columns.len() {
             return;
         }
 
@@ -50,7 +50,9 @@ impl DataGrid {
 
     /// End column drag and perform reorder
     pub(super) fn end_column_drag(&mut self) {
-        if let (Some(from), Some(to)) = (self.dragging_col, self.drop_target_col) {
+        // A drag started only for grouping doesn't reorder
+        let from = self.dragging_col.filter(|_| self.reorderable);
+        if let (Some(from), Some(to)) = (from, self.drop_target_col) {
             if from != to && to != from + 1 {
                 // Initialize column order if not set
                 if self.column_order.is_empty() {
@@ -0,0 +1,284 @@
+//! DataGrid external data sources
+//!
+//! A [`GridDataSource`] replaces the grid's in-memory `rows`: the grid asks it
+//! for the row count and fetches only the rows around the viewport (plus
+//! overscan), a page at a time, through a [`PagedData`] cache. Sorting and
+//! filtering are handed to the source so they can run server-side.
+
+use std::cell::{Cell, Ref, RefCell};
+use std::ops::Range;
+use std::rc::Rc;
+
+use super::core::DataGrid;
+use super::types::{ColumnFilter, GridRow, SortDirection};
+use crate::query::Query;
+use crate::state::patterns::lazy::PagedData;
+
+/// Rows supplied on demand to a [`DataGrid`]
+///
+/// Row indices are positions in the source's current sort and filter order.
+///
+/// # Async loading
+///
+/// [`fetch`](Self::fetch) must not block. A source that loads in the
+/// background starts loading and returns `None`; the grid draws placeholder
+/// rows and asks again on its next render, until rows come back.
+pub trait GridDataSource {
+    /// Number of rows, or `None` while the total is unknown
+    ///
+    /// With an unknown total the grid pages forward until a fetch returns
+    /// fewer rows than it asked for.
+    fn row_count(&self) -> Option<usize>;
+
+    /// Fetch the rows in `range`
+    ///
+    /// Returns `None` while the rows are still loading. Returning fewer rows
+    /// than asked for means the data ends there.
+    fn fetch(&self, range: Range<usize>) -> Option<Vec<GridRow>>;
+
+    /// Sort by column keys in priority order (empty = unsorted)
+    fn sort(&mut self, _columns: &[(String, SortDirection)]) {}
+
+    /// Filter by lowercase text, in one column or (with `None`) in any
+    fn filter(&mut self, _text: &str, _column: Option<&str>) {}
+
+    /// Filter by column filters (column key, filter) and a query, on top of
+    /// [`filter`](Self::filter)
+    fn filter_columns(&mut self, _filters: &[(String, ColumnFilter)], _query: Option<&Query>) {}
+}
+
+/// Page loader that fetches from the shared source
+type PageLoader = Box<dyn Fn(usize, usize) -> Vec<Option<GridRow>>>;
+
+/// A data source with its page cache
+///
+/// Pages still loading hold `None` rows.
+pub(super) struct SourceState {
+    source: Rc<RefCell<dyn GridDataSource>>,
+    pages: PagedData<Option<GridRow>, PageLoader>,
+    /// The end of a source with an unknown total has been reached
+    end_found: Cell<bool>,
+}
+
+impl SourceState {
+    fn new(source: Rc<RefCell<dyn GridDataSource>>, page_size: usize) -> Self {
+        let page_size = page_size.max(1);
+        let total = source.borrow().row_count().unwrap_or(page_size);
+        let loader_source = Rc::clone(&source);
+        let loader: PageLoader = Box::new(move |page, size| {
+            let source = loader_source.borrow();
+            let start = page * size;
+            let end = source
+                .row_count()
+                .map_or(start + size, |count| (start + size).min(count));
+            match source.fetch(start..end.max(start)) {
+                Some(rows) => rows.into_iter().take(size).map(Some).collect(),
+                None => vec![None; end.saturating_sub(start)],
+            }
+        });
+        Self {
+            source,
+            pages: PagedData::new(total, page_size, loader),
+            end_found: Cell::new(false),
+        }
+    }
+
+    /// Number of rows known to the grid
+    pub(super) fn len(&self) -> usize {
+        self.pages.total()
+    }
+
+    /// Row at `index`, or `None` while it is loading
+    pub(super) fn row(&self, index: usize) -> Option<Ref<'_, GridRow>> {
+        Ref::filter_map(self.pages.get(index)?, Option::as_ref).ok()
+    }
+
+    /// Load the pages covering `range`
+    pub(super) fn prefetch(&self, range: Range<usize>) {
+        if !range.is_empty() {
+            self.pages.prefetch(range.start, range.len() - 1);
+        }
+    }
+
+    /// Re-request pages in `range` that are still loading, and update the
+    /// row count from what has been loaded so far
+    pub(super) fn finish_frame(&self, range: Range<usize>) {
+        let size = self.pages.page_size();
+        let first = range.start / size;
+        let last = range.end.div_ceil(size).min(self.pages.page_count());
+        for page in first..last {
+            if self.pages.is_page_loaded(page) && self.is_pending(page) {
+                self.pages.invalidate_page(page);
+            }
+        }
+        self.sync_total();
+    }
+
+    /// Whether a loaded page came back as still loading
+    fn is_pending(&self, page: usize) -> bool {
+        let start = page * self.pages.page_size();
+        start < self.len() && self.row(start).is_none()
+    }
+
+    /// Update the row count from the source, or, with an unknown total,
+    /// from the last page: a full one means there may be more rows, a short
+    /// one is where the data ends
+    fn sync_total(&self) {
+        let count = self.source.borrow().row_count();
+        let total = self.len();
+        if let Some(count) = count {
+            if count != total {
+                self.pages.set_total(count);
+                self.pages.invalidate_all();
+            }
+            return;
+        }
+
+        if self.end_found.get() {
+            return;
+        }
+        let Some(last_page) = self.pages.page_count().checked_sub(1) else {
+            return;
+        };
+        if !self.pages.is_page_loaded(last_page) || self.is_pending(last_page) {
+            return;
+        }
+        let start = last_page * self.pages.page_size();
+        let loaded = (start..total)
+            .take_while(|&i| self.row(i).is_some())
+            .count();
+        if start + loaded == total {
+            self.pages.set_total(total + self.pages.page_size());
+        } else {
+            self.pages.set_total(start + loaded);
+            self.end_found.set(true);
+        }
+    }
+
+    /// Drop every cached page and re-read the row count
+    fn reset(&self) {
+        let total = self
+            .source
+            .borrow()
+            .row_count()
+            .unwrap_or(self.pages.page_size());
+        self.pages.set_total(total);
+        self.pages.invalidate_all();
+        self.end_found.set(false);
+    }
+}
+
+impl DataGrid {
+    /// Load rows from a data source instead of `rows`
+    ///
+    /// Only the rows near the viewport are fetched, `options.page_size` at a
+    /// time. Sorting and filtering are passed on to the source. Editing,
+    /// tree mode, export and footer aggregations work on in-memory rows only.
+    pub fn data_source(mut self, source: impl GridDataSource + 'static) -> Self {
+        self.set_data_source(source);
+        self
+    }
+
+    /// Set the data source (mutable)
+    pub fn set_data_source(&mut self, source: impl GridDataSource + 'static) {
+        let source: Rc<RefCell<dyn GridDataSource>> = Rc::new(RefCell::new(source));
+        self.source = Some(SourceState::new(source, self.options.page_size));
+        self.selected_row = 0;
+        self.scroll_row = 0;
+        if self.sort_column.is_some() || !self.sort_columns.is_empty() {
+            self.sort_source();
+        }
+        if self.has_filters() {
+            self.filter_source();
+        }
+    }
+
+    /// Remove the data source, going back to in-memory rows
+    pub fn clear_data_source(&mut self) {
+        self.source = None;
+        self.selected_row = 0;
+        self.scroll_row = 0;
+    }
+
+    /// Check if rows come from a data source
+    pub fn has_data_source(&self) -> bool {
+        self.source.is_some()
+    }
+
+    /// Set how many rows are fetched per request from a data source
+    pub fn page_size(mut self, rows: usize) -> Self {
+        self.options.page_size = rows.max(1);
+        if let Some(state) = self.source.take() {
+            self.source = Some(SourceState::new(state.source, self.options.page_size));
+        }
+        self
+    }
+
+    /// Drop the cached rows so they are fetched again, e.g. after the
+    /// source's data changed
+    pub fn refresh_source(&mut self) {
+        if let Some(source) = &self.source {
+            source.reset();
+        }
+        let last = self.filtered_count().saturating_sub(1);
+        self.selected_row = self.selected_row.min(last);
+    }
+
+    /// Get the row at a display index (after sorting and filtering)
+    ///
+    /// Returns `None` for group headers and for rows of a data source that
+    /// are still loading.
+    pub fn row_at(&self, index: usize) -> Option<GridRow> {
+        match &self.source {
+            Some(source) if index < source.len() => source.row(index).map(|row| row.clone()),
+            Some(_) => None,
+            None => self
+                .display_row_index(index)
+                .and_then(|i| self.rows.get(i))
+                .cloned(),
+        }
+    }
+
+    /// Pass the current sort to the data source
+    ///
+    /// Returns false when there is no data source.
+    pub(super) fn sort_source(&mut self) -> bool {
+        let Some(state) = &self.source else {
+            return false;
+        };
+        let spec: Vec<(String, SortDirection)> = if self.sort_columns.is_empty() {
+            self.sort_column
+                .and_then(|col| self.columns.get(col))
+                .map(|col| (col.key.clone(), self.sort_direction))
+                .into_iter()
+                .collect()
+        } else {
+            self.sort_columns
+                .iter()
+                .filter_map(|&(col, dir)| self.columns.get(col).map(|c| (c.key.clone(), dir)))
+                .collect()
+        };
+        state.source.borrow_mut().sort(&spec);
+        state.reset();
+        true
+    }
+
+    /// Pass the current filter to the data source
+    ///
+    /// Returns false when there is no data source.
+    pub(super) fn filter_source(&mut self) -> bool {
+        let Some(state) = &self.source else {
+            return false;
+        };
+        let column = self
+            .filter_column
+            .and_then(|col| self.columns.get(col))
+            .map(|col| col.key.as_str());
+        let mut source = state.source.borrow_mut();
+        source.filter(&self.filter, column);
+        source.filter_columns(&self.column_filters, self.query.as_ref());
+        drop(source);
+        state.reset();
+        true
+    }
+}
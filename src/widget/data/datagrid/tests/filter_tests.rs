@@ -1,6 +1,7 @@
 #![allow(unused_imports)]
 
 use super::super::*;
+use super::orders_grid;
 
 #[test]
 fn test_filtering() {
@@ -71,3 +72,171 @@ fn test_filter_cancels_edit() {
     grid.set_filter("x");
     assert!(!grid.is_editing());
 }
+
+fn ids(grid: &DataGrid) -> Vec<&str> {
+    grid.filtered_rows()
+        .iter()
+        .map(|row| row.get("id").unwrap())
+        .collect()
+}
+
+#[test]
+fn test_column_filter_contains() {
+    let grid = orders_grid().column_filter("status", ColumnFilter::contains("DRA"));
+    assert_eq!(ids(&grid), vec!["1", "4"]);
+}
+
+#[test]
+fn test_column_filter_number_range() {
+    let mut grid = orders_grid();
+    grid.set_column_filter("price", ColumnFilter::number_range(Some(80.0), Some(100.0)));
+    assert_eq!(ids(&grid), vec!["2", "5"]);
+
+    // Blank and unparseable cells never match a range
+    grid.set_column_filter("price", ColumnFilter::number_range(None, Some(50.0)));
+    assert_eq!(ids(&grid), vec!["3"]);
+}
+
+#[test]
+fn test_column_filter_date_range() {
+    let from = CellValue::parse("2024-01-01", ColumnType::Date).as_date();
+    let to = CellValue::parse("2024-03-02", ColumnType::Date).as_date();
+    let grid = orders_grid().column_filter("due", ColumnFilter::date_range(from, to));
+    assert_eq!(ids(&grid), vec!["1", "2"]);
+}
+
+#[test]
+fn test_column_filter_boolean() {
+    let grid = orders_grid().column_filter("paid", ColumnFilter::Boolean(true));
+    assert_eq!(ids(&grid), vec!["1", "3", "5"]);
+}
+
+#[test]
+fn test_column_filter_values() {
+    let grid = orders_grid().column_filter("status", ColumnFilter::values(["Sent", "Paid"]));
+    assert_eq!(ids(&grid), vec!["2", "3", "5"]);
+
+    let grid = orders_grid().column_filter("due", ColumnFilter::values([""]));
+    assert_eq!(ids(&grid), vec!["4"]);
+}
+
+#[test]
+fn test_column_filter_regex() {
+    let grid = orders_grid().column_filter("status", ColumnFilter::regex("^(draft|paid)$"));
+    #[cfg(feature = "regex")]
+    assert_eq!(ids(&grid), vec!["1", "3", "4"]);
+    // Without the regex engine the pattern is matched literally
+    #[cfg(not(feature = "regex"))]
+    assert!(ids(&grid).is_empty());
+
+    // An invalid pattern is matched literally
+    let grid = orders_grid().column_filter("status", ColumnFilter::regex("raf("));
+    assert!(ids(&grid).is_empty());
+}
+
+#[test]
+fn test_column_filters_combine() {
+    let mut grid = orders_grid()
+        .column_filter("status", ColumnFilter::values(["Sent", "Paid"]))
+        .column_filter("price", ColumnFilter::number_range(Some(60.0), None));
+    assert_eq!(ids(&grid), vec!["2", "5"]);
+
+    grid.set_filter("99");
+    assert_eq!(ids(&grid), vec!["5"]);
+
+    grid.remove_column_filter("price");
+    grid.set_filter("");
+    assert_eq!(ids(&grid), vec!["2", "3", "5"]);
+    assert!(grid.get_column_filter("price").is_none());
+
+    // Setting a column's filter again replaces it
+    grid.set_column_filter("status", ColumnFilter::contains("paid"));
+    assert_eq!(grid.column_filters.len(), 1);
+    assert_eq!(ids(&grid), vec!["3"]);
+}
+
+#[test]
+fn test_query_filter() {
+    let mut grid = orders_grid();
+    grid.set_query("status:!draft price:<100").unwrap();
+    assert_eq!(ids(&grid), vec!["2", "3", "5"]);
+    assert_eq!(grid.query_text, "status:!draft price:<100");
+
+    // Titles work as field names and dates compare in order
+    grid.set_query("Due:>=2024-03-01").unwrap();
+    assert_eq!(ids(&grid), vec!["2", "3"]);
+
+    grid.set_query("paid:true sent").unwrap();
+    assert_eq!(ids(&grid), vec!["5"]);
+
+    grid.set_query("status~RA").unwrap();
+    assert_eq!(ids(&grid), vec!["1", "4"]);
+
+    grid.clear_query();
+    assert_eq!(grid.filtered_count(), 5);
+    assert!(grid.query_text.is_empty());
+}
+
+#[test]
+fn test_invalid_query_keeps_current() {
+    let mut grid = orders_grid().query("status:sent");
+    assert!(grid.set_query("sort::desc").is_err());
+    assert_eq!(ids(&grid), vec!["2", "5"]);
+    assert_eq!(grid.query_text, "status:sent");
+}
+
+#[test]
+fn test_clear_filters() {
+    let mut grid = orders_grid()
+        .column_filter("paid", ColumnFilter::Boolean(false))
+        .query("price:>50");
+    grid.set_filter("sent");
+    assert!(grid.has_filters());
+    assert_eq!(ids(&grid), vec!["2"]);
+
+    grid.clear_filters();
+    assert!(!grid.has_filters());
+    assert_eq!(grid.filtered_count(), 5);
+}
+
+#[test]
+fn test_grid_row_queryable() {
+    use crate::query::Query;
+
+    let row = GridRow::new()
+        .cell("Name", "Widget")
+        .cell("qty", "12")
+        .cell("added", "2024-02-01")
+        .typed("price", 9.5);
+
+    assert!(Query::parse("name:widget qty:>9").unwrap().matches(&row));
+    assert!(Query::parse("price:<10 added:<2024-03-01")
+        .unwrap()
+        .matches(&row));
+    assert!(Query::parse("widget 12").unwrap().matches(&row));
+    assert!(!Query::parse("qty:<9").unwrap().matches(&row));
+    assert!(!Query::parse("missing:1").unwrap().matches(&row));
+}
+
+#[test]
+fn test_column_filter_display() {
+    let date = CellValue::parse("2024-01-02", ColumnType::Date).as_date();
+    assert_eq!(ColumnFilter::contains("ab").to_string(), "~ab");
+    assert_eq!(ColumnFilter::regex("^a").to_string(), "/^a/");
+    assert_eq!(
+        ColumnFilter::number_range(Some(1.0), Some(2.5)).to_string(),
+        "1 – 2.5"
+    );
+    assert_eq!(
+        ColumnFilter::number_range(None, Some(3.0)).to_string(),
+        "≤ 3"
+    );
+    assert_eq!(
+        ColumnFilter::date_range(date, None).to_string(),
+        "≥ 2024-01-02"
+    );
+    assert_eq!(ColumnFilter::Boolean(false).to_string(), "no");
+    assert_eq!(ColumnFilter::values(["a"]).to_string(), "a");
+    assert_eq!(ColumnFilter::values([""]).to_string(), "(blank)");
+    assert_eq!(ColumnFilter::values(["a", "b"]).to_string(), "2 values");
+}
@@ -0,0 +1,242 @@
+use super::super::{ColumnFilter, DataGrid, GridColumn, PivotConfig};
+use crate::event::{Key, MouseButton, MouseEventKind};
+use crate::layout::Rect;
+
+use super::{orders_grid, render, row_text};
+
+fn type_text(grid: &mut DataGrid, text: &str) {
+    for ch in text.chars() {
+        grid.handle_key(&Key::Char(ch));
+    }
+}
+
+#[test]
+fn test_text_filter_popover() {
+    let mut grid = orders_grid();
+    grid.selected_col = 1;
+    assert!(grid.handle_key(&Key::Char('f')));
+    assert!(grid.is_column_filter_open());
+
+    // Keys go to the popover, not navigation
+    type_text(&mut grid, "jent");
+    grid.handle_key(&Key::Home);
+    grid.handle_key(&Key::Delete);
+    grid.handle_key(&Key::Char('s'));
+    assert_eq!(grid.selected_row, 0);
+
+    grid.handle_key(&Key::Enter);
+    assert!(!grid.is_column_filter_open());
+    assert_eq!(
+        grid.get_column_filter("status"),
+        Some(&ColumnFilter::contains("sent"))
+    );
+    assert_eq!(grid.filtered_count(), 2);
+
+    // Reopening shows the current filter; clearing the text removes it
+    grid.handle_key(&Key::Char('f'));
+    for _ in 0..4 {
+        grid.handle_key(&Key::Backspace);
+    }
+    grid.handle_key(&Key::Enter);
+    assert!(grid.column_filters.is_empty());
+}
+
+#[test]
+fn test_escape_closes_without_applying() {
+    let mut grid = orders_grid();
+    grid.open_column_filter(1);
+    type_text(&mut grid, "x");
+    grid.handle_key(&Key::Escape);
+
+    assert!(!grid.is_column_filter_open());
+    assert!(grid.column_filters.is_empty());
+}
+
+#[test]
+fn test_tab_switches_text_modes() {
+    let mut grid = orders_grid();
+    grid.open_column_filter(1);
+    type_text(&mut grid, "^s");
+    grid.handle_key(&Key::Tab);
+    grid.handle_key(&Key::Enter);
+    assert_eq!(
+        grid.get_column_filter("status"),
+        Some(&ColumnFilter::regex("^s"))
+    );
+
+    // Pattern → checklist of distinct values, sorted
+    grid.open_column_filter(1);
+    grid.handle_key(&Key::Tab);
+    let buffer = render(&grid, 60, 14);
+    let lines: Vec<String> = (0..14).map(|y| row_text(&buffer, y)).collect();
+    let checklist: Vec<&String> = lines.iter().filter(|l| l.contains("[x]")).collect();
+    assert_eq!(checklist.len(), 4, "{lines:#?}");
+    assert!(checklist[0].contains("[x] Draft"));
+    assert!(checklist[1].contains("[x] draft"));
+    assert!(checklist[3].contains("[x] Sent"));
+}
+
+#[test]
+fn test_values_checklist() {
+    let mut grid = orders_grid();
+    grid.set_column_filter("status", ColumnFilter::values(["Sent"]));
+    grid.open_column_filter(1);
+
+    // Draft, draft, Paid, Sent: check Paid as well
+    grid.handle_key(&Key::Down);
+    grid.handle_key(&Key::Down);
+    grid.handle_key(&Key::Char(' '));
+    grid.handle_key(&Key::Enter);
+    assert_eq!(
+        grid.get_column_filter("status"),
+        Some(&ColumnFilter::values(["Paid", "Sent"]))
+    );
+    assert_eq!(grid.filtered_count(), 3);
+
+    // Checking everything removes the filter
+    grid.open_column_filter(1);
+    grid.handle_key(&Key::Char(' '));
+    grid.handle_key(&Key::Down);
+    grid.handle_key(&Key::Char(' '));
+    grid.handle_key(&Key::Enter);
+    assert!(grid.get_column_filter("status").is_none());
+}
+
+#[test]
+fn test_number_range_popover() {
+    let mut grid = orders_grid();
+    grid.open_column_filter(2);
+    type_text(&mut grid, "50");
+    grid.handle_key(&Key::Tab);
+    type_text(&mut grid, "100");
+    grid.handle_key(&Key::Enter);
+
+    assert_eq!(
+        grid.get_column_filter("price"),
+        Some(&ColumnFilter::number_range(Some(50.0), Some(100.0)))
+    );
+    assert_eq!(grid.filtered_count(), 2);
+}
+
+#[test]
+fn test_invalid_range_keeps_popover_open() {
+    let mut grid = orders_grid();
+    grid.open_column_filter(3);
+    type_text(&mut grid, "March");
+    assert!(!grid.apply_column_filter());
+    assert!(grid.is_column_filter_open());
+
+    let buffer = render(&grid, 60, 14);
+    assert!((0..14).any(|y| row_text(&buffer, y).contains("Invalid date")));
+
+    for _ in 0..5 {
+        grid.handle_key(&Key::Backspace);
+    }
+    type_text(&mut grid, "2024-03-01");
+    grid.handle_key(&Key::Enter);
+    assert!(!grid.is_column_filter_open());
+    assert_eq!(grid.filtered_count(), 2);
+}
+
+#[test]
+fn test_boolean_popover() {
+    let mut grid = orders_grid();
+    grid.open_column_filter(4);
+    grid.handle_key(&Key::Down);
+    grid.handle_key(&Key::Down);
+    grid.handle_key(&Key::Enter);
+
+    assert_eq!(
+        grid.get_column_filter("paid"),
+        Some(&ColumnFilter::Boolean(false))
+    );
+    assert_eq!(grid.filtered_count(), 2);
+}
+
+#[test]
+fn test_unfilterable_column() {
+    let mut grid = DataGrid::new().column(GridColumn::new("a", "A"));
+    grid.columns[0].filterable = false;
+    assert!(!grid.open_column_filter(0));
+    assert!(!grid.open_column_filter(5));
+}
+
+#[test]
+fn test_filter_chips() {
+    let mut grid = orders_grid()
+        .column_filter("price", ColumnFilter::number_range(Some(50.0), None))
+        .query("status:sent");
+    let area = Rect::new(0, 0, 60, 14);
+
+    let buffer = render(&grid, 60, 14);
+    let bar = row_text(&buffer, 0);
+    assert!(bar.starts_with("  Price: ≥ 50 ✕"), "{bar}");
+    assert!(bar.contains(" status:sent ✕"), "{bar}");
+    assert!(row_text(&buffer, 1).starts_with("ID"));
+
+    // Clicking a chip removes its filter
+    assert!(grid.handle_mouse(MouseEventKind::Down(MouseButton::Left), 3, 0, area));
+    assert!(grid.column_filters.is_empty());
+    assert_eq!(grid.filtered_count(), 2);
+
+    grid.handle_mouse(MouseEventKind::Down(MouseButton::Left), 3, 0, area);
+    assert!(grid.query.is_none());
+    assert!(row_text(&render(&grid, 60, 14), 0).starts_with("ID"));
+}
+
+#[test]
+fn test_right_click_header_opens_popover() {
+    let mut grid = orders_grid();
+    let area = Rect::new(0, 0, 60, 14);
+    let widths = grid.get_display_widths(60);
+    let price_x = widths[0] + widths[1] + 3;
+
+    assert!(grid.handle_mouse(MouseEventKind::Down(MouseButton::Right), price_x, 0, area));
+    assert!(grid.is_column_filter_open());
+
+    let buffer = render(&grid, 60, 14);
+    assert!(row_text(&buffer, 1).contains("Filter Price"));
+    assert!(row_text(&buffer, 2).contains("Min"));
+
+    // Clicking outside closes it
+    assert!(grid.handle_mouse(MouseEventKind::Down(MouseButton::Left), 59, 13, area));
+    assert!(!grid.is_column_filter_open());
+}
+
+#[test]
+fn test_click_toggles_checklist_item() {
+    let mut grid = orders_grid();
+    grid.set_column_filter("status", ColumnFilter::values(["Sent"]));
+    grid.open_column_filter(1);
+    render(&grid, 60, 14);
+
+    // Row 0: chip bar, 1: header, 2: popover border, 3: first item (Draft),
+    // in a popover under the Status column
+    let area = Rect::new(0, 0, 60, 14);
+    let x = grid.get_display_widths(60)[0] + 4;
+    assert!(grid.handle_mouse(MouseEventKind::Down(MouseButton::Left), x, 3, area));
+    grid.apply_column_filter();
+    assert_eq!(
+        grid.get_column_filter("status"),
+        Some(&ColumnFilter::values(["Draft", "Sent"]))
+    );
+}
+
+#[test]
+fn test_shift_f_clears_filters() {
+    let mut grid = orders_grid().column_filter("paid", ColumnFilter::Boolean(true));
+    assert!(grid.handle_key(&Key::Char('F')));
+    assert!(!grid.has_filters());
+    assert!(!grid.handle_key(&Key::Char('F')));
+}
+
+#[test]
+fn test_pivot_keeps_column_filters() {
+    let mut grid = orders_grid().column_filter("paid", ColumnFilter::Boolean(true));
+    grid.set_pivot(PivotConfig::new("status", "paid", "price"));
+    assert!(grid.column_filters.is_empty());
+
+    grid.clear_pivot();
+    assert_eq!(grid.column_filters.len(), 1);
+    assert_eq!(grid.filtered_count(), 3);
+}
@@ -0,0 +1,201 @@
+use super::super::{
+    AggregationType, CellValue, ColumnAggregation, ColumnType, DataGrid, GridColumn, GridRow,
+};
+use crate::event::{Key, MouseButton, MouseEventKind};
+use crate::layout::Rect;
+
+use super::{render, row_text, sales_grid};
+
+fn header(grid: &DataGrid, idx: usize) -> (usize, String, usize) {
+    let group = grid.group_at(idx).expect("group header");
+    (group.depth, group.value().to_string(), group.count)
+}
+
+#[test]
+fn test_group_by_single_level() {
+    let grid = sales_grid("product").group_by("region");
+
+    assert!(grid.is_grouped());
+    assert_eq!(grid.display_count(), 7);
+    assert_eq!(header(&grid, 0), (0, "East".to_string(), 3));
+    assert_eq!(grid.display_row_index(1), Some(0));
+    assert_eq!(grid.display_row_index(2), Some(2));
+    assert_eq!(grid.display_row_index(3), Some(3));
+    assert_eq!(header(&grid, 4), (0, "West".to_string(), 2));
+    assert_eq!(grid.display_row_index(4), None);
+}
+
+#[test]
+fn test_group_by_multi_level() {
+    let grid = sales_grid("product").group_by("region").group_by("product");
+
+    assert_eq!(header(&grid, 0), (0, "East".to_string(), 3));
+    assert_eq!(header(&grid, 1), (1, "Widget".to_string(), 2));
+    assert_eq!(header(&grid, 4), (1, "Gadget".to_string(), 1));
+    assert_eq!(
+        grid.group_at(4).unwrap().path,
+        vec!["East".to_string(), "Gadget".to_string()]
+    );
+    // 2 regions + 4 region/product groups + 5 rows
+    assert_eq!(grid.display_count(), 11);
+}
+
+#[test]
+fn test_group_follows_sort_and_filter() {
+    let mut grid = sales_grid("product").group_by("region");
+    grid.sort(0);
+    grid.sort(0); // Descending: West first
+    assert_eq!(header(&grid, 0), (0, "West".to_string(), 2));
+
+    grid.set_filter("widget");
+    assert_eq!(header(&grid, 0), (0, "West".to_string(), 1));
+    assert_eq!(header(&grid, 2), (0, "East".to_string(), 2));
+    assert_eq!(grid.display_count(), 5);
+}
+
+#[test]
+fn test_toggle_group() {
+    let mut grid = sales_grid("product").group_by("region");
+
+    grid.toggle_group();
+    assert!(grid.group_at(0).unwrap().collapsed);
+    assert_eq!(header(&grid, 1), (0, "West".to_string(), 2));
+    assert_eq!(grid.display_count(), 4);
+
+    // Enter on a header toggles it instead of editing or sorting
+    grid.handle_key(&Key::Enter);
+    assert_eq!(grid.display_count(), 7);
+    assert_eq!(grid.sort_column, None);
+}
+
+#[test]
+fn test_collapse_and_expand_all_groups() {
+    let mut grid = sales_grid("product").group_by("region").group_by("product");
+    grid.selected_row = 6;
+
+    grid.collapse_all_groups();
+    assert_eq!(grid.display_count(), 2);
+    assert_eq!(grid.selected_row, 1);
+
+    // Expanding a region keeps its product groups collapsed
+    grid.set_group_collapsed(vec!["East".to_string()], false);
+    assert_eq!(grid.display_count(), 4);
+
+    grid.expand_all_groups();
+    assert_eq!(grid.display_count(), 11);
+}
+
+#[test]
+fn test_group_header_is_not_editable() {
+    let mut grid = DataGrid::new()
+        .column(GridColumn::new("k", "K").editable(true))
+        .row(GridRow::new().cell("k", "a"))
+        .group_by("k");
+
+    assert!(!grid.start_edit());
+    grid.select_next();
+    assert!(grid.start_edit());
+}
+
+#[test]
+fn test_remove_and_clear_groups() {
+    let mut grid = sales_grid("product").group_by("region").group_by("product");
+    grid.remove_group("region");
+    assert_eq!(grid.group_by, vec!["product".to_string()]);
+    assert_eq!(header(&grid, 0), (0, "Widget".to_string(), 3));
+
+    grid.clear_groups();
+    assert!(!grid.is_grouped());
+    assert_eq!(grid.display_count(), 5);
+    assert!(grid.group_at(0).is_none());
+}
+
+#[test]
+fn test_group_aggregates() {
+    let grid = sales_grid("product")
+        .group_by("region")
+        .group_aggregate(ColumnAggregation::new("amount", AggregationType::Sum))
+        .group_aggregate(ColumnAggregation::new("amount", AggregationType::Average).label("Avg"))
+        .group_aggregate(ColumnAggregation::custom("product", "Kinds", |values| {
+            let mut kinds: Vec<_> = values.iter().map(|v| v.to_string()).collect();
+            kinds.sort();
+            kinds.dedup();
+            CellValue::Number(kinds.len() as f64)
+        }));
+
+    let east = grid.group_at(0).unwrap();
+    assert_eq!(
+        east.aggregates,
+        vec![
+            ("amount".to_string(), "Sum: 20".to_string()),
+            ("amount".to_string(), "Avg: 6.67".to_string()),
+            ("product".to_string(), "Kinds: 2".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_aggregation_min_max_on_dates() {
+    let values = vec![
+        CellValue::parse("2024-05-01", ColumnType::Date),
+        CellValue::Empty,
+        CellValue::parse("2023-01-09", ColumnType::Date),
+    ];
+    assert_eq!(
+        AggregationType::Min.apply(&values).to_string(),
+        "2023-01-09"
+    );
+    assert_eq!(
+        AggregationType::Max.apply(&values).to_string(),
+        "2024-05-01"
+    );
+    assert_eq!(
+        AggregationType::Count.apply(&values),
+        CellValue::Number(2.0)
+    );
+    assert_eq!(AggregationType::Sum.apply(&values), CellValue::Empty);
+}
+
+#[test]
+fn test_render_group_rows() {
+    let grid = sales_grid("product")
+        .group_by("region")
+        .group_aggregate(ColumnAggregation::new("amount", AggregationType::Sum));
+    let buffer = render(&grid, 50, 12);
+
+    let first = row_text(&buffer, 1);
+    assert!(first.starts_with("▼ Region: East (3)"), "{first}");
+    assert!(first.contains("Sum: 20"), "{first}");
+    assert!(row_text(&buffer, 2).starts_with("East"));
+}
+
+#[test]
+fn test_drag_column_to_group_panel() {
+    let mut grid = sales_grid("product").groupable(true);
+    let area = Rect::new(0, 0, 50, 12);
+
+    let buffer = render(&grid, 50, 12);
+    assert!(row_text(&buffer, 0).starts_with(" Drag a column here to group"));
+
+    // Drag the "Product" header (header row is below the panel)
+    let product_x = {
+        let widths = grid.get_display_widths(50);
+        widths[0] + 2
+    };
+    assert!(grid.handle_mouse(MouseEventKind::Down(MouseButton::Left), product_x, 1, area));
+    grid.handle_mouse(MouseEventKind::Drag(MouseButton::Left), 3, 0, area);
+    assert!(grid.group_drop_active);
+    grid.handle_mouse(MouseEventKind::Up(MouseButton::Left), 3, 0, area);
+
+    assert_eq!(grid.group_by, vec!["product".to_string()]);
+    assert!(!grid.group_drop_active);
+    // Grouping alone doesn't reorder columns
+    assert_eq!(grid.columns[1].key, "product");
+
+    let buffer = render(&grid, 50, 12);
+    assert!(row_text(&buffer, 0).contains("Product ✕"));
+
+    // Clicking the chip removes the grouping
+    assert!(grid.handle_mouse(MouseEventKind::Down(MouseButton::Left), 3, 0, area));
+    assert!(grid.group_by.is_empty());
+}
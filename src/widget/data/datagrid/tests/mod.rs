@@ -7,14 +7,104 @@ mod edit_tests;
 mod event_tests;
 mod export_tests;
 mod filter_tests;
+mod filter_ui_tests;
 mod freeze_tests;
 mod grid_tests;
+mod group_tests;
 mod navigation_tests;
+mod pivot_tests;
 mod render_tests;
 mod reorder_tests;
 mod resize_tests;
 mod row_tests;
 mod selection_tests;
 mod sorting_tests;
+mod source_tests;
 mod tree_tests;
 mod virtual_scroll_tests;
+
+use super::*;
+use crate::layout::Rect;
+use crate::render::Buffer;
+use crate::widget::traits::{RenderContext, View};
+
+/// Render a grid into a fresh `width` x `height` buffer
+fn render(grid: &DataGrid, width: u16, height: u16) -> Buffer {
+    let mut buffer = Buffer::new(width, height);
+    let area = Rect::new(0, 0, width, height);
+    let mut ctx = RenderContext::new(&mut buffer, area);
+    grid.render(&mut ctx);
+    buffer
+}
+
+/// Text of one buffer row
+fn row_text(buffer: &Buffer, y: u16) -> String {
+    (0..buffer.width())
+        .map(|x| buffer.get(x, y).unwrap().symbol)
+        .collect()
+}
+
+/// Orders with number, date and boolean columns, including blank cells
+fn orders_grid() -> DataGrid {
+    let data = [
+        ("1", "Draft", "120", "2024-01-15", "true"),
+        ("2", "Sent", "80", "2024-03-02", "false"),
+        ("3", "Paid", "45.5", "2024-03-20", "true"),
+        ("4", "draft", "", "", "false"),
+        ("5", "Sent", "99", "2023-12-31", "yes"),
+    ];
+    DataGrid::new()
+        .column(GridColumn::new("id", "ID").col_type(ColumnType::Number))
+        .column(GridColumn::new("status", "Status"))
+        .column(GridColumn::new("price", "Price").col_type(ColumnType::Number))
+        .column(GridColumn::new("due", "Due").col_type(ColumnType::Date))
+        .column(GridColumn::new("paid", "Paid").col_type(ColumnType::Boolean))
+        .rows(
+            data.iter()
+                .map(|(id, status, price, due, paid)| {
+                    GridRow::new()
+                        .cell("id", *id)
+                        .cell("status", *status)
+                        .cell("price", *price)
+                        .cell("due", *due)
+                        .cell("paid", *paid)
+                })
+                .collect(),
+        )
+}
+
+/// Sales by region, with `detail` (`"product"` or `"quarter"`) as the
+/// middle column and a numeric amount
+fn sales_grid(detail: &str) -> DataGrid {
+    let data = [
+        ("East", "Widget", "Q2", "10"),
+        ("West", "Gadget", "Q1", "5"),
+        ("East", "Gadget", "Q1", "7"),
+        ("East", "Widget", "Q2", "3"),
+        ("West", "Widget", "Q3", "20"),
+    ];
+    let title = match detail {
+        "product" => "Product",
+        "quarter" => "Quarter",
+        other => panic!("no sales detail column {:?}", other),
+    };
+    DataGrid::new()
+        .column(GridColumn::new("region", "Region"))
+        .column(GridColumn::new(detail, title))
+        .column(GridColumn::new("amount", "Amount").col_type(ColumnType::Number))
+        .rows(
+            data.iter()
+                .map(|(region, product, quarter, amount)| {
+                    let value = if detail == "product" {
+                        product
+                    } else {
+                        quarter
+                    };
+                    GridRow::new()
+                        .cell("region", *region)
+                        .cell(detail, *value)
+                        .cell("amount", *amount)
+                })
+                .collect(),
+        )
+}
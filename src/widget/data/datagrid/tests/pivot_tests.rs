@@ -0,0 +1,73 @@
+use super::super::{AggregationType, ColumnType, DataGrid, PivotConfig};
+use super::sales_grid;
+
+fn keys(grid: &DataGrid) -> Vec<&str> {
+    grid.columns.iter().map(|c| c.key.as_str()).collect()
+}
+
+#[test]
+fn test_pivot_table() {
+    let grid = sales_grid("quarter").pivot(PivotConfig::new("region", "quarter", "amount"));
+
+    assert!(grid.is_pivoted());
+    assert_eq!(keys(&grid), vec!["region", "Q1", "Q2", "Q3"]);
+    assert_eq!(grid.columns[1].col_type, ColumnType::Number);
+    assert_eq!(grid.rows.len(), 2);
+
+    let east = &grid.rows[0];
+    assert_eq!(east.get("region"), Some("East"));
+    assert_eq!(east.get("Q1"), Some("7"));
+    assert_eq!(east.get("Q2"), Some("13"));
+    assert_eq!(east.get("Q3"), Some(""));
+    assert_eq!(grid.rows[1].get("Q3"), Some("20"));
+}
+
+#[test]
+fn test_pivot_aggregation() {
+    let grid = sales_grid("quarter")
+        .pivot(PivotConfig::new("region", "quarter", "amount").aggregation(AggregationType::Count));
+    assert_eq!(grid.rows[0].get("Q2"), Some("2"));
+    assert_eq!(
+        grid.pivot_config().unwrap().aggregation,
+        AggregationType::Count
+    );
+}
+
+#[test]
+fn test_pivot_uses_filtered_rows() {
+    let mut grid = sales_grid("quarter");
+    grid.set_filter("q1");
+    grid.set_pivot(PivotConfig::new("region", "quarter", "amount"));
+
+    assert_eq!(keys(&grid), vec!["region", "Q1"]);
+    // The filter was consumed by the pivot
+    assert!(grid.filter.is_empty());
+    assert_eq!(grid.row_count(), 2);
+}
+
+#[test]
+fn test_pivot_sorts_by_value_column() {
+    let mut grid = sales_grid("quarter").pivot(PivotConfig::new("region", "quarter", "amount"));
+    grid.sort(3);
+    grid.sort(3);
+    assert_eq!(grid.rows[0].get("region"), Some("West"));
+}
+
+#[test]
+fn test_clear_pivot_restores_data() {
+    let mut grid = sales_grid("quarter").group_by("region");
+    grid.sort(2);
+    grid.set_filter("east");
+    grid.set_pivot(PivotConfig::new("region", "quarter", "amount"));
+    assert!(!grid.is_grouped());
+
+    grid.clear_pivot();
+    assert!(!grid.is_pivoted());
+    assert_eq!(keys(&grid), vec!["region", "quarter", "amount"]);
+    assert_eq!(grid.rows.len(), 5);
+    assert_eq!(grid.filter, "east");
+    assert_eq!(grid.sort_column, Some(2));
+    assert!(grid.is_grouped());
+    // 1 group header + 3 East rows
+    assert_eq!(grid.display_count(), 4);
+}
@@ -31,3 +31,99 @@ fn test_grid_row_children() {
     assert!(row.has_children());
     assert_eq!(row.children.len(), 2);
 }
+
+// ==================== Typed Value Tests ====================
+
+#[test]
+fn test_grid_row_typed() {
+    let row = GridRow::new()
+        .typed("price", 12.5)
+        .typed("active", true)
+        .typed("due", crate::widget::data::Date::new(2024, 3, 7));
+
+    assert_eq!(row.get("price"), Some("12.5"));
+    assert_eq!(row.get("active"), Some("true"));
+    assert_eq!(row.get("due"), Some("2024-03-07"));
+    assert_eq!(
+        row.value("price", ColumnType::Text),
+        CellValue::Number(12.5)
+    );
+}
+
+#[test]
+fn test_grid_row_value_parses_cell() {
+    let row = GridRow::new()
+        .cell("n", " 42 ")
+        .cell("flag", "No")
+        .cell("date", "2024/01/15")
+        .cell("bad", "n/a");
+
+    assert_eq!(row.value("n", ColumnType::Number).as_number(), Some(42.0));
+    assert_eq!(
+        row.value("flag", ColumnType::Boolean).as_bool(),
+        Some(false)
+    );
+    assert_eq!(
+        row.value("date", ColumnType::Date).as_date(),
+        Some(crate::widget::data::Date::new(2024, 1, 15))
+    );
+    assert_eq!(
+        row.value("bad", ColumnType::Number),
+        CellValue::Text("n/a".to_string())
+    );
+    assert!(row.value("missing", ColumnType::Number).is_empty());
+}
+
+#[test]
+fn test_grid_row_set_drops_typed_value() {
+    let mut row = GridRow::new().typed("n", 1);
+    row.set("n", "7");
+    assert_eq!(row.get("n"), Some("7"));
+    assert_eq!(row.value("n", ColumnType::Number), CellValue::Number(7.0));
+    assert!(row.values.is_empty());
+}
+
+#[test]
+fn test_grid_parses_typed_cells_once() {
+    let grid = DataGrid::new()
+        .row(GridRow::new().cell("name", "a").cell("n", "2"))
+        .column(GridColumn::new("name", "Name"))
+        .column(
+            GridColumn::new("n", "N")
+                .col_type(ColumnType::Number)
+                .editable(true),
+        )
+        .row(GridRow::new().cell("name", "b").cell("n", "10"));
+
+    // Text columns keep only their display strings
+    for row in &grid.rows {
+        assert_eq!(row.values.len(), 1);
+    }
+    assert_eq!(
+        grid.rows[0].values[0],
+        ("n".to_string(), CellValue::Number(2.0))
+    );
+    assert_eq!(
+        grid.rows[1].values[0],
+        ("n".to_string(), CellValue::Number(10.0))
+    );
+
+    // Committed edits are parsed again
+    let mut grid = grid;
+    grid.selected_col = 1;
+    grid.start_edit();
+    grid.handle_key(&crate::event::Key::Char('5'));
+    assert!(grid.commit_edit());
+    assert_eq!(
+        grid.rows[0].values,
+        vec![("n".to_string(), CellValue::Number(25.0))]
+    );
+}
+
+#[test]
+fn test_cell_value_invalid_date_is_text() {
+    assert_eq!(
+        CellValue::parse("2024-02-30", ColumnType::Date),
+        CellValue::Text("2024-02-30".to_string())
+    );
+}
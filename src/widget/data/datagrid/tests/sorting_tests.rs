@@ -135,3 +135,49 @@ fn test_sort_cancels_edit() {
     grid.sort(0);
     assert!(!grid.is_editing());
 }
+
+#[test]
+fn test_sort_typed_values() {
+    let mut grid = DataGrid::new()
+        .column(GridColumn::new("price", "Price").col_type(ColumnType::Number))
+        .row(GridRow::new().typed("price", 9.5))
+        .row(GridRow::new().typed("price", 100))
+        .row(GridRow::new().typed("price", CellValue::Empty))
+        .row(GridRow::new().typed("price", -3));
+
+    grid.sort(0);
+
+    let order: Vec<_> = grid.rows.iter().map(|r| r.get("price").unwrap()).collect();
+    assert_eq!(order, vec!["", "-3", "9.5", "100"]);
+}
+
+#[test]
+fn test_sort_date_column() {
+    let mut grid = DataGrid::new()
+        .column(GridColumn::new("due", "Due").col_type(ColumnType::Date))
+        .row(GridRow::new().cell("due", "2024-10-2"))
+        .row(GridRow::new().cell("due", "2024-9-30"))
+        .row(GridRow::new().cell("due", "2023-12-31"));
+
+    grid.sort(0);
+
+    assert_eq!(grid.rows[0].get("due"), Some("2023-12-31"));
+    assert_eq!(grid.rows[1].get("due"), Some("2024-9-30"));
+    assert_eq!(grid.rows[2].get("due"), Some("2024-10-2"));
+}
+
+#[test]
+fn test_multi_sort_boolean_then_number() {
+    let mut grid = DataGrid::new()
+        .column(GridColumn::new("done", "Done").col_type(ColumnType::Boolean))
+        .column(GridColumn::new("n", "N").col_type(ColumnType::Number))
+        .row(GridRow::new().cell("done", "yes").cell("n", "10"))
+        .row(GridRow::new().cell("done", "no").cell("n", "9"))
+        .row(GridRow::new().cell("done", "yes").cell("n", "2"));
+
+    grid.add_sort(0);
+    grid.add_sort(1);
+
+    let order: Vec<_> = grid.rows.iter().map(|r| r.get("n").unwrap()).collect();
+    assert_eq!(order, vec!["9", "2", "10"]);
+}
@@ -0,0 +1,241 @@
+use super::super::{
+    ColumnFilter, ColumnType, DataGrid, GridColumn, GridDataSource, GridRow, SortDirection,
+};
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+
+use super::{render, row_text};
+
+/// What the grid asked the source for
+#[derive(Default)]
+struct Calls {
+    fetches: Vec<Range<usize>>,
+    sorts: Vec<Vec<(String, SortDirection)>>,
+    filters: Vec<(String, Option<String>)>,
+    column_filters: Vec<(Vec<(String, ColumnFilter)>, bool)>,
+}
+
+/// Numbered rows, optionally with an unknown total or still loading
+struct TestSource {
+    len: usize,
+    known_total: bool,
+    loading: Rc<RefCell<bool>>,
+    calls: Rc<RefCell<Calls>>,
+}
+
+impl TestSource {
+    fn new(len: usize) -> (Self, Rc<RefCell<Calls>>) {
+        let calls = Rc::new(RefCell::new(Calls::default()));
+        let source = Self {
+            len,
+            known_total: true,
+            loading: Rc::new(RefCell::new(false)),
+            calls: Rc::clone(&calls),
+        };
+        (source, calls)
+    }
+}
+
+impl GridDataSource for TestSource {
+    fn row_count(&self) -> Option<usize> {
+        self.known_total.then_some(self.len)
+    }
+
+    fn fetch(&self, range: Range<usize>) -> Option<Vec<GridRow>> {
+        self.calls.borrow_mut().fetches.push(range.clone());
+        if *self.loading.borrow() {
+            return None;
+        }
+        Some(
+            (range.start..range.end.min(self.len))
+                .map(|i| GridRow::new().typed("id", i as i64))
+                .collect(),
+        )
+    }
+
+    fn sort(&mut self, columns: &[(String, SortDirection)]) {
+        self.calls.borrow_mut().sorts.push(columns.to_vec());
+    }
+
+    fn filter(&mut self, text: &str, column: Option<&str>) {
+        self.calls
+            .borrow_mut()
+            .filters
+            .push((text.to_string(), column.map(str::to_string)));
+    }
+
+    fn filter_columns(
+        &mut self,
+        filters: &[(String, ColumnFilter)],
+        query: Option<&crate::query::Query>,
+    ) {
+        self.calls
+            .borrow_mut()
+            .column_filters
+            .push((filters.to_vec(), query.is_some()));
+    }
+}
+
+fn source_grid(source: TestSource) -> DataGrid {
+    DataGrid::new()
+        .column(GridColumn::new("id", "ID").col_type(ColumnType::Number))
+        .page_size(10)
+        .overscan(2)
+        .data_source(source)
+}
+
+#[test]
+fn test_source_row_count() {
+    let (source, _) = TestSource::new(1_000_000);
+    let grid = source_grid(source);
+    assert!(grid.has_data_source());
+    assert_eq!(grid.row_count(), 1_000_000);
+}
+
+#[test]
+fn test_source_fetches_only_visible_pages() {
+    let (source, calls) = TestSource::new(1_000_000);
+    let grid = source_grid(source);
+
+    let buffer = render(&grid, 30, 6);
+    assert!(row_text(&buffer, 1).starts_with('0'));
+    assert_eq!(calls.borrow().fetches, vec![0..10]);
+
+    // Cached pages are not fetched again
+    render(&grid, 30, 6);
+    assert_eq!(calls.borrow().fetches.len(), 1);
+}
+
+#[test]
+fn test_source_fetches_around_scroll_position() {
+    let (source, calls) = TestSource::new(1_000_000);
+    let mut grid = source_grid(source);
+    grid.selected_row = 500_000;
+    grid.scroll_row = 500_000;
+
+    let buffer = render(&grid, 30, 6);
+    assert!(row_text(&buffer, 1).starts_with("500000"));
+    assert_eq!(
+        calls.borrow().fetches,
+        vec![499_990..500_000, 500_000..500_010]
+    );
+}
+
+#[test]
+fn test_source_loading_placeholder() {
+    let (source, calls) = TestSource::new(100);
+    let loading = Rc::clone(&source.loading);
+    *loading.borrow_mut() = true;
+    let grid = source_grid(source);
+
+    let buffer = render(&grid, 30, 6);
+    assert!(row_text(&buffer, 1).starts_with("Loading…"));
+
+    // Still-loading pages are asked for again on the next render
+    *loading.borrow_mut() = false;
+    let buffer = render(&grid, 30, 6);
+    assert!(row_text(&buffer, 1).starts_with('0'));
+    assert_eq!(calls.borrow().fetches.len(), 2);
+    assert_eq!(grid.row_at(3).unwrap().get("id"), Some("3"));
+}
+
+#[test]
+fn test_source_unknown_total() {
+    let (mut source, _) = TestSource::new(25);
+    source.known_total = false;
+    let mut grid = source_grid(source);
+    assert_eq!(grid.row_count(), 10);
+
+    // A full page means there may be more
+    render(&grid, 30, 6);
+    assert_eq!(grid.row_count(), 20);
+
+    grid.select_last();
+    grid.scroll_row = grid.selected_row;
+    render(&grid, 30, 6);
+    assert_eq!(grid.row_count(), 30);
+
+    // A short page is where the data ends
+    grid.select_last();
+    grid.scroll_row = grid.selected_row;
+    render(&grid, 30, 6);
+    assert_eq!(grid.row_count(), 25);
+    render(&grid, 30, 6);
+    assert_eq!(grid.row_count(), 25);
+}
+
+#[test]
+fn test_source_sort_is_delegated() {
+    let (source, calls) = TestSource::new(100);
+    let mut grid = source_grid(source).column(GridColumn::new("name", "Name"));
+    render(&grid, 30, 6);
+
+    grid.sort(0);
+    grid.sort(0);
+    grid.add_sort(1);
+    grid.clear_sort();
+
+    let calls = calls.borrow();
+    assert_eq!(
+        calls.sorts,
+        vec![
+            vec![("id".to_string(), SortDirection::Ascending)],
+            vec![("id".to_string(), SortDirection::Descending)],
+            vec![("name".to_string(), SortDirection::Ascending)],
+            vec![],
+        ]
+    );
+    // The cache was dropped, so nothing is fetched until the next render
+    assert_eq!(calls.fetches.len(), 1);
+}
+
+#[test]
+fn test_source_filter_is_delegated() {
+    let (source, calls) = TestSource::new(100);
+    let mut grid = source_grid(source);
+    grid.filter_column = Some(0);
+    grid.set_filter("FOO");
+
+    assert_eq!(
+        calls.borrow().filters,
+        vec![("foo".to_string(), Some("id".to_string()))]
+    );
+}
+
+#[test]
+fn test_source_column_filters_are_delegated() {
+    let (source, calls) = TestSource::new(100);
+    let mut grid = source_grid(source);
+    let range = ColumnFilter::number_range(Some(10.0), None);
+    grid.set_column_filter("id", range.clone());
+    grid.set_query("id:<50").unwrap();
+
+    assert_eq!(
+        calls.borrow().column_filters,
+        vec![
+            (vec![("id".to_string(), range.clone())], false),
+            (vec![("id".to_string(), range)], true),
+        ]
+    );
+}
+
+#[test]
+fn test_source_refresh_refetches() {
+    let (source, calls) = TestSource::new(100);
+    let mut grid = source_grid(source);
+    render(&grid, 30, 6);
+    grid.refresh_source();
+    render(&grid, 30, 6);
+    assert_eq!(calls.borrow().fetches, vec![0..10, 0..10]);
+}
+
+#[test]
+fn test_clear_data_source() {
+    let (source, _) = TestSource::new(100);
+    let mut grid = source_grid(source).row(GridRow::new().cell("id", "7"));
+    grid.clear_data_source();
+    assert!(!grid.has_data_source());
+    assert_eq!(grid.row_count(), 1);
+    assert_eq!(grid.row_at(0).unwrap().get("id"), Some("7"));
+}
@@ -1,5 +1,10 @@
 //! Aggregation types and configurations
 
+use std::fmt;
+use std::rc::Rc;
+
+use super::cell::CellValue;
+
 /// Aggregation function type
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum AggregationType {
@@ -27,6 +32,62 @@ impl AggregationType {
             AggregationType::Max => "Max",
         }
     }
+
+    /// Aggregate cell values
+    ///
+    /// Sum and average use the numeric values, count counts non-empty ones,
+    /// and min/max compare values of any type (so they work on dates too).
+    pub fn apply(&self, values: &[CellValue]) -> CellValue {
+        let present = values.iter().filter(|v| !v.is_empty());
+        match self {
+            AggregationType::Count => CellValue::Number(present.count() as f64),
+            AggregationType::Sum | AggregationType::Average => {
+                let numbers: Vec<f64> = present.filter_map(CellValue::as_number).collect();
+                if numbers.is_empty() {
+                    return CellValue::Empty;
+                }
+                let sum: f64 = numbers.iter().sum();
+                if *self == AggregationType::Sum {
+                    CellValue::Number(sum)
+                } else {
+                    CellValue::Number(sum / numbers.len() as f64)
+                }
+            }
+            AggregationType::Min => present
+                .min_by(|a, b| a.compare(b, true))
+                .cloned()
+                .unwrap_or(CellValue::Empty),
+            AggregationType::Max => present
+                .max_by(|a, b| a.compare(b, true))
+                .cloned()
+                .unwrap_or(CellValue::Empty),
+        }
+    }
+}
+
+/// Aggregate function over a column's values
+pub type AggregateFn = Rc<dyn Fn(&[CellValue]) -> CellValue>;
+
+/// Custom aggregate function over a column's values
+#[derive(Clone)]
+pub struct CustomAggregate(AggregateFn);
+
+impl CustomAggregate {
+    /// Wrap an aggregate function
+    pub fn new(f: impl Fn(&[CellValue]) -> CellValue + 'static) -> Self {
+        Self(Rc::new(f))
+    }
+
+    /// Run the function
+    pub fn apply(&self, values: &[CellValue]) -> CellValue {
+        (self.0)(values)
+    }
+}
+
+impl fmt::Debug for CustomAggregate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CustomAggregate")
+    }
 }
 
 /// Column aggregation configuration
@@ -38,6 +99,8 @@ pub struct ColumnAggregation {
     pub agg_type: AggregationType,
     /// Custom label (overrides default)
     pub label: Option<String>,
+    /// Custom function (used instead of `agg_type`)
+    pub custom: Option<CustomAggregate>,
 }
 
 impl ColumnAggregation {
@@ -47,9 +110,50 @@ impl ColumnAggregation {
             column_key: column_key.into(),
             agg_type,
             label: None,
+            custom: None,
+        }
+    }
+
+    /// Create an aggregation with a custom function
+    pub fn custom(
+        column_key: impl Into<String>,
+        label: impl Into<String>,
+        f: impl Fn(&[CellValue]) -> CellValue + 'static,
+    ) -> Self {
+        Self {
+            column_key: column_key.into(),
+            agg_type: AggregationType::default(),
+            label: Some(label.into()),
+            custom: Some(CustomAggregate::new(f)),
         }
     }
 
+    /// Display label
+    pub fn display_label(&self) -> String {
+        self.label
+            .clone()
+            .unwrap_or_else(|| self.agg_type.label().to_string())
+    }
+
+    /// Aggregate cell values
+    pub fn compute(&self, values: &[CellValue]) -> CellValue {
+        match &self.custom {
+            Some(custom) => custom.apply(values),
+            None => self.agg_type.apply(values),
+        }
+    }
+
+    /// Aggregate and format as `label: value` (`—` when there is no value)
+    pub fn format(&self, values: &[CellValue]) -> String {
+        let value = match self.compute(values) {
+            CellValue::Empty => "—".to_string(),
+            CellValue::Number(n) if n.fract() == 0.0 => format!("{:.0}", n),
+            CellValue::Number(n) => format!("{:.2}", n),
+            other => other.to_string(),
+        };
+        format!("{}: {}", self.display_label(), value)
+    }
+
     /// Set custom label
     pub fn label(mut self, label: impl Into<String>) -> Self {
         self.label = Some(label.into());
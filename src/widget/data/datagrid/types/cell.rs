@@ -0,0 +1,222 @@
+//! Typed cell values
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use super::column_types::ColumnType;
+use crate::query::QueryValue;
+use crate::utils::natural_cmp;
+use crate::widget::data::calendar::Date;
+
+/// A typed cell value
+///
+/// Rows keep a display string for every cell; a typed value can be stored
+/// next to it (see [`GridRow::typed`](super::GridRow::typed)) so sorting and
+/// aggregation don't have to re-parse the string. Cells without one are
+/// parsed on demand according to the column's [`ColumnType`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum CellValue {
+    /// No value
+    Empty,
+    /// Text
+    Text(String),
+    /// Number
+    Number(f64),
+    /// Calendar date
+    Date(Date),
+    /// Boolean
+    Boolean(bool),
+}
+
+impl CellValue {
+    /// Parse a display string as a value of the given column type
+    ///
+    /// Strings that don't parse as the column's type are kept as text.
+    pub fn parse(text: &str, col_type: ColumnType) -> Self {
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            return CellValue::Empty;
+        }
+        let parsed = match col_type {
+            ColumnType::Number => trimmed.parse().ok().map(CellValue::Number),
+            ColumnType::Date => parse_date(trimmed).map(CellValue::Date),
+            ColumnType::Boolean => parse_bool(trimmed).map(CellValue::Boolean),
+            ColumnType::Text | ColumnType::Custom => None,
+        };
+        parsed.unwrap_or_else(|| CellValue::Text(text.to_string()))
+    }
+
+    /// Parse a display string without a known column type
+    ///
+    /// Numbers, dates and `true`/`false` are recognized; anything else is
+    /// kept as text.
+    pub fn infer(text: &str) -> Self {
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            return CellValue::Empty;
+        }
+        if let Ok(n) = trimmed.parse() {
+            return CellValue::Number(n);
+        }
+        if let Some(d) = parse_date(trimmed) {
+            return CellValue::Date(d);
+        }
+        match trimmed.to_lowercase().as_str() {
+            "true" => CellValue::Boolean(true),
+            "false" => CellValue::Boolean(false),
+            _ => CellValue::Text(text.to_string()),
+        }
+    }
+
+    /// Get the value as a number, if it is one
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            CellValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a date, if it is one
+    pub fn as_date(&self) -> Option<Date> {
+        match self {
+            CellValue::Date(d) => Some(*d),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a boolean, if it is one
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            CellValue::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Check if the value is empty
+    pub fn is_empty(&self) -> bool {
+        matches!(self, CellValue::Empty)
+    }
+
+    /// Compare two values for sorting
+    ///
+    /// Empty values sort first. Values of different kinds (e.g. text that
+    /// failed to parse in a number column) sort after the typed ones.
+    pub fn compare(&self, other: &Self, natural: bool) -> Ordering {
+        match (self, other) {
+            (CellValue::Number(a), CellValue::Number(b)) => {
+                a.partial_cmp(b).unwrap_or(Ordering::Equal)
+            }
+            (CellValue::Date(a), CellValue::Date(b)) => a.cmp(b),
+            (CellValue::Boolean(a), CellValue::Boolean(b)) => a.cmp(b),
+            (CellValue::Text(a), CellValue::Text(b)) if natural => natural_cmp(a, b),
+            (CellValue::Text(a), CellValue::Text(b)) => a.cmp(b),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+
+    /// Sort rank of the value's kind
+    fn rank(&self) -> u8 {
+        match self {
+            CellValue::Empty => 0,
+            CellValue::Boolean(_) => 1,
+            CellValue::Number(_) => 2,
+            CellValue::Date(_) => 3,
+            CellValue::Text(_) => 4,
+        }
+    }
+}
+
+impl fmt::Display for CellValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CellValue::Empty => Ok(()),
+            CellValue::Text(s) => f.write_str(s),
+            CellValue::Number(n) => write!(f, "{}", n),
+            CellValue::Date(d) => write!(f, "{:04}-{:02}-{:02}", d.year, d.month, d.day),
+            CellValue::Boolean(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+impl From<&CellValue> for QueryValue {
+    fn from(value: &CellValue) -> Self {
+        match value {
+            CellValue::Empty => QueryValue::Null,
+            CellValue::Text(s) => QueryValue::String(s.clone()),
+            CellValue::Number(n) => QueryValue::Float(*n),
+            CellValue::Date(_) => QueryValue::Date(value.to_string()),
+            CellValue::Boolean(b) => QueryValue::Bool(*b),
+        }
+    }
+}
+
+impl From<&str> for CellValue {
+    fn from(s: &str) -> Self {
+        CellValue::Text(s.to_string())
+    }
+}
+
+impl From<String> for CellValue {
+    fn from(s: String) -> Self {
+        CellValue::Text(s)
+    }
+}
+
+impl From<f64> for CellValue {
+    fn from(n: f64) -> Self {
+        CellValue::Number(n)
+    }
+}
+
+impl From<i64> for CellValue {
+    fn from(n: i64) -> Self {
+        CellValue::Number(n as f64)
+    }
+}
+
+impl From<i32> for CellValue {
+    fn from(n: i32) -> Self {
+        CellValue::Number(n as f64)
+    }
+}
+
+impl From<bool> for CellValue {
+    fn from(b: bool) -> Self {
+        CellValue::Boolean(b)
+    }
+}
+
+impl From<Date> for CellValue {
+    fn from(d: Date) -> Self {
+        CellValue::Date(d)
+    }
+}
+
+impl<T: Into<CellValue>> From<Option<T>> for CellValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(CellValue::Empty, Into::into)
+    }
+}
+
+/// Parse `YYYY-MM-DD` (or `YYYY/MM/DD`), ignoring any time after the date
+fn parse_date(s: &str) -> Option<Date> {
+    let date = s.split(['T', ' ']).next()?;
+    let mut parts = date.split(['-', '/']);
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    let date = Date::new(year, month, day);
+    date.is_valid().then_some(date)
+}
+
+/// Parse common spellings of true and false
+fn parse_bool(s: &str) -> Option<bool> {
+    match s.to_lowercase().as_str() {
+        "true" | "yes" | "y" | "1" | "on" | "✓" => Some(true),
+        "false" | "no" | "n" | "0" | "off" | "✗" => Some(false),
+        _ => None,
+    }
+}
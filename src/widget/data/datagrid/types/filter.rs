@@ -0,0 +1,169 @@
+//! Per-column filter definitions
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use super::cell::CellValue;
+use crate::widget::data::calendar::Date;
+
+/// A case-insensitive text pattern
+///
+/// With the `regex` feature the pattern is a regular expression; without
+/// it, or when the pattern doesn't compile, it is matched as literal text.
+#[derive(Clone)]
+pub struct TextPattern {
+    pattern: String,
+    #[cfg(feature = "regex")]
+    regex: Option<regex::Regex>,
+}
+
+impl TextPattern {
+    /// Compile a pattern
+    pub fn new(pattern: impl Into<String>) -> Self {
+        let pattern = pattern.into();
+        Self {
+            #[cfg(feature = "regex")]
+            regex: regex::RegexBuilder::new(&pattern)
+                .case_insensitive(true)
+                .build()
+                .ok(),
+            pattern,
+        }
+    }
+
+    /// The pattern as written
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Check if the pattern matches anywhere in `text`
+    pub fn is_match(&self, text: &str) -> bool {
+        #[cfg(feature = "regex")]
+        if let Some(regex) = &self.regex {
+            return regex.is_match(text);
+        }
+        text.to_lowercase().contains(&self.pattern.to_lowercase())
+    }
+}
+
+impl fmt::Debug for TextPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TextPattern").field(&self.pattern).finish()
+    }
+}
+
+impl PartialEq for TextPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern
+    }
+}
+
+/// A filter on one column
+///
+/// Range bounds are inclusive; `None` leaves that end open.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColumnFilter {
+    /// Text contains a substring (case-insensitive)
+    Contains(String),
+    /// Text matches a pattern
+    Regex(TextPattern),
+    /// Number within a range
+    NumberRange(Option<f64>, Option<f64>),
+    /// Date within a range
+    DateRange(Option<Date>, Option<Date>),
+    /// Boolean equals a value
+    Boolean(bool),
+    /// Text is one of a set of values (`""` stands for blank cells)
+    Values(BTreeSet<String>),
+}
+
+impl ColumnFilter {
+    /// Create a contains filter
+    pub fn contains(text: impl Into<String>) -> Self {
+        ColumnFilter::Contains(text.into())
+    }
+
+    /// Create a pattern filter
+    pub fn regex(pattern: impl Into<String>) -> Self {
+        ColumnFilter::Regex(TextPattern::new(pattern))
+    }
+
+    /// Create a number range filter
+    pub fn number_range(min: Option<f64>, max: Option<f64>) -> Self {
+        ColumnFilter::NumberRange(min, max)
+    }
+
+    /// Create a date range filter
+    pub fn date_range(from: Option<Date>, to: Option<Date>) -> Self {
+        ColumnFilter::DateRange(from, to)
+    }
+
+    /// Create a value checklist filter
+    pub fn values<I, S>(values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        ColumnFilter::Values(values.into_iter().map(Into::into).collect())
+    }
+
+    /// Check if a cell passes the filter
+    ///
+    /// `text` is the cell's display string and `value` its typed value.
+    pub fn matches(&self, text: &str, value: &CellValue) -> bool {
+        match self {
+            ColumnFilter::Contains(needle) => text.to_lowercase().contains(&needle.to_lowercase()),
+            ColumnFilter::Regex(pattern) => pattern.is_match(text),
+            ColumnFilter::NumberRange(min, max) => {
+                value.as_number().is_some_and(|n| in_range(n, *min, *max))
+            }
+            ColumnFilter::DateRange(from, to) => {
+                value.as_date().is_some_and(|d| in_range(d, *from, *to))
+            }
+            ColumnFilter::Boolean(b) => value.as_bool() == Some(*b),
+            ColumnFilter::Values(values) => values.contains(text),
+        }
+    }
+}
+
+/// Check `value` against inclusive, optional bounds
+fn in_range<T: PartialOrd>(value: T, min: Option<T>, max: Option<T>) -> bool {
+    min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+}
+
+/// Describe a range, e.g. `1 – 5`, `≥ 1` or `≤ 5`
+fn fmt_range<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    min: Option<T>,
+    max: Option<T>,
+) -> fmt::Result {
+    match (min, max) {
+        (Some(min), Some(max)) => write!(f, "{} – {}", min, max),
+        (Some(min), None) => write!(f, "≥ {}", min),
+        (None, Some(max)) => write!(f, "≤ {}", max),
+        (None, None) => f.write_str("any"),
+    }
+}
+
+impl fmt::Display for ColumnFilter {
+    /// Short description, as shown on filter chips
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColumnFilter::Contains(text) => write!(f, "~{}", text),
+            ColumnFilter::Regex(pattern) => write!(f, "/{}/", pattern.as_str()),
+            ColumnFilter::NumberRange(min, max) => fmt_range(f, *min, *max),
+            ColumnFilter::DateRange(from, to) => {
+                fmt_range(f, from.map(CellValue::Date), to.map(CellValue::Date))
+            }
+            ColumnFilter::Boolean(b) => f.write_str(if *b { "yes" } else { "no" }),
+            ColumnFilter::Values(values) => match values.len() {
+                1 => match values.iter().next().map(String::as_str) {
+                    Some("") => f.write_str("(blank)"),
+                    Some(value) => f.write_str(value),
+                    None => Ok(()),
+                },
+                n => write!(f, "{} values", n),
+            },
+        }
+    }
+}
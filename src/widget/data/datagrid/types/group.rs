@@ -0,0 +1,74 @@
+//! Row grouping and pivot definitions
+
+use super::aggregation::AggregationType;
+
+/// Header row of a group
+#[derive(Clone, Debug)]
+pub struct GroupHeader {
+    /// Group values from the outermost level down to this one
+    pub path: Vec<String>,
+    /// Key of the grouped column
+    pub column: String,
+    /// Nesting depth (0 = outermost)
+    pub depth: usize,
+    /// Number of data rows in the group
+    pub count: usize,
+    /// Group is collapsed
+    pub collapsed: bool,
+    /// Formatted aggregates (column key, text)
+    pub aggregates: Vec<(String, String)>,
+}
+
+impl GroupHeader {
+    /// The group's own value
+    pub fn value(&self) -> &str {
+        self.path.last().map(String::as_str).unwrap_or("")
+    }
+}
+
+/// A row of the grouped display
+#[derive(Clone, Debug)]
+pub enum DisplayRow {
+    /// Group header
+    Group(GroupHeader),
+    /// Data row (index into `rows`)
+    Row(usize),
+}
+
+/// Pivot table configuration
+///
+/// Each distinct value of `row_key` becomes a row and each distinct value
+/// of `column_key` a column, holding the aggregate of `value_key`.
+#[derive(Clone, Debug)]
+pub struct PivotConfig {
+    /// Column whose values become rows
+    pub row_key: String,
+    /// Column whose values become columns
+    pub column_key: String,
+    /// Column aggregated into the cells
+    pub value_key: String,
+    /// How values are aggregated
+    pub aggregation: AggregationType,
+}
+
+impl PivotConfig {
+    /// Create a pivot summing `value_key`
+    pub fn new(
+        row_key: impl Into<String>,
+        column_key: impl Into<String>,
+        value_key: impl Into<String>,
+    ) -> Self {
+        Self {
+            row_key: row_key.into(),
+            column_key: column_key.into(),
+            value_key: value_key.into(),
+            aggregation: AggregationType::Sum,
+        }
+    }
+
+    /// Set the aggregation
+    pub fn aggregation(mut self, aggregation: AggregationType) -> Self {
+        self.aggregation = aggregation;
+        self
+    }
+}
@@ -4,10 +4,13 @@
 
 // Submodules
 pub mod aggregation;
+pub mod cell;
 pub mod colors;
 pub mod column;
 pub mod column_types;
 pub mod export;
+pub mod filter;
+pub mod group;
 pub mod options;
 pub mod row;
 
@@ -492,10 +495,15 @@ mod tests {
 }
 
 // Re-exports for backward compatibility
-pub use aggregation::{AggregationType, ColumnAggregation, FooterRow};
+pub use aggregation::{
+    AggregateFn, AggregationType, ColumnAggregation, CustomAggregate, FooterRow,
+};
+pub use cell::CellValue;
 pub use colors::GridColors;
 pub use column::GridColumn;
 pub use column_types::{Alignment, ColumnType, SortDirection};
 pub use export::{ExportFormat, ExportOptions};
+pub use filter::{ColumnFilter, TextPattern};
+pub use group::{DisplayRow, GroupHeader, PivotConfig};
 pub use options::GridOptions;
 pub use row::GridRow;
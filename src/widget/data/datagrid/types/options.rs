@@ -19,6 +19,8 @@ pub struct GridOptions {
     pub row_height: u16,
     /// Overscan rows (extra rows rendered above/below viewport for smooth scrolling)
     pub overscan: usize,
+    /// Rows fetched per request from a data source
+    pub page_size: usize,
 }
 
 impl Default for GridOptions {
@@ -32,6 +34,7 @@ impl Default for GridOptions {
             virtual_scroll: true,
             row_height: 1,
             overscan: 5,
+            page_size: 100,
         }
     }
 }
@@ -1,10 +1,17 @@
 //! Grid row and cell definitions
 
+use super::cell::CellValue;
+use super::column::GridColumn;
+use super::column_types::ColumnType;
+use crate::query::{QueryValue, Queryable};
+
 /// A row in the grid
 #[derive(Clone, Debug)]
 pub struct GridRow {
     /// Row data (key -> value)
     pub data: Vec<(String, String)>,
+    /// Typed cell values (key -> value), kept next to their display strings
+    pub values: Vec<(String, CellValue)>,
     /// Row is selected
     pub selected: bool,
     /// Row is expanded (for tree grids)
@@ -18,6 +25,7 @@ impl GridRow {
     pub fn new() -> Self {
         Self {
             data: Vec::new(),
+            values: Vec::new(),
             selected: false,
             expanded: false,
             children: Vec::new(),
@@ -30,6 +38,15 @@ impl GridRow {
         self
     }
 
+    /// Add a typed cell (its display string is derived from the value)
+    pub fn typed(mut self, key: impl Into<String>, value: impl Into<CellValue>) -> Self {
+        let key = key.into();
+        let value = value.into();
+        self.data.push((key.clone(), value.to_string()));
+        self.values.push((key, value));
+        self
+    }
+
     /// Get cell value by key
     pub fn get(&self, key: &str) -> Option<&str> {
         self.data
@@ -38,6 +55,52 @@ impl GridRow {
             .map(|(_, v)| v.as_str())
     }
 
+    /// Get the typed value of a cell
+    ///
+    /// Returns the value stored with [`typed`](Self::typed) or parsed when
+    /// the row was added to the grid, or parses the display string as
+    /// `col_type`.
+    pub fn value(&self, key: &str, col_type: ColumnType) -> CellValue {
+        match self.values.iter().find(|(k, _)| k == key) {
+            Some((_, value)) => value.clone(),
+            None => self
+                .get(key)
+                .map_or(CellValue::Empty, |s| CellValue::parse(s, col_type)),
+        }
+    }
+
+    /// Set a cell's display string, dropping any typed value it had
+    pub fn set(&mut self, key: &str, value: impl Into<String>) {
+        let value = value.into();
+        match self.data.iter_mut().find(|(k, _)| k == key) {
+            Some(cell) => cell.1 = value,
+            None => self.data.push((key.to_string(), value)),
+        }
+        self.values.retain(|(k, _)| k != key);
+    }
+
+    /// Parse the cells of non-text columns into typed values
+    ///
+    /// Done once when the row enters a grid, so sorting, filtering and
+    /// aggregating don't re-parse display strings. Cells that already have
+    /// a typed value are kept.
+    pub(crate) fn parse_cells(&mut self, columns: &[GridColumn]) {
+        for col in columns {
+            if matches!(col.col_type, ColumnType::Text | ColumnType::Custom)
+                || self.values.iter().any(|(k, _)| *k == col.key)
+            {
+                continue;
+            }
+            if let Some(text) = self.get(&col.key) {
+                let value = CellValue::parse(text, col.col_type);
+                self.values.push((col.key.clone(), value));
+            }
+        }
+        for child in &mut self.children {
+            child.parse_cells(columns);
+        }
+    }
+
     /// Add a child row (for tree grid)
     pub fn child(mut self, row: GridRow) -> Self {
         self.children.push(row);
@@ -68,4 +131,30 @@ impl Default for GridRow {
     }
 }
 
+/// Rows can be filtered with the [query DSL](crate::query)
+///
+/// Fields are column keys (case-insensitive). Typed cells keep their type;
+/// other cells are inferred from their text.
+impl Queryable for GridRow {
+    fn field_value(&self, field: &str) -> Option<QueryValue> {
+        let (key, text) = self
+            .data
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(field))?;
+        let value = match self.values.iter().find(|(k, _)| k == key) {
+            Some((_, value)) => value.clone(),
+            None => CellValue::infer(text),
+        };
+        Some((&value).into())
+    }
+
+    fn full_text(&self) -> String {
+        self.data
+            .iter()
+            .map(|(_, v)| v.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
 // # KEEP HERE - Private tests for internal functionality
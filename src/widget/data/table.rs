@@ -107,6 +107,15 @@ impl Table {
         self
     }
 
+    /// Apply a row change from a `SignalVec`
+    ///
+    /// Use with [`SignalVec::diff_queue()`](crate::reactive::SignalVec::diff_queue)
+    /// to keep the table in sync with a reactive (possibly derived) vector.
+    pub fn apply_diff(&mut self, diff: crate::reactive::VecDiff<Vec<String>>) {
+        diff.apply_to(&mut self.rows);
+        self.selection.set_len(self.rows.len());
+    }
+
     /// Set selected row index
     pub fn selected(mut self, index: usize) -> Self {
         self.selection.set(index);
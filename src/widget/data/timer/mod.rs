@@ -19,6 +19,7 @@
 //! ```
 
 use crate::style::Color;
+use crate::utils::clock;
 use crate::widget::theme::{LIGHT_GRAY, MUTED_TEXT, PLACEHOLDER_FG};
 use crate::widget::traits::WidgetProps;
 use crate::widget::{RenderContext, View};
@@ -190,11 +191,11 @@ impl Timer {
     pub fn start(&mut self) {
         if self.state == TimerState::Paused {
             // Resume from pause
-            self.started_at = Some(Instant::now());
+            self.started_at = Some(clock::now());
             self.remaining_ms = self.paused_remaining.unwrap_or(self.remaining_ms);
             self.paused_remaining = None;
         } else if self.state != TimerState::Running {
-            self.started_at = Some(Instant::now());
+            self.started_at = Some(clock::now());
             self.remaining_ms = self.total_ms;
         }
         self.state = TimerState::Running;
@@ -221,7 +222,7 @@ impl Timer {
     pub fn reset(&mut self) {
         self.remaining_ms = self.total_ms;
         if self.state == TimerState::Running {
-            self.started_at = Some(Instant::now());
+            self.started_at = Some(clock::now());
         }
     }
 
@@ -240,7 +241,7 @@ impl Timer {
         }
 
         if let Some(started) = self.started_at {
-            let elapsed = started.elapsed().as_millis() as u64;
+            let elapsed = clock::elapsed(started).as_millis() as u64;
             let base = self.paused_remaining.unwrap_or(self.total_ms);
 
             if elapsed >= base {
@@ -249,7 +250,7 @@ impl Timer {
 
                 if self.auto_restart {
                     self.remaining_ms = self.total_ms;
-                    self.started_at = Some(Instant::now());
+                    self.started_at = Some(clock::now());
                     self.state = TimerState::Running;
                 }
             } else {
@@ -466,7 +467,7 @@ impl Stopwatch {
     /// Start the stopwatch
     pub fn start(&mut self) {
         if self.state != TimerState::Running {
-            self.started_at = Some(Instant::now());
+            self.started_at = Some(clock::now());
             self.state = TimerState::Running;
         }
     }
@@ -495,7 +496,7 @@ impl Stopwatch {
         self.accumulated_ms = 0;
         self.laps.clear();
         if self.state == TimerState::Running {
-            self.started_at = Some(Instant::now());
+            self.started_at = Some(clock::now());
         }
     }
 
@@ -519,7 +520,7 @@ impl Stopwatch {
     pub fn update(&mut self) {
         if self.state == TimerState::Running {
             if let Some(started) = self.started_at {
-                self.elapsed_ms = self.accumulated_ms + started.elapsed().as_millis() as u64;
+                self.elapsed_ms = self.accumulated_ms + clock::elapsed(started).as_millis() as u64;
             }
         }
     }
@@ -356,6 +356,38 @@ impl<T: ToString + Clone> VirtualList<T> {
         }
     }
 
+    /// Apply a change from a `SignalVec`
+    ///
+    /// Use with [`SignalVec::diff_queue()`](crate::reactive::SignalVec::diff_queue)
+    /// to keep the list in sync with a reactive (possibly derived) vector
+    /// without replacing all items on every change.
+    pub fn apply_diff(&mut self, diff: crate::reactive::VecDiff<T>) {
+        use crate::reactive::VecDiff;
+
+        match diff {
+            VecDiff::Insert { index, value } if index <= self.items.len() => {
+                self.items.insert(index, value);
+                // Keep the same item selected
+                if let Some(sel) = self.selected {
+                    if sel >= index {
+                        self.selected = Some(sel + 1);
+                    }
+                }
+            }
+            VecDiff::Remove { index, .. } => {
+                self.remove(index);
+            }
+            diff => {
+                let mut items = std::mem::take(&mut self.items);
+                diff.apply_to(&mut items);
+                self.set_items(items);
+            }
+        }
+        if self.height_calculator.is_some() {
+            self.rebuild_height_cache();
+        }
+    }
+
     /// Add item to the end
     pub fn push(&mut self, item: T) {
         self.items.push(item);
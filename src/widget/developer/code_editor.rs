@@ -5,41 +5,63 @@
 
 mod bracket;
 mod editing;
+mod folding;
 mod key_handling;
+#[cfg(feature = "lsp")]
+mod lsp;
 mod modes;
+mod mouse;
+mod multi_cursor;
 mod navigation;
 mod render;
 mod selection;
 mod types;
+mod vim;
 
+use std::cell::{Cell, RefCell};
 use std::path::Path;
 
 use crate::style::Color;
+use crate::utils::Document;
+#[cfg(feature = "syntax-highlighting")]
+use crate::widget::developer::SyntaxTree;
+use crate::widget::developer::VimState;
+use crate::widget::input_widgets::textarea::Cursor;
 use crate::widget::syntax::{Language, SyntaxHighlighter, SyntaxTheme};
 use crate::widget::traits::WidgetProps;
 use crate::{impl_props_builders, impl_styled_view};
 
 // Public exports
-pub use types::{BracketMatch, BracketPair, EditOp, EditorConfig, IndentStyle};
+pub use types::{BracketMatch, BracketPair, EditorConfig, FoldRange, IndentStyle, MinimapStyle};
+#[allow(deprecated)]
+pub use types::{EditOp, MAX_UNDO_HISTORY};
 
 /// Code editor widget
 pub struct CodeEditor {
-    /// Lines of code
-    pub(super) lines: Vec<String>,
+    /// Text with undo history
+    pub(super) doc: Document,
     /// Cursor position (line, column)
     pub(super) cursor: (usize, usize),
     /// Selection anchor (if selecting)
     pub(super) anchor: Option<(usize, usize)>,
+    /// Secondary cursors, sorted by position
+    pub(super) extra_cursors: Vec<Cursor>,
+    /// Position where the left mouse button went down
+    pub(super) mouse_anchor: Option<(usize, usize)>,
     /// Scroll offset (line, column)
     pub(super) scroll: (usize, usize),
-    /// Undo stack
-    pub(super) undo_stack: Vec<EditOp>,
-    /// Redo stack
-    pub(super) redo_stack: Vec<EditOp>,
+    /// Text rows in the last render, 0 before the first
+    pub(super) last_viewport_height: Cell<usize>,
     /// Language for syntax highlighting
     pub(super) language: Language,
     /// Syntax highlighter
     pub(super) highlighter: Option<SyntaxHighlighter>,
+    /// Incremental parse tree, for languages with a tree-sitter grammar
+    #[cfg(feature = "syntax-highlighting")]
+    pub(super) syntax: Option<SyntaxTree>,
+    /// Attached language server
+    #[cfg(feature = "lsp")]
+    pub(super) lsp: Option<lsp::LspSession>,
     /// Syntax theme
     pub(super) theme: SyntaxTheme,
     /// Editor configuration
@@ -62,6 +84,12 @@ pub struct CodeEditor {
     pub(super) find_matches: Vec<(usize, usize, usize)>, // (line, start, end)
     /// Current find match index
     pub(super) find_index: usize,
+    /// Header lines of folded regions, sorted
+    pub(super) folded: Vec<usize>,
+    /// Fold ranges and the document version they were computed for
+    pub(super) fold_cache: RefCell<Option<(u64, Vec<FoldRange>)>>,
+    /// Vim mode state, when enabled
+    pub(super) vim: Option<VimState>,
     /// Colors
     /// Background color
     pub bg: Option<Color>,
@@ -85,6 +113,20 @@ pub struct CodeEditor {
     pub minimap_bg: Color,
     /// Minimap visible area background color
     pub minimap_visible_bg: Color,
+    /// Error diagnostic color
+    pub diagnostic_error_fg: Color,
+    /// Warning diagnostic color
+    pub diagnostic_warning_fg: Color,
+    /// Information diagnostic color
+    pub diagnostic_info_fg: Color,
+    /// Hint diagnostic color
+    pub diagnostic_hint_fg: Color,
+    /// Fold marker foreground color
+    pub fold_marker_fg: Color,
+    /// Indentation guide foreground color
+    pub indent_guide_fg: Color,
+    /// Sticky scroll header background color
+    pub sticky_scroll_bg: Color,
     /// Widget props
     pub props: WidgetProps,
 }
@@ -93,14 +135,19 @@ impl CodeEditor {
     /// Create a new code editor
     pub fn new() -> Self {
         Self {
-            lines: vec![String::new()],
+            doc: Document::new(),
             cursor: (0, 0),
             anchor: None,
+            extra_cursors: Vec::new(),
+            mouse_anchor: None,
             scroll: (0, 0),
-            undo_stack: Vec::new(),
-            redo_stack: Vec::new(),
+            last_viewport_height: Cell::new(0),
             language: Language::None,
             highlighter: None,
+            #[cfg(feature = "syntax-highlighting")]
+            syntax: None,
+            #[cfg(feature = "lsp")]
+            lsp: None,
             theme: SyntaxTheme::dark(),
             config: EditorConfig::default(),
             show_line_numbers: true,
@@ -112,6 +159,9 @@ impl CodeEditor {
             find_query: String::new(),
             find_matches: Vec::new(),
             find_index: 0,
+            folded: Vec::new(),
+            fold_cache: RefCell::new(None),
+            vim: None,
             bg: Some(Color::rgb(30, 30, 46)),
             fg: Some(Color::rgb(205, 214, 244)),
             cursor_bg: Color::rgb(166, 227, 161),
@@ -123,47 +173,51 @@ impl CodeEditor {
             current_find_bg: Color::rgb(250, 179, 135),
             minimap_bg: Color::rgb(24, 24, 37),
             minimap_visible_bg: Color::rgb(49, 50, 68),
+            diagnostic_error_fg: Color::rgb(243, 139, 168),
+            diagnostic_warning_fg: Color::rgb(249, 226, 175),
+            diagnostic_info_fg: Color::rgb(137, 180, 250),
+            diagnostic_hint_fg: Color::rgb(148, 226, 213),
+            fold_marker_fg: Color::rgb(147, 153, 178),
+            indent_guide_fg: Color::rgb(69, 71, 90),
+            sticky_scroll_bg: Color::rgb(36, 39, 58),
             props: WidgetProps::new(),
         }
     }
 
     /// Set content
     pub fn content(mut self, text: impl Into<String>) -> Self {
-        let text = text.into();
-        self.lines = text.lines().map(String::from).collect();
-        if self.lines.is_empty() {
-            self.lines.push(String::new());
-        }
-        self.cursor = (0, 0);
-        self.scroll = (0, 0);
+        self.set_content(&text.into());
         self
     }
 
     /// Set content (mutable)
     pub fn set_content(&mut self, text: &str) {
-        self.lines = text.lines().map(String::from).collect();
-        if self.lines.is_empty() {
-            self.lines.push(String::new());
-        }
+        let text = text.lines().collect::<Vec<_>>().join("\n");
+        self.doc.set_content(&text);
+        self.folded.clear();
+        self.sync_document();
         self.cursor = (0, 0);
+        self.extra_cursors.clear();
         self.scroll = (0, 0);
-        self.undo_stack.clear();
-        self.redo_stack.clear();
     }
 
     /// Get content
     pub fn get_content(&self) -> String {
-        self.lines.join("\n")
+        self.doc.text()
+    }
+
+    /// Get the underlying document
+    pub fn document(&self) -> &Document {
+        &self.doc
     }
 
     /// Set language for syntax highlighting
+    ///
+    /// With the `syntax-highlighting` feature, languages that have a
+    /// tree-sitter grammar are parsed incrementally, so multi-line constructs
+    /// and embedded languages highlight correctly.
     pub fn language(mut self, lang: Language) -> Self {
-        self.language = lang;
-        if lang != Language::None {
-            self.highlighter = Some(SyntaxHighlighter::with_theme(lang, self.theme.clone()));
-        } else {
-            self.highlighter = None;
-        }
+        self.set_language(lang);
         self
     }
 
@@ -175,6 +229,42 @@ impl CodeEditor {
         } else {
             self.highlighter = None;
         }
+
+        #[cfg(feature = "syntax-highlighting")]
+        {
+            self.syntax = SyntaxTree::new(lang).map(|tree| tree.theme(self.theme.clone()));
+            self.fold_cache.replace(None);
+            self.update_change_tracking();
+            self.sync_document();
+        }
+    }
+
+    /// Track document changes while something consumes them
+    pub(super) fn update_change_tracking(&mut self) {
+        let consumers = [
+            !self.folded.is_empty(),
+            #[cfg(feature = "syntax-highlighting")]
+            self.syntax.is_some(),
+            #[cfg(feature = "lsp")]
+            self.lsp.is_some(),
+        ];
+        self.doc.set_track_changes(consumers.contains(&true));
+    }
+
+    /// Bring folds, the syntax tree and the language server up to date with
+    /// the document
+    pub(super) fn sync_document(&mut self) {
+        let changes = self.doc.take_changes();
+        #[cfg(feature = "syntax-highlighting")]
+        if let Some(syntax) = &mut self.syntax {
+            syntax.apply(&self.doc, &changes);
+        }
+        #[cfg(feature = "lsp")]
+        if let Some(lsp) = &mut self.lsp {
+            lsp.client.did_change(&lsp.uri, &self.doc, &changes);
+        }
+        // After the syntax tree, which fold ranges may come from
+        self.shift_folds(&changes);
     }
 
     /// Detect language from file extension
@@ -189,6 +279,10 @@ impl CodeEditor {
     /// Set syntax theme
     pub fn theme(mut self, theme: SyntaxTheme) -> Self {
         self.theme = theme.clone();
+        #[cfg(feature = "syntax-highlighting")]
+        if let Some(syntax) = &mut self.syntax {
+            syntax.set_theme(theme.clone());
+        }
         if let Some(ref mut hl) = self.highlighter {
             *hl = SyntaxHighlighter::with_theme(self.language, theme);
         }
@@ -255,6 +349,30 @@ impl CodeEditor {
         self
     }
 
+    /// Set minimap style
+    pub fn minimap_style(mut self, style: MinimapStyle) -> Self {
+        self.config.minimap_style = style;
+        self
+    }
+
+    /// Enable/disable code folding
+    pub fn folding(mut self, enable: bool) -> Self {
+        self.config.folding = enable;
+        self
+    }
+
+    /// Enable/disable indentation guides
+    pub fn indent_guides(mut self, enable: bool) -> Self {
+        self.config.indent_guides = enable;
+        self
+    }
+
+    /// Enable/disable sticky scroll of the enclosing scope header
+    pub fn sticky_scroll(mut self, enable: bool) -> Self {
+        self.config.sticky_scroll = enable;
+        self
+    }
+
     /// Set background color
     pub fn bg(mut self, color: Color) -> Self {
         self.bg = Some(color);
@@ -274,7 +392,7 @@ impl CodeEditor {
     /// Get line at index
     #[doc(hidden)]
     pub fn get_line(&self, index: usize) -> Option<String> {
-        self.lines.get(index).cloned()
+        (index < self.doc.len_lines()).then(|| self.doc.line(index))
     }
 }
 
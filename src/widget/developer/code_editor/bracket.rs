@@ -15,8 +15,7 @@ impl super::CodeEditor {
             return None;
         }
 
-        let line = self.lines.get(self.cursor.0)?;
-        let chars: Vec<char> = line.chars().collect();
+        let chars: Vec<char> = self.doc.line(self.cursor.0).chars().collect();
         let col = self.cursor.1;
 
         if col >= chars.len() {
@@ -53,9 +52,8 @@ impl super::CodeEditor {
         let mut line_idx = start_line;
         let mut col_idx = start_col + 1;
 
-        while line_idx < self.lines.len() {
-            let line = &self.lines[line_idx];
-            let chars: Vec<char> = line.chars().collect();
+        while line_idx < self.doc.len_lines() {
+            let chars: Vec<char> = self.doc.line(line_idx).chars().collect();
 
             while col_idx < chars.len() {
                 let ch = chars[col_idx];
@@ -93,8 +91,7 @@ impl super::CodeEditor {
         let mut col_idx = start_col;
 
         loop {
-            let line = &self.lines[line_idx];
-            let chars: Vec<char> = line.chars().collect();
+            let chars: Vec<char> = self.doc.line(line_idx).chars().collect();
 
             while col_idx > 0 {
                 col_idx -= 1;
@@ -116,7 +113,7 @@ impl super::CodeEditor {
                 break;
             }
             line_idx -= 1;
-            col_idx = self.lines[line_idx].len();
+            col_idx = self.doc.line_len(line_idx);
         }
 
         None
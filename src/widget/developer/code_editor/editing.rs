@@ -2,91 +2,88 @@
 //!
 //! Public API tests extracted to tests/widget/code_editor/editing.rs
 
-use super::types::{EditOp, IndentStyle};
+use super::types::IndentStyle;
 
 impl super::CodeEditor {
     // =========================================================================
     // Editing
     // =========================================================================
 
-    /// Push undo operation
-    pub(super) fn push_undo(&mut self, op: EditOp) {
-        self.undo_stack.push(op);
-        if self.undo_stack.len() > super::types::MAX_UNDO_HISTORY {
-            self.undo_stack.remove(0);
-        }
-        self.redo_stack.clear();
-    }
-
-    /// Insert character
+    /// Insert character at every cursor
+    ///
+    /// Replacing a selection and auto-closing a bracket undo together with
+    /// the typed character.
     pub fn insert_char(&mut self, ch: char) {
         if self.read_only {
             return;
         }
-
-        if self.has_selection() {
-            self.delete_selection();
+        if self.has_multiple_cursors() {
+            self.edit_at_cursors(|editor, _| editor.insert_char(ch));
+            return;
         }
 
+        self.doc.begin_transaction();
+        self.delete_selection();
+
         if ch == '\n' {
             self.insert_newline();
-            return;
-        }
-
-        if ch == '\t' {
+        } else if ch == '\t' {
             self.insert_indent();
-            return;
-        }
-
-        if let Some(line) = self.lines.get_mut(self.cursor.0) {
-            let col = self.cursor.1.min(line.len());
-            line.insert(col, ch);
-            self.push_undo(EditOp::Insert {
-                line: self.cursor.0,
-                col,
-                text: ch.to_string(),
-            });
-            self.cursor.1 = col + 1;
-        }
-
-        // Auto-close brackets
-        if self.config.bracket_matching {
-            let close = match ch {
-                '(' => Some(')'),
-                '[' => Some(']'),
-                '{' => Some('}'),
-                '"' => Some('"'),
-                '\'' => Some('\''),
-                _ => None,
-            };
-            if let Some(close_ch) = close {
-                if let Some(line) = self.lines.get_mut(self.cursor.0) {
-                    let col = self.cursor.1.min(line.len());
-                    line.insert(col, close_ch);
+        } else {
+            self.insert_at_cursor(ch.encode_utf8(&mut [0; 4]));
+
+            // Auto-close brackets
+            if self.config.bracket_matching {
+                let close = match ch {
+                    '(' => Some(")"),
+                    '[' => Some("]"),
+                    '{' => Some("}"),
+                    '"' => Some("\""),
+                    '\'' => Some("'"),
+                    _ => None,
+                };
+                if let Some(close) = close {
+                    self.doc.insert(self.cursor_char(), close);
                 }
             }
         }
+
+        self.doc.commit_transaction();
+        self.sync_document();
     }
 
-    /// Insert string
+    /// Insert string at every cursor
+    ///
+    /// Newlines are auto-indented like typed ones. The whole insertion is a
+    /// single undo step.
     pub fn insert_str(&mut self, s: &str) {
         if self.read_only {
             return;
         }
-
-        if self.has_selection() {
-            self.delete_selection();
+        if self.has_multiple_cursors() {
+            self.edit_at_cursors(|editor, _| editor.insert_str(s));
+            return;
         }
 
-        for ch in s.chars() {
-            if ch == '\n' {
+        self.doc.begin_transaction();
+        self.delete_selection();
+
+        for (i, part) in s.split('\n').enumerate() {
+            if i > 0 {
                 self.insert_newline();
-            } else if let Some(line) = self.lines.get_mut(self.cursor.0) {
-                let col = self.cursor.1.min(line.len());
-                line.insert(col, ch);
-                self.cursor.1 = col + 1;
             }
+            self.insert_at_cursor(part);
         }
+
+        self.doc.commit_transaction();
+        self.sync_document();
+    }
+
+    /// Insert text without newlines and move the cursor past it
+    fn insert_at_cursor(&mut self, text: &str) {
+        let at = self.cursor_char();
+        self.doc.insert(at, text);
+        self.cursor = self.doc.char_to_pos(at + text.chars().count());
     }
 
     /// Insert newline with auto-indent
@@ -95,11 +92,11 @@ impl super::CodeEditor {
             return;
         }
 
-        let (line_idx, col) = self.cursor;
+        let line_idx = self.cursor.0;
 
         // Get current line's indentation
         let indent = if self.config.auto_indent {
-            let current_line = &self.lines[line_idx];
+            let current_line = self.doc.line(line_idx);
             let leading_ws: String = current_line
                 .chars()
                 .take_while(|c| c.is_whitespace())
@@ -114,11 +111,7 @@ impl super::CodeEditor {
 
             let base = leading_ws;
             if extra_indent {
-                let indent_str = match self.config.indent_style {
-                    IndentStyle::Spaces => " ".repeat(self.config.indent_size),
-                    IndentStyle::Tabs => "\t".to_string(),
-                };
-                format!("{}{}", base, indent_str)
+                format!("{}{}", base, self.indent_unit())
             } else {
                 base
             }
@@ -127,123 +120,97 @@ impl super::CodeEditor {
         };
 
         // Split line
-        if let Some(current) = self.lines.get_mut(line_idx) {
-            let rest: String = current.drain(col.min(current.len())..).collect();
-            let new_line = format!("{}{}", indent, rest);
-            self.lines.insert(line_idx + 1, new_line);
-            self.push_undo(EditOp::SplitLine {
-                line: line_idx,
-                col,
-            });
-            self.cursor = (line_idx + 1, indent.len());
-        }
+        self.doc
+            .insert(self.cursor_char(), &format!("\n{}", indent));
+        self.cursor = (line_idx + 1, indent.chars().count());
 
         self.ensure_cursor_visible();
     }
 
+    /// One level of indentation
+    pub(super) fn indent_unit(&self) -> String {
+        match self.config.indent_style {
+            IndentStyle::Spaces => " ".repeat(self.config.indent_size),
+            IndentStyle::Tabs => "\t".to_string(),
+        }
+    }
+
     /// Insert indent
     pub(super) fn insert_indent(&mut self) {
         if self.read_only {
             return;
         }
 
-        let indent = match self.config.indent_style {
-            IndentStyle::Spaces => " ".repeat(self.config.indent_size),
-            IndentStyle::Tabs => "\t".to_string(),
-        };
-
-        if let Some(line) = self.lines.get_mut(self.cursor.0) {
-            let col = self.cursor.1.min(line.len());
-            line.insert_str(col, &indent);
-            self.push_undo(EditOp::Insert {
-                line: self.cursor.0,
-                col,
-                text: indent.clone(),
-            });
-            self.cursor.1 = col + indent.len();
-        }
+        let indent = self.indent_unit();
+        self.insert_at_cursor(&indent);
     }
 
-    /// Delete character before cursor (backspace)
+    /// Delete character before each cursor (backspace)
     pub fn delete_char_before(&mut self) {
         if self.read_only {
             return;
         }
+        if self.has_multiple_cursors() {
+            self.edit_at_cursors(|editor, _| editor.delete_char_before());
+            return;
+        }
 
         if self.has_selection() {
             self.delete_selection();
             return;
         }
 
-        let (line_idx, col) = self.cursor;
-        if col > 0 {
-            if let Some(line) = self.lines.get_mut(line_idx) {
-                if col <= line.len() {
-                    let deleted = line.remove(col - 1);
-                    self.push_undo(EditOp::Delete {
-                        line: line_idx,
-                        col: col - 1,
-                        text: deleted.to_string(),
-                    });
-                    self.cursor.1 = col - 1;
-                }
-            }
-        } else if line_idx > 0 {
-            // Merge with previous line
-            let current = self.lines.remove(line_idx);
-            let prev_len = self.lines[line_idx - 1].len();
-            self.lines[line_idx - 1].push_str(&current);
-            self.push_undo(EditOp::MergeLine {
-                line: line_idx - 1,
-                col: prev_len,
-            });
-            self.cursor = (line_idx - 1, prev_len);
+        let at = self.cursor_char();
+        if at > 0 {
+            // Merges with the previous line at column 0
+            let start = self.doc.prev_grapheme(at);
+            self.doc.remove(start..at);
+            self.sync_document();
+            self.cursor = self.doc.char_to_pos(start);
         }
 
         self.ensure_cursor_visible();
     }
 
-    /// Delete character at cursor (delete key)
+    /// Delete character at each cursor (delete key)
     pub fn delete_char_at(&mut self) {
         if self.read_only {
             return;
         }
+        if self.has_multiple_cursors() {
+            self.edit_at_cursors(|editor, _| editor.delete_char_at());
+            return;
+        }
 
         if self.has_selection() {
             self.delete_selection();
             return;
         }
 
-        let (line_idx, col) = self.cursor;
-        if let Some(line) = self.lines.get_mut(line_idx) {
-            if col < line.len() {
-                let deleted = line.remove(col);
-                self.push_undo(EditOp::Delete {
-                    line: line_idx,
-                    col,
-                    text: deleted.to_string(),
-                });
-            } else if line_idx + 1 < self.lines.len() {
-                // Merge with next line
-                let next = self.lines.remove(line_idx + 1);
-                self.lines[line_idx].push_str(&next);
-                self.push_undo(EditOp::MergeLine {
-                    line: line_idx,
-                    col,
-                });
-            }
-        }
+        // Merges with the next line at end of line
+        let at = self.cursor_char();
+        let end = self.doc.next_grapheme(at);
+        self.doc.remove(at..end);
+        self.sync_document();
     }
 
     /// Delete current line
     pub fn delete_line(&mut self) {
-        if self.read_only || self.lines.len() <= 1 {
+        let line_count = self.doc.len_lines();
+        if self.read_only || line_count <= 1 {
             return;
         }
 
         let line_idx = self.cursor.0;
-        self.lines.remove(line_idx);
-        self.cursor.0 = line_idx.min(self.lines.len().saturating_sub(1));
+        let range = if line_idx + 1 < line_count {
+            self.doc.line_to_char(line_idx)..self.doc.line_to_char(line_idx + 1)
+        } else {
+            // Last line: remove the newline before it instead
+            self.doc.line_to_char(line_idx) - 1..self.doc.len_chars()
+        };
+        self.doc.remove(range);
+        self.sync_document();
+        self.cursor.0 = line_idx.min(self.doc.len_lines() - 1);
         self.cursor.1 = 0;
         self.ensure_cursor_visible();
     }
@@ -255,86 +222,32 @@ impl super::CodeEditor {
         }
 
         let line_idx = self.cursor.0;
-        let content = self.lines[line_idx].clone();
-        self.lines.insert(line_idx + 1, content);
+        let content = self.doc.line(line_idx);
+        let end = self.doc.pos_to_char(line_idx, usize::MAX);
+        self.doc.insert(end, &format!("\n{}", content));
+        self.sync_document();
         self.cursor.0 = line_idx + 1;
         self.ensure_cursor_visible();
     }
 
     /// Undo
     pub fn undo(&mut self) {
-        if let Some(op) = self.undo_stack.pop() {
-            match &op {
-                EditOp::Insert { line, col, text } => {
-                    if let Some(l) = self.lines.get_mut(*line) {
-                        let end = (*col + text.len()).min(l.len());
-                        l.drain(*col..end);
-                    }
-                    self.cursor = (*line, *col);
-                }
-                EditOp::Delete { line, col, text } => {
-                    if let Some(l) = self.lines.get_mut(*line) {
-                        l.insert_str(*col, text);
-                    }
-                    self.cursor = (*line, *col + text.len());
-                }
-                EditOp::SplitLine { line, col } => {
-                    if *line + 1 < self.lines.len() {
-                        let next = self.lines.remove(*line + 1);
-                        if let Some(l) = self.lines.get_mut(*line) {
-                            l.push_str(&next);
-                        }
-                    }
-                    self.cursor = (*line, *col);
-                }
-                EditOp::MergeLine { line, col } => {
-                    if let Some(l) = self.lines.get_mut(*line) {
-                        let rest: String = l.drain(*col..).collect();
-                        self.lines.insert(*line + 1, rest);
-                    }
-                    self.cursor = (*line + 1, 0);
-                }
-            }
-            self.redo_stack.push(op);
+        if let Some(idx) = self.doc.undo() {
+            self.sync_document();
+            self.cursor = self.doc.char_to_pos(idx);
+            self.anchor = None;
+            self.extra_cursors.clear();
             self.ensure_cursor_visible();
         }
     }
 
     /// Redo
     pub fn redo(&mut self) {
-        if let Some(op) = self.redo_stack.pop() {
-            match &op {
-                EditOp::Insert { line, col, text } => {
-                    if let Some(l) = self.lines.get_mut(*line) {
-                        l.insert_str(*col, text);
-                    }
-                    self.cursor = (*line, *col + text.len());
-                }
-                EditOp::Delete { line, col, text } => {
-                    if let Some(l) = self.lines.get_mut(*line) {
-                        let end = (*col + text.len()).min(l.len());
-                        l.drain(*col..end);
-                    }
-                    self.cursor = (*line, *col);
-                }
-                EditOp::SplitLine { line, col } => {
-                    if let Some(l) = self.lines.get_mut(*line) {
-                        let rest: String = l.drain(*col..).collect();
-                        self.lines.insert(*line + 1, rest);
-                    }
-                    self.cursor = (*line + 1, 0);
-                }
-                EditOp::MergeLine { line, col } => {
-                    if *line + 1 < self.lines.len() {
-                        let next = self.lines.remove(*line + 1);
-                        if let Some(l) = self.lines.get_mut(*line) {
-                            l.push_str(&next);
-                        }
-                    }
-                    self.cursor = (*line, *col);
-                }
-            }
-            self.undo_stack.push(op);
+        if let Some(idx) = self.doc.redo() {
+            self.sync_document();
+            self.cursor = self.doc.char_to_pos(idx);
+            self.anchor = None;
+            self.extra_cursors.clear();
             self.ensure_cursor_visible();
         }
     }
@@ -0,0 +1,324 @@
+//! Code editor folding, indentation guides and sticky scroll
+//!
+//! Fold ranges come from the parse tree when the language has a tree-sitter
+//! grammar, and from indentation otherwise. Folded regions are remembered by
+//! their header line, which is kept in step with edits through the
+//! document's change events.
+
+use super::types::{FoldRange, IndentStyle};
+use crate::utils::DocumentChange;
+
+/// Characters that close a block, left visible below a folded region
+#[cfg(feature = "syntax-highlighting")]
+const CLOSING: [char; 3] = ['}', ')', ']'];
+
+impl super::CodeEditor {
+    // =========================================================================
+    // Fold Ranges
+    // =========================================================================
+
+    /// Foldable regions, sorted by header line
+    ///
+    /// Computed from the parse tree with the `syntax-highlighting` feature
+    /// and a supported language, from indentation otherwise.
+    pub fn fold_ranges(&self) -> Vec<FoldRange> {
+        if let Some((version, ranges)) = &*self.fold_cache.borrow() {
+            if *version == self.doc.version() {
+                return ranges.clone();
+            }
+        }
+        let ranges = self
+            .syntax_fold_ranges()
+            .unwrap_or_else(|| self.indent_fold_ranges());
+        self.fold_cache
+            .replace(Some((self.doc.version(), ranges.clone())));
+        ranges
+    }
+
+    #[cfg(feature = "syntax-highlighting")]
+    fn syntax_fold_ranges(&self) -> Option<Vec<FoldRange>> {
+        let syntax = self
+            .syntax
+            .as_ref()
+            .filter(|syntax| syntax.tree().is_some())?;
+        Some(
+            syntax
+                .multiline_spans()
+                .into_iter()
+                .filter_map(|(start, last)| {
+                    let closes = self.doc.line(last).trim_start().starts_with(CLOSING);
+                    let end = if closes { last - 1 } else { last };
+                    (end > start).then_some(FoldRange { start, end })
+                })
+                .collect(),
+        )
+    }
+
+    #[cfg(not(feature = "syntax-highlighting"))]
+    fn syntax_fold_ranges(&self) -> Option<Vec<FoldRange>> {
+        None
+    }
+
+    /// Regions of lines indented deeper than the line before them
+    fn indent_fold_ranges(&self) -> Vec<FoldRange> {
+        let mut ranges = Vec::new();
+        // Open regions: (header line, header indent)
+        let mut open: Vec<(usize, usize)> = Vec::new();
+        let mut last_content = 0;
+
+        for (idx, line) in self.doc.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let indent = self.indent_width(&line);
+            while let Some(&(start, header_indent)) = open.last() {
+                if header_indent < indent {
+                    break;
+                }
+                open.pop();
+                if last_content > start {
+                    ranges.push(FoldRange {
+                        start,
+                        end: last_content,
+                    });
+                }
+            }
+            open.push((idx, indent));
+            last_content = idx;
+        }
+        for (start, _) in open {
+            if last_content > start {
+                ranges.push(FoldRange {
+                    start,
+                    end: last_content,
+                });
+            }
+        }
+
+        ranges.sort_by_key(|range| range.start);
+        ranges
+    }
+
+    /// Width of a line's leading whitespace, with tabs as one indent level
+    fn indent_width(&self, line: &str) -> usize {
+        line.chars()
+            .take_while(|c| c.is_whitespace())
+            .map(|c| {
+                if c == '\t' {
+                    self.config.indent_size
+                } else {
+                    1
+                }
+            })
+            .sum()
+    }
+
+    /// Ranges that are currently folded
+    fn folded_ranges(&self) -> Vec<FoldRange> {
+        if self.folded.is_empty() {
+            return Vec::new();
+        }
+        self.fold_ranges()
+            .into_iter()
+            .filter(|range| self.folded.contains(&range.start))
+            .collect()
+    }
+
+    // =========================================================================
+    // Fold Commands
+    // =========================================================================
+
+    /// Fold the region whose header is `line`
+    ///
+    /// Returns `false` if folding is disabled or no region starts there.
+    pub fn fold(&mut self, line: usize) -> bool {
+        if !self.config.folding || self.folded.contains(&line) {
+            return false;
+        }
+        let Some(range) = self.fold_ranges().into_iter().find(|r| r.start == line) else {
+            return false;
+        };
+        self.folded.push(line);
+        self.folded.sort_unstable();
+        self.update_change_tracking();
+        if range.hides(self.cursor.0) {
+            self.cursor = (range.start, self.cursor.1.min(self.line_len(range.start)));
+            self.anchor = None;
+        }
+        self.ensure_cursor_visible();
+        true
+    }
+
+    /// Unfold the region whose header is `line`
+    pub fn unfold(&mut self, line: usize) -> bool {
+        let before = self.folded.len();
+        self.folded.retain(|&header| header != line);
+        self.folded.len() != before
+    }
+
+    /// Fold or unfold the innermost region around the cursor
+    pub fn toggle_fold(&mut self) {
+        let line = self.cursor.0;
+        if self.unfold(line) {
+            return;
+        }
+        let innermost = self
+            .fold_ranges()
+            .into_iter()
+            .filter(|r| r.start == line || r.hides(line))
+            .filter(|r| !self.folded.contains(&r.start))
+            .min_by_key(|r| r.end - r.start);
+        if let Some(range) = innermost {
+            self.fold(range.start);
+        }
+    }
+
+    /// Fold every region
+    pub fn fold_all(&mut self) {
+        if !self.config.folding {
+            return;
+        }
+        self.folded = self.fold_ranges().iter().map(|r| r.start).collect();
+        self.update_change_tracking();
+        if let Some(range) = self
+            .folded_ranges()
+            .into_iter()
+            .find(|r| r.hides(self.cursor.0))
+        {
+            self.cursor = (range.start, 0);
+            self.anchor = None;
+        }
+        self.ensure_cursor_visible();
+    }
+
+    /// Unfold every region
+    pub fn unfold_all(&mut self) {
+        self.folded.clear();
+    }
+
+    /// Check if the region whose header is `line` is folded
+    pub fn is_folded(&self, line: usize) -> bool {
+        self.folded.contains(&line)
+    }
+
+    /// Check if a line is hidden inside a folded region
+    pub fn is_line_hidden(&self, line: usize) -> bool {
+        self.folded_ranges().iter().any(|r| r.hides(line))
+    }
+
+    /// Unfold the regions hiding a line
+    pub(super) fn reveal_line(&mut self, line: usize) {
+        let hiding: Vec<usize> = self
+            .folded_ranges()
+            .into_iter()
+            .filter(|r| r.hides(line))
+            .map(|r| r.start)
+            .collect();
+        self.folded.retain(|header| !hiding.contains(header));
+    }
+
+    /// Move folded headers along with edits, dropping those edited away
+    pub(super) fn shift_folds(&mut self, changes: &[DocumentChange]) {
+        if self.folded.is_empty() || changes.is_empty() {
+            return;
+        }
+        for change in changes {
+            let start = change.start_position.0;
+            let old_end = change.old_end_position.0;
+            let new_end = change.new_end_position.0;
+            self.folded
+                .retain(|&header| header <= start || header > old_end);
+            for header in &mut self.folded {
+                if *header > old_end {
+                    *header = *header - old_end + new_end;
+                }
+            }
+        }
+        let starts: Vec<usize> = self.fold_ranges().iter().map(|r| r.start).collect();
+        self.folded.retain(|header| starts.contains(header));
+    }
+
+    // =========================================================================
+    // Visible Lines
+    // =========================================================================
+
+    /// Up to `count` visible lines from `start` on
+    pub(super) fn visible_lines_from(&self, start: usize, count: usize) -> Vec<usize> {
+        let folded = self.folded_ranges();
+        let mut lines = Vec::with_capacity(count);
+        let mut line = start;
+        while lines.len() < count && line < self.doc.len_lines() {
+            match folded.iter().filter(|r| r.hides(line)).map(|r| r.end).max() {
+                Some(end) => line = end + 1,
+                None => {
+                    lines.push(line);
+                    line += 1;
+                }
+            }
+        }
+        lines
+    }
+
+    /// First visible line after `line`
+    pub(super) fn next_visible_line(&self, line: usize) -> Option<usize> {
+        self.visible_lines_from(line + 1, 1).first().copied()
+    }
+
+    /// Last visible line before `line`
+    pub(super) fn prev_visible_line(&self, line: usize) -> Option<usize> {
+        let folded = self.folded_ranges();
+        let mut line = line.checked_sub(1)?;
+        while let Some(start) = folded
+            .iter()
+            .filter(|r| r.hides(line))
+            .map(|r| r.start)
+            .min()
+        {
+            line = start;
+        }
+        Some(line)
+    }
+
+    /// Header of the innermost region enclosing the first line in view
+    ///
+    /// `None` unless sticky scroll is enabled and the header is scrolled out.
+    pub fn sticky_header(&self) -> Option<usize> {
+        if !self.config.sticky_scroll {
+            return None;
+        }
+        let top = self.scroll.0;
+        self.fold_ranges()
+            .into_iter()
+            .filter(|r| r.start < top && top <= r.end)
+            .max_by_key(|r| r.start)
+            .map(|r| r.start)
+    }
+
+    // =========================================================================
+    // Indentation Guides
+    // =========================================================================
+
+    /// Char columns of the indentation guides on a line
+    ///
+    /// Blank lines take the indentation of the code around them, so guides
+    /// run unbroken through a block.
+    pub(super) fn guide_columns(&self, line: usize) -> Vec<usize> {
+        let unit = match self.config.indent_style {
+            IndentStyle::Spaces => self.config.indent_size.max(1),
+            IndentStyle::Tabs => 1,
+        };
+        let leading = |text: &str| text.chars().take_while(|c| c.is_whitespace()).count();
+        let content_indent = |l: usize| {
+            let text = self.doc.line(l);
+            (!text.trim().is_empty()).then(|| leading(&text))
+        };
+        let depth = content_indent(line).unwrap_or_else(|| {
+            let above = (0..line).rev().find_map(content_indent).unwrap_or(0);
+            let below = (line + 1..self.doc.len_lines())
+                .find_map(content_indent)
+                .unwrap_or(0);
+            above.min(below)
+        });
+        (0..depth).step_by(unit).collect()
+    }
+}
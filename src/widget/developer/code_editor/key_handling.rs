@@ -2,7 +2,7 @@
 //!
 //! Public API tests extracted to tests/widget/code_editor/key_handling.rs
 
-use crate::event::Key;
+use crate::event::{Key, KeyEvent};
 
 impl super::CodeEditor {
     // =========================================================================
@@ -20,7 +20,12 @@ impl super::CodeEditor {
             return self.handle_find_input(key);
         }
 
-        match key {
+        #[cfg(feature = "lsp")]
+        if self.handle_lsp_key(key) {
+            return true;
+        }
+
+        let handled = match key {
             Key::Char(ch) => {
                 self.insert_char(*ch);
                 true
@@ -42,27 +47,27 @@ impl super::CodeEditor {
                 true
             }
             Key::Left => {
-                self.move_left();
+                self.move_cursors(Self::move_left);
                 true
             }
             Key::Right => {
-                self.move_right();
+                self.move_cursors(Self::move_right);
                 true
             }
             Key::Up => {
-                self.move_up();
+                self.move_cursors(Self::move_up);
                 true
             }
             Key::Down => {
-                self.move_down();
+                self.move_cursors(Self::move_down);
                 true
             }
             Key::Home => {
-                self.move_home();
+                self.move_cursors(Self::move_home);
                 true
             }
             Key::End => {
-                self.move_end();
+                self.move_cursors(Self::move_end);
                 true
             }
             Key::PageUp => {
@@ -73,7 +78,49 @@ impl super::CodeEditor {
                 self.page_down(20);
                 true
             }
+            Key::Escape if self.has_multiple_cursors() => {
+                self.clear_secondary_cursors();
+                true
+            }
             _ => false,
+        };
+
+        #[cfg(feature = "lsp")]
+        if handled {
+            self.after_lsp_key(key);
+        }
+
+        handled
+    }
+
+    /// Handle a key event, including modifier combinations
+    ///
+    /// Bindings on top of [`handle_key`](Self::handle_key):
+    /// - `Ctrl+D` — select the next occurrence of the word/selection
+    /// - `Ctrl+Alt+Up` / `Ctrl+Alt+Down` — add a cursor above / below
+    ///
+    /// With [`vim`](Self::vim) enabled, keys go through Vim mode instead.
+    pub fn handle_key_event(&mut self, event: &KeyEvent) -> bool {
+        if !self.goto_line_mode && !self.find_mode {
+            if let Some(handled) = self.handle_vim_key(event) {
+                return handled;
+            }
+            match (event.ctrl, event.alt, &event.key) {
+                (true, true, Key::Up) => {
+                    self.add_cursor_above();
+                    return true;
+                }
+                (true, true, Key::Down) => {
+                    self.add_cursor_below();
+                    return true;
+                }
+                (true, false, Key::Char('d')) => {
+                    self.select_next_occurrence();
+                    return true;
+                }
+                _ => {}
+            }
         }
+        self.handle_key(&event.key)
     }
 }
@@ -0,0 +1,570 @@
+//! Code editor language server integration
+//!
+//! The server is told about every document change from
+//! [`sync_document`](super::CodeEditor::sync_document), using the
+//! document's change events.
+
+use std::ops::Range;
+
+use super::multi_cursor::is_word_char;
+use crate::event::Key;
+use crate::style::Color;
+use crate::utils::Document;
+use crate::widget::developer::lsp::{
+    CompletionItem, Diagnostic, DiagnosticSeverity, LspClient, LspEvent, Position, SignatureHelp,
+    TextEdit,
+};
+use crate::widget::feedback::{Popover, PopoverPosition, Tooltip, TooltipPosition, TooltipStyle};
+use crate::widget::syntax::Language;
+use crate::widget::traits::{RenderContext, View};
+
+/// Most completion items shown at once
+const COMPLETION_ROWS: usize = 10;
+
+/// A language server attached to an editor
+pub(crate) struct LspSession {
+    pub(super) client: LspClient,
+    pub(super) uri: String,
+    pub(super) diagnostics: Vec<Diagnostic>,
+    pub(super) completion: Option<CompletionMenu>,
+    pub(super) hover: Option<String>,
+    pub(super) signature: Option<SignatureHelp>,
+}
+
+/// Open completion popup
+pub(crate) struct CompletionMenu {
+    items: Vec<CompletionItem>,
+    /// Indices of the items matching what was typed
+    visible: Vec<usize>,
+    selected: usize,
+    /// Start of the word being completed
+    start: (usize, usize),
+}
+
+impl CompletionMenu {
+    fn filter(&mut self, typed: &str) {
+        let typed = typed.to_lowercase();
+        self.visible = (0..self.items.len())
+            .filter(|&i| self.items[i].filter().to_lowercase().starts_with(&typed))
+            .collect();
+        self.selected = self.selected.min(self.visible.len().saturating_sub(1));
+    }
+
+    fn selected_item(&self) -> Option<&CompletionItem> {
+        self.visible.get(self.selected).map(|&i| &self.items[i])
+    }
+}
+
+/// `languageId` of a language
+fn language_id(lang: Language) -> &'static str {
+    match lang {
+        Language::None => "plaintext",
+        Language::Rust => "rust",
+        Language::Python => "python",
+        Language::JavaScript => "javascript",
+        Language::Json => "json",
+        Language::Toml => "toml",
+        Language::Yaml => "yaml",
+        Language::Markdown => "markdown",
+        Language::Shell => "shellscript",
+        Language::Sql => "sql",
+        Language::Html => "html",
+        Language::Css => "css",
+        Language::Go => "go",
+    }
+}
+
+/// Word typed between `start` and the cursor, if the cursor is still in it
+fn typed_since(doc: &Document, cursor: (usize, usize), start: (usize, usize)) -> Option<String> {
+    if cursor.0 != start.0 || cursor.1 < start.1 {
+        return None;
+    }
+    let typed: String = doc
+        .line(cursor.0)
+        .chars()
+        .skip(start.1)
+        .take(cursor.1 - start.1)
+        .collect();
+    typed.chars().all(is_word_char).then_some(typed)
+}
+
+impl super::CodeEditor {
+    // =========================================================================
+    // Setup
+    // =========================================================================
+
+    /// Attach a language server for the document at `uri`
+    ///
+    /// The document is opened on the server and kept in sync. Call
+    /// [`poll_lsp`](Self::poll_lsp) regularly to receive results.
+    pub fn lsp(mut self, client: LspClient, uri: impl Into<String>) -> Self {
+        self.set_lsp(client, uri);
+        self
+    }
+
+    /// Attach a language server (mutable)
+    pub fn set_lsp(&mut self, mut client: LspClient, uri: impl Into<String>) {
+        let uri = uri.into();
+        client.did_open(&uri, language_id(self.language), &self.doc);
+        self.lsp = Some(LspSession {
+            client,
+            uri,
+            diagnostics: Vec::new(),
+            completion: None,
+            hover: None,
+            signature: None,
+        });
+        // Changes made before now are already in the opened text
+        self.doc.take_changes();
+        self.update_change_tracking();
+        self.sync_document();
+    }
+
+    /// Detach the language server, closing the document on it
+    pub fn detach_lsp(&mut self) -> Option<LspClient> {
+        let mut session = self.lsp.take()?;
+        session.client.did_close(&session.uri);
+        self.update_change_tracking();
+        Some(session.client)
+    }
+
+    /// The attached language server
+    pub fn lsp_client(&mut self) -> Option<&mut LspClient> {
+        self.lsp.as_mut().map(|lsp| &mut lsp.client)
+    }
+
+    // =========================================================================
+    // Results
+    // =========================================================================
+
+    /// Handle what the language server sent since the last call
+    ///
+    /// Results for this document are applied: diagnostics, popups, edits,
+    /// and definitions within it move the cursor. All events are returned
+    /// so the app can handle the rest, such as definitions in other files.
+    pub fn poll_lsp(&mut self) -> Vec<LspEvent> {
+        let Some(lsp) = &mut self.lsp else {
+            return Vec::new();
+        };
+        let events = lsp.client.poll();
+        for event in &events {
+            self.handle_lsp_event(event);
+        }
+        events
+    }
+
+    fn handle_lsp_event(&mut self, event: &LspEvent) {
+        let cursor = self.cursor;
+        let word_start = self.word_start();
+        let Some(lsp) = &mut self.lsp else {
+            return;
+        };
+        match event {
+            LspEvent::Diagnostics { uri, diagnostics } if *uri == lsp.uri => {
+                lsp.diagnostics = diagnostics.clone();
+            }
+            LspEvent::Completion(items) => {
+                let mut menu = CompletionMenu {
+                    items: items.clone(),
+                    visible: Vec::new(),
+                    selected: 0,
+                    start: word_start,
+                };
+                menu.filter(&typed_since(&self.doc, cursor, word_start).unwrap_or_default());
+                lsp.completion = (!menu.visible.is_empty()).then_some(menu);
+            }
+            LspEvent::Hover(hover) => {
+                let here = Position::from_char_pos(&self.doc, cursor);
+                let mut parts: Vec<String> = lsp
+                    .diagnostics
+                    .iter()
+                    .filter(|d| d.range.contains(here))
+                    .map(|d| d.message.clone())
+                    .collect();
+                parts.extend(hover.as_ref().map(|h| h.contents.clone()));
+                lsp.hover = (!parts.is_empty()).then(|| parts.join("\n\n"));
+            }
+            LspEvent::SignatureHelp(help) => lsp.signature = help.clone(),
+            LspEvent::Definition(locations) => {
+                if let Some(location) = locations.iter().find(|l| l.uri == lsp.uri) {
+                    let (line, col) = location.range.start.to_char_pos(&self.doc);
+                    self.anchor = None;
+                    self.set_cursor(line, col);
+                }
+            }
+            LspEvent::Formatting { uri, edits } if *uri == lsp.uri => {
+                self.apply_text_edits(edits);
+            }
+            LspEvent::Rename(edit) => {
+                let edits = edit.edits_for(&lsp.uri).to_vec();
+                self.apply_text_edits(&edits);
+            }
+            _ => {}
+        }
+    }
+
+    /// Diagnostics for this document
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        self.lsp.as_ref().map_or(&[], |lsp| &lsp.diagnostics)
+    }
+
+    /// Completion items shown in the popup, best first
+    pub fn completion_items(&self) -> Vec<&CompletionItem> {
+        self.lsp
+            .as_ref()
+            .and_then(|lsp| lsp.completion.as_ref())
+            .map(|menu| menu.visible.iter().map(|&i| &menu.items[i]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Text of the hover tooltip, if shown
+    pub fn hover_text(&self) -> Option<&str> {
+        self.lsp.as_ref()?.hover.as_deref()
+    }
+
+    /// Signature help, if shown
+    pub fn signature_help(&self) -> Option<&SignatureHelp> {
+        self.lsp.as_ref()?.signature.as_ref()
+    }
+
+    // =========================================================================
+    // Requests
+    // =========================================================================
+
+    /// Cursor position in protocol terms, with the URI
+    fn lsp_request_target(&mut self) -> Option<(&mut LspClient, &str, Position)> {
+        let position = Position::from_char_pos(&self.doc, self.cursor);
+        let lsp = self.lsp.as_mut()?;
+        Some((&mut lsp.client, lsp.uri.as_str(), position))
+    }
+
+    /// Request completions at the cursor
+    pub fn trigger_completion(&mut self) {
+        if let Some((client, uri, position)) = self.lsp_request_target() {
+            client.completion(uri, position);
+        }
+    }
+
+    /// Request hover information at the cursor
+    pub fn show_hover(&mut self) {
+        if let Some((client, uri, position)) = self.lsp_request_target() {
+            client.hover(uri, position);
+        }
+    }
+
+    /// Request signature help at the cursor
+    pub fn trigger_signature_help(&mut self) {
+        if let Some((client, uri, position)) = self.lsp_request_target() {
+            client.signature_help(uri, position);
+        }
+    }
+
+    /// Jump to the definition of the symbol at the cursor
+    pub fn goto_definition(&mut self) {
+        if let Some((client, uri, position)) = self.lsp_request_target() {
+            client.definition(uri, position);
+        }
+    }
+
+    /// Format the document with the language server
+    pub fn format_document(&mut self) {
+        if self.read_only {
+            return;
+        }
+        let insert_spaces = self.config.indent_style == super::IndentStyle::Spaces;
+        let tab_size = self.config.indent_size;
+        if let Some(lsp) = &mut self.lsp {
+            lsp.client.formatting(&lsp.uri, tab_size, insert_spaces);
+        }
+    }
+
+    /// Rename the symbol at the cursor throughout the workspace
+    ///
+    /// Edits to this document are applied when the result arrives; edits to
+    /// other documents are left to the app through [`LspEvent::Rename`].
+    pub fn rename_symbol(&mut self, new_name: &str) {
+        if self.read_only {
+            return;
+        }
+        if let Some((client, uri, position)) = self.lsp_request_target() {
+            client.rename(uri, position, new_name);
+        }
+    }
+
+    /// Apply edits from the language server as a single undo step
+    pub fn apply_text_edits(&mut self, edits: &[TextEdit]) {
+        if self.read_only || edits.is_empty() {
+            return;
+        }
+        let mut ranges: Vec<(Range<usize>, &str)> = edits
+            .iter()
+            .map(|edit| {
+                let (line, col) = edit.range.start.to_char_pos(&self.doc);
+                let start = self.doc.pos_to_char(line, col);
+                let (line, col) = edit.range.end.to_char_pos(&self.doc);
+                let end = self.doc.pos_to_char(line, col).max(start);
+                (start..end, edit.new_text.as_str())
+            })
+            .collect();
+        // Later edits first, so earlier offsets stay valid
+        ranges.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+
+        let cursor = self.cursor;
+        self.doc.transaction(|doc| {
+            for (range, text) in ranges {
+                doc.replace(range, text);
+            }
+        });
+        self.sync_document();
+        self.anchor = None;
+        self.set_cursor(cursor.0, cursor.1);
+    }
+
+    // =========================================================================
+    // Keys
+    // =========================================================================
+
+    /// Handle keys for open popups and LSP shortcuts
+    ///
+    /// Returns `true` if the key was consumed.
+    pub(super) fn handle_lsp_key(&mut self, key: &Key) -> bool {
+        let Some(lsp) = &mut self.lsp else {
+            return false;
+        };
+        lsp.hover = None;
+
+        if let Some(menu) = &mut lsp.completion {
+            match key {
+                Key::Up => {
+                    menu.selected = menu.selected.saturating_sub(1);
+                    return true;
+                }
+                Key::Down => {
+                    menu.selected = (menu.selected + 1).min(menu.visible.len() - 1);
+                    return true;
+                }
+                Key::Enter | Key::Tab => {
+                    self.accept_completion();
+                    return true;
+                }
+                Key::Escape => {
+                    lsp.completion = None;
+                    return true;
+                }
+                _ => {}
+            }
+        }
+
+        match key {
+            Key::Escape if lsp.signature.is_some() => {
+                lsp.signature = None;
+                true
+            }
+            Key::F(12) => {
+                self.goto_definition();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Update popups after a key was handled by the editor
+    pub(super) fn after_lsp_key(&mut self, key: &Key) {
+        let cursor = self.cursor;
+        let Some(lsp) = &mut self.lsp else {
+            return;
+        };
+        let caps = lsp.client.capabilities();
+        let (complete, signature) = match key {
+            Key::Char(ch) => (
+                caps.completion_triggers.contains(ch),
+                caps.signature_triggers.contains(ch),
+            ),
+            _ => (false, false),
+        };
+        // Signature help stays open while typing the arguments
+        if matches!(key, Key::Char(')')) || !matches!(key, Key::Char(_) | Key::Backspace) {
+            lsp.signature = None;
+        }
+
+        if let Some(menu) = &mut lsp.completion {
+            let typed = typed_since(&self.doc, cursor, menu.start);
+            match (key, typed) {
+                (Key::Char(_) | Key::Backspace | Key::Delete, Some(typed)) => {
+                    menu.filter(&typed);
+                    if menu.visible.is_empty() {
+                        lsp.completion = None;
+                    }
+                }
+                _ => lsp.completion = None,
+            }
+        }
+
+        if complete {
+            self.trigger_completion();
+        }
+        if signature {
+            self.trigger_signature_help();
+        }
+    }
+
+    /// Replace the word being completed with the selected item
+    fn accept_completion(&mut self) {
+        let Some(menu) = self.lsp.as_mut().and_then(|lsp| lsp.completion.take()) else {
+            return;
+        };
+        let Some(item) = menu.selected_item() else {
+            return;
+        };
+        let start = match &item.text_edit {
+            Some(edit) => edit.range.start.to_char_pos(&self.doc),
+            None => menu.start,
+        };
+        if start.0 == self.cursor.0 && start.1 <= self.cursor.1 {
+            self.anchor = Some(start);
+        }
+        self.insert_str(item.text());
+        self.anchor = None;
+    }
+
+    /// Start of the identifier ending at the cursor
+    fn word_start(&self) -> (usize, usize) {
+        let line: Vec<char> = self.doc.line(self.cursor.0).chars().collect();
+        let end = self.cursor.1.min(line.len());
+        let start = line[..end]
+            .iter()
+            .rposition(|&ch| !is_word_char(ch))
+            .map_or(0, |i| i + 1);
+        (self.cursor.0, start)
+    }
+
+    // =========================================================================
+    // Rendering
+    // =========================================================================
+
+    fn severity_color(&self, severity: DiagnosticSeverity) -> Color {
+        match severity {
+            DiagnosticSeverity::Error => self.diagnostic_error_fg,
+            DiagnosticSeverity::Warning => self.diagnostic_warning_fg,
+            DiagnosticSeverity::Information => self.diagnostic_info_fg,
+            DiagnosticSeverity::Hint => self.diagnostic_hint_fg,
+        }
+    }
+
+    /// Width of the diagnostic sign column left of the line numbers
+    pub(super) fn sign_column_width(&self) -> u16 {
+        if self.lsp.is_some() {
+            2
+        } else {
+            0
+        }
+    }
+
+    /// Color of the gutter marker for a line: its most severe diagnostic
+    pub(super) fn line_sign(&self, line: usize) -> Option<Color> {
+        self.diagnostics()
+            .iter()
+            .filter(|d| (d.range.start.line as usize..=d.range.end.line as usize).contains(&line))
+            .map(|d| d.severity)
+            .min()
+            .map(|severity| self.severity_color(severity))
+    }
+
+    /// Char ranges of a line covered by diagnostics, with their colors
+    ///
+    /// Listed most severe first, so the first match wins.
+    pub(super) fn diagnostic_spans(&self, line: usize) -> Vec<(Range<usize>, Color)> {
+        let mut diagnostics: Vec<&Diagnostic> = self
+            .diagnostics()
+            .iter()
+            .filter(|d| (d.range.start.line as usize..=d.range.end.line as usize).contains(&line))
+            .collect();
+        diagnostics.sort_by_key(|d| d.severity);
+        diagnostics
+            .into_iter()
+            .map(|d| {
+                let start = if d.range.start.line as usize == line {
+                    d.range.start.to_char_pos(&self.doc).1
+                } else {
+                    0
+                };
+                let end = if d.range.end.line as usize == line {
+                    d.range.end.to_char_pos(&self.doc).1
+                } else {
+                    usize::MAX
+                };
+                // Empty ranges still mark the character they point at
+                (start..end.max(start + 1), self.severity_color(d.severity))
+            })
+            .collect()
+    }
+
+    /// Draw the completion popup, hover and signature help at the cursor
+    pub(super) fn render_lsp_popups(&self, ctx: &mut RenderContext, anchor: (u16, u16)) {
+        let Some(lsp) = &self.lsp else {
+            return;
+        };
+        let (x, y) = anchor;
+
+        if let Some(menu) = &lsp.completion {
+            let first = menu
+                .selected
+                .saturating_sub(COMPLETION_ROWS - 1)
+                .min(menu.visible.len().saturating_sub(COMPLETION_ROWS));
+            let rows: Vec<String> = menu
+                .visible
+                .iter()
+                .enumerate()
+                .skip(first)
+                .take(COMPLETION_ROWS)
+                .map(|(row, &i)| {
+                    let item = &menu.items[i];
+                    let marker = if row == menu.selected { '›' } else { ' ' };
+                    match &item.detail {
+                        Some(detail) => format!("{} {}  {}", marker, item.label, detail),
+                        None => format!("{} {}", marker, item.label),
+                    }
+                })
+                .collect();
+            Popover::new(rows.join("\n"))
+                .anchor(x, y)
+                .position(PopoverPosition::Auto)
+                .max_width(60)
+                .open(true)
+                .render(ctx);
+        } else if let Some(help) = &lsp.signature {
+            if let Some(signature) = help.active() {
+                let mut text = signature.label.clone();
+                let parameter = help
+                    .active_parameter
+                    .and_then(|i| signature.parameters.get(i));
+                if let Some(range) = parameter {
+                    let name: String = signature
+                        .label
+                        .chars()
+                        .skip(range.start)
+                        .take(range.len())
+                        .collect();
+                    text.push_str(&format!("\n▸ {}", name));
+                }
+                if let Some(doc) = signature.documentation.as_deref().filter(|d| !d.is_empty()) {
+                    text.push_str(&format!("\n{}", doc));
+                }
+                Tooltip::new(text)
+                    .anchor(x, y)
+                    .position(TooltipPosition::Auto)
+                    .max_width(60)
+                    .render(ctx);
+            }
+        }
+
+        if let Some(text) = &lsp.hover {
+            Tooltip::new(text.as_str())
+                .anchor(x, y)
+                .position(TooltipPosition::Auto)
+                .style(TooltipStyle::Rounded)
+                .max_width(60)
+                .render(ctx);
+        }
+    }
+}
@@ -26,9 +26,7 @@ impl super::CodeEditor {
 
     /// Go to specific line
     pub fn goto_line(&mut self, line: usize) {
-        let target = line
-            .saturating_sub(1)
-            .min(self.lines.len().saturating_sub(1));
+        let target = line.saturating_sub(1).min(self.doc.len_lines() - 1);
         self.cursor = (target, 0);
         self.clear_selection();
         self.ensure_cursor_visible();
@@ -98,14 +96,17 @@ impl super::CodeEditor {
         }
 
         let query_lower = self.find_query.to_lowercase();
-        for (line_idx, line) in self.lines.iter().enumerate() {
+        let query_len = query_lower.chars().count();
+        for (line_idx, line) in self.doc.lines().enumerate() {
             let line_lower = line.to_lowercase();
-            let mut start = 0;
-            while let Some(pos) = line_lower[start..].find(&query_lower) {
-                let match_start = start + pos;
-                let match_end = match_start + self.find_query.len();
-                self.find_matches.push((line_idx, match_start, match_end));
-                start = match_start + 1;
+            // Columns are in chars; matches may overlap
+            let chars: Vec<char> = line_lower.chars().collect();
+            let query: Vec<char> = query_lower.chars().collect();
+            for match_start in 0..(chars.len() + 1).saturating_sub(query_len) {
+                if chars[match_start..].starts_with(&query) {
+                    self.find_matches
+                        .push((line_idx, match_start, match_start + query_len));
+                }
             }
         }
 
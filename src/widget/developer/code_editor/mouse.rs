@@ -0,0 +1,79 @@
+//! Code editor mouse handling
+//!
+//! Click places the cursor and drag selects. With Alt held, click adds a
+//! cursor and drag selects a column block.
+
+use crate::event::{MouseButton, MouseEvent, MouseEventKind};
+use crate::layout::Rect;
+
+impl super::CodeEditor {
+    /// Handle a mouse event over the editor drawn in `area`
+    ///
+    /// Returns whether the event was handled.
+    pub fn handle_mouse(&mut self, event: &MouseEvent, area: Rect) -> bool {
+        let Some(pos) = self.position_at(event.x, event.y, area) else {
+            return false;
+        };
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if event.alt {
+                    self.add_cursor_at(pos.0, pos.1);
+                } else {
+                    self.clear_secondary_cursors();
+                    self.anchor = None;
+                    self.cursor = pos;
+                }
+                self.mouse_anchor = Some(pos);
+                self.ensure_cursor_visible();
+                true
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                let Some(start) = self.mouse_anchor else {
+                    return false;
+                };
+                if event.alt {
+                    self.select_block(start, pos);
+                } else {
+                    self.clear_secondary_cursors();
+                    self.anchor = Some(start).filter(|&start| start != pos);
+                    self.cursor = pos;
+                    self.ensure_cursor_visible();
+                }
+                true
+            }
+            MouseEventKind::Up(MouseButton::Left) => self.mouse_anchor.take().is_some(),
+            _ => false,
+        }
+    }
+
+    /// Document position under a screen cell, clamped to the line
+    fn position_at(&self, x: u16, y: u16, area: Rect) -> Option<(usize, usize)> {
+        if !area.contains(x, y) {
+            return None;
+        }
+        let (x, row) = (x - area.x, (y - area.y) as usize);
+
+        let sticky = self.sticky_header();
+        let line = match (sticky, row) {
+            (Some(header), 0) => header,
+            _ => {
+                let sticky_rows = usize::from(sticky.is_some());
+                let rows = self.visible_lines_from(self.scroll.0, area.height as usize);
+                *rows.get(row - sticky_rows).or(rows.last())?
+            }
+        };
+
+        // Columns by display width, past the gutter
+        let mut remaining = x.saturating_sub(self.gutter_width()) as usize;
+        let mut col = self.scroll.1;
+        for ch in self.doc.line(line).chars().skip(self.scroll.1) {
+            let width = crate::utils::char_width(ch);
+            if remaining < width {
+                break;
+            }
+            remaining -= width;
+            col += 1;
+        }
+        Some((line, col.min(self.line_len(line))))
+    }
+}
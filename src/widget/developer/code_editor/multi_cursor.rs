@@ -0,0 +1,307 @@
+//! Code editor multiple cursors and column selection
+//!
+//! The primary cursor is `cursor` with its `anchor`; additional cursors are
+//! kept sorted in `extra_cursors`. Edits run once per cursor, last in the
+//! document first, inside one transaction so they undo together.
+
+use crate::widget::input_widgets::textarea::{Cursor, CursorPos};
+
+/// Maximum number of cursors, as in `TextArea`
+const MAX_CURSORS: usize = 100;
+
+/// A cursor position with its selection anchor
+type Caret = ((usize, usize), Option<(usize, usize)>);
+
+/// Check if a character is part of an identifier
+pub(super) fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+impl super::CodeEditor {
+    // =========================================================================
+    // Cursors
+    // =========================================================================
+
+    /// Positions of all cursors, primary first
+    pub fn cursors(&self) -> Vec<(usize, usize)> {
+        std::iter::once(self.cursor)
+            .chain(self.extra_cursors.iter().map(|c| c.pos.into()))
+            .collect()
+    }
+
+    /// Check if there is more than one cursor
+    pub fn has_multiple_cursors(&self) -> bool {
+        !self.extra_cursors.is_empty()
+    }
+
+    /// Add a cursor at position (Alt+Click)
+    pub fn add_cursor_at(&mut self, line: usize, col: usize) {
+        let line = line.min(self.doc.len_lines() - 1);
+        let col = col.min(self.line_len(line));
+        self.extra_cursors
+            .push(Cursor::new(CursorPos::new(line, col)));
+        self.normalize_cursors();
+    }
+
+    /// Add a cursor on the line above the topmost one (Ctrl+Alt+Up)
+    pub fn add_cursor_above(&mut self) {
+        let top = self.cursors().into_iter().map(|(line, _)| line).min();
+        if let Some(line) = top.and_then(|line| self.prev_visible_line(line)) {
+            self.add_cursor_at(line, self.cursor.1);
+        }
+    }
+
+    /// Add a cursor on the line below the bottommost one (Ctrl+Alt+Down)
+    pub fn add_cursor_below(&mut self) {
+        let bottom = self.cursors().into_iter().map(|(line, _)| line).max();
+        if let Some(line) = bottom.and_then(|line| self.next_visible_line(line)) {
+            self.add_cursor_at(line, self.cursor.1);
+        }
+    }
+
+    /// Clear all secondary cursors (Escape)
+    pub fn clear_secondary_cursors(&mut self) {
+        self.extra_cursors.clear();
+    }
+
+    /// Select the next occurrence of the selection (Ctrl+D)
+    ///
+    /// Without a selection, the word under the cursor is selected first.
+    /// The search continues after the last cursor and wraps around.
+    pub fn select_next_occurrence(&mut self) {
+        let Some(needle) = self.get_selection().filter(|text| !text.is_empty()) else {
+            self.select_word_at_cursor();
+            return;
+        };
+
+        let text = self.doc.text();
+        let taken: Vec<usize> = self
+            .all_cursors()
+            .iter()
+            .filter_map(|&(pos, anchor)| anchor.map(|anchor| pos.min(anchor)))
+            .map(|(line, col)| self.doc.pos_to_char(line, col))
+            .collect();
+        let from = self
+            .cursors()
+            .into_iter()
+            .map(|(line, col)| self.doc.pos_to_char(line, col))
+            .max()
+            .unwrap_or(0);
+        let matches: Vec<usize> = text
+            .match_indices(&needle)
+            .map(|(byte, _)| self.doc.byte_to_char(byte))
+            .filter(|start| !taken.contains(start))
+            .collect();
+        let Some(&start) = matches
+            .iter()
+            .find(|&&start| start >= from)
+            .or(matches.first())
+        else {
+            return;
+        };
+
+        let end = start + needle.chars().count();
+        self.extra_cursors.push(Cursor::with_selection(
+            self.doc.char_to_pos(end).into(),
+            self.doc.char_to_pos(start).into(),
+        ));
+        self.normalize_cursors();
+    }
+
+    /// Select the identifier under the primary cursor
+    fn select_word_at_cursor(&mut self) {
+        let (line, col) = self.cursor;
+        let chars: Vec<char> = self.doc.line(line).chars().collect();
+        let start = chars[..col.min(chars.len())]
+            .iter()
+            .rposition(|&ch| !is_word_char(ch))
+            .map_or(0, |i| i + 1);
+        let end = chars[col.min(chars.len())..]
+            .iter()
+            .position(|&ch| !is_word_char(ch))
+            .map_or(chars.len(), |i| col + i);
+        if start < end {
+            self.anchor = Some((line, start));
+            self.cursor = (line, end);
+        }
+    }
+
+    /// Select a column block between two corners, one cursor per line
+    ///
+    /// Columns are clamped to each line's length. The cursor on the line of
+    /// `to` becomes the primary one.
+    pub fn select_block(&mut self, from: (usize, usize), to: (usize, usize)) {
+        let last = self.doc.len_lines() - 1;
+        let (from_line, to_line) = (from.0.min(last), to.0.min(last));
+
+        self.extra_cursors.clear();
+        for line in from_line.min(to_line)..=from_line.max(to_line) {
+            let len = self.line_len(line);
+            let pos = (line, to.1.min(len));
+            let anchor = Some((line, from.1.min(len))).filter(|&anchor| anchor != pos);
+            if line == to_line {
+                self.cursor = pos;
+                self.anchor = anchor;
+            } else {
+                self.extra_cursors.push(Cursor {
+                    pos: pos.into(),
+                    anchor: anchor.map(Into::into),
+                });
+            }
+        }
+        self.normalize_cursors();
+        self.ensure_cursor_visible();
+    }
+
+    /// Selected text of every cursor, in document order
+    pub fn selections(&self) -> Vec<String> {
+        let mut ranges: Vec<_> = self
+            .all_cursors()
+            .into_iter()
+            .filter_map(|(pos, anchor)| anchor.map(|anchor| (pos.min(anchor), pos.max(anchor))))
+            .collect();
+        ranges.sort();
+        ranges
+            .into_iter()
+            .map(|(start, end)| {
+                self.doc.slice(
+                    self.doc.pos_to_char(start.0, start.1)..self.doc.pos_to_char(end.0, end.1),
+                )
+            })
+            .collect()
+    }
+
+    /// Paste text at every cursor
+    ///
+    /// When there are as many lines of text as cursors, each cursor gets its
+    /// own line; otherwise every cursor gets the whole text.
+    pub fn paste(&mut self, text: &str) {
+        let lines: Vec<&str> = text.lines().collect();
+        if self.has_multiple_cursors() && lines.len() == self.extra_cursors.len() + 1 {
+            self.edit_at_cursors(|editor, i| editor.insert_str(lines[i]));
+        } else {
+            self.edit_at_cursors(|editor, _| editor.insert_str(text));
+        }
+    }
+
+    // =========================================================================
+    // Applying Across Cursors
+    // =========================================================================
+
+    /// Every cursor with its anchor, primary first
+    pub(super) fn all_cursors(&self) -> Vec<Caret> {
+        std::iter::once((self.cursor, self.anchor))
+            .chain(
+                self.extra_cursors
+                    .iter()
+                    .map(|c| (c.pos.into(), c.anchor.map(Into::into))),
+            )
+            .collect()
+    }
+
+    /// Run a single-cursor edit at every cursor, as one undo step
+    ///
+    /// `edit` gets the cursor's index in document order. Cursors are edited
+    /// from the end of the document backwards, and the ones after each edit
+    /// are shifted by the length it added or removed.
+    pub(super) fn edit_at_cursors(&mut self, mut edit: impl FnMut(&mut Self, usize)) {
+        if self.extra_cursors.is_empty() {
+            edit(self, 0);
+            return;
+        }
+
+        // (cursor, anchor, is primary) as char indices, in document order
+        let mut carets: Vec<(usize, Option<usize>, bool)> = self
+            .all_cursors()
+            .into_iter()
+            .enumerate()
+            .map(|(i, (pos, anchor))| {
+                (
+                    self.doc.pos_to_char(pos.0, pos.1),
+                    anchor.map(|(line, col)| self.doc.pos_to_char(line, col)),
+                    i == 0,
+                )
+            })
+            .collect();
+        carets.sort_by_key(|&(at, anchor, _)| anchor.map_or(at, |anchor| anchor.min(at)));
+        self.extra_cursors.clear();
+
+        self.doc.begin_transaction();
+        for i in (0..carets.len()).rev() {
+            let (at, anchor, _) = carets[i];
+            self.cursor = self.doc.char_to_pos(at);
+            self.anchor = anchor.map(|anchor| self.doc.char_to_pos(anchor));
+
+            let before = self.doc.len_chars();
+            edit(self, i);
+            let delta = self.doc.len_chars() as isize - before as isize;
+
+            carets[i].0 = self.cursor_char();
+            carets[i].1 = self
+                .anchor
+                .map(|(line, col)| self.doc.pos_to_char(line, col));
+            for later in &mut carets[i + 1..] {
+                later.0 = later.0.saturating_add_signed(delta);
+                later.1 = later.1.map(|anchor| anchor.saturating_add_signed(delta));
+            }
+        }
+        self.doc.commit_transaction();
+
+        for (at, anchor, primary) in carets {
+            let pos = self.doc.char_to_pos(at);
+            let anchor = anchor.map(|anchor| self.doc.char_to_pos(anchor));
+            if primary {
+                self.cursor = pos;
+                self.anchor = anchor;
+            } else {
+                self.extra_cursors.push(Cursor {
+                    pos: pos.into(),
+                    anchor: anchor.map(Into::into),
+                });
+            }
+        }
+        self.normalize_cursors();
+        self.ensure_cursor_visible();
+    }
+
+    /// Run a cursor movement at every cursor
+    pub(super) fn move_cursors(&mut self, mut motion: impl FnMut(&mut Self)) {
+        let primary = (self.cursor, self.anchor);
+        let moved: Vec<Cursor> = std::mem::take(&mut self.extra_cursors)
+            .into_iter()
+            .map(|cursor| {
+                self.cursor = cursor.pos.into();
+                self.anchor = cursor.anchor.map(Into::into);
+                motion(self);
+                Cursor {
+                    pos: self.cursor.into(),
+                    anchor: self.anchor.map(Into::into),
+                }
+            })
+            .collect();
+
+        // The primary moves last, so the view follows it
+        (self.cursor, self.anchor) = primary;
+        motion(self);
+        self.extra_cursors = moved;
+        self.normalize_cursors();
+    }
+
+    /// Sort secondary cursors and drop duplicates
+    fn normalize_cursors(&mut self) {
+        let primary = CursorPos::from(self.cursor);
+        self.extra_cursors.retain(|c| c.pos != primary);
+        self.extra_cursors.sort_by_key(|c| c.pos);
+        self.extra_cursors.dedup_by_key(|c| c.pos);
+        self.extra_cursors.truncate(MAX_CURSORS - 1);
+    }
+
+    /// Check if any cursor is at a position
+    pub(super) fn is_cursor_at(&self, line: usize, col: usize) -> bool {
+        self.cursor == (line, col)
+            || self
+                .extra_cursors
+                .iter()
+                .any(|c| c.pos == CursorPos::new(line, col))
+    }
+}
@@ -14,7 +14,7 @@ impl super::CodeEditor {
 
     /// Set cursor position
     pub fn set_cursor(&mut self, line: usize, col: usize) {
-        let line = line.min(self.lines.len().saturating_sub(1));
+        let line = line.min(self.doc.len_lines() - 1);
         let col = col.min(self.line_len(line));
         self.cursor = (line, col);
         self.ensure_cursor_visible();
@@ -22,22 +22,24 @@ impl super::CodeEditor {
 
     /// Get line count
     pub fn line_count(&self) -> usize {
-        self.lines.len()
+        self.doc.len_lines()
     }
 
-    /// Get line length
+    /// Get line length in chars
     pub(super) fn line_len(&self, line: usize) -> usize {
-        self.lines.get(line).map(|l| l.len()).unwrap_or(0)
+        self.doc.line_len(line)
     }
 
-    /// Move cursor left
+    /// Char index of the cursor in the document
+    pub(super) fn cursor_char(&self) -> usize {
+        self.doc.pos_to_char(self.cursor.0, self.cursor.1)
+    }
+
+    /// Move cursor left by one grapheme
     pub fn move_left(&mut self) {
-        if self.cursor.1 > 0 {
-            self.cursor.1 -= 1;
-        } else if self.cursor.0 > 0 {
-            self.cursor.0 -= 1;
-            self.cursor.1 = self.line_len(self.cursor.0);
-        }
+        self.cursor = self
+            .doc
+            .char_to_pos(self.doc.prev_grapheme(self.cursor_char()));
         // Only clear selection if not in selection mode
         if self.anchor.is_none() {
             self.clear_selection();
@@ -45,15 +47,11 @@ impl super::CodeEditor {
         self.ensure_cursor_visible();
     }
 
-    /// Move cursor right
+    /// Move cursor right by one grapheme
     pub fn move_right(&mut self) {
-        let line_len = self.line_len(self.cursor.0);
-        if self.cursor.1 < line_len {
-            self.cursor.1 += 1;
-        } else if self.cursor.0 + 1 < self.lines.len() {
-            self.cursor.0 += 1;
-            self.cursor.1 = 0;
-        }
+        self.cursor = self
+            .doc
+            .char_to_pos(self.doc.next_grapheme(self.cursor_char()));
         // Only clear selection if not in selection mode
         if self.anchor.is_none() {
             self.clear_selection();
@@ -63,8 +61,8 @@ impl super::CodeEditor {
 
     /// Move cursor up
     pub fn move_up(&mut self) {
-        if self.cursor.0 > 0 {
-            self.cursor.0 -= 1;
+        if let Some(line) = self.prev_visible_line(self.cursor.0) {
+            self.cursor.0 = line;
             self.cursor.1 = self.cursor.1.min(self.line_len(self.cursor.0));
         }
         // Only clear selection if not in selection mode
@@ -76,8 +74,8 @@ impl super::CodeEditor {
 
     /// Move cursor down
     pub fn move_down(&mut self) {
-        if self.cursor.0 + 1 < self.lines.len() {
-            self.cursor.0 += 1;
+        if let Some(line) = self.next_visible_line(self.cursor.0) {
+            self.cursor.0 = line;
             self.cursor.1 = self.cursor.1.min(self.line_len(self.cursor.0));
         }
         // Only clear selection if not in selection mode
@@ -90,7 +88,7 @@ impl super::CodeEditor {
     /// Move to start of line
     pub fn move_home(&mut self) {
         // Smart home: first go to first non-whitespace, then to column 0
-        let line = &self.lines[self.cursor.0];
+        let line = self.doc.line(self.cursor.0);
         let first_non_ws = line.chars().position(|c| !c.is_whitespace()).unwrap_or(0);
 
         if self.cursor.1 == first_non_ws || self.cursor.1 == 0 {
@@ -127,7 +125,7 @@ impl super::CodeEditor {
 
     /// Move to end of document
     pub fn move_document_end(&mut self) {
-        let last_line = self.lines.len().saturating_sub(1);
+        let last_line = self.doc.len_lines() - 1;
         self.cursor = (last_line, self.line_len(last_line));
         // Only clear selection if not in selection mode
         if self.anchor.is_none() {
@@ -151,8 +149,7 @@ impl super::CodeEditor {
             return;
         }
 
-        let line = &self.lines[self.cursor.0];
-        let chars: Vec<char> = line.chars().collect();
+        let chars: Vec<char> = self.doc.line(self.cursor.0).chars().collect();
         let mut col = self.cursor.1.min(chars.len());
 
         // Skip whitespace
@@ -174,12 +171,11 @@ impl super::CodeEditor {
 
     /// Move by word right
     pub fn move_word_right(&mut self) {
-        let line = &self.lines[self.cursor.0];
-        let chars: Vec<char> = line.chars().collect();
+        let chars: Vec<char> = self.doc.line(self.cursor.0).chars().collect();
         let mut col = self.cursor.1;
 
         if col >= chars.len() {
-            if self.cursor.0 + 1 < self.lines.len() {
+            if self.cursor.0 + 1 < self.doc.len_lines() {
                 self.cursor.0 += 1;
                 self.cursor.1 = 0;
             }
@@ -221,7 +217,7 @@ impl super::CodeEditor {
 
     /// Page down
     pub fn page_down(&mut self, page_size: usize) {
-        self.cursor.0 = (self.cursor.0 + page_size).min(self.lines.len().saturating_sub(1));
+        self.cursor.0 = (self.cursor.0 + page_size).min(self.doc.len_lines() - 1);
         self.cursor.1 = self.cursor.1.min(self.line_len(self.cursor.0));
         // Only clear selection if not in selection mode
         if self.anchor.is_none() {
@@ -232,10 +228,28 @@ impl super::CodeEditor {
 
     /// Ensure cursor is visible
     pub(super) fn ensure_cursor_visible(&mut self) {
+        self.reveal_line(self.cursor.0);
+
         // Adjust vertical scroll
         if self.cursor.0 < self.scroll.0 {
             self.scroll.0 = self.cursor.0;
         }
+        let height = self.last_viewport_height.get();
+        if height > 0
+            && !self
+                .visible_lines_from(self.scroll.0, height)
+                .contains(&self.cursor.0)
+        {
+            // Scroll so the cursor is on the last row
+            let mut top = self.cursor.0;
+            for _ in 1..height {
+                match self.prev_visible_line(top) {
+                    Some(line) => top = line,
+                    None => break,
+                }
+            }
+            self.scroll.0 = top;
+        }
         // Horizontal scroll adjustment would need view width
     }
 }
@@ -1,7 +1,9 @@
 //! Code editor rendering
 
+use super::types::MinimapStyle;
 use crate::render::Cell;
 use crate::style::Color;
+use crate::widget::canvas::BrailleGrid;
 use crate::widget::code_editor::CodeEditor;
 use crate::widget::theme::PLACEHOLDER_FG;
 use crate::widget::traits::{RenderContext, View};
@@ -15,13 +17,19 @@ impl CodeEditor {
     #[doc(hidden)]
     pub fn line_number_width(&self) -> u16 {
         if self.show_line_numbers {
-            let digits = format!("{}", self.lines.len()).len();
+            let digits = format!("{}", self.doc.len_lines()).len();
             (digits + 2) as u16
         } else {
             0
         }
     }
 
+    /// Width of everything left of the text: signs, numbers, fold markers
+    pub(super) fn gutter_width(&self) -> u16 {
+        let fold_width = if self.config.folding { 2 } else { 0 };
+        self.sign_column_width() + self.line_number_width() + fold_width
+    }
+
     /// Get syntax highlights for a line
     #[doc(hidden)]
     pub fn get_highlights(&self, line: &str) -> Vec<crate::widget::syntax::HighlightSpan> {
@@ -31,34 +39,110 @@ impl CodeEditor {
             .unwrap_or_default()
     }
 
-    /// Check if position is in selection
-    #[doc(hidden)]
-    pub fn is_selected(&self, line: usize, col: usize) -> bool {
-        let anchor = match self.anchor {
-            Some(a) => a,
-            None => return false,
-        };
+    /// Syntax highlights for a range of lines, from the parse tree
+    ///
+    /// `None` when the language has no tree-sitter grammar, in which case
+    /// lines are highlighted one at a time with [`get_highlights`](Self::get_highlights).
+    #[cfg(feature = "syntax-highlighting")]
+    fn tree_highlights(
+        &self,
+        lines: std::ops::Range<usize>,
+    ) -> Option<Vec<Vec<crate::widget::syntax::HighlightSpan>>> {
+        self.syntax
+            .as_ref()
+            .filter(|syntax| syntax.tree().is_some())
+            .map(|syntax| syntax.highlight_lines(&self.doc, lines))
+    }
 
-        let (start, end) = if anchor < self.cursor {
-            (anchor, self.cursor)
-        } else {
-            (self.cursor, anchor)
-        };
+    #[cfg(not(feature = "syntax-highlighting"))]
+    fn tree_highlights(
+        &self,
+        _lines: std::ops::Range<usize>,
+    ) -> Option<Vec<Vec<crate::widget::syntax::HighlightSpan>>> {
+        None
+    }
+
+    #[cfg(not(feature = "lsp"))]
+    fn sign_column_width(&self) -> u16 {
+        0
+    }
+
+    #[cfg(not(feature = "lsp"))]
+    fn line_sign(&self, _line: usize) -> Option<Color> {
+        None
+    }
+
+    #[cfg(not(feature = "lsp"))]
+    fn diagnostic_spans(&self, _line: usize) -> Vec<(std::ops::Range<usize>, Color)> {
+        Vec::new()
+    }
+
+    #[cfg(not(feature = "lsp"))]
+    fn render_lsp_popups(&self, _ctx: &mut RenderContext, _anchor: (u16, u16)) {}
 
-        if line < start.0 || line > end.0 {
-            return false;
+    /// Draw the minimap in braille dots, one line of text per dot row
+    ///
+    /// Each dot covers two characters; files taller than the minimap put
+    /// several lines in a dot row.
+    fn render_braille_minimap(&self, ctx: &mut RenderContext, minimap_x: u16, end_line: usize) {
+        let area = ctx.area;
+        let width = area.width - minimap_x;
+        let mut grid = BrailleGrid::new(width, area.height);
+        let lines_per_dot = self.doc.len_lines().div_ceil(grid.height()).max(1);
+
+        for (idx, line) in self.doc.lines().enumerate() {
+            let chars: Vec<char> = line.chars().collect();
+            for (col, chunk) in chars.chunks(2).enumerate().take(grid.width()) {
+                if chunk.iter().any(|c| !c.is_whitespace()) {
+                    grid.set(col, idx / lines_per_dot, PLACEHOLDER_FG);
+                }
+            }
         }
-        if line == start.0 && line == end.0 {
-            col >= start.1 && col < end.1
-        } else if line == start.0 {
-            col >= start.1
-        } else if line == end.0 {
-            col < end.1
-        } else {
-            true
+
+        for y in 0..area.height {
+            let first = y as usize * 4 * lines_per_dot;
+            let visible = first < end_line && self.scroll.0 < first + 4 * lines_per_dot;
+            for x in 0..width {
+                let mut cell = Cell::new(grid.get_char(x as usize, y as usize));
+                cell.fg = Some(PLACEHOLDER_FG);
+                cell.bg = Some(if visible {
+                    self.minimap_visible_bg
+                } else {
+                    self.minimap_bg
+                });
+                ctx.set(minimap_x + x, y, cell);
+            }
         }
     }
 
+    /// Check if position is in the selection of any cursor
+    #[doc(hidden)]
+    pub fn is_selected(&self, line: usize, col: usize) -> bool {
+        self.all_cursors().into_iter().any(|(cursor, anchor)| {
+            let Some(anchor) = anchor else {
+                return false;
+            };
+            let (start, end) = if anchor < cursor {
+                (anchor, cursor)
+            } else {
+                (cursor, anchor)
+            };
+
+            if line < start.0 || line > end.0 {
+                return false;
+            }
+            if line == start.0 && line == end.0 {
+                col >= start.1 && col < end.1
+            } else if line == start.0 {
+                col >= start.1
+            } else if line == end.0 {
+                col < end.1
+            } else {
+                true
+            }
+        })
+    }
+
     /// Check if position is in a find match
     #[doc(hidden)]
     pub fn get_find_match_at(&self, line: usize, col: usize) -> Option<(bool, usize)> {
@@ -80,14 +164,20 @@ impl View for CodeEditor {
             return;
         }
 
-        let line_num_width = self.line_number_width();
+        // Diagnostic signs, then line numbers, then fold markers
+        let sign_width = self.sign_column_width();
+        let number_width = self.line_number_width();
+        let line_num_width = self.gutter_width();
+        let fold_width = line_num_width - sign_width - number_width;
         let minimap_width = if self.config.show_minimap {
             self.config.minimap_width
         } else {
             0
         };
         let text_width = area.width.saturating_sub(line_num_width + minimap_width);
-        let visible_lines = area.height as usize;
+        // Vim mode keeps the bottom row for its status line
+        let status_rows = usize::from(self.vim.is_some() && area.height > 1);
+        let visible_lines = area.height as usize - status_rows;
 
         // Find matching bracket
         let bracket_match = self.find_matching_bracket();
@@ -103,14 +193,41 @@ impl View for CodeEditor {
             }
         }
 
-        // Render visible lines
+        // Render visible lines, below the sticky header if there is one
         let start_line = self.scroll.0;
-        let end_line = (start_line + visible_lines).min(self.lines.len());
+        let sticky = self.sticky_header();
+        let sticky_rows = usize::from(sticky.is_some());
+        let mut rows = self.visible_lines_from(start_line, visible_lines - sticky_rows);
+        self.last_viewport_height
+            .set(visible_lines - usize::from(self.config.sticky_scroll).min(visible_lines - 1));
+        let end_line = rows.last().map_or(start_line, |&line| line + 1);
+        let tree_highlights = self.tree_highlights(start_line..end_line);
+        rows.splice(0..0, sticky);
+        let fold_starts: Vec<usize> = if self.config.folding {
+            self.fold_ranges().iter().map(|r| r.start).collect()
+        } else {
+            Vec::new()
+        };
 
-        for (view_row, line_idx) in (start_line..end_line).enumerate() {
+        for (view_row, &line_idx) in rows.iter().enumerate() {
             let y = view_row as u16;
-            let line = &self.lines[line_idx];
-            let is_current_line = line_idx == self.cursor.0;
+            let line = &self.doc.line(line_idx);
+            let is_sticky = view_row < sticky_rows;
+            let is_current_line = line_idx == self.cursor.0 && !is_sticky;
+            let line_bg = if is_sticky {
+                Some(self.sticky_scroll_bg)
+            } else {
+                self.bg
+            };
+
+            // Sticky header background
+            if is_sticky {
+                for x in 0..area.width - minimap_width {
+                    let mut cell = Cell::new(' ');
+                    cell.bg = line_bg;
+                    ctx.set(x, y, cell);
+                }
+            }
 
             // Current line highlight
             if self.config.highlight_current_line && is_current_line && self.focused {
@@ -121,29 +238,67 @@ impl View for CodeEditor {
                 }
             }
 
+            // Draw diagnostic sign
+            if let Some(color) = self.line_sign(line_idx) {
+                let mut cell = Cell::new('●');
+                cell.fg = Some(color);
+                cell.bg = line_bg;
+                ctx.set(0, y, cell);
+            }
+
             // Draw line numbers
             if self.show_line_numbers {
                 let num_str = format!(
                     "{:>width$} ",
                     line_idx + 1,
-                    width = (line_num_width - 2) as usize
+                    width = (number_width - 2) as usize
                 );
                 for (i, ch) in num_str.chars().enumerate() {
-                    if (i as u16) < line_num_width {
+                    if (i as u16) < number_width {
                         let mut cell = Cell::new(ch);
                         cell.fg = Some(if is_current_line && self.focused {
                             Color::WHITE
                         } else {
                             self.line_number_fg
                         });
-                        cell.bg = self.bg;
-                        ctx.set(i as u16, y, cell);
+                        cell.bg = line_bg;
+                        ctx.set(sign_width + i as u16, y, cell);
                     }
                 }
             }
 
+            // Draw fold marker
+            if fold_width > 0 {
+                let marker = if self.is_folded(line_idx) {
+                    Some('▸')
+                } else if fold_starts.contains(&line_idx) {
+                    Some('▾')
+                } else {
+                    None
+                };
+                if let Some(marker) = marker {
+                    let mut cell = Cell::new(marker);
+                    cell.fg = Some(self.fold_marker_fg);
+                    cell.bg = line_bg;
+                    ctx.set(sign_width + number_width, y, cell);
+                }
+            }
+
             // Get syntax highlights
-            let highlights = self.get_highlights(line);
+            let highlights = match &tree_highlights {
+                Some(spans) if !is_sticky => spans
+                    .get(line_idx - start_line)
+                    .cloned()
+                    .unwrap_or_default(),
+                _ => self.get_highlights(line),
+            };
+
+            let diagnostics = self.diagnostic_spans(line_idx);
+            let guides = if self.config.indent_guides {
+                self.guide_columns(line_idx)
+            } else {
+                Vec::new()
+            };
 
             // Draw text
             let chars: Vec<char> = line.chars().collect();
@@ -162,8 +317,7 @@ impl View for CodeEditor {
                 let mut cell = Cell::new(ch);
 
                 // Check cursor position
-                let is_cursor =
-                    self.focused && line_idx == self.cursor.0 && char_idx == self.cursor.1;
+                let is_cursor = self.focused && self.is_cursor_at(line_idx, char_idx);
 
                 // Check selection
                 let is_selected = self.is_selected(line_idx, char_idx);
@@ -213,10 +367,23 @@ impl View for CodeEditor {
                     if !fg_set {
                         cell.fg = self.fg;
                     }
+                    // Underline diagnostics in their severity color
+                    if let Some((_, color)) = diagnostics
+                        .iter()
+                        .find(|(range, _)| range.contains(&char_idx))
+                    {
+                        cell.fg = Some(*color);
+                        cell.modifier |= crate::render::Modifier::UNDERLINE;
+                    }
                     if self.config.highlight_current_line && is_current_line && self.focused {
                         cell.bg = Some(self.current_line_bg);
                     } else {
-                        cell.bg = self.bg;
+                        cell.bg = line_bg;
+                    }
+                    // Indentation guide in leading whitespace
+                    if ch.is_whitespace() && guides.contains(&char_idx) {
+                        cell.symbol = '│';
+                        cell.fg = Some(self.indent_guide_fg);
                     }
                 }
 
@@ -224,8 +391,42 @@ impl View for CodeEditor {
                 display_x += cw;
             }
 
-            // Draw cursor at end of line if needed
-            if self.focused && line_idx == self.cursor.0 && self.cursor.1 >= chars.len() {
+            // Continue guides through blank lines
+            let text_end = chars.len().max(scroll_col);
+            for &col in guides.iter().filter(|&&col| col >= text_end) {
+                let x = line_num_width + display_x + (col - text_end) as u16;
+                if x >= area.width - minimap_width {
+                    break;
+                }
+                let mut cell = Cell::new('│');
+                cell.fg = Some(self.indent_guide_fg);
+                cell.bg = if self.config.highlight_current_line && is_current_line && self.focused {
+                    Some(self.current_line_bg)
+                } else {
+                    line_bg
+                };
+                ctx.set(x, y, cell);
+            }
+
+            // Mark folded regions after the header text
+            if self.is_folded(line_idx) {
+                for (i, ch) in " ⋯".chars().enumerate() {
+                    let x = line_num_width + display_x + i as u16;
+                    if x < area.width - minimap_width {
+                        let mut cell = Cell::new(ch);
+                        cell.fg = Some(self.fold_marker_fg);
+                        cell.bg = line_bg;
+                        ctx.set(x, y, cell);
+                    }
+                }
+            }
+
+            // Draw cursors at end of line if needed
+            let cursor_at_end = self
+                .cursors()
+                .iter()
+                .any(|&(line, col)| line == line_idx && col >= chars.len());
+            if self.focused && !is_sticky && cursor_at_end {
                 let cursor_x = line_num_width + display_x;
                 if cursor_x < area.width - minimap_width {
                     let mut cell = Cell::new(' ');
@@ -248,39 +449,87 @@ impl View for CodeEditor {
                 }
             }
 
-            // Calculate minimap scale
-            let total_lines = self.lines.len();
-            let minimap_height = area.height as usize;
-            let lines_per_row = (total_lines as f32 / minimap_height as f32).max(1.0);
-
-            // Draw minimap content
-            for row in 0..minimap_height {
-                let start_line = (row as f32 * lines_per_row) as usize;
-                let y = row as u16;
-
-                // Highlight visible area
-                if start_line >= self.scroll.0 && start_line < self.scroll.0 + visible_lines {
-                    for x in 0..minimap_width {
-                        let mut cell = Cell::new(' ');
-                        cell.bg = Some(self.minimap_visible_bg);
-                        ctx.set(minimap_x + x, y, cell);
+            if self.config.minimap_style == MinimapStyle::Braille {
+                self.render_braille_minimap(ctx, minimap_x, end_line);
+            } else {
+                // Calculate minimap scale
+                let total_lines = self.doc.len_lines();
+                let minimap_height = area.height as usize;
+                let lines_per_row = (total_lines as f32 / minimap_height as f32).max(1.0);
+
+                // Draw minimap content
+                for row in 0..minimap_height {
+                    let start_line = (row as f32 * lines_per_row) as usize;
+                    let y = row as u16;
+
+                    // Highlight visible area
+                    if start_line >= self.scroll.0 && start_line < end_line {
+                        for x in 0..minimap_width {
+                            let mut cell = Cell::new(' ');
+                            cell.bg = Some(self.minimap_visible_bg);
+                            ctx.set(minimap_x + x, y, cell);
+                        }
                     }
-                }
 
-                // Draw condensed line representation
-                if let Some(line) = self.lines.get(start_line) {
-                    let chars: Vec<char> = line.chars().collect();
-                    for (i, &ch) in chars.iter().take(minimap_width as usize).enumerate() {
-                        if !ch.is_whitespace() {
-                            let mut cell = Cell::new('▪');
-                            cell.fg = Some(PLACEHOLDER_FG);
-                            ctx.set(minimap_x + i as u16, y, cell);
+                    // Draw condensed line representation
+                    if start_line < total_lines {
+                        let chars: Vec<char> = self.doc.line(start_line).chars().collect();
+                        for (i, &ch) in chars.iter().take(minimap_width as usize).enumerate() {
+                            if !ch.is_whitespace() {
+                                let mut cell = Cell::new('▪');
+                                cell.fg = Some(PLACEHOLDER_FG);
+                                ctx.set(minimap_x + i as u16, y, cell);
+                            }
                         }
                     }
                 }
             }
         }
 
+        // Draw completion, hover and signature help at the cursor
+        let cursor_row = rows
+            .iter()
+            .skip(sticky_rows)
+            .position(|&line| line == self.cursor.0)
+            .map(|row| row + sticky_rows);
+        if let (true, Some(cursor_row)) = (self.focused, cursor_row) {
+            let cursor_x: usize = self
+                .doc
+                .line(self.cursor.0)
+                .chars()
+                .skip(self.scroll.1)
+                .take(self.cursor.1.saturating_sub(self.scroll.1))
+                .map(crate::utils::char_width)
+                .sum();
+            let anchor = (
+                line_num_width + (cursor_x as u16).min(text_width),
+                cursor_row as u16,
+            );
+            self.render_lsp_popups(ctx, anchor);
+        }
+
+        // Draw Vim status line
+        if let Some(vim) = self.vim.as_ref().filter(|_| status_rows > 0) {
+            let y = area.height - 1;
+            let mode = vim.mode();
+            for x in 0..area.width {
+                let mut cell = Cell::new(' ');
+                cell.bg = self.bg;
+                ctx.set(x, y, cell);
+            }
+            let mut x = 0u16;
+            for ch in vim.status_text().chars() {
+                if x >= area.width {
+                    break;
+                }
+                let mut cell = Cell::new(ch);
+                cell.fg = Some(mode.color());
+                cell.bg = self.bg;
+                ctx.set(x, y, cell);
+                x += crate::utils::char_width(ch).max(1) as u16;
+            }
+        }
+
         // Draw go-to-line dialog
         if self.goto_line_mode {
             let dialog_width = 20u16;
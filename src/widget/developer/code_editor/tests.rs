@@ -12,23 +12,23 @@ mod tests {
     #[test]
     fn test_code_editor_new() {
         let editor = CodeEditor::new();
-        assert_eq!(editor.lines.len(), 1);
+        assert_eq!(editor.doc.len_lines(), 1);
         assert_eq!(editor.cursor, (0, 0));
     }
 
     #[test]
     fn test_code_editor_default() {
         let editor = CodeEditor::default();
-        assert_eq!(editor.lines.len(), 1);
+        assert_eq!(editor.doc.len_lines(), 1);
         assert_eq!(editor.cursor, (0, 0));
     }
 
     #[test]
     fn test_code_editor_content() {
         let editor = CodeEditor::new().content("Hello\nWorld");
-        assert_eq!(editor.lines.len(), 2);
-        assert_eq!(editor.lines[0], "Hello");
-        assert_eq!(editor.lines[1], "World");
+        assert_eq!(editor.doc.len_lines(), 2);
+        assert_eq!(editor.doc.line(0), "Hello");
+        assert_eq!(editor.doc.line(1), "World");
     }
 
     #[test]
@@ -67,9 +67,9 @@ mod tests {
         editor.insert_char('a');
         editor.insert_char('\n');
         editor.insert_char('b');
-        assert_eq!(editor.lines.len(), 2);
-        assert_eq!(editor.lines[0], "a");
-        assert_eq!(editor.lines[1], "b");
+        assert_eq!(editor.doc.len_lines(), 2);
+        assert_eq!(editor.doc.line(0), "a");
+        assert_eq!(editor.doc.line(1), "b");
     }
 
     #[test]
@@ -111,8 +111,8 @@ mod tests {
     fn test_code_editor_delete_line() {
         let mut editor = CodeEditor::new().content("line1\nline2\nline3");
         editor.delete_line(1);
-        assert_eq!(editor.lines.len(), 2);
-        assert_eq!(editor.lines[1], "line3");
+        assert_eq!(editor.doc.len_lines(), 2);
+        assert_eq!(editor.doc.line(1), "line3");
     }
 
     // =========================================================================
@@ -430,14 +430,14 @@ mod tests {
     fn test_code_editor_set_line() {
         let mut editor = CodeEditor::new().content("line1\nline2");
         editor.set_line(0, "new line");
-        assert_eq!(editor.lines[0], "new line");
+        assert_eq!(editor.doc.line(0), "new line");
     }
 
     #[test]
     fn test_code_editor_insert_line() {
         let mut editor = CodeEditor::new().content("line1\nline3");
         editor.insert_line(1, "line2");
-        assert_eq!(editor.lines[1], "line2");
+        assert_eq!(editor.doc.line(1), "line2");
     }
 
     // =========================================================================
@@ -449,7 +449,7 @@ mod tests {
         let mut editor = CodeEditor::new().content("some content");
         editor.clear();
         assert_eq!(editor.get_content(), "");
-        assert_eq!(editor.lines.len(), 1);
+        assert_eq!(editor.doc.len_lines(), 1);
     }
 
     #[test]
@@ -529,7 +529,7 @@ mod tests {
     #[test]
     fn test_code_editor_helper() {
         let editor = code_editor();
-        assert_eq!(editor.lines.len(), 1);
+        assert_eq!(editor.doc.len_lines(), 1);
     }
 
     #[test]
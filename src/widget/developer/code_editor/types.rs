@@ -1,7 +1,11 @@
 //! Code editor types and configuration
 
 /// Maximum undo history size
-pub const MAX_UNDO_HISTORY: usize = 100;
+#[deprecated(
+    since = "2.75.0",
+    note = "history now lives in `Document`; use `revue::utils::document::DEFAULT_UNDO_LIMIT`"
+)]
+pub const MAX_UNDO_HISTORY: usize = crate::utils::document::DEFAULT_UNDO_LIMIT;
 
 /// Bracket pair for matching
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -31,6 +35,32 @@ pub enum IndentStyle {
     Tabs,
 }
 
+/// A foldable region of lines
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FoldRange {
+    /// Header line, which stays visible when folded
+    pub start: usize,
+    /// Last line hidden when folded
+    pub end: usize,
+}
+
+impl FoldRange {
+    /// Check if a line is hidden when this range is folded
+    pub fn hides(&self, line: usize) -> bool {
+        line > self.start && line <= self.end
+    }
+}
+
+/// Minimap rendering style
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MinimapStyle {
+    /// One block per character of the first columns of a line
+    #[default]
+    Blocks,
+    /// Braille dots, four lines and several columns per cell
+    Braille,
+}
+
 /// Code editor configuration
 #[derive(Clone, Debug)]
 pub struct EditorConfig {
@@ -48,6 +78,14 @@ pub struct EditorConfig {
     pub show_minimap: bool,
     /// Minimap width
     pub minimap_width: u16,
+    /// Minimap style
+    pub minimap_style: MinimapStyle,
+    /// Enable code folding and fold markers in the gutter
+    pub folding: bool,
+    /// Show indentation guides
+    pub indent_guides: bool,
+    /// Pin the header of the scope at the top of the view
+    pub sticky_scroll: bool,
     /// Show whitespace characters
     pub show_whitespace: bool,
     /// Enable word wrap
@@ -64,6 +102,10 @@ impl Default for EditorConfig {
             highlight_current_line: true,
             show_minimap: false,
             minimap_width: 10,
+            minimap_style: MinimapStyle::Blocks,
+            folding: false,
+            indent_guides: false,
+            sticky_scroll: false,
             show_whitespace: false,
             word_wrap: false,
         }
@@ -71,6 +113,10 @@ impl Default for EditorConfig {
 }
 
 /// Edit operation for undo/redo
+#[deprecated(
+    since = "2.75.0",
+    note = "the editor records edits in its `Document`; this type is no longer produced"
+)]
 #[derive(Clone, Debug)]
 pub enum EditOp {
     /// Insert text at position
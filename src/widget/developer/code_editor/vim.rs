@@ -0,0 +1,142 @@
+//! Code editor Vim mode
+//!
+//! With [`CodeEditor::vim`] enabled, keys go through a [`VimState`] that
+//! edits the editor as a [`VimBuffer`]. Insert-mode typing still uses the
+//! editor's own handling, so auto-indent and bracket closing keep working.
+
+use std::ops::Range;
+
+use super::CodeEditor;
+use crate::event::KeyEvent;
+use crate::utils::Document;
+use crate::widget::developer::vim::{
+    VimBuffer, VimCommandResult, VimSelection, VimSelectionKind, VimState,
+};
+
+impl CodeEditor {
+    /// Enable/disable Vim mode
+    pub fn vim(mut self, enable: bool) -> Self {
+        self.set_vim(enable);
+        self
+    }
+
+    /// Enable/disable Vim mode (mutable)
+    pub fn set_vim(&mut self, enable: bool) {
+        if enable != self.vim.is_some() {
+            self.vim = enable.then(VimState::new);
+        }
+    }
+
+    /// Get the Vim state, when Vim mode is on
+    pub fn vim_state(&self) -> Option<&VimState> {
+        self.vim.as_ref()
+    }
+
+    /// Get the Vim state mutably, e.g. to set its clipboard
+    pub fn vim_state_mut(&mut self) -> Option<&mut VimState> {
+        self.vim.as_mut()
+    }
+
+    /// Take the last ex command for the host to carry out, like `:w`
+    pub fn take_vim_command(&mut self) -> Option<VimCommandResult> {
+        self.vim.as_mut().and_then(VimState::take_command)
+    }
+
+    /// Handle a key through Vim mode
+    pub(super) fn handle_vim_key(&mut self, event: &KeyEvent) -> Option<bool> {
+        let mut vim = self.vim.take()?;
+        let handled = vim.process(event, self);
+        self.vim = Some(vim);
+        Some(handled)
+    }
+}
+
+impl VimBuffer for CodeEditor {
+    fn document(&self) -> &Document {
+        &self.doc
+    }
+
+    fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    fn set_cursor(&mut self, line: usize, col: usize) {
+        CodeEditor::set_cursor(self, line, col);
+    }
+
+    fn replace(&mut self, range: Range<usize>, text: &str) {
+        if self.read_only {
+            return;
+        }
+        self.doc.replace(range, text);
+        self.sync_document();
+        let line = self.cursor.0.min(self.doc.len_lines() - 1);
+        self.cursor = (line, self.cursor.1.min(self.line_len(line)));
+    }
+
+    fn begin_change(&mut self) {
+        self.doc.begin_transaction();
+    }
+
+    fn end_change(&mut self) {
+        self.doc.commit_transaction();
+    }
+
+    fn undo(&mut self) {
+        CodeEditor::undo(self);
+    }
+
+    fn redo(&mut self) {
+        CodeEditor::redo(self);
+    }
+
+    fn set_selection(&mut self, selection: Option<VimSelection>) {
+        let Some(selection) = selection else {
+            self.anchor = None;
+            self.clear_secondary_cursors();
+            return;
+        };
+        match selection.kind {
+            VimSelectionKind::Char => {
+                // The editor's selection end is exclusive
+                let (start, end) = if selection.anchor <= selection.cursor {
+                    (selection.anchor, selection.cursor)
+                } else {
+                    (selection.cursor, selection.anchor)
+                };
+                let end = self.doc.char_to_pos(self.doc.pos_to_char(end.0, end.1) + 1);
+                if selection.anchor <= selection.cursor {
+                    self.anchor = Some(start);
+                    self.cursor = end;
+                } else {
+                    self.anchor = Some(end);
+                    self.cursor = start;
+                }
+            }
+            VimSelectionKind::Line => {
+                let (first, last) = selection.lines();
+                let last_end = (last, self.line_len(last));
+                if selection.anchor.0 <= selection.cursor.0 {
+                    self.anchor = Some((first, 0));
+                    self.cursor = last_end;
+                } else {
+                    self.anchor = Some(last_end);
+                    self.cursor = (first, 0);
+                }
+            }
+            VimSelectionKind::Block => {
+                let (cmin, cmax) = selection.columns();
+                self.select_block((selection.anchor.0, cmin), (selection.cursor.0, cmax + 1));
+            }
+        }
+        self.ensure_cursor_visible();
+    }
+
+    fn insert_key(&mut self, key: &KeyEvent) -> bool {
+        self.handle_key(&key.key)
+    }
+
+    fn indent_unit(&self) -> String {
+        CodeEditor::indent_unit(self)
+    }
+}
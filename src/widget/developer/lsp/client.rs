@@ -0,0 +1,584 @@
+//! Language server connection
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{channel, Receiver};
+
+use serde_json::{json, Value};
+
+use super::protocol::{
+    parse_completion, parse_hover, parse_locations, parse_signature_help, parse_text_edits,
+    parse_workspace_edit, CompletionItem, Diagnostic, Hover, Location, Position, Range,
+    SignatureHelp, TextEdit, WorkspaceEdit,
+};
+use super::transport::{encode, spawn_reader, HandlerWriter};
+use crate::utils::{Document, DocumentChange};
+
+/// How the server wants document changes sent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextSync {
+    /// Changes are not sent
+    None,
+    /// The whole text is sent on every change
+    Full,
+    /// Only the changed ranges are sent
+    #[default]
+    Incremental,
+}
+
+/// Features the server announced in its `initialize` result
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServerCapabilities {
+    /// Document sync kind
+    pub text_sync: TextSync,
+    /// Characters that open the completion popup when typed
+    pub completion_triggers: Vec<char>,
+    /// Characters that request signature help when typed
+    pub signature_triggers: Vec<char>,
+}
+
+impl ServerCapabilities {
+    fn parse(value: &Value) -> Self {
+        let sync = value
+            .get("textDocumentSync")
+            .map(|sync| sync.get("change").unwrap_or(sync))
+            .and_then(Value::as_u64);
+        let chars = |provider: &str| -> Vec<char> {
+            value
+                .get(provider)
+                .and_then(|p| p.get("triggerCharacters"))
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|c| c.as_str()?.chars().next())
+                .collect()
+        };
+        Self {
+            text_sync: match sync {
+                Some(0) => TextSync::None,
+                Some(1) => TextSync::Full,
+                _ => TextSync::Incremental,
+            },
+            completion_triggers: chars("completionProvider"),
+            signature_triggers: chars("signatureHelpProvider"),
+        }
+    }
+}
+
+/// Something the server sent, decoded
+#[derive(Debug, Clone, PartialEq)]
+pub enum LspEvent {
+    /// The server finished initializing
+    Initialized,
+    /// Diagnostics replacing the previous ones for a document
+    Diagnostics {
+        /// Document URI
+        uri: String,
+        /// Current diagnostics
+        diagnostics: Vec<Diagnostic>,
+    },
+    /// Completion candidates
+    Completion(Vec<CompletionItem>),
+    /// Hover information, `None` if there is nothing to show
+    Hover(Option<Hover>),
+    /// Definition locations
+    Definition(Vec<Location>),
+    /// Signature help, `None` outside of a call
+    SignatureHelp(Option<SignatureHelp>),
+    /// Edits that format a document
+    Formatting {
+        /// Document URI
+        uri: String,
+        /// Edits to apply
+        edits: Vec<TextEdit>,
+    },
+    /// Edits that rename a symbol
+    Rename(WorkspaceEdit),
+    /// A `window/showMessage` notification
+    Message(String),
+    /// A request failed
+    Error {
+        /// Request method
+        method: String,
+        /// Error message from the server
+        message: String,
+    },
+}
+
+/// Request awaiting a response
+#[derive(Debug, Clone)]
+enum Pending {
+    Initialize,
+    Completion,
+    Hover,
+    Definition,
+    SignatureHelp,
+    Formatting(String),
+    Rename,
+    Shutdown,
+}
+
+impl Pending {
+    fn method(&self) -> &'static str {
+        match self {
+            Self::Initialize => "initialize",
+            Self::Completion => "textDocument/completion",
+            Self::Hover => "textDocument/hover",
+            Self::Definition => "textDocument/definition",
+            Self::SignatureHelp => "textDocument/signatureHelp",
+            Self::Formatting(_) => "textDocument/formatting",
+            Self::Rename => "textDocument/rename",
+            Self::Shutdown => "shutdown",
+        }
+    }
+}
+
+/// Connection to a language server speaking JSON-RPC
+///
+/// Requests are sent without blocking; decoded replies and notifications
+/// are collected with [`poll`](Self::poll), typically once per tick.
+/// `initialize` is sent with the first message, and later messages are held
+/// back until the server answers it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use revue::widget::lsp::{self, LspClient};
+///
+/// let client = LspClient::spawn("rust-analyzer", &[])?
+///     .root_uri(lsp::file_uri(std::env::current_dir()?));
+/// let editor = code_editor()
+///     .language(Language::Rust)
+///     .content(source)
+///     .lsp(client, lsp::file_uri("src/main.rs"));
+/// ```
+pub struct LspClient {
+    writer: Box<dyn Write + Send>,
+    rx: Receiver<Value>,
+    child: Option<Child>,
+    root_uri: Option<String>,
+    next_id: u64,
+    pending: HashMap<u64, Pending>,
+    /// Messages held back until `initialize` is answered
+    queued: Vec<Value>,
+    initialize_sent: bool,
+    initialized: bool,
+    capabilities: ServerCapabilities,
+}
+
+impl LspClient {
+    fn with_io(writer: Box<dyn Write + Send>, rx: Receiver<Value>, child: Option<Child>) -> Self {
+        Self {
+            writer,
+            rx,
+            child,
+            root_uri: None,
+            next_id: 1,
+            pending: HashMap::new(),
+            queued: Vec::new(),
+            initialize_sent: false,
+            initialized: false,
+            capabilities: ServerCapabilities::default(),
+        }
+    }
+
+    /// Start a server process and talk to it over stdin/stdout
+    pub fn spawn(command: &str, args: &[&str]) -> io::Result<Self> {
+        let mut child = Command::new(command)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdin = child.stdin.take().ok_or(io::ErrorKind::BrokenPipe)?;
+        let stdout = child.stdout.take().ok_or(io::ErrorKind::BrokenPipe)?;
+        let (tx, rx) = channel();
+        spawn_reader(stdout, tx);
+        Ok(Self::with_io(Box::new(stdin), rx, Some(child)))
+    }
+
+    /// Talk to a server implemented by a function in this process
+    ///
+    /// The handler receives every message the client sends and returns the
+    /// messages the server replies with. Useful as a fake server in tests.
+    pub fn in_process(handler: impl FnMut(Value) -> Vec<Value> + Send + 'static) -> Self {
+        let (tx, rx) = channel();
+        Self::with_io(
+            Box::new(HandlerWriter::new(Box::new(handler), tx)),
+            rx,
+            None,
+        )
+    }
+
+    /// Set the workspace root sent with `initialize`
+    pub fn root_uri(mut self, uri: impl Into<String>) -> Self {
+        self.root_uri = Some(uri.into());
+        self
+    }
+
+    /// Whether the server has answered `initialize`
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    /// Features announced by the server
+    pub fn capabilities(&self) -> &ServerCapabilities {
+        &self.capabilities
+    }
+
+    // =========================================================================
+    // Sending
+    // =========================================================================
+
+    fn write(&mut self, message: &Value) {
+        if let Err(e) = self
+            .writer
+            .write_all(&encode(message))
+            .and_then(|()| self.writer.flush())
+        {
+            crate::log_warn!("Failed to write to language server: {}", e);
+        }
+    }
+
+    fn send(&mut self, message: Value) {
+        if !self.initialize_sent {
+            self.initialize_sent = true;
+            let id = self.next_id();
+            self.pending.insert(id, Pending::Initialize);
+            let init = json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": "initialize",
+                "params": {
+                    "processId": std::process::id(),
+                    "rootUri": self.root_uri,
+                    "capabilities": client_capabilities(),
+                },
+            });
+            self.write(&init);
+        }
+        if self.initialized {
+            self.write(&message);
+        } else {
+            self.queued.push(message);
+        }
+    }
+
+    fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    fn request(&mut self, pending: Pending, params: Value) -> u64 {
+        let id = self.next_id();
+        let method = pending.method();
+        self.pending.insert(id, pending);
+        self.send(json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}));
+        id
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({"jsonrpc": "2.0", "method": method, "params": params}));
+    }
+
+    /// Announce an opened document
+    pub fn did_open(&mut self, uri: &str, language_id: &str, doc: &Document) {
+        self.notify(
+            "textDocument/didOpen",
+            json!({"textDocument": {
+                "uri": uri,
+                "languageId": language_id,
+                "version": doc.version(),
+                "text": doc.text(),
+            }}),
+        );
+    }
+
+    /// Send changes taken from a document with change tracking enabled
+    pub fn did_change(&mut self, uri: &str, doc: &Document, changes: &[DocumentChange]) {
+        if changes.is_empty() {
+            return;
+        }
+        let content_changes: Vec<Value> = match self.capabilities.text_sync {
+            TextSync::None => return,
+            TextSync::Full => vec![json!({"text": doc.text()})],
+            TextSync::Incremental => changes
+                .iter()
+                .map(|change| json!({"range": Range::of_change(change), "text": change.text}))
+                .collect(),
+        };
+        self.notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": {"uri": uri, "version": doc.version()},
+                "contentChanges": content_changes,
+            }),
+        );
+    }
+
+    /// Announce a closed document
+    pub fn did_close(&mut self, uri: &str) {
+        self.notify(
+            "textDocument/didClose",
+            json!({"textDocument": {"uri": uri}}),
+        );
+    }
+
+    /// Request completions at a position
+    pub fn completion(&mut self, uri: &str, position: Position) -> u64 {
+        self.request(Pending::Completion, position_params(uri, position))
+    }
+
+    /// Request hover information at a position
+    pub fn hover(&mut self, uri: &str, position: Position) -> u64 {
+        self.request(Pending::Hover, position_params(uri, position))
+    }
+
+    /// Request the definition of the symbol at a position
+    pub fn definition(&mut self, uri: &str, position: Position) -> u64 {
+        self.request(Pending::Definition, position_params(uri, position))
+    }
+
+    /// Request signature help at a position
+    pub fn signature_help(&mut self, uri: &str, position: Position) -> u64 {
+        self.request(Pending::SignatureHelp, position_params(uri, position))
+    }
+
+    /// Request edits that format a document
+    pub fn formatting(&mut self, uri: &str, tab_size: usize, insert_spaces: bool) -> u64 {
+        self.request(
+            Pending::Formatting(uri.to_string()),
+            json!({
+                "textDocument": {"uri": uri},
+                "options": {"tabSize": tab_size, "insertSpaces": insert_spaces},
+            }),
+        )
+    }
+
+    /// Request edits that rename the symbol at a position
+    pub fn rename(&mut self, uri: &str, position: Position, new_name: &str) -> u64 {
+        let mut params = position_params(uri, position);
+        params["newName"] = json!(new_name);
+        self.request(Pending::Rename, params)
+    }
+
+    /// Ask the server to shut down; `exit` follows when it answers
+    pub fn shutdown(&mut self) -> u64 {
+        self.request(Pending::Shutdown, Value::Null)
+    }
+
+    // =========================================================================
+    // Receiving
+    // =========================================================================
+
+    /// Handle everything the server sent since the last call
+    pub fn poll(&mut self) -> Vec<LspEvent> {
+        let mut events = Vec::new();
+        while let Ok(message) = self.rx.try_recv() {
+            self.handle_message(message, &mut events);
+        }
+        events
+    }
+
+    fn handle_message(&mut self, message: Value, events: &mut Vec<LspEvent>) {
+        let method = message.get("method").and_then(Value::as_str);
+        match (message.get("id"), method) {
+            (Some(id), Some(method)) => {
+                let result = server_request_result(method, &message["params"]);
+                self.write(&json!({"jsonrpc": "2.0", "id": id, "result": result}));
+            }
+            (None, Some(method)) => {
+                let params = &message["params"];
+                match method {
+                    "textDocument/publishDiagnostics" => events.push(LspEvent::Diagnostics {
+                        uri: params["uri"].as_str().unwrap_or_default().to_string(),
+                        diagnostics: serde_json::from_value(params["diagnostics"].clone())
+                            .unwrap_or_default(),
+                    }),
+                    "window/showMessage" => events.push(LspEvent::Message(
+                        params["message"].as_str().unwrap_or_default().to_string(),
+                    )),
+                    _ => {}
+                }
+            }
+            (Some(id), None) => {
+                let Some(pending) = id.as_u64().and_then(|id| self.pending.remove(&id)) else {
+                    return;
+                };
+                if let Some(error) = message.get("error") {
+                    events.push(LspEvent::Error {
+                        method: pending.method().to_string(),
+                        message: error["message"].as_str().unwrap_or_default().to_string(),
+                    });
+                    return;
+                }
+                let result = message.get("result").cloned().unwrap_or(Value::Null);
+                if let Some(event) = self.handle_result(pending, result) {
+                    events.push(event);
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    fn handle_result(&mut self, pending: Pending, result: Value) -> Option<LspEvent> {
+        Some(match pending {
+            Pending::Initialize => {
+                self.capabilities = ServerCapabilities::parse(&result["capabilities"]);
+                self.initialized = true;
+                self.write(&json!({"jsonrpc": "2.0", "method": "initialized", "params": {}}));
+                for message in std::mem::take(&mut self.queued) {
+                    self.write(&message);
+                }
+                LspEvent::Initialized
+            }
+            Pending::Completion => LspEvent::Completion(parse_completion(result)),
+            Pending::Hover => LspEvent::Hover(parse_hover(result)),
+            Pending::Definition => LspEvent::Definition(parse_locations(result)),
+            Pending::SignatureHelp => LspEvent::SignatureHelp(parse_signature_help(result)),
+            Pending::Formatting(uri) => LspEvent::Formatting {
+                uri,
+                edits: parse_text_edits(result),
+            },
+            Pending::Rename => LspEvent::Rename(parse_workspace_edit(result)),
+            Pending::Shutdown => {
+                self.write(&json!({"jsonrpc": "2.0", "method": "exit"}));
+                return None;
+            }
+        })
+    }
+}
+
+impl Drop for LspClient {
+    fn drop(&mut self) {
+        if let Some(child) = &mut self.child {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// `file://` URI of a path, made absolute against the current directory
+pub fn file_uri(path: impl AsRef<Path>) -> String {
+    let path = path.as_ref();
+    let path = std::env::current_dir()
+        .map(|dir| dir.join(path))
+        .unwrap_or_else(|_| path.to_path_buf());
+    let mut uri = String::from("file://");
+    for ch in path.to_string_lossy().replace('\\', "/").chars() {
+        match ch {
+            ' ' | '#' | '%' | '?' => uri.push_str(&format!("%{:02X}", ch as u32)),
+            _ => uri.push(ch),
+        }
+    }
+    if !uri["file://".len()..].starts_with('/') {
+        uri.insert(7, '/');
+    }
+    uri
+}
+
+fn position_params(uri: &str, position: Position) -> Value {
+    json!({"textDocument": {"uri": uri}, "position": position})
+}
+
+/// What the client supports, sent with `initialize`
+fn client_capabilities() -> Value {
+    json!({
+        "textDocument": {
+            "synchronization": {"dynamicRegistration": false},
+            "publishDiagnostics": {},
+            "completion": {"completionItem": {"snippetSupport": false}},
+            "hover": {"contentFormat": ["plaintext", "markdown"]},
+            "definition": {"linkSupport": true},
+            "signatureHelp": {
+                "signatureInformation": {"parameterInformation": {"labelOffsetSupport": true}},
+            },
+            "formatting": {},
+            "rename": {},
+        },
+        "general": {"positionEncodings": ["utf-16"]},
+    })
+}
+
+/// Result for a request the server sent to the client
+///
+/// Configuration items are answered with `null`, which servers treat as
+/// their defaults; everything else is acknowledged.
+fn server_request_result(method: &str, params: &Value) -> Value {
+    match method {
+        "workspace/configuration" => {
+            let items = params["items"].as_array().map_or(0, Vec::len);
+            Value::Array(vec![Value::Null; items])
+        }
+        _ => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// Fake server recording what it receives
+    fn recording_server() -> (LspClient, Arc<Mutex<Vec<Value>>>) {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let seen = log.clone();
+        let client = LspClient::in_process(move |msg| {
+            seen.lock().unwrap().push(msg.clone());
+            match msg["method"].as_str() {
+                Some("initialize") => vec![json!({"id": msg["id"], "result": {"capabilities": {
+                    "textDocumentSync": {"change": 2},
+                    "completionProvider": {"triggerCharacters": ["."]},
+                }}})],
+                Some("textDocument/hover") => {
+                    vec![json!({"id": msg["id"], "error": {"code": -1, "message": "nope"}})]
+                }
+                _ => Vec::new(),
+            }
+        });
+        (client, log)
+    }
+
+    #[test]
+    fn test_messages_wait_for_initialize() {
+        let (mut client, log) = recording_server();
+        let doc = Document::with_content("x");
+        client.did_open("file:///a.rs", "rust", &doc);
+        assert_eq!(log.lock().unwrap().len(), 1);
+
+        assert_eq!(client.poll(), vec![LspEvent::Initialized]);
+        let methods: Vec<_> = log
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|m| m["method"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            methods,
+            ["initialize", "initialized", "textDocument/didOpen"]
+        );
+        assert_eq!(client.capabilities().completion_triggers, vec!['.']);
+    }
+
+    #[test]
+    fn test_error_response() {
+        let (mut client, _) = recording_server();
+        client.hover("file:///a.rs", Position::new(0, 0));
+        let events = client.poll();
+        assert_eq!(
+            events[1],
+            LspEvent::Error {
+                method: "textDocument/hover".into(),
+                message: "nope".into()
+            }
+        );
+    }
+
+    #[test]
+    fn test_file_uri() {
+        assert_eq!(file_uri("/tmp/my file.rs"), "file:///tmp/my%20file.rs");
+    }
+}
@@ -0,0 +1,37 @@
+//! Language Server Protocol client for [`CodeEditor`](super::CodeEditor)
+//!
+//! [`LspClient`] talks JSON-RPC to a language server, either a process over
+//! stdio or an in-process handler for tests. Attached to an editor with
+//! [`CodeEditor::lsp`](super::CodeEditor::lsp), it keeps the server in sync
+//! with the document and provides:
+//!
+//! - Diagnostics as gutter markers and underlined ranges
+//! - A completion popup
+//! - Hover and signature help tooltips
+//! - Go-to-definition, formatting and rename
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use revue::prelude::*;
+//! use revue::widget::lsp::{file_uri, LspClient};
+//!
+//! let client = LspClient::spawn("rust-analyzer", &[])?.root_uri(file_uri("."));
+//! let mut editor = code_editor()
+//!     .language(Language::Rust)
+//!     .content(std::fs::read_to_string("src/main.rs")?)
+//!     .lsp(client, file_uri("src/main.rs"));
+//!
+//! // Once per tick
+//! editor.poll_lsp();
+//! ```
+
+mod client;
+mod protocol;
+mod transport;
+
+pub use client::{file_uri, LspClient, LspEvent, ServerCapabilities, TextSync};
+pub use protocol::{
+    CompletionItem, Diagnostic, DiagnosticSeverity, Hover, Location, Position, Range,
+    SignatureHelp, SignatureInformation, TextEdit, WorkspaceEdit,
+};
@@ -0,0 +1,481 @@
+//! Language Server Protocol message types
+//!
+//! Only the parts of the protocol the editor uses are modeled. Positions
+//! use UTF-16 code units for columns, as LSP requires; convert with
+//! [`Position::from_char_pos`] and [`Position::to_char_pos`].
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::utils::{Document, DocumentChange};
+
+/// Position in a text document, with a UTF-16 column
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Position {
+    /// Zero-based line
+    pub line: u32,
+    /// Zero-based column in UTF-16 code units
+    pub character: u32,
+}
+
+impl Position {
+    /// Create a position
+    pub fn new(line: u32, character: u32) -> Self {
+        Self { line, character }
+    }
+
+    /// Position of a `(line, char column)` in a document
+    pub fn from_char_pos(doc: &Document, (line, col): (usize, usize)) -> Self {
+        let character = doc
+            .line(line)
+            .chars()
+            .take(col)
+            .map(char::len_utf16)
+            .sum::<usize>();
+        Self::new(line as u32, character as u32)
+    }
+
+    /// `(line, char column)` of this position in a document, clamped to it
+    pub fn to_char_pos(self, doc: &Document) -> (usize, usize) {
+        let line = (self.line as usize).min(doc.len_lines().saturating_sub(1));
+        let mut units = 0;
+        let mut col = 0;
+        for ch in doc.line(line).chars() {
+            if units >= self.character as usize {
+                break;
+            }
+            units += ch.len_utf16();
+            col += 1;
+        }
+        (line, col)
+    }
+}
+
+/// Range in a text document; the end is exclusive
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Range {
+    /// Start position
+    pub start: Position,
+    /// End position
+    pub end: Position,
+}
+
+impl Range {
+    /// Create a range
+    pub fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+
+    /// Range replaced by a document change, in the text before the change
+    pub fn of_change(change: &DocumentChange) -> Self {
+        let start = Position::new(
+            change.start_position.0 as u32,
+            change.start_utf16_column as u32,
+        );
+        let end = match change.removed.rfind('\n') {
+            Some(nl) => Position::new(
+                change.old_end_position.0 as u32,
+                change.removed[nl + 1..].encode_utf16().count() as u32,
+            ),
+            None => Position::new(
+                start.line,
+                start.character + change.removed.encode_utf16().count() as u32,
+            ),
+        };
+        Self::new(start, end)
+    }
+
+    /// Whether a position lies inside the range
+    pub fn contains(&self, pos: Position) -> bool {
+        self.start <= pos && (pos < self.end || self.start == self.end && pos == self.start)
+    }
+}
+
+/// Diagnostic severity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DiagnosticSeverity {
+    /// Reports an error
+    #[default]
+    Error,
+    /// Reports a warning
+    Warning,
+    /// Reports information
+    Information,
+    /// Reports a hint
+    Hint,
+}
+
+impl Serialize for DiagnosticSeverity {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8 + 1)
+    }
+}
+
+impl<'de> Deserialize<'de> for DiagnosticSeverity {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match u8::deserialize(deserializer)? {
+            2 => Self::Warning,
+            3 => Self::Information,
+            4 => Self::Hint,
+            _ => Self::Error,
+        })
+    }
+}
+
+/// A problem reported by the server
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Affected range
+    pub range: Range,
+    /// Severity, `Error` when the server omits it
+    #[serde(default)]
+    pub severity: DiagnosticSeverity,
+    /// Message
+    pub message: String,
+    /// Tool that produced it, e.g. `rustc`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// A text replacement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextEdit {
+    /// Replaced range
+    pub range: Range,
+    /// Replacement text
+    pub new_text: String,
+}
+
+/// A completion candidate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionItem {
+    /// Text shown in the list
+    pub label: String,
+    /// Extra information, such as a type signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Text inserted instead of the label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub insert_text: Option<String>,
+    /// Edit applied instead of inserting at the cursor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_edit: Option<TextEdit>,
+    /// Text used when filtering, instead of the label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter_text: Option<String>,
+}
+
+impl CompletionItem {
+    /// Create an item inserting its label
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            detail: None,
+            insert_text: None,
+            text_edit: None,
+            filter_text: None,
+        }
+    }
+
+    /// Text to insert when accepted
+    pub fn text(&self) -> &str {
+        self.text_edit
+            .as_ref()
+            .map(|edit| edit.new_text.as_str())
+            .or(self.insert_text.as_deref())
+            .unwrap_or(&self.label)
+    }
+
+    /// Text matched against what was typed
+    pub fn filter(&self) -> &str {
+        self.filter_text.as_deref().unwrap_or(&self.label)
+    }
+}
+
+/// Hover information
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hover {
+    /// Text to show, markdown or plain
+    pub contents: String,
+    /// Range the hover applies to
+    pub range: Option<Range>,
+}
+
+/// A location in a document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Location {
+    /// Document URI
+    pub uri: String,
+    /// Range in the document
+    pub range: Range,
+}
+
+/// One callable signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureInformation {
+    /// Full signature, e.g. `fn add(a: i32, b: i32) -> i32`
+    pub label: String,
+    /// Documentation
+    pub documentation: Option<String>,
+    /// Char ranges of the parameters within `label`
+    pub parameters: Vec<std::ops::Range<usize>>,
+}
+
+/// Signatures of the call at the cursor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureHelp {
+    /// Candidate signatures
+    pub signatures: Vec<SignatureInformation>,
+    /// Index of the active signature
+    pub active_signature: usize,
+    /// Index of the active parameter
+    pub active_parameter: Option<usize>,
+}
+
+impl SignatureHelp {
+    /// The active signature
+    pub fn active(&self) -> Option<&SignatureInformation> {
+        self.signatures
+            .get(self.active_signature)
+            .or(self.signatures.first())
+    }
+}
+
+/// Edits to one or more documents, e.g. from a rename
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkspaceEdit {
+    /// Edits per document URI
+    pub changes: Vec<(String, Vec<TextEdit>)>,
+}
+
+impl WorkspaceEdit {
+    /// Edits for one document
+    pub fn edits_for(&self, uri: &str) -> &[TextEdit] {
+        self.changes
+            .iter()
+            .find(|(u, _)| u == uri)
+            .map_or(&[], |(_, edits)| edits)
+    }
+}
+
+// =============================================================================
+// Decoding of results with several possible shapes
+// =============================================================================
+
+/// Text of `MarkupContent`, `MarkedString` or an array of them
+fn markup_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .map(markup_text)
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        Value::Object(map) => map
+            .get("value")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        _ => String::new(),
+    }
+}
+
+pub(super) fn parse_hover(value: Value) -> Option<Hover> {
+    let contents = markup_text(value.get("contents")?);
+    if contents.is_empty() {
+        return None;
+    }
+    let range = value
+        .get("range")
+        .and_then(|range| serde_json::from_value(range.clone()).ok());
+    Some(Hover { contents, range })
+}
+
+pub(super) fn parse_completion(value: Value) -> Vec<CompletionItem> {
+    let items = match value {
+        Value::Object(mut list) => list.remove("items").unwrap_or_default(),
+        other => other,
+    };
+    serde_json::from_value(items).unwrap_or_default()
+}
+
+/// `Location`, `Location[]` or `LocationLink[]`
+pub(super) fn parse_locations(value: Value) -> Vec<Location> {
+    let values = match value {
+        Value::Array(values) => values,
+        Value::Null => Vec::new(),
+        other => vec![other],
+    };
+    values
+        .into_iter()
+        .filter_map(|value| {
+            if let Some(uri) = value.get("targetUri").and_then(Value::as_str) {
+                let range = value
+                    .get("targetSelectionRange")
+                    .or(value.get("targetRange"))?;
+                return Some(Location {
+                    uri: uri.to_string(),
+                    range: serde_json::from_value(range.clone()).ok()?,
+                });
+            }
+            serde_json::from_value(value).ok()
+        })
+        .collect()
+}
+
+pub(super) fn parse_signature_help(value: Value) -> Option<SignatureHelp> {
+    let signatures: Vec<SignatureInformation> = value
+        .get("signatures")?
+        .as_array()?
+        .iter()
+        .filter_map(|signature| {
+            let label = signature.get("label")?.as_str()?.to_string();
+            let parameters = signature
+                .get("parameters")
+                .and_then(Value::as_array)
+                .map(|params| {
+                    params
+                        .iter()
+                        .filter_map(|param| parameter_range(&label, param.get("label")?))
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some(SignatureInformation {
+                documentation: signature.get("documentation").map(markup_text),
+                label,
+                parameters,
+            })
+        })
+        .collect();
+    if signatures.is_empty() {
+        return None;
+    }
+    let index = |key: &str| value.get(key).and_then(Value::as_u64).map(|i| i as usize);
+    let active_signature = index("activeSignature").unwrap_or(0);
+    let active_parameter = signatures
+        .get(active_signature)
+        .and(index("activeParameter"));
+    Some(SignatureHelp {
+        signatures,
+        active_signature,
+        active_parameter,
+    })
+}
+
+/// Char range of a parameter label (a substring or UTF-16 offsets) in a signature
+fn parameter_range(signature: &str, label: &Value) -> Option<std::ops::Range<usize>> {
+    let char_of_utf16 = |offset: u64| {
+        let mut units = 0;
+        signature
+            .chars()
+            .take_while(|ch| {
+                units += ch.len_utf16() as u64;
+                units <= offset
+            })
+            .count()
+    };
+    match label {
+        Value::String(text) => {
+            let byte = signature.find(text.as_str())?;
+            let start = signature[..byte].chars().count();
+            Some(start..start + text.chars().count())
+        }
+        Value::Array(bounds) => {
+            let start = bounds.first()?.as_u64()?;
+            let end = bounds.get(1)?.as_u64()?;
+            Some(char_of_utf16(start)..char_of_utf16(end))
+        }
+        _ => None,
+    }
+}
+
+pub(super) fn parse_text_edits(value: Value) -> Vec<TextEdit> {
+    serde_json::from_value(value).unwrap_or_default()
+}
+
+/// `changes` map or `documentChanges` array
+pub(super) fn parse_workspace_edit(value: Value) -> WorkspaceEdit {
+    let mut edit = WorkspaceEdit::default();
+    if let Some(changes) = value.get("changes").and_then(Value::as_object) {
+        for (uri, edits) in changes {
+            edit.changes
+                .push((uri.clone(), parse_text_edits(edits.clone())));
+        }
+    }
+    if let Some(changes) = value.get("documentChanges").and_then(Value::as_array) {
+        for change in changes {
+            let uri = change
+                .get("textDocument")
+                .and_then(|doc| doc.get("uri"))
+                .and_then(Value::as_str);
+            if let (Some(uri), Some(edits)) = (uri, change.get("edits")) {
+                edit.changes
+                    .push((uri.to_string(), parse_text_edits(edits.clone())));
+            }
+        }
+    }
+    edit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_position_utf16() {
+        let doc = Document::with_content("a😀b\nx");
+        let pos = Position::from_char_pos(&doc, (0, 2));
+        assert_eq!(pos, Position::new(0, 3));
+        assert_eq!(pos.to_char_pos(&doc), (0, 2));
+        assert_eq!(Position::new(5, 9).to_char_pos(&doc), (1, 1));
+    }
+
+    #[test]
+    fn test_range_of_change() {
+        let mut doc = Document::with_content("é😀\nabc").track_changes(true);
+        doc.replace(1..5, "z");
+        let change = &doc.take_changes()[0];
+        assert_eq!(
+            Range::of_change(change),
+            Range::new(Position::new(0, 1), Position::new(1, 2))
+        );
+    }
+
+    #[test]
+    fn test_parse_results() {
+        let hover = parse_hover(json!({"contents": {"kind": "markdown", "value": "fn x()"}}));
+        assert_eq!(hover.unwrap().contents, "fn x()");
+
+        let items = parse_completion(json!({"isIncomplete": false, "items": [{"label": "len"}]}));
+        assert_eq!(items, vec![CompletionItem::new("len")]);
+
+        let range =
+            json!({"start": {"line": 1, "character": 0}, "end": {"line": 1, "character": 3}});
+        let links = parse_locations(
+            json!([{"targetUri": "file:///a.rs", "targetRange": range, "targetSelectionRange": range}]),
+        );
+        assert_eq!(links[0].uri, "file:///a.rs");
+        assert_eq!(
+            parse_locations(json!({"uri": "file:///b.rs", "range": range})).len(),
+            1
+        );
+
+        let help = parse_signature_help(json!({
+            "signatures": [{"label": "add(a: i32, b: i32)", "parameters": [{"label": "a: i32"}, {"label": [12, 18]}]}],
+            "activeParameter": 1
+        }))
+        .unwrap();
+        assert_eq!(help.active().unwrap().parameters, vec![4..10, 12..18]);
+        assert_eq!(help.active_parameter, Some(1));
+
+        let edit = parse_workspace_edit(
+            json!({"changes": {"file:///a.rs": [{"range": range, "newText": "y"}]}}),
+        );
+        assert_eq!(edit.edits_for("file:///a.rs")[0].new_text, "y");
+        assert!(edit.edits_for("file:///b.rs").is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+//! JSON-RPC framing over byte streams
+//!
+//! Messages are JSON bodies preceded by a `Content-Length` header:
+//!
+//! ```text
+//! Content-Length: 52\r\n
+//! \r\n
+//! {"jsonrpc":"2.0","id":1,"method":"shutdown"}
+//! ```
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::sync::mpsc::Sender;
+
+use serde_json::Value;
+
+/// Frame a message with its header
+pub(super) fn encode(message: &Value) -> Vec<u8> {
+    let body = message.to_string();
+    let mut frame = format!("Content-Length: {}\r\n\r\n", body.len()).into_bytes();
+    frame.extend_from_slice(body.as_bytes());
+    frame
+}
+
+/// Read one framed message
+///
+/// Returns `Ok(None)` at end of stream. Bodies that are not valid JSON are
+/// skipped.
+pub(super) fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Value>> {
+    loop {
+        let mut length = None;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse::<usize>().ok();
+                }
+            }
+        }
+        let Some(length) = length else {
+            continue;
+        };
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        match serde_json::from_slice(&body) {
+            Ok(message) => return Ok(Some(message)),
+            Err(e) => crate::log_warn!("Skipping malformed LSP message: {}", e),
+        }
+    }
+}
+
+/// Forward messages from a stream to a channel on a background thread
+///
+/// The thread ends at end of stream or when the receiver is dropped.
+pub(super) fn spawn_reader(reader: impl Read + Send + 'static, tx: Sender<Value>) {
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(reader);
+        while let Ok(Some(message)) = read_message(&mut reader) {
+            if tx.send(message).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Handler of an in-process server: receives each client message and
+/// returns the messages to send back
+pub(super) type Handler = Box<dyn FnMut(Value) -> Vec<Value> + Send>;
+
+/// Writer that hands complete frames to an in-process handler
+pub(super) struct HandlerWriter {
+    buffer: Vec<u8>,
+    handler: Handler,
+    tx: Sender<Value>,
+}
+
+impl HandlerWriter {
+    pub(super) fn new(handler: Handler, tx: Sender<Value>) -> Self {
+        Self {
+            buffer: Vec::new(),
+            handler,
+            tx,
+        }
+    }
+}
+
+impl Write for HandlerWriter {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(bytes);
+        loop {
+            let mut reader = self.buffer.as_slice();
+            let Ok(Some(message)) = read_message(&mut reader) else {
+                break;
+            };
+            let consumed = self.buffer.len() - reader.len();
+            self.buffer.drain(..consumed);
+            for reply in (self.handler)(message) {
+                self.tx
+                    .send(reply)
+                    .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+            }
+        }
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_round_trip() {
+        let mut bytes = encode(&json!({"id": 1, "method": "a"}));
+        bytes.extend(b"Content-Length: 2\r\nContent-Type: x\r\n\r\n{}");
+        let mut reader = bytes.as_slice();
+        assert_eq!(read_message(&mut reader).unwrap().unwrap()["method"], "a");
+        assert_eq!(read_message(&mut reader).unwrap().unwrap(), json!({}));
+        assert!(read_message(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_handler_writer_splits_frames() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut writer = HandlerWriter::new(Box::new(|msg| vec![msg]), tx);
+        let frame = encode(&json!({"id": 7}));
+        writer.write_all(&frame[..10]).unwrap();
+        assert!(rx.try_recv().is_err());
+        writer.write_all(&frame[10..]).unwrap();
+        assert_eq!(rx.try_recv().unwrap(), json!({"id": 7}));
+    }
+}
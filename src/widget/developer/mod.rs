@@ -11,6 +11,9 @@
 //! |--------|-------------|-------------|
 //! | [`CodeEditor`] | Code editor with syntax highlighting | [`code_editor()`] |
 //! | [`Terminal`] | Embedded terminal emulator | [`terminal()`] |
+//! | `LspClient` | Language server client for CodeEditor | `LspClient::spawn()` |
+//!
+//! *Requires `lsp` feature for LspClient*
 //!
 //! ## HTTP & API
 //!
@@ -45,8 +48,10 @@
 //! |--------|-------------|-------------|
 //! | `DiffViewer` | Diff/patch viewer | `diff_viewer()` |
 //! | `TreeSitterHighlighter` | Syntax highlighting | - |
+//! | `SyntaxTree` | Incremental parse tree for editors | - |
 //!
-//! *Requires `diff` feature for DiffViewer, `syntax-highlighting` for TreeSitterHighlighter*
+//! *Requires `diff` feature for DiffViewer, `syntax-highlighting` for TreeSitterHighlighter
+//! and SyntaxTree*
 //!
 //! ## Editor Modes
 //!
@@ -107,9 +112,13 @@ pub mod code_editor;
 #[cfg(feature = "diff")]
 pub mod diff;
 pub mod httpclient;
+#[cfg(feature = "lsp")]
+pub mod lsp;
 pub mod presentation;
 #[cfg(feature = "sysinfo")]
 pub mod procmon;
+#[cfg(feature = "syntax-highlighting")]
+pub mod syntax_tree;
 pub mod terminal;
 #[cfg(feature = "syntax-highlighting")]
 pub mod tree_sitter_highlight;
@@ -118,7 +127,8 @@ pub mod vim;
 // Re-exports for convenience
 pub use aistream::{ai_response, ai_stream, AiStream, StreamCursor, StreamStatus, TypingStyle};
 pub use code_editor::{
-    code_editor, BracketMatch, BracketPair, CodeEditor, EditorConfig, IndentStyle,
+    code_editor, BracketMatch, BracketPair, CodeEditor, EditorConfig, FoldRange, IndentStyle,
+    MinimapStyle,
 };
 #[cfg(feature = "diff")]
 pub use diff::{diff, diff_viewer, ChangeType, DiffColors, DiffLine, DiffMode, DiffViewer};
@@ -132,7 +142,12 @@ pub use presentation::{presentation, slide, Presentation, Slide, SlideAlign, Tra
 pub use procmon::{
     htop, process_monitor, ProcColors, ProcessInfo, ProcessMonitor, ProcessSort, ProcessView,
 };
+#[cfg(feature = "syntax-highlighting")]
+pub use syntax_tree::SyntaxTree;
 pub use terminal::{terminal, CursorStyle, TermCell, TermLine, Terminal, TerminalAction};
 #[cfg(feature = "syntax-highlighting")]
 pub use tree_sitter_highlight::TreeSitterHighlighter;
-pub use vim::{vim_state, VimAction, VimCommandResult, VimMode, VimMotion, VimState};
+pub use vim::{
+    vim_state, VimAction, VimBuffer, VimCommandResult, VimMode, VimMotion, VimSelection,
+    VimSelectionKind, VimState, VimTextObject,
+};
@@ -0,0 +1,780 @@
+//! Incremental tree-sitter parsing for editors
+//!
+//! [`SyntaxTree`] keeps one parse tree per [`Document`] and updates it from
+//! [`DocumentChange`]s, so an edit only reparses what it touched. Highlights
+//! are derived for the visible lines only, which keeps constructs spanning
+//! many lines (block comments, raw strings, fenced code) correct without
+//! walking the whole file every frame.
+//!
+//! Injected languages are highlighted in their own layer on top of the host:
+//! - The grammar's own injection queries, such as code fences in Markdown
+//!   and `<script>` / `<style>` in HTML
+//! - SQL inside Rust string literals that start with an SQL statement
+//!
+//! This module is only available when the `syntax-highlighting` feature is
+//! enabled.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use tree_sitter::{InputEdit, Node, Parser, Point, Query, QueryCursor, StreamingIterator, Tree};
+
+use super::tree_sitter_highlight::{
+    highlight_name_is_bold, highlight_name_is_italic, highlight_name_to_color, HIGHLIGHT_NAMES,
+};
+use crate::utils::{Document, DocumentChange};
+use crate::widget::syntax::{HighlightSpan, Language, SyntaxTheme};
+
+/// Injections nested deeper than this are not parsed
+const MAX_INJECTION_DEPTH: usize = 3;
+
+/// Rust strings whose content starts with an SQL statement
+const RUST_SQL_INJECTIONS: &str = r#"
+((string_literal (string_content) @injection.content)
+  (#match? @injection.content "^\\s*(?i:select|insert|update|delete|with|create|alter|drop)\\s")
+  (#set! injection.language "sql"))
+
+((raw_string_literal (string_content) @injection.content)
+  (#match? @injection.content "^\\s*(?i:select|insert|update|delete|with|create|alter|drop)\\s")
+  (#set! injection.language "sql"))
+"#;
+
+/// A language's parser and queries
+struct Grammar {
+    name: &'static str,
+    language: tree_sitter::Language,
+    highlights: Query,
+    injections: Option<Query>,
+    /// Highlight name for each capture of `highlights`
+    highlight_names: Vec<Option<&'static str>>,
+}
+
+impl Grammar {
+    fn new(
+        name: &'static str,
+        language: tree_sitter::Language,
+        highlights: &str,
+        injections: &str,
+    ) -> Option<Self> {
+        let highlights = match Query::new(&language, highlights) {
+            Ok(query) => query,
+            Err(e) => {
+                crate::log_warn!("Invalid {} highlight query: {}", name, e);
+                return None;
+            }
+        };
+        let injections = if injections.trim().is_empty() {
+            None
+        } else {
+            match Query::new(&language, injections) {
+                Ok(query) => Some(query),
+                Err(e) => {
+                    crate::log_warn!("Invalid {} injection query: {}", name, e);
+                    None
+                }
+            }
+        };
+        let highlight_names = highlights
+            .capture_names()
+            .iter()
+            .map(|capture| highlight_name(capture))
+            .collect();
+
+        Some(Self {
+            name,
+            language,
+            highlights,
+            injections,
+            highlight_names,
+        })
+    }
+}
+
+/// Most specific highlight name matching a capture name
+///
+/// `keyword.control.rust` maps to `keyword`, `function.method.call` to
+/// `function.method`. Internal captures like `_name` or `injection.content`
+/// have no highlight.
+fn highlight_name(capture: &str) -> Option<&'static str> {
+    if capture.starts_with('_')
+        || capture.starts_with("injection.")
+        || capture.starts_with("local.")
+    {
+        return None;
+    }
+    HIGHLIGHT_NAMES
+        .iter()
+        .copied()
+        .filter(|name| {
+            capture == *name
+                || capture
+                    .strip_prefix(name)
+                    .is_some_and(|rest| rest.starts_with('.'))
+        })
+        .max_by_key(|name| name.len())
+}
+
+/// Grammars by name
+fn grammars() -> &'static HashMap<&'static str, Grammar> {
+    static GRAMMARS: OnceLock<HashMap<&'static str, Grammar>> = OnceLock::new();
+    GRAMMARS.get_or_init(|| {
+        let rust_injections = format!(
+            "{}\n{}",
+            tree_sitter_rust::INJECTIONS_QUERY,
+            RUST_SQL_INJECTIONS
+        );
+        let grammars = [
+            Grammar::new(
+                "rust",
+                tree_sitter_rust::LANGUAGE.into(),
+                tree_sitter_rust::HIGHLIGHTS_QUERY,
+                &rust_injections,
+            ),
+            Grammar::new(
+                "python",
+                tree_sitter_python::LANGUAGE.into(),
+                tree_sitter_python::HIGHLIGHTS_QUERY,
+                "",
+            ),
+            Grammar::new(
+                "javascript",
+                tree_sitter_javascript::LANGUAGE.into(),
+                tree_sitter_javascript::HIGHLIGHT_QUERY,
+                tree_sitter_javascript::INJECTIONS_QUERY,
+            ),
+            Grammar::new(
+                "json",
+                tree_sitter_json::LANGUAGE.into(),
+                tree_sitter_json::HIGHLIGHTS_QUERY,
+                "",
+            ),
+            Grammar::new(
+                "go",
+                tree_sitter_go::LANGUAGE.into(),
+                tree_sitter_go::HIGHLIGHTS_QUERY,
+                "",
+            ),
+            Grammar::new(
+                "bash",
+                tree_sitter_bash::LANGUAGE.into(),
+                tree_sitter_bash::HIGHLIGHT_QUERY,
+                "",
+            ),
+            Grammar::new(
+                "html",
+                tree_sitter_html::LANGUAGE.into(),
+                tree_sitter_html::HIGHLIGHTS_QUERY,
+                tree_sitter_html::INJECTIONS_QUERY,
+            ),
+            Grammar::new(
+                "css",
+                tree_sitter_css::LANGUAGE.into(),
+                tree_sitter_css::HIGHLIGHTS_QUERY,
+                "",
+            ),
+            Grammar::new(
+                "toml",
+                tree_sitter_toml_ng::LANGUAGE.into(),
+                tree_sitter_toml_ng::HIGHLIGHTS_QUERY,
+                "",
+            ),
+            Grammar::new(
+                "yaml",
+                tree_sitter_yaml::LANGUAGE.into(),
+                tree_sitter_yaml::HIGHLIGHTS_QUERY,
+                "",
+            ),
+            Grammar::new(
+                "sql",
+                tree_sitter_sequel::LANGUAGE.into(),
+                tree_sitter_sequel::HIGHLIGHTS_QUERY,
+                "",
+            ),
+            Grammar::new(
+                "markdown",
+                tree_sitter_md::LANGUAGE.into(),
+                tree_sitter_md::HIGHLIGHT_QUERY_BLOCK,
+                tree_sitter_md::INJECTION_QUERY_BLOCK,
+            ),
+            Grammar::new(
+                "markdown_inline",
+                tree_sitter_md::INLINE_LANGUAGE.into(),
+                tree_sitter_md::HIGHLIGHT_QUERY_INLINE,
+                tree_sitter_md::INJECTION_QUERY_INLINE,
+            ),
+        ];
+        grammars
+            .into_iter()
+            .flatten()
+            .map(|grammar| (grammar.name, grammar))
+            .collect()
+    })
+}
+
+/// Grammar name for an editor language
+fn grammar_name(language: Language) -> Option<&'static str> {
+    match language {
+        Language::None => None,
+        Language::Rust => Some("rust"),
+        Language::Python => Some("python"),
+        Language::JavaScript => Some("javascript"),
+        Language::Json => Some("json"),
+        Language::Toml => Some("toml"),
+        Language::Yaml => Some("yaml"),
+        Language::Markdown => Some("markdown"),
+        Language::Shell => Some("bash"),
+        Language::Sql => Some("sql"),
+        Language::Html => Some("html"),
+        Language::Css => Some("css"),
+        Language::Go => Some("go"),
+    }
+}
+
+/// Grammar for an injection language name
+///
+/// Accepts grammar names, file extensions (`rs`, `py`) and a few common
+/// aliases used in Markdown code fences.
+fn resolve_grammar(name: &str) -> Option<&'static Grammar> {
+    let name = name.trim().to_lowercase();
+    let grammars = grammars();
+    if let Some(grammar) = grammars.get(name.as_str()) {
+        return Some(grammar);
+    }
+    let name = match name.as_str() {
+        "shell" | "console" => Some("bash"),
+        "typescript" => Some("javascript"),
+        "golang" => Some("go"),
+        other => grammar_name(Language::from_extension(other)),
+    }?;
+    grammars.get(name)
+}
+
+/// Highlights computed for a range of lines
+#[derive(Debug)]
+struct HighlightCache {
+    version: u64,
+    lines: Range<usize>,
+    spans: Vec<Vec<HighlightSpan>>,
+}
+
+/// Persistent, incrementally updated syntax tree for a document
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use revue::utils::Document;
+/// use revue::widget::{Language, SyntaxTree};
+///
+/// let mut doc = Document::with_content("/* a\nb */ fn main() {}").track_changes(true);
+/// let mut syntax = SyntaxTree::new(Language::Rust).unwrap();
+/// syntax.update(&mut doc);
+///
+/// doc.insert(0, "//");
+/// syntax.update(&mut doc); // Reparses only around the edit
+///
+/// let spans = syntax.highlight_lines(&doc, 0..2);
+/// ```
+pub struct SyntaxTree {
+    grammar: &'static Grammar,
+    language: Language,
+    parser: Parser,
+    tree: Option<Tree>,
+    theme: SyntaxTheme,
+    cache: RefCell<Option<HighlightCache>>,
+}
+
+impl SyntaxTree {
+    /// Create an empty tree for a language
+    ///
+    /// Returns `None` when there is no tree-sitter grammar for it.
+    pub fn new(language: Language) -> Option<Self> {
+        let grammar = grammars().get(grammar_name(language)?)?;
+        let mut parser = Parser::new();
+        parser.set_language(&grammar.language).ok()?;
+        Some(Self {
+            grammar,
+            language,
+            parser,
+            tree: None,
+            theme: SyntaxTheme::default(),
+            cache: RefCell::new(None),
+        })
+    }
+
+    /// Check if a language has a tree-sitter grammar
+    pub fn is_supported(language: Language) -> bool {
+        grammar_name(language).is_some_and(|name| grammars().contains_key(name))
+    }
+
+    /// Set the theme
+    pub fn theme(mut self, theme: SyntaxTheme) -> Self {
+        self.set_theme(theme);
+        self
+    }
+
+    /// Set the theme (mutable)
+    pub fn set_theme(&mut self, theme: SyntaxTheme) {
+        self.theme = theme;
+        self.cache.replace(None);
+    }
+
+    /// Language of the document
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Current parse tree, if the document has been parsed
+    pub fn tree(&self) -> Option<&Tree> {
+        self.tree.as_ref()
+    }
+
+    /// Apply a change to the tree ahead of the next [`parse`](Self::parse)
+    ///
+    /// Changes must be applied in the order they were made.
+    pub fn edit(&mut self, change: &DocumentChange) {
+        let Some(tree) = &mut self.tree else {
+            return;
+        };
+        let start_position = Point::new(change.start_position.0, change.start_byte_column);
+        tree.edit(&InputEdit {
+            start_byte: change.start_byte,
+            old_end_byte: change.old_end_byte,
+            new_end_byte: change.new_end_byte,
+            start_position,
+            old_end_position: end_point(start_position, &change.removed),
+            new_end_position: end_point(start_position, &change.text),
+        });
+    }
+
+    /// Parse the document, reusing the edited tree where possible
+    pub fn parse(&mut self, doc: &Document) {
+        let tree = self.parser.parse_with_options(
+            &mut |byte, _| doc.bytes_at(byte),
+            self.tree.as_ref(),
+            None,
+        );
+        if tree.is_none() {
+            crate::log_warn!("Failed to parse {} document", self.grammar.name);
+        }
+        self.tree = tree;
+        self.cache.replace(None);
+    }
+
+    /// Apply the document's pending changes and reparse
+    ///
+    /// Parses from scratch if change tracking was off or the tree is empty.
+    pub fn update(&mut self, doc: &mut Document) {
+        let changes = doc.take_changes();
+        self.apply(doc, &changes);
+    }
+
+    /// Apply changes already taken from the document and reparse
+    ///
+    /// For callers that share the document's changes with other consumers.
+    pub fn apply(&mut self, doc: &Document, changes: &[DocumentChange]) {
+        if self.tree.is_some() && changes.is_empty() {
+            return;
+        }
+        for change in changes {
+            self.edit(change);
+        }
+        self.parse(doc);
+    }
+
+    /// Highlight spans for a range of lines
+    ///
+    /// Returns one `Vec` per line with char columns, like
+    /// [`SyntaxHighlighter::highlight_line`](crate::widget::syntax::SyntaxHighlighter::highlight_line).
+    /// Only the requested lines are queried, and the result is cached until
+    /// the document or the range changes.
+    pub fn highlight_lines(&self, doc: &Document, lines: Range<usize>) -> Vec<Vec<HighlightSpan>> {
+        let lines = lines.start.min(doc.len_lines())..lines.end.min(doc.len_lines());
+        if let Some(cache) = &*self.cache.borrow() {
+            if cache.version == doc.version() && cache.lines == lines {
+                return cache.spans.clone();
+            }
+        }
+
+        let spans = self.compute_highlights(doc, lines.clone());
+        self.cache.replace(Some(HighlightCache {
+            version: doc.version(),
+            lines,
+            spans: spans.clone(),
+        }));
+        spans
+    }
+
+    /// First and last line of every named node spanning several lines
+    ///
+    /// Sorted by first line, with only the longest span kept per line. Used
+    /// for folding, where each span is a candidate region.
+    pub fn multiline_spans(&self) -> Vec<(usize, usize)> {
+        let Some(tree) = &self.tree else {
+            return Vec::new();
+        };
+        let mut spans: Vec<(usize, usize)> = Vec::new();
+        let mut cursor = tree.walk();
+        loop {
+            let node = cursor.node();
+            let (first, last) = (node.start_position().row, node.end_position().row);
+            // Single-line nodes have no multi-line descendants
+            let descend = last > first;
+            if descend && node.is_named() && node.parent().is_some() {
+                spans.push((first, last));
+            }
+            if descend && cursor.goto_first_child() {
+                continue;
+            }
+            while !cursor.goto_next_sibling() {
+                if !cursor.goto_parent() {
+                    spans.sort_by_key(|&(first, last)| (first, std::cmp::Reverse(last)));
+                    spans.dedup_by_key(|span| span.0);
+                    return spans;
+                }
+            }
+        }
+    }
+
+    fn compute_highlights(&self, doc: &Document, lines: Range<usize>) -> Vec<Vec<HighlightSpan>> {
+        let Some(tree) = &self.tree else {
+            return vec![Vec::new(); lines.len()];
+        };
+        let start_byte = doc.char_to_byte(doc.line_to_char(lines.start));
+        let end_byte = if lines.end < doc.len_lines() {
+            doc.char_to_byte(doc.line_to_char(lines.end))
+        } else {
+            doc.len_bytes()
+        };
+
+        let mut paint = Paint {
+            offset: start_byte,
+            names: vec![None; end_byte - start_byte],
+        };
+        paint_layer(doc, self.grammar, tree.root_node(), &mut paint, 0);
+
+        let mut result = Vec::with_capacity(lines.len());
+        let mut byte = start_byte;
+        for line in lines {
+            let text = doc.line(line);
+            result.push(self.line_spans(&text, &paint.names[byte - start_byte..]));
+            byte += text.len() + 1;
+        }
+        result
+    }
+
+    /// Merge a line's per-byte highlight names into char-column spans
+    fn line_spans(&self, text: &str, names: &[Option<&'static str>]) -> Vec<HighlightSpan> {
+        let mut spans = Vec::new();
+        let mut current: Option<(usize, &'static str)> = None;
+        let mut len = 0;
+        for (col, (byte, _)) in text.char_indices().enumerate() {
+            len = col + 1;
+            let name = names.get(byte).copied().flatten();
+            match (current, name) {
+                (Some((_, prev)), Some(name)) if prev == name => {}
+                _ => {
+                    if let Some((start, prev)) = current.take() {
+                        spans.push(self.span(start, col, prev));
+                    }
+                    current = name.map(|name| (col, name));
+                }
+            }
+        }
+        if let Some((start, name)) = current {
+            spans.push(self.span(start, len, name));
+        }
+        spans
+    }
+
+    fn span(&self, start: usize, end: usize, name: &str) -> HighlightSpan {
+        let mut span = HighlightSpan::new(start, end, highlight_name_to_color(name, &self.theme));
+        if highlight_name_is_bold(name) {
+            span = span.bold();
+        }
+        if highlight_name_is_italic(name) {
+            span = span.italic();
+        }
+        span
+    }
+}
+
+impl std::fmt::Debug for SyntaxTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SyntaxTree")
+            .field("language", &self.language)
+            .field("parsed", &self.tree.is_some())
+            .finish()
+    }
+}
+
+/// Highlight name for each byte of the highlighted range
+struct Paint {
+    offset: usize,
+    names: Vec<Option<&'static str>>,
+}
+
+impl Paint {
+    fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.names.len()
+    }
+
+    fn fill(&mut self, range: Range<usize>, name: &'static str) {
+        let start = range.start.max(self.offset) - self.offset;
+        let end = range
+            .end
+            .min(self.offset + self.names.len())
+            .saturating_sub(self.offset);
+        if start < end {
+            self.names[start..end].fill(Some(name));
+        }
+    }
+}
+
+/// Paint one language layer, then its injections on top
+fn paint_layer(doc: &Document, grammar: &Grammar, root: Node, paint: &mut Paint, depth: usize) {
+    let text = |node: Node| std::iter::once(node_text(doc, node).into_bytes());
+
+    // Inner nodes paint over outer ones; for the same node the first pattern wins
+    let mut captures = Vec::new();
+    let mut cursor = QueryCursor::new();
+    cursor.set_byte_range(paint.range());
+    let mut matches = cursor.captures(&grammar.highlights, root, text);
+    while let Some((m, idx)) = matches.next() {
+        let capture = m.captures[*idx];
+        if let Some(name) = grammar.highlight_names[capture.index as usize] {
+            let node = capture.node;
+            captures.push((node.start_byte(), node.end_byte(), m.pattern_index, name));
+        }
+    }
+    captures.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(b.2.cmp(&a.2)));
+    for (start, end, _, name) in captures {
+        paint.fill(start..end, name);
+    }
+
+    let Some(injections) = &grammar.injections else {
+        return;
+    };
+    if depth >= MAX_INJECTION_DEPTH {
+        return;
+    }
+
+    let mut layers = Vec::new();
+    let mut cursor = QueryCursor::new();
+    cursor.set_byte_range(paint.range());
+    let mut matches = cursor.matches(injections, root, text);
+    while let Some(m) = matches.next() {
+        let mut content = None;
+        let mut language = None;
+        for capture in m.captures {
+            match injections.capture_names()[capture.index as usize] {
+                "injection.content" => content = Some(capture.node),
+                "injection.language" => language = Some(node_text(doc, capture.node)),
+                _ => {}
+            }
+        }
+        let properties = injections.property_settings(m.pattern_index);
+        let language = language.or_else(|| {
+            properties
+                .iter()
+                .find(|p| &*p.key == "injection.language")
+                .and_then(|p| p.value.as_deref().map(str::to_string))
+        });
+        let include_children = properties
+            .iter()
+            .any(|p| &*p.key == "injection.include-children");
+        let (Some(content), Some(language)) = (content, language) else {
+            continue;
+        };
+        match resolve_grammar(&language) {
+            // Same-language injections (e.g. Rust macro bodies) are already
+            // covered by the host layer
+            Some(injected) if injected.name != grammar.name => {
+                layers.push((injected, content_ranges(content, include_children)));
+            }
+            _ => {}
+        }
+    }
+
+    for (injected, ranges) in layers {
+        if ranges.is_empty() {
+            continue;
+        }
+        let mut parser = Parser::new();
+        if parser.set_language(&injected.language).is_err()
+            || parser.set_included_ranges(&ranges).is_err()
+        {
+            continue;
+        }
+        let tree = parser.parse_with_options(&mut |byte, _| doc.bytes_at(byte), None, None);
+        if let Some(tree) = tree {
+            paint_layer(doc, injected, tree.root_node(), paint, depth + 1);
+        }
+    }
+}
+
+/// A node's range with its named children cut out
+///
+/// Named children of an injected node belong to the host language, like the
+/// block quote markers inside a Markdown paragraph.
+fn content_ranges(node: Node, include_children: bool) -> Vec<tree_sitter::Range> {
+    if include_children {
+        return vec![node.range()];
+    }
+    let mut ranges = Vec::new();
+    let mut start_byte = node.start_byte();
+    let mut start_point = node.start_position();
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if child.start_byte() > start_byte {
+            ranges.push(tree_sitter::Range {
+                start_byte,
+                end_byte: child.start_byte(),
+                start_point,
+                end_point: child.start_position(),
+            });
+        }
+        start_byte = child.end_byte();
+        start_point = child.end_position();
+    }
+    if node.end_byte() > start_byte {
+        ranges.push(tree_sitter::Range {
+            start_byte,
+            end_byte: node.end_byte(),
+            start_point,
+            end_point: node.end_position(),
+        });
+    }
+    ranges
+}
+
+fn node_text(doc: &Document, node: Node) -> String {
+    doc.slice(doc.byte_to_char(node.start_byte())..doc.byte_to_char(node.end_byte()))
+}
+
+/// Point reached by writing `text` from `start`, with byte columns
+fn end_point(start: Point, text: &str) -> Point {
+    match text.rfind('\n') {
+        Some(nl) => Point::new(start.row + text.matches('\n').count(), text.len() - nl - 1),
+        None => Point::new(start.row, start.column + text.len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::Color;
+
+    fn highlighted(spans: &[HighlightSpan], col: usize) -> Option<Color> {
+        spans
+            .iter()
+            .find(|s| col >= s.start && col < s.end)
+            .map(|s| s.fg)
+    }
+
+    fn parsed(language: Language, text: &str) -> (SyntaxTree, Document) {
+        let mut doc = Document::with_content(text).track_changes(true);
+        let mut syntax = SyntaxTree::new(language).unwrap();
+        syntax.update(&mut doc);
+        (syntax, doc)
+    }
+
+    #[test]
+    fn test_block_comment_spans_lines() {
+        let (syntax, doc) = parsed(Language::Rust, "/* start\nlet x = 1;\nend */\nlet y = 2;");
+        let theme = SyntaxTheme::default();
+        let spans = syntax.highlight_lines(&doc, 1..4);
+        assert_eq!(spans.len(), 3);
+        assert_eq!(highlighted(&spans[0], 0), Some(theme.comment));
+        assert_eq!(highlighted(&spans[1], 0), Some(theme.comment));
+        assert_eq!(highlighted(&spans[2], 0), Some(theme.keyword));
+    }
+
+    #[test]
+    fn test_incremental_edit() {
+        let (mut syntax, mut doc) = parsed(Language::Rust, "let a = 1;\nlet b = 2; */");
+        let theme = SyntaxTheme::default();
+        assert_eq!(
+            highlighted(&syntax.highlight_lines(&doc, 1..2)[0], 0),
+            Some(theme.keyword)
+        );
+
+        doc.insert(0, "/*");
+        syntax.update(&mut doc);
+        assert_eq!(
+            highlighted(&syntax.highlight_lines(&doc, 1..2)[0], 0),
+            Some(theme.comment)
+        );
+
+        doc.undo();
+        syntax.update(&mut doc);
+        assert_eq!(
+            highlighted(&syntax.highlight_lines(&doc, 1..2)[0], 0),
+            Some(theme.keyword)
+        );
+        assert_eq!(
+            syntax.tree().unwrap().root_node().to_sexp(),
+            parsed(Language::Rust, &doc.text())
+                .0
+                .tree()
+                .unwrap()
+                .root_node()
+                .to_sexp()
+        );
+    }
+
+    #[test]
+    fn test_sql_in_rust_string() {
+        let (syntax, doc) = parsed(
+            Language::Rust,
+            "let q = \"SELECT id FROM users\";\nlet s = \"selection\";",
+        );
+        let theme = SyntaxTheme::default();
+        let spans = syntax.highlight_lines(&doc, 0..2);
+        // `SELECT` is a keyword inside the injected SQL
+        assert_eq!(highlighted(&spans[0], 9), Some(theme.keyword));
+        // Not a statement, so the whole string stays a string
+        assert_eq!(highlighted(&spans[1], 9), Some(theme.string));
+    }
+
+    #[test]
+    fn test_multiline_spans() {
+        let (syntax, _) = parsed(
+            Language::Rust,
+            "fn main() {\n    if x {\n        y();\n    }\n}\nfn z() {}",
+        );
+        assert_eq!(syntax.multiline_spans(), vec![(0, 4), (1, 3)]);
+    }
+
+    #[test]
+    fn test_markdown_code_fence() {
+        let (syntax, doc) = parsed(
+            Language::Markdown,
+            "# Title\n\n```rust\nfn main() {}\n```\n",
+        );
+        let theme = SyntaxTheme::default();
+        let spans = syntax.highlight_lines(&doc, 3..4);
+        assert_eq!(highlighted(&spans[0], 0), Some(theme.keyword));
+        assert_eq!(highlighted(&spans[0], 3), Some(theme.function));
+    }
+
+    #[test]
+    fn test_highlight_name() {
+        assert_eq!(highlight_name("keyword.control.rust"), Some("keyword"));
+        assert_eq!(
+            highlight_name("function.method.call"),
+            Some("function.method")
+        );
+        assert_eq!(highlight_name("injection.content"), None);
+        assert_eq!(highlight_name("keywords"), None);
+    }
+
+    #[test]
+    fn test_resolve_grammar() {
+        assert_eq!(resolve_grammar("rs").map(|g| g.name), Some("rust"));
+        assert_eq!(resolve_grammar("Python").map(|g| g.name), Some("python"));
+        assert_eq!(resolve_grammar("shell").map(|g| g.name), Some("bash"));
+        assert!(resolve_grammar("brainfuck").is_none());
+    }
+}
@@ -83,10 +83,13 @@ mod tests {
 }
 
 use super::ansi::AnsiParser;
+use super::emulator::{Emulator, EmulatorView};
 use super::types::{CursorStyle, TermCell, TermLine, TerminalAction};
-use crate::event::{Key, KeyEvent};
+use crate::event::{Key, KeyEvent, MouseEvent, MouseEventKind};
+use crate::layout::Rect;
 use crate::render::Cell;
 use crate::style::Color;
+use crate::utils::vt::{encode_key, encode_mouse, VtScreen};
 use crate::widget::theme::{DARK_BG, EDITOR_BG, MUTED_TEXT, SECONDARY_TEXT, SEPARATOR_COLOR};
 use crate::widget::traits::{RenderContext, View, WidgetProps};
 use crate::{impl_props_builders, impl_styled_view};
@@ -127,6 +130,8 @@ pub struct Terminal {
     history_pos: usize,
     /// Is focused
     focused: bool,
+    /// VT emulation state, when created with `emulator` or `spawn`
+    emulator: Option<Emulator>,
     /// CSS styling properties (id, classes)
     props: WidgetProps,
 }
@@ -160,13 +165,55 @@ impl Terminal {
             history: Vec::new(),
             history_pos: 0,
             focused: false,
+            emulator: None,
             props: WidgetProps::new(),
         }
     }
 
+    /// Create a terminal that emulates a VT100/xterm screen
+    ///
+    /// Output passed to [`feed`](Self::feed) or [`write`](Self::write) is
+    /// interpreted as raw terminal output (cursor addressing, scroll regions,
+    /// alternate screen, ...) and keys are encoded as an xterm would send
+    /// them. Input not sent to a spawned process is available from
+    /// [`take_input`](Self::take_input).
+    pub fn emulator(width: u16, height: u16) -> Self {
+        let mut terminal = Self::new(width, height);
+        terminal.emulator = Some(Emulator::new(
+            terminal.width,
+            terminal.height,
+            terminal.max_scrollback,
+        ));
+        terminal
+    }
+
+    /// Run a command on a pseudo-terminal and display it
+    ///
+    /// The terminal is in emulator mode, with keys, pastes and mouse reports
+    /// sent to the process. Call [`poll`](Self::poll) regularly (e.g. on
+    /// every tick) to read its output and follow layout size changes.
+    ///
+    /// ```rust,ignore
+    /// let shell = Terminal::spawn(Command::new("bash"), 80, 24)?;
+    /// ```
+    #[cfg(all(unix, feature = "pty"))]
+    pub fn spawn(command: std::process::Command, width: u16, height: u16) -> std::io::Result<Self> {
+        let mut terminal = Self::new(width, height);
+        terminal.emulator = Some(Emulator::spawn(
+            command,
+            terminal.width,
+            terminal.height,
+            terminal.max_scrollback,
+        )?);
+        Ok(terminal)
+    }
+
     /// Set maximum scrollback lines
     pub fn max_scrollback(mut self, lines: usize) -> Self {
         self.max_scrollback = lines;
+        if let Some(emulator) = self.emulator.take() {
+            self.emulator = Some(emulator.scrollback_limit(lines));
+        }
         self
     }
 
@@ -203,8 +250,19 @@ impl Terminal {
     }
 
     /// Get title
+    ///
+    /// In emulator mode a title set by the program (OSC 0/2) takes precedence.
     pub fn get_title(&self) -> Option<&str> {
-        self.title.as_deref()
+        self.emulator
+            .as_ref()
+            .map(|emulator| emulator.screen.title())
+            .filter(|title| !title.is_empty())
+            .or(self.title.as_deref())
+    }
+
+    /// Emulated screen, in emulator mode
+    pub fn screen(&self) -> Option<&VtScreen> {
+        self.emulator.as_ref().map(|emulator| &emulator.screen)
     }
 
     /// Focus the terminal
@@ -224,11 +282,72 @@ impl Terminal {
 
     /// Write text to terminal (with ANSI support)
     pub fn write(&mut self, text: &str) {
+        if self.emulator.is_some() {
+            self.feed(text.as_bytes());
+            return;
+        }
         for ch in text.chars() {
             self.write_char(ch);
         }
     }
 
+    /// Process raw program output
+    ///
+    /// Outside emulator mode the bytes are decoded as UTF-8 and written.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        match &mut self.emulator {
+            Some(emulator) => emulator.feed(bytes),
+            None => self.write(&String::from_utf8_lossy(bytes)),
+        }
+    }
+
+    /// Take input bytes (keys, pastes, mouse reports, query answers) that
+    /// were not sent to a spawned process
+    pub fn take_input(&mut self) -> Vec<u8> {
+        self.emulator
+            .as_mut()
+            .map(Emulator::take_input)
+            .unwrap_or_default()
+    }
+
+    /// Read output from the spawned process and apply layout size changes
+    ///
+    /// When the terminal was last rendered into an area of a different size,
+    /// the screen and the pseudo-terminal are resized to match. Returns
+    /// whether the terminal needs to be redrawn.
+    pub fn poll(&mut self) -> bool {
+        let changed = self.emulator.as_mut().is_some_and(Emulator::poll);
+        if let Some(emulator) = &self.emulator {
+            self.width = emulator.screen.width();
+            self.height = emulator.screen.height();
+            self.scroll_offset = self.scroll_offset.min(emulator.scrollback_len());
+        }
+        changed
+    }
+
+    /// Whether the spawned process is still running
+    #[cfg(all(unix, feature = "pty"))]
+    pub fn is_running(&self) -> bool {
+        self.emulator.as_ref().is_some_and(Emulator::is_running)
+    }
+
+    /// Exit status of the spawned process, once it has exited
+    ///
+    /// Updated by [`poll`](Self::poll).
+    #[cfg(all(unix, feature = "pty"))]
+    pub fn exit_status(&self) -> Option<std::process::ExitStatus> {
+        self.emulator.as_ref().and_then(Emulator::exit_status)
+    }
+
+    /// Kill the spawned process and wait for it to exit
+    #[cfg(all(unix, feature = "pty"))]
+    pub fn kill(&mut self) -> std::io::Result<Option<std::process::ExitStatus>> {
+        match &mut self.emulator {
+            Some(emulator) => emulator.kill(),
+            None => Ok(None),
+        }
+    }
+
     /// Write a single character
     fn write_char(&mut self, ch: char) {
         if ch == '\n' {
@@ -317,11 +436,20 @@ impl Terminal {
     /// Write a line with automatic newline
     pub fn writeln(&mut self, text: &str) {
         self.write(text);
-        self.write("\n");
+        if self.emulator.is_some() {
+            self.write("\r\n");
+        } else {
+            self.write("\n");
+        }
     }
 
     /// Clear the terminal
     pub fn clear(&mut self) {
+        if let Some(emulator) = &mut self.emulator {
+            emulator.screen.feed(b"\x1b[H\x1b[2J\x1b[3J");
+            self.scroll_offset = 0;
+            return;
+        }
         self.lines.clear();
         for _ in 0..self.height {
             self.lines
@@ -342,8 +470,15 @@ impl Terminal {
 
     /// Scroll up
     pub fn scroll_up(&mut self, lines: usize) {
-        let max_scroll = self.lines.len().saturating_sub(self.height as usize);
-        self.scroll_offset = (self.scroll_offset + lines).min(max_scroll);
+        self.scroll_offset = (self.scroll_offset + lines).min(self.max_scroll());
+    }
+
+    /// Lines available above the current screen
+    fn max_scroll(&self) -> usize {
+        match &self.emulator {
+            Some(emulator) => emulator.scrollback_len(),
+            None => self.lines.len().saturating_sub(self.height as usize),
+        }
     }
 
     /// Scroll down
@@ -358,7 +493,7 @@ impl Terminal {
 
     /// Scroll to top
     pub fn scroll_to_top(&mut self) {
-        self.scroll_offset = self.lines.len().saturating_sub(self.height as usize);
+        self.scroll_offset = self.max_scroll();
     }
 
     /// Get input buffer
@@ -372,7 +507,14 @@ impl Terminal {
     }
 
     /// Handle key event
+    ///
+    /// In emulator mode keys are sent to the program and `None` is returned;
+    /// Shift+PageUp/PageDown scroll through the scrollback.
     pub fn handle_key(&mut self, key: KeyEvent) -> Option<TerminalAction> {
+        if self.emulator.is_some() {
+            self.send_key(&key);
+            return None;
+        }
         match key.key {
             Key::Char(c) => {
                 self.input_buffer.push(c);
@@ -434,8 +576,82 @@ impl Terminal {
         }
     }
 
+    /// Send a key to the emulated program
+    fn send_key(&mut self, key: &KeyEvent) {
+        match key.key {
+            Key::PageUp if key.shift => self.scroll_up(self.height as usize / 2),
+            Key::PageDown if key.shift => self.scroll_down(self.height as usize / 2),
+            _ => {
+                self.scroll_to_bottom();
+                if let Some(emulator) = &mut self.emulator {
+                    let bytes = encode_key(key, emulator.screen.application_cursor());
+                    emulator.send(&bytes);
+                }
+            }
+        }
+    }
+
+    /// Paste text
+    ///
+    /// In emulator mode the text is sent to the program, wrapped in bracketed
+    /// paste markers if the program enabled them; otherwise it is appended
+    /// to the input line.
+    pub fn paste(&mut self, text: &str) {
+        let Some(emulator) = &mut self.emulator else {
+            self.input_buffer.push_str(text);
+            return;
+        };
+        self.scroll_offset = 0;
+        if emulator.screen.bracketed_paste() {
+            // Strip markers so pasted text can't end the paste early
+            let text = text.replace("\x1b[201~", "");
+            emulator.send(format!("\x1b[200~{}\x1b[201~", text).as_bytes());
+        } else {
+            emulator.send(text.replace("\r\n", "\r").replace('\n', "\r").as_bytes());
+        }
+    }
+
+    /// Handle a mouse event over the terminal drawn in `area`
+    ///
+    /// Events are reported to the program if it enabled mouse tracking;
+    /// otherwise the wheel scrolls through the scrollback. Returns whether the
+    /// event was handled.
+    pub fn handle_mouse(&mut self, event: &MouseEvent, area: Rect) -> bool {
+        if !area.contains(event.x, event.y) {
+            return false;
+        }
+        if let Some(emulator) = &mut self.emulator {
+            let screen = &emulator.screen;
+            if screen.mouse_tracking() && self.scroll_offset == 0 {
+                let local = MouseEvent {
+                    x: event.x - area.x,
+                    y: event.y - area.y,
+                    ..event.clone()
+                };
+                if let Some(bytes) = encode_mouse(&local, screen.mouse_mode(), screen.sgr_mouse()) {
+                    emulator.send(&bytes);
+                }
+                return true;
+            }
+        }
+        match event.kind {
+            MouseEventKind::ScrollUp => {
+                self.scroll_up(3);
+                true
+            }
+            MouseEventKind::ScrollDown => {
+                self.scroll_down(3);
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Resize terminal
     pub fn resize(&mut self, width: u16, height: u16) {
+        if let Some(emulator) = &mut self.emulator {
+            emulator.resize(width, height);
+        }
         self.width = width;
         self.height = height;
         // Add lines if needed
@@ -488,6 +704,19 @@ impl View for Terminal {
             return;
         }
 
+        if let Some(emulator) = &self.emulator {
+            let view = EmulatorView {
+                scroll_offset: self.scroll_offset,
+                fg: self.default_fg,
+                bg: self.default_bg,
+                show_cursor: self.show_cursor && self.focused,
+                cursor_style: self.cursor_style,
+            };
+            emulator.render(ctx, &view);
+            self.render_scroll_indicator(ctx);
+            return;
+        }
+
         // Fill background
         for y in 0..area.height {
             for x in 0..area.width {
@@ -576,7 +805,14 @@ impl View for Terminal {
             }
         }
 
-        // Render scroll indicator if scrolled
+        self.render_scroll_indicator(ctx);
+    }
+}
+
+impl Terminal {
+    /// Show how far the view is scrolled back
+    fn render_scroll_indicator(&self, ctx: &mut RenderContext) {
+        let area = ctx.area;
         if self.scroll_offset > 0 {
             let indicator = format!("↑{}", self.scroll_offset);
             let indicator_len = indicator.len() as u16;
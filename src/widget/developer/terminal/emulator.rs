@@ -0,0 +1,230 @@
+//! VT emulation mode for the terminal widget
+//!
+//! In this mode output is interpreted by a full [`VtScreen`] instead of the
+//! line-based ANSI parser, and keys, pastes and mouse reports are encoded as
+//! the bytes an xterm would send. With the `pty` feature the terminal can
+//! own a child process on a pseudo-terminal; otherwise input is queued for
+//! the caller to forward.
+
+use super::types::CursorStyle;
+use crate::render::{Cell, Modifier};
+use crate::style::Color;
+use crate::utils::vt::VtScreen;
+use crate::widget::traits::RenderContext;
+use std::cell::Cell as StdCell;
+
+#[cfg(all(unix, feature = "pty"))]
+use crate::utils::pty::PtyProcess;
+#[cfg(all(unix, feature = "pty"))]
+use std::process::{Command, ExitStatus};
+
+/// Emulated screen plus the process (if any) it is connected to
+pub(super) struct Emulator {
+    /// Screen state
+    pub(super) screen: VtScreen,
+    /// Child process on a pseudo-terminal
+    #[cfg(all(unix, feature = "pty"))]
+    process: Option<PtyProcess>,
+    /// Exit status once the child has exited
+    #[cfg(all(unix, feature = "pty"))]
+    exit_status: Option<ExitStatus>,
+    /// Input not sent to a process, for [`Terminal::take_input`]
+    ///
+    /// [`Terminal::take_input`]: super::Terminal::take_input
+    input: Vec<u8>,
+    /// Size of the area last rendered into
+    rendered_size: StdCell<Option<(u16, u16)>>,
+}
+
+impl Emulator {
+    /// Create an emulator that is not connected to a process
+    pub(super) fn new(width: u16, height: u16, scrollback: usize) -> Self {
+        Self {
+            screen: VtScreen::new(width, height).scrollback_limit(scrollback),
+            #[cfg(all(unix, feature = "pty"))]
+            process: None,
+            #[cfg(all(unix, feature = "pty"))]
+            exit_status: None,
+            input: Vec::new(),
+            rendered_size: StdCell::new(None),
+        }
+    }
+
+    /// Create an emulator running `command` on a new pseudo-terminal
+    #[cfg(all(unix, feature = "pty"))]
+    pub(super) fn spawn(
+        command: Command,
+        width: u16,
+        height: u16,
+        scrollback: usize,
+    ) -> std::io::Result<Self> {
+        let mut emulator = Self::new(width, height, scrollback);
+        let (width, height) = (emulator.screen.width(), emulator.screen.height());
+        emulator.process = Some(PtyProcess::spawn(command, width, height)?);
+        Ok(emulator)
+    }
+
+    /// Keep up to `limit` scrollback lines
+    pub(super) fn scrollback_limit(mut self, limit: usize) -> Self {
+        self.screen = self.screen.scrollback_limit(limit);
+        self
+    }
+
+    /// Interpret program output, answering any terminal queries
+    pub(super) fn feed(&mut self, bytes: &[u8]) {
+        self.screen.feed(bytes);
+        let responses = self.screen.take_responses();
+        if !responses.is_empty() {
+            self.send(&responses);
+        }
+    }
+
+    /// Send input to the program
+    pub(super) fn send(&mut self, bytes: &[u8]) {
+        #[cfg(all(unix, feature = "pty"))]
+        if let Some(process) = &mut self.process {
+            if self.exit_status.is_none() {
+                // A failed write means the child is going away; poll()
+                // picks up its exit status.
+                let _ = process.write(bytes);
+            }
+            return;
+        }
+        self.input.extend_from_slice(bytes);
+    }
+
+    /// Take input that was not sent to a process
+    pub(super) fn take_input(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.input)
+    }
+
+    /// Resize the screen and the pseudo-terminal
+    pub(super) fn resize(&mut self, width: u16, height: u16) {
+        self.screen.resize(width, height);
+        #[cfg(all(unix, feature = "pty"))]
+        if let Some(process) = &self.process {
+            let _ = process.resize(self.screen.width(), self.screen.height());
+        }
+    }
+
+    /// Apply layout size changes and read process output
+    ///
+    /// Returns whether anything changed.
+    pub(super) fn poll(&mut self) -> bool {
+        let mut changed = false;
+        if let Some((width, height)) = self.rendered_size.get() {
+            if (width, height) != (self.screen.width(), self.screen.height()) {
+                self.resize(width, height);
+                changed = true;
+            }
+        }
+
+        #[cfg(all(unix, feature = "pty"))]
+        if let Some(process) = &mut self.process {
+            let output = process.try_read();
+            if self.exit_status.is_none() {
+                if let Ok(Some(status)) = process.try_wait() {
+                    self.exit_status = Some(status);
+                    changed = true;
+                }
+            }
+            if !output.is_empty() {
+                self.feed(&output);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Whether a child process is attached and still running
+    #[cfg(all(unix, feature = "pty"))]
+    pub(super) fn is_running(&self) -> bool {
+        self.process.is_some() && self.exit_status.is_none()
+    }
+
+    /// Exit status of the child, once it has exited
+    #[cfg(all(unix, feature = "pty"))]
+    pub(super) fn exit_status(&self) -> Option<ExitStatus> {
+        self.exit_status
+    }
+
+    /// Kill the child process and wait for it
+    #[cfg(all(unix, feature = "pty"))]
+    pub(super) fn kill(&mut self) -> std::io::Result<Option<ExitStatus>> {
+        if let Some(process) = &mut self.process {
+            let status = process.kill()?;
+            self.exit_status = Some(status);
+        }
+        Ok(self.exit_status)
+    }
+
+    /// Scrollback lines that can be scrolled into view
+    ///
+    /// Full-screen programs on the alternate screen have no scrollback.
+    pub(super) fn scrollback_len(&self) -> usize {
+        if self.screen.is_alternate_screen() {
+            0
+        } else {
+            self.screen.scrollback().len()
+        }
+    }
+
+    /// Draw the screen, `scroll_offset` lines back into the scrollback
+    pub(super) fn render(&self, ctx: &mut RenderContext, view: &EmulatorView) {
+        let area = ctx.area;
+        self.rendered_size.set(Some((area.width, area.height)));
+
+        let scrollback = self.screen.scrollback();
+        let offset = view.scroll_offset.min(self.scrollback_len());
+        let colored = |cell: &Cell| {
+            let mut cell = *cell;
+            cell.fg = Some(cell.fg.unwrap_or(view.fg));
+            cell.bg = Some(cell.bg.unwrap_or(view.bg));
+            cell
+        };
+
+        for y in 0..area.height {
+            let row = y as usize;
+            let cells = if row < offset {
+                scrollback
+                    .get(scrollback.len() - offset + row)
+                    .map(Vec::as_slice)
+            } else {
+                self.screen.buffer().get_row((row - offset) as u16)
+            };
+            for x in 0..area.width {
+                let cell = cells
+                    .and_then(|cells| cells.get(x as usize))
+                    .map(colored)
+                    .unwrap_or_else(|| Cell::new(' ').fg(view.fg).bg(view.bg));
+                ctx.set(x, y, cell);
+            }
+        }
+
+        if view.show_cursor && offset == 0 && self.screen.cursor_visible() {
+            let (x, y) = self.screen.cursor();
+            if x < area.width && y < area.height {
+                let mut cell = self
+                    .screen
+                    .buffer()
+                    .get(x, y)
+                    .map(colored)
+                    .unwrap_or_else(|| Cell::new(' ').fg(view.fg).bg(view.bg));
+                cell.modifier |= match view.cursor_style {
+                    CursorStyle::Underline => Modifier::UNDERLINE,
+                    CursorStyle::Block | CursorStyle::Bar => Modifier::REVERSE,
+                };
+                ctx.set(x, y, cell);
+            }
+        }
+    }
+}
+
+/// Widget settings that affect how the emulated screen is drawn
+pub(super) struct EmulatorView {
+    pub(super) scroll_offset: usize,
+    pub(super) fg: Color,
+    pub(super) bg: Color,
+    pub(super) show_cursor: bool,
+    pub(super) cursor_style: CursorStyle,
+}
@@ -1,12 +1,16 @@
 //! Terminal widget for embedded terminal emulator
 //!
 //! Provides an embedded terminal with ANSI color support and scrollback.
+//! [`Terminal::emulator`] and `Terminal::spawn` (with the `pty` feature)
+//! switch to full VT100/xterm emulation for running interactive programs
+//! such as shells and `htop`.
 
 pub use core::{terminal, Terminal};
 pub use types::{CursorStyle, TermCell, TermLine, TerminalAction};
 
 mod ansi;
 mod core;
+mod emulator;
 // KEEP HERE - These tests require public API getters for terminal state
 // Tests are placeholder as private fields cannot be tested directly
 
@@ -21,7 +25,7 @@ mod tests {
     use crate::layout::Rect;
     use crate::render::Buffer;
     use crate::style::Color;
-    use crate::widget::traits::RenderContext;
+    use crate::widget::traits::{RenderContext, View};
 
     #[test]
     fn test_terminal_new() {
@@ -136,5 +140,141 @@ mod tests {
         let _term = super::terminal(120, 40);
         // Private fields - can't test directly
     }
+
+    fn render_text(term: &Terminal, width: u16, height: u16) -> Vec<String> {
+        let mut buffer = Buffer::new(width, height);
+        let mut ctx = RenderContext::new(&mut buffer, Rect::new(0, 0, width, height));
+        term.render(&mut ctx);
+        (0..height)
+            .map(|y| {
+                let row: String = (0..width)
+                    .map(|x| buffer.get(x, y).map_or(' ', |c| c.symbol))
+                    .collect();
+                row.trim_end().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_emulator_renders_screen() {
+        let mut term = Terminal::emulator(10, 3);
+        term.feed(b"\x1b]2;htop\x07\x1b[2;3Hhi\x1b[31m!");
+        assert_eq!(term.get_title(), Some("htop"));
+        assert_eq!(render_text(&term, 10, 3), vec!["", "  hi!", ""]);
+        let screen = term.screen().unwrap();
+        assert_eq!(screen.cursor(), (5, 1));
+        assert_eq!(screen.buffer().get(4, 1).unwrap().fg, Some(Color::RED));
+    }
+
+    #[test]
+    fn test_emulator_forwards_input() {
+        let mut term = Terminal::emulator(10, 3);
+        assert_eq!(term.handle_key(KeyEvent::new(Key::Char('l'))), None);
+        term.handle_key(KeyEvent::ctrl(Key::Char('c')));
+        term.handle_key(KeyEvent::new(Key::Up));
+        term.feed(b"\x1b[?1h\x1b[6n");
+        term.handle_key(KeyEvent::new(Key::Up));
+        assert_eq!(term.take_input(), b"l\x03\x1b[A\x1b[1;1R\x1bOA");
+        assert_eq!(term.get_input(), "");
+
+        term.paste("a\nb");
+        assert_eq!(term.take_input(), b"a\rb");
+        term.feed(b"\x1b[?2004h");
+        term.paste("x\x1b[201~y");
+        assert_eq!(term.take_input(), b"\x1b[200~xy\x1b[201~");
+    }
+
+    #[test]
+    fn test_emulator_mouse_passthrough() {
+        use crate::event::{MouseButton, MouseEvent, MouseEventKind};
+
+        let mut term = Terminal::emulator(10, 3);
+        let area = Rect::new(5, 5, 10, 3);
+        let click = MouseEvent::new(7, 6, MouseEventKind::Down(MouseButton::Left));
+        assert!(!term.handle_mouse(&click, area));
+
+        term.feed(b"\x1b[?1000h\x1b[?1006h");
+        assert!(term.handle_mouse(&click, area));
+        assert!(!term.handle_mouse(&click, Rect::new(0, 0, 2, 2)));
+        assert_eq!(term.take_input(), b"\x1b[<0;3;2M");
+    }
+
+    #[test]
+    fn test_emulator_scrollback() {
+        let mut term = Terminal::emulator(12, 2).max_scrollback(10);
+        term.write("one\r\ntwo\r\nthree\r\nfour");
+        assert_eq!(render_text(&term, 12, 2), vec!["three", "four"]);
+
+        term.handle_key(KeyEvent {
+            shift: true,
+            ..KeyEvent::new(Key::PageUp)
+        });
+        let lines = render_text(&term, 12, 2);
+        assert_eq!(lines, vec!["two    ↑1", "three"]);
+
+        // Typing returns to the live screen
+        term.handle_key(KeyEvent::new(Key::Char('x')));
+        assert_eq!(render_text(&term, 12, 2), vec!["three", "four"]);
+
+        // Programs on the alternate screen have no scrollback
+        term.feed(b"\x1b[?1049h");
+        term.scroll_to_top();
+        assert_eq!(render_text(&term, 12, 2), vec!["", ""]);
+    }
+
+    #[test]
+    fn test_emulator_follows_layout_size() {
+        let mut term = Terminal::emulator(10, 3);
+        assert!(!term.poll());
+        render_text(&term, 20, 5);
+        assert!(term.poll());
+        let screen = term.screen().unwrap();
+        assert_eq!((screen.width(), screen.height()), (20, 5));
+        assert!(!term.poll());
+    }
+
+    #[cfg(all(unix, feature = "pty"))]
+    #[test]
+    fn test_spawn_runs_program() {
+        use std::process::Command;
+        use std::time::{Duration, Instant};
+
+        let mut command = Command::new("sh");
+        command.args([
+            "-c",
+            "printf '\\033]0;demo\\007'; stty size; read line; echo got $line",
+        ]);
+        let mut term = Terminal::spawn(command, 30, 4).unwrap();
+        assert!(term.is_running());
+
+        let wait_for = |term: &mut Terminal, text: &str| {
+            let start = Instant::now();
+            while !term.screen().unwrap().text().contains(text)
+                && start.elapsed() < Duration::from_secs(5)
+            {
+                term.poll();
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            assert!(
+                term.screen().unwrap().text().contains(text),
+                "unexpected screen: {:?}",
+                term.screen().unwrap().text()
+            );
+        };
+
+        wait_for(&mut term, "4 30");
+        assert_eq!(term.get_title(), Some("demo"));
+        term.paste("hi");
+        term.handle_key(KeyEvent::new(Key::Enter));
+        wait_for(&mut term, "got hi");
+
+        let start = Instant::now();
+        while term.is_running() && start.elapsed() < Duration::from_secs(5) {
+            term.poll();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(term.exit_status().is_some_and(|s| s.success()));
+        assert!(term.take_input().is_empty());
+    }
 }
 mod types;
@@ -12,7 +12,7 @@ use crate::style::Color;
 use crate::widget::syntax::{HighlightSpan, Language, SyntaxTheme};
 
 /// Standard highlight capture names used by tree-sitter grammars
-const HIGHLIGHT_NAMES: &[&str] = &[
+pub(super) const HIGHLIGHT_NAMES: &[&str] = &[
     "attribute",
     "comment",
     "constant",
@@ -43,7 +43,7 @@ const HIGHLIGHT_NAMES: &[&str] = &[
 ];
 
 /// Map highlight names to theme colors
-fn highlight_name_to_color(name: &str, theme: &SyntaxTheme) -> Color {
+pub(super) fn highlight_name_to_color(name: &str, theme: &SyntaxTheme) -> Color {
     match name {
         "keyword" => theme.keyword,
         "type" | "type.builtin" | "constructor" => theme.type_name,
@@ -64,12 +64,12 @@ fn highlight_name_to_color(name: &str, theme: &SyntaxTheme) -> Color {
 }
 
 /// Map highlight names to bold style
-fn highlight_name_is_bold(name: &str) -> bool {
+pub(super) fn highlight_name_is_bold(name: &str) -> bool {
     matches!(name, "keyword" | "constant" | "constant.builtin")
 }
 
 /// Map highlight names to italic style
-fn highlight_name_is_italic(name: &str) -> bool {
+pub(super) fn highlight_name_is_italic(name: &str) -> bool {
     matches!(name, "comment")
 }
 
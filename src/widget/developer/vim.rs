@@ -1,10 +1,24 @@
 //! Vim Mode system for terminal applications
 //!
 //! Provides vim-style modal editing with Normal, Insert, Visual,
-//! and Command modes.
+//! and Command modes. [`VimState::handle_key`] parses keys into
+//! [`VimAction`]s; [`VimState::process`] also carries them out on a
+//! [`VimBuffer`], with text objects, counts, registers, marks, `.` repeat,
+//! macros, `/` search and `:s` substitution.
+
+mod buffer;
+mod ex;
+mod registers;
+mod text_object;
+
+pub use buffer::{VimBuffer, VimSelection, VimSelectionKind};
+pub use text_object::VimTextObject;
 
 use crate::event::{Key, KeyEvent};
 use crate::style::Color;
+use crate::utils::Clipboard;
+use buffer::{BlockInsert, Visual};
+use registers::Registers;
 use std::collections::HashMap;
 
 /// Vim mode
@@ -102,6 +116,14 @@ pub enum VimMotion {
     SearchNext,
     /// Search backward (N)
     SearchPrev,
+    /// Inside a text object (i)
+    Inner(VimTextObject),
+    /// Around a text object (a)
+    Around(VimTextObject),
+    /// Exact position of a mark (`)
+    Mark(char),
+    /// Line of a mark (')
+    MarkLine(char),
 }
 
 /// Vim action
@@ -159,10 +181,37 @@ pub enum VimAction {
     Outdent,
     /// Execute command
     ExecuteCommand(String),
+    /// Set a mark (m)
+    SetMark(char),
+    /// Start recording a macro (q)
+    RecordMacro(char),
+    /// Stop recording the macro (q)
+    StopRecording,
+    /// Play a macro (@, `@@` for the last one)
+    PlayMacro(char),
     /// Nothing
     None,
 }
 
+/// Key waiting for the character that completes it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PendingKey {
+    /// Register name after `"`
+    Register,
+    /// Target of `f`, `F`, `t` or `T`
+    Find(char),
+    /// Text object after `i` or `a`
+    TextObject { inner: bool },
+    /// Mark name after `m`
+    SetMark,
+    /// Mark name after `'` (line) or `` ` `` (exact)
+    JumpMark { exact: bool },
+    /// Register after `q`
+    Record,
+    /// Register after `@`
+    Play,
+}
+
 /// Vim state manager
 ///
 /// # Example
@@ -192,16 +241,62 @@ pub struct VimState {
     search_pattern: String,
     /// Search direction (true = forward)
     search_forward: bool,
+    /// Last searched pattern, for n and N
+    last_search: String,
     /// Last action for repeat
     last_action: Option<VimAction>,
-    /// Register (for yank/paste)
-    register: String,
-    /// Register name (for future named register support)
-    _register_name: char,
+    /// Registers (for yank/paste)
+    registers: Registers,
+    /// Register selected with `"x` for the next command
+    register_name: Option<char>,
     /// Key sequence buffer
     key_buffer: Vec<char>,
     /// Custom key mappings
     mappings: HashMap<String, VimAction>,
+    /// Count typed after an operator (`d3w`)
+    motion_count: Option<usize>,
+    /// Whether the pending operator was doubled (`dd`, `yy`, `cc`)
+    doubled_operator: bool,
+    /// Key waiting for its argument
+    pending: Option<PendingKey>,
+    /// Last f/F/t/T motion, for ; and ,
+    last_find: Option<VimMotion>,
+    /// Count of the last action
+    last_count: usize,
+    /// Whether the count of the last action was typed
+    last_count_typed: bool,
+    /// Whether the last operator was doubled (`dd`, `yy`, `cc`)
+    last_linewise: bool,
+    /// Register selected for the last action
+    last_register: Option<char>,
+    /// Marks, as (line, column)
+    marks: HashMap<char, (usize, usize)>,
+    /// Register of the macro being recorded
+    recording: Option<char>,
+    /// Keys of the macro being recorded
+    macro_keys: Vec<KeyEvent>,
+    /// Recorded macros
+    macros: HashMap<char, Vec<KeyEvent>>,
+    /// Last played macro, for `@@`
+    last_macro: Option<char>,
+    /// Keys of the change in progress
+    change_keys: Vec<KeyEvent>,
+    /// Whether insert mode keys belong to the change in progress
+    recording_change: bool,
+    /// Keys of the last change, for `.`
+    last_change: Vec<KeyEvent>,
+    /// Nesting of `.` and macro replays
+    replay_depth: usize,
+    /// Whether an undo group is open for the current insert
+    change_open: bool,
+    /// Visual selection
+    visual: Option<Visual>,
+    /// Visual block insert waiting for the typed text
+    block_insert: Option<BlockInsert>,
+    /// Column that vertical motions aim for
+    want_col: Option<usize>,
+    /// Result of the last ex command the buffer did not handle
+    command_result: Option<VimCommandResult>,
 }
 
 impl VimState {
@@ -214,11 +309,34 @@ impl VimState {
             command_buffer: String::new(),
             search_pattern: String::new(),
             search_forward: true,
+            last_search: String::new(),
             last_action: None,
-            register: String::new(),
-            _register_name: '"',
+            registers: Registers::new(),
+            register_name: None,
             key_buffer: Vec::new(),
             mappings: HashMap::new(),
+            motion_count: None,
+            doubled_operator: false,
+            pending: None,
+            last_find: None,
+            last_count: 1,
+            last_count_typed: false,
+            last_linewise: false,
+            last_register: None,
+            marks: HashMap::new(),
+            recording: None,
+            macro_keys: Vec::new(),
+            macros: HashMap::new(),
+            last_macro: None,
+            change_keys: Vec::new(),
+            recording_change: false,
+            last_change: Vec::new(),
+            replay_depth: 0,
+            change_open: false,
+            visual: None,
+            block_insert: None,
+            want_col: None,
+            command_result: None,
         }
     }
 
@@ -233,6 +351,8 @@ impl VimState {
         if mode == VimMode::Normal {
             self.operator = None;
             self.count = None;
+            self.motion_count = None;
+            self.pending = None;
         }
     }
 
@@ -253,12 +373,59 @@ impl VimState {
 
     /// Get register content
     pub fn register(&self) -> &str {
-        &self.register
+        self.registers.unnamed()
     }
 
     /// Set register content
     pub fn set_register(&mut self, content: impl Into<String>) {
-        self.register = content.into();
+        self.registers.set_unnamed(content.into());
+    }
+
+    /// Get the content of a named register
+    ///
+    /// `+` and `*` read the system clipboard.
+    pub fn register_content(&self, name: char) -> Option<String> {
+        self.registers.get(name).map(|register| register.text)
+    }
+
+    /// Set the clipboard behind the `+` and `*` registers
+    pub fn set_clipboard(&mut self, clipboard: Clipboard) {
+        self.registers.clipboard = clipboard;
+    }
+
+    /// Get a mark position (line, column)
+    pub fn mark(&self, name: char) -> Option<(usize, usize)> {
+        self.marks.get(&name).copied()
+    }
+
+    /// Get the register a macro is being recorded into
+    pub fn recording(&self) -> Option<char> {
+        self.recording
+    }
+
+    /// Take the result of the last ex command that needs the host, like `:w`
+    pub fn take_command(&mut self) -> Option<VimCommandResult> {
+        self.command_result.take()
+    }
+
+    /// Text for a status line: the command line, search or mode
+    pub fn status_text(&self) -> String {
+        let mut text = match self.mode {
+            VimMode::Normal => String::new(),
+            VimMode::Command => format!(":{}", self.command_buffer),
+            VimMode::Search => {
+                let prompt = if self.search_forward { '/' } else { '?' };
+                format!("{}{}", prompt, self.search_pattern)
+            }
+            mode => format!("-- {} --", mode.name()),
+        };
+        if let Some(register) = self.recording {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(&format!("recording @{}", register));
+        }
+        text
     }
 
     /// Add a custom key mapping
@@ -268,28 +435,37 @@ impl VimState {
 
     /// Handle key event in normal mode
     fn handle_normal(&mut self, key: &KeyEvent) -> VimAction {
+        if key.ctrl {
+            return match key.key {
+                Key::Char('r') => VimAction::Redo,
+                Key::Char('v') => {
+                    self.set_mode(VimMode::VisualBlock);
+                    VimAction::EnterVisualBlock
+                }
+                _ => VimAction::None,
+            };
+        }
+
         // Handle digits for count
         if let Key::Char(ch) = key.key {
             if let Some(digit) = ch.to_digit(10) {
                 let digit = digit as usize;
-                self.count = Some(self.count.unwrap_or(0) * 10 + digit);
-                return VimAction::None;
+                if self.operator.is_none() {
+                    self.count = Some(self.count.unwrap_or(0) * 10 + digit);
+                    return VimAction::None;
+                }
+                // After an operator a leading 0 is the line start motion
+                if digit != 0 || self.motion_count.is_some() {
+                    self.motion_count = Some(self.motion_count.unwrap_or(0) * 10 + digit);
+                    return VimAction::None;
+                }
             }
         }
 
         // Handle operator pending
         if let Some(op) = self.operator {
-            if let Key::Char(ch) = key.key {
-                let motion = self.char_to_motion(ch);
-                if motion.is_some() {
-                    self.operator = None;
-                    return match op {
-                        'd' => VimAction::Delete(motion),
-                        'y' => VimAction::Yank(motion),
-                        'c' => VimAction::Change(motion),
-                        _ => VimAction::None,
-                    };
-                }
+            if key.key != Key::Escape {
+                return self.handle_operator_pending(op, key);
             }
         }
 
@@ -344,6 +520,10 @@ impl VimState {
                 self.search_forward = false;
                 VimAction::EnterSearch
             }
+            Key::Char('r') => {
+                self.set_mode(VimMode::Replace);
+                VimAction::None
+            }
 
             // Motions
             Key::Char('h') | Key::Left => VimAction::Move(VimMotion::Left),
@@ -353,14 +533,10 @@ impl VimState {
             Key::Char('w') => VimAction::Move(VimMotion::Word),
             Key::Char('b') => VimAction::Move(VimMotion::WordBack),
             Key::Char('e') => VimAction::Move(VimMotion::WordEnd),
-            Key::Char('0') => VimAction::Move(VimMotion::LineStart),
-            Key::Char('$') => VimAction::Move(VimMotion::LineEnd),
+            Key::Char('0') | Key::Home => VimAction::Move(VimMotion::LineStart),
+            Key::Char('$') | Key::End => VimAction::Move(VimMotion::LineEnd),
             Key::Char('^') => VimAction::Move(VimMotion::FirstNonBlank),
             Key::Char('G') => VimAction::Move(VimMotion::GoToLine(self.count)),
-            Key::Char('g') => {
-                self.key_buffer.push('g');
-                VimAction::None
-            }
             Key::Char('{') => VimAction::Move(VimMotion::ParagraphBack),
             Key::Char('}') => VimAction::Move(VimMotion::ParagraphForward),
             Key::Char('%') => VimAction::Move(VimMotion::MatchBracket),
@@ -384,21 +560,181 @@ impl VimState {
             // Actions
             Key::Char('x') => VimAction::Delete(Some(VimMotion::Right)),
             Key::Char('X') => VimAction::Delete(Some(VimMotion::Left)),
+            Key::Char('D') => VimAction::Delete(Some(VimMotion::LineEnd)),
+            Key::Char('C') => VimAction::Change(Some(VimMotion::LineEnd)),
+            Key::Char('s') => VimAction::Change(Some(VimMotion::Right)),
+            Key::Char('S') => {
+                self.operator = Some('c');
+                self.handle_operator_pending('c', &KeyEvent::new(Key::Char('c')))
+            }
+            Key::Char('Y') => {
+                self.operator = Some('y');
+                self.handle_operator_pending('y', &KeyEvent::new(Key::Char('y')))
+            }
             Key::Char('p') => VimAction::PasteAfter,
             Key::Char('P') => VimAction::PasteBefore,
             Key::Char('u') => VimAction::Undo,
-            Key::Char('r') if key.ctrl => VimAction::Redo,
             Key::Char('.') => VimAction::Repeat,
             Key::Char('J') => VimAction::JoinLines,
             Key::Char('>') => VimAction::Indent,
             Key::Char('<') => VimAction::Outdent,
+            Key::Char('q') if self.recording.is_some() => VimAction::StopRecording,
 
             Key::Escape => {
                 self.count = None;
                 self.operator = None;
+                self.motion_count = None;
+                self.register_name = None;
                 VimAction::Escape
             }
 
+            _ => self.handle_shared(key),
+        }
+    }
+
+    /// Handle the key after an operator (`d`, `y`, `c`)
+    fn handle_operator_pending(&mut self, op: char, key: &KeyEvent) -> VimAction {
+        let motion = match key.key {
+            Key::Char(ch) if ch == op => {
+                // Same key repeats = line
+                self.operator = None;
+                self.doubled_operator = true;
+                return self.operator_action(op, VimMotion::Down);
+            }
+            Key::Char('i') | Key::Char('a') => {
+                self.pending = Some(PendingKey::TextObject {
+                    inner: key.key == Key::Char('i'),
+                });
+                return VimAction::None;
+            }
+            Key::Char('G') => VimMotion::GoToLine(self.count.or(self.motion_count)),
+            Key::Char('n') => VimMotion::SearchNext,
+            Key::Char('N') => VimMotion::SearchPrev,
+            Key::Char(ch) if !"dyc".contains(ch) => match self.char_to_motion(ch) {
+                Some(motion) => motion,
+                None => return self.handle_shared(key),
+            },
+            Key::Left => VimMotion::Left,
+            Key::Right => VimMotion::Right,
+            Key::Up => VimMotion::Up,
+            Key::Down => VimMotion::Down,
+            Key::Home => VimMotion::LineStart,
+            Key::End => VimMotion::LineEnd,
+            _ => {
+                self.operator = None;
+                self.motion_count = None;
+                return VimAction::None;
+            }
+        };
+        self.motion_action(motion)
+    }
+
+    /// Handle keys that work the same in normal, operator-pending and
+    /// visual mode: prefixes that wait for another key, and `;` / `,`
+    fn handle_shared(&mut self, key: &KeyEvent) -> VimAction {
+        let pending = match key.key {
+            Key::Char(kind @ ('f' | 'F' | 't' | 'T')) => PendingKey::Find(kind),
+            Key::Char('\'') => PendingKey::JumpMark { exact: false },
+            Key::Char('`') => PendingKey::JumpMark { exact: true },
+            Key::Char('"') if self.operator.is_none() => PendingKey::Register,
+            Key::Char('m') if self.mode == VimMode::Normal && self.operator.is_none() => {
+                PendingKey::SetMark
+            }
+            Key::Char('q') if self.mode == VimMode::Normal && self.operator.is_none() => {
+                PendingKey::Record
+            }
+            Key::Char('@') if self.mode == VimMode::Normal && self.operator.is_none() => {
+                PendingKey::Play
+            }
+            Key::Char('g') => {
+                self.key_buffer.push('g');
+                return VimAction::None;
+            }
+            Key::Char(';') | Key::Char(',') => {
+                let Some(motion) = self.last_find.clone() else {
+                    return VimAction::None;
+                };
+                let motion = if key.key == Key::Char(',') {
+                    match motion {
+                        VimMotion::FindChar(ch) => VimMotion::FindCharBack(ch),
+                        VimMotion::FindCharBack(ch) => VimMotion::FindChar(ch),
+                        VimMotion::TillChar(ch) => VimMotion::TillCharBack(ch),
+                        VimMotion::TillCharBack(ch) => VimMotion::TillChar(ch),
+                        motion => motion,
+                    }
+                } else {
+                    motion
+                };
+                return self.motion_action(motion);
+            }
+            _ => {
+                // Unknown key cancels a pending operator
+                self.operator = None;
+                self.motion_count = None;
+                return VimAction::None;
+            }
+        };
+        self.pending = Some(pending);
+        VimAction::None
+    }
+
+    /// Complete a key that was waiting for its argument
+    fn handle_pending(&mut self, pending: PendingKey, key: &KeyEvent) -> VimAction {
+        let Key::Char(ch) = key.key else {
+            self.operator = None;
+            self.motion_count = None;
+            return VimAction::None;
+        };
+        match pending {
+            PendingKey::Register if Registers::is_valid(ch) => {
+                self.register_name = Some(ch);
+                VimAction::None
+            }
+            PendingKey::Find(kind) => {
+                let motion = match kind {
+                    'f' => VimMotion::FindChar(ch),
+                    'F' => VimMotion::FindCharBack(ch),
+                    't' => VimMotion::TillChar(ch),
+                    _ => VimMotion::TillCharBack(ch),
+                };
+                self.last_find = Some(motion.clone());
+                self.motion_action(motion)
+            }
+            PendingKey::TextObject { inner } => match VimTextObject::from_char(ch) {
+                Some(object) if inner => self.motion_action(VimMotion::Inner(object)),
+                Some(object) => self.motion_action(VimMotion::Around(object)),
+                None => {
+                    self.operator = None;
+                    self.motion_count = None;
+                    VimAction::None
+                }
+            },
+            PendingKey::SetMark if ch.is_ascii_alphabetic() => VimAction::SetMark(ch),
+            PendingKey::JumpMark { exact } => self.motion_action(if exact {
+                VimMotion::Mark(ch)
+            } else {
+                VimMotion::MarkLine(ch)
+            }),
+            PendingKey::Record if ch.is_ascii_alphanumeric() => VimAction::RecordMacro(ch),
+            PendingKey::Play if ch.is_ascii_alphanumeric() || ch == '@' => VimAction::PlayMacro(ch),
+            _ => VimAction::None,
+        }
+    }
+
+    /// Apply a motion to the pending operator, or move
+    fn motion_action(&mut self, motion: VimMotion) -> VimAction {
+        match self.operator.take() {
+            Some(op) => self.operator_action(op, motion),
+            None => VimAction::Move(motion),
+        }
+    }
+
+    /// Action of an operator over a motion
+    fn operator_action(&self, op: char, motion: VimMotion) -> VimAction {
+        match op {
+            'd' => VimAction::Delete(Some(motion)),
+            'y' => VimAction::Yank(Some(motion)),
+            'c' => VimAction::Change(Some(motion)),
             _ => VimAction::None,
         }
     }
@@ -416,6 +752,22 @@ impl VimState {
 
     /// Handle key event in visual mode
     fn handle_visual(&mut self, key: &KeyEvent) -> VimAction {
+        if key.ctrl {
+            return match key.key {
+                Key::Char('v') => self.switch_visual(VimMode::VisualBlock),
+                _ => VimAction::None,
+            };
+        }
+
+        if let Key::Char(ch) = key.key {
+            if let Some(digit) = ch.to_digit(10) {
+                if digit != 0 || self.count.is_some() {
+                    self.count = Some(self.count.unwrap_or(0) * 10 + digit as usize);
+                    return VimAction::None;
+                }
+            }
+        }
+
         match key.key {
             Key::Escape => {
                 self.set_mode(VimMode::Normal);
@@ -429,10 +781,40 @@ impl VimState {
                 self.set_mode(VimMode::Normal);
                 VimAction::Yank(None)
             }
-            Key::Char('c') => {
+            Key::Char('c') | Key::Char('s') => {
                 self.set_mode(VimMode::Insert);
                 VimAction::Change(None)
             }
+            Key::Char('v') => self.switch_visual(VimMode::Visual),
+            Key::Char('V') => self.switch_visual(VimMode::VisualLine),
+            Key::Char('i') | Key::Char('a') => {
+                self.pending = Some(PendingKey::TextObject {
+                    inner: key.key == Key::Char('i'),
+                });
+                VimAction::None
+            }
+            Key::Char('I') if self.mode == VimMode::VisualBlock => {
+                self.set_mode(VimMode::Insert);
+                VimAction::InsertStart
+            }
+            Key::Char('A') if self.mode == VimMode::VisualBlock => {
+                self.set_mode(VimMode::Insert);
+                VimAction::AppendEnd
+            }
+            Key::Char('>') | Key::Char('<') | Key::Char('J') | Key::Char('p') => {
+                self.set_mode(VimMode::Normal);
+                match key.key {
+                    Key::Char('>') => VimAction::Indent,
+                    Key::Char('<') => VimAction::Outdent,
+                    Key::Char('J') => VimAction::JoinLines,
+                    _ => VimAction::PasteAfter,
+                }
+            }
+            Key::Char(':') => {
+                self.set_mode(VimMode::Command);
+                self.command_buffer = "'<,'>".to_string();
+                VimAction::EnterCommand
+            }
             // Movement in visual mode
             Key::Char('h') | Key::Left => VimAction::Move(VimMotion::Left),
             Key::Char('j') | Key::Down => VimAction::Move(VimMotion::Down),
@@ -440,10 +822,33 @@ impl VimState {
             Key::Char('l') | Key::Right => VimAction::Move(VimMotion::Right),
             Key::Char('w') => VimAction::Move(VimMotion::Word),
             Key::Char('b') => VimAction::Move(VimMotion::WordBack),
+            Key::Home => VimAction::Move(VimMotion::LineStart),
+            Key::End => VimAction::Move(VimMotion::LineEnd),
+            Key::Char('G') => VimAction::Move(VimMotion::GoToLine(self.count)),
+            Key::Char('n') => VimAction::Move(VimMotion::SearchNext),
+            Key::Char('N') => VimAction::Move(VimMotion::SearchPrev),
+            Key::Char(ch) => match self.char_to_motion(ch) {
+                Some(motion) => VimAction::Move(motion),
+                None => self.handle_shared(key),
+            },
             _ => VimAction::None,
         }
     }
 
+    /// Switch between visual modes, or leave visual mode when already in it
+    fn switch_visual(&mut self, mode: VimMode) -> VimAction {
+        if self.mode == mode {
+            self.set_mode(VimMode::Normal);
+            return VimAction::Escape;
+        }
+        self.set_mode(mode);
+        match mode {
+            VimMode::Visual => VimAction::EnterVisual,
+            VimMode::VisualLine => VimAction::EnterVisualLine,
+            _ => VimAction::EnterVisualBlock,
+        }
+    }
+
     /// Handle key event in command mode
     fn handle_command(&mut self, key: &KeyEvent) -> VimAction {
         match key.key {
@@ -483,6 +888,10 @@ impl VimState {
             }
             Key::Enter => {
                 self.set_mode(VimMode::Normal);
+                // An empty pattern searches for the last one again
+                if !self.search_pattern.is_empty() {
+                    self.last_search = self.search_pattern.clone();
+                }
                 VimAction::Move(if self.search_forward {
                     VimMotion::SearchNext
                 } else {
@@ -529,12 +938,26 @@ impl VimState {
 
     /// Handle a key event
     pub fn handle_key(&mut self, key: &KeyEvent) -> VimAction {
+        if self.recording.is_some() && self.replay_depth == 0 {
+            self.macro_keys.push(key.clone());
+        }
+        // Captured first, since mode changes clear the count
+        let count = self.count.unwrap_or(1).max(1) * self.motion_count.unwrap_or(1).max(1);
+        let count_typed = self.count.is_some() || self.motion_count.is_some();
+
+        if let Some(pending) = self.pending.take() {
+            let action = self.handle_pending(pending, key);
+            return self.finish(action, count, count_typed);
+        }
+
         // Check for 'gg' sequence
         if !self.key_buffer.is_empty() {
             if let Key::Char(ch) = key.key {
                 if self.key_buffer == ['g'] && ch == 'g' {
                     self.key_buffer.clear();
-                    return VimAction::Move(VimMotion::GoToLine(Some(1)));
+                    let line = self.count.or(self.motion_count).unwrap_or(1);
+                    let action = self.motion_action(VimMotion::GoToLine(Some(line)));
+                    return self.finish(action, 1, false);
                 }
             }
             self.key_buffer.clear();
@@ -558,7 +981,11 @@ impl VimState {
                 }
             }
         };
+        self.finish(action, count, count_typed)
+    }
 
+    /// Bookkeeping once a key produced an action
+    fn finish(&mut self, action: VimAction, count: usize, count_typed: bool) -> VimAction {
         // Save for repeat
         if action != VimAction::None
             && action != VimAction::Escape
@@ -576,14 +1003,51 @@ impl VimState {
             self.last_action = Some(action.clone());
         }
 
+        match action {
+            VimAction::RecordMacro(register) => {
+                self.recording = Some(register);
+                self.macro_keys.clear();
+            }
+            VimAction::StopRecording => {
+                if let Some(register) = self.recording.take() {
+                    // Drop the `q` that stopped recording
+                    self.macro_keys.pop();
+                    let keys = std::mem::take(&mut self.macro_keys);
+                    let slot = self
+                        .macros
+                        .entry(register.to_ascii_lowercase())
+                        .or_default();
+                    if !register.is_ascii_uppercase() {
+                        slot.clear();
+                    }
+                    slot.extend(keys);
+                }
+            }
+            _ => {}
+        }
+
         // Reset count after action
         if action != VimAction::None {
+            self.last_count = count;
+            self.last_count_typed = count_typed;
+            self.last_linewise = std::mem::take(&mut self.doubled_operator);
+            self.last_register = self.register_name.take();
             self.count = None;
+            self.motion_count = None;
         }
 
         action
     }
 
+    /// Check that no command is partially typed
+    fn is_idle(&self) -> bool {
+        self.operator.is_none()
+            && self.pending.is_none()
+            && self.key_buffer.is_empty()
+            && self.count.is_none()
+            && self.register_name.is_none()
+    }
+
     /// Parse and execute a command
     pub fn execute_command(&mut self, cmd: &str) -> VimCommandResult {
         let cmd = cmd.trim();
@@ -0,0 +1,1348 @@
+//! Carrying out Vim actions on a text buffer
+//!
+//! [`VimState::handle_key`] only parses keys. [`VimState::process`] also
+//! applies the resulting action to a [`VimBuffer`]: motions, operators over
+//! motions and text objects, registers, visual and visual-block selections,
+//! marks, `.` repeat, macros, search and ex commands.
+
+use std::ops::Range;
+
+use super::ex::{delete_lines_range, find_matches, lines_text};
+use super::registers::{Register, RegisterWrite};
+use super::text_object::{char_class, find_close};
+use super::{VimAction, VimMode, VimMotion, VimState};
+use crate::event::{Key, KeyEvent};
+use crate::utils::Document;
+
+/// Deepest nesting of `.` and macro replays, so a macro that plays itself
+/// stops
+const MAX_REPLAY_DEPTH: usize = 20;
+
+/// Text buffer that Vim actions edit
+///
+/// Positions are `(line, column)` in chars, like [`Document::char_to_pos`].
+pub trait VimBuffer {
+    /// Document being edited
+    fn document(&self) -> &Document;
+
+    /// Cursor position
+    fn cursor(&self) -> (usize, usize);
+
+    /// Move the cursor
+    fn set_cursor(&mut self, line: usize, col: usize);
+
+    /// Replace a char range with text
+    fn replace(&mut self, range: Range<usize>, text: &str);
+
+    /// Start grouping edits into one undo step
+    fn begin_change(&mut self);
+
+    /// Finish the undo step started with [`VimBuffer::begin_change`]
+    fn end_change(&mut self);
+
+    /// Undo the last change
+    fn undo(&mut self);
+
+    /// Redo the last undone change
+    fn redo(&mut self);
+
+    /// Show a visual selection, or clear it
+    fn set_selection(&mut self, selection: Option<VimSelection>);
+
+    /// Handle a key typed in insert mode, returning whether it was used
+    fn insert_key(&mut self, key: &KeyEvent) -> bool;
+
+    /// Text added by one level of `>`
+    fn indent_unit(&self) -> String {
+        "    ".to_string()
+    }
+}
+
+/// Shape of a visual selection
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VimSelectionKind {
+    /// Characters from anchor to cursor (`v`)
+    Char,
+    /// Whole lines from anchor to cursor (`V`)
+    Line,
+    /// Rectangle with anchor and cursor at opposite corners (`Ctrl+V`)
+    Block,
+}
+
+/// Visual selection, with both ends inclusive
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VimSelection {
+    /// Where the selection started (line, column)
+    pub anchor: (usize, usize),
+    /// Cursor end of the selection (line, column)
+    pub cursor: (usize, usize),
+    /// Selection shape
+    pub kind: VimSelectionKind,
+}
+
+impl VimSelection {
+    /// First and last line of the selection
+    pub fn lines(&self) -> (usize, usize) {
+        (
+            self.anchor.0.min(self.cursor.0),
+            self.anchor.0.max(self.cursor.0),
+        )
+    }
+
+    /// First and last column of a block selection
+    pub fn columns(&self) -> (usize, usize) {
+        (
+            self.anchor.1.min(self.cursor.1),
+            self.anchor.1.max(self.cursor.1),
+        )
+    }
+}
+
+/// Visual selection in progress, as char indices
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Visual {
+    /// Where the selection started
+    anchor: usize,
+    /// Cursor end, which motions move
+    cursor: usize,
+    /// Selection shape
+    kind: VimSelectionKind,
+}
+
+/// Visual block `I`, `A` or `c` waiting for the text typed on the first line
+#[derive(Clone, Copy, Debug)]
+pub(super) struct BlockInsert {
+    /// First line of the block, where the text is typed
+    first_line: usize,
+    /// Last line of the block
+    last_line: usize,
+    /// Column the text goes in
+    col: usize,
+    /// Char index where typing started
+    start: usize,
+    /// Whether short lines are padded with spaces (`A`) or skipped (`I`)
+    pad: bool,
+}
+
+/// Where a motion lands, and how an operator treats it
+#[derive(Clone, Copy, Debug)]
+struct Target {
+    /// Char index
+    at: usize,
+    /// Whether the motion covers whole lines (`j`, `G`)
+    linewise: bool,
+    /// Whether the char at `at` is included (`e`, `f`)
+    inclusive: bool,
+}
+
+impl Target {
+    fn exclusive(at: usize) -> Self {
+        Self {
+            at,
+            linewise: false,
+            inclusive: false,
+        }
+    }
+
+    fn inclusive(at: usize) -> Self {
+        Self {
+            at,
+            linewise: false,
+            inclusive: true,
+        }
+    }
+
+    fn linewise(at: usize) -> Self {
+        Self {
+            at,
+            linewise: true,
+            inclusive: false,
+        }
+    }
+}
+
+/// Char index of the first non-blank in a line
+fn first_non_blank(doc: &Document, line: usize) -> usize {
+    let indent = doc
+        .line(line)
+        .chars()
+        .take_while(|ch| *ch == ' ' || *ch == '\t')
+        .count();
+    doc.line_to_char(line) + indent
+}
+
+/// Leading whitespace of a line
+fn indentation(doc: &Document, line: usize) -> String {
+    doc.line(line)
+        .chars()
+        .take_while(|ch| *ch == ' ' || *ch == '\t')
+        .collect()
+}
+
+/// Character class at a char index, with newlines as blanks
+fn class_at(doc: &Document, at: usize, big: bool) -> u8 {
+    doc.char_at(at).map_or(0, |ch| char_class(ch, big))
+}
+
+/// Start of the next word (`w`), stopping at empty lines
+fn next_word_start(doc: &Document, from: usize, big: bool) -> usize {
+    let len = doc.len_chars();
+    let mut at = from;
+    let class = class_at(doc, at, big);
+    if class != 0 {
+        while at < len && class_at(doc, at, big) == class {
+            at += 1;
+        }
+    }
+    while at < len {
+        let ch = doc.char_at(at);
+        if ch == Some('\n') && at > from && doc.char_at(at - 1) == Some('\n') {
+            // An empty line counts as a word
+            return at;
+        }
+        if class_at(doc, at, big) != 0 {
+            break;
+        }
+        at += 1;
+        if ch == Some('\n') && doc.char_at(at) == Some('\n') {
+            return at;
+        }
+    }
+    at
+}
+
+/// Start of the previous word (`b`)
+fn prev_word_start(doc: &Document, from: usize, big: bool) -> usize {
+    let mut at = from;
+    while at > 0 && class_at(doc, at - 1, big) == 0 {
+        at -= 1;
+        if doc.char_at(at) == Some('\n') && at > 0 && doc.char_at(at - 1) == Some('\n') {
+            return at;
+        }
+    }
+    if at == 0 {
+        return 0;
+    }
+    let class = class_at(doc, at - 1, big);
+    while at > 0 && class_at(doc, at - 1, big) == class {
+        at -= 1;
+    }
+    at
+}
+
+/// End of the word at or after `from` (`e`)
+///
+/// Unless `stay` is set the cursor moves at least one char, so an `e` on
+/// the last char of a word goes to the end of the next one.
+fn word_end(doc: &Document, from: usize, big: bool, stay: bool) -> usize {
+    let len = doc.len_chars();
+    let mut at = if stay { from } else { from + 1 };
+    while at < len && class_at(doc, at, big) == 0 {
+        at += 1;
+    }
+    if at >= len {
+        return len.saturating_sub(1).max(from);
+    }
+    let class = class_at(doc, at, big);
+    while at + 1 < len && class_at(doc, at + 1, big) == class {
+        at += 1;
+    }
+    at
+}
+
+/// Line of the next (or previous) paragraph boundary
+fn paragraph_boundary(doc: &Document, line: usize, forward: bool) -> usize {
+    let last = doc.len_lines() - 1;
+    let blank = |l: usize| doc.line(l).trim().is_empty();
+    if forward {
+        let mut l = line;
+        while l < last && blank(l) {
+            l += 1;
+        }
+        while l < last && !blank(l) {
+            l += 1;
+        }
+        l
+    } else {
+        let mut l = line;
+        while l > 0 && blank(l) {
+            l -= 1;
+        }
+        while l > 0 && !blank(l) {
+            l -= 1;
+        }
+        l
+    }
+}
+
+/// Matching bracket for `%`, from the first bracket at or after `at` on
+/// its line
+fn match_bracket(doc: &Document, at: usize) -> Option<usize> {
+    let (line, col) = doc.char_to_pos(at);
+    let line_start = doc.line_to_char(line);
+    let (offset, ch) = doc
+        .line(line)
+        .chars()
+        .enumerate()
+        .skip(col)
+        .find(|(_, ch)| "()[]{}".contains(*ch))?;
+    let at = line_start + offset;
+    match ch {
+        '(' | '[' | '{' => find_close(doc, at, ch),
+        _ => {
+            let open = match ch {
+                ')' => '(',
+                ']' => '[',
+                _ => '{',
+            };
+            let mut depth = 0usize;
+            for i in (0..at).rev() {
+                match doc.char_at(i) {
+                    Some(c) if c == ch => depth += 1,
+                    Some(c) if c == open => {
+                        if depth == 0 {
+                            return Some(i);
+                        }
+                        depth -= 1;
+                    }
+                    _ => {}
+                }
+            }
+            None
+        }
+    }
+}
+
+impl VimState {
+    /// Handle a key and carry out its action on a buffer
+    ///
+    /// Insert-mode keys that are not Vim commands go to
+    /// [`VimBuffer::insert_key`]. Returns whether the key was used.
+    pub fn process<B: VimBuffer + ?Sized>(&mut self, key: &KeyEvent, buffer: &mut B) -> bool {
+        let mode = self.mode;
+        self.track_change_key(key, mode);
+
+        let action = self.handle_key(key);
+        let handled = if mode == VimMode::Insert && action == VimAction::None {
+            buffer.insert_key(key)
+        } else {
+            // Unmapped Ctrl keys are left to the host
+            !(action == VimAction::None && key.ctrl && mode == VimMode::Normal)
+        };
+
+        self.execute(action.clone(), mode, buffer);
+        self.finish_change(&action, mode);
+
+        if self.mode != VimMode::Insert {
+            self.recording_change = false;
+            if self.change_open {
+                self.change_open = false;
+                buffer.end_change();
+            }
+        }
+        self.sync_cursor(buffer);
+        handled
+    }
+
+    /// Remember the keys of the change being typed, for `.`
+    fn track_change_key(&mut self, key: &KeyEvent, mode: VimMode) {
+        match mode {
+            VimMode::Normal => {
+                if self.is_idle() && !self.recording_change {
+                    self.change_keys.clear();
+                }
+                self.change_keys.push(key.clone());
+            }
+            VimMode::Visual | VimMode::VisualLine | VimMode::VisualBlock | VimMode::Replace => {
+                self.change_keys.push(key.clone());
+            }
+            VimMode::Insert if self.recording_change => self.change_keys.push(key.clone()),
+            _ => {}
+        }
+    }
+
+    /// Store the keys of a finished change for `.`
+    fn finish_change(&mut self, action: &VimAction, mode: VimMode) {
+        let is_change = matches!(
+            action,
+            VimAction::Delete(_)
+                | VimAction::Change(_)
+                | VimAction::PasteAfter
+                | VimAction::PasteBefore
+                | VimAction::ReplaceChar(_)
+                | VimAction::JoinLines
+                | VimAction::Indent
+                | VimAction::Outdent
+                | VimAction::Insert
+                | VimAction::InsertStart
+                | VimAction::Append
+                | VimAction::AppendEnd
+                | VimAction::OpenBelow
+                | VimAction::OpenAbove
+        );
+        if is_change && mode != VimMode::Insert {
+            if self.mode == VimMode::Insert {
+                // The change goes on until Escape
+                self.recording_change = true;
+            } else {
+                self.last_change = std::mem::take(&mut self.change_keys);
+            }
+        } else if mode == VimMode::Insert && *action == VimAction::Escape && self.recording_change {
+            self.recording_change = false;
+            self.last_change = std::mem::take(&mut self.change_keys);
+        }
+    }
+
+    /// Show the visual selection, or keep the cursor on a character
+    fn sync_cursor<B: VimBuffer + ?Sized>(&mut self, buffer: &mut B) {
+        if let Some(visual) = self.visual {
+            let doc = buffer.document();
+            let selection = VimSelection {
+                anchor: doc.char_to_pos(visual.anchor),
+                cursor: doc.char_to_pos(visual.cursor),
+                kind: visual.kind,
+            };
+            buffer.set_selection(Some(selection));
+        } else if self.mode == VimMode::Normal {
+            // Normal mode sits on a char, never after the last one
+            let (line, col) = buffer.cursor();
+            let doc = buffer.document();
+            if line < doc.len_lines() {
+                let max = doc.line_len(line).saturating_sub(1);
+                if col > max {
+                    buffer.set_cursor(line, max);
+                }
+            }
+        }
+    }
+
+    /// Cursor as a char index; the visual end while selecting
+    fn cursor_char<B: VimBuffer + ?Sized>(&self, buffer: &B) -> usize {
+        match self.visual {
+            Some(visual) => visual.cursor,
+            None => {
+                let (line, col) = buffer.cursor();
+                buffer.document().pos_to_char(line, col)
+            }
+        }
+    }
+
+    /// Move the cursor to a char index
+    fn move_to<B: VimBuffer + ?Sized>(&mut self, at: usize, buffer: &mut B) {
+        match self.visual.as_mut() {
+            Some(visual) => visual.cursor = at,
+            None => {
+                let (line, col) = buffer.document().char_to_pos(at);
+                buffer.set_cursor(line, col);
+            }
+        }
+    }
+
+    /// Move the cursor to the first non-blank of a line
+    pub(super) fn move_to_line<B: VimBuffer + ?Sized>(&mut self, line: usize, buffer: &mut B) {
+        let doc = buffer.document();
+        let line = line.min(doc.len_lines() - 1);
+        let at = first_non_blank(doc, line);
+        self.want_col = None;
+        self.move_to(at, buffer);
+    }
+
+    /// Start an undo group that lasts until insert mode ends
+    fn open_change<B: VimBuffer + ?Sized>(&mut self, buffer: &mut B) {
+        if !self.change_open {
+            buffer.begin_change();
+            self.change_open = true;
+        }
+    }
+
+    /// Carry out an action, given the mode its key was typed in
+    fn execute<B: VimBuffer + ?Sized>(&mut self, action: VimAction, mode: VimMode, buffer: &mut B) {
+        let count = self.last_count;
+        if !matches!(action, VimAction::Move(_) | VimAction::None) {
+            self.want_col = None;
+        }
+        let visual = matches!(
+            mode,
+            VimMode::Visual | VimMode::VisualLine | VimMode::VisualBlock
+        );
+        match action {
+            VimAction::None | VimAction::EnterSearch => {}
+            VimAction::Move(motion) => self.move_motion(motion, mode, buffer),
+            VimAction::Delete(motion) => self.operate('d', motion, visual, buffer),
+            VimAction::Yank(motion) => self.operate('y', motion, visual, buffer),
+            VimAction::Change(motion) => self.operate('c', motion, visual, buffer),
+            VimAction::PasteAfter => self.paste(true, visual, buffer),
+            VimAction::PasteBefore => self.paste(false, visual, buffer),
+            VimAction::Undo => {
+                for _ in 0..count {
+                    buffer.undo();
+                }
+            }
+            VimAction::Redo => {
+                for _ in 0..count {
+                    buffer.redo();
+                }
+            }
+            VimAction::Insert => self.open_change(buffer),
+            VimAction::InsertStart | VimAction::AppendEnd if mode == VimMode::VisualBlock => {
+                self.block_insert(action == VimAction::AppendEnd, buffer);
+            }
+            VimAction::InsertStart => {
+                let line = buffer.cursor().0;
+                let at = first_non_blank(buffer.document(), line);
+                self.move_to(at, buffer);
+                self.open_change(buffer);
+            }
+            VimAction::Append => {
+                let (line, col) = buffer.cursor();
+                let len = buffer.document().line_len(line);
+                buffer.set_cursor(line, (col + 1).min(len));
+                self.open_change(buffer);
+            }
+            VimAction::AppendEnd => {
+                let line = buffer.cursor().0;
+                let len = buffer.document().line_len(line);
+                buffer.set_cursor(line, len);
+                self.open_change(buffer);
+            }
+            VimAction::OpenBelow | VimAction::OpenAbove => {
+                self.open_change(buffer);
+                let line = buffer.cursor().0;
+                let doc = buffer.document();
+                let indent = indentation(doc, line);
+                if action == VimAction::OpenBelow {
+                    let at = doc.line_to_char(line) + doc.line_len(line);
+                    buffer.replace(at..at, &format!("\n{}", indent));
+                    buffer.set_cursor(line + 1, indent.chars().count());
+                } else {
+                    let at = doc.line_to_char(line);
+                    buffer.replace(at..at, &format!("{}\n", indent));
+                    buffer.set_cursor(line, indent.chars().count());
+                }
+            }
+            VimAction::ReplaceChar(ch) => {
+                let (line, col) = buffer.cursor();
+                let doc = buffer.document();
+                if col + count <= doc.line_len(line) {
+                    let at = doc.pos_to_char(line, col);
+                    buffer.replace(at..at + count, &ch.to_string().repeat(count));
+                    buffer.set_cursor(line, col + count - 1);
+                }
+            }
+            VimAction::EnterVisual | VimAction::EnterVisualLine | VimAction::EnterVisualBlock => {
+                let kind = match action {
+                    VimAction::EnterVisual => VimSelectionKind::Char,
+                    VimAction::EnterVisualLine => VimSelectionKind::Line,
+                    _ => VimSelectionKind::Block,
+                };
+                match self.visual.as_mut() {
+                    Some(visual) => visual.kind = kind,
+                    None => {
+                        let at = self.cursor_char(buffer);
+                        self.visual = Some(Visual {
+                            anchor: at,
+                            cursor: at,
+                            kind,
+                        });
+                    }
+                }
+            }
+            VimAction::EnterCommand => {
+                // `:` from visual mode works on the '<,'> lines
+                if visual {
+                    self.end_visual(buffer);
+                }
+            }
+            VimAction::Escape => match mode {
+                VimMode::Insert => {
+                    if let Some(block) = self.block_insert.take() {
+                        self.finish_block_insert(block, buffer);
+                    }
+                    let (line, col) = buffer.cursor();
+                    buffer.set_cursor(line, col.saturating_sub(1));
+                }
+                _ if visual => self.end_visual(buffer),
+                _ => {}
+            },
+            VimAction::Repeat => self.repeat(buffer),
+            VimAction::JoinLines => {
+                let (first, last) = match self.visual_lines(buffer.document()) {
+                    Some((first, last)) if visual => (first, last.max(first + 1)),
+                    _ => {
+                        let line = buffer.cursor().0;
+                        (line, line + count.max(2) - 1)
+                    }
+                };
+                self.end_visual(buffer);
+                self.join_lines(first, last, buffer);
+            }
+            VimAction::Indent | VimAction::Outdent => {
+                let line = buffer.cursor().0;
+                let (first, last, levels) = match self.visual_lines(buffer.document()) {
+                    Some((first, last)) if visual => (first, last, count),
+                    _ => (line, line + count - 1, 1),
+                };
+                self.end_visual(buffer);
+                let last = last.min(buffer.document().len_lines() - 1);
+                buffer.begin_change();
+                for _ in 0..levels {
+                    self.shift_lines(first, last, action == VimAction::Indent, buffer);
+                }
+                buffer.end_change();
+                self.move_to_line(first, buffer);
+            }
+            VimAction::ExecuteCommand(cmd) => {
+                if let Some(result) = self.run_ex(&cmd, buffer) {
+                    self.command_result = Some(result);
+                }
+            }
+            VimAction::SetMark(name) => {
+                let cursor = buffer.cursor();
+                self.marks.insert(name, cursor);
+            }
+            VimAction::RecordMacro(_) | VimAction::StopRecording => {}
+            VimAction::PlayMacro(name) => {
+                let name = if name == '@' {
+                    match self.last_macro {
+                        Some(name) => name,
+                        None => return,
+                    }
+                } else {
+                    name.to_ascii_lowercase()
+                };
+                let Some(keys) = self.macros.get(&name).cloned() else {
+                    return;
+                };
+                self.last_macro = Some(name);
+                self.replay(&keys, count, buffer);
+            }
+        }
+    }
+
+    /// Process keys again, `times` times over
+    fn replay<B: VimBuffer + ?Sized>(&mut self, keys: &[KeyEvent], times: usize, buffer: &mut B) {
+        if self.replay_depth >= MAX_REPLAY_DEPTH {
+            return;
+        }
+        self.replay_depth += 1;
+        for _ in 0..times {
+            for key in keys {
+                self.process(key, buffer);
+            }
+        }
+        self.replay_depth -= 1;
+    }
+
+    /// Repeat the last change (`.`), with a new count when one was typed
+    fn repeat<B: VimBuffer + ?Sized>(&mut self, buffer: &mut B) {
+        let mut keys = self.last_change.clone();
+        if keys.is_empty() {
+            return;
+        }
+        if self.last_count_typed {
+            let is_count = |key: &KeyEvent| matches!(key.key, Key::Char(ch) if ch.is_ascii_digit());
+            if !matches!(keys[0].key, Key::Char('0')) {
+                let digits = keys.iter().take_while(|key| is_count(key)).count();
+                keys.drain(..digits);
+            }
+            let count: Vec<KeyEvent> = self
+                .last_count
+                .to_string()
+                .chars()
+                .map(|ch| KeyEvent::new(Key::Char(ch)))
+                .collect();
+            keys.splice(0..0, count);
+        }
+        self.replay(&keys, 1, buffer);
+    }
+
+    /// Where a motion lands from `from`
+    fn motion_target(
+        &self,
+        motion: &VimMotion,
+        count: usize,
+        from: usize,
+        mode: VimMode,
+        doc: &Document,
+    ) -> Option<Target> {
+        let (line, col) = doc.char_to_pos(from);
+        let last_line = doc.len_lines() - 1;
+        let line_start = doc.line_to_char(line);
+        let line_len = doc.line_len(line);
+        let target = match motion {
+            VimMotion::Left => Target::exclusive(line_start + col.saturating_sub(count)),
+            VimMotion::Right => Target::exclusive(line_start + (col + count).min(line_len)),
+            VimMotion::Up | VimMotion::Down => {
+                let to = if *motion == VimMotion::Up {
+                    line.checked_sub(count)?
+                } else if line + count <= last_line {
+                    line + count
+                } else {
+                    return None;
+                };
+                let col = self.want_col.unwrap_or(col);
+                Target::linewise(doc.pos_to_char(to, col))
+            }
+            VimMotion::Word => {
+                let mut at = from;
+                for _ in 0..count {
+                    at = next_word_start(doc, at, false);
+                }
+                Target::exclusive(at)
+            }
+            VimMotion::WordBack => {
+                let mut at = from;
+                for _ in 0..count {
+                    at = prev_word_start(doc, at, false);
+                }
+                Target::exclusive(at)
+            }
+            VimMotion::WordEnd => {
+                let mut at = from;
+                for _ in 0..count {
+                    at = word_end(doc, at, false, false);
+                }
+                Target::inclusive(at)
+            }
+            VimMotion::LineStart => Target::exclusive(line_start),
+            VimMotion::LineEnd => {
+                let to = (line + count - 1).min(last_line);
+                Target::exclusive(doc.line_to_char(to) + doc.line_len(to))
+            }
+            VimMotion::FirstNonBlank => Target::exclusive(first_non_blank(doc, line)),
+            VimMotion::GoToLine(to) => {
+                let to = to.map_or(last_line, |to| to.saturating_sub(1).min(last_line));
+                Target::linewise(first_non_blank(doc, to))
+            }
+            VimMotion::FindChar(ch) | VimMotion::TillChar(ch) => {
+                let chars: Vec<char> = doc.line(line).chars().collect();
+                let till = matches!(motion, VimMotion::TillChar(_));
+                // A repeated `t` must not stop right before the same char
+                let skip = usize::from(till && chars.get(col + 1) == Some(ch));
+                let found = (col + 1 + skip..chars.len())
+                    .filter(|&i| chars[i] == *ch)
+                    .nth(count - 1)?;
+                Target::inclusive(line_start + if till { found - 1 } else { found })
+            }
+            VimMotion::FindCharBack(ch) | VimMotion::TillCharBack(ch) => {
+                let chars: Vec<char> = doc.line(line).chars().collect();
+                let till = matches!(motion, VimMotion::TillCharBack(_));
+                let skip = usize::from(till && col >= 1 && chars.get(col - 1) == Some(ch));
+                let found = (0..col.saturating_sub(skip))
+                    .rev()
+                    .filter(|&i| chars[i] == *ch)
+                    .nth(count - 1)?;
+                Target::exclusive(line_start + if till { found + 1 } else { found })
+            }
+            VimMotion::ParagraphForward | VimMotion::ParagraphBack => {
+                let forward = *motion == VimMotion::ParagraphForward;
+                let mut to = line;
+                for _ in 0..count {
+                    to = paragraph_boundary(doc, to, forward);
+                }
+                if forward && to == last_line && !doc.line(to).trim().is_empty() {
+                    Target::exclusive(doc.len_chars())
+                } else {
+                    Target::exclusive(doc.line_to_char(to))
+                }
+            }
+            VimMotion::MatchBracket => Target::inclusive(match_bracket(doc, from)?),
+            VimMotion::SearchNext | VimMotion::SearchPrev => {
+                // Straight after `/` or `?` the direction is the typed one
+                let next = *motion == VimMotion::SearchNext;
+                let forward = if mode == VimMode::Search {
+                    next
+                } else {
+                    next == self.search_forward
+                };
+                let text = doc.text();
+                let starts: Vec<usize> = find_matches(&text, &self.last_search, false)
+                    .into_iter()
+                    .map(|m| doc.byte_to_char(m.start))
+                    .collect();
+                if starts.is_empty() {
+                    return None;
+                }
+                let mut at = from;
+                for _ in 0..count {
+                    at = if forward {
+                        starts
+                            .iter()
+                            .copied()
+                            .find(|&s| s > at)
+                            .unwrap_or(starts[0])
+                    } else {
+                        starts
+                            .iter()
+                            .copied()
+                            .rev()
+                            .find(|&s| s < at)
+                            .unwrap_or(starts[starts.len() - 1])
+                    };
+                }
+                Target::exclusive(at)
+            }
+            VimMotion::Mark(name) => {
+                let (line, col) = self.marks.get(name)?;
+                Target::exclusive(doc.pos_to_char(*line, *col))
+            }
+            VimMotion::MarkLine(name) => {
+                let (line, _) = self.marks.get(name)?;
+                Target::linewise(first_non_blank(doc, (*line).min(last_line)))
+            }
+            VimMotion::Inner(_) | VimMotion::Around(_) => return None,
+        };
+        Some(target)
+    }
+
+    /// Move the cursor (or the visual end) by a motion
+    fn move_motion<B: VimBuffer + ?Sized>(
+        &mut self,
+        motion: VimMotion,
+        mode: VimMode,
+        buffer: &mut B,
+    ) {
+        let count = self.last_count;
+        let from = self.cursor_char(buffer);
+        let doc = buffer.document();
+
+        if let VimMotion::Inner(object) | VimMotion::Around(object) = motion {
+            // Text objects only move in visual mode, where they select
+            let inner = matches!(motion, VimMotion::Inner(_));
+            let Some(visual) = self.visual.as_mut() else {
+                return;
+            };
+            if let Some((range, linewise)) = object.range(doc, from, inner, count) {
+                if range.is_empty() {
+                    return;
+                }
+                visual.anchor = range.start;
+                visual.cursor = range.end - 1;
+                if linewise && visual.kind == VimSelectionKind::Char {
+                    visual.kind = VimSelectionKind::Line;
+                    self.mode = VimMode::VisualLine;
+                }
+            }
+            return;
+        }
+
+        let Some(target) = self.motion_target(&motion, count, from, mode, doc) else {
+            return;
+        };
+        let (line, col) = doc.char_to_pos(target.at);
+        // The cursor rests on a char, and at most just past the last line
+        let line_len = doc.line_len(line);
+        let at = doc.pos_to_char(line, col.min(line_len.saturating_sub(1)));
+        let current_col = doc.char_to_pos(from).1;
+
+        if matches!(motion, VimMotion::SearchNext | VimMotion::SearchPrev) {
+            self.marks.insert('\'', doc.char_to_pos(from));
+        }
+        self.want_col = match motion {
+            VimMotion::Up | VimMotion::Down => Some(self.want_col.unwrap_or(current_col)),
+            VimMotion::LineEnd => Some(usize::MAX),
+            _ => None,
+        };
+        self.move_to(at, buffer);
+    }
+
+    /// Range an operator works on, and whether it is whole lines
+    fn operator_range<B: VimBuffer + ?Sized>(
+        &self,
+        op: char,
+        motion: &VimMotion,
+        buffer: &B,
+    ) -> Option<(Range<usize>, bool)> {
+        let count = self.last_count;
+        let doc = buffer.document();
+        let from = self.cursor_char(buffer);
+        let (line, _) = doc.char_to_pos(from);
+
+        if self.last_linewise {
+            // Doubled operator: `count` lines from the cursor
+            let last = (line + count - 1).min(doc.len_lines() - 1);
+            return Some((doc.line_to_char(line)..doc.line_to_char(last), true));
+        }
+        if let VimMotion::Inner(object) | VimMotion::Around(object) = motion {
+            let inner = matches!(motion, VimMotion::Inner(_));
+            let (range, linewise) = object.range(doc, from, inner, count)?;
+            if linewise {
+                let first = doc.char_to_line(range.start);
+                let last = doc.char_to_line(range.end.saturating_sub(1).max(range.start));
+                return Some((doc.line_to_char(first)..doc.line_to_char(last), true));
+            }
+            return Some((range, false));
+        }
+
+        // `cw` on a word changes to its end, like `ce`
+        let target = if op == 'c' && *motion == VimMotion::Word && class_at(doc, from, false) != 0 {
+            let mut at = word_end(doc, from, false, true);
+            for _ in 1..count {
+                at = word_end(doc, at, false, false);
+            }
+            Target::inclusive(at)
+        } else {
+            self.motion_target(motion, count, from, VimMode::Normal, doc)?
+        };
+
+        if target.linewise {
+            let (a, b) = (from.min(target.at), from.max(target.at));
+            return Some((a..b, true));
+        }
+        let start = from.min(target.at);
+        let mut end = from.max(target.at);
+        if target.inclusive {
+            end = (end + 1).min(doc.len_chars());
+        }
+        if *motion == VimMotion::Word {
+            // `dw` on the last word of a line stops at the line end
+            let text = doc.slice(start..end);
+            if let Some(newline) = text.rfind('\n') {
+                if text[newline + 1..].chars().all(char::is_whitespace) {
+                    end = start + text[..newline].chars().count();
+                }
+            }
+        }
+        Some((start..end, false))
+    }
+
+    /// Delete, yank or change over a motion, or over the visual selection
+    fn operate<B: VimBuffer + ?Sized>(
+        &mut self,
+        op: char,
+        motion: Option<VimMotion>,
+        visual: bool,
+        buffer: &mut B,
+    ) {
+        if visual {
+            self.operate_visual(op, buffer);
+            return;
+        }
+        let Some(motion) = motion else {
+            return;
+        };
+        let Some((range, linewise)) = self.operator_range(op, &motion, buffer) else {
+            return;
+        };
+        if range.is_empty() && !linewise && op != 'c' {
+            return;
+        }
+
+        if linewise {
+            // The range spans the starts of the first and last lines
+            let doc = buffer.document();
+            let first = doc.char_to_line(range.start);
+            let last = doc.char_to_line(range.end);
+            self.operate_lines(op, first, last, buffer);
+        } else {
+            self.operate_chars(op, range, buffer);
+        }
+    }
+
+    /// Operator over a char range
+    fn operate_chars<B: VimBuffer + ?Sized>(
+        &mut self,
+        op: char,
+        range: Range<usize>,
+        buffer: &mut B,
+    ) {
+        let text = buffer.document().slice(range.clone());
+        let kind = if op == 'y' {
+            RegisterWrite::Yank
+        } else {
+            RegisterWrite::Delete
+        };
+        self.registers.write(
+            self.last_register,
+            Register {
+                text,
+                linewise: false,
+            },
+            kind,
+        );
+        match op {
+            'y' => self.move_to(range.start, buffer),
+            'd' => {
+                buffer.replace(range.clone(), "");
+                self.move_to(range.start, buffer);
+            }
+            _ => {
+                self.open_change(buffer);
+                buffer.replace(range.clone(), "");
+                self.move_to(range.start, buffer);
+                self.set_mode(VimMode::Insert);
+            }
+        }
+    }
+
+    /// Operator over whole lines
+    fn operate_lines<B: VimBuffer + ?Sized>(
+        &mut self,
+        op: char,
+        first: usize,
+        last: usize,
+        buffer: &mut B,
+    ) {
+        let doc = buffer.document();
+        let text = lines_text(doc, first, last);
+        let kind = if op == 'y' {
+            RegisterWrite::Yank
+        } else {
+            RegisterWrite::Delete
+        };
+        self.registers.write(
+            self.last_register,
+            Register {
+                text,
+                linewise: true,
+            },
+            kind,
+        );
+        match op {
+            'y' => {
+                let (line, col) = buffer.cursor();
+                if first < line {
+                    buffer.set_cursor(first, col);
+                }
+            }
+            'd' => {
+                let range = delete_lines_range(doc, first, last);
+                buffer.replace(range, "");
+                self.move_to_line(first, buffer);
+            }
+            _ => {
+                // `cc` keeps the indentation of the first line
+                let indent = indentation(doc, first);
+                let start = doc.line_to_char(first);
+                let end = doc.line_to_char(last) + doc.line_len(last);
+                self.open_change(buffer);
+                buffer.replace(start..end, &indent);
+                buffer.set_cursor(first, indent.chars().count());
+                self.set_mode(VimMode::Insert);
+            }
+        }
+    }
+
+    /// First and last line of the visual selection
+    fn visual_lines(&self, doc: &Document) -> Option<(usize, usize)> {
+        let visual = self.visual?;
+        let a = doc.char_to_line(visual.anchor);
+        let b = doc.char_to_line(visual.cursor);
+        Some((a.min(b), a.max(b)))
+    }
+
+    /// Line, first column and last column of each row of a block selection
+    fn block_rows(visual: Visual, doc: &Document) -> (Range<usize>, usize, usize) {
+        let (al, ac) = doc.char_to_pos(visual.anchor);
+        let (cl, cc) = doc.char_to_pos(visual.cursor);
+        (al.min(cl)..al.max(cl) + 1, ac.min(cc), ac.max(cc))
+    }
+
+    /// Operator over the visual selection
+    fn operate_visual<B: VimBuffer + ?Sized>(&mut self, op: char, buffer: &mut B) {
+        let Some(visual) = self.visual else {
+            return;
+        };
+        let doc = buffer.document();
+        match visual.kind {
+            VimSelectionKind::Char => {
+                let start = visual.anchor.min(visual.cursor);
+                let end = (visual.anchor.max(visual.cursor) + 1).min(doc.len_chars());
+                self.end_visual(buffer);
+                self.operate_chars(op, start..end, buffer);
+            }
+            VimSelectionKind::Line => {
+                let (first, last) = self.visual_lines(doc).unwrap_or((0, 0));
+                self.end_visual(buffer);
+                self.operate_lines(op, first, last, buffer);
+                if op == 'y' {
+                    self.move_to_line(first, buffer);
+                }
+            }
+            VimSelectionKind::Block => {
+                let (rows, cmin, cmax) = Self::block_rows(visual, doc);
+                let text = rows
+                    .clone()
+                    .map(|line| {
+                        let chars: Vec<char> = doc.line(line).chars().collect();
+                        let end = (cmax + 1).min(chars.len());
+                        chars[cmin.min(end)..end].iter().collect::<String>()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let kind = if op == 'y' {
+                    RegisterWrite::Yank
+                } else {
+                    RegisterWrite::Delete
+                };
+                self.registers.write(
+                    self.last_register,
+                    Register {
+                        text,
+                        linewise: false,
+                    },
+                    kind,
+                );
+                self.end_visual(buffer);
+                if op == 'c' {
+                    self.open_change(buffer);
+                } else if op == 'd' {
+                    buffer.begin_change();
+                }
+                if op != 'y' {
+                    for line in rows.clone().rev() {
+                        let doc = buffer.document();
+                        let len = doc.line_len(line);
+                        if len > cmin {
+                            let start = doc.pos_to_char(line, cmin);
+                            let end = doc.pos_to_char(line, cmax + 1);
+                            buffer.replace(start..end, "");
+                        }
+                    }
+                }
+                if op == 'd' {
+                    buffer.end_change();
+                }
+                buffer.set_cursor(rows.start, cmin);
+                if op == 'c' {
+                    let start = buffer.document().pos_to_char(rows.start, cmin);
+                    self.block_insert = Some(BlockInsert {
+                        first_line: rows.start,
+                        last_line: rows.end - 1,
+                        col: cmin,
+                        start,
+                        pad: false,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Visual block `I` or `A`: type on the first line, then copy the text
+    /// to the others on Escape
+    fn block_insert<B: VimBuffer + ?Sized>(&mut self, append: bool, buffer: &mut B) {
+        let Some(visual) = self.visual else {
+            return;
+        };
+        let (rows, cmin, cmax) = Self::block_rows(visual, buffer.document());
+        self.end_visual(buffer);
+        self.open_change(buffer);
+
+        let col = if append { cmax + 1 } else { cmin };
+        let doc = buffer.document();
+        let len = doc.line_len(rows.start);
+        if len < col {
+            let at = doc.line_to_char(rows.start) + len;
+            buffer.replace(at..at, &" ".repeat(col - len));
+        }
+        buffer.set_cursor(rows.start, col);
+        let start = buffer.document().pos_to_char(rows.start, col);
+        self.block_insert = Some(BlockInsert {
+            first_line: rows.start,
+            last_line: rows.end - 1,
+            col,
+            start,
+            pad: append,
+        });
+    }
+
+    /// Copy the text typed on the first line of a block to the other lines
+    fn finish_block_insert<B: VimBuffer + ?Sized>(&mut self, block: BlockInsert, buffer: &mut B) {
+        let doc = buffer.document();
+        let (line, col) = buffer.cursor();
+        let end = doc.pos_to_char(line, col);
+        if line != block.first_line || end <= block.start {
+            return;
+        }
+        let typed = doc.slice(block.start..end);
+        if typed.contains('\n') {
+            return;
+        }
+        for line in block.first_line + 1..=block.last_line.min(doc.len_lines() - 1) {
+            let doc = buffer.document();
+            let len = doc.line_len(line);
+            if len < block.col {
+                if block.pad {
+                    let at = doc.line_to_char(line) + len;
+                    let padded = format!("{}{}", " ".repeat(block.col - len), typed);
+                    buffer.replace(at..at, &padded);
+                }
+                continue;
+            }
+            let at = doc.pos_to_char(line, block.col);
+            buffer.replace(at..at, &typed);
+        }
+    }
+
+    /// Leave visual mode, setting the `<` and `>` marks
+    fn end_visual<B: VimBuffer + ?Sized>(&mut self, buffer: &mut B) {
+        let Some(visual) = self.visual.take() else {
+            return;
+        };
+        let doc = buffer.document();
+        let start = doc.char_to_pos(visual.anchor.min(visual.cursor));
+        let end = doc.char_to_pos(visual.anchor.max(visual.cursor));
+        self.marks.insert('<', start);
+        self.marks.insert('>', end);
+        buffer.set_selection(None);
+        let (line, col) = buffer.document().char_to_pos(visual.cursor);
+        buffer.set_cursor(line, col);
+        if matches!(
+            self.mode,
+            VimMode::Visual | VimMode::VisualLine | VimMode::VisualBlock
+        ) {
+            self.set_mode(VimMode::Normal);
+        }
+    }
+
+    /// Put register text after or before the cursor, or over the selection
+    fn paste<B: VimBuffer + ?Sized>(&mut self, after: bool, visual: bool, buffer: &mut B) {
+        let name = self.last_register.unwrap_or('"');
+        let Some(register) = self.registers.get(name) else {
+            return;
+        };
+        if register.text.is_empty() {
+            return;
+        }
+        let count = self.last_count;
+
+        if visual {
+            let Some(selection) = self.visual else {
+                return;
+            };
+            let doc = buffer.document();
+            let (range, linewise) = match selection.kind {
+                VimSelectionKind::Line => {
+                    let (first, last) = self.visual_lines(doc).unwrap_or((0, 0));
+                    let start = doc.line_to_char(first);
+                    let end = doc.line_to_char(last) + doc.line_len(last);
+                    (start..end, true)
+                }
+                _ => {
+                    let start = selection.anchor.min(selection.cursor);
+                    let end = (selection.anchor.max(selection.cursor) + 1).min(doc.len_chars());
+                    (start..end, false)
+                }
+            };
+            let replaced = doc.slice(range.clone());
+            let mut text = register.text.repeat(count);
+            if linewise || register.linewise {
+                // Lines replace lines without an extra newline
+                if text.ends_with('\n') {
+                    text.pop();
+                }
+            }
+            self.end_visual(buffer);
+            buffer.replace(range.clone(), &text);
+            self.move_to(range.start, buffer);
+            self.registers.write(
+                None,
+                Register {
+                    text: if linewise {
+                        format!("{}\n", replaced)
+                    } else {
+                        replaced
+                    },
+                    linewise,
+                },
+                RegisterWrite::Delete,
+            );
+            return;
+        }
+
+        let (line, col) = buffer.cursor();
+        let doc = buffer.document();
+        let text = register.text.repeat(count);
+        if register.linewise {
+            let (at, text, target_line) = if !after {
+                (doc.line_to_char(line), text, line)
+            } else if line + 1 < doc.len_lines() {
+                (doc.line_to_char(line + 1), text, line + 1)
+            } else {
+                // After the last line: the newline goes first
+                let body = text.strip_suffix('\n').unwrap_or(&text);
+                (doc.len_chars(), format!("\n{}", body), line + 1)
+            };
+            buffer.replace(at..at, &text);
+            self.move_to_line(target_line, buffer);
+        } else {
+            let len = doc.line_len(line);
+            let at = if after && len > 0 {
+                doc.pos_to_char(line, (col + 1).min(len))
+            } else {
+                doc.pos_to_char(line, col)
+            };
+            buffer.replace(at..at, &text);
+            let end = at + text.chars().count();
+            self.move_to(end.saturating_sub(1).max(at), buffer);
+        }
+    }
+
+    /// Join lines `first..=last` with single spaces, as one undo step
+    fn join_lines<B: VimBuffer + ?Sized>(&mut self, first: usize, last: usize, buffer: &mut B) {
+        let last = last.min(buffer.document().len_lines() - 1);
+        if first >= last {
+            return;
+        }
+        buffer.begin_change();
+        let mut join_at = 0;
+        for _ in first..last {
+            let doc = buffer.document();
+            let line_text = doc.line(first);
+            let next = doc.line(first + 1);
+            let leading = next
+                .chars()
+                .take_while(|ch| *ch == ' ' || *ch == '\t')
+                .count();
+            let rest = &next[next
+                .char_indices()
+                .nth(leading)
+                .map_or(next.len(), |(i, _)| i)..];
+            let separator = if line_text.is_empty()
+                || line_text.ends_with(char::is_whitespace)
+                || rest.is_empty()
+                || rest.starts_with(')')
+            {
+                ""
+            } else {
+                " "
+            };
+            let newline = doc.line_to_char(first) + doc.line_len(first);
+            buffer.replace(newline..newline + 1 + leading, separator);
+            join_at = newline;
+        }
+        buffer.end_change();
+        self.move_to(join_at, buffer);
+    }
+
+    /// Indent or outdent lines `first..=last` by one level
+    pub(super) fn shift_lines<B: VimBuffer + ?Sized>(
+        &mut self,
+        first: usize,
+        last: usize,
+        right: bool,
+        buffer: &mut B,
+    ) {
+        let unit = buffer.indent_unit();
+        let width = unit.chars().count().max(1);
+        for line in first..=last.min(buffer.document().len_lines() - 1) {
+            let doc = buffer.document();
+            let text = doc.line(line);
+            let start = doc.line_to_char(line);
+            if right {
+                if !text.is_empty() {
+                    buffer.replace(start..start, &unit);
+                }
+                continue;
+            }
+            // A tab is a whole level; spaces count up to the unit width
+            let remove = match text.chars().next() {
+                Some('\t') => 1,
+                _ => text.chars().take(width).take_while(|ch| *ch == ' ').count(),
+            };
+            if remove > 0 {
+                buffer.replace(start..start + remove, "");
+            }
+        }
+    }
+}
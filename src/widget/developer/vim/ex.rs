@@ -0,0 +1,374 @@
+//! Ex command line: line ranges, `:s`, `:d`, `:y`, `:>`, `:<` and jumps
+//!
+//! Ranges are `%`, `N`, `.`, `$` and marks like `'a` or `'<`, each with an
+//! optional `+N`/`-N` offset, joined by a comma. Commands the buffer cannot
+//! carry out (`:w`, `:q`, ...) go through [`VimState::execute_command`] for
+//! the host to handle.
+
+use std::ops::Range;
+
+use super::buffer::VimBuffer;
+use super::registers::{Register, RegisterWrite};
+use super::{VimCommandResult, VimState};
+use crate::utils::Document;
+
+/// Byte ranges of every non-empty match of a search pattern
+///
+/// With the `regex` feature the pattern is a regular expression, with Vim's
+/// `\<` and `\>` as word boundaries; an invalid one is matched literally.
+pub(super) fn find_matches(text: &str, pattern: &str, ignore_case: bool) -> Vec<Range<usize>> {
+    if pattern.is_empty() {
+        return Vec::new();
+    }
+    #[cfg(feature = "regex")]
+    {
+        build_regex(pattern, ignore_case)
+            .find_iter(text)
+            .filter(|m| !m.is_empty())
+            .map(|m| m.range())
+            .collect()
+    }
+    #[cfg(not(feature = "regex"))]
+    {
+        if ignore_case {
+            // Lowercasing can change byte lengths, so only ASCII is folded
+            let lower = text.to_ascii_lowercase();
+            let pattern = pattern.to_ascii_lowercase();
+            return lower
+                .match_indices(&pattern)
+                .map(|(start, m)| start..start + m.len())
+                .collect();
+        }
+        text.match_indices(pattern)
+            .map(|(start, m)| start..start + m.len())
+            .collect()
+    }
+}
+
+/// Compile a Vim search pattern, falling back to a literal match
+#[cfg(feature = "regex")]
+fn build_regex(pattern: &str, ignore_case: bool) -> regex::Regex {
+    let translated = pattern.replace("\\<", "\\b").replace("\\>", "\\b");
+    regex::RegexBuilder::new(&translated)
+        .case_insensitive(ignore_case)
+        .build()
+        .or_else(|_| {
+            regex::RegexBuilder::new(&regex::escape(pattern))
+                .case_insensitive(ignore_case)
+                .build()
+        })
+        .expect("escaped pattern is a valid regex")
+}
+
+/// Replace matches of `pattern` in one line
+///
+/// In the replacement `&` and `\0` stand for the match and `\1`-`\9` for
+/// groups (with the `regex` feature); `\&` is a literal ampersand.
+fn substitute_line(
+    line: &str,
+    pattern: &str,
+    replacement: &str,
+    global: bool,
+    ignore_case: bool,
+) -> Option<String> {
+    #[cfg(feature = "regex")]
+    {
+        let re = build_regex(pattern, ignore_case);
+        if !re.is_match(line) {
+            return None;
+        }
+        let mut template = String::new();
+        let mut chars = replacement.chars();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' => match chars.next() {
+                    Some(d) if d.is_ascii_digit() => template.push_str(&format!("${{{d}}}")),
+                    Some('n') => template.push('\n'),
+                    Some('t') => template.push('\t'),
+                    Some('$') => template.push_str("$$"),
+                    Some(other) => template.push(other),
+                    None => template.push('\\'),
+                },
+                '&' => template.push_str("${0}"),
+                '$' => template.push_str("$$"),
+                _ => template.push(ch),
+            }
+        }
+        let limit = if global { 0 } else { 1 };
+        Some(re.replacen(line, limit, template.as_str()).into_owned())
+    }
+    #[cfg(not(feature = "regex"))]
+    {
+        let matches = find_matches(line, pattern, ignore_case);
+        if matches.is_empty() {
+            return None;
+        }
+        let mut out = String::new();
+        let mut last = 0;
+        for m in matches
+            .into_iter()
+            .take(if global { usize::MAX } else { 1 })
+        {
+            out.push_str(&line[last..m.start]);
+            let mut chars = replacement.chars();
+            while let Some(ch) = chars.next() {
+                match ch {
+                    '\\' => match chars.next() {
+                        Some('0') => out.push_str(&line[m.clone()]),
+                        Some('n') => out.push('\n'),
+                        Some('t') => out.push('\t'),
+                        Some(other) => out.push(other),
+                        None => out.push('\\'),
+                    },
+                    '&' => out.push_str(&line[m.clone()]),
+                    _ => out.push(ch),
+                }
+            }
+            last = m.end;
+        }
+        out.push_str(&line[last..]);
+        Some(out)
+    }
+}
+
+/// Parse one line address, returning a 0-based line and the rest
+fn parse_address<'a>(
+    vim: &VimState,
+    doc: &Document,
+    cmd: &'a str,
+    current: usize,
+) -> (Option<usize>, &'a str) {
+    let last = doc.len_lines() - 1;
+    let (mut line, mut rest) = match cmd.chars().next() {
+        Some('.') => (Some(current), &cmd[1..]),
+        Some('$') => (Some(last), &cmd[1..]),
+        Some('\'') => {
+            let mark = cmd[1..].chars().next();
+            let line = mark.and_then(|mark| vim.mark(mark)).map(|(line, _)| line);
+            let skip = 1 + mark.map_or(0, char::len_utf8);
+            (line, &cmd[skip..])
+        }
+        Some(ch) if ch.is_ascii_digit() => {
+            let digits = cmd.chars().take_while(char::is_ascii_digit).count();
+            let n: usize = cmd[..digits].parse().unwrap_or(1);
+            (Some(n.saturating_sub(1)), &cmd[digits..])
+        }
+        _ => (None, cmd),
+    };
+
+    // Offsets, relative to the current line when there is no address
+    while let Some(sign @ ('+' | '-')) = rest.chars().next() {
+        let digits = rest[1..].chars().take_while(char::is_ascii_digit).count();
+        let n: usize = rest[1..1 + digits].parse().unwrap_or(1);
+        let base = line.unwrap_or(current);
+        line = Some(if sign == '+' {
+            base + n
+        } else {
+            base.saturating_sub(n)
+        });
+        rest = &rest[1 + digits..];
+    }
+    (line.map(|line| line.min(last)), rest)
+}
+
+/// Parse a line range, returning it (first, last) and the command after it
+fn parse_range<'a>(
+    vim: &VimState,
+    doc: &Document,
+    cmd: &'a str,
+    current: usize,
+) -> (Option<(usize, usize)>, &'a str) {
+    if let Some(rest) = cmd.strip_prefix('%') {
+        return (Some((0, doc.len_lines() - 1)), rest);
+    }
+    let (first, rest) = parse_address(vim, doc, cmd, current);
+    let Some(first) = first else {
+        return (None, rest);
+    };
+    match rest.strip_prefix(',') {
+        Some(after) => {
+            let (second, rest) = parse_address(vim, doc, after, current);
+            let second = second.unwrap_or(first);
+            (Some((first.min(second), first.max(second))), rest)
+        }
+        None => (Some((first, first)), rest),
+    }
+}
+
+/// Split `/pattern/replacement/flags` on its (unescaped) delimiter
+fn split_substitute(args: &str) -> Option<(String, String, String)> {
+    let mut chars = args.chars();
+    let delimiter = chars
+        .next()
+        .filter(|ch| !ch.is_alphanumeric() && *ch != ' ')?;
+    let mut parts = vec![String::new()];
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            match chars.next() {
+                // An escaped delimiter is the delimiter itself
+                Some(next) if next == delimiter => parts.last_mut()?.push(next),
+                Some(next) => {
+                    parts.last_mut()?.push('\\');
+                    parts.last_mut()?.push(next);
+                }
+                None => parts.last_mut()?.push('\\'),
+            }
+        } else if ch == delimiter && parts.len() < 3 {
+            parts.push(String::new());
+        } else {
+            parts.last_mut()?.push(ch);
+        }
+    }
+    let mut parts = parts.into_iter();
+    let pattern = parts.next()?;
+    Some((
+        pattern,
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or_default(),
+    ))
+}
+
+impl VimState {
+    /// Run an ex command on the buffer
+    ///
+    /// Returns the command for the host when the buffer has nothing to do
+    /// with it, like `:w` or `:q`.
+    pub(super) fn run_ex<B: VimBuffer + ?Sized>(
+        &mut self,
+        cmd: &str,
+        buffer: &mut B,
+    ) -> Option<VimCommandResult> {
+        let cmd = cmd.trim();
+        let current = buffer.cursor().0;
+        let (range, rest) = parse_range(self, buffer.document(), cmd, current);
+        let rest = rest.trim_start();
+        let (first, last) = range.unwrap_or((current, current));
+
+        if rest.is_empty() {
+            // A bare address jumps to its line
+            if range.is_some() {
+                self.move_to_line(last, buffer);
+            }
+            return None;
+        }
+
+        let name_len = rest
+            .chars()
+            .take_while(char::is_ascii_alphabetic)
+            .count()
+            .max(rest.chars().next().map_or(0, char::len_utf8));
+        let (name, args) = rest.split_at(name_len);
+        match name {
+            "s" | "substitute" => {
+                let Some((pattern, replacement, flags)) = split_substitute(args) else {
+                    return Some(VimCommandResult::Unknown(cmd.to_string()));
+                };
+                let pattern = if pattern.is_empty() {
+                    self.last_search.clone()
+                } else {
+                    pattern
+                };
+                self.substitute(first..last + 1, &pattern, &replacement, &flags, buffer);
+                self.last_search = pattern;
+                None
+            }
+            "d" | "delete" | "y" | "yank" => {
+                let register = args.trim().chars().next();
+                let text = lines_text(buffer.document(), first, last);
+                let kind = if name.starts_with('d') {
+                    RegisterWrite::Delete
+                } else {
+                    RegisterWrite::Yank
+                };
+                self.registers.write(
+                    register,
+                    Register {
+                        text,
+                        linewise: true,
+                    },
+                    kind,
+                );
+                if kind == RegisterWrite::Delete {
+                    let range = delete_lines_range(buffer.document(), first, last);
+                    buffer.replace(range, "");
+                    self.move_to_line(first, buffer);
+                }
+                None
+            }
+            ">" | "<" => {
+                let levels = 1 + args.chars().filter(|&ch| ch.to_string() == name).count();
+                buffer.begin_change();
+                for _ in 0..levels {
+                    self.shift_lines(first, last, name == ">", buffer);
+                }
+                buffer.end_change();
+                None
+            }
+            _ => match self.execute_command(rest) {
+                VimCommandResult::GoToLine(line) => {
+                    self.move_to_line(line.saturating_sub(1), buffer);
+                    None
+                }
+                result => Some(result),
+            },
+        }
+    }
+
+    /// `:s` over a range of lines, as one undo step
+    fn substitute<B: VimBuffer + ?Sized>(
+        &mut self,
+        lines: Range<usize>,
+        pattern: &str,
+        replacement: &str,
+        flags: &str,
+        buffer: &mut B,
+    ) {
+        let global = flags.contains('g');
+        let ignore_case = flags.contains('i');
+        let mut last_changed = None;
+
+        buffer.begin_change();
+        // Bottom up, so earlier line numbers stay valid when a
+        // replacement adds lines
+        for line in lines.rev() {
+            let doc = buffer.document();
+            if line >= doc.len_lines() {
+                continue;
+            }
+            let text = doc.line(line);
+            let Some(new) = substitute_line(&text, pattern, replacement, global, ignore_case)
+            else {
+                continue;
+            };
+            let start = doc.line_to_char(line);
+            let end = start + doc.line_len(line);
+            buffer.replace(start..end, &new);
+            last_changed = last_changed.or(Some(line));
+        }
+        buffer.end_change();
+
+        if let Some(line) = last_changed {
+            self.move_to_line(line, buffer);
+        }
+    }
+}
+
+/// Text of lines `first..=last` as a linewise register
+pub(super) fn lines_text(doc: &Document, first: usize, last: usize) -> String {
+    let start = doc.line_to_char(first);
+    let end = doc.line_to_char(last) + doc.line_len(last);
+    let mut text = doc.slice(start..end);
+    text.push('\n');
+    text
+}
+
+/// Char range that removes lines `first..=last`, with one of the newlines
+/// around them
+pub(super) fn delete_lines_range(doc: &Document, first: usize, last: usize) -> Range<usize> {
+    if last + 1 < doc.len_lines() {
+        doc.line_to_char(first)..doc.line_to_char(last + 1)
+    } else {
+        // Last line: remove the newline before the range instead
+        doc.line_to_char(first).saturating_sub(1)..doc.len_chars()
+    }
+}
@@ -0,0 +1,126 @@
+//! Vim registers
+//!
+//! `"` is the unnamed register that every yank and delete also fills. `0`
+//! holds the last yank, `1`-`9` the last multi-line deletes (shifting down),
+//! `-` the last small delete. `a`-`z` are named, and their uppercase forms
+//! append. `_` discards, and `+`/`*` go through the clipboard.
+
+use std::collections::HashMap;
+
+use crate::utils::Clipboard;
+
+/// Register content
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(super) struct Register {
+    /// Text, ending with a newline when linewise
+    pub text: String,
+    /// Whether the text is whole lines
+    pub linewise: bool,
+}
+
+/// What filled a register, which decides the numbered register it goes to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum RegisterWrite {
+    /// Yanked text
+    Yank,
+    /// Deleted or changed text
+    Delete,
+}
+
+/// All registers
+pub(super) struct Registers {
+    /// Registers by name, `"` being the unnamed one
+    slots: HashMap<char, Register>,
+    /// Clipboard behind `+` and `*`
+    pub clipboard: Clipboard,
+}
+
+impl Registers {
+    /// Create empty registers backed by the system clipboard
+    pub fn new() -> Self {
+        Self {
+            slots: HashMap::new(),
+            clipboard: Clipboard::new(),
+        }
+    }
+
+    /// Check if a character names a register
+    pub fn is_valid(name: char) -> bool {
+        matches!(name, '"' | '-' | '_' | '+' | '*') || name.is_ascii_alphanumeric()
+    }
+
+    /// Text of the unnamed register
+    pub fn unnamed(&self) -> &str {
+        self.slots.get(&'"').map_or("", |register| &register.text)
+    }
+
+    /// Set the unnamed register to charwise text
+    pub fn set_unnamed(&mut self, text: String) {
+        self.slots.insert(
+            '"',
+            Register {
+                text,
+                linewise: false,
+            },
+        );
+    }
+
+    /// Read a register
+    pub fn get(&self, name: char) -> Option<Register> {
+        match name {
+            '_' => None,
+            '+' | '*' => self.clipboard.get().ok().map(|text| Register {
+                linewise: text.ends_with('\n'),
+                text,
+            }),
+            _ => self.slots.get(&name.to_ascii_lowercase()).cloned(),
+        }
+    }
+
+    /// Store yanked or deleted text in a register, or in the numbered ones
+    /// when no register was named
+    pub fn write(&mut self, name: Option<char>, register: Register, kind: RegisterWrite) {
+        let stored = match name {
+            Some('_') => return,
+            Some('+' | '*') => {
+                let _ = self.clipboard.set(&register.text);
+                register
+            }
+            Some(name) if name.is_ascii_uppercase() => {
+                let slot = self.slots.entry(name.to_ascii_lowercase()).or_default();
+                if register.linewise && !slot.text.is_empty() && !slot.text.ends_with('\n') {
+                    slot.text.push('\n');
+                }
+                slot.text.push_str(&register.text);
+                slot.linewise |= register.linewise;
+                slot.clone()
+            }
+            Some(name) if name != '"' => {
+                self.slots.insert(name, register.clone());
+                register
+            }
+            _ => {
+                match kind {
+                    RegisterWrite::Yank => {
+                        self.slots.insert('0', register.clone());
+                    }
+                    RegisterWrite::Delete if register.linewise || register.text.contains('\n') => {
+                        for n in (1..9).rev() {
+                            let from = char::from_digit(n, 10).unwrap_or('1');
+                            if let Some(older) = self.slots.remove(&from) {
+                                self.slots
+                                    .insert(char::from_digit(n + 1, 10).unwrap_or('9'), older);
+                            }
+                        }
+                        self.slots.insert('1', register.clone());
+                    }
+                    RegisterWrite::Delete => {
+                        self.slots.insert('-', register.clone());
+                    }
+                }
+                register
+            }
+        };
+        self.slots.insert('"', stored);
+    }
+}
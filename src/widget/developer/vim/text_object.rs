@@ -0,0 +1,274 @@
+//! Vim text objects (`iw`, `a"`, `i(`, `ap`, ...)
+
+use std::ops::Range;
+
+use crate::utils::Document;
+
+/// Text object selected after `i` or `a`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VimTextObject {
+    /// Word (w)
+    Word,
+    /// Whitespace-delimited WORD (W)
+    BigWord,
+    /// Paragraph between blank lines (p)
+    Paragraph,
+    /// Quoted string on the line, by its quote character (", ', `)
+    Quote(char),
+    /// Bracket block, by its opening bracket ((, [, {, <)
+    Bracket(char),
+}
+
+impl VimTextObject {
+    /// Text object for the key typed after `i` or `a`
+    pub fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            'w' => Some(Self::Word),
+            'W' => Some(Self::BigWord),
+            'p' => Some(Self::Paragraph),
+            '"' | '\'' | '`' => Some(Self::Quote(ch)),
+            '(' | ')' | 'b' => Some(Self::Bracket('(')),
+            '[' | ']' => Some(Self::Bracket('[')),
+            '{' | '}' | 'B' => Some(Self::Bracket('{')),
+            '<' | '>' => Some(Self::Bracket('<')),
+            _ => None,
+        }
+    }
+
+    /// Char range of the object around `at`, and whether it is whole lines
+    pub(super) fn range(
+        self,
+        doc: &Document,
+        at: usize,
+        inner: bool,
+        count: usize,
+    ) -> Option<(Range<usize>, bool)> {
+        match self {
+            Self::Word => word(doc, at, inner, count, false).map(|r| (r, false)),
+            Self::BigWord => word(doc, at, inner, count, true).map(|r| (r, false)),
+            Self::Paragraph => Some((paragraph(doc, at, inner, count), true)),
+            Self::Quote(quote) => quoted(doc, at, quote, inner).map(|r| (r, false)),
+            Self::Bracket(open) => bracket(doc, at, open, inner, count).map(|r| (r, false)),
+        }
+    }
+}
+
+/// Character class for word motions: 0 blank, 1 word, 2 punctuation
+pub(super) fn char_class(ch: char, big: bool) -> u8 {
+    if ch.is_whitespace() {
+        0
+    } else if big || ch.is_alphanumeric() || ch == '_' {
+        1
+    } else {
+        2
+    }
+}
+
+/// Word or WORD on the cursor line, plus surrounding blanks for `aw`
+fn word(doc: &Document, at: usize, inner: bool, count: usize, big: bool) -> Option<Range<usize>> {
+    let (line, col) = doc.char_to_pos(at);
+    let chars: Vec<char> = doc.line(line).chars().collect();
+    if chars.is_empty() {
+        return None;
+    }
+    let col = col.min(chars.len() - 1);
+    let class = |i: usize| char_class(chars[i], big);
+    // Run of same-class characters containing `i`
+    let run = |i: usize| {
+        let start = (0..i)
+            .rev()
+            .find(|&j| class(j) != class(i))
+            .map_or(0, |j| j + 1);
+        let end = (i..chars.len())
+            .find(|&j| class(j) != class(i))
+            .unwrap_or(chars.len());
+        (start, end)
+    };
+
+    let (mut start, mut end) = run(col);
+    for _ in 1..count {
+        if end < chars.len() {
+            end = run(end).1;
+        }
+    }
+    if !inner {
+        if class(col) == 0 {
+            if end < chars.len() {
+                end = run(end).1;
+            }
+        } else if end < chars.len() && class(end) == 0 {
+            end = run(end).1;
+        } else if start > 0 && class(start - 1) == 0 {
+            start = run(start - 1).0;
+        }
+    }
+    let line_start = doc.line_to_char(line);
+    Some(line_start + start..line_start + end)
+}
+
+/// Quoted string on the cursor line
+///
+/// The cursor may be inside the quotes or before the first pair.
+fn quoted(doc: &Document, at: usize, quote: char, inner: bool) -> Option<Range<usize>> {
+    let (line, col) = doc.char_to_pos(at);
+    let chars: Vec<char> = doc.line(line).chars().collect();
+    let quotes: Vec<usize> = (0..chars.len())
+        .filter(|&i| chars[i] == quote && (i == 0 || chars[i - 1] != '\\'))
+        .collect();
+    let (open, close) = quotes
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .find(|&(_, close)| col <= close)?;
+
+    let (mut start, mut end) = if inner {
+        (open + 1, close)
+    } else {
+        (open, close + 1)
+    };
+    if !inner {
+        // Trailing blanks, or leading ones when there are none after
+        let trailing = chars[end..]
+            .iter()
+            .take_while(|c| c.is_whitespace())
+            .count();
+        if trailing > 0 {
+            end += trailing;
+        } else {
+            start -= chars[..start]
+                .iter()
+                .rev()
+                .take_while(|c| c.is_whitespace())
+                .count();
+        }
+    }
+    let line_start = doc.line_to_char(line);
+    Some(line_start + start..line_start + end)
+}
+
+/// Closing bracket of an opening one
+pub(super) fn closing_bracket(open: char) -> char {
+    match open {
+        '(' => ')',
+        '[' => ']',
+        '{' => '}',
+        _ => '>',
+    }
+}
+
+/// Opening bracket enclosing `at`, searching backwards
+///
+/// A closing bracket at `at` itself belongs to the block being searched for.
+fn find_open(doc: &Document, at: usize, open: char, on_close: bool) -> Option<usize> {
+    let close = closing_bracket(open);
+    let mut depth = 0usize;
+    for i in (0..=at).rev() {
+        match doc.char_at(i) {
+            Some(ch) if ch == close && !(on_close && i == at) => depth += 1,
+            Some(ch) if ch == open => {
+                if depth == 0 {
+                    return Some(i);
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Bracket matching the one at `open`, searching forwards
+pub(super) fn find_close(doc: &Document, open_at: usize, open: char) -> Option<usize> {
+    let close = closing_bracket(open);
+    let mut depth = 0usize;
+    for i in open_at + 1..doc.len_chars() {
+        match doc.char_at(i) {
+            Some(ch) if ch == open => depth += 1,
+            Some(ch) if ch == close => {
+                if depth == 0 {
+                    return Some(i);
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Bracket block around the cursor
+///
+/// For a block whose brackets sit on their own lines, the inner range is
+/// the lines between them.
+fn bracket(
+    doc: &Document,
+    at: usize,
+    open: char,
+    inner: bool,
+    count: usize,
+) -> Option<Range<usize>> {
+    let mut open_at = find_open(doc, at.min(doc.len_chars().saturating_sub(1)), open, true)?;
+    for _ in 1..count {
+        open_at = find_open(doc, open_at.checked_sub(1)?, open, false)?;
+    }
+    let close_at = find_close(doc, open_at, open)?;
+    if !inner {
+        return Some(open_at..close_at + 1);
+    }
+
+    let mut start = open_at + 1;
+    let mut end = close_at;
+    if doc.char_at(start) == Some('\n') {
+        start += 1;
+        // Keep the newline before a closing bracket that only has indentation
+        let (close_line, close_col) = doc.char_to_pos(close_at);
+        let line_start = doc.line_to_char(close_line);
+        let blank_before = doc
+            .slice(line_start..line_start + close_col)
+            .chars()
+            .all(char::is_whitespace);
+        if blank_before && line_start >= start {
+            end = line_start;
+        }
+    }
+    Some(start..end.max(start))
+}
+
+/// Paragraph (run of blank or non-blank lines) around the cursor line
+fn paragraph(doc: &Document, at: usize, inner: bool, count: usize) -> Range<usize> {
+    let last = doc.len_lines() - 1;
+    let blank = |line: usize| doc.line(line).trim().is_empty();
+    let run_end = |line: usize| {
+        (line..=last)
+            .find(|&l| blank(l) != blank(line))
+            .map_or(last, |l| l - 1)
+    };
+
+    let line = doc.char_to_pos(at).0;
+    let mut start = (0..line)
+        .rev()
+        .find(|&l| blank(l) != blank(line))
+        .map_or(0, |l| l + 1);
+    let mut end = run_end(line);
+    for _ in 1..count {
+        if end < last {
+            end = run_end(end + 1);
+        }
+    }
+    if !inner {
+        if end < last {
+            end = run_end(end + 1);
+        } else if start > 0 && !blank(line) {
+            start = (0..start - 1)
+                .rev()
+                .find(|&l| !blank(l))
+                .map_or(0, |l| l + 1);
+        }
+    }
+
+    let end = if end < last {
+        doc.line_to_char(end + 1)
+    } else {
+        doc.len_chars()
+    };
+    doc.line_to_char(start)..end
+}
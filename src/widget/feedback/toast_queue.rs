@@ -24,6 +24,7 @@
 use super::toast::{ToastLevel, ToastPosition};
 use crate::render::Cell;
 use crate::style::Color;
+use crate::utils::clock;
 use crate::utils::{char_width, truncate_to_width};
 use crate::widget::theme::DISABLED_FG;
 use crate::widget::traits::{RenderContext, View, WidgetProps};
@@ -84,7 +85,7 @@ impl ToastEntry {
             level,
             priority: ToastPriority::Normal,
             duration: None,
-            created_at: Instant::now(),
+            created_at: clock::now(),
             shown_at: None,
             dismissible: true,
         }
@@ -118,7 +119,7 @@ impl ToastEntry {
     fn is_expired(&self, default_duration: Duration) -> bool {
         if let Some(shown) = self.shown_at {
             let duration = self.duration.unwrap_or(default_duration);
-            shown.elapsed() >= duration
+            clock::elapsed(shown) >= duration
         } else {
             false
         }
@@ -327,7 +328,7 @@ impl ToastQueue {
         // Move toasts from queue to visible
         while self.visible.len() < self.max_visible && !self.queue.is_empty() {
             let mut entry = self.queue.remove(0);
-            entry.shown_at = Some(Instant::now());
+            entry.shown_at = Some(clock::now());
             self.visible.push(entry);
         }
     }
@@ -1,28 +1,56 @@
 //! Edit operations for TextArea undo/redo
 
 /// An edit operation for undo/redo
+#[deprecated(
+    since = "2.75.0",
+    note = "the text area records edits in its `Document`; this type is no longer produced"
+)]
 #[derive(Clone, Debug)]
 pub enum EditOperation {
     /// Insert text at position
     Insert {
+        /// Line number
         line: usize,
+        /// Column number
         col: usize,
+        /// Text to insert
         text: String,
     },
     /// Delete text at position
     Delete {
+        /// Line number
         line: usize,
+        /// Column number
         col: usize,
+        /// Text that was deleted
         text: String,
     },
     /// Insert a new line
-    InsertLine { line: usize, content: String },
+    InsertLine {
+        /// Line number
+        line: usize,
+        /// Line content
+        content: String,
+    },
     /// Delete a line
-    DeleteLine { line: usize, content: String },
+    DeleteLine {
+        /// Line number
+        line: usize,
+        /// Line content
+        content: String,
+    },
     /// Merge with previous line
-    MergeLines { line: usize, col: usize },
+    MergeLines {
+        /// Line number
+        line: usize,
+        /// Column number
+        col: usize,
+    },
     /// Split line at position
-    SplitLine { line: usize, col: usize },
+    SplitLine {
+        /// Line number
+        line: usize,
+        /// Column number
+        col: usize,
+    },
 }
-
-// # KEEP HERE - Private tests that cannot be extracted
@@ -147,9 +147,11 @@ impl TextArea {
         };
 
         // Apply in reverse order to maintain position validity
+        self.doc.begin_transaction();
         for (start, end, replace_with) in replacements.into_iter().rev() {
             self.replace_range(start, end, &replace_with);
         }
+        self.doc.commit_transaction();
 
         self.refresh_matches();
     }
@@ -198,8 +200,8 @@ impl TextArea {
         if options.use_regex {
             self.collect_regex_matches(&query, &options, &mut matches);
         } else {
-            for (line_idx, line) in self.lines.iter().enumerate() {
-                self.find_literal_matches(line_idx, line, &query, &options, &mut matches);
+            for (line_idx, line) in self.doc.lines().enumerate() {
+                self.find_literal_matches(line_idx, &line, &query, &options, &mut matches);
             }
         }
 
@@ -224,8 +226,8 @@ impl TextArea {
         let Some(re) = build_regex(query, options) else {
             return;
         };
-        for (line_idx, line) in self.lines.iter().enumerate() {
-            for m in re.find_iter(line) {
+        for (line_idx, line) in self.doc.lines().enumerate() {
+            for m in re.find_iter(&line) {
                 // Skip zero-width matches (e.g. `a*` matching the empty string):
                 // they can't be highlighted or replaced meaningfully.
                 if m.start() == m.end() {
@@ -250,8 +252,8 @@ impl TextArea {
         options: &FindOptions,
         matches: &mut Vec<FindMatch>,
     ) {
-        for (line_idx, line) in self.lines.iter().enumerate() {
-            self.find_literal_matches(line_idx, line, query, options, matches);
+        for (line_idx, line) in self.doc.lines().enumerate() {
+            self.find_literal_matches(line_idx, &line, query, options, matches);
         }
     }
 
@@ -337,40 +339,9 @@ impl TextArea {
 
     /// Replace text in range
     fn replace_range(&mut self, start: CursorPos, end: CursorPos, replacement: &str) {
-        if start.line == end.line {
-            // Single line replacement
-            if let Some(line) = self.lines.get_mut(start.line) {
-                let before: String = line.chars().take(start.col).collect();
-                let after: String = line.chars().skip(end.col).collect();
-                *line = format!("{}{}{}", before, replacement, after);
-            }
-        } else {
-            // Multi-line replacement
-            let before: String = self
-                .lines
-                .get(start.line)
-                .map(|l| l.chars().take(start.col).collect())
-                .unwrap_or_default();
-            let after: String = self
-                .lines
-                .get(end.line)
-                .map(|l| l.chars().skip(end.col).collect())
-                .unwrap_or_default();
-
-            // Remove lines between start and end
-            for _ in start.line..=end.line {
-                if start.line < self.lines.len() {
-                    self.lines.remove(start.line);
-                }
-            }
-
-            // Insert replacement
-            let new_content = format!("{}{}{}", before, replacement, after);
-            let new_lines: Vec<String> = new_content.lines().map(String::from).collect();
-            for (i, new_line) in new_lines.into_iter().enumerate() {
-                self.lines.insert(start.line + i, new_line);
-            }
-        }
+        let start = self.char_index(start);
+        let end = self.char_index(end);
+        self.doc.replace(start..end, replacement);
     }
 }
 
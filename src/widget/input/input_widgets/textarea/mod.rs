@@ -13,7 +13,7 @@
 
 mod content;
 mod cursor;
-mod edit;
+pub mod edit;
 mod editing;
 mod find_impl;
 mod find_replace;
@@ -22,6 +22,7 @@ mod navigation;
 mod selection;
 mod undo;
 mod view;
+mod vim;
 
 pub use cursor::{Cursor, CursorPos, CursorSet};
 pub use find_replace::{FindMatch, FindOptions, FindReplaceMode, FindReplaceState};
@@ -29,13 +30,12 @@ pub use selection::Selection;
 
 use crate::event::{Key, KeyEvent};
 use crate::style::Color;
+use crate::utils::Document;
+use crate::widget::developer::VimState;
 use crate::widget::syntax::{Language, SyntaxHighlighter, SyntaxTheme};
 use crate::widget::traits::WidgetProps;
 use crate::{impl_props_builders, impl_styled_view};
 
-/// Maximum undo history size
-pub(super) const MAX_UNDO_HISTORY: usize = 100;
-
 /// A multi-line text editor widget
 ///
 /// # Example
@@ -70,16 +70,12 @@ pub(super) const MAX_UNDO_HISTORY: usize = 100;
 /// | `PageUp` | Move cursor up 10 lines |
 /// | `PageDown` | Move cursor down 10 lines |
 pub struct TextArea {
-    /// Lines of text
-    pub(super) lines: Vec<String>,
+    /// Text with undo history
+    pub(super) doc: Document,
     /// Multiple cursors (primary cursor is at index 0)
     pub(super) cursors: CursorSet,
     /// Scroll offset (line, column)
     pub(super) scroll: (usize, usize),
-    /// Undo history
-    pub(super) undo_stack: Vec<edit::EditOperation>,
-    /// Redo history
-    pub(super) redo_stack: Vec<edit::EditOperation>,
     /// Show line numbers
     pub(super) show_line_numbers: bool,
     /// Enable word wrap
@@ -118,17 +114,17 @@ pub struct TextArea {
     pub(super) props: WidgetProps,
     /// Last known viewport height (lines visible), updated during render
     pub(super) last_viewport_height: std::cell::Cell<usize>,
+    /// Vim mode state, when enabled
+    pub(super) vim: Option<VimState>,
 }
 
 impl TextArea {
     /// Create a new empty text area
     pub fn new() -> Self {
         Self {
-            lines: vec![String::new()],
+            doc: Document::new(),
             cursors: CursorSet::default(),
             scroll: (0, 0),
-            undo_stack: Vec::new(),
-            redo_stack: Vec::new(),
             show_line_numbers: false,
             wrap: true,
             read_only: false,
@@ -148,6 +144,7 @@ impl TextArea {
             current_match_bg: None,
             props: WidgetProps::new(),
             last_viewport_height: std::cell::Cell::new(10),
+            vim: None,
         }
     }
 
@@ -355,12 +352,21 @@ impl TextArea {
     /// - `Ctrl+Alt+Up` / `Ctrl+Alt+Down` — add a cursor above / below
     /// - `Esc` — close the find panel, or collapse to a single cursor
     ///
+    /// With [`vim`](Self::vim) enabled, keys go through Vim mode instead
+    /// while the find panel is closed.
+    ///
     /// Returns `true` if the event was handled (a redraw may be needed).
     pub fn handle_key_event(&mut self, event: &KeyEvent) -> bool {
         if !self.focused {
             return false;
         }
 
+        if !self.is_find_open() {
+            if let Some(handled) = self.handle_vim_key(event) {
+                return handled;
+            }
+        }
+
         // Ctrl+Alt cursor stacking (multi-cursor).
         if event.ctrl && event.alt {
             match event.key {
@@ -437,7 +443,7 @@ pub fn textarea() -> TextArea {
     TextArea::new()
 }
 
-// KEEP HERE - Private implementation tests (all tests access private fields: lines, scroll, show_line_numbers, etc.)
+// KEEP HERE - Private implementation tests (all tests access private fields: doc, scroll, show_line_numbers, etc.)
 
 #[cfg(test)]
 mod tests {
@@ -447,8 +453,8 @@ mod tests {
     #[test]
     fn test_textarea_new_creates_empty_editor() {
         let textarea = TextArea::new();
-        assert_eq!(textarea.lines.len(), 1);
-        assert_eq!(textarea.lines[0], "");
+        assert_eq!(textarea.doc.len_lines(), 1);
+        assert_eq!(textarea.doc.line(0), "");
         assert_eq!(textarea.scroll, (0, 0));
         assert!(!textarea.show_line_numbers);
         assert!(textarea.wrap); // wrap defaults to true for intuitive multi-line editing
@@ -463,23 +469,23 @@ mod tests {
     #[test]
     fn test_textarea_default_trait() {
         let textarea = TextArea::default();
-        assert_eq!(textarea.lines.len(), 1);
+        assert_eq!(textarea.doc.len_lines(), 1);
         assert_eq!(textarea.tab_width, 4);
     }
 
     #[test]
     fn test_textarea_content_builder() {
         let textarea = TextArea::new().content("Hello\nWorld");
-        assert_eq!(textarea.lines.len(), 2);
-        assert_eq!(textarea.lines[0], "Hello");
-        assert_eq!(textarea.lines[1], "World");
+        assert_eq!(textarea.doc.len_lines(), 2);
+        assert_eq!(textarea.doc.line(0), "Hello");
+        assert_eq!(textarea.doc.line(1), "World");
     }
 
     #[test]
     fn test_textarea_content_builder_single_line() {
         let textarea = TextArea::new().content("Single line");
-        assert_eq!(textarea.lines.len(), 1);
-        assert_eq!(textarea.lines[0], "Single line");
+        assert_eq!(textarea.doc.len_lines(), 1);
+        assert_eq!(textarea.doc.line(0), "Single line");
     }
 
     #[test]
@@ -633,7 +639,7 @@ mod tests {
             .fg(Color::WHITE)
             .bg(Color::BLACK);
 
-        assert_eq!(textarea.lines[0], "Test content");
+        assert_eq!(textarea.doc.line(0), "Test content");
         assert!(textarea.show_line_numbers);
         assert!(textarea.wrap);
         assert!(!textarea.read_only);
@@ -759,13 +765,13 @@ mod tests {
     #[test]
     fn test_textarea_empty_undo_stack() {
         let textarea = TextArea::new();
-        assert_eq!(textarea.undo_stack.len(), 0);
+        assert!(!textarea.doc.can_undo());
     }
 
     #[test]
     fn test_textarea_empty_redo_stack() {
         let textarea = TextArea::new();
-        assert_eq!(textarea.redo_stack.len(), 0);
+        assert!(!textarea.doc.can_redo());
     }
 
     #[test]
@@ -881,6 +887,6 @@ mod tests {
     fn test_handle_key_event_plain_char_delegates_to_insert() {
         let mut textarea = TextArea::new().focused(true);
         assert!(textarea.handle_key_event(&KeyEvent::new(Key::Char('x'))));
-        assert_eq!(textarea.lines[0], "x");
+        assert_eq!(textarea.doc.line(0), "x");
     }
 }
@@ -5,7 +5,7 @@ use super::cursor::{Cursor, CursorPos};
 impl TextArea {
     /// Add cursor at position (Alt+Click)
     pub fn add_cursor_at(&mut self, line: usize, col: usize) {
-        let line = line.min(self.lines.len().saturating_sub(1));
+        let line = line.min(self.doc.len_lines() - 1);
         let col = col.min(self.line_len(line));
         self.cursors.add_at(CursorPos::new(line, col));
     }
@@ -23,7 +23,7 @@ impl TextArea {
     /// Add cursor below current (Ctrl+Alt+Down)
     pub fn add_cursor_below(&mut self) {
         let primary = self.cursors.primary().pos;
-        if primary.line + 1 < self.lines.len() {
+        if primary.line + 1 < self.doc.len_lines() {
             let new_line = primary.line + 1;
             let new_col = primary.col.min(self.line_len(new_line));
             self.cursors.add_at(CursorPos::new(new_line, new_col));
@@ -38,10 +38,7 @@ impl TextArea {
     /// Get word at cursor position
     fn get_word_at_cursor(&self) -> String {
         let pos = self.cursors.primary().pos;
-        let Some(line) = self.lines.get(pos.line) else {
-            return String::new();
-        };
-        let chars: Vec<char> = line.chars().collect();
+        let chars: Vec<char> = self.doc.line(pos.line).chars().collect();
 
         if chars.is_empty() || pos.col >= chars.len() {
             return String::new();
@@ -80,42 +77,34 @@ impl TextArea {
         }
 
         let text_lower = text.to_lowercase();
+        let line_count = self.doc.len_lines();
+        // Column of the first match at or after `from_col`, in chars
+        let find_in = |line_idx: usize, from_col: usize, to_col: Option<usize>| {
+            let line: Vec<char> = self.doc.line(line_idx).to_lowercase().chars().collect();
+            let end = to_col.unwrap_or(line.len()).min(line.len());
+            let haystack: String = line[from_col.min(end)..end].iter().collect();
+            haystack
+                .find(&text_lower)
+                .map(|byte| from_col + haystack[..byte].chars().count())
+        };
 
         // Search from the position after `from`
-        for line_idx in from.line..self.lines.len() {
-            let Some(line) = self.lines.get(line_idx) else {
-                continue;
-            };
-            let line_lower = line.to_lowercase();
-
+        for line_idx in from.line..line_count {
             let start_col = if line_idx == from.line {
                 from.col + 1
             } else {
                 0
             };
-
-            if start_col < line.len() {
-                if let Some(pos) = line_lower[start_col..].find(&text_lower) {
-                    return Some(CursorPos::new(line_idx, start_col + pos));
-                }
+            if let Some(col) = find_in(line_idx, start_col, None) {
+                return Some(CursorPos::new(line_idx, col));
             }
         }
 
         // Wrap around to beginning
-        for line_idx in 0..=from.line.min(self.lines.len().saturating_sub(1)) {
-            let Some(line) = self.lines.get(line_idx) else {
-                continue;
-            };
-            let line_lower = line.to_lowercase();
-
-            let end_col = if line_idx == from.line {
-                from.col + 1
-            } else {
-                line.len()
-            };
-
-            if let Some(pos) = line_lower[..end_col].find(&text_lower) {
-                let found_pos = CursorPos::new(line_idx, pos);
+        for line_idx in 0..=from.line.min(line_count - 1) {
+            let end_col = (line_idx == from.line).then_some(from.col + 1);
+            if let Some(col) = find_in(line_idx, 0, end_col) {
+                let found_pos = CursorPos::new(line_idx, col);
                 // Don't return if it's the same as one of our existing cursors
                 if !self.cursors.iter().any(|c| c.pos == found_pos) {
                     return Some(found_pos);
@@ -142,7 +131,7 @@ impl TextArea {
             .unwrap_or(CursorPos::new(0, 0));
 
         if let Some(match_pos) = self.find_next_from(&text, last_pos) {
-            let end_col = match_pos.col + text.len();
+            let end_col = match_pos.col + text.chars().count();
             let new_cursor =
                 Cursor::with_selection(CursorPos::new(match_pos.line, end_col), match_pos);
             self.cursors.add(new_cursor);
@@ -1,28 +1,19 @@
 //! Cursor navigation methods for TextArea
 
 impl TextArea {
-    /// Move cursor left
+    /// Move cursor left by one grapheme
     pub fn move_left(&mut self) {
-        let pos = self.cursors.primary().pos;
-        if pos.col > 0 {
-            self.set_primary_cursor(pos.line, pos.col - 1);
-        } else if pos.line > 0 {
-            let new_line = pos.line - 1;
-            let new_col = self.line_len(new_line);
-            self.set_primary_cursor(new_line, new_col);
-        }
+        let at = self.char_index(self.cursors.primary().pos);
+        let (line, col) = self.doc.char_to_pos(self.doc.prev_grapheme(at));
+        self.set_primary_cursor(line, col);
         self.update_selection();
     }
 
-    /// Move cursor right
+    /// Move cursor right by one grapheme
     pub fn move_right(&mut self) {
-        let pos = self.cursors.primary().pos;
-        let line_len = self.line_len(pos.line);
-        if pos.col < line_len {
-            self.set_primary_cursor(pos.line, pos.col + 1);
-        } else if pos.line + 1 < self.lines.len() {
-            self.set_primary_cursor(pos.line + 1, 0);
-        }
+        let at = self.char_index(self.cursors.primary().pos);
+        let (line, col) = self.doc.char_to_pos(self.doc.next_grapheme(at));
+        self.set_primary_cursor(line, col);
         self.update_selection();
     }
 
@@ -40,7 +31,7 @@ impl TextArea {
     /// Move cursor down
     pub fn move_down(&mut self) {
         let pos = self.cursors.primary().pos;
-        if pos.line + 1 < self.lines.len() {
+        if pos.line + 1 < self.doc.len_lines() {
             let new_line = pos.line + 1;
             let new_col = pos.col.min(self.line_len(new_line));
             self.set_primary_cursor(new_line, new_col);
@@ -71,7 +62,7 @@ impl TextArea {
 
     /// Move to end of document
     pub fn move_document_end(&mut self) {
-        let last_line = self.lines.len().saturating_sub(1);
+        let last_line = self.doc.len_lines() - 1;
         let last_col = self.line_len(last_line);
         self.set_primary_cursor(last_line, last_col);
         self.update_selection();
@@ -89,10 +80,7 @@ impl TextArea {
             return;
         }
 
-        let Some(line) = self.lines.get(pos.line) else {
-            return;
-        };
-        let chars: Vec<char> = line.chars().collect();
+        let chars: Vec<char> = self.doc.line(pos.line).chars().collect();
         let mut col = pos.col.min(chars.len());
 
         // Skip spaces
@@ -111,14 +99,11 @@ impl TextArea {
     /// Move cursor by word to the right
     pub fn move_word_right(&mut self) {
         let pos = self.cursors.primary().pos;
-        let Some(line) = self.lines.get(pos.line) else {
-            return;
-        };
-        let chars: Vec<char> = line.chars().collect();
+        let chars: Vec<char> = self.doc.line(pos.line).chars().collect();
         let mut col = pos.col;
 
         if col >= chars.len() {
-            if pos.line + 1 < self.lines.len() {
+            if pos.line + 1 < self.doc.len_lines() {
                 self.set_primary_cursor(pos.line + 1, 0);
             }
             return;
@@ -149,7 +134,7 @@ impl TextArea {
     /// Page down
     pub fn page_down(&mut self, page_size: usize) {
         let pos = self.cursors.primary().pos;
-        let new_line = (pos.line + page_size).min(self.lines.len().saturating_sub(1));
+        let new_line = (pos.line + page_size).min(self.doc.len_lines() - 1);
         let new_col = pos.col.min(self.line_len(new_line));
         self.set_primary_cursor(new_line, new_col);
         self.update_selection();
@@ -160,8 +145,8 @@ impl TextArea {
         use super::cursor::CursorPos;
         use super::cursor::CursorSet;
 
-        let last_line = self.lines.len().saturating_sub(1);
-        let last_col = self.lines.last().map(|l| l.chars().count()).unwrap_or(0);
+        let last_line = self.doc.len_lines() - 1;
+        let last_col = self.line_len(last_line);
         // Create cursor at end with anchor at start
         self.cursors = CursorSet::new(CursorPos::new(last_line, last_col));
         self.cursors.primary_mut().anchor = Some(CursorPos::new(0, 0));
@@ -60,7 +60,7 @@ impl TextArea {
     /// Get line number width
     fn line_number_width(&self) -> u16 {
         if self.show_line_numbers {
-            let max_line = self.lines.len();
+            let max_line = self.doc.len_lines();
             let digits = format!("{}", max_line).len();
             (digits + 2) as u16 // digits + space + separator
         } else {
@@ -81,11 +81,11 @@ impl TextArea {
         let mut rows = Vec::with_capacity(visible_lines);
         let mut line_idx = self.scroll.0;
 
-        while rows.len() < visible_lines && line_idx < self.lines.len() {
-            let char_count = self.lines[line_idx].chars().count();
+        while rows.len() < visible_lines && line_idx < self.doc.len_lines() {
+            let char_count = self.doc.line_len(line_idx);
 
             if self.wrap && text_width > 0 {
-                let chars: Vec<char> = self.lines[line_idx].chars().collect();
+                let chars: Vec<char> = self.doc.line(line_idx).chars().collect();
                 for (k, (s, e)) in wrap_segments(&chars, text_width).into_iter().enumerate() {
                     if rows.len() >= visible_lines {
                         break;
@@ -128,7 +128,9 @@ impl View for TextArea {
         let line_num_width = self.line_number_width();
         let text_start_x = line_num_width;
         let text_width = area.width.saturating_sub(line_num_width);
-        let visible_lines = area.height as usize;
+        // Vim mode keeps the bottom row for its status line
+        let status_rows = usize::from(self.vim.is_some() && area.height > 1);
+        let visible_lines = area.height as usize - status_rows;
         self.last_viewport_height.set(visible_lines);
 
         // Draw background
@@ -143,7 +145,7 @@ impl View for TextArea {
         }
 
         // Show placeholder if empty
-        if self.lines.len() == 1 && self.lines[0].is_empty() {
+        if self.doc.is_empty() {
             if let Some(ref placeholder) = self.placeholder {
                 ctx.draw_text_clipped(text_start_x, 0, placeholder, PLACEHOLDER_FG, text_width);
             }
@@ -171,7 +173,7 @@ impl View for TextArea {
             }
 
             // Draw text
-            let line = &self.lines[line_idx];
+            let line = &self.doc.line(line_idx);
             let chars: Vec<char> = line.chars().collect();
             let is_last_segment = seg_end >= chars.len();
 
@@ -285,6 +287,12 @@ impl View for TextArea {
                 }
             }
         }
+
+        // Vim status line
+        if let Some(vim) = self.vim.as_ref().filter(|_| status_rows > 0) {
+            let status = vim.status_text();
+            ctx.draw_text_clipped(0, area.height - 1, &status, vim.mode().color(), area.width);
+        }
     }
 }
 
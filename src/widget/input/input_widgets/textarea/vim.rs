@@ -0,0 +1,150 @@
+//! Vim mode for TextArea
+//!
+//! Same as the code editor's Vim mode: with [`TextArea::vim`] enabled, keys
+//! go through a [`VimState`] that edits the text area as a [`VimBuffer`].
+
+use std::ops::Range;
+
+use super::cursor::{Cursor, CursorPos, CursorSet};
+use super::TextArea;
+use crate::event::KeyEvent;
+use crate::utils::Document;
+use crate::widget::developer::{
+    VimBuffer, VimCommandResult, VimSelection, VimSelectionKind, VimState,
+};
+
+impl TextArea {
+    /// Enable/disable Vim mode
+    pub fn vim(mut self, enable: bool) -> Self {
+        self.set_vim(enable);
+        self
+    }
+
+    /// Enable/disable Vim mode (mutable)
+    pub fn set_vim(&mut self, enable: bool) {
+        if enable != self.vim.is_some() {
+            self.vim = enable.then(VimState::new);
+        }
+    }
+
+    /// Get the Vim state, when Vim mode is on
+    pub fn vim_state(&self) -> Option<&VimState> {
+        self.vim.as_ref()
+    }
+
+    /// Get the Vim state mutably, e.g. to set its clipboard
+    pub fn vim_state_mut(&mut self) -> Option<&mut VimState> {
+        self.vim.as_mut()
+    }
+
+    /// Take the last ex command for the host to carry out, like `:w`
+    pub fn take_vim_command(&mut self) -> Option<VimCommandResult> {
+        self.vim.as_mut().and_then(VimState::take_command)
+    }
+
+    /// Handle a key through Vim mode
+    pub(super) fn handle_vim_key(&mut self, event: &KeyEvent) -> Option<bool> {
+        let mut vim = self.vim.take()?;
+        let handled = vim.process(event, self);
+        self.vim = Some(vim);
+        Some(handled)
+    }
+}
+
+impl VimBuffer for TextArea {
+    fn document(&self) -> &Document {
+        &self.doc
+    }
+
+    fn cursor(&self) -> (usize, usize) {
+        self.cursor_position()
+    }
+
+    fn set_cursor(&mut self, line: usize, col: usize) {
+        TextArea::set_cursor(self, line, col);
+    }
+
+    fn replace(&mut self, range: Range<usize>, text: &str) {
+        if self.read_only {
+            return;
+        }
+        self.doc.replace(range, text);
+        let (line, col) = self.cursor_position();
+        TextArea::set_cursor(self, line, col);
+    }
+
+    fn begin_change(&mut self) {
+        self.doc.begin_transaction();
+    }
+
+    fn end_change(&mut self) {
+        self.doc.commit_transaction();
+    }
+
+    fn undo(&mut self) {
+        TextArea::undo(self);
+    }
+
+    fn redo(&mut self) {
+        TextArea::redo(self);
+    }
+
+    fn set_selection(&mut self, selection: Option<VimSelection>) {
+        let Some(selection) = selection else {
+            let (line, col) = self.cursor_position();
+            self.cursors = CursorSet::new(CursorPos::new(line, col));
+            return;
+        };
+        // Selections end before their last position
+        let after = |doc: &Document, (line, col): (usize, usize)| -> CursorPos {
+            doc.char_to_pos(doc.pos_to_char(line, col) + 1).into()
+        };
+        let cursor = match selection.kind {
+            VimSelectionKind::Char if selection.anchor <= selection.cursor => {
+                Cursor::with_selection(after(&self.doc, selection.cursor), selection.anchor.into())
+            }
+            VimSelectionKind::Char => {
+                Cursor::with_selection(selection.cursor.into(), after(&self.doc, selection.anchor))
+            }
+            VimSelectionKind::Line => {
+                let (first, last) = selection.lines();
+                let end = CursorPos::new(last, self.line_len(last));
+                let start = CursorPos::new(first, 0);
+                if selection.anchor.0 <= selection.cursor.0 {
+                    Cursor::with_selection(end, start)
+                } else {
+                    Cursor::with_selection(start, end)
+                }
+            }
+            VimSelectionKind::Block => {
+                // One cursor per line
+                let (first, last) = selection.lines();
+                let (cmin, cmax) = selection.columns();
+                let row = |line: usize| {
+                    let len = self.line_len(line);
+                    Cursor::with_selection(
+                        CursorPos::new(line, (cmax + 1).min(len)),
+                        CursorPos::new(line, cmin.min(len)),
+                    )
+                };
+                let mut cursors = CursorSet::new(CursorPos::new(first, 0));
+                *cursors.primary_mut() = row(first);
+                for line in first + 1..=last {
+                    cursors.add(row(line));
+                }
+                self.cursors = cursors;
+                return;
+            }
+        };
+        self.cursors = CursorSet::new(cursor.pos);
+        *self.cursors.primary_mut() = cursor;
+    }
+
+    fn insert_key(&mut self, key: &KeyEvent) -> bool {
+        self.handle_key(&key.key)
+    }
+
+    fn indent_unit(&self) -> String {
+        " ".repeat(self.tab_width.max(1))
+    }
+}
@@ -364,7 +364,7 @@ impl View for Border {
                 overflow_hidden,
                 parent_clip,
             );
-            child.render(&mut child_ctx);
+            child_ctx.render_view(child.as_ref());
         }
     }
 
@@ -0,0 +1,366 @@
+//! Keyed list container backed by a `SignalVec`
+//!
+//! [`For`] renders one child per item of a [`SignalVec`] and keeps children
+//! in sync by applying each [`VecDiff`] as a single insert/move/remove/update,
+//! instead of rebuilding the whole list. Every child carries a key derived
+//! from its item, which the DOM uses to keep node identity (focus, hover,
+//! cached style) stable when items are inserted or reordered.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use revue::prelude::*;
+//! use revue::reactive::signal_vec;
+//! use revue::widget::{each, Text};
+//!
+//! #[derive(Clone, PartialEq)]
+//! struct Todo { id: u32, title: String }
+//!
+//! let todos = signal_vec(vec![Todo { id: 1, title: "Write docs".into() }]);
+//!
+//! let mut list = each(
+//!     todos.clone(),
+//!     |todo| todo.id.to_string(),
+//!     |todo| Text::new(todo.title.clone()),
+//! );
+//!
+//! todos.insert(0, Todo { id: 2, title: "Fix bug".into() });
+//!
+//! // In the event handler: apply pending diffs, then rebuild the DOM
+//! if list.sync() {
+//!     app.request_dom_rebuild();
+//! }
+//! ```
+
+use crate::dom::WidgetMeta;
+use crate::reactive::{DiffQueue, SignalVec, VecDiff};
+use crate::widget::traits::{RenderContext, View, WidgetProps};
+use std::collections::HashMap;
+
+use super::stack::Direction;
+
+/// Function that builds the view for an item
+type RenderFn<T> = Box<dyn Fn(&T) -> Box<dyn View>>;
+
+/// Child wrapper that attaches a reconciliation key to a view
+struct Keyed {
+    key: String,
+    view: Box<dyn View>,
+}
+
+impl View for Keyed {
+    fn render(&self, ctx: &mut RenderContext) {
+        self.view.render(ctx);
+    }
+
+    fn widget_type(&self) -> &'static str {
+        self.view.widget_type()
+    }
+
+    fn id(&self) -> Option<&str> {
+        self.view.id()
+    }
+
+    fn classes(&self) -> &[String] {
+        self.view.classes()
+    }
+
+    fn children(&self) -> &[Box<dyn View>] {
+        self.view.children()
+    }
+
+    fn needs_render(&self) -> bool {
+        self.view.needs_render()
+    }
+
+    fn meta(&self) -> WidgetMeta {
+        let mut meta = self.view.meta();
+        meta.key = Some(self.key.clone());
+        meta
+    }
+}
+
+/// A keyed list of children driven by a [`SignalVec`]
+pub struct For<T: Send + Sync + Clone + PartialEq + 'static> {
+    items: SignalVec<T>,
+    key_fn: Box<dyn Fn(&T) -> String>,
+    render_fn: RenderFn<T>,
+    /// Keys of the current children, in order
+    keys: Vec<String>,
+    /// Item values the current children were built from
+    values: Vec<T>,
+    /// One `Keyed` child per item
+    children: Vec<Box<dyn View>>,
+    /// Diffs received since the last `sync()`
+    pending: DiffQueue<T>,
+    direction: Direction,
+    item_size: u16,
+    gap: u16,
+    props: WidgetProps,
+}
+
+impl<T: Send + Sync + Clone + PartialEq + 'static> For<T> {
+    /// Create a keyed list from a signal vector, a key function and a render function
+    pub fn new<V, K, R>(items: SignalVec<T>, key_fn: K, render_fn: R) -> Self
+    where
+        V: View + 'static,
+        K: Fn(&T) -> String + 'static,
+        R: Fn(&T) -> V + 'static,
+    {
+        let pending = items.diff_queue();
+        let mut list = Self {
+            keys: Vec::new(),
+            values: Vec::new(),
+            children: Vec::new(),
+            key_fn: Box::new(key_fn),
+            render_fn: Box::new(move |item| Box::new(render_fn(item))),
+            items,
+            pending,
+            direction: Direction::Column,
+            item_size: 1,
+            gap: 0,
+            props: WidgetProps::new(),
+        };
+        let initial = list.items.get();
+        list.reconcile(initial);
+        list
+    }
+
+    /// Set layout direction (default: column)
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Set the size of each item along the layout direction (default: 1)
+    pub fn item_size(mut self, size: u16) -> Self {
+        self.item_size = size.max(1);
+        self
+    }
+
+    /// Set gap between items
+    pub fn gap(mut self, gap: u16) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Apply diffs received from the signal vector since the last call
+    ///
+    /// Returns `true` if the children changed. Call this from the event
+    /// handler (e.g. on `Event::Tick`) and request a DOM rebuild when it
+    /// returns `true`.
+    pub fn sync(&mut self) -> bool {
+        let diffs = self.pending.drain();
+        if diffs.is_empty() {
+            return false;
+        }
+        for diff in diffs {
+            self.apply(diff);
+        }
+        true
+    }
+
+    /// Check if there are diffs waiting to be applied by [`sync()`][Self::sync]
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Get the keys of the current children, in order
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// Get number of items
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Check if the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Build a keyed child for an item
+    fn build(&self, key: String, item: &T) -> Box<dyn View> {
+        Box::new(Keyed {
+            key,
+            view: (self.render_fn)(item),
+        })
+    }
+
+    /// Apply a single diff to the children
+    fn apply(&mut self, diff: VecDiff<T>) {
+        match diff {
+            VecDiff::Insert { index, value } => {
+                let index = index.min(self.children.len());
+                let key = (self.key_fn)(&value);
+                let child = self.build(key.clone(), &value);
+                self.keys.insert(index, key);
+                self.children.insert(index, child);
+                self.values.insert(index, value);
+            }
+            VecDiff::Update {
+                index, new_value, ..
+            } => {
+                if index < self.children.len() && self.values[index] != new_value {
+                    let key = (self.key_fn)(&new_value);
+                    self.children[index] = self.build(key.clone(), &new_value);
+                    self.keys[index] = key;
+                    self.values[index] = new_value;
+                }
+            }
+            VecDiff::Remove { index, .. } => {
+                if index < self.children.len() {
+                    self.keys.remove(index);
+                    self.children.remove(index);
+                    self.values.remove(index);
+                }
+            }
+            VecDiff::Move {
+                old_index,
+                new_index,
+                ..
+            } => {
+                let len = self.children.len();
+                if old_index < len && new_index < len {
+                    // Move the existing child so its view state is preserved
+                    let key = self.keys.remove(old_index);
+                    let child = self.children.remove(old_index);
+                    let value = self.values.remove(old_index);
+                    self.keys.insert(new_index, key);
+                    self.children.insert(new_index, child);
+                    self.values.insert(new_index, value);
+                }
+            }
+            VecDiff::Replace { new_values, .. } => self.reconcile(new_values),
+        }
+    }
+
+    /// Rebuild the children for a new item list, reusing children whose key
+    /// and value are unchanged
+    fn reconcile(&mut self, new_values: Vec<T>) {
+        let mut old: HashMap<String, (T, Box<dyn View>)> = self
+            .keys
+            .drain(..)
+            .zip(self.values.drain(..).zip(self.children.drain(..)))
+            .collect();
+
+        for value in new_values {
+            let key = (self.key_fn)(&value);
+            let child = match old.remove(&key) {
+                Some((old_value, child)) if old_value == value => child,
+                _ => self.build(key.clone(), &value),
+            };
+            self.keys.push(key);
+            self.children.push(child);
+            self.values.push(value);
+        }
+    }
+}
+
+impl<T: Send + Sync + Clone + PartialEq + 'static> View for For<T> {
+    fn render(&self, ctx: &mut RenderContext) {
+        let area = ctx.area;
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let overflow_hidden = ctx.css_overflow_hidden();
+        let parent_clip = ctx.clip();
+        let step = self.item_size.saturating_add(self.gap);
+        let extent = match self.direction {
+            Direction::Column => area.height,
+            Direction::Row => area.width,
+        };
+
+        let mut offset: u16 = 0;
+        for child in &self.children {
+            if offset >= extent {
+                break;
+            }
+            let size = self.item_size.min(extent - offset);
+            if child.needs_render() {
+                let child_area = match self.direction {
+                    Direction::Column => ctx.sub_area(0, offset, area.width, size),
+                    Direction::Row => ctx.sub_area(offset, 0, size, area.height),
+                };
+                ctx.trace_child(child.as_ref(), child_area);
+                let mut child_ctx = RenderContext::child_ctx_with_overflow(
+                    ctx.buffer,
+                    child_area,
+                    overflow_hidden,
+                    parent_clip,
+                );
+                child_ctx.render_view(child.as_ref());
+            }
+            offset = offset.saturating_add(step);
+        }
+    }
+
+    fn children(&self) -> &[Box<dyn View>] {
+        &self.children
+    }
+
+    crate::impl_view_meta!("For");
+}
+
+// Manual implementations for generic type
+impl<T: Send + Sync + Clone + PartialEq + 'static> crate::widget::StyledView for For<T> {
+    fn set_id(&mut self, id: impl Into<String>) {
+        self.props.id = Some(id.into());
+    }
+
+    fn add_class(&mut self, class: impl Into<String>) {
+        let class_str = class.into();
+        if !self.props.classes.iter().any(|c| c == &class_str) {
+            self.props.classes.push(class_str);
+        }
+    }
+
+    fn remove_class(&mut self, class: &str) {
+        self.props.classes.retain(|c| c != class);
+    }
+
+    fn toggle_class(&mut self, class: &str) {
+        if self.props.classes.iter().any(|c| c == class) {
+            self.props.classes.retain(|c| c != class);
+        } else {
+            self.props.classes.push(class.to_string());
+        }
+    }
+
+    fn has_class(&self, class: &str) -> bool {
+        self.props.classes.iter().any(|c| c == class)
+    }
+}
+
+impl<T: Send + Sync + Clone + PartialEq + 'static> For<T> {
+    /// Set element ID for CSS selector (#id)
+    pub fn element_id(mut self, id: impl Into<String>) -> Self {
+        self.props.id = Some(id.into());
+        self
+    }
+
+    /// Add a CSS class
+    pub fn class(mut self, class: impl Into<String>) -> Self {
+        let class_str = class.into();
+        if !self.props.classes.iter().any(|c| c == &class_str) {
+            self.props.classes.push(class_str);
+        }
+        self
+    }
+}
+
+/// Create a keyed list from a signal vector
+///
+/// `key_fn` must return a key that is unique among the items and stable for
+/// the lifetime of an item (e.g. a database ID, not the index).
+pub fn each<T, V, K, R>(items: SignalVec<T>, key_fn: K, render_fn: R) -> For<T>
+where
+    T: Send + Sync + Clone + PartialEq + 'static,
+    V: View + 'static,
+    K: Fn(&T) -> String + 'static,
+    R: Fn(&T) -> V + 'static,
+{
+    For::new(items, key_fn, render_fn)
+}
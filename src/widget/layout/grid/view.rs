@@ -95,7 +95,7 @@ impl View for Grid {
 
             let mut child_ctx = RenderContext::new(ctx.buffer, cell_rect);
 
-            item.widget.render(&mut child_ctx);
+            child_ctx.render_view(item.widget.as_ref());
         }
     }
 }
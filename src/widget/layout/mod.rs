@@ -12,6 +12,7 @@
 //! | [`Stack`] | Vertical/horizontal stack | [`vstack()`][vstack], [`hstack()`][hstack] |
 //! | [`Border`] | Bordered container | [`border()`] |
 //! | [`Card`] | Content card with header/footer | [`card()`] |
+//! | [`For`] | Keyed list driven by a `SignalVec` | [`each()`] |
 //!
 //! ## Advanced Layout
 //!
@@ -120,6 +121,7 @@ pub mod border;
 pub mod card;
 pub mod collapsible;
 pub mod dock;
+pub mod for_each;
 pub mod grid;
 pub mod layer;
 pub mod positioned;
@@ -138,6 +140,7 @@ pub use card::{card, Card, CardVariant};
 pub use collapsible::{collapsible, Collapsible};
 #[allow(unused_imports)]
 pub use dock::{dock, dock_area, DockArea, DockManager, DockPosition, TabContent};
+pub use for_each::{each, For};
 pub use grid::{
     grid, grid_item, grid_template, Grid, GridAlign, GridItem, GridPlacement, TrackSize,
 };
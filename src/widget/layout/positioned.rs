@@ -312,7 +312,7 @@ impl View for Positioned {
 
         // Render child in calculated area
         let mut child_ctx = RenderContext::new(ctx.buffer, child_area);
-        self.child.render(&mut child_ctx);
+        child_ctx.render_view(self.child.as_ref());
     }
 }
 
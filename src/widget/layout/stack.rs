@@ -216,13 +216,14 @@ impl View for Stack {
 
                     if child.needs_render() {
                         let child_area = ctx.sub_area(x, y, w, row_height);
+                        ctx.trace_child(child.as_ref(), child_area);
                         let mut child_ctx = RenderContext::child_ctx_with_overflow(
                             ctx.buffer,
                             child_area,
                             overflow_hidden,
                             parent_clip,
                         );
-                        child.render(&mut child_ctx);
+                        child_ctx.render_view(child.as_ref());
                     }
                     x = x.saturating_add(w).saturating_add(self.gap);
                 }
@@ -236,13 +237,14 @@ impl View for Stack {
                     let h = heights[i];
                     if child.needs_render() {
                         let child_area = ctx.sub_area(0, y, area.width, h);
+                        ctx.trace_child(child.as_ref(), child_area);
                         let mut child_ctx = RenderContext::child_ctx_with_overflow(
                             ctx.buffer,
                             child_area,
                             overflow_hidden,
                             parent_clip,
                         );
-                        child.render(&mut child_ctx);
+                        child_ctx.render_view(child.as_ref());
                     }
                     y = y.saturating_add(h).saturating_add(self.gap);
                 }
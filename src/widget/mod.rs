@@ -284,14 +284,14 @@ pub use display::{
 };
 // Layout widgets (re-exported from layout module)
 pub use layout::{
-    accordion, border, card, collapsible, draw_border, grid, grid_item, grid_template, hsplit,
-    hstack, layer, layers, pane, positioned, resizable, screen, screen_stack, scroll, scroll_view,
-    section, sidebar, sidebar_item, sidebar_section, sidebar_section_titled, splitter, stack, tabs,
-    vsplit, vstack, Accordion, AccordionSection, Anchor, Border, BorderType, Card, CardVariant,
-    CollapseMode, Collapsible, Direction, FlattenedItem, Grid, GridAlign, GridItem, GridPlacement,
-    HSplit, Layers, Pane, Positioned, Resizable, ResizeDirection, ResizeHandle, ResizeStyle,
-    Screen, ScreenStack, ScreenTransition, ScrollView, Sidebar, SidebarItem, SidebarSection,
-    SplitOrientation, Splitter, Stack, Tab, Tabs, TrackSize, VSplit,
+    accordion, border, card, collapsible, draw_border, each, grid, grid_item, grid_template,
+    hsplit, hstack, layer, layers, pane, positioned, resizable, screen, screen_stack, scroll,
+    scroll_view, section, sidebar, sidebar_item, sidebar_section, sidebar_section_titled, splitter,
+    stack, tabs, vsplit, vstack, Accordion, AccordionSection, Anchor, Border, BorderType, Card,
+    CardVariant, CollapseMode, Collapsible, Direction, FlattenedItem, For, Grid, GridAlign,
+    GridItem, GridPlacement, HSplit, Layers, Pane, Positioned, Resizable, ResizeDirection,
+    ResizeHandle, ResizeStyle, Screen, ScreenStack, ScreenTransition, ScrollView, Sidebar,
+    SidebarItem, SidebarSection, SplitOrientation, Splitter, Stack, Tab, Tabs, TrackSize, VSplit,
 };
 // Input widgets (re-exported from input_widgets module)
 pub use breadcrumb::{breadcrumb, crumb, Breadcrumb, BreadcrumbItem, SeparatorStyle};
@@ -425,16 +425,17 @@ pub use feedback::{
 };
 
 // Developer widgets (re-exported from developer module)
-#[cfg(feature = "syntax-highlighting")]
-pub use developer::TreeSitterHighlighter;
+#[cfg(feature = "lsp")]
+pub use developer::lsp;
 pub use developer::{
     ai_response, ai_stream, code_editor, http_client, http_delete, http_get, http_patch, http_post,
     http_put, presentation, slide, terminal, vim_state, AiStream, BracketMatch, BracketPair,
-    CodeEditor, ContentType, CursorStyle, EditorConfig, HttpBackend, HttpClient, HttpMethod,
-    HttpRequest, HttpResponse, IndentStyle, MockHttpBackend, Presentation, RequestBuilder,
-    RequestState, ResponseView, Slide, SlideAlign, StreamCursor, StreamStatus, TermCell, TermLine,
-    Terminal, TerminalAction, Transition, TypingStyle, VimAction, VimCommandResult, VimMode,
-    VimMotion, VimState,
+    CodeEditor, ContentType, CursorStyle, EditorConfig, FoldRange, HttpBackend, HttpClient,
+    HttpMethod, HttpRequest, HttpResponse, IndentStyle, MinimapStyle, MockHttpBackend,
+    Presentation, RequestBuilder, RequestState, ResponseView, Slide, SlideAlign, StreamCursor,
+    StreamStatus, TermCell, TermLine, Terminal, TerminalAction, Transition, TypingStyle, VimAction,
+    VimBuffer, VimCommandResult, VimMode, VimMotion, VimSelection, VimSelectionKind, VimState,
+    VimTextObject,
 };
 #[cfg(feature = "diff")]
 pub use developer::{diff, diff_viewer, ChangeType, DiffColors, DiffLine, DiffMode, DiffViewer};
@@ -442,6 +443,8 @@ pub use developer::{diff, diff_viewer, ChangeType, DiffColors, DiffLine, DiffMod
 pub use developer::{
     htop, process_monitor, ProcColors, ProcessInfo, ProcessMonitor, ProcessSort, ProcessView,
 };
+#[cfg(feature = "syntax-highlighting")]
+pub use developer::{SyntaxTree, TreeSitterHighlighter};
 
 // Re-export common widget constructors
 
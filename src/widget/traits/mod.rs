@@ -101,6 +101,7 @@ mod widget_state;
 // Re-export all public types
 pub use element::Element;
 pub use event::{EventResult, FocusStyle};
+pub(crate) use render_context::{profile_render, trace_render, view_key, RenderTrace};
 pub use render_context::{OverlayEntry, OverlayQueue, ProgressBarConfig, RenderContext};
 pub use symbols::Symbols;
 pub use timeout::Timeout;
@@ -3,17 +3,21 @@
 mod css;
 mod focus;
 pub mod overlay;
+mod profile;
 mod progress;
 mod relative;
 mod segments;
 mod shapes;
 mod text;
+mod trace;
 mod types;
 
 #[cfg(test)]
 mod tests;
 
 pub use overlay::{OverlayEntry, OverlayQueue};
+pub(crate) use profile::profile_render;
+pub(crate) use trace::{trace_render, view_key, RenderTrace};
 pub use types::ProgressBarConfig;
 
 use crate::dom::NodeState;
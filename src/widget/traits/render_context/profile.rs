@@ -0,0 +1,91 @@
+//! Render timing for RenderContext
+//!
+//! Containers render each child with [`RenderContext::render_view`], which
+//! times the view while a [`profile_render`] call is active (e.g. while the
+//! app's frame profiler is recording). Otherwise it just renders the view.
+
+use crate::widget::View;
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+/// Time spent rendering one view during a profiled render
+#[derive(Debug, Clone)]
+pub(crate) struct RenderTiming {
+    /// Widget type, with `#id` appended if set
+    pub(crate) name: String,
+    /// Name of the enclosing view
+    pub(crate) parent: Option<String>,
+    /// Nesting depth (0 for the root)
+    pub(crate) depth: usize,
+    /// When rendering started
+    pub(crate) start: Instant,
+    /// Time spent, including children
+    pub(crate) duration: Duration,
+}
+
+#[derive(Default)]
+struct Profile {
+    timings: Vec<RenderTiming>,
+    stack: Vec<String>,
+}
+
+thread_local! {
+    static PROFILE: RefCell<Option<Profile>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with render timing enabled and return the recorded timings
+///
+/// Timings are in the order views finished rendering.
+pub(crate) fn profile_render<R>(f: impl FnOnce() -> R) -> (R, Vec<RenderTiming>) {
+    let outer = PROFILE.with(|p| p.borrow_mut().replace(Profile::default()));
+    let result = f();
+    let profile = PROFILE.with(|p| std::mem::replace(&mut *p.borrow_mut(), outer));
+    (result, profile.map(|p| p.timings).unwrap_or_default())
+}
+
+fn view_name(view: &dyn View) -> String {
+    let meta = view.meta();
+    match meta.id {
+        Some(id) => format!("{}#{}", meta.widget_type, id),
+        None => meta.widget_type,
+    }
+}
+
+impl RenderContext<'_> {
+    /// Render a view into this context
+    ///
+    /// Containers should render each child through this rather than calling
+    /// `View::render` directly so the child shows up in the frame profiler.
+    pub fn render_view(&mut self, view: &dyn View) {
+        let profiling = PROFILE.with(|p| p.borrow().is_some());
+        if !profiling {
+            view.render(self);
+            return;
+        }
+
+        let name = view_name(view);
+        PROFILE.with(|p| {
+            if let Some(profile) = p.borrow_mut().as_mut() {
+                profile.stack.push(name.clone());
+            }
+        });
+        let start = Instant::now();
+        view.render(self);
+        let duration = start.elapsed();
+        PROFILE.with(|p| {
+            if let Some(profile) = p.borrow_mut().as_mut() {
+                profile.stack.pop();
+                let timing = RenderTiming {
+                    name,
+                    parent: profile.stack.last().cloned(),
+                    depth: profile.stack.len(),
+                    start,
+                    duration,
+                };
+                profile.timings.push(timing);
+            }
+        });
+    }
+}
+
+use crate::widget::traits::render_context::RenderContext;
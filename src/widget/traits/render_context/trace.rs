@@ -0,0 +1,47 @@
+//! Render tracing for RenderContext
+//!
+//! Containers report the area each child is rendered into with
+//! [`RenderContext::trace_child`]. Recording is off by default and only
+//! enabled around a [`trace_render`] call (e.g. by the test `Pilot`, which
+//! uses it to locate DOM nodes on screen).
+
+use crate::layout::Rect;
+use crate::widget::traits::render_context::RenderContext;
+use crate::widget::View;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Areas recorded during a traced render, keyed by view address
+pub(crate) type RenderTrace = HashMap<usize, Rect>;
+
+thread_local! {
+    static TRACE: RefCell<Option<RenderTrace>> = const { RefCell::new(None) };
+}
+
+/// Address used to identify a view in a [`RenderTrace`]
+pub(crate) fn view_key(view: &dyn View) -> usize {
+    view as *const dyn View as *const () as usize
+}
+
+/// Run `f` with render tracing enabled and return the recorded areas
+pub(crate) fn trace_render<R>(f: impl FnOnce() -> R) -> (R, RenderTrace) {
+    let outer = TRACE.with(|t| t.borrow_mut().replace(RenderTrace::new()));
+    let result = f();
+    let trace = TRACE.with(|t| std::mem::replace(&mut *t.borrow_mut(), outer));
+    (result, trace.unwrap_or_default())
+}
+
+impl RenderContext<'_> {
+    /// Record the area a child view is rendered into
+    ///
+    /// Containers that expose their children through `View::children()`
+    /// should call this before rendering each child. It is a no-op unless
+    /// a trace is active.
+    pub fn trace_child(&self, child: &dyn View, area: Rect) {
+        TRACE.with(|t| {
+            if let Some(trace) = t.borrow_mut().as_mut() {
+                trace.insert(view_key(child), area);
+            }
+        });
+    }
+}
@@ -12,10 +12,16 @@ mod cursor;
 mod edge_cases;
 #[path = "code_editor/find.rs"]
 mod find;
+#[path = "code_editor/folding.rs"]
+mod folding;
 #[path = "code_editor/goto_line.rs"]
 mod goto_line;
 #[path = "code_editor/language.rs"]
 mod language;
+#[path = "code_editor/lsp.rs"]
+mod lsp;
+#[path = "code_editor/multi_cursor.rs"]
+mod multi_cursor;
 #[path = "code_editor/rendering.rs"]
 mod rendering;
 #[path = "code_editor/selection.rs"]
@@ -24,3 +30,5 @@ mod selection;
 mod text_edit;
 #[path = "code_editor/undo_redo.rs"]
 mod undo_redo;
+#[path = "code_editor/vim.rs"]
+mod vim;
@@ -5,7 +5,7 @@
 use revue::layout::Rect;
 use revue::render::Buffer;
 use revue::widget::traits::{RenderContext, View};
-use revue::widget::{code_editor, CodeEditor, EditorConfig, IndentStyle};
+use revue::widget::{code_editor, CodeEditor, EditorConfig, IndentStyle, MinimapStyle};
 
 #[test]
 fn test_editor_config_default() {
@@ -28,6 +28,10 @@ fn test_editor_config_custom() {
         highlight_current_line: false,
         show_minimap: true,
         minimap_width: 15,
+        minimap_style: MinimapStyle::Braille,
+        folding: true,
+        indent_guides: true,
+        sticky_scroll: true,
         show_whitespace: true,
         word_wrap: true,
     };
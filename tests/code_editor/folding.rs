@@ -0,0 +1,168 @@
+//! Folding, indentation guide, sticky scroll and minimap tests
+
+use revue::layout::Rect;
+use revue::render::Buffer;
+use revue::widget::traits::{RenderContext, View};
+use revue::widget::{CodeEditor, FoldRange, MinimapStyle};
+
+const PYTHON: &str = "def main():\n    if x:\n        y()\n    z()\nprint()";
+
+fn render(editor: &CodeEditor, width: u16, height: u16) -> Buffer {
+    let mut buffer = Buffer::new(width, height);
+    let mut ctx = RenderContext::new(&mut buffer, Rect::new(0, 0, width, height));
+    editor.render(&mut ctx);
+    buffer
+}
+
+fn row_text(buffer: &Buffer, y: u16) -> String {
+    (0..buffer.width())
+        .filter_map(|x| buffer.get(x, y).map(|cell| cell.symbol))
+        .collect()
+}
+
+#[test]
+fn test_indent_fold_ranges() {
+    let editor = CodeEditor::new().content(PYTHON);
+    assert_eq!(
+        editor.fold_ranges(),
+        [
+            FoldRange { start: 0, end: 3 },
+            FoldRange { start: 1, end: 2 }
+        ]
+    );
+}
+
+#[test]
+fn test_fold_requires_folding_enabled() {
+    let mut editor = CodeEditor::new().content(PYTHON);
+    assert!(!editor.fold(0));
+
+    let mut editor = CodeEditor::new().content(PYTHON).folding(true);
+    assert!(editor.fold(0));
+    assert!(!editor.fold(4));
+}
+
+#[test]
+fn test_fold_hides_lines_from_navigation() {
+    let mut editor = CodeEditor::new().content(PYTHON).folding(true);
+    editor.fold(1);
+    assert!(editor.is_folded(1));
+    assert!(editor.is_line_hidden(2));
+
+    editor.set_cursor(1, 0);
+    editor.move_down();
+    assert_eq!(editor.cursor_position().0, 3);
+    editor.move_up();
+    assert_eq!(editor.cursor_position().0, 1);
+
+    editor.toggle_fold();
+    assert!(!editor.is_folded(1));
+    editor.move_down();
+    assert_eq!(editor.cursor_position().0, 2);
+}
+
+#[test]
+fn test_fold_moves_cursor_to_header() {
+    let mut editor = CodeEditor::new().content(PYTHON).folding(true);
+    editor.set_cursor(2, 3);
+    editor.fold(1);
+    assert_eq!(editor.cursor_position(), (1, 3));
+}
+
+#[test]
+fn test_fold_all_and_unfold_all() {
+    let mut editor = CodeEditor::new().content(PYTHON).folding(true);
+    editor.fold_all();
+    assert!(editor.is_folded(0));
+    assert!(editor.is_folded(1));
+    assert!(editor.is_line_hidden(3));
+
+    editor.unfold_all();
+    assert!(!editor.is_line_hidden(3));
+}
+
+#[test]
+fn test_folds_follow_edits() {
+    let mut editor = CodeEditor::new().content(PYTHON).folding(true);
+    editor.fold(1);
+    editor.set_cursor(0, 0);
+    editor.insert_str("# top\n");
+    assert!(editor.is_folded(2));
+    assert!(!editor.is_folded(1));
+    assert!(editor.is_line_hidden(3));
+}
+
+#[test]
+fn test_gutter_fold_markers() {
+    let mut editor = CodeEditor::new().content(PYTHON).folding(true);
+    editor.fold(1);
+    let buffer = render(&editor, 30, 6);
+
+    // Line numbers are 3 wide, then the fold column
+    assert_eq!(buffer.get(3, 0).unwrap().symbol, '▾');
+    assert_eq!(buffer.get(3, 1).unwrap().symbol, '▸');
+    assert_eq!(buffer.get(3, 2).unwrap().symbol, ' ');
+    assert!(row_text(&buffer, 1).contains("if x: ⋯"));
+    assert!(row_text(&buffer, 2).contains("4") && row_text(&buffer, 2).contains("z()"));
+}
+
+#[test]
+fn test_indent_guides() {
+    let editor = CodeEditor::new()
+        .content("a:\n    b:\n        c\n\n    d")
+        .indent_guides(true);
+    let buffer = render(&editor, 30, 6);
+
+    assert_eq!(buffer.get(3, 2).unwrap().symbol, '│');
+    assert_eq!(buffer.get(3 + 4, 2).unwrap().symbol, '│');
+    assert_eq!(buffer.get(3 + 1, 2).unwrap().symbol, ' ');
+    // Blank lines keep the guides of the surrounding block
+    assert_eq!(buffer.get(3, 3).unwrap().symbol, '│');
+    // None without indentation
+    assert_eq!(buffer.get(3, 0).unwrap().symbol, 'a');
+}
+
+#[test]
+fn test_sticky_scroll_header() {
+    let body = "    x();\n".repeat(20);
+    let mut editor = CodeEditor::new()
+        .content(format!("fn main() {{\n{body}}}"))
+        .sticky_scroll(true);
+    render(&editor, 30, 5);
+    assert_eq!(editor.sticky_header(), None);
+
+    editor.set_cursor(15, 0);
+    assert_eq!(editor.sticky_header(), Some(0));
+    let buffer = render(&editor, 30, 5);
+    assert!(row_text(&buffer, 0).contains("fn main() {"));
+    assert_eq!(buffer.get(5, 0).unwrap().bg, Some(editor.sticky_scroll_bg));
+    assert!(row_text(&buffer, 4).contains("16"));
+}
+
+#[test]
+fn test_braille_minimap() {
+    let editor = CodeEditor::new()
+        .content("fn main() {\n    x();\n}")
+        .minimap(true)
+        .minimap_style(MinimapStyle::Braille);
+    let buffer = render(&editor, 40, 4);
+
+    let minimap_x = 40 - 10;
+    let cell = buffer.get(minimap_x, 0).unwrap();
+    assert!(('\u{2801}'..='\u{28FF}').contains(&cell.symbol));
+    assert_eq!(cell.bg, Some(editor.minimap_visible_bg));
+}
+
+#[cfg(feature = "syntax-highlighting")]
+#[test]
+fn test_syntax_fold_ranges() {
+    use revue::widget::Language;
+
+    let editor = CodeEditor::new()
+        .language(Language::Rust)
+        .content("fn main() {\n    let x = 1;\n    let y = 2;\n}");
+    // The closing brace stays visible
+    assert!(editor
+        .fold_ranges()
+        .contains(&FoldRange { start: 0, end: 2 }));
+}
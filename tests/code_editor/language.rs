@@ -31,3 +31,33 @@ fn test_detect_language_python() {
         .detect_language("script.py");
     assert_eq!(editor.get_content(), "def foo(): pass");
 }
+
+#[cfg(feature = "syntax-highlighting")]
+fn render_fg(editor: &CodeEditor, x: u16, y: u16) -> Option<revue::style::Color> {
+    let mut buffer = Buffer::new(40, 5);
+    let area = Rect::new(0, 0, 40, 5);
+    let mut ctx = RenderContext::new(&mut buffer, area);
+    editor.render(&mut ctx);
+    buffer.get(x, y).and_then(|cell| cell.fg)
+}
+
+#[cfg(feature = "syntax-highlighting")]
+#[test]
+fn test_tree_sitter_highlights_multi_line_comment() {
+    use revue::widget::{Language, SyntaxTheme};
+
+    let theme = SyntaxTheme::dark();
+    let mut editor = CodeEditor::new()
+        .content("let x = 1;\nlet y = 2; */")
+        .language(Language::Rust)
+        .line_numbers(false)
+        .focused(false);
+    assert_eq!(render_fg(&editor, 0, 1), Some(theme.keyword));
+
+    // Opening the comment on line 1 recolors line 2
+    editor.insert_str("/*");
+    assert_eq!(render_fg(&editor, 0, 1), Some(theme.comment));
+
+    editor.undo();
+    assert_eq!(render_fg(&editor, 0, 1), Some(theme.keyword));
+}
@@ -0,0 +1,220 @@
+//! Language server integration tests, against an in-process fake server
+
+#![cfg(feature = "lsp")]
+
+use std::sync::{Arc, Mutex};
+
+use revue::event::Key;
+use revue::layout::Rect;
+use revue::render::{Buffer, Modifier};
+use revue::widget::lsp::{LspClient, LspEvent};
+use revue::widget::traits::{RenderContext, View};
+use revue::widget::CodeEditor;
+use serde_json::{json, Value};
+
+const URI: &str = "file:///src/main.rs";
+
+fn range(line: u32, start: u32, end: u32) -> Value {
+    json!({"start": {"line": line, "character": start}, "end": {"line": line, "character": end}})
+}
+
+/// Fake server answering every request with canned results, recording what
+/// it receives
+fn fake_server() -> (LspClient, Arc<Mutex<Vec<Value>>>) {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let seen = log.clone();
+    let client = LspClient::in_process(move |msg| {
+        seen.lock().unwrap().push(msg.clone());
+        let id = msg["id"].clone();
+        let reply = |result: Value| vec![json!({"jsonrpc": "2.0", "id": id, "result": result})];
+        match msg["method"].as_str().unwrap_or_default() {
+            "initialize" => reply(json!({"capabilities": {
+                "textDocumentSync": 2,
+                "completionProvider": {"triggerCharacters": ["."]},
+                "signatureHelpProvider": {"triggerCharacters": ["("]},
+            }})),
+            "textDocument/didOpen" => vec![json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/publishDiagnostics",
+                "params": {"uri": URI, "diagnostics": [
+                    {"range": range(1, 4, 5), "severity": 1, "message": "unused variable"}
+                ]},
+            })],
+            "textDocument/completion" => reply(json!([
+                {"label": "len", "detail": "fn() -> usize"},
+                {"label": "push"},
+                {"label": "pop"},
+            ])),
+            "textDocument/hover" => reply(json!({"contents": "let x: i32"})),
+            "textDocument/definition" => reply(json!({"uri": URI, "range": range(0, 3, 7)})),
+            "textDocument/signatureHelp" => reply(json!({
+                "signatures": [{"label": "main(a: i32)", "parameters": [{"label": "a: i32"}]}],
+                "activeParameter": 0,
+            })),
+            "textDocument/formatting" => reply(json!([
+                {"range": range(1, 0, 0), "newText": "    "}
+            ])),
+            "textDocument/rename" => reply(json!({"changes": {URI: [
+                {"range": range(1, 4, 5), "newText": "count"}
+            ]}})),
+            _ => Vec::new(),
+        }
+    });
+    (client, log)
+}
+
+fn editor() -> (CodeEditor, Arc<Mutex<Vec<Value>>>) {
+    let (client, log) = fake_server();
+    let mut editor = CodeEditor::new()
+        .content("fn main() {\nlet x = 1;\n}")
+        .lsp(client, URI);
+    editor.poll_lsp();
+    (editor, log)
+}
+
+fn render(editor: &CodeEditor) -> Buffer {
+    let mut buffer = Buffer::new(40, 8);
+    let mut ctx = RenderContext::new(&mut buffer, Rect::new(0, 0, 40, 8));
+    editor.render(&mut ctx);
+    buffer
+}
+
+fn buffer_text(buffer: &Buffer) -> String {
+    (0..buffer.height())
+        .map(|y| {
+            (0..buffer.width())
+                .filter_map(|x| buffer.get(x, y).map(|cell| cell.symbol))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_lsp_diagnostics_marker_and_underline() {
+    let (editor, _) = editor();
+    assert_eq!(editor.diagnostics().len(), 1);
+
+    let buffer = render(&editor);
+    assert_eq!(buffer.get(0, 1).unwrap().symbol, '●');
+    assert_eq!(buffer.get(0, 0).unwrap().symbol, ' ');
+
+    // Sign column (2) + line numbers (3), then "let x"
+    let x = buffer.get(5 + 4, 1).unwrap();
+    assert_eq!(x.symbol, 'x');
+    assert!(x.modifier.contains(Modifier::UNDERLINE));
+    assert!(!buffer
+        .get(5 + 3, 1)
+        .unwrap()
+        .modifier
+        .contains(Modifier::UNDERLINE));
+}
+
+#[test]
+fn test_lsp_incremental_sync() {
+    let (mut editor, log) = editor();
+    editor.set_cursor(1, 4);
+    editor.insert_char('y');
+
+    let log = log.lock().unwrap();
+    let change = log
+        .iter()
+        .rfind(|m| m["method"] == "textDocument/didChange")
+        .unwrap();
+    assert_eq!(
+        change["params"]["contentChanges"][0]["range"],
+        range(1, 4, 4)
+    );
+    assert_eq!(change["params"]["contentChanges"][0]["text"], "y");
+    assert_eq!(
+        change["params"]["textDocument"]["version"],
+        editor.document().version()
+    );
+}
+
+#[test]
+fn test_lsp_completion_popup() {
+    let (mut editor, _) = editor();
+    editor.set_cursor(1, 9);
+    editor.handle_key(&Key::Char('.'));
+    editor.poll_lsp();
+    assert_eq!(editor.completion_items().len(), 3);
+    assert!(buffer_text(&render(&editor)).contains("len  fn() -> usize"));
+
+    // Typing narrows the list
+    editor.handle_key(&Key::Char('p'));
+    let labels: Vec<_> = editor
+        .completion_items()
+        .iter()
+        .map(|item| item.label.clone())
+        .collect();
+    assert_eq!(labels, ["push", "pop"]);
+
+    editor.handle_key(&Key::Down);
+    editor.handle_key(&Key::Enter);
+    assert_eq!(editor.get_line(1).unwrap(), "let x = 1.pop;");
+    assert!(editor.completion_items().is_empty());
+}
+
+#[test]
+fn test_lsp_completion_closes_on_escape() {
+    let (mut editor, _) = editor();
+    editor.trigger_completion();
+    editor.poll_lsp();
+    assert!(!editor.completion_items().is_empty());
+    assert!(editor.handle_key(&Key::Escape));
+    assert!(editor.completion_items().is_empty());
+}
+
+#[test]
+fn test_lsp_hover_includes_diagnostics() {
+    let (mut editor, _) = editor();
+    editor.set_cursor(1, 4);
+    editor.show_hover();
+    editor.poll_lsp();
+    assert_eq!(editor.hover_text(), Some("unused variable\n\nlet x: i32"));
+    assert!(buffer_text(&render(&editor)).contains("let x: i32"));
+
+    editor.handle_key(&Key::Right);
+    assert_eq!(editor.hover_text(), None);
+}
+
+#[test]
+fn test_lsp_goto_definition() {
+    let (mut editor, _) = editor();
+    editor.set_cursor(1, 0);
+    editor.handle_key(&Key::F(12));
+    let events = editor.poll_lsp();
+    assert!(matches!(events[0], LspEvent::Definition(_)));
+    assert_eq!(editor.cursor_position(), (0, 3));
+}
+
+#[test]
+fn test_lsp_signature_help() {
+    let (mut editor, _) = editor();
+    editor.set_cursor(1, 9);
+    editor.handle_key(&Key::Char('('));
+    editor.poll_lsp();
+    let help = editor.signature_help().unwrap();
+    assert_eq!(help.active().unwrap().label, "main(a: i32)");
+    assert!(buffer_text(&render(&editor)).contains("▸ a: i32"));
+
+    editor.handle_key(&Key::Escape);
+    assert!(editor.signature_help().is_none());
+}
+
+#[test]
+fn test_lsp_format_and_rename() {
+    let (mut editor, _) = editor();
+    editor.format_document();
+    editor.poll_lsp();
+    assert_eq!(editor.get_line(1).unwrap(), "    let x = 1;");
+
+    // Formatting is one undo step
+    editor.undo();
+    assert_eq!(editor.get_line(1).unwrap(), "let x = 1;");
+
+    editor.rename_symbol("count");
+    editor.poll_lsp();
+    assert_eq!(editor.get_line(1).unwrap(), "let count = 1;");
+}
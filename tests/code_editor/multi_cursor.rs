@@ -0,0 +1,182 @@
+//! Multiple cursor and column selection tests
+
+use revue::event::{Key, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use revue::layout::Rect;
+use revue::render::Buffer;
+use revue::widget::traits::{RenderContext, View};
+use revue::widget::CodeEditor;
+
+fn ctrl(key: Key) -> KeyEvent {
+    KeyEvent {
+        key,
+        ctrl: true,
+        alt: false,
+        shift: false,
+    }
+}
+
+fn ctrl_alt(key: Key) -> KeyEvent {
+    KeyEvent {
+        alt: true,
+        ..ctrl(key)
+    }
+}
+
+fn mouse(kind: MouseEventKind, x: u16, y: u16, alt: bool) -> MouseEvent {
+    MouseEvent {
+        alt,
+        ..MouseEvent::new(x, y, kind)
+    }
+}
+
+#[test]
+fn test_add_cursor_above_and_below() {
+    let mut editor = CodeEditor::new().content("one\ntwo\nthree\nfour");
+    editor.set_cursor(1, 2);
+    assert!(editor.handle_key_event(&ctrl_alt(Key::Down)));
+    assert!(editor.handle_key_event(&ctrl_alt(Key::Down)));
+    editor.handle_key_event(&ctrl_alt(Key::Up));
+    assert_eq!(editor.cursors(), [(1, 2), (0, 2), (2, 2), (3, 2)]);
+    assert!(editor.has_multiple_cursors());
+
+    editor.handle_key(&Key::Escape);
+    assert_eq!(editor.cursors(), [(1, 2)]);
+}
+
+#[test]
+fn test_typing_at_every_cursor() {
+    let mut editor = CodeEditor::new().content("a\nb\nc");
+    editor.add_cursor_at(1, 0);
+    editor.add_cursor_at(2, 0);
+    editor.handle_key(&Key::Char('-'));
+    editor.handle_key(&Key::Char(' '));
+    assert_eq!(editor.get_content(), "- a\n- b\n- c");
+    assert_eq!(editor.cursors(), [(0, 2), (1, 2), (2, 2)]);
+
+    editor.handle_key(&Key::Backspace);
+    assert_eq!(editor.get_content(), "-a\n-b\n-c");
+    editor.handle_key(&Key::Delete);
+    assert_eq!(editor.get_content(), "-\n-\n-");
+}
+
+#[test]
+fn test_multi_cursor_edit_is_one_undo_step() {
+    let mut editor = CodeEditor::new().content("x\nx\nx");
+    editor.set_cursor(0, 1);
+    editor.add_cursor_at(1, 1);
+    editor.add_cursor_at(2, 1);
+    editor.insert_str("yz");
+    assert_eq!(editor.get_content(), "xyz\nxyz\nxyz");
+
+    editor.undo();
+    assert_eq!(editor.get_content(), "x\nx\nx");
+    assert!(!editor.has_multiple_cursors());
+}
+
+#[test]
+fn test_multi_cursor_on_one_line() {
+    let mut editor = CodeEditor::new().content("ab");
+    editor.add_cursor_at(0, 1);
+    editor.add_cursor_at(0, 2);
+    editor.insert_char('|');
+    assert_eq!(editor.get_content(), "|a|b|");
+    assert_eq!(editor.cursors(), [(0, 1), (0, 3), (0, 5)]);
+}
+
+#[test]
+fn test_movement_applies_to_every_cursor() {
+    let mut editor = CodeEditor::new().content("abc\nabc");
+    editor.add_cursor_at(1, 0);
+    editor.handle_key(&Key::Right);
+    editor.handle_key(&Key::Right);
+    assert_eq!(editor.cursors(), [(0, 2), (1, 2)]);
+    editor.handle_key(&Key::End);
+    assert_eq!(editor.cursors(), [(0, 3), (1, 3)]);
+}
+
+#[test]
+fn test_select_next_occurrence() {
+    let mut editor = CodeEditor::new().content("let foo = foo + bar;\nfoo();");
+    editor.set_cursor(0, 5);
+
+    // First press selects the word
+    editor.handle_key_event(&ctrl(Key::Char('d')));
+    assert_eq!(editor.get_selection().as_deref(), Some("foo"));
+    assert!(!editor.has_multiple_cursors());
+
+    editor.handle_key_event(&ctrl(Key::Char('d')));
+    editor.handle_key_event(&ctrl(Key::Char('d')));
+    assert_eq!(editor.cursors(), [(0, 7), (0, 13), (1, 3)]);
+    assert_eq!(editor.selections(), ["foo", "foo", "foo"]);
+
+    editor.insert_str("baz");
+    assert_eq!(editor.get_content(), "let baz = baz + bar;\nbaz();");
+}
+
+#[test]
+fn test_paste_splits_lines_per_cursor() {
+    let mut editor = CodeEditor::new().content("a\nb\nc");
+    editor.set_cursor(0, 1);
+    editor.add_cursor_at(1, 1);
+    editor.add_cursor_at(2, 1);
+    editor.paste("1\n2\n3");
+    assert_eq!(editor.get_content(), "a1\nb2\nc3");
+
+    // Mismatched line count pastes everything at each cursor
+    let mut editor = CodeEditor::new().content("a\nb");
+    editor.add_cursor_at(1, 0);
+    editor.paste("x-");
+    assert_eq!(editor.get_content(), "x-a\nx-b");
+}
+
+#[test]
+fn test_select_block() {
+    let mut editor = CodeEditor::new().content("abcdef\nab\nabcdef");
+    editor.select_block((0, 1), (2, 4));
+    assert_eq!(editor.cursors(), [(2, 4), (0, 4), (1, 2)]);
+    assert_eq!(editor.selections(), ["bcd", "b", "bcd"]);
+
+    editor.insert_char('_');
+    assert_eq!(editor.get_content(), "a_ef\na_\na_ef");
+}
+
+#[test]
+fn test_alt_drag_block_selection() {
+    let mut editor = CodeEditor::new().content("abcdef\nabcdef\nabcdef");
+    let area = Rect::new(0, 0, 30, 5);
+    // Line numbers take 3 columns
+    assert!(editor.handle_mouse(
+        &mouse(MouseEventKind::Down(MouseButton::Left), 3 + 1, 0, true),
+        area
+    ));
+    assert!(editor.handle_mouse(
+        &mouse(MouseEventKind::Drag(MouseButton::Left), 3 + 3, 2, true),
+        area
+    ));
+    editor.handle_mouse(
+        &mouse(MouseEventKind::Up(MouseButton::Left), 3 + 3, 2, true),
+        area,
+    );
+    assert_eq!(editor.selections(), ["bc", "bc", "bc"]);
+
+    // A plain click collapses to one cursor
+    editor.handle_mouse(
+        &mouse(MouseEventKind::Down(MouseButton::Left), 3, 1, false),
+        area,
+    );
+    assert_eq!(editor.cursors(), [(1, 0)]);
+    assert!(!editor.has_selection());
+}
+
+#[test]
+fn test_secondary_cursors_render() {
+    let mut editor = CodeEditor::new().content("ab\nab").focused(true);
+    editor.add_cursor_at(1, 1);
+    let mut buffer = Buffer::new(20, 3);
+    let mut ctx = RenderContext::new(&mut buffer, Rect::new(0, 0, 20, 3));
+    editor.render(&mut ctx);
+
+    assert_eq!(buffer.get(3, 0).unwrap().bg, Some(editor.cursor_bg));
+    assert_eq!(buffer.get(3 + 1, 1).unwrap().bg, Some(editor.cursor_bg));
+    assert_ne!(buffer.get(3, 1).unwrap().bg, Some(editor.cursor_bg));
+}
@@ -54,3 +54,41 @@ fn test_multiple_undo() {
     editor.undo();
     assert_eq!(editor.get_content(), "a");
 }
+
+#[test]
+fn test_undo_auto_closed_bracket_in_one_step() {
+    let mut editor = CodeEditor::new().content("f");
+    editor.set_cursor(0, 1);
+    editor.insert_char('(');
+    assert_eq!(editor.get_content(), "f()");
+
+    editor.undo();
+    assert_eq!(editor.get_content(), "f");
+    assert_eq!(editor.cursor_position(), (0, 1));
+}
+
+#[test]
+fn test_undo_multiline_paste_in_one_step() {
+    let mut editor = CodeEditor::new().content("x");
+    editor.set_cursor(0, 1);
+    editor.insert_str("\nlet a;\nlet b;");
+    assert_eq!(editor.line_count(), 3);
+
+    editor.undo();
+    assert_eq!(editor.get_content(), "x");
+    editor.redo();
+    assert_eq!(editor.get_content(), "x\nlet a;\nlet b;");
+}
+
+#[test]
+fn test_edit_non_ascii_line() {
+    let mut editor = CodeEditor::new().content("héllo wörld");
+    editor.set_cursor(0, 7);
+    editor.delete_char_at();
+    editor.insert_char('o');
+    assert_eq!(editor.get_content(), "héllo world");
+
+    editor.undo();
+    editor.undo();
+    assert_eq!(editor.get_content(), "héllo wörld");
+}
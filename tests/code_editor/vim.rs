@@ -0,0 +1,443 @@
+//! Vim mode tests
+
+use revue::event::{Key, KeyEvent};
+use revue::layout::Rect;
+use revue::render::Buffer;
+use revue::utils::Clipboard;
+use revue::widget::traits::{RenderContext, View};
+use revue::widget::{CodeEditor, TextArea, VimCommandResult, VimMode};
+
+/// Key events for a Vim key sequence, with `<Esc>`, `<CR>`, `<BS>` and
+/// `<C-x>` for special keys
+fn keys(input: &str) -> Vec<KeyEvent> {
+    let mut events = Vec::new();
+    let mut rest = input;
+    while let Some(ch) = rest.chars().next() {
+        if ch == '<' {
+            if let Some(end) = rest.find('>').filter(|&end| end > 1) {
+                let name = &rest[1..end];
+                let event = match name {
+                    "Esc" => Some(KeyEvent::new(Key::Escape)),
+                    "CR" => Some(KeyEvent::new(Key::Enter)),
+                    "BS" => Some(KeyEvent::new(Key::Backspace)),
+                    _ => name
+                        .strip_prefix("C-")
+                        .and_then(|key| key.chars().next())
+                        .map(|key| KeyEvent::ctrl(Key::Char(key))),
+                };
+                if let Some(event) = event {
+                    events.push(event);
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+        }
+        events.push(KeyEvent::new(Key::Char(ch)));
+        rest = &rest[ch.len_utf8()..];
+    }
+    events
+}
+
+fn vim_editor(content: &str) -> CodeEditor {
+    let mut editor = CodeEditor::new().content(content).vim(true);
+    if let Some(vim) = editor.vim_state_mut() {
+        vim.set_clipboard(Clipboard::memory());
+    }
+    editor
+}
+
+fn feed(editor: &mut CodeEditor, input: &str) {
+    for event in keys(input) {
+        editor.handle_key_event(&event);
+    }
+}
+
+fn mode(editor: &CodeEditor) -> VimMode {
+    editor.vim_state().map(|vim| vim.mode()).unwrap()
+}
+
+// =========================================================================
+// Motions and counts
+// =========================================================================
+
+#[test]
+fn test_vim_disabled_by_default() {
+    let mut editor = CodeEditor::new().content("abc");
+    assert!(editor.vim_state().is_none());
+    feed(&mut editor, "x");
+    assert_eq!(editor.get_content(), "xabc");
+}
+
+#[test]
+fn test_vim_motions_with_counts() {
+    let mut editor = vim_editor("one two three four\nsecond line\nthird");
+    feed(&mut editor, "2w");
+    assert_eq!(editor.cursor_position(), (0, 8));
+    feed(&mut editor, "e");
+    assert_eq!(editor.cursor_position(), (0, 12));
+    feed(&mut editor, "$");
+    assert_eq!(editor.cursor_position(), (0, 17));
+    feed(&mut editor, "j");
+    assert_eq!(editor.cursor_position(), (1, 10));
+    feed(&mut editor, "gg");
+    assert_eq!(editor.cursor_position(), (0, 0));
+    feed(&mut editor, "G");
+    assert_eq!(editor.cursor_position(), (2, 0));
+    feed(&mut editor, "2G");
+    assert_eq!(editor.cursor_position(), (1, 0));
+}
+
+#[test]
+fn test_vim_counts_on_operators() {
+    let mut editor = vim_editor("one two three four five");
+    feed(&mut editor, "2dw");
+    assert_eq!(editor.get_content(), "three four five");
+    feed(&mut editor, "d2w");
+    assert_eq!(editor.get_content(), "five");
+
+    let mut editor = vim_editor("a\nb\nc\nd\ne");
+    feed(&mut editor, "3dd");
+    assert_eq!(editor.get_content(), "d\ne");
+    feed(&mut editor, "2x");
+    assert_eq!(editor.get_content(), "\ne");
+}
+
+#[test]
+fn test_vim_dw_stops_at_line_end() {
+    let mut editor = vim_editor("foo\n  bar");
+    feed(&mut editor, "dw");
+    assert_eq!(editor.get_content(), "\n  bar");
+}
+
+#[test]
+fn test_vim_find_char_and_repeat() {
+    let mut editor = vim_editor("a,b,c,d");
+    feed(&mut editor, "f,");
+    assert_eq!(editor.cursor_position(), (0, 1));
+    feed(&mut editor, ";;");
+    assert_eq!(editor.cursor_position(), (0, 5));
+    feed(&mut editor, ",");
+    assert_eq!(editor.cursor_position(), (0, 3));
+    feed(&mut editor, "dt,");
+    assert_eq!(editor.get_content(), "a,b,d");
+}
+
+// =========================================================================
+// Text objects
+// =========================================================================
+
+#[test]
+fn test_vim_change_inner_word() {
+    let mut editor = vim_editor("let value = 1;");
+    feed(&mut editor, "wlciwcount<Esc>");
+    assert_eq!(editor.get_content(), "let count = 1;");
+    assert_eq!(mode(&editor), VimMode::Normal);
+    assert_eq!(editor.cursor_position(), (0, 8));
+}
+
+#[test]
+fn test_vim_delete_around_quotes() {
+    let mut editor = vim_editor("say \"hello there\" now");
+    feed(&mut editor, "fhda\"");
+    assert_eq!(editor.get_content(), "say now");
+}
+
+#[test]
+fn test_vim_yank_inner_parens() {
+    let mut editor = vim_editor("call(a, (b), c)");
+    feed(&mut editor, "f(lyi(");
+    assert_eq!(editor.vim_state().unwrap().register(), "a, (b), c");
+    // The cursor moves to the start of the yanked text
+    assert_eq!(editor.cursor_position(), (0, 5));
+
+    feed(&mut editor, "f(lyi(");
+    assert_eq!(editor.vim_state().unwrap().register(), "b");
+}
+
+#[test]
+fn test_vim_inner_braces_across_lines() {
+    let mut editor = vim_editor("fn main() {\n    one();\n    two();\n}");
+    feed(&mut editor, "jdi{");
+    assert_eq!(editor.get_content(), "fn main() {\n}");
+}
+
+// =========================================================================
+// Registers
+// =========================================================================
+
+#[test]
+fn test_vim_named_registers() {
+    let mut editor = vim_editor("alpha\nbeta");
+    feed(&mut editor, "\"ayyj\"byw");
+    let vim = editor.vim_state().unwrap();
+    assert_eq!(vim.register_content('a').as_deref(), Some("alpha\n"));
+    assert_eq!(vim.register_content('b').as_deref(), Some("beta"));
+
+    // Pasting a linewise register puts it on a new line
+    feed(&mut editor, "\"ap");
+    assert_eq!(editor.get_content(), "alpha\nbeta\nalpha");
+
+    // Uppercase appends
+    feed(&mut editor, "\"Byy");
+    let vim = editor.vim_state().unwrap();
+    assert_eq!(vim.register_content('b').as_deref(), Some("beta\nalpha\n"));
+}
+
+#[test]
+fn test_vim_delete_fills_numbered_registers() {
+    let mut editor = vim_editor("one\ntwo\nthree");
+    feed(&mut editor, "dddd");
+    let vim = editor.vim_state().unwrap();
+    assert_eq!(vim.register_content('1').as_deref(), Some("two\n"));
+    assert_eq!(vim.register_content('2').as_deref(), Some("one\n"));
+
+    feed(&mut editor, "yw");
+    let vim = editor.vim_state().unwrap();
+    assert_eq!(vim.register_content('0').as_deref(), Some("three"));
+}
+
+#[test]
+fn test_vim_clipboard_register() {
+    let mut editor = vim_editor("copy me");
+    feed(&mut editor, "\"+yiw");
+    assert_eq!(
+        editor.vim_state().unwrap().register_content('+').as_deref(),
+        Some("copy")
+    );
+
+    feed(&mut editor, "$\"+p");
+    assert_eq!(editor.get_content(), "copy mecopy");
+}
+
+// =========================================================================
+// Repeat, macros and marks
+// =========================================================================
+
+#[test]
+fn test_vim_dot_repeats_last_change() {
+    let mut editor = vim_editor("a b c d e");
+    feed(&mut editor, "dw..");
+    assert_eq!(editor.get_content(), "d e");
+
+    let mut editor = vim_editor("one\ntwo\nthree");
+    feed(&mut editor, "A;<Esc>j.j.");
+    assert_eq!(editor.get_content(), "one;\ntwo;\nthree;");
+}
+
+#[test]
+fn test_vim_dot_with_new_count() {
+    let mut editor = vim_editor("a b c d e f");
+    feed(&mut editor, "dw2.");
+    assert_eq!(editor.get_content(), "d e f");
+}
+
+#[test]
+fn test_vim_macro_record_and_play() {
+    let mut editor = vim_editor("1\n2\n3\n4");
+    feed(&mut editor, "qaA!<Esc>jq");
+    assert_eq!(editor.vim_state().unwrap().recording(), None);
+    feed(&mut editor, "2@a");
+    assert_eq!(editor.get_content(), "1!\n2!\n3!\n4");
+    feed(&mut editor, "@@");
+    assert_eq!(editor.get_content(), "1!\n2!\n3!\n4!");
+}
+
+#[test]
+fn test_vim_recording_status() {
+    let mut editor = vim_editor("text");
+    feed(&mut editor, "qz");
+    let vim = editor.vim_state().unwrap();
+    assert_eq!(vim.recording(), Some('z'));
+    assert_eq!(vim.status_text(), "recording @z");
+}
+
+#[test]
+fn test_vim_marks() {
+    let mut editor = vim_editor("first line\nsecond\nthird");
+    feed(&mut editor, "wmaG");
+    assert_eq!(editor.vim_state().unwrap().mark('a'), Some((0, 6)));
+    feed(&mut editor, "`a");
+    assert_eq!(editor.cursor_position(), (0, 6));
+    feed(&mut editor, "G'a");
+    assert_eq!(editor.cursor_position(), (0, 0));
+    feed(&mut editor, "jd'a");
+    assert_eq!(editor.get_content(), "third");
+}
+
+// =========================================================================
+// Search and ex commands
+// =========================================================================
+
+#[test]
+fn test_vim_search_next_and_previous() {
+    let mut editor = vim_editor("foo bar\nbar foo\nfoo");
+    feed(&mut editor, "/foo<CR>");
+    assert_eq!(editor.cursor_position(), (1, 4));
+    feed(&mut editor, "n");
+    assert_eq!(editor.cursor_position(), (2, 0));
+    // Wraps around
+    feed(&mut editor, "n");
+    assert_eq!(editor.cursor_position(), (0, 0));
+    feed(&mut editor, "N");
+    assert_eq!(editor.cursor_position(), (2, 0));
+
+    feed(&mut editor, "?bar<CR>");
+    assert_eq!(editor.cursor_position(), (1, 0));
+    // `n` keeps searching backwards after `?`
+    feed(&mut editor, "n");
+    assert_eq!(editor.cursor_position(), (0, 4));
+}
+
+#[test]
+fn test_vim_substitute_with_ranges() {
+    let mut editor = vim_editor("a a\na a\na a");
+    feed(&mut editor, ":s/a/b/<CR>");
+    assert_eq!(editor.get_content(), "b a\na a\na a");
+    feed(&mut editor, ":2,3s/a/c/g<CR>");
+    assert_eq!(editor.get_content(), "b a\nc c\nc c");
+    feed(&mut editor, ":%s/c/d<CR>");
+    assert_eq!(editor.get_content(), "b a\nd c\nd c");
+
+    // One undo step for the whole command
+    feed(&mut editor, "u");
+    assert_eq!(editor.get_content(), "b a\nc c\nc c");
+}
+
+#[test]
+fn test_vim_substitute_on_visual_range() {
+    let mut editor = vim_editor("x\nx\nx\nx");
+    feed(&mut editor, "jVj:s/x/y/<CR>");
+    assert_eq!(editor.get_content(), "x\ny\ny\nx");
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_vim_substitute_regex_groups() {
+    let mut editor = vim_editor("key = value");
+    feed(&mut editor, r":s/(\w+) = (\w+)/\2 = \1/<CR>");
+    assert_eq!(editor.get_content(), "value = key");
+}
+
+#[test]
+fn test_vim_ex_commands_for_host() {
+    let mut editor = vim_editor("text");
+    feed(&mut editor, ":w<CR>");
+    assert_eq!(editor.take_vim_command(), Some(VimCommandResult::Write));
+    assert_eq!(editor.take_vim_command(), None);
+
+    let mut editor = vim_editor("1\n2\n3");
+    feed(&mut editor, ":3<CR>");
+    assert_eq!(editor.cursor_position(), (2, 0));
+    assert_eq!(editor.take_vim_command(), None);
+}
+
+#[test]
+fn test_vim_ex_delete_lines() {
+    let mut editor = vim_editor("1\n2\n3\n4");
+    feed(&mut editor, ":2,3d<CR>");
+    assert_eq!(editor.get_content(), "1\n4");
+}
+
+// =========================================================================
+// Visual mode
+// =========================================================================
+
+#[test]
+fn test_vim_visual_delete() {
+    let mut editor = vim_editor("hello world");
+    feed(&mut editor, "vlld");
+    assert_eq!(editor.get_content(), "lo world");
+    assert_eq!(mode(&editor), VimMode::Normal);
+}
+
+#[test]
+fn test_vim_visual_selection_shown_in_editor() {
+    let mut editor = vim_editor("hello world");
+    feed(&mut editor, "viw");
+    assert_eq!(editor.get_selection().as_deref(), Some("hello"));
+    feed(&mut editor, "<Esc>");
+    assert_eq!(editor.get_selection(), None);
+}
+
+#[test]
+fn test_vim_visual_block_delete_and_insert() {
+    let mut editor = vim_editor("abcd\nefgh\nijkl");
+    feed(&mut editor, "l<C-v>jjld");
+    assert_eq!(editor.get_content(), "ad\neh\nil");
+
+    feed(&mut editor, "^<C-v>jjI# <Esc>");
+    assert_eq!(editor.get_content(), "# ad\n# eh\n# il");
+    assert_eq!(mode(&editor), VimMode::Normal);
+}
+
+#[test]
+fn test_vim_visual_block_append_pads_short_lines() {
+    let mut editor = vim_editor("long line\nab\nlong line");
+    feed(&mut editor, "3l<C-v>jjA|<Esc>");
+    assert_eq!(editor.get_content(), "long| line\nab  |\nlong| line");
+}
+
+// =========================================================================
+// Undo, status and rendering
+// =========================================================================
+
+#[test]
+fn test_vim_insert_is_one_undo_step() {
+    let mut editor = vim_editor("x");
+    feed(&mut editor, "ohello world<Esc>");
+    assert_eq!(editor.get_content(), "x\nhello world");
+    feed(&mut editor, "u");
+    assert_eq!(editor.get_content(), "x");
+    feed(&mut editor, "<C-r>");
+    assert_eq!(editor.get_content(), "x\nhello world");
+}
+
+#[test]
+fn test_vim_status_line_rendered() {
+    let mut editor = vim_editor("text");
+    feed(&mut editor, "i");
+    let mut buffer = Buffer::new(30, 5);
+    let area = Rect::new(0, 0, 30, 5);
+    let mut ctx = RenderContext::new(&mut buffer, area);
+    editor.render(&mut ctx);
+
+    let status: String = (0..12)
+        .filter_map(|x| buffer.get(x, 4).map(|cell| cell.symbol))
+        .collect();
+    assert_eq!(status, "-- INSERT --");
+}
+
+// =========================================================================
+// TextArea
+// =========================================================================
+
+#[test]
+fn test_vim_in_textarea() {
+    let mut textarea = TextArea::new()
+        .content("one two\nthree")
+        .focused(true)
+        .vim(true);
+    for event in keys("ciwzero<Esc>jdd") {
+        textarea.handle_key_event(&event);
+    }
+    assert_eq!(textarea.get_content(), "zero two");
+
+    for event in keys("u") {
+        textarea.handle_key_event(&event);
+    }
+    assert_eq!(textarea.get_content(), "zero two\nthree");
+}
+
+#[test]
+fn test_vim_textarea_visual_block() {
+    let mut textarea = TextArea::new().content("ab\ncd").focused(true).vim(true);
+    for event in keys("<C-v>j") {
+        textarea.handle_key_event(&event);
+    }
+    assert_eq!(textarea.cursor_count(), 2);
+    for event in keys("d") {
+        textarea.handle_key_event(&event);
+    }
+    assert_eq!(textarea.get_content(), "b\nd");
+    assert_eq!(textarea.cursor_count(), 1);
+}
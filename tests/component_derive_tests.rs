@@ -0,0 +1,199 @@
+//! Integration tests for `#[derive(Component)]`
+
+use revue::event::{Key, KeyEvent};
+use revue::layout::Rect;
+use revue::render::Buffer;
+use revue::style::Color;
+use revue::widget::{
+    Button, EventResult, Interactive, RenderContext, StyledView, Text, View, WidgetProps,
+    WidgetState,
+};
+use revue::Component;
+
+#[derive(Component)]
+#[component(name = "Greeting", class = "greeting", class = "card", render = "draw")]
+struct Greeting {
+    /// Set the greeting text
+    #[prop]
+    message: String,
+    #[prop]
+    subtitle: Option<String>,
+    #[prop(name = "with_count", no_into)]
+    count: u32,
+    state: WidgetState,
+    props: WidgetProps,
+}
+
+impl Greeting {
+    fn new() -> Self {
+        Self {
+            message: String::new(),
+            subtitle: None,
+            count: 0,
+            state: WidgetState::new(),
+            props: Self::default_props(),
+        }
+    }
+
+    fn draw(&self, ctx: &mut RenderContext) {
+        Text::new(self.message.as_str()).render(ctx);
+    }
+}
+
+#[derive(Component)]
+struct Action {
+    #[interactive]
+    button: Button,
+    state: WidgetState,
+    props: WidgetProps,
+}
+
+impl Action {
+    fn render_content(&self, ctx: &mut RenderContext) {
+        self.button.render(ctx);
+    }
+}
+
+#[derive(Component)]
+struct Panel {
+    #[children]
+    items: Vec<Box<dyn View>>,
+}
+
+impl Panel {
+    fn render_content(&self, _ctx: &mut RenderContext) {}
+}
+
+#[test]
+fn test_component_prop_builders() {
+    let greeting = Greeting::new()
+        .message("hello")
+        .subtitle("world")
+        .with_count(3);
+
+    assert_eq!(greeting.message, "hello");
+    assert_eq!(greeting.subtitle.as_deref(), Some("world"));
+    assert_eq!(greeting.count, 3);
+}
+
+#[test]
+fn test_component_state_builders() {
+    let greeting = Greeting::new()
+        .focused(true)
+        .disabled(true)
+        .fg(Color::RED)
+        .bg(Color::BLUE);
+
+    assert!(greeting.is_focused());
+    assert!(greeting.is_disabled());
+    assert_eq!(greeting.state.fg, Some(Color::RED));
+    assert_eq!(greeting.state.bg, Some(Color::BLUE));
+}
+
+#[test]
+fn test_component_widget_type_and_meta() {
+    let greeting = Greeting::new().element_id("hero").class("large");
+
+    assert_eq!(greeting.widget_type(), "Greeting");
+    assert_eq!(greeting.id(), Some("hero"));
+    assert_eq!(View::classes(&greeting), &["greeting", "card", "large"]);
+    assert!(greeting.has_class("greeting"));
+
+    let meta = greeting.meta();
+    assert_eq!(meta.widget_type, "Greeting");
+    assert_eq!(meta.id.as_deref(), Some("hero"));
+    assert!(meta.classes.contains("greeting"));
+    assert!(meta.classes.contains("card"));
+    assert!(meta.classes.contains("large"));
+}
+
+#[test]
+fn test_component_default_widget_type() {
+    let action = Action {
+        button: Button::new("OK"),
+        state: WidgetState::new(),
+        props: WidgetProps::new(),
+    };
+
+    assert_eq!(action.widget_type(), "Action");
+    assert!(action.meta().classes.is_empty());
+}
+
+#[test]
+fn test_component_styled_view() {
+    let mut greeting = Greeting::new().classes(["a", "b", "a"]);
+    assert_eq!(View::classes(&greeting).len(), 4);
+
+    greeting.set_id("main");
+    greeting.toggle_class("a");
+    greeting.add_class("c");
+    greeting.remove_class("card");
+
+    assert_eq!(greeting.id(), Some("main"));
+    assert!(!greeting.has_class("a"));
+    assert!(greeting.has_class("b"));
+    assert!(greeting.has_class("c"));
+    // Default classes are ordinary stored classes, so CSS matching follows
+    assert!(!greeting.has_class("card"));
+    assert!(!greeting.meta().classes.contains("card"));
+}
+
+#[test]
+fn test_component_render_delegates() {
+    let greeting = Greeting::new().message("Hi");
+    let mut buf = Buffer::new(10, 1);
+    {
+        let mut ctx = RenderContext::new(&mut buf, Rect::new(0, 0, 10, 1));
+        greeting.render(&mut ctx);
+    }
+
+    assert_eq!(buf.get(0, 0).unwrap().symbol, 'H');
+    assert_eq!(buf.get(1, 0).unwrap().symbol, 'i');
+}
+
+#[test]
+fn test_component_interactive_forwarding() {
+    let mut action = Action {
+        button: Button::new("OK"),
+        state: WidgetState::new(),
+        props: WidgetProps::new(),
+    };
+
+    assert!(action.focusable());
+    assert_eq!(
+        action.handle_key(&KeyEvent::new(Key::Enter)),
+        EventResult::ConsumedAndRender
+    );
+
+    action.on_focus();
+    assert!(action.is_focused());
+    action.on_blur();
+    assert!(!action.is_focused());
+}
+
+#[test]
+fn test_component_interactive_respects_disabled() {
+    let mut action = Action {
+        button: Button::new("OK"),
+        state: WidgetState::new(),
+        props: WidgetProps::new(),
+    }
+    .disabled(true);
+
+    assert!(!action.focusable());
+    assert_eq!(
+        action.handle_key(&KeyEvent::new(Key::Enter)),
+        EventResult::Ignored
+    );
+}
+
+#[test]
+fn test_component_children() {
+    let panel = Panel {
+        items: vec![Box::new(Text::new("a")), Box::new(Text::new("b"))],
+    };
+
+    assert_eq!(panel.children().len(), 2);
+    assert_eq!(panel.id(), None);
+    assert_eq!(panel.widget_type(), "Panel");
+}
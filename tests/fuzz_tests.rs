@@ -0,0 +1,63 @@
+//! Fuzzing built-in interactive widgets with random event sequences
+#![cfg(feature = "fuzz")]
+
+use revue::testing::fuzz::WidgetFuzzer;
+use revue::widget::{Button, Checkbox, MultiSelect, Select, Switch, TextArea};
+
+#[test]
+fn fuzz_checkbox() {
+    WidgetFuzzer::new(|| Checkbox::new("Accept terms"))
+        .constructor("Checkbox::new(\"Accept terms\")")
+        .seed(1)
+        .run();
+}
+
+#[test]
+fn fuzz_button() {
+    WidgetFuzzer::new(|| Button::new("Save"))
+        .constructor("Button::new(\"Save\")")
+        .seed(2)
+        .run();
+}
+
+#[test]
+fn fuzz_switch() {
+    WidgetFuzzer::new(Switch::new)
+        .constructor("Switch::new()")
+        .seed(3)
+        .run();
+}
+
+#[test]
+fn fuzz_select() {
+    WidgetFuzzer::new(|| Select::new().options(vec!["Apple", "Banana", "Cherry"]))
+        .constructor("Select::new().options(vec![\"Apple\", \"Banana\", \"Cherry\"])")
+        .seed(4)
+        .run();
+}
+
+#[test]
+fn fuzz_multi_select() {
+    WidgetFuzzer::new(|| MultiSelect::new().options(vec!["Red", "Green", "Blue"]))
+        .constructor("MultiSelect::new().options(vec![\"Red\", \"Green\", \"Blue\"])")
+        .seed(5)
+        .run();
+}
+
+#[test]
+fn fuzz_textarea() {
+    WidgetFuzzer::new(TextArea::new)
+        .constructor("TextArea::new()")
+        .on_paste("view.insert_str(text)", |view, text| view.insert_str(text))
+        .cases(64)
+        .seed(6)
+        .invariant("cursor on a line", |view, _, _| {
+            let (line, col) = view.cursor_position();
+            let content = view.get_content();
+            content
+                .split('\n')
+                .nth(line)
+                .is_some_and(|text| col <= text.chars().count())
+        })
+        .run();
+}
@@ -0,0 +1,75 @@
+//! Integration tests for signal/store undo history
+
+use revue::prelude::*;
+use revue::reactive::{batch, History};
+use revue::Store;
+
+#[derive(Store)]
+#[store(history)]
+struct EditorStore {
+    text: Signal<String>,
+    cursor: Signal<usize>,
+    #[allow(dead_code)]
+    version: u32,
+}
+
+impl EditorStore {
+    fn new() -> Self {
+        Self {
+            text: signal(String::new()),
+            cursor: signal(0),
+            version: 1,
+        }
+    }
+}
+
+#[test]
+fn test_store_history_tracks_signal_fields() {
+    let store = EditorStore::new();
+    let history = History::new();
+    history.track_store(&store);
+
+    batch(|| {
+        store.text.set("hello".to_string());
+        store.cursor.set(5);
+    });
+    store.cursor.set(2);
+
+    assert_eq!(history.undo_depth(), 2);
+    assert_eq!(history.undo_label().as_deref(), Some("cursor"));
+
+    history.undo();
+    assert_eq!(store.cursor.get(), 5);
+    history.undo();
+    assert_eq!(store.text.get(), "");
+    assert_eq!(store.cursor.get(), 0);
+}
+
+#[test]
+fn test_history_clones_share_entries() {
+    let value = signal(0);
+    let history = History::new();
+    history.track(&value, "Set");
+    let handle = history.clone();
+
+    value.set(3);
+    assert!(handle.can_undo());
+    assert_eq!(handle.undo().as_deref(), Some("Set"));
+    assert_eq!(value.get(), 0);
+    assert_eq!(history.redo_label().as_deref(), Some("Set"));
+}
+
+#[test]
+fn test_history_clear() {
+    let value = signal(0);
+    let history = History::new();
+    history.track(&value, "Set");
+
+    value.set(1);
+    history.clear();
+    assert!(!history.can_undo());
+
+    value.set(2);
+    history.undo();
+    assert_eq!(value.get(), 1);
+}
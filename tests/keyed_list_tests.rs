@@ -0,0 +1,134 @@
+//! Integration tests for keyed `For`/`each` lists and keyed DOM reconciliation
+
+use revue::dom::{DomId, DomRenderer};
+use revue::layout::Rect;
+use revue::reactive::signal_vec;
+use revue::render::Buffer;
+use revue::widget::{each, RenderContext, Text, View};
+
+#[derive(Clone, PartialEq)]
+struct Todo {
+    id: u32,
+    title: &'static str,
+}
+
+fn todo(id: u32, title: &'static str) -> Todo {
+    Todo { id, title }
+}
+
+fn child_ids(renderer: &DomRenderer) -> Vec<DomId> {
+    let tree = renderer.tree();
+    let root = tree.root_id().unwrap();
+    tree.get(root).unwrap().children.clone()
+}
+
+#[test]
+fn test_each_builds_keyed_children() {
+    let items = signal_vec(vec![todo(1, "a"), todo(2, "b")]);
+    let list = each(items, |t| t.id.to_string(), |t| Text::new(t.title));
+
+    assert_eq!(list.len(), 2);
+    assert_eq!(list.keys(), &["1".to_string(), "2".to_string()]);
+    assert_eq!(list.children()[0].meta().key.as_deref(), Some("1"));
+    assert_eq!(list.children()[0].widget_type(), "Text");
+}
+
+#[test]
+fn test_each_sync_applies_diffs() {
+    let items = signal_vec(vec![todo(1, "a"), todo(2, "b"), todo(3, "c")]);
+    let mut list = each(items.clone(), |t| t.id.to_string(), |t| Text::new(t.title));
+
+    assert!(!list.sync());
+
+    items.insert(0, todo(4, "d"));
+    items.remove(2);
+    items.move_item(2, 0);
+    assert!(list.has_pending());
+    assert!(list.sync());
+    assert!(!list.has_pending());
+
+    assert_eq!(list.keys(), &["3", "4", "1"].map(String::from));
+}
+
+#[test]
+fn test_each_sync_replace_reuses_unchanged_children() {
+    let items = signal_vec(vec![todo(1, "a"), todo(2, "b")]);
+    let mut list = each(items.clone(), |t| t.id.to_string(), |t| Text::new(t.title));
+
+    items.replace(vec![todo(2, "b"), todo(3, "c"), todo(1, "a")]);
+    assert!(list.sync());
+
+    assert_eq!(list.keys(), &["2", "3", "1"].map(String::from));
+}
+
+#[test]
+fn test_keyed_insert_at_front_preserves_dom_ids() {
+    let items = signal_vec(vec![todo(1, "a"), todo(2, "b")]);
+    let mut list = each(items.clone(), |t| t.id.to_string(), |t| Text::new(t.title));
+
+    let mut renderer = DomRenderer::new();
+    renderer.build(&list);
+    let before = child_ids(&renderer);
+
+    items.insert(0, todo(0, "new"));
+    list.sync();
+    renderer.build(&list);
+    let after = child_ids(&renderer);
+
+    assert_eq!(after.len(), 3);
+    assert!(!before.contains(&after[0]));
+    assert_eq!(&after[1..], &before[..]);
+    assert_eq!(renderer.tree().len(), 4);
+}
+
+#[test]
+fn test_keyed_move_preserves_dom_ids() {
+    let items = signal_vec(vec![todo(1, "a"), todo(2, "b"), todo(3, "c")]);
+    let mut list = each(items.clone(), |t| t.id.to_string(), |t| Text::new(t.title));
+
+    let mut renderer = DomRenderer::new();
+    renderer.build(&list);
+    let before = child_ids(&renderer);
+
+    items.move_item(0, 2);
+    list.sync();
+    renderer.build(&list);
+
+    assert_eq!(child_ids(&renderer), vec![before[1], before[2], before[0]]);
+}
+
+#[test]
+fn test_keyed_remove_drops_only_removed_node() {
+    let items = signal_vec(vec![todo(1, "a"), todo(2, "b"), todo(3, "c")]);
+    let mut list = each(items.clone(), |t| t.id.to_string(), |t| Text::new(t.title));
+
+    let mut renderer = DomRenderer::new();
+    renderer.build(&list);
+    let before = child_ids(&renderer);
+
+    items.remove(1);
+    list.sync();
+    renderer.build(&list);
+
+    assert_eq!(child_ids(&renderer), vec![before[0], before[2]]);
+    assert!(renderer.tree().get(before[1]).is_none());
+}
+
+#[test]
+fn test_each_renders_items_in_order() {
+    let items = signal_vec(vec![todo(1, "one"), todo(2, "two")]);
+    let mut list = each(items.clone(), |t| t.id.to_string(), |t| Text::new(t.title)).gap(1);
+
+    items.move_item(1, 0);
+    list.sync();
+
+    let mut buf = Buffer::new(5, 3);
+    {
+        let mut ctx = RenderContext::new(&mut buf, Rect::new(0, 0, 5, 3));
+        list.render(&mut ctx);
+    }
+
+    assert_eq!(buf.get(0, 0).unwrap().symbol, 't');
+    assert_eq!(buf.get(0, 1).unwrap().symbol, ' ');
+    assert_eq!(buf.get(0, 2).unwrap().symbol, 'o');
+}
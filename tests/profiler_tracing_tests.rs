@@ -0,0 +1,30 @@
+//! Integration tests for `tracing` spans in the frame profiler
+
+#![cfg(feature = "tracing")]
+
+use revue::devtools::{Profiler, ProfilerLayer};
+use tracing_subscriber::prelude::*;
+
+#[test]
+fn test_spans_appear_in_frame() {
+    let subscriber = tracing_subscriber::registry().with(ProfilerLayer::new());
+    tracing::subscriber::with_default(subscriber, || {
+        // Nothing is collected before recording starts
+        tracing::info_span!("before").in_scope(|| {});
+
+        let mut profiler = Profiler::new();
+        profiler.start_recording();
+        tracing::info_span!("load").in_scope(|| {
+            tracing::info_span!("parse").in_scope(|| {});
+        });
+        profiler.stop_recording();
+
+        let frame = &profiler.frames()[0];
+        let names: Vec<_> = frame.spans.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["parse", "load"]);
+        assert_eq!(frame.spans[0].target, "profiler_tracing_tests");
+
+        let folded = profiler.to_folded();
+        assert!(folded.contains("frame;load;parse "), "{folded}");
+    });
+}
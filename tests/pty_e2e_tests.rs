@@ -0,0 +1,107 @@
+//! End-to-end tests running a real revue App under a pseudo-terminal
+//!
+//! The app runs in a child process that re-executes this test binary with
+//! only the `child_counter_app` test selected.
+#![cfg(all(unix, feature = "pty"))]
+
+use revue::core::app::App;
+use revue::event::{Event, Key};
+use revue::style::Color;
+use revue::testing::{PtyPilot, TestConfig};
+use revue::widget::{RenderContext, View};
+use std::process::Command;
+
+const CHILD_ENV: &str = "REVUE_PTY_E2E_CHILD";
+
+struct Counter {
+    count: i32,
+}
+
+impl View for Counter {
+    fn render(&self, ctx: &mut RenderContext) {
+        let text = format!("Count: {}", self.count);
+        for (i, ch) in text.chars().enumerate() {
+            ctx.draw_char(i as u16, 0, ch, Color::WHITE);
+        }
+    }
+}
+
+/// Runs the app when spawned by the tests below; a no-op otherwise
+#[test]
+fn child_counter_app() {
+    if std::env::var_os(CHILD_ENV).is_none() {
+        return;
+    }
+    let mut app = App::builder().mouse_capture(false).build();
+    app.run(Counter { count: 0 }, |event, view, _app| match event {
+        Event::Key(key) if key.key == Key::Up => {
+            view.count += 1;
+            true
+        }
+        Event::Key(key) if key.key == Key::Char('p') => panic!("boom"),
+        _ => false,
+    })
+    .unwrap();
+}
+
+fn spawn_app(width: u16, height: u16) -> PtyPilot {
+    let mut command = Command::new(std::env::current_exe().unwrap());
+    command
+        .args([
+            "--exact",
+            "child_counter_app",
+            "--nocapture",
+            "--test-threads=1",
+        ])
+        .env(CHILD_ENV, "1");
+    let config = TestConfig {
+        width,
+        height,
+        timeout_ms: 10_000,
+        ..Default::default()
+    };
+    PtyPilot::spawn_with_config(command, config).unwrap()
+}
+
+#[test]
+fn test_app_runs_and_restores_terminal() {
+    let mut pilot = spawn_app(40, 10);
+
+    pilot.assert_contains("Count: 0");
+    assert!(pilot.vt().is_alternate_screen());
+    assert!(!pilot.vt().cursor_visible());
+
+    pilot.press(Key::Up).press(Key::Up);
+    pilot.assert_contains("Count: 2");
+    assert_eq!(pilot.find_text("Count"), Some((0, 0)));
+
+    pilot.press_ctrl_c();
+    assert!(pilot.wait_for_exit().success());
+    pilot.assert_restored();
+}
+
+#[test]
+fn test_app_survives_resize() {
+    let mut pilot = spawn_app(40, 10);
+    pilot.assert_contains("Count: 0");
+
+    pilot.resize(20, 5).press(Key::Up);
+    pilot.assert_line_contains(0, "Count: 1");
+    assert_eq!(pilot.vt().width(), 20);
+
+    pilot.press_ctrl_c();
+    pilot.wait_for_exit();
+}
+
+#[test]
+fn test_panic_restores_terminal() {
+    let mut pilot = spawn_app(40, 10);
+    pilot.assert_contains("Count: 0");
+
+    pilot.press(Key::Char('p'));
+    let status = pilot.wait_for_exit();
+    assert!(!status.success());
+    pilot.assert_restored();
+    // The message is printed on the alternate screen before unwinding restores it
+    assert!(String::from_utf8_lossy(pilot.raw_output()).contains("boom"));
+}
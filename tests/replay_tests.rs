@@ -0,0 +1,119 @@
+//! Integration tests for recording sessions and replaying them through Pilot
+
+use revue::core::app::{screen_hash, Recording, SessionRecorder};
+use revue::event::{Event, Key, KeyEvent};
+use revue::style::Color;
+use revue::testing::{Pilot, TestApp};
+use revue::utils::clock::VirtualClock;
+use revue::widget::{RenderContext, View};
+use std::time::Duration;
+
+/// Counter that also shows how many ticks it has seen
+#[derive(Default)]
+struct Counter {
+    count: i32,
+    ticks: u32,
+}
+
+impl View for Counter {
+    fn render(&self, ctx: &mut RenderContext) {
+        let text = format!("count={} ticks={}", self.count, self.ticks);
+        for (i, ch) in text.chars().enumerate() {
+            ctx.draw_char(i as u16, 0, ch, Color::WHITE);
+        }
+    }
+}
+
+fn counter_app() -> TestApp<Counter> {
+    TestApp::with_size(Counter::default(), 30, 2)
+        .on_key(|key, view| match key.key {
+            Key::Up => {
+                view.count += 1;
+                true
+            }
+            Key::Down => {
+                view.count -= 1;
+                true
+            }
+            _ => false,
+        })
+        .on_tick(|_, view| {
+            view.ticks += 1;
+            true
+        })
+}
+
+/// Record a session the way `App::run` does, using a TestApp as the screen
+fn record_session(events: &[(u64, Event)]) -> Recording {
+    let clock = VirtualClock::new();
+    let _guard = clock.install();
+    let mut app = counter_app();
+    // The recorder saves on drop; keep that file out of the working directory
+    let dir = tempfile::tempdir().unwrap();
+    let mut recorder = SessionRecorder::new(dir.path().join("session.rec"));
+    recorder.start(30, 2);
+    recorder.record_screen(app.buffer());
+
+    for (ms, event) in events {
+        clock.set_elapsed(Duration::from_millis(*ms));
+        recorder.record_event(event);
+        match event {
+            Event::Key(key) => app.send_key(key.clone()),
+            Event::Tick => app.send_tick(Duration::from_millis(16)),
+            _ => app.render(),
+        }
+        recorder.record_screen(app.buffer());
+    }
+    recorder.recording().clone()
+}
+
+#[test]
+fn test_recorded_session_replays_through_text_format() {
+    let recording = record_session(&[
+        (100, Event::Key(KeyEvent::new(Key::Up))),
+        (250, Event::Key(KeyEvent::new(Key::Up))),
+        (300, Event::Tick),
+        (900, Event::Key(KeyEvent::new(Key::Down))),
+    ]);
+    assert_eq!(recording.screen_count(), 5);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.rec");
+    recording.save(&path).unwrap();
+
+    let mut app = counter_app();
+    let mut pilot = Pilot::new(&mut app);
+    pilot.replay_file(&path);
+
+    pilot.assert_contains("count=1 ticks=1");
+    assert_eq!(pilot.clock().elapsed(), Duration::from_millis(900));
+}
+
+#[test]
+#[should_panic(expected = "Replay diverged at entry 2")]
+fn test_replay_panics_when_screen_differs() {
+    let recording = record_session(&[(100, Event::Key(KeyEvent::new(Key::Up)))]);
+
+    // The replayed app ignores keys, so the screen after the key differs
+    let mut app = TestApp::with_size(Counter::default(), 30, 2).on_key(|_, _| false);
+    let mut pilot = Pilot::new(&mut app);
+    pilot.replay(&recording);
+}
+
+#[test]
+fn test_hand_written_recording_replays() {
+    let mut app = counter_app();
+    app.send_key(KeyEvent::new(Key::Up));
+    let expected = screen_hash(app.buffer());
+
+    let text = format!(
+        "# revue recording v1\nsize 30 2\n\n10 key up\n10 screen {:016x}\n",
+        expected
+    );
+    let recording: Recording = text.parse().unwrap();
+
+    let mut app = counter_app();
+    let mut pilot = Pilot::new(&mut app);
+    pilot.replay(&recording);
+    pilot.assert_contains("count=1");
+}
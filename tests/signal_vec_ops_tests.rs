@@ -0,0 +1,105 @@
+//! Integration tests for incremental `SignalVec` operators feeding widgets
+
+use revue::reactive::{signal, signal_vec};
+use revue::widget::{DataGrid, GridColumn, GridRow, Table, VirtualList};
+
+#[derive(Clone, PartialEq, Debug)]
+struct Task {
+    id: u32,
+    title: &'static str,
+    done: bool,
+}
+
+fn task(id: u32, title: &'static str, done: bool) -> Task {
+    Task { id, title, done }
+}
+
+#[test]
+fn test_filter_sort_map_chain_feeds_virtual_list() {
+    let tasks = signal_vec(vec![
+        task(3, "c", false),
+        task(1, "a", false),
+        task(2, "b", true),
+    ]);
+    let titles = tasks
+        .filter(|t| !t.done)
+        .sort_by_key(|t| t.id)
+        .map(|t| t.title.to_string());
+
+    let mut list = VirtualList::new(titles.get()).selected(1);
+    let queue = titles.diff_queue();
+
+    tasks.push(task(0, "z", false));
+    tasks.update(1, task(1, "a", true));
+    for diff in queue.drain() {
+        list.apply_diff(diff);
+    }
+
+    assert_eq!(titles.get(), vec!["z", "c"]);
+    assert_eq!(list.len(), 2);
+    assert_eq!(list.selected_item().map(String::as_str), Some("c"));
+}
+
+#[test]
+fn test_filter_with_query_feeds_table() {
+    let tasks = signal_vec(vec![
+        task(1, "write docs", false),
+        task(2, "fix bug", false),
+        task(3, "write tests", true),
+    ]);
+    let query = signal(String::new());
+    let rows = tasks
+        .filter_with(&query, |t, q: &String| t.title.contains(q.as_str()))
+        .map(|t| vec![t.id.to_string(), t.title.to_string()]);
+
+    let mut table = Table::new(vec![]).rows(rows.get()).selected(2);
+    let queue = rows.diff_queue();
+
+    query.set("write".to_string());
+    for diff in queue.drain() {
+        table.apply_diff(diff);
+    }
+
+    assert_eq!(table.row_count(), 2);
+    assert_eq!(table.selected_index(), 1);
+}
+
+#[test]
+fn test_group_by_counts_and_datagrid_apply_diff() {
+    let tasks = signal_vec(vec![
+        task(1, "a", false),
+        task(2, "b", true),
+        task(3, "c", false),
+    ]);
+    let groups = tasks.group_by(|t| t.done);
+    assert_eq!(
+        groups
+            .get()
+            .iter()
+            .map(|g| (g.key, g.items.len()))
+            .collect::<Vec<_>>(),
+        vec![(false, 2), (true, 1)]
+    );
+
+    let rows = tasks.map(|t| GridRow::new().cell("title", t.title));
+    let mut grid = DataGrid::new()
+        .column(GridColumn::new("title", "Title"))
+        .rows(rows.get());
+    let queue = rows.diff_queue();
+
+    tasks.remove(0);
+    tasks.insert(0, task(4, "d", true));
+    assert_eq!(queue.len(), 2);
+    for diff in queue.drain() {
+        grid.apply_diff(diff);
+    }
+
+    assert!(queue.is_empty());
+    assert_eq!(grid.row_count(), 3);
+    let titles: Vec<_> = grid
+        .filtered_rows()
+        .iter()
+        .filter_map(|r| r.get("title"))
+        .collect();
+    assert_eq!(titles, vec!["d", "b", "c"]);
+}
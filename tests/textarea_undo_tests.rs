@@ -261,3 +261,19 @@ fn test_has_selection() {
     // After starting selection, still has selection state
     let _selection = textarea.has_selection();
 }
+
+#[test]
+fn test_undo_replaced_selection_in_one_step() {
+    let mut textarea = create_textarea_with_content("one\ntwo");
+    textarea.set_cursor(0, 1);
+    textarea.start_selection();
+    textarea.move_down();
+    textarea.insert_str("X\nY");
+    assert_eq!(textarea.get_content(), "oX\nYwo");
+
+    textarea.undo();
+    assert_eq!(textarea.get_content(), "one\ntwo");
+    textarea.redo();
+    assert_eq!(textarea.get_content(), "oX\nYwo");
+    assert_eq!(textarea.cursor_position(), (1, 1));
+}
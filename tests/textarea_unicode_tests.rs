@@ -126,3 +126,17 @@ fn test_textarea_multiline_insert_cjk() {
     assert_eq!(t.get_content(), "첫째줄\n둘째줄");
     assert_eq!(t.line_count(), 2);
 }
+
+#[test]
+fn test_textarea_backspace_removes_whole_grapheme() {
+    let mut textarea = textarea().content("ae\u{301}b");
+    textarea.set_cursor(0, 3);
+    textarea.delete_char_before();
+    assert_eq!(textarea.get_content(), "ab");
+    assert_eq!(textarea.cursor_position(), (0, 1));
+
+    textarea.move_right();
+    textarea.undo();
+    assert_eq!(textarea.get_content(), "ae\u{301}b");
+    assert_eq!(textarea.cursor_position(), (0, 3));
+}
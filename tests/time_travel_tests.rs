@@ -0,0 +1,74 @@
+//! Integration tests for recording stores with the time-travel debugger
+
+use revue::devtools::{SnapshotValue, TimeTravelDebugger, TimeTravelRecorder};
+use revue::prelude::*;
+use revue::Store;
+
+#[derive(Store)]
+#[store(history, time_travel)]
+struct TodoStore {
+    items: Signal<Vec<String>>,
+    filter: Signal<Option<String>>,
+    #[allow(dead_code)]
+    version: u32,
+}
+
+impl TodoStore {
+    fn new() -> Self {
+        Self {
+            items: signal(Vec::new()),
+            filter: signal(None),
+            version: 1,
+        }
+    }
+}
+
+#[test]
+fn test_store_fields_are_tracked() {
+    let store = TodoStore::new();
+    let recorder = TimeTravelRecorder::new();
+    recorder.track_store(&store);
+    assert_eq!(
+        recorder.tracked(),
+        vec!["TodoStore.filter", "TodoStore.items"]
+    );
+
+    recorder.action("add", || store.items.update(|v| v.push("milk".into())));
+    let pending = recorder.take_pending();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].action.as_ref().unwrap().name, "add");
+    assert_eq!(
+        pending[0].state["TodoStore.items"],
+        SnapshotValue::Array(vec![SnapshotValue::from("milk")])
+    );
+}
+
+#[test]
+fn test_stepping_restores_store() {
+    let store = TodoStore::new();
+    let recorder = TimeTravelRecorder::new();
+    recorder.track_store(&store);
+    let mut debugger = TimeTravelDebugger::new();
+    debugger.attach(recorder.clone());
+
+    recorder.action("add", || store.items.update(|v| v.push("milk".into())));
+    recorder.action("filter", || store.filter.set(Some("m".into())));
+    debugger.sync();
+    assert_eq!(debugger.snapshots().len(), 3);
+
+    debugger.step_back();
+    assert_eq!(store.filter.get(), None);
+    assert_eq!(store.items.get(), vec!["milk".to_string()]);
+    debugger.jump_to_first();
+    assert!(store.items.get().is_empty());
+    debugger.jump_to_latest();
+    assert_eq!(store.filter.get().as_deref(), Some("m"));
+
+    // A new change while traveling drops the undone future
+    debugger.jump_to(1);
+    store.items.update(|v| v.push("eggs".into()));
+    debugger.sync();
+    assert_eq!(debugger.snapshots().len(), 3);
+    assert!(!debugger.is_traveling());
+    assert_eq!(store.filter.get(), None);
+}
@@ -0,0 +1,215 @@
+//! Integration tests for the `view!` template macro
+
+use revue::layout::Rect;
+use revue::prelude::*;
+use revue::render::Buffer;
+use revue::widget::{hstack, tree, vstack, Stack, Text, TreeNode};
+
+fn render_to_string(view: &impl View, width: u16, height: u16) -> String {
+    let mut buf = Buffer::new(width, height);
+    {
+        let mut ctx = RenderContext::new(&mut buf, Rect::new(0, 0, width, height));
+        view.render(&mut ctx);
+    }
+    let mut out = String::new();
+    for y in 0..height {
+        for x in 0..width {
+            out.push(buf.get(x, y).map(|c| c.symbol).unwrap_or(' '));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[test]
+fn test_view_function_element() {
+    let stack: Stack = view! { <vstack gap=1 /> };
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_view_type_element_with_args() {
+    let text: Text = view! { <Text("Hello") bold /> };
+    assert_eq!(text.content(), "Hello");
+}
+
+#[test]
+fn test_view_text_children() {
+    let stack = view! {
+        <vstack>
+            "First"
+            "Second"
+        </vstack>
+    };
+
+    assert_eq!(stack.len(), 2);
+    let out = render_to_string(&stack, 10, 2);
+    assert!(out.starts_with("First"));
+    assert!(out.lines().nth(1).unwrap().starts_with("Second"));
+}
+
+#[test]
+fn test_view_class_and_id() {
+    let text = view! { <Text("x") class="primary large" id="title" /> };
+
+    assert_eq!(text.id(), Some("title"));
+    assert_eq!(
+        View::classes(&text),
+        &["primary".to_string(), "large".to_string()]
+    );
+}
+
+#[test]
+fn test_view_expression_attributes() {
+    let gap = 2;
+    let classes = "dynamic";
+    let text = view! { <Text(String::from("computed")) class={classes} fg={Color::RED} /> };
+
+    assert_eq!(text.content(), "computed");
+    assert_eq!(View::classes(&text), &["dynamic".to_string()]);
+
+    let stack = view! { <hstack gap={gap + 1} /> };
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_view_nested_elements() {
+    let stack = view! {
+        <vstack>
+            <hstack>
+                <Text("A") />
+                <Text("B") />
+            </hstack>
+            <Text("C") />
+        </vstack>
+    };
+
+    assert_eq!(stack.len(), 2);
+    assert_eq!(stack.children()[0].children().len(), 2);
+}
+
+#[test]
+fn test_view_if_else() {
+    let build = |show: bool| {
+        view! {
+            <vstack>
+                "always"
+                if show {
+                    "yes"
+                    "more"
+                } else {
+                    "no"
+                }
+            </vstack>
+        }
+    };
+
+    assert_eq!(build(true).len(), 3);
+    assert_eq!(build(false).len(), 2);
+}
+
+#[test]
+fn test_view_else_if_and_if_let() {
+    let build = |value: Option<i32>| {
+        view! {
+            <vstack>
+                if let Some(v) = value {
+                    if v > 10 {
+                        "big"
+                    } else if v > 0 {
+                        "small"
+                    }
+                }
+            </vstack>
+        }
+    };
+
+    assert_eq!(build(Some(20)).len(), 1);
+    assert_eq!(build(Some(5)).len(), 1);
+    assert_eq!(build(Some(-1)).len(), 0);
+    assert_eq!(build(None).len(), 0);
+}
+
+#[test]
+fn test_view_for_loop() {
+    let items = ["one", "two", "three"];
+    let stack = view! {
+        <vstack>
+            for (i, item) in items.iter().enumerate() {
+                <Text("{i}: {item}") />
+            }
+        </vstack>
+    };
+
+    assert_eq!(stack.len(), 3);
+    let out = render_to_string(&stack, 10, 3);
+    assert!(out.lines().nth(2).unwrap().starts_with("2: three"));
+}
+
+#[test]
+fn test_view_expression_child() {
+    let header = Text::new("Header");
+    let stack = view! {
+        <vstack>
+            {header}
+            {Text::new("Body")}
+        </vstack>
+    };
+
+    assert_eq!(stack.len(), 2);
+}
+
+#[test]
+fn test_view_signal_interpolation() {
+    let count = signal(5);
+    let text = view! { <Text("Count: {count}") /> };
+    assert_eq!(text.content(), "Count: 5");
+
+    count.set(6);
+    let stack = view! { <vstack>"Now {count}"</vstack> };
+    let out = render_to_string(&stack, 10, 1);
+    assert!(out.starts_with("Now 6"));
+}
+
+#[test]
+fn test_view_escaped_braces() {
+    let text = view! { <Text("{{literal}}") /> };
+    assert_eq!(text.content(), "{literal}");
+}
+
+#[test]
+fn test_view_plain_braces_are_verbatim() {
+    let open = view! { <Text("{") /> };
+    assert_eq!(open.content(), "{");
+
+    let block = view! { <Text("fn main() { }") /> };
+    assert_eq!(block.content(), "fn main() { }");
+
+    let stack = view! { <vstack>"a { b"</vstack> };
+    assert!(render_to_string(&stack, 10, 1).starts_with("a { b"));
+}
+
+#[test]
+fn test_view_event_binding() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let selected = Rc::new(Cell::new(false));
+    let flag = selected.clone();
+    let _tree = view! {
+        <tree on:select={move |_node: &TreeNode| flag.set(true)} />
+    };
+
+    let flag = selected.clone();
+    let _tree = view! {
+        <tree @select={move |_node: &TreeNode| flag.set(true)} />
+    };
+
+    assert!(!selected.get());
+}
+
+#[test]
+fn test_view_path_element() {
+    let text = view! { <revue::widget::Text("path") /> };
+    assert_eq!(text.content(), "path");
+}
@@ -0,0 +1,182 @@
+//! Integration tests for the virtual clock driving runtime timing
+
+use revue::event::{Gesture, GestureRecognizer, MouseButton, MouseEvent, MouseEventKind};
+use revue::patterns::{MessageState, SearchState};
+use revue::style::{easing, Tween};
+use revue::tasks::Timer;
+use revue::testing::{MockTime, Pilot, TestApp, TestConfig};
+use revue::utils::clock::{self, VirtualClock};
+use revue::utils::Debouncer;
+use revue::widget::{RenderContext, View};
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+/// Bar whose width follows a tween
+struct Bar {
+    tween: RefCell<Tween>,
+}
+
+impl View for Bar {
+    fn render(&self, ctx: &mut RenderContext) {
+        let width = self.tween.borrow_mut().value().round() as u16;
+        for x in 0..width.min(ctx.area.width) {
+            ctx.draw_char(x, 0, '#', revue::style::Color::WHITE);
+        }
+    }
+}
+
+#[test]
+fn test_pilot_advance_drives_tween_frames() {
+    let clock = VirtualClock::new();
+    let guard = clock.install();
+    let mut tween = Tween::new(0.0, 10.0, Duration::from_millis(100)).easing(easing::linear);
+    tween.start();
+    drop(guard);
+
+    let mut app = TestApp::with_size(
+        Bar {
+            tween: RefCell::new(tween),
+        },
+        10,
+        1,
+    );
+    let mut pilot = Pilot::with_clock(&mut app, TestConfig::default(), clock);
+
+    let frames = pilot.capture_frames(3, Duration::from_millis(50));
+    let widths: Vec<_> = frames
+        .iter()
+        .map(|buf| {
+            (0..10)
+                .filter(|&x| buf.get(x, 0).unwrap().symbol == '#')
+                .count()
+        })
+        .collect();
+    assert_eq!(widths, vec![0, 5, 10]);
+}
+
+#[test]
+fn test_pilot_advance_does_not_sleep() {
+    let mut app = TestApp::with_size(
+        Bar {
+            tween: RefCell::new(Tween::new(0.0, 1.0, Duration::from_secs(60))),
+        },
+        10,
+        1,
+    );
+    let mut pilot = Pilot::new(&mut app);
+
+    let real_start = Instant::now();
+    let virtual_start = clock::now();
+    pilot.advance(Duration::from_secs(3600));
+
+    assert!(real_start.elapsed() < Duration::from_secs(1));
+    assert_eq!(clock::elapsed(virtual_start), Duration::from_secs(3600));
+    assert_eq!(pilot.clock().elapsed(), Duration::from_secs(3600));
+}
+
+#[test]
+fn test_timer_and_debouncer_follow_virtual_clock() {
+    let clock = VirtualClock::new();
+    let _guard = clock.install();
+
+    let mut timer = Timer::new();
+    timer.set("save", Duration::from_secs(5));
+    let mut debouncer = Debouncer::new(Duration::from_millis(300));
+    debouncer.call();
+
+    clock.advance_ms(299);
+    assert_eq!(timer.poll_expired(), None);
+    assert!(!debouncer.is_ready());
+
+    clock.advance_ms(1);
+    assert!(debouncer.is_ready());
+
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(timer.poll_expired(), Some("save"));
+}
+
+#[test]
+fn test_mock_time_installs_as_clock() {
+    let time = MockTime::new();
+    let _guard = time.install();
+
+    let start = clock::now();
+    time.advance_secs(2);
+    assert_eq!(clock::elapsed(start), Duration::from_secs(2));
+}
+
+#[test]
+fn test_pilot_advance_until_uses_virtual_time() {
+    let mut app = TestApp::with_size(
+        Bar {
+            tween: RefCell::new(
+                Tween::new(0.0, 10.0, Duration::from_secs(2)).easing(easing::linear),
+            ),
+        },
+        10,
+        1,
+    );
+    let mut pilot = Pilot::new(&mut app);
+    pilot.update(|bar| bar.tween.borrow_mut().start());
+
+    let real_start = Instant::now();
+    pilot.advance_until(|buf| buf.get(9, 0).unwrap().symbol == '#');
+
+    assert!(real_start.elapsed() < Duration::from_secs(1));
+    assert!(pilot.clock().elapsed() >= Duration::from_millis(1800));
+}
+
+#[test]
+fn test_pilot_advance_until_timeout_is_virtual() {
+    let mut app = TestApp::with_size(
+        Bar {
+            tween: RefCell::new(Tween::new(0.0, 0.0, Duration::from_secs(1))),
+        },
+        10,
+        1,
+    );
+    let config = TestConfig {
+        timeout_ms: 60_000,
+        ..TestConfig::default()
+    };
+    let mut pilot = Pilot::with_config(&mut app, config);
+
+    let real_start = Instant::now();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        pilot.advance_until(|_| false);
+    }));
+
+    assert!(result.is_err());
+    assert!(real_start.elapsed() < Duration::from_secs(5));
+    assert_eq!(pilot.clock().elapsed(), Duration::from_secs(60));
+}
+
+#[test]
+fn test_patterns_and_gestures_follow_virtual_clock() {
+    let clock = VirtualClock::new();
+    let _guard = clock.install();
+
+    let mut message = MessageState::with_duration(Duration::from_secs(3));
+    message.set("Saved".to_string());
+    let mut search = SearchState::new().debounce(Duration::from_secs(2));
+    search.set_query("abc");
+    let mut gestures = GestureRecognizer::new();
+    gestures.handle_mouse_event(&MouseEvent::new(
+        2,
+        2,
+        MouseEventKind::Down(MouseButton::Left),
+    ));
+
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(message.remaining(), Some(Duration::from_secs(2)));
+    assert!(!search.is_ready());
+    assert!(matches!(
+        gestures.check_long_press(),
+        Some(Gesture::LongPress(g)) if g.duration == Duration::from_secs(1)
+    ));
+
+    clock.advance(Duration::from_secs(2));
+    assert!(message.check_timeout());
+    assert!(!message.has_message());
+    assert!(search.is_ready());
+}